@@ -0,0 +1,19 @@
+//! 窗口聚合 Sink：按键分组统计条数与数值字段之和，只向下游输出聚合结果
+//!
+//! 内部 Sink 在 `sink` 参数中以嵌套规格给出，由注册到 [`AggregateSinkFactory`] 的工厂构建：
+//!
+//! ```text
+//! key_field = "host"
+//! sum_fields = ["bytes", "latency_ms"]
+//! window_secs = 60
+//! sink = { kind = "clickhouse", params = { endpoint = "http://ck:8123", ... } }
+//! ```
+//!
+//! 每个窗口内的每个键输出一条记录：键字段、`window_start` / `window_end`、`count`
+//! 及 `<field>_sum`。窗口结束时由后台定时任务输出，停止时输出当前窗口。
+
+mod factory;
+mod sink;
+
+pub use factory::AggregateSinkFactory;
+pub use sink::{AggregateConf, AggregateSink};
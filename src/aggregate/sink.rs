@@ -0,0 +1,414 @@
+//! 窗口聚合 Sink 实现
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::{Mutex, oneshot};
+use tokio::task::JoinHandle;
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, SinkReason, SinkResult,
+};
+use wp_model_core::model::{DataField, DataRecord, Value};
+
+/// 聚合参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregateConf {
+    /// 分组键字段
+    pub key_field: String,
+    /// 求和的数值字段
+    pub sum_fields: Vec<String>,
+    /// 滚动窗口长度（秒）
+    pub window_secs: u64,
+}
+
+/// 单个字段的累加值；全部为整数时保持整数，出现浮点数后按浮点数累加
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Sum {
+    Int(i64),
+    Float(f64),
+}
+
+impl Sum {
+    fn add(&mut self, value: &Value) {
+        *self = match (*self, value) {
+            (Sum::Int(acc), Value::Digit(v)) => Sum::Int(acc.saturating_add(*v)),
+            (Sum::Int(acc), Value::Float(v)) => Sum::Float(acc as f64 + v),
+            (Sum::Float(acc), Value::Digit(v)) => Sum::Float(acc + *v as f64),
+            (Sum::Float(acc), Value::Float(v)) => Sum::Float(acc + v),
+            (acc, _) => acc,
+        };
+    }
+
+    fn into_field(self, name: String) -> DataField {
+        match self {
+            Sum::Int(v) => DataField::from_digit(name, v),
+            Sum::Float(v) => DataField::from_float(name, v),
+        }
+    }
+}
+
+/// 一个分组在当前窗口内的聚合状态
+struct Group {
+    /// 键字段（取分组内第一条记录的字段）；记录缺少键字段时为 `None`
+    key: Option<DataField>,
+    count: i64,
+    sums: Vec<Sum>,
+}
+
+/// 按键字段分组、在滚动窗口内累计条数与数值字段之和，窗口关闭时向内部 Sink 输出聚合记录
+///
+/// 窗口按墙钟时间对齐到 `window_secs` 的整数倍；窗口结束时由后台定时任务输出（见
+/// [`AggregateSink::start_flush_task`]），写入时发现已进入新窗口也会先输出上一窗口，
+/// `stop` 时输出当前窗口。聚合记录包含键字段、`window_start` / `window_end`、`count`
+/// 以及每个求和字段的 `<field>_sum`（非数值或缺失的值不参与求和）。
+pub struct AggregateSink {
+    /// 窗口状态，与定时输出任务共享
+    state: Arc<Mutex<WindowState>>,
+    window_secs: u64,
+    stop_tx: Option<oneshot::Sender<()>>,
+    flush_handle: Option<JoinHandle<()>>,
+}
+
+/// 内部 Sink 与当前窗口的聚合状态
+struct WindowState {
+    inner: Box<dyn AsyncSink + 'static>,
+    conf: AggregateConf,
+    /// 当前窗口的起始时间（Unix 秒）
+    window_start: Option<i64>,
+    /// 分组键的字符串形式到分组状态，缺少键字段的记录归入 `None`
+    groups: BTreeMap<Option<String>, Group>,
+}
+
+impl AggregateSink {
+    pub fn new(inner: Box<dyn AsyncSink + 'static>, conf: AggregateConf) -> Self {
+        let window_secs = conf.window_secs;
+        Self {
+            state: Arc::new(Mutex::new(WindowState {
+                inner,
+                conf,
+                window_start: None,
+                groups: BTreeMap::new(),
+            })),
+            window_secs,
+            stop_tx: None,
+            flush_handle: None,
+        }
+    }
+
+    /// 启动后台任务，在每个窗口结束时输出该窗口，无后续写入时聚合结果也不会滞留
+    pub(crate) fn start_flush_task(&mut self) {
+        if self.flush_handle.is_some() {
+            return;
+        }
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let state = self.state.clone();
+        let secs = self.window_secs as i64;
+        let handle = tokio::spawn(async move {
+            loop {
+                let now = Utc::now();
+                let next_end = (now.timestamp().div_euclid(secs) + 1) * secs;
+                let wait = (next_end * 1000 - now.timestamp_millis()).max(0) as u64;
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_millis(wait)) => {
+                        if let Err(e) = state.lock().await.close_expired(Utc::now()).await {
+                            wp_log::error_data!("[aggregate] window flush fail: {}", e);
+                        }
+                    }
+                    _ = &mut stop_rx => break,
+                }
+            }
+        });
+        self.stop_tx = Some(stop_tx);
+        self.flush_handle = Some(handle);
+    }
+
+    async fn stop_flush_task(&mut self) {
+        if let Some(tx) = self.stop_tx.take() {
+            let _ = tx.send(());
+        }
+        if let Some(handle) = self.flush_handle.take()
+            && let Err(e) = handle.await
+        {
+            wp_log::error_data!("[aggregate] flush task join error: {}", e);
+        }
+    }
+
+    #[cfg(test)]
+    async fn aggregate_at(
+        &mut self,
+        data: &[Arc<DataRecord>],
+        now: DateTime<Utc>,
+    ) -> SinkResult<()> {
+        self.state.lock().await.aggregate_at(data, now).await
+    }
+}
+
+impl Drop for AggregateSink {
+    fn drop(&mut self) {
+        self.stop_tx.take();
+        if let Some(handle) = self.flush_handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+impl WindowState {
+    fn window_of(&self, now: DateTime<Utc>) -> i64 {
+        let secs = self.conf.window_secs as i64;
+        now.timestamp().div_euclid(secs) * secs
+    }
+
+    /// 累计一批记录；记录所属窗口晚于当前窗口时先输出当前窗口
+    async fn aggregate_at(
+        &mut self,
+        data: &[Arc<DataRecord>],
+        now: DateTime<Utc>,
+    ) -> SinkResult<()> {
+        let window = self.window_of(now);
+        if self.window_start.is_some_and(|start| start != window) {
+            self.emit().await?;
+        }
+        self.window_start = Some(window);
+        for record in data {
+            self.accumulate(record);
+        }
+        Ok(())
+    }
+
+    /// 当前窗口已结束时输出该窗口
+    async fn close_expired(&mut self, now: DateTime<Utc>) -> SinkResult<()> {
+        if self
+            .window_start
+            .is_some_and(|start| start != self.window_of(now))
+        {
+            self.emit().await?;
+            self.window_start = None;
+        }
+        Ok(())
+    }
+
+    fn accumulate(&mut self, record: &DataRecord) {
+        let key = record
+            .get_value(&self.conf.key_field)
+            .map(|v| v.to_string());
+        let sum_count = self.conf.sum_fields.len();
+        let group = self.groups.entry(key).or_insert_with(|| Group {
+            key: record.get_field_owned(&self.conf.key_field),
+            count: 0,
+            sums: vec![Sum::Int(0); sum_count],
+        });
+        group.count += 1;
+        for (sum, field) in group.sums.iter_mut().zip(&self.conf.sum_fields) {
+            if let Some(value) = record.get_value(field) {
+                sum.add(value);
+            }
+        }
+    }
+
+    /// 输出当前窗口的聚合记录并清空状态；内部 Sink 写入失败时保留状态，下次定时输出、写入或停止时重试
+    async fn emit(&mut self) -> SinkResult<()> {
+        let Some(start) = self.window_start else {
+            return Ok(());
+        };
+        if self.groups.is_empty() {
+            return Ok(());
+        }
+        let end = start + self.conf.window_secs as i64;
+        let records = self
+            .groups
+            .values()
+            .map(|group| Arc::new(self.aggregate_record(group, start, end)))
+            .collect();
+        self.inner.sink_records(records).await?;
+        self.groups.clear();
+        Ok(())
+    }
+
+    fn aggregate_record(&self, group: &Group, start: i64, end: i64) -> DataRecord {
+        let time = |secs: i64| {
+            DateTime::from_timestamp(secs, 0)
+                .unwrap_or_default()
+                .naive_utc()
+        };
+        let mut record = DataRecord::default();
+        if let Some(key) = &group.key {
+            record.append(key.clone());
+        }
+        record.append(DataField::from_time("window_start", time(start)));
+        record.append(DataField::from_time("window_end", time(end)));
+        record.append(DataField::from_digit("count", group.count));
+        for (sum, field) in group.sums.iter().zip(&self.conf.sum_fields) {
+            record.append(sum.into_field(format!("{field}_sum")));
+        }
+        record
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for AggregateSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.stop_flush_task().await;
+        let mut state = self.state.lock().await;
+        state.emit().await?;
+        state.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.state.lock().await.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for AggregateSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.sink_records(vec![Arc::new(data.clone())]).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.state
+            .lock()
+            .await
+            .aggregate_at(&data, Utc::now())
+            .await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for AggregateSink {
+    async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+        Err(SinkReason::sink("aggregate sink does not accept raw input").into())
+    }
+
+    async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+        Err(SinkReason::sink("aggregate sink does not accept raw bytes").into())
+    }
+
+    async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+        Err(SinkReason::sink("aggregate sink does not accept raw input").into())
+    }
+
+    async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+        Err(SinkReason::sink("aggregate sink does not accept raw bytes").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySinkFactory;
+    use wp_connector_api::{SinkBuildCtx, SinkFactory, SinkSpec};
+
+    fn record(host: &str, bytes: i64, latency: f64) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("host", host));
+        record.append(DataField::from_digit("bytes", bytes));
+        record.append(DataField::from_float("latency", latency));
+        Arc::new(record)
+    }
+
+    async fn sink_with_memory() -> (AggregateSink, MemorySinkFactory) {
+        sink_with_window(60).await
+    }
+
+    async fn sink_with_window(window_secs: u64) -> (AggregateSink, MemorySinkFactory) {
+        let memory = MemorySinkFactory::new();
+        let spec = SinkSpec {
+            name: "agg.out".into(),
+            kind: "memory".into(),
+            connector_id: String::new(),
+            group: String::new(),
+            params: Default::default(),
+            filter: None,
+        };
+        let handle = memory
+            .build(&spec, &SinkBuildCtx::new(std::env::temp_dir()))
+            .await
+            .unwrap();
+        let conf = AggregateConf {
+            key_field: "host".into(),
+            sum_fields: vec!["bytes".into(), "latency".into()],
+            window_secs,
+        };
+        (AggregateSink::new(handle.sink, conf), memory)
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn records_in_one_window_produce_one_aggregate_per_key() {
+        let (mut sink, memory) = sink_with_memory().await;
+        let batch = vec![
+            record("web-1", 100, 0.5),
+            record("web-2", 7, 1.0),
+            record("web-1", 50, 0.25),
+        ];
+        sink.aggregate_at(&batch, at(1_700_000_010)).await.unwrap();
+        sink.aggregate_at(&[record("web-1", 1, 0.25)], at(1_700_000_039))
+            .await
+            .unwrap();
+        assert!(memory.buffer().lock().unwrap().is_empty());
+
+        sink.stop().await.unwrap();
+        let out = memory.buffer().lock().unwrap().clone();
+        assert_eq!(out.len(), 2);
+        let web1 = &out[0];
+        assert_eq!(
+            web1.get_value("host").map(|v| v.to_string()),
+            Some("web-1".into())
+        );
+        assert_eq!(web1.get_value("count"), Some(&Value::Digit(3)));
+        assert_eq!(web1.get_value("bytes_sum"), Some(&Value::Digit(151)));
+        assert_eq!(web1.get_value("latency_sum"), Some(&Value::Float(1.0)));
+        let start = DateTime::from_timestamp(1_699_999_980, 0)
+            .unwrap()
+            .naive_utc();
+        assert_eq!(web1.get_value("window_start"), Some(&Value::Time(start)));
+        assert_eq!(out[1].get_value("count"), Some(&Value::Digit(1)));
+        assert_eq!(out[1].get_value("bytes_sum"), Some(&Value::Digit(7)));
+    }
+
+    #[tokio::test]
+    async fn entering_next_window_emits_previous_window() {
+        let (mut sink, memory) = sink_with_memory().await;
+        sink.aggregate_at(&[record("web-1", 1, 0.0)], at(1_700_000_010))
+            .await
+            .unwrap();
+        sink.aggregate_at(&[record("web-1", 2, 0.0)], at(1_700_000_050))
+            .await
+            .unwrap();
+        assert_eq!(memory.buffer().lock().unwrap().len(), 1);
+        assert_eq!(
+            memory.buffer().lock().unwrap()[0].get_value("bytes_sum"),
+            Some(&Value::Digit(1))
+        );
+
+        sink.stop().await.unwrap();
+        let out = memory.buffer().lock().unwrap().clone();
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[1].get_value("bytes_sum"), Some(&Value::Digit(2)));
+    }
+
+    #[tokio::test]
+    async fn idle_window_is_emitted_when_it_ends() {
+        let (mut sink, memory) = sink_with_window(1).await;
+        sink.start_flush_task();
+        sink.sink_records(vec![record("web-1", 5, 0.0)])
+            .await
+            .unwrap();
+
+        // 窗口结束后没有后续写入，定时任务仍输出该窗口
+        tokio::time::sleep(Duration::from_millis(2200)).await;
+        let out = memory.buffer().lock().unwrap().clone();
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].get_value("bytes_sum"), Some(&Value::Digit(5)));
+
+        sink.stop().await.unwrap();
+        assert_eq!(memory.buffer().lock().unwrap().len(), 1);
+        assert!(sink.flush_handle.is_none());
+    }
+}
@@ -0,0 +1,244 @@
+//! 通用熔断装饰器
+//!
+//! 后端完全不可用时，每次写入都要等满超时才失败，整条管道随之停滞。
+//! 参数 `circuit_breaker = true` 时，Sink 被 [`CircuitBreakerSink`] 包装：
+//!
+//! - 连续失败达到 `failure_threshold`（缺省 5）次后熔断（open），持续 `open_duration`（缺省 30s）；
+//! - 熔断期间不访问后端，按 `on_open` 处理后续数据：`fail`（缺省）立即返回错误，`drop` 直接丢弃；
+//! - 熔断到期后进入半开（half-open），放行下一次写入作为探测：成功则恢复（closed），失败则再次熔断。
+//!
+//! `open_duration` 可为整数秒，或带单位的字符串（`500ms`、`30s`、`5m`）。
+
+mod sink;
+
+pub use sink::CircuitBreakerSink;
+
+use std::time::Duration;
+
+use serde_json::Value;
+use wp_connector_api::{SinkHandle, SinkReason, SinkResult, SinkSpec};
+
+/// 熔断开关参数名
+pub const CIRCUIT_BREAKER_PARAM: &str = "circuit_breaker";
+
+/// 熔断相关的其余参数，仅在 `circuit_breaker = true` 时允许配置
+pub const BREAKER_OPTION_PARAMS: [&str; 3] = ["failure_threshold", "open_duration", "on_open"];
+
+const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// 熔断期间对后续数据的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnOpen {
+    /// 立即返回错误，不访问后端
+    #[default]
+    Fail,
+    /// 丢弃数据并返回成功
+    Drop,
+}
+
+/// 熔断配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakerConf {
+    /// 触发熔断的连续失败次数
+    pub failure_threshold: u32,
+    /// 熔断持续时长，到期后半开探测
+    pub open_duration: Duration,
+    pub on_open: OnOpen,
+}
+
+impl Default for BreakerConf {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            open_duration: DEFAULT_OPEN_DURATION,
+            on_open: OnOpen::Fail,
+        }
+    }
+}
+
+/// 读取熔断参数；未启用时返回 `None`
+pub fn parse_circuit_breaker(spec: &SinkSpec) -> SinkResult<Option<BreakerConf>> {
+    let kind = &spec.kind;
+    let invalid = |key: &str, expect: &str| -> SinkResult<Option<BreakerConf>> {
+        Err(SinkReason::sink(format!("{kind}.{key} must be {expect}")).into())
+    };
+    let enabled = match spec.params.get(CIRCUIT_BREAKER_PARAM) {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(_) => return invalid(CIRCUIT_BREAKER_PARAM, "a bool"),
+    };
+    if !enabled {
+        if let Some(key) = BREAKER_OPTION_PARAMS
+            .into_iter()
+            .find(|key| spec.params.contains_key(*key))
+        {
+            return Err(SinkReason::sink(format!(
+                "{kind}.{key} only applies when {kind}.{CIRCUIT_BREAKER_PARAM} = true"
+            ))
+            .into());
+        }
+        return Ok(None);
+    }
+
+    let mut conf = BreakerConf::default();
+    if let Some(v) = spec.params.get("failure_threshold") {
+        match v.as_u64().and_then(|n| u32::try_from(n).ok()) {
+            Some(n) if n > 0 => conf.failure_threshold = n,
+            _ => return invalid("failure_threshold", "a positive integer"),
+        }
+    }
+    if let Some(v) = spec.params.get("open_duration") {
+        match parse_duration(v) {
+            Some(d) if !d.is_zero() => conf.open_duration = d,
+            _ => {
+                return invalid(
+                    "open_duration",
+                    "a positive number of seconds or a duration like \"500ms\", \"30s\", \"5m\"",
+                );
+            }
+        }
+    }
+    if let Some(v) = spec.params.get("on_open") {
+        conf.on_open = match v.as_str().map(|s| s.trim().to_ascii_lowercase()).as_deref() {
+            Some("fail") => OnOpen::Fail,
+            Some("drop") => OnOpen::Drop,
+            _ => return invalid("on_open", "\"fail\" or \"drop\""),
+        };
+    }
+    Ok(Some(conf))
+}
+
+/// 整数按秒解析；字符串支持 `ms` / `s` / `m` 后缀，无后缀按秒
+pub(crate) fn parse_duration(value: &Value) -> Option<Duration> {
+    if let Some(secs) = value.as_u64() {
+        return Some(Duration::from_secs(secs));
+    }
+    let raw = value.as_str()?.trim();
+    let (num, unit) = raw
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or((raw, ""), |at| raw.split_at(at));
+    let n: u64 = num.parse().ok()?;
+    match unit.trim() {
+        "ms" => Some(Duration::from_millis(n)),
+        "" | "s" => Some(Duration::from_secs(n)),
+        "m" => Some(Duration::from_secs(n.checked_mul(60)?)),
+        _ => None,
+    }
+}
+
+/// 按 `circuit_breaker` 参数包装已构建的 Sink；未启用时原样返回
+pub fn wrap_from_spec(spec: &SinkSpec, handle: SinkHandle) -> SinkResult<SinkHandle> {
+    match parse_circuit_breaker(spec)? {
+        Some(conf) => Ok(SinkHandle::new(Box::new(CircuitBreakerSink::new(
+            handle.sink,
+            spec.kind.clone(),
+            conf,
+        )))),
+        None => Ok(handle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn spec_with(pairs: &[(&str, Value)]) -> SinkSpec {
+        let params: BTreeMap<String, Value> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        SinkSpec {
+            group: "test".into(),
+            name: "guarded".into(),
+            kind: "http".into(),
+            connector_id: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn parse_reads_defaults_and_overrides() {
+        assert_eq!(parse_circuit_breaker(&spec_with(&[])).unwrap(), None);
+        assert_eq!(
+            parse_circuit_breaker(&spec_with(&[(CIRCUIT_BREAKER_PARAM, json!(false))])).unwrap(),
+            None
+        );
+        assert_eq!(
+            parse_circuit_breaker(&spec_with(&[(CIRCUIT_BREAKER_PARAM, json!(true))])).unwrap(),
+            Some(BreakerConf::default())
+        );
+
+        for (duration, expect) in [
+            (json!(10), Duration::from_secs(10)),
+            (json!("500ms"), Duration::from_millis(500)),
+            (json!("45s"), Duration::from_secs(45)),
+            (json!("2m"), Duration::from_secs(120)),
+        ] {
+            let conf = parse_circuit_breaker(&spec_with(&[
+                (CIRCUIT_BREAKER_PARAM, json!(true)),
+                ("failure_threshold", json!(3)),
+                ("open_duration", duration),
+                ("on_open", json!("drop")),
+            ]))
+            .unwrap()
+            .expect("enabled");
+            assert_eq!(
+                conf,
+                BreakerConf {
+                    failure_threshold: 3,
+                    open_duration: expect,
+                    on_open: OnOpen::Drop,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_invalid_values() {
+        for (pairs, expect) in [
+            (
+                vec![(CIRCUIT_BREAKER_PARAM, json!("yes"))],
+                "http.circuit_breaker must be a bool",
+            ),
+            (
+                vec![("failure_threshold", json!(3))],
+                "http.failure_threshold only applies when http.circuit_breaker = true",
+            ),
+            (
+                vec![
+                    (CIRCUIT_BREAKER_PARAM, json!(true)),
+                    ("failure_threshold", json!(0)),
+                ],
+                "http.failure_threshold",
+            ),
+            (
+                vec![
+                    (CIRCUIT_BREAKER_PARAM, json!(true)),
+                    ("open_duration", json!("1h")),
+                ],
+                "http.open_duration",
+            ),
+            (
+                vec![
+                    (CIRCUIT_BREAKER_PARAM, json!(true)),
+                    ("open_duration", json!(0)),
+                ],
+                "http.open_duration",
+            ),
+            (
+                vec![
+                    (CIRCUIT_BREAKER_PARAM, json!(true)),
+                    ("on_open", json!("retry")),
+                ],
+                "http.on_open",
+            ),
+        ] {
+            let err = parse_circuit_breaker(&spec_with(&pairs)).expect_err(expect);
+            assert!(format!("{err}").contains(expect), "{err}");
+        }
+    }
+}
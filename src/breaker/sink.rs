@@ -0,0 +1,295 @@
+//! 熔断 Sink 实现
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, SinkError, SinkResult,
+};
+use wp_log::warn_data;
+use wp_model_core::model::DataRecord;
+
+use super::{BreakerConf, OnOpen};
+use crate::utils::error_kind::ErrorKind;
+
+/// 在熔断器保护下执行一次写入；熔断期间按 `on_open` 快速失败或丢弃
+macro_rules! guarded {
+    ($self:ident, $n:expr, $write:expr) => {{
+        if !$self.admit() {
+            return $self.reject($n);
+        }
+        let result = $write.await;
+        $self.record(&result);
+        result
+    }};
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// 正常放行，记录连续失败次数
+    Closed { failures: u32 },
+    /// 熔断中，到期前不访问后端
+    Open { until: Instant },
+    /// 熔断到期，放行一次写入探测后端是否恢复
+    HalfOpen,
+}
+
+/// 熔断装饰器，包装任意 [`AsyncSink`]，后端持续失败时停止访问后端
+pub struct CircuitBreakerSink {
+    inner: Box<dyn AsyncSink + 'static>,
+    /// 日志与错误信息中的 sink 类型
+    label: String,
+    conf: BreakerConf,
+    state: State,
+    /// 熔断期间被拒绝（快速失败或丢弃）的条数
+    rejected: u64,
+}
+
+impl CircuitBreakerSink {
+    pub fn new(inner: Box<dyn AsyncSink + 'static>, label: String, conf: BreakerConf) -> Self {
+        Self {
+            inner,
+            label,
+            conf,
+            state: State::Closed { failures: 0 },
+            rejected: 0,
+        }
+    }
+
+    /// 判断本次写入是否访问后端；熔断到期时转入半开
+    fn admit(&mut self) -> bool {
+        match self.state {
+            State::Open { until } if Instant::now() < until => false,
+            State::Open { .. } => {
+                wp_log::info_data!("[{}] circuit half-open, probing backend", self.label);
+                self.state = State::HalfOpen;
+                true
+            }
+            State::Closed { .. } | State::HalfOpen => true,
+        }
+    }
+
+    /// 按写入结果更新状态
+    fn record(&mut self, result: &SinkResult<()>) {
+        match (self.state, result) {
+            (State::HalfOpen, Ok(())) => {
+                wp_log::info_data!(
+                    "[{}] circuit closed, backend recovered ({} items rejected while open)",
+                    self.label,
+                    self.rejected
+                );
+                self.state = State::Closed { failures: 0 };
+                self.rejected = 0;
+            }
+            (_, Ok(())) => self.state = State::Closed { failures: 0 },
+            (State::HalfOpen, Err(err)) => self.open(err),
+            (State::Closed { failures }, Err(err)) => {
+                let failures = failures + 1;
+                if failures >= self.conf.failure_threshold {
+                    self.open(err);
+                } else {
+                    self.state = State::Closed { failures };
+                }
+            }
+            (State::Open { .. }, Err(_)) => {}
+        }
+    }
+
+    fn open(&mut self, err: &SinkError) {
+        warn_data!(
+            "[{}] circuit open for {:?}: {}",
+            self.label,
+            self.conf.open_duration,
+            err
+        );
+        self.state = State::Open {
+            until: Instant::now() + self.conf.open_duration,
+        };
+    }
+
+    fn reject(&mut self, n: usize) -> SinkResult<()> {
+        self.rejected += n as u64;
+        match self.conf.on_open {
+            OnOpen::Drop => Ok(()),
+            OnOpen::Fail => Err(ErrorKind::Connect
+                .sink(
+                    &self.label,
+                    "circuit open, backend skipped after consecutive failures",
+                )
+                .into()),
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for CircuitBreakerSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for CircuitBreakerSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        guarded!(self, 1, self.inner.sink_record(data))
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        guarded!(self, data.len(), self.inner.sink_records(data))
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for CircuitBreakerSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        guarded!(self, 1, self.inner.sink_str(data))
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        guarded!(self, 1, self.inner.sink_bytes(data))
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        guarded!(self, data.len(), self.inner.sink_str_batch(data))
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        guarded!(self, data.len(), self.inner.sink_bytes_batch(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::time::Duration;
+    use wp_connector_api::SinkReason;
+
+    /// 可切换健康状态的后端，统计实际收到的调用次数
+    #[derive(Default)]
+    struct FlakySink {
+        down: Arc<AtomicBool>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl FlakySink {
+        fn write(&self) -> SinkResult<()> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.down.load(Ordering::SeqCst) {
+                return Err(SinkReason::sink("backend down").into());
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for FlakySink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for FlakySink {
+        async fn sink_record(&mut self, _data: &DataRecord) -> SinkResult<()> {
+            self.write()
+        }
+        async fn sink_records(&mut self, _data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            self.write()
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for FlakySink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            self.write()
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            self.write()
+        }
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            self.write()
+        }
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            self.write()
+        }
+    }
+
+    const OPEN: Duration = Duration::from_millis(100);
+
+    fn guarded(on_open: OnOpen) -> (CircuitBreakerSink, Arc<AtomicBool>, Arc<AtomicUsize>) {
+        let inner = FlakySink::default();
+        let (down, calls) = (inner.down.clone(), inner.calls.clone());
+        let conf = BreakerConf {
+            failure_threshold: 3,
+            open_duration: OPEN,
+            on_open,
+        };
+        let sink = CircuitBreakerSink::new(Box::new(inner), "http".into(), conf);
+        (sink, down, calls)
+    }
+
+    #[tokio::test]
+    async fn transitions_closed_open_half_open_closed() {
+        let (mut sink, down, calls) = guarded(OnOpen::Fail);
+        let record = DataRecord::default();
+        sink.sink_record(&record).await.unwrap();
+
+        // 连续失败达到阈值后熔断，此前的失败不影响状态
+        down.store(true, Ordering::SeqCst);
+        for failures in 1..3 {
+            assert!(sink.sink_record(&record).await.is_err());
+            assert_eq!(sink.state, State::Closed { failures });
+        }
+        assert!(sink.sink_record(&record).await.is_err());
+        assert!(matches!(sink.state, State::Open { .. }));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        // 熔断期间快速失败，不访问后端
+        for _ in 0..5 {
+            let err = sink.sink_str("line").await.expect_err("fast fail");
+            assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Connect));
+            assert!(err.to_string().contains("circuit open"), "{err}");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        // 半开探测失败：再次熔断
+        tokio::time::sleep(OPEN).await;
+        assert!(sink.sink_record(&record).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+        assert!(matches!(sink.state, State::Open { .. }));
+        assert!(sink.sink_record(&record).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+        // 后端恢复后半开探测成功：恢复正常放行
+        down.store(false, Ordering::SeqCst);
+        tokio::time::sleep(OPEN).await;
+        sink.sink_records(vec![Arc::new(DataRecord::default())])
+            .await
+            .unwrap();
+        assert_eq!(sink.state, State::Closed { failures: 0 });
+        sink.sink_record(&record).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 7);
+    }
+
+    #[tokio::test]
+    async fn drop_mode_discards_while_open() {
+        let (mut sink, down, calls) = guarded(OnOpen::Drop);
+        down.store(true, Ordering::SeqCst);
+        for _ in 0..3 {
+            assert!(sink.sink_str_batch(vec!["a", "b"]).await.is_err());
+        }
+        sink.sink_str_batch(vec!["a", "b"]).await.expect("dropped");
+        sink.sink_bytes(b"c").await.expect("dropped");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(sink.rejected, 3);
+    }
+}
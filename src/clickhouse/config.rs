@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::utils::compress::RequestCompression;
+use crate::utils::http_client::HttpClientOptions;
+use crate::utils::tls::TlsOptions;
+
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
 const DEFAULT_MAX_RETRIES: i32 = 3;
 const DEFAULT_ENDPOINT: &str = "http://localhost:8123";
@@ -21,6 +25,15 @@ pub struct ClickHouseSinkConfig {
     pub timeout_secs: u64,
     /// 最大重试次数（-1 表示无限重试）
     pub max_retries: i32,
+    /// HTTPS 连接的 TLS 配置（自定义 CA、客户端证书等）
+    #[serde(default)]
+    pub tls: TlsOptions,
+    /// HTTP 客户端超时与连接池配置；`request_timeout_secs` 覆盖 `timeout_secs`
+    #[serde(default)]
+    pub http: HttpClientOptions,
+    /// 插入请求体压缩方式；为空时不压缩
+    #[serde(default)]
+    pub compression: Option<RequestCompression>,
 }
 
 impl ClickHouseSinkConfig {
@@ -54,9 +67,30 @@ impl ClickHouseSinkConfig {
             password,
             timeout_secs: timeout_secs.unwrap_or(Self::default_timeout_secs()),
             max_retries: max_retries.unwrap_or(Self::default_max_retries()),
+            tls: TlsOptions::default(),
+            http: HttpClientOptions::default(),
+            compression: None,
         }
     }
 
+    /// 指定 TLS 配置
+    pub fn with_tls(mut self, tls: TlsOptions) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// 指定 HTTP 客户端超时与连接池配置
+    pub fn with_http(mut self, http: HttpClientOptions) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// 指定插入请求体压缩方式
+    pub fn with_compression(mut self, compression: Option<RequestCompression>) -> Self {
+        self.compression = compression;
+        self
+    }
+
     pub fn default_endpoint() -> &'static str {
         DEFAULT_ENDPOINT
     }
@@ -1,4 +1,10 @@
 use crate::clickhouse::{ClickHouseSink, ClickHouseSinkConfig};
+use crate::spill::{self, SPILL_DIR_PARAM};
+use crate::utils::bytes_encoding::{BYTES_ENCODING_PARAM, parse_bytes_encoding};
+use crate::utils::compress::{COMPRESSION_PARAM, RequestCompression};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::http_client::{HTTP_CLIENT_PARAMS, HttpClientOptions};
+use crate::utils::tls::{TLS_PARAMS, TlsOptions};
 use async_trait::async_trait;
 use serde_json::{Value, json};
 use wp_connector_api::{
@@ -16,6 +22,8 @@ impl SinkFactory for ClickHouseSinkFactory {
     }
 
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        spill::parse_spill_dir(spec)?;
         // 验证必填参数非空
         ensure_not_empty(spec, "endpoint")?;
         ensure_not_empty(spec, "database")?;
@@ -46,6 +54,10 @@ impl SinkFactory for ClickHouseSinkFactory {
         {
             return Err(SinkReason::sink("clickhouse.max_retries must be >= -1").into());
         }
+        TlsOptions::from_params("clickhouse", &spec.params)?;
+        HttpClientOptions::from_params("clickhouse", &spec.params)?;
+        RequestCompression::from_params("clickhouse", &spec.params)?;
+        parse_bytes_encoding(spec)?;
 
         Ok(())
     }
@@ -58,6 +70,10 @@ impl SinkFactory for ClickHouseSinkFactory {
         let password = optional_string(spec, "password").unwrap_or_default();
         let timeout_secs = get_u64(spec, "timeout_secs");
         let max_retries = get_i64(spec, "max_retries").map(|r| r as i32);
+        let tls = TlsOptions::from_params("clickhouse", &spec.params)?;
+        let http = HttpClientOptions::from_params("clickhouse", &spec.params)?;
+        let compression = RequestCompression::from_params("clickhouse", &spec.params)?;
+        let bytes_encoding = parse_bytes_encoding(spec)?;
 
         let cfg = ClickHouseSinkConfig::new(
             endpoint,
@@ -67,15 +83,22 @@ impl SinkFactory for ClickHouseSinkFactory {
             password,
             timeout_secs,
             max_retries,
-        );
-
-        let sink = ClickHouseSink::new(cfg).await.map_err(|err| {
-            SinkError::from(SinkReason::sink(format!(
-                "init clickhouse sink failed: {err}"
-            )))
-        })?;
-
-        Ok(SinkHandle::new(Box::new(sink)))
+        )
+        .with_tls(tls)
+        .with_http(http)
+        .with_compression(compression);
+
+        let sink = ClickHouseSink::new(cfg)
+            .await
+            .map_err(|err| {
+                SinkError::from(SinkReason::sink(format!(
+                    "init clickhouse sink failed: {err}"
+                )))
+            })?
+            .with_bytes_encoding(bytes_encoding);
+
+        let handle = spill::wrap_from_spec(spec, SinkHandle::new(Box::new(sink)))?;
+        decorate_sink(spec, handle)
     }
 }
 
@@ -93,8 +116,14 @@ impl SinkDefProvider for ClickHouseSinkFactory {
                 "password",
                 "timeout_secs",
                 "max_retries",
+                COMPRESSION_PARAM,
+                BYTES_ENCODING_PARAM,
+                SPILL_DIR_PARAM,
             ]
             .into_iter()
+            .chain(TLS_PARAMS)
+            .chain(HTTP_CLIENT_PARAMS)
+            .chain(DECORATOR_PARAMS)
             .map(str::to_string)
             .collect(),
             default_params: clickhouse_defaults(),
@@ -435,4 +464,26 @@ mod tests {
         assert_eq!(params.get("timeout_secs").and_then(Value::as_u64), Some(30));
         assert_eq!(params.get("max_retries").and_then(Value::as_i64), Some(3));
     }
+
+    #[tokio::test]
+    async fn build_reports_invalid_tls_params() {
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+
+        let mut spec = base_spec();
+        spec.params
+            .insert("tls_ca_cert".into(), json!("/nonexistent/ca.pem"));
+        let err = ClickHouseSinkFactory
+            .build(&spec, &ctx)
+            .await
+            .expect_err("missing ca file");
+        assert!(format!("{err}").contains("clickhouse.tls_ca_cert: read '/nonexistent/ca.pem'"));
+
+        let mut spec = base_spec();
+        spec.params.insert("tls_insecure".into(), json!("true"));
+        assert!(ClickHouseSinkFactory.validate_spec(&spec).is_err());
+
+        let mut spec = base_spec();
+        spec.params.insert("tls_insecure".into(), json!(true));
+        assert!(ClickHouseSinkFactory.build(&spec, &ctx).await.is_ok());
+    }
 }
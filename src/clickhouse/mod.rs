@@ -53,6 +53,10 @@
 //! - `password`: 认证密码（可选）
 //! - `timeout_secs`: 请求超时时间，默认 30 秒
 //! - `max_retries`: 最大重试次数，默认 3 次，-1 表示无限重试
+//! - `tls_ca_cert` / `tls_client_cert` / `tls_client_key` / `tls_insecure`: HTTPS 连接的 TLS 配置，
+//!   见 [`crate::utils::tls`]
+//! - `request_timeout_secs` / `connect_timeout_secs` / `pool_max_idle_per_host`: HTTP 客户端超时与
+//!   连接池配置，`request_timeout_secs` 覆盖 `timeout_secs`，见 [`crate::utils::http_client`]
 //!
 //! # 错误处理
 //!
@@ -1,6 +1,12 @@
 use super::config::ClickHouseSinkConfig;
+use crate::utils::bytes_encoding::BytesEncoding;
+use crate::utils::compress::RequestCompression;
+use crate::utils::db_metrics::record_flush;
 use crate::utils::fmt::{BatchFormat, fmt_strs};
+use crate::utils::http_client::HttpClientOptions;
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
 use crate::utils::time_stat_utils::TimeStatUtils;
+use crate::utils::tls::TlsOptions;
 use async_trait::async_trait;
 use clickhouse::Client;
 use std::sync::Arc;
@@ -14,14 +20,99 @@ use wp_model_core::model::DataRecord;
 // 全局原子计数器，用于生成唯一的实例 ID
 static INSTANCE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// 构建自定义 hyper 客户端，clickhouse 库默认客户端不支持自定义证书、连接超时与连接池设置
+///
+/// 配置了 TLS 时使用基于 rustls 的 HTTP(S) 连接器；`request_timeout_secs` 由 hyper
+/// 客户端无法表达，仅作用于压缩插入使用的 reqwest 客户端。
+fn custom_client(tls: &TlsOptions, opts: &HttpClientOptions) -> SinkResult<Client> {
+    use hyper_util::client::legacy::{Client as HyperClient, connect::HttpConnector};
+    use hyper_util::rt::TokioExecutor;
+
+    let mut http = HttpConnector::new();
+    http.enforce_http(false);
+    http.set_connect_timeout(opts.connect_timeout);
+    let mut builder = HyperClient::builder(TokioExecutor::new());
+    if let Some(n) = opts.pool_max_idle_per_host {
+        builder.pool_max_idle_per_host(n);
+    }
+    if !tls.is_configured() {
+        return Ok(Client::with_http_client(builder.build(http)));
+    }
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_tls_config(tls.rustls_config("clickhouse")?)
+        .https_or_http()
+        .enable_http1()
+        .wrap_connector(http);
+    Ok(Client::with_http_client(builder.build(connector)))
+}
+
+/// 压缩插入所需的 HTTP 客户端与认证信息
+///
+/// clickhouse 库的 query 接口不支持压缩请求体，开启压缩时直接调用 HTTP 接口，
+/// 将 NDJSON 作为压缩后的请求体提交。
+struct CompressedInsert {
+    http: reqwest::Client,
+    endpoint: String,
+    username: String,
+    password: String,
+    compression: RequestCompression,
+}
+
+impl CompressedInsert {
+    fn new(config: &ClickHouseSinkConfig, compression: RequestCompression) -> SinkResult<Self> {
+        let builder = reqwest::Client::builder().timeout(Duration::from_secs(config.timeout_secs));
+        let http = config
+            .http
+            .apply_reqwest(config.tls.apply_reqwest("clickhouse", builder)?)
+            .build()
+            .map_err(|e| sink_error(format!("build clickhouse http client failed: {}", e)))?;
+        Ok(Self {
+            http,
+            endpoint: config.endpoint.clone(),
+            username: config.username.clone(),
+            password: config.password.clone(),
+            compression,
+        })
+    }
+
+    /// 提交一次压缩插入，失败时返回服务端错误信息（含 `Code: N`）
+    async fn execute(&self, database: &str, query: &str, body: Vec<u8>) -> Result<(), String> {
+        let mut url = reqwest::Url::parse(&self.endpoint).map_err(|e| e.to_string())?;
+        url.query_pairs_mut()
+            .append_pair("query", query)
+            .append_pair("async_insert", "0")
+            .append_pair("wait_for_async_insert", "0");
+        let response = self
+            .http
+            .post(url)
+            .header("X-ClickHouse-User", &self.username)
+            .header("X-ClickHouse-Key", &self.password)
+            .header("X-ClickHouse-Database", database)
+            .header("Content-Encoding", self.compression.content_encoding())
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let text = response.text().await.unwrap_or_default();
+        Err(format!("status={}, body={}", status, text.trim()))
+    }
+}
+
 /// ClickHouse Sink 实现，负责将数据记录批量写入 ClickHouse
 pub struct ClickHouseSink {
-    client: Client,            // ClickHouse 客户端
-    database: String,          // 数据库名称
-    table: String,             // 目标表名称
-    max_retries: i32,          // 最大重试次数
-    instance_id: u64,          // 实例唯一 ID
-    time_stats: TimeStatUtils, // 性能统计工具
+    client: Client,                        // ClickHouse 客户端
+    compressed: Option<CompressedInsert>,  // 开启请求压缩时使用的 HTTP 插入
+    database: String,                      // 数据库名称
+    table: String,                         // 目标表名称
+    max_retries: i32,                      // 最大重试次数
+    instance_id: u64,                      // 实例唯一 ID
+    time_stats: TimeStatUtils,             // 性能统计工具
+    bytes_encoding: Option<BytesEncoding>, // 字节类字段的文本编码
+    counters: SinkCounters,                // 收发计数
 }
 
 impl ClickHouseSink {
@@ -33,8 +124,20 @@ impl ClickHouseSink {
     /// # Returns
     /// * `anyhow::Result<Self>` - 成功返回初始化后的 sink
     pub async fn new(config: ClickHouseSinkConfig) -> anyhow::Result<Self> {
-        // 构建 ClickHouse 客户端
-        let client = Client::default()
+        // 构建 ClickHouse 客户端；配置了 TLS、连接超时或连接池参数时改用自定义 hyper 客户端
+        let client = if config.tls.is_configured()
+            || config.http.connect_timeout.is_some()
+            || config.http.pool_max_idle_per_host.is_some()
+        {
+            custom_client(&config.tls, &config.http)?
+        } else {
+            Client::default()
+        };
+        let compressed = config
+            .compression
+            .map(|compression| CompressedInsert::new(&config, compression))
+            .transpose()?;
+        let client = client
             .with_url(&config.endpoint)
             .with_database(&config.database)
             .with_user(&config.username)
@@ -45,14 +148,23 @@ impl ClickHouseSink {
 
         Ok(Self {
             client,
+            compressed,
             database: config.database,
             table: config.table,
             max_retries: config.max_retries,
             instance_id,
             time_stats: TimeStatUtils::new(),
+            bytes_encoding: None,
+            counters: SinkCounters::default(),
         })
     }
 
+    /// 设置字节类字段的文本编码，`None` 时按原样输出
+    pub fn with_bytes_encoding(mut self, bytes_encoding: Option<BytesEncoding>) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
     /// 将批量记录转换为 NDJSON 格式（每行一个 JSON 对象）
     ///
     /// # Arguments
@@ -61,7 +173,11 @@ impl ClickHouseSink {
     /// # Returns
     /// * `SinkResult<String>` - NDJSON 字符串
     fn records_to_ndjson(&self, records: &[Arc<DataRecord>]) -> SinkResult<String> {
-        Ok(fmt_strs(records.to_vec(), BatchFormat::Ndjson))
+        let records = match self.bytes_encoding {
+            Some(encoding) => records.iter().map(|r| encoding.encode_record(r)).collect(),
+            None => records.to_vec(),
+        };
+        Ok(fmt_strs(records, BatchFormat::Ndjson))
     }
 
     /// 执行批量插入请求（使用同步插入确保立即捕获错误）
@@ -71,8 +187,8 @@ impl ClickHouseSink {
     /// * `row_count` - 行数
     ///
     /// # Returns
-    /// * `SinkResult<()>` - 成功或错误
-    async fn insert_batch(&self, ndjson: String, row_count: usize) -> SinkResult<()> {
+    /// * `SinkResult<usize>` - 成功时返回发送的请求体字节数
+    async fn insert_batch(&self, ndjson: String, row_count: usize) -> SinkResult<usize> {
         let mut retries = 0;
         let max_retries = if self.max_retries < 0 {
             i32::MAX
@@ -80,35 +196,28 @@ impl ClickHouseSink {
             self.max_retries
         };
 
+        // 压缩模式下请求体只需压缩一次，重试时复用
+        let compressed_body = match &self.compressed {
+            Some(insert) => Some(
+                insert
+                    .compression
+                    .encode(ndjson.as_bytes())
+                    .map_err(|e| sink_error(format!("compress insert body failed: {}", e)))?,
+            ),
+            None => None,
+        };
+
         loop {
-            // 使用原始 SQL 查询插入 JSONEachRow 格式数据，使用完整的表名（database.table）
-            let query = format!(
-                "INSERT INTO {}.{} FORMAT JSONEachRow\n{}",
-                self.database, self.table, ndjson
-            );
-
-            // 使用同步插入设置，确保立即返回错误
-            // async_insert=0: 禁用异步插入
-            // wait_for_async_insert=0: 不等待异步插入（因为已禁用）
-            match self
-                .client
-                .query(&query)
-                .with_setting("async_insert", "0")
-                .with_setting("wait_for_async_insert", "0")
-                .execute()
-                .await
-            {
+            match self.execute_insert(&ndjson, compressed_body.as_ref()).await {
                 Ok(_) => {
                     log::info!(
                         "ClickHouseSink-{}: successfully inserted {} rows",
                         self.instance_id,
                         row_count
                     );
-                    return Ok(());
+                    return Ok(compressed_body.as_ref().map_or(ndjson.len(), Vec::len));
                 }
-                Err(e) => {
-                    let error_msg = e.to_string();
-
+                Err(error_msg) => {
                     // 检查是否是客户端错误（不重试）
                     if error_msg.contains("Code: 62") // Syntax error
                         || error_msg.contains("Code: 60") // Table doesn't exist
@@ -149,6 +258,35 @@ impl ClickHouseSink {
     }
 }
 
+impl ClickHouseSink {
+    /// 执行一次插入；失败时返回错误信息用于判断是否重试
+    async fn execute_insert(
+        &self,
+        ndjson: &str,
+        compressed: Option<&Vec<u8>>,
+    ) -> Result<(), String> {
+        let table = format!("{}.{}", self.database, self.table);
+        if let (Some(insert), Some(body)) = (&self.compressed, compressed) {
+            let query = format!("INSERT INTO {} FORMAT JSONEachRow", table);
+            return insert.execute(&self.database, &query, body.clone()).await;
+        }
+
+        // 使用原始 SQL 查询插入 JSONEachRow 格式数据，使用完整的表名（database.table）
+        let query = format!("INSERT INTO {} FORMAT JSONEachRow\n{}", table, ndjson);
+
+        // 使用同步插入设置，确保立即返回错误
+        // async_insert=0: 禁用异步插入
+        // wait_for_async_insert=0: 不等待异步插入（因为已禁用）
+        self.client
+            .query(&query)
+            .with_setting("async_insert", "0")
+            .with_setting("wait_for_async_insert", "0")
+            .execute()
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
 #[async_trait]
 impl AsyncRecordSink for ClickHouseSink {
     async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
@@ -159,6 +297,7 @@ impl AsyncRecordSink for ClickHouseSink {
         if data.is_empty() {
             return Ok(());
         }
+        self.counters.receive(data.len());
         // 开始统计
         self.time_stats.start_stat(data.len() as u64);
 
@@ -167,7 +306,16 @@ impl AsyncRecordSink for ClickHouseSink {
         let row_count = data.len();
 
         // 执行批量插入
-        self.insert_batch(ndjson, row_count).await?;
+        let result = self.insert_batch(ndjson, row_count).await;
+        record_flush(
+            "clickhouse",
+            &format!("{}.{}", self.database, self.table),
+            row_count,
+            &result,
+        );
+        let bytes = *result.as_ref().unwrap_or(&0);
+        self.counters.finish(row_count, bytes, &result);
+        result?;
 
         // 结束统计
         self.time_stats.end_stat();
@@ -216,7 +364,92 @@ impl AsyncCtrl for ClickHouseSink {
     }
 }
 
+impl SinkMetrics for ClickHouseSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
+}
+
 /// 统一封装 sink 层错误
 fn sink_error(msg: impl Into<String>) -> SinkError {
     SinkError::from(SinkReason::Sink(msg.into()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use std::io::Read;
+    use wp_model_core::model::DataField;
+
+    #[tokio::test]
+    async fn gzip_insert_body_decompresses_to_ndjson() {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 1));
+        record.append(DataField::from_chars("msg", "hello"));
+        let records = vec![Arc::new(record)];
+        let expected = fmt_strs(records.clone(), BatchFormat::Ndjson);
+
+        let server = MockServer::start_async().await;
+        let insert_mock = server
+            .mock_async(move |when, then| {
+                when.method(POST)
+                    .path("/")
+                    .query_param("query", "INSERT INTO db.events FORMAT JSONEachRow")
+                    .header("content-encoding", "gzip")
+                    .header("x-clickhouse-user", "default")
+                    .is_true(move |req| {
+                        let mut body = String::new();
+                        flate2::read::GzDecoder::new(&req.body_vec()[..])
+                            .read_to_string(&mut body)
+                            .is_ok()
+                            && body == expected
+                    });
+                then.status(200);
+            })
+            .await;
+        let cfg = ClickHouseSinkConfig::new(
+            server.base_url(),
+            "db".into(),
+            "events".into(),
+            "default".into(),
+            String::new(),
+            None,
+            Some(0),
+        )
+        .with_compression(Some(RequestCompression::Gzip));
+        let mut sink = ClickHouseSink::new(cfg).await.expect("build sink");
+
+        sink.sink_records(records).await.expect("insert accepted");
+        insert_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn bytes_encoding_renders_hex_fields() {
+        use wp_model_core::model::{DataType, HexT, Value};
+
+        let mut record = DataRecord::default();
+        record.append(DataField::new(
+            DataType::Hex,
+            "raw",
+            Value::Hex(HexT(0x7770)),
+        ));
+        let records = vec![Arc::new(record)];
+        let cfg = ClickHouseSinkConfig::new(
+            "http://localhost:8123".into(),
+            "db".into(),
+            "events".into(),
+            "default".into(),
+            String::new(),
+            None,
+            None,
+        );
+        let sink = ClickHouseSink::new(cfg).await.expect("build sink");
+        let plain = sink.records_to_ndjson(&records).expect("ndjson");
+        assert!(plain.contains("0x7770"), "{plain}");
+
+        let sink = sink.with_bytes_encoding(Some(BytesEncoding::Hex));
+        let encoded = sink.records_to_ndjson(&records).expect("ndjson");
+        assert!(encoded.contains("\"raw\":\"7770\""), "{encoded}");
+    }
+}
@@ -7,6 +7,7 @@ use wp_connector_api::{
 };
 
 use crate::count::{CountSink, CountSource};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
 
 pub struct CountSourceFactory;
 
@@ -54,16 +55,17 @@ impl SinkFactory for CountSinkFactory {
         "count"
     }
 
-    fn validate_spec(&self, _spec: &SinkSpec) -> SinkResult<()> {
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
         Ok(())
     }
 
-    async fn build(&self, _spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
         let sink = CountSink::new().await.map_err(|err| {
             SinkError::from(SinkReason::sink(format!("init count sink failed: {err}")))
         })?;
 
-        Ok(SinkHandle::new(Box::new(sink)))
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
     }
 }
 
@@ -73,7 +75,7 @@ impl SinkDefProvider for CountSinkFactory {
             id: "count_sink".into(),
             kind: self.kind().into(),
             scope: ConnectorScope::Sink,
-            allow_override: vec![].into_iter().map(str::to_string).collect(),
+            allow_override: DECORATOR_PARAMS.into_iter().map(str::to_string).collect(),
             default_params: count_defaults(),
             origin: Some("wp-connectors:count_sink".into()),
         }
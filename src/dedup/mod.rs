@@ -0,0 +1,171 @@
+//! 通用记录去重装饰器
+//!
+//! 上游重放时，短时间内可能出现重复记录。参数 `dedup_field` 指定去重键字段（如 `wp_event_id`），
+//! Sink 被 [`DedupSink`] 包装：
+//!
+//! - 键在窗口内已出现过的记录直接丢弃，不写入内部 Sink；
+//! - 窗口最多保存 `dedup_window`（缺省 100000）个键，超出时淘汰最早的键；
+//! - 键在 `dedup_ttl`（缺省 60s）后过期，过期后同一键再次出现会放行。
+//!
+//! 缺少去重字段的记录与原始文本/字节不参与去重。`dedup_ttl` 的格式与 `open_duration` 相同。
+
+mod sink;
+
+pub use sink::DedupSink;
+
+use std::time::Duration;
+
+use serde_json::Value;
+use wp_connector_api::{SinkHandle, SinkReason, SinkResult, SinkSpec};
+
+use crate::breaker::parse_duration;
+
+/// 去重键字段参数名
+pub const DEDUP_FIELD_PARAM: &str = "dedup_field";
+
+/// 去重窗口的其余参数，仅在配置 `dedup_field` 时允许配置
+pub const DEDUP_OPTION_PARAMS: [&str; 2] = ["dedup_window", "dedup_ttl"];
+
+const DEFAULT_WINDOW: usize = 100_000;
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+/// 去重配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DedupConf {
+    /// 去重键字段
+    pub field: String,
+    /// 窗口内最多保存的键数
+    pub window: usize,
+    /// 键的有效期
+    pub ttl: Duration,
+}
+
+/// 读取去重参数；未配置 `dedup_field` 时返回 `None`
+pub fn parse_dedup(spec: &SinkSpec) -> SinkResult<Option<DedupConf>> {
+    let kind = &spec.kind;
+    let invalid = |key: &str, expect: &str| -> SinkResult<Option<DedupConf>> {
+        Err(SinkReason::sink(format!("{kind}.{key} must be {expect}")).into())
+    };
+    let field = match spec.params.get(DEDUP_FIELD_PARAM) {
+        None | Some(Value::Null) => None,
+        Some(Value::String(s)) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Some(_) => return invalid(DEDUP_FIELD_PARAM, "a non-empty string"),
+    };
+    let Some(field) = field else {
+        if let Some(key) = DEDUP_OPTION_PARAMS
+            .into_iter()
+            .find(|key| spec.params.contains_key(*key))
+        {
+            return Err(SinkReason::sink(format!(
+                "{kind}.{key} only applies when {kind}.{DEDUP_FIELD_PARAM} is set"
+            ))
+            .into());
+        }
+        return Ok(None);
+    };
+
+    let mut conf = DedupConf {
+        field,
+        window: DEFAULT_WINDOW,
+        ttl: DEFAULT_TTL,
+    };
+    if let Some(v) = spec.params.get("dedup_window") {
+        match v.as_u64().and_then(|n| usize::try_from(n).ok()) {
+            Some(n) if n > 0 => conf.window = n,
+            _ => return invalid("dedup_window", "a positive integer"),
+        }
+    }
+    if let Some(v) = spec.params.get("dedup_ttl") {
+        match parse_duration(v) {
+            Some(d) if !d.is_zero() => conf.ttl = d,
+            _ => {
+                return invalid(
+                    "dedup_ttl",
+                    "a positive number of seconds or a duration like \"500ms\", \"30s\", \"5m\"",
+                );
+            }
+        }
+    }
+    Ok(Some(conf))
+}
+
+/// 按 `dedup_field` 参数包装已构建的 Sink；未配置时原样返回
+pub fn wrap_from_spec(spec: &SinkSpec, handle: SinkHandle) -> SinkResult<SinkHandle> {
+    match parse_dedup(spec)? {
+        Some(conf) => Ok(SinkHandle::new(Box::new(DedupSink::new(handle.sink, conf)))),
+        None => Ok(handle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn spec_with(pairs: &[(&str, Value)]) -> SinkSpec {
+        let params: BTreeMap<String, Value> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        SinkSpec {
+            group: "test".into(),
+            name: "deduped".into(),
+            kind: "memory".into(),
+            connector_id: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn parse_reads_defaults_and_overrides() {
+        assert_eq!(parse_dedup(&spec_with(&[])).unwrap(), None);
+        assert_eq!(
+            parse_dedup(&spec_with(&[(DEDUP_FIELD_PARAM, json!(" wp_event_id "))])).unwrap(),
+            Some(DedupConf {
+                field: "wp_event_id".into(),
+                window: DEFAULT_WINDOW,
+                ttl: DEFAULT_TTL,
+            })
+        );
+        assert_eq!(
+            parse_dedup(&spec_with(&[
+                (DEDUP_FIELD_PARAM, json!("id")),
+                ("dedup_window", json!(500)),
+                ("dedup_ttl", json!("2m")),
+            ]))
+            .unwrap(),
+            Some(DedupConf {
+                field: "id".into(),
+                window: 500,
+                ttl: Duration::from_secs(120),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_values() {
+        for (pairs, expect) in [
+            (
+                vec![(DEDUP_FIELD_PARAM, json!(""))],
+                "memory.dedup_field must be a non-empty string",
+            ),
+            (
+                vec![("dedup_ttl", json!(10))],
+                "memory.dedup_ttl only applies when memory.dedup_field is set",
+            ),
+            (
+                vec![(DEDUP_FIELD_PARAM, json!("id")), ("dedup_window", json!(0))],
+                "memory.dedup_window",
+            ),
+            (
+                vec![(DEDUP_FIELD_PARAM, json!("id")), ("dedup_ttl", json!("1h"))],
+                "memory.dedup_ttl",
+            ),
+        ] {
+            let err = parse_dedup(&spec_with(&pairs)).expect_err(expect);
+            assert!(format!("{err}").contains(expect), "{err}");
+        }
+    }
+}
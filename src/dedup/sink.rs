@@ -0,0 +1,328 @@
+//! 去重 Sink 实现
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, SinkResult};
+use wp_model_core::model::DataRecord;
+
+use super::DedupConf;
+
+/// 去重装饰器：按 [`DedupConf`] 丢弃窗口内键重复的记录，其余交给内部 Sink
+pub struct DedupSink {
+    inner: Box<dyn AsyncSink + 'static>,
+    conf: DedupConf,
+    /// 键到最近一次放行时间
+    seen: HashMap<String, Instant>,
+    /// 按放行顺序排列的键，用于过期与容量淘汰
+    order: VecDeque<(String, Instant)>,
+    dropped: u64,
+}
+
+impl DedupSink {
+    pub fn new(inner: Box<dyn AsyncSink + 'static>, conf: DedupConf) -> Self {
+        Self {
+            inner,
+            conf,
+            seen: HashMap::new(),
+            order: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+
+    /// 已丢弃的重复记录数
+    pub fn dropped_duplicates(&self) -> u64 {
+        self.dropped
+    }
+
+    /// 判断记录是否放行；放行时记下其键，并追加到 `marked` 以便写入失败时撤销
+    fn keep(&mut self, record: &DataRecord, marked: &mut Marked) -> bool {
+        let Some(key) = record.get_value(&self.conf.field).map(|v| v.to_string()) else {
+            return true;
+        };
+        let now = Instant::now();
+        self.evict(now);
+        if self.seen.contains_key(&key) {
+            self.dropped += 1;
+            marked.dropped += 1;
+            return false;
+        }
+        self.seen.insert(key.clone(), now);
+        self.order.push_back((key.clone(), now));
+        marked.keys.push((key, now));
+        self.evict(now);
+        true
+    }
+
+    /// 撤销 `marked` 中记下的键与丢弃计数，使内部写入失败后重试的记录不被当作重复丢弃
+    fn rollback(&mut self, marked: Marked) {
+        for entry in marked.keys.into_iter().rev() {
+            // 本次记下且未被淘汰的键位于 `order` 末尾
+            if self.order.back() == Some(&entry) {
+                self.order.pop_back();
+            }
+            let (key, at) = entry;
+            if self.seen.get(&key) == Some(&at) {
+                self.seen.remove(&key);
+            }
+        }
+        self.dropped -= marked.dropped;
+    }
+
+    /// 内部写入失败时撤销本次记下的键
+    fn settle(&mut self, result: SinkResult<()>, marked: Marked) -> SinkResult<()> {
+        if result.is_err() {
+            self.rollback(marked);
+        }
+        result
+    }
+
+    /// 淘汰过期的键，以及超出窗口容量的最早的键
+    fn evict(&mut self, now: Instant) {
+        while let Some((key, at)) = self.order.front() {
+            let expired = now.duration_since(*at) >= self.conf.ttl;
+            if !expired && self.seen.len() <= self.conf.window {
+                break;
+            }
+            // 只有最近一次放行的记录才对应 `seen` 中的条目
+            if self.seen.get(key) == Some(at) {
+                self.seen.remove(key);
+            }
+            self.order.pop_front();
+        }
+    }
+
+    fn dedup(&mut self, data: Vec<Arc<DataRecord>>, marked: &mut Marked) -> Vec<Arc<DataRecord>> {
+        data.into_iter().filter(|r| self.keep(r, marked)).collect()
+    }
+}
+
+/// 一次写入中记下的键及丢弃的记录数
+#[derive(Default)]
+struct Marked {
+    keys: Vec<(String, Instant)>,
+    dropped: u64,
+}
+
+#[async_trait]
+impl AsyncCtrl for DedupSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        wp_log::info_ctrl!(
+            "[dedup] {} dropped {} duplicate records",
+            self.conf.field,
+            self.dropped
+        );
+        self.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for DedupSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        let mut marked = Marked::default();
+        if !self.keep(data, &mut marked) {
+            return Ok(());
+        }
+        let result = self.inner.sink_record(data).await;
+        self.settle(result, marked)
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        let mut marked = Marked::default();
+        let data = self.dedup(data, &mut marked);
+        if data.is_empty() {
+            return Ok(());
+        }
+        let result = self.inner.sink_records(data).await;
+        self.settle(result, marked)
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for DedupSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.inner.sink_str(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.inner.sink_bytes(data).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.inner.sink_str_batch(data).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.inner.sink_bytes_batch(data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use wp_connector_api::{SinkError, SinkReason};
+    use wp_model_core::model::DataField;
+
+    /// 保存收到记录的 `id` 字段；`failures` 大于零时先让相应次数的写入失败
+    #[derive(Default)]
+    struct CollectingSink {
+        ids: Arc<Mutex<Vec<String>>>,
+        failures: usize,
+    }
+
+    impl CollectingSink {
+        fn push(&self, record: &DataRecord) {
+            let id = record.get_value("id").map(|v| v.to_string());
+            self.ids.lock().unwrap().push(id.unwrap_or_default());
+        }
+
+        fn fail_once(&mut self) -> SinkResult<()> {
+            if self.failures == 0 {
+                return Ok(());
+            }
+            self.failures -= 1;
+            Err(SinkError::from(SinkReason::sink("inner write failed")))
+        }
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for CollectingSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for CollectingSink {
+        async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+            self.fail_once()?;
+            self.push(data);
+            Ok(())
+        }
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            self.fail_once()?;
+            for record in &data {
+                self.push(record);
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for CollectingSink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    fn deduped(window: usize, ttl: Duration) -> (DedupSink, Arc<Mutex<Vec<String>>>) {
+        deduped_failing(window, ttl, 0)
+    }
+
+    fn deduped_failing(
+        window: usize,
+        ttl: Duration,
+        failures: usize,
+    ) -> (DedupSink, Arc<Mutex<Vec<String>>>) {
+        let inner = CollectingSink {
+            failures,
+            ..CollectingSink::default()
+        };
+        let ids = inner.ids.clone();
+        let conf = DedupConf {
+            field: "id".into(),
+            window,
+            ttl,
+        };
+        (DedupSink::new(Box::new(inner), conf), ids)
+    }
+
+    fn record(id: &str) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("id", id));
+        Arc::new(record)
+    }
+
+    #[tokio::test]
+    async fn repeated_key_within_window_is_dropped() {
+        let (mut sink, ids) = deduped(100, Duration::from_secs(60));
+        sink.sink_records(vec![record("a"), record("b"), record("a")])
+            .await
+            .unwrap();
+        sink.sink_record(&record("b")).await.unwrap();
+        sink.sink_records(vec![record("c")]).await.unwrap();
+
+        // 缺少去重字段的记录始终放行
+        let bare = Arc::new(DataRecord::default());
+        sink.sink_records(vec![bare.clone(), bare]).await.unwrap();
+
+        assert_eq!(*ids.lock().unwrap(), vec!["a", "b", "c", "", ""]);
+        assert_eq!(sink.dropped_duplicates(), 2);
+    }
+
+    #[tokio::test]
+    async fn same_key_after_expiry_passes() {
+        let (mut sink, ids) = deduped(100, Duration::from_millis(50));
+        sink.sink_record(&record("a")).await.unwrap();
+        sink.sink_record(&record("a")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        sink.sink_record(&record("a")).await.unwrap();
+        sink.sink_record(&record("a")).await.unwrap();
+
+        assert_eq!(*ids.lock().unwrap(), vec!["a", "a"]);
+        assert_eq!(sink.dropped_duplicates(), 2);
+    }
+
+    #[tokio::test]
+    async fn oldest_key_is_evicted_when_window_is_full() {
+        let (mut sink, ids) = deduped(2, Duration::from_secs(60));
+        let batch = ["a", "b", "b", "c", "a", "c"]
+            .into_iter()
+            .map(record)
+            .collect();
+        sink.sink_records(batch).await.unwrap();
+
+        // 写入 `c` 时淘汰 `a`，之后的 `a` 被视为新键
+        assert_eq!(*ids.lock().unwrap(), vec!["a", "b", "c", "a"]);
+        assert_eq!(sink.dropped_duplicates(), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_inner_write_does_not_mark_keys_as_seen() {
+        let (mut sink, ids) = deduped_failing(100, Duration::from_secs(60), 2);
+        let batch = || vec![record("a"), record("b"), record("a")];
+        sink.sink_records(batch())
+            .await
+            .expect_err("first write fails");
+        sink.sink_record(&record("c"))
+            .await
+            .expect_err("second write fails");
+        assert_eq!(sink.dropped_duplicates(), 0);
+
+        // 重试同一批记录时正常写出
+        sink.sink_records(batch()).await.unwrap();
+        sink.sink_record(&record("c")).await.unwrap();
+        assert_eq!(*ids.lock().unwrap(), vec!["a", "b", "c"]);
+        assert_eq!(sink.dropped_duplicates(), 1);
+    }
+}
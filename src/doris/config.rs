@@ -1,13 +1,17 @@
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 const DEFAULT_TIMEOUT_SECS: u64 = 60;
 const DEFAULT_MAX_RETRIES: i32 = 3;
+const DEFAULT_MAX_STMT_BYTES: usize = 4 * 1024 * 1024;
+const DEFAULT_HTTP_PORT: u16 = 8030;
 
 /// Configuration for building a [`DorisSink`](crate::doris::DorisSink) using Stream Load API.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DorisSinkConfig {
-    /// HTTP endpoint for Doris BE node (e.g., "http://localhost:8040")
+    /// HTTP endpoint of the Doris FE node (e.g., "http://localhost:8030");
+    /// a comma-separated list names several FEs for failover
     pub endpoint: String,
     /// Target database name
     pub database: String,
@@ -24,6 +28,12 @@ pub struct DorisSinkConfig {
     /// Optional custom headers for Stream Load parameters
     #[serde(skip_serializing_if = "Option::is_none")]
     pub headers: Option<HashMap<String, String>>,
+    /// Maximum body size in bytes of a single Stream Load request; larger batches are split
+    #[serde(default = "DorisSinkConfig::default_max_stmt_bytes")]
+    pub max_stmt_bytes: usize,
+    /// HTTP port of the FE node; overrides the port in `endpoint` when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_port: Option<u16>,
 }
 
 impl DorisSinkConfig {
@@ -53,7 +63,12 @@ impl DorisSinkConfig {
         headers: Option<HashMap<String, String>>,
     ) -> Self {
         Self {
-            endpoint: endpoint.trim().to_string(),
+            endpoint: endpoint
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(","),
             database: database.trim().to_string(),
             table: table.trim().to_string(),
             user: user.trim().to_string(),
@@ -61,9 +76,77 @@ impl DorisSinkConfig {
             timeout_secs: timeout_secs.unwrap_or(Self::default_timeout_secs()),
             max_retries: max_retries.unwrap_or(Self::default_max_retries()),
             headers,
+            max_stmt_bytes: Self::default_max_stmt_bytes(),
+            http_port: None,
         }
     }
 
+    /// Override the per-request body size limit (default: 4 MiB).
+    pub fn with_max_stmt_bytes(mut self, max_stmt_bytes: Option<usize>) -> Self {
+        if let Some(limit) = max_stmt_bytes.filter(|n| *n > 0) {
+            self.max_stmt_bytes = limit;
+        }
+        self
+    }
+
+    /// Override the HTTP port used for Stream Load and health checks.
+    pub fn with_http_port(mut self, http_port: Option<u16>) -> Self {
+        self.http_port = http_port;
+        self
+    }
+
+    /// FE endpoints listed in `endpoint`, in configured order.
+    pub fn endpoints(&self) -> Vec<&str> {
+        self.endpoint
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// Host name of the first FE node, derived from `endpoint`.
+    pub fn fe_host(&self) -> Option<String> {
+        self.endpoints()
+            .first()
+            .and_then(|endpoint| Url::parse(endpoint).ok())
+            .and_then(|url| url.host_str().map(str::to_string))
+    }
+
+    /// HTTP base URL (`scheme://fe_host:port`) of the first FE node.
+    pub fn http_base_url(&self) -> anyhow::Result<String> {
+        self.http_base_urls()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("doris endpoint must not be empty"))
+    }
+
+    /// HTTP base URLs of all FE nodes, used to compose Stream Load / health check URLs.
+    ///
+    /// The port is `http_port` when set, otherwise the port in each endpoint,
+    /// falling back to the FE default (8030) when the endpoint has none.
+    pub fn http_base_urls(&self) -> anyhow::Result<Vec<String>> {
+        let endpoints = self.endpoints();
+        if endpoints.is_empty() {
+            anyhow::bail!("doris endpoint must not be empty");
+        }
+        endpoints
+            .into_iter()
+            .map(|endpoint| self.base_url_of(endpoint))
+            .collect()
+    }
+
+    fn base_url_of(&self, endpoint: &str) -> anyhow::Result<String> {
+        let mut url = Url::parse(endpoint)
+            .map_err(|e| anyhow::anyhow!("invalid doris endpoint '{endpoint}': {e}"))?;
+        let port = self
+            .http_port
+            .or(url.port())
+            .unwrap_or(Self::default_http_port());
+        url.set_port(Some(port))
+            .map_err(|_| anyhow::anyhow!("doris endpoint '{endpoint}' has no host"))?;
+        Ok(url.as_str().trim_end_matches('/').to_string())
+    }
+
     pub fn default_timeout_secs() -> u64 {
         DEFAULT_TIMEOUT_SECS
     }
@@ -71,6 +154,14 @@ impl DorisSinkConfig {
     pub fn default_max_retries() -> i32 {
         DEFAULT_MAX_RETRIES
     }
+
+    pub fn default_max_stmt_bytes() -> usize {
+        DEFAULT_MAX_STMT_BYTES
+    }
+
+    pub fn default_http_port() -> u16 {
+        DEFAULT_HTTP_PORT
+    }
 }
 
 #[cfg(test)]
@@ -95,6 +186,10 @@ mod tests {
         assert_eq!(cfg.database, "demo");
         assert_eq!(cfg.endpoint, "http://localhost:8040");
         assert_eq!(cfg.headers, None);
+        assert_eq!(
+            cfg.max_stmt_bytes,
+            DorisSinkConfig::default_max_stmt_bytes()
+        );
     }
 
     #[test]
@@ -116,4 +211,58 @@ mod tests {
         assert_eq!(cfg.max_retries, 5);
         assert_eq!(cfg.headers, Some(headers));
     }
+
+    #[test]
+    fn http_base_url_uses_http_port() {
+        let cfg = DorisSinkConfig::new(
+            "http://doris-fe.local:9030".into(),
+            "demo".into(),
+            "events".into(),
+            "root".into(),
+            "".into(),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(cfg.fe_host().as_deref(), Some("doris-fe.local"));
+        assert_eq!(cfg.http_base_url().unwrap(), "http://doris-fe.local:9030");
+
+        let cfg = cfg.with_http_port(Some(8030));
+        assert_eq!(cfg.http_base_url().unwrap(), "http://doris-fe.local:8030");
+
+        let mut cfg = cfg.with_http_port(None);
+        cfg.endpoint = "https://doris-fe.local/".into();
+        assert_eq!(cfg.http_base_url().unwrap(), "https://doris-fe.local:8030");
+    }
+
+    #[test]
+    fn endpoint_list_yields_one_base_url_per_fe() {
+        let cfg = DorisSinkConfig::new(
+            " http://fe1:8030 , http://fe2,,https://fe3:9030/ ".into(),
+            "demo".into(),
+            "events".into(),
+            "root".into(),
+            "".into(),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(cfg.endpoint, "http://fe1:8030,http://fe2,https://fe3:9030/");
+        assert_eq!(
+            cfg.endpoints(),
+            vec!["http://fe1:8030", "http://fe2", "https://fe3:9030/"]
+        );
+        assert_eq!(cfg.fe_host().as_deref(), Some("fe1"));
+        assert_eq!(
+            cfg.http_base_urls().unwrap(),
+            vec!["http://fe1:8030", "http://fe2:8030", "https://fe3:9030"]
+        );
+
+        let cfg = cfg.with_http_port(Some(8040));
+        assert_eq!(cfg.http_base_url().unwrap(), "http://fe1:8040");
+
+        let mut cfg = cfg;
+        cfg.endpoint = "http://fe1,not a url".into();
+        assert!(cfg.http_base_urls().is_err());
+    }
 }
@@ -1,4 +1,11 @@
 use crate::doris::{DorisSink, config::DorisSinkConfig};
+use crate::spill::{self, SPILL_DIR_PARAM};
+use crate::utils::bytes_encoding::{BYTES_ENCODING_PARAM, parse_bytes_encoding};
+use crate::utils::column_filter::{COLUMN_FILTER_PARAMS, ColumnFilter};
+use crate::utils::column_map::{COLUMN_MAP_PARAM, ColumnMap};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::schema::{SCHEMA_PARAMS, SchemaOptions};
+use crate::utils::table_route::{TABLE_ROUTE_PARAMS, TableRouter};
 use async_trait::async_trait;
 use serde_json::{Value, json};
 use std::collections::HashMap;
@@ -16,17 +23,21 @@ impl SinkFactory for DorisSinkFactory {
     }
 
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        spill::parse_spill_dir(spec)?;
         ensure_not_empty(spec, "endpoint")?;
         ensure_not_empty(spec, "user")?;
         ensure_not_empty(spec, "table")?;
         ensure_not_empty(spec, "database")?;
 
-        // 验证 endpoint 格式
+        // 验证 endpoint 格式，逗号分隔的每个 FE 都须是 http(s) 地址
         let endpoint = required_param(spec, "endpoint")?;
-        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
-            return Err(
-                SinkReason::sink("doris.endpoint must start with http:// or https://").into(),
-            );
+        for fe in endpoint.split(',').map(str::trim) {
+            if !fe.starts_with("http://") && !fe.starts_with("https://") {
+                return Err(
+                    SinkReason::sink("doris.endpoint must start with http:// or https://").into(),
+                );
+            }
         }
 
         // 验证 timeout
@@ -48,6 +59,15 @@ impl SinkFactory for DorisSinkFactory {
             return Err(SinkReason::sink("doris.retries must be >= -1").into());
         }
 
+        parse_u64_param(spec, &["max_stmt_bytes"])?;
+        parse_http_port(spec)?;
+        parse_empty_as_null(spec)?;
+        parse_bytes_encoding(spec)?;
+        SchemaOptions::from_params("doris", &spec.params)?;
+        ColumnMap::from_params("doris", &spec.params)?;
+        ColumnFilter::from_params("doris", &spec.params)?;
+        TableRouter::from_params("doris", &spec.params)?;
+
         Ok(())
     }
 
@@ -60,6 +80,14 @@ impl SinkFactory for DorisSinkFactory {
         let timeout_secs: Option<u64> = parse_u64_param(spec, &["timeout_secs", "timeout"])?;
         let max_retries = parse_i32_param(spec, &["max_retries", "retries"])?;
         let headers = parse_headers(spec)?;
+        let max_stmt_bytes = parse_u64_param(spec, &["max_stmt_bytes"])?;
+        let http_port = parse_http_port(spec)?;
+        let empty_as_null = parse_empty_as_null(spec)?;
+        let bytes_encoding = parse_bytes_encoding(spec)?;
+        let schema = SchemaOptions::from_params("doris", &spec.params)?.build("doris")?;
+        let column_map = ColumnMap::from_params("doris", &spec.params)?;
+        let column_filter = ColumnFilter::from_params("doris", &spec.params)?;
+        let router = TableRouter::from_params("doris", &spec.params)?;
 
         let cfg = DorisSinkConfig::new(
             endpoint,
@@ -70,13 +98,30 @@ impl SinkFactory for DorisSinkFactory {
             timeout_secs,
             max_retries,
             headers,
-        );
+        )
+        .with_max_stmt_bytes(max_stmt_bytes.map(|n| n as usize))
+        .with_http_port(http_port);
 
-        let sink = DorisSink::new(cfg).await.map_err(|err| {
-            SinkError::from(SinkReason::sink(format!("init doris sink failed: {err}")))
-        })?;
+        let mut sink = DorisSink::new(cfg)
+            .await
+            .map_err(|err| {
+                SinkError::from(SinkReason::sink(format!("init doris sink failed: {err}")))
+            })?
+            .with_schema(schema)
+            .with_column_map(column_map)
+            .with_column_filter(column_filter)
+            .with_empty_as_null(empty_as_null)
+            .with_bytes_encoding(bytes_encoding);
+        if let Some(router) = router {
+            sink = sink.with_table_router(router);
+        }
+        // 表结构仅用于识别 JSON 列，读取失败时按普通字符串写入
+        if let Err(err) = sink.load_json_columns().await {
+            wp_log::warn_data!("[doris] load table schema failed, json columns disabled: {err}");
+        }
 
-        Ok(SinkHandle::new(Box::new(sink)))
+        let handle = spill::wrap_from_spec(spec, SinkHandle::new(Box::new(sink)))?;
+        decorate_sink(spec, handle)
     }
 }
 
@@ -97,8 +142,18 @@ impl SinkDefProvider for DorisSinkFactory {
                 "max_retries",
                 "retries",
                 "headers",
+                "max_stmt_bytes",
+                "http_port",
+                "empty_as_null",
+                BYTES_ENCODING_PARAM,
+                COLUMN_MAP_PARAM,
+                SPILL_DIR_PARAM,
             ]
             .into_iter()
+            .chain(COLUMN_FILTER_PARAMS)
+            .chain(SCHEMA_PARAMS)
+            .chain(TABLE_ROUTE_PARAMS)
+            .chain(DECORATOR_PARAMS)
             .map(str::to_string)
             .collect(),
             default_params: doris_defaults(),
@@ -175,6 +230,28 @@ fn parse_i32_param(spec: &SinkSpec, keys: &[&str]) -> SinkResult<Option<i32>> {
     Ok(None)
 }
 
+/// 解析 FE HTTP 端口，取值范围 1..=65535。
+fn parse_http_port(spec: &SinkSpec) -> SinkResult<Option<u16>> {
+    match spec.params.get("http_port") {
+        None => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .and_then(|n| u16::try_from(n).ok())
+            .filter(|n| *n > 0)
+            .map(Some)
+            .ok_or_else(|| SinkReason::sink("doris.http_port must be in 1..=65535").into()),
+    }
+}
+
+/// 解析 `empty_as_null`，缺省 `false`
+fn parse_empty_as_null(spec: &SinkSpec) -> SinkResult<bool> {
+    match spec.params.get("empty_as_null") {
+        None => Ok(false),
+        Some(Value::Bool(b)) => Ok(*b),
+        Some(_) => Err(SinkReason::sink("doris.empty_as_null must be a bool").into()),
+    }
+}
+
 /// 解析 headers 参数（可以是对象或嵌套的 headers 字段）。
 fn parse_headers(spec: &SinkSpec) -> SinkResult<Option<HashMap<String, String>>> {
     if let Some(headers_value) = spec.params.get("headers")
@@ -210,6 +287,10 @@ fn doris_defaults() -> ParamMap {
         "max_retries".into(),
         json!(DorisSinkConfig::default_max_retries()),
     );
+    params.insert(
+        "max_stmt_bytes".into(),
+        json!(DorisSinkConfig::default_max_stmt_bytes()),
+    );
     params
 }
 
@@ -258,6 +339,23 @@ mod tests {
         assert!(factory.validate_spec(&spec).is_err());
     }
 
+    #[test]
+    fn validate_checks_each_endpoint_in_list() {
+        let factory = DorisSinkFactory;
+        let mut spec = base_spec();
+        spec.params.insert(
+            "endpoint".into(),
+            json!("http://fe1:8030, https://fe2:8030"),
+        );
+        assert!(factory.validate_spec(&spec).is_ok());
+
+        for bad in ["http://fe1:8030,fe2:8030", "http://fe1:8030,,http://fe2"] {
+            spec.params.insert("endpoint".into(), json!(bad));
+            let err = factory.validate_spec(&spec).expect_err(bad);
+            assert!(err.to_string().contains("doris.endpoint"), "{err}");
+        }
+    }
+
     #[test]
     fn validate_accepts_minimal_spec() {
         let spec = base_spec();
@@ -272,4 +370,79 @@ mod tests {
         let factory = DorisSinkFactory;
         assert!(factory.validate_spec(&spec).is_err());
     }
+
+    #[test]
+    fn validate_rejects_zero_max_stmt_bytes() {
+        let mut spec = base_spec();
+        spec.params.insert("max_stmt_bytes".into(), Value::from(0));
+        let factory = DorisSinkFactory;
+        assert!(factory.validate_spec(&spec).is_err());
+
+        spec.params
+            .insert("max_stmt_bytes".into(), Value::from(1024 * 1024));
+        assert!(factory.validate_spec(&spec).is_ok());
+    }
+
+    #[test]
+    fn validate_checks_schema_params() {
+        let factory = DorisSinkFactory;
+        let mut spec = base_spec();
+        spec.params.insert("on_invalid".into(), json!("dlq"));
+        let err = factory.validate_spec(&spec).expect_err("dlq without path");
+        assert!(err.to_string().contains("doris.dlq_path must be set"));
+
+        spec.params
+            .insert("dlq_path".into(), json!("/tmp/doris_dlq.jsonl"));
+        spec.params
+            .insert("schema".into(), json!("/tmp/doris_schema.json"));
+        assert!(factory.validate_spec(&spec).is_ok());
+    }
+
+    #[test]
+    fn validate_checks_empty_as_null() {
+        let factory = DorisSinkFactory;
+        let mut spec = base_spec();
+        spec.params.insert("empty_as_null".into(), json!(true));
+        assert!(factory.validate_spec(&spec).is_ok());
+
+        spec.params.insert("empty_as_null".into(), json!("yes"));
+        let err = factory.validate_spec(&spec).expect_err("non-bool");
+        assert!(
+            err.to_string()
+                .contains("doris.empty_as_null must be a bool")
+        );
+    }
+
+    #[test]
+    fn schema_file_is_compiled_at_build() {
+        let path = std::env::temp_dir().join("wp_connectors_doris_bad_schema.json");
+        std::fs::write(&path, r#"{"type": 12}"#).unwrap();
+        let mut params = ParamMap::new();
+        params.insert("schema".into(), json!(path.display().to_string()));
+        let err = SchemaOptions::from_params("doris", &params)
+            .unwrap()
+            .build("doris")
+            .err()
+            .expect("invalid schema");
+        assert!(
+            err.to_string()
+                .contains("doris.schema is not a valid JSON Schema")
+        );
+    }
+
+    #[test]
+    fn validate_checks_http_port_range() {
+        let factory = DorisSinkFactory;
+        for bad in [json!(0), json!(65536), json!("8030")] {
+            let mut spec = base_spec();
+            spec.params.insert("http_port".into(), bad);
+            let err = factory.validate_spec(&spec).expect_err("bad http_port");
+            assert!(err.to_string().contains("doris.http_port"));
+        }
+
+        let mut spec = base_spec();
+        spec.params.insert("http_port".into(), json!(8030));
+        assert!(factory.validate_spec(&spec).is_ok());
+        assert_eq!(parse_http_port(&spec).unwrap(), Some(8030));
+    }
 }
@@ -13,8 +13,37 @@
 //! 每次批量导入基于批次内容生成确定性 label，保证重试幂等：
 //! - 相同 payload 会得到相同 label
 //! - Doris 可据此识别重复导入请求
+//!
+//! # 批次拆分
+//!
+//! 单次请求体超过 `max_stmt_bytes` 时，批次按记录边界拆分为多次 Stream Load 顺序提交，
+//! 每个分片使用各自的确定性 label，重试时已提交的分片会被 Doris 识别为重复导入。
+//!
+//! # JSON 列
+//!
+//! 构建时通过 `/api/{db}/{table}/_schema` 读取列类型。写入 `JSON`/`JSONB`/`VARIANT` 列的
+//! 字符串字段若本身是合法 JSON，则按原始 JSON 写入而不是转义后的字符串，以便保存嵌套结构。
+//!
+//! # 多表路由
+//!
+//! 配置 `table_field` / `tables` 时按记录字段选择目标表（见 [`crate::utils::table_route`]），
+//! 每张表使用各自的 Stream Load URL 与 JSON 列，分组后逐表提交。路由到其他表的分片
+//! label 额外包含表名，避免同库不同表的相同内容被 Doris 视为重复导入。
+//!
+//! # 多 FE 故障转移
+//!
+//! `endpoint` 可配置逗号分隔的多个 FE。构建时按配置顺序探测 `/api/health`，选用第一个可达的
+//! FE；请求发送失败时切换到下一个 FE 后重试，`reconnect` 时从下一个 FE 开始重新探测。
 
 use crate::doris::config::DorisSinkConfig;
+use crate::utils::bytes_encoding::BytesEncoding;
+use crate::utils::column_filter::ColumnFilter;
+use crate::utils::column_map::ColumnMap;
+use crate::utils::db_metrics::{record_flush, record_invalid};
+use crate::utils::schema::RecordValidator;
+use crate::utils::shutdown::DeadlineCtrl;
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
+use crate::utils::table_route::TableRouter;
 use crate::utils::time_stat_utils::TimeStatUtils;
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -22,9 +51,9 @@ use reqwest::{Client, StatusCode};
 use serde::Deserialize;
 use serde::Serialize;
 use serde::ser::{SerializeMap, SerializeSeq};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::time::Duration;
 use wp_connector_api::{
     AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
@@ -34,17 +63,59 @@ use wp_model_core::model::{DataRecord, DataType, Value};
 // 全局原子计数器，用于生成唯一的实例 ID
 static INSTANCE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+// 探测 FE 是否可达的单次请求超时
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 一张目标表的 Stream Load 信息
+struct LoadTarget {
+    name: String,                  // 表名，路由时与字段取值比较
+    table: String,                 // `database.table`，用作写入指标的标签
+    api: String,                   // `/api/{database}/{table}`，与当前 FE 的 base URL 拼接
+    json_columns: HashSet<String>, // JSON/VARIANT 类型的目标列
+}
+
+impl LoadTarget {
+    fn new(database: &str, table: &str) -> Self {
+        Self {
+            name: table.to_string(),
+            table: format!("{database}.{table}"),
+            api: format!("/api/{database}/{table}"),
+            json_columns: HashSet::new(),
+        }
+    }
+
+    fn url(&self, base_url: &str) -> String {
+        format!("{base_url}{}/_stream_load", self.api)
+    }
+
+    fn schema_url(&self, base_url: &str) -> String {
+        format!("{base_url}{}/_schema", self.api)
+    }
+}
+
 pub struct DorisSink {
     client: Client,
-    url: String, // 预先构建的完整 URL
+    base_urls: Vec<String>, // 各 FE 的 base URL，按配置顺序
+    active: AtomicUsize,    // 当前使用的 FE 下标
+    database: String,
+    target: LoadTarget,                  // 默认目标表
+    router: Option<TableRouter>,         // 多表路由，`None` 表示全部写入默认表
+    routes: HashMap<String, LoadTarget>, // 路由名单中除默认表外的表
     user: String,
     password: String,
     max_retries: i32,
     headers: HashMap<String, String>,
+    max_stmt_bytes: usize,
     instance_id: u64, // 实例唯一 ID
     // 时间统计工具
     time_stats: TimeStatUtils,
     stopped: bool,
+    schema: Option<RecordValidator>,       // 记录级 Schema 校验
+    column_map: ColumnMap,                 // 字段名到列名的映射
+    column_filter: ColumnFilter,           // 字段白名单/黑名单
+    empty_as_null: bool,                   // 空字符串按 JSON null 写入
+    bytes_encoding: Option<BytesEncoding>, // 字节类字段的文本编码
+    counters: SinkCounters,                // 收发计数
 }
 
 #[derive(Debug, Deserialize)]
@@ -85,86 +156,245 @@ impl DorisSink {
             .no_proxy() // 禁用所有代理
             .build()?;
 
-        // 预先构建完整的 Stream Load URL
-        let url = format!(
-            "{}/api/{}/{}/_stream_load",
-            config.endpoint, config.database, config.table
-        );
+        let base_urls = config.http_base_urls()?;
+        let active = match first_reachable(&client, &base_urls, 0).await {
+            Some(index) => index,
+            None => {
+                log::warn!("no reachable doris FE in [{}]", base_urls.join(", "));
+                0
+            }
+        };
+        let target = LoadTarget::new(&config.database, &config.table);
 
         // 从全局原子变量获取递增的实例 ID
         let instance_id = INSTANCE_COUNTER.fetch_add(1, Ordering::SeqCst);
 
         Ok(Self {
             client,
-            url,
+            base_urls,
+            active: AtomicUsize::new(active),
+            database: config.database,
+            target,
+            router: None,
+            routes: HashMap::new(),
             user: config.user,
             password: config.password,
             max_retries: config.max_retries,
             headers: config.headers.unwrap_or_default(),
+            max_stmt_bytes: config.max_stmt_bytes.max(1),
             instance_id,
             time_stats: TimeStatUtils::new(),
             stopped: false,
+            schema: None,
+            column_map: ColumnMap::default(),
+            column_filter: ColumnFilter::default(),
+            empty_as_null: false,
+            bytes_encoding: None,
+            counters: SinkCounters::default(),
         })
     }
 
+    /// 从 Doris 读取各目标表的结构，记录 JSON/VARIANT 类型的列，返回这类列的总数
+    pub async fn load_json_columns(&mut self) -> anyhow::Result<usize> {
+        let base_url = self.base_url().to_string();
+        self.target.json_columns = self
+            .fetch_json_columns(&self.target.schema_url(&base_url))
+            .await?;
+        let mut total = self.target.json_columns.len();
+        let routes: Vec<_> = self
+            .routes
+            .iter()
+            .map(|(name, target)| (name.clone(), target.schema_url(&base_url)))
+            .collect();
+        for (name, schema_url) in routes {
+            let columns = self.fetch_json_columns(&schema_url).await?;
+            total += columns.len();
+            if let Some(target) = self.routes.get_mut(&name) {
+                target.json_columns = columns;
+            }
+        }
+        Ok(total)
+    }
+
+    async fn fetch_json_columns(&self, schema_url: &str) -> anyhow::Result<HashSet<String>> {
+        let resp = self
+            .client
+            .get(schema_url)
+            .basic_auth(&self.user, Some(&self.password))
+            .send()
+            .await?;
+        let status = resp.status();
+        if !status.is_success() {
+            anyhow::bail!("GET {} returned {status}", schema_url);
+        }
+        let body: serde_json::Value = resp.json().await?;
+        // 新版本包在 `data` 中，旧版本直接返回 `properties`
+        let properties = body
+            .pointer("/data/properties")
+            .or_else(|| body.get("properties"))
+            .and_then(serde_json::Value::as_array)
+            .ok_or_else(|| anyhow::anyhow!("schema response has no properties"))?;
+        Ok(properties
+            .iter()
+            .filter(|col| {
+                col.get("type")
+                    .and_then(serde_json::Value::as_str)
+                    .is_some_and(is_json_type)
+            })
+            .filter_map(|col| col.get("name").and_then(serde_json::Value::as_str))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// 设置写入前的记录校验器，`None` 表示不校验
+    pub fn with_schema(mut self, schema: Option<RecordValidator>) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    /// 设置字段名到列名的映射，序列化时以目标列名作为 JSON 键
+    pub fn with_column_map(mut self, column_map: ColumnMap) -> Self {
+        self.column_map = column_map;
+        self
+    }
+
+    /// 设置字段白名单/黑名单，被排除的字段不会写入 Stream Load 数据
+    pub fn with_column_filter(mut self, column_filter: ColumnFilter) -> Self {
+        self.column_filter = column_filter;
+        self
+    }
+
+    /// 设置是否将空字符串写为 JSON `null`，由 Doris 按 NULL 入库
+    pub fn with_empty_as_null(mut self, empty_as_null: bool) -> Self {
+        self.empty_as_null = empty_as_null;
+        self
+    }
+
+    /// 设置字节类字段的文本编码，`None` 时按原样输出
+    pub fn with_bytes_encoding(mut self, bytes_encoding: Option<BytesEncoding>) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
+    /// 设置多表路由，名单中除默认表外的每张表使用各自的 Stream Load URL
+    pub fn with_table_router(mut self, router: TableRouter) -> Self {
+        self.routes = router
+            .tables()
+            .iter()
+            .filter(|name| **name != self.target.name)
+            .map(|name| {
+                let target = LoadTarget::new(&self.database, name);
+                (name.clone(), target)
+            })
+            .collect();
+        self.router = Some(router);
+        self
+    }
+
+    /// 当前使用的 FE 的 base URL
+    fn base_url(&self) -> &str {
+        &self.base_urls[self.active.load(Ordering::Relaxed) % self.base_urls.len()]
+    }
+
+    /// 切换到下一个 FE，仅配置了多个 FE 时生效
+    fn rotate_endpoint(&self) {
+        if self.base_urls.len() < 2 {
+            return;
+        }
+        let next = (self.active.load(Ordering::Relaxed) + 1) % self.base_urls.len();
+        self.active.store(next, Ordering::Relaxed);
+        log::warn!("doris failover to FE {}", self.base_urls[next]);
+    }
+
+    /// 目标表的 Stream Load 信息；不在路由名单中的表即默认表
+    fn target(&self, name: &str) -> &LoadTarget {
+        self.routes.get(name).unwrap_or(&self.target)
+    }
+
     /// 生成唯一的 label 用于 Stream Load。
     ///
-    /// 使用批次内容生成稳定标签，确保上游重试同一批数据时仍能命中 Doris 的幂等语义。
+    /// 使用整批内容生成稳定标签，确保上游重试同一批数据时仍能命中 Doris 的幂等语义；
+    /// 批次拆分为多个分片时追加分片序号与分片数，内容相同的分片也不会共用 label。
     ///
     /// # Returns
-    /// * `SinkResult<String>` - label 字符串
-    fn generate_label(&self, payload: &Bytes) -> String {
-        let (hash_a, hash_b) = fingerprint_bytes(payload.as_ref());
+    /// * `String` - 第 `index` 个分片的 label
+    fn generate_label(&self, target: &LoadTarget, chunks: &[Bytes], index: usize) -> String {
+        let mut parts: Vec<&[u8]> = Vec::with_capacity(chunks.len() + 2);
+        // 默认表只对内容取指纹，与未启用路由时的 label 保持一致
+        if target.name != self.target.name {
+            parts.extend([target.name.as_bytes(), b"\n".as_slice()]);
+        }
+        parts.extend(chunks.iter().map(Bytes::as_ref));
+        let (hash_a, hash_b) = fingerprint_parts(&parts);
+        let len = chunks.iter().map(Bytes::len).sum::<usize>();
         format!(
-            "doris_load_{:016x}_{:016x}_{:x}",
+            "doris_load_{:016x}_{:016x}_{:x}_{}_{}",
             hash_a,
             hash_b,
-            payload.len()
+            len,
+            index,
+            chunks.len()
         )
     }
 
-    /// 将 DataRecord 转换为 JSON 对象。
+    /// 将批量记录转换为若干 NDJSON 分片，每个分片不超过 `max_stmt_bytes`。
     ///
-    /// # Arguments
-    /// * `record` - 数据记录
-    ///
-    /// # Returns
-    /// 将批量记录转换为 NDJSON 格式（每行一个 JSON 对象）。
+    /// 按记录边界拆分；单条记录本身超过上限时独占一个分片。
     ///
     /// # Arguments
+    /// * `target` - 目标表
     /// * `records` - 数据记录列表
     ///
     /// # Returns
-    /// * `SinkResult<Bytes>` - NDJSON 字节流
-    fn records_to_ndjson(&self, records: &[Arc<DataRecord>]) -> SinkResult<Bytes> {
+    /// * `SinkResult<Vec<Bytes>>` - NDJSON 分片列表
+    fn records_to_ndjson_chunks(
+        &self,
+        target: &LoadTarget,
+        records: &[Arc<DataRecord>],
+    ) -> SinkResult<Vec<Bytes>> {
+        let mut chunks = Vec::new();
         let mut buffer = Vec::new();
+        let mut line = Vec::new();
 
         for record in records {
-            serde_json::to_writer(&mut buffer, &JsonRecord(record.as_ref()))
-                .map_err(|e| sink_error(format!("json serialization failed: {}", e)))?;
-            buffer.push(b'\n');
+            line.clear();
+            serde_json::to_writer(
+                &mut line,
+                &JsonRecord(record.as_ref(), self, &target.json_columns),
+            )
+            .map_err(|e| sink_error(format!("json serialization failed: {}", e)))?;
+            line.push(b'\n');
+
+            if !buffer.is_empty() && buffer.len() + line.len() > self.max_stmt_bytes {
+                chunks.push(Bytes::from(std::mem::take(&mut buffer)));
+            }
+            buffer.extend_from_slice(&line);
+        }
+        if !buffer.is_empty() {
+            chunks.push(Bytes::from(buffer));
         }
 
-        Ok(Bytes::from(buffer))
+        Ok(chunks)
     }
 
     /// 执行 Stream Load 请求。
     ///
     /// # Arguments
     ///
+    /// * `url` - 目标表的 Stream Load URL
     /// * `label` - 唯一标识符（使用第一条记录的 wp_event_id）
     /// * `data` - NDJSON 格式的数据
     ///
     /// # Returns
     ///
     /// * `SinkResult<()>` - 成功或错误
-    async fn stream_load(&self, label: &str, data: Bytes) -> SinkResult<()> {
+    async fn stream_load(&self, target: &LoadTarget, label: &str, data: Bytes) -> SinkResult<()> {
         let mut retries = 0i32;
 
         loop {
             let mut request = self
                 .client
-                .put(&self.url)
+                .put(target.url(self.base_url()))
                 .basic_auth(&self.user, Some(&self.password))
                 .header("label", label)
                 .header("format", "json")
@@ -213,6 +443,8 @@ impl DorisSink {
                     }
                 }
                 Err(e) => {
+                    // 请求未送达当前 FE，下一次重试改用其他 FE
+                    self.rotate_endpoint();
                     if self.max_retries >= 0 && retries >= self.max_retries {
                         return Err(sink_error(format!(
                             "max retries ({}) exceeded: request failed: {}",
@@ -240,6 +472,24 @@ impl DorisSink {
         }
     }
 
+    /// 按 `max_stmt_bytes` 拆分记录并逐个分片提交 Stream Load，返回提交的字节数
+    async fn load_chunks(
+        &self,
+        target: &LoadTarget,
+        records: &[Arc<DataRecord>],
+    ) -> SinkResult<usize> {
+        let chunks = self.records_to_ndjson_chunks(target, records)?;
+        let labels: Vec<_> = (0..chunks.len())
+            .map(|index| self.generate_label(target, &chunks, index))
+            .collect();
+        let mut bytes = 0;
+        for (ndjson, label) in chunks.into_iter().zip(labels) {
+            bytes += ndjson.len();
+            self.stream_load(target, &label, ndjson).await?;
+        }
+        Ok(bytes)
+    }
+
     /// 刷新缓冲区，将所有缓存的记录发送到 Doris。
     ///
     /// # Returns
@@ -349,11 +599,27 @@ impl AsyncCtrl for DorisSink {
         if self.stopped {
             return Err(sink_error("doris sink is stopped"));
         }
-        // HTTP 客户端无需显式重连
+        // HTTP 客户端无需显式重连，多 FE 时从下一个 FE 开始选择可达的节点
+        if self.base_urls.len() > 1 {
+            let start = self.active.load(Ordering::Relaxed) + 1;
+            match first_reachable(&self.client, &self.base_urls, start).await {
+                Some(index) => self.active.store(index, Ordering::Relaxed),
+                None => self.rotate_endpoint(),
+            }
+        }
         Ok(())
     }
 }
 
+// Stream Load 按批同步提交，默认实现即可在截止时间内完成 `stop`
+impl DeadlineCtrl for DorisSink {}
+
+impl SinkMetrics for DorisSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
+}
+
 #[async_trait]
 impl AsyncRecordSink for DorisSink {
     async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
@@ -362,7 +628,17 @@ impl AsyncRecordSink for DorisSink {
 
     async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
         self.ensure_running()?;
-
+        self.counters.receive(data.len());
+
+        // 未通过 Schema 校验的记录不参与 Stream Load，只计数（或写入死信文件）
+        let data = match self.schema.as_mut() {
+            Some(validator) => {
+                let (valid, invalid) = validator.split(data)?;
+                record_invalid("doris", &self.target.table, invalid);
+                valid
+            }
+            None => data,
+        };
         if data.is_empty() {
             return Ok(());
         }
@@ -370,10 +646,25 @@ impl AsyncRecordSink for DorisSink {
         // 开始统计
         self.time_stats.start_stat(data.len() as u64);
 
-        // 生成label
-        let ndjson = self.records_to_ndjson(&data)?;
-        let label = self.generate_label(&ndjson);
-        self.stream_load(&label, ndjson).await?;
+        let groups = match &self.router {
+            Some(router) => {
+                let routed = router.route(&self.target.name, data);
+                record_invalid("doris", &self.target.table, routed.rejected);
+                routed.groups
+            }
+            None => vec![(self.target.name.clone(), data)],
+        };
+
+        // 逐表按大小拆分后顺序提交，每个分片生成各自的 label；
+        // 某张表失败时直接返回，上游重试时已提交的分片由 label 去重
+        for (name, records) in groups {
+            let target = self.target(&name);
+            let result = self.load_chunks(target, &records).await;
+            record_flush("doris", &target.table, records.len(), &result);
+            let bytes = *result.as_ref().unwrap_or(&0);
+            self.counters.finish(records.len(), bytes, &result);
+            result?;
+        }
 
         // 结束统计
         self.time_stats.end_stat();
@@ -408,11 +699,29 @@ impl AsyncRawDataSink for DorisSink {
 }
 
 /// 统一封装 sink 层错误。
+/// 从 `start` 开始依次探测各 FE 的 `/api/health`，返回第一个有 HTTP 响应的下标
+async fn first_reachable(client: &Client, base_urls: &[String], start: usize) -> Option<usize> {
+    for offset in 0..base_urls.len() {
+        let index = (start + offset) % base_urls.len();
+        let probe = client
+            .get(format!("{}/api/health", base_urls[index]))
+            .timeout(PROBE_TIMEOUT)
+            .send()
+            .await;
+        match probe {
+            Ok(_) => return Some(index),
+            Err(err) => log::warn!("doris FE {} unreachable: {err}", base_urls[index]),
+        }
+    }
+    None
+}
+
 fn sink_error(msg: impl Into<String>) -> SinkError {
     SinkError::from(SinkReason::Sink(msg.into()))
 }
 
-fn fingerprint_bytes(bytes: &[u8]) -> (u64, u64) {
+/// 对依次拼接的若干字节片计算指纹
+fn fingerprint_parts(parts: &[&[u8]]) -> (u64, u64) {
     const OFFSET_A: u64 = 0xcbf29ce484222325;
     const OFFSET_B: u64 = 0x84222325cbf29ce4;
     const PRIME_A: u64 = 0x100000001b3;
@@ -421,7 +730,8 @@ fn fingerprint_bytes(bytes: &[u8]) -> (u64, u64) {
     let mut hash_a = OFFSET_A;
     let mut hash_b = OFFSET_B;
 
-    for (idx, byte) in bytes.iter().copied().enumerate() {
+    let bytes = parts.iter().flat_map(|part| part.iter().copied());
+    for (idx, byte) in bytes.enumerate() {
         hash_a ^= u64::from(byte);
         hash_a = hash_a.wrapping_mul(PRIME_A);
 
@@ -432,27 +742,54 @@ fn fingerprint_bytes(bytes: &[u8]) -> (u64, u64) {
     (hash_a, hash_b)
 }
 
-struct JsonRecord<'a>(&'a DataRecord);
+/// Doris 中以 JSON 存储的列类型
+fn is_json_type(ty: &str) -> bool {
+    let ty = ty.trim().to_ascii_uppercase();
+    ty.starts_with("JSON") || ty.starts_with("VARIANT")
+}
+
+/// 按 Sink 的列映射、字段过滤与 JSON 列设置序列化一条记录
+struct JsonRecord<'a>(&'a DataRecord, &'a DorisSink, &'a HashSet<String>);
 
 impl Serialize for JsonRecord<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let field_count = self
+        let sink = self.1;
+        let fields: Vec<_> = self
             .0
             .items
             .iter()
             .filter(|field| *field.get_meta() != DataType::Ignore)
-            .count();
-        let mut map = serializer.serialize_map(Some(field_count))?;
-
-        for field in &self.0.items {
-            if *field.get_meta() == DataType::Ignore {
+            .map(|field| (sink.column_map.resolve(field.get_name()), field))
+            .filter(|(column, field)| sink.column_filter.allows(field.get_name(), column))
+            .collect();
+        let mut map = serializer.serialize_map(Some(fields.len()))?;
+
+        for (column, field) in fields {
+            if sink.empty_as_null
+                && let Value::Chars(text) = field.get_value()
+                && text.is_empty()
+            {
+                map.serialize_entry(column, &())?;
                 continue;
             }
-
-            map.serialize_entry(field.get_name(), &JsonFieldValue(field.get_value()))?;
+            if self.2.contains(column)
+                && let Value::Chars(text) = field.get_value()
+                && let Ok(raw) = serde_json::from_str::<serde_json::Value>(text)
+            {
+                map.serialize_entry(column, &raw)?;
+                continue;
+            }
+            if let Some(text) = sink
+                .bytes_encoding
+                .and_then(|encoding| encoding.render(field.get_value()))
+            {
+                map.serialize_entry(column, &text)?;
+                continue;
+            }
+            map.serialize_entry(column, &JsonFieldValue(field.get_value()))?;
         }
 
         map.end()
@@ -557,6 +894,14 @@ mod tests {
         .unwrap()
     }
 
+    fn single_chunk(sink: &DorisSink, record: DataRecord) -> Bytes {
+        let mut chunks = sink
+            .records_to_ndjson_chunks(&sink.target, &[Arc::new(record)])
+            .unwrap();
+        assert_eq!(chunks.len(), 1);
+        chunks.remove(0)
+    }
+
     fn sample_record() -> DataRecord {
         let mut record = DataRecord::default();
         record.append(DataField::from_digit("id", 1));
@@ -675,7 +1020,7 @@ mod tests {
         );
         record.append(DataField::new(DataType::Obj, "meta", Value::Obj(nested)));
 
-        let encoded = sink.records_to_ndjson(&[Arc::new(record)]).unwrap();
+        let encoded = single_chunk(&sink, record);
         let json: serde_json::Value = serde_json::from_slice(encoded.as_ref()).unwrap();
 
         assert_eq!(json["zip_code"], serde_json::Value::String("00123".into()));
@@ -684,15 +1029,188 @@ mod tests {
         assert!(json["meta"].get("ignored").is_none());
     }
 
+    #[tokio::test]
+    async fn empty_as_null_writes_json_null() {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("name", "alice"));
+        record.append(DataField::from_chars("note", ""));
+
+        let sink = DorisSink::new(test_config()).await.unwrap();
+        let json: serde_json::Value =
+            serde_json::from_slice(&single_chunk(&sink, record.clone())).unwrap();
+        assert_eq!(json, serde_json::json!({"name": "alice", "note": ""}));
+
+        let sink = sink.with_empty_as_null(true);
+        let json: serde_json::Value = serde_json::from_slice(&single_chunk(&sink, record)).unwrap();
+        assert_eq!(json, serde_json::json!({"name": "alice", "note": null}));
+    }
+
+    #[tokio::test]
+    async fn bytes_encoding_renders_hex_fields() {
+        use wp_model_core::model::HexT;
+
+        let mut record = DataRecord::default();
+        record.append(DataField::new(
+            DataType::Hex,
+            "raw",
+            Value::Hex(HexT(0x7770)),
+        ));
+
+        let sink = DorisSink::new(test_config()).await.unwrap();
+        let json: serde_json::Value =
+            serde_json::from_slice(&single_chunk(&sink, record.clone())).unwrap();
+        assert_eq!(json, serde_json::json!({"raw": "0x7770"}));
+
+        let sink = sink.with_bytes_encoding(Some(BytesEncoding::Base64));
+        let json: serde_json::Value = serde_json::from_slice(&single_chunk(&sink, record)).unwrap();
+        assert_eq!(json, serde_json::json!({"raw": "d3A="}));
+    }
+
+    #[tokio::test]
+    async fn json_column_strings_render_as_raw_json() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/api/demo/events/_schema");
+                then.status(200).json_body(serde_json::json!({
+                    "code": 0,
+                    "data": {"properties": [
+                        {"name": "id", "type": "BIGINT"},
+                        {"name": "attrs", "type": "JSON"},
+                        {"name": "extra", "type": "VARIANT"},
+                        {"name": "note", "type": "VARCHAR(64)"}
+                    ]}
+                }));
+            })
+            .await;
+        let mut sink = create_mock_sink(&server, 0).await;
+        assert_eq!(sink.load_json_columns().await.unwrap(), 2);
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 1));
+        record.append(DataField::from_chars("attrs", r#"{"k":[1,2]}"#));
+        record.append(DataField::from_chars("extra", "not json"));
+        record.append(DataField::from_chars("note", r#"{"k":1}"#));
+
+        let chunk = single_chunk(&sink, record);
+        let line = std::str::from_utf8(chunk.trim_ascii_end()).unwrap();
+        assert!(line.contains(r#""attrs":{"k":[1,2]}"#), "{line}");
+        let json: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(json["attrs"]["k"], serde_json::json!([1, 2]));
+        assert_eq!(json["extra"], serde_json::json!("not json"));
+        assert_eq!(json["note"], serde_json::json!(r#"{"k":1}"#));
+    }
+
+    #[tokio::test]
+    async fn column_map_renames_json_keys() {
+        let sink = DorisSink::new(test_config())
+            .await
+            .unwrap()
+            .with_column_map([("srcIp", "src_ip")].into_iter().collect());
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("srcIp", "10.0.0.1"));
+        record.append(DataField::from_digit("port", 443));
+
+        let chunk = single_chunk(&sink, record);
+        let json: serde_json::Value = serde_json::from_slice(chunk.trim_ascii_end()).unwrap();
+        assert_eq!(json, serde_json::json!({"src_ip": "10.0.0.1", "port": 443}));
+    }
+
+    #[tokio::test]
+    async fn excluded_field_is_never_serialized() {
+        let sink = DorisSink::new(test_config())
+            .await
+            .unwrap()
+            .with_column_map([("pwd", "raw_password")].into_iter().collect())
+            .with_column_filter(ColumnFilter::default().excluding(["raw_password"]));
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("user", "alice"));
+        record.append(DataField::from_chars("pwd", "s3cret"));
+
+        let chunk = single_chunk(&sink, record);
+        assert!(!String::from_utf8_lossy(&chunk).contains("s3cret"));
+        let json: serde_json::Value = serde_json::from_slice(chunk.trim_ascii_end()).unwrap();
+        assert_eq!(json, serde_json::json!({"user": "alice"}));
+    }
+
+    #[tokio::test]
+    async fn oversized_batch_is_split_by_max_stmt_bytes() {
+        let record = sample_record();
+        let line_len = single_chunk(
+            &DorisSink::new(test_config()).await.unwrap(),
+            record.clone(),
+        )
+        .len();
+
+        let limit = line_len * 3;
+        let sink = DorisSink::new(test_config().with_max_stmt_bytes(Some(limit)))
+            .await
+            .unwrap();
+        let records: Vec<_> = (0..10).map(|_| Arc::new(record.clone())).collect();
+        let chunks = sink
+            .records_to_ndjson_chunks(&sink.target, &records)
+            .unwrap();
+
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= limit));
+        assert_eq!(chunks.last().unwrap().len(), line_len);
+        assert_eq!(
+            chunks.iter().map(Bytes::len).sum::<usize>(),
+            line_len * records.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn chunks_of_one_batch_get_distinct_labels() {
+        let record = sample_record();
+        let line_len = single_chunk(
+            &DorisSink::new(test_config()).await.unwrap(),
+            record.clone(),
+        )
+        .len();
+        let sink = DorisSink::new(test_config().with_max_stmt_bytes(Some(line_len * 3)))
+            .await
+            .unwrap();
+        let records: Vec<_> = (0..10).map(|_| Arc::new(record.clone())).collect();
+        let chunks = sink
+            .records_to_ndjson_chunks(&sink.target, &records)
+            .unwrap();
+        // 前三个分片内容相同
+        assert_eq!(chunks[0], chunks[1]);
+
+        let labels: Vec<_> = (0..chunks.len())
+            .map(|index| sink.generate_label(&sink.target, &chunks, index))
+            .collect();
+        for (i, a) in labels.iter().enumerate() {
+            for b in &labels[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn single_record_over_limit_is_sent_alone() {
+        let sink = DorisSink::new(test_config().with_max_stmt_bytes(Some(1)))
+            .await
+            .unwrap();
+        let records = vec![Arc::new(sample_record()), Arc::new(sample_record())];
+        assert_eq!(
+            sink.records_to_ndjson_chunks(&sink.target, &records)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
     #[tokio::test]
     async fn label_is_deterministic_for_same_payload() {
         let sink = DorisSink::new(test_config()).await.unwrap();
         let record = sample_record();
 
-        let payload = sink.records_to_ndjson(&[Arc::new(record.clone())]).unwrap();
-        let label_a = sink.generate_label(&payload);
-        let payload_again = sink.records_to_ndjson(&[Arc::new(record)]).unwrap();
-        let label_b = sink.generate_label(&payload_again);
+        let payload = single_chunk(&sink, record.clone());
+        let label_a = sink.generate_label(&sink.target, &[payload], 0);
+        let payload_again = single_chunk(&sink, record);
+        let label_b = sink.generate_label(&sink.target, &[payload_again], 0);
 
         assert_eq!(label_a, label_b);
     }
@@ -702,8 +1220,8 @@ mod tests {
         let server = MockServer::start_async().await;
         let mut sink = create_mock_sink(&server, 1).await;
         let record = sample_record();
-        let payload = sink.records_to_ndjson(&[Arc::new(record.clone())]).unwrap();
-        let expected_label = sink.generate_label(&payload);
+        let payload = single_chunk(&sink, record.clone());
+        let expected_label = sink.generate_label(&sink.target, &[payload], 0);
 
         let running_mock = server
             .mock_async(|when, then| {
@@ -799,4 +1317,179 @@ mod tests {
         assert!(err.to_string().contains("partially failed"));
         filtered_mock.assert_calls_async(1).await;
     }
+
+    #[tokio::test]
+    async fn unreachable_first_fe_fails_over_to_next() {
+        let server = MockServer::start_async().await;
+        let load_mock = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/api/demo/events/_stream_load");
+                then.status(200).json_body_obj(&serde_json::json!({
+                    "Status": "Success",
+                    "Message": "",
+                    "NumberTotalRows": 1,
+                    "NumberLoadedRows": 1
+                }));
+            })
+            .await;
+        let endpoint = format!("http://127.0.0.1:1,{}", server.base_url());
+        let mut config = test_config();
+        config.endpoint = endpoint;
+        config.max_retries = 1;
+        let mut sink = DorisSink::new(config).await.unwrap();
+        assert_eq!(sink.base_url(), server.base_url());
+
+        // 当前 FE 不可达时，发送失败后切换到下一个 FE 重试
+        sink.active.store(0, Ordering::Relaxed);
+        sink.sink_records(vec![Arc::new(sample_record())])
+            .await
+            .expect("load via second FE");
+        assert_eq!(sink.base_url(), server.base_url());
+        load_mock.assert_calls_async(1).await;
+
+        sink.active.store(0, Ordering::Relaxed);
+        sink.reconnect().await.unwrap();
+        assert_eq!(sink.base_url(), server.base_url());
+    }
+
+    #[tokio::test]
+    async fn flush_result_increments_db_sink_rows() {
+        use crate::utils::db_metrics::DB_SINK_ROWS;
+
+        let server = MockServer::start_async().await;
+        let mut sink = create_mock_sink(&server, 0).await;
+        sink.target.table = "demo.doris_metrics_t".into();
+        let ok = DB_SINK_ROWS.with_label_values(&["doris", "demo.doris_metrics_t", "ok"]);
+        let err = DB_SINK_ROWS.with_label_values(&["doris", "demo.doris_metrics_t", "err"]);
+        let (ok_before, err_before) = (ok.get(), err.get());
+
+        let success_mock = server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/api/demo/events/_stream_load");
+                then.status(200).json_body_obj(&serde_json::json!({
+                    "Status": "Success",
+                    "Message": "OK"
+                }));
+            })
+            .await;
+        sink.sink_records(vec![Arc::new(sample_record()), Arc::new(sample_record())])
+            .await
+            .expect("stream load succeeds");
+        assert_eq!(ok.get(), ok_before + 2);
+        assert_eq!(err.get(), err_before);
+        success_mock.delete_async().await;
+
+        server
+            .mock_async(|when, then| {
+                when.method(PUT).path("/api/demo/events/_stream_load");
+                then.status(500).json_body_obj(&serde_json::json!({
+                    "Status": "Fail",
+                    "Message": "type mismatch"
+                }));
+            })
+            .await;
+        sink.sink_records(vec![Arc::new(sample_record())])
+            .await
+            .expect_err("stream load fails");
+        assert_eq!(ok.get(), ok_before + 2);
+        assert_eq!(err.get(), err_before + 1);
+    }
+
+    #[tokio::test]
+    async fn routed_records_are_loaded_per_table() {
+        let server = MockServer::start_async().await;
+        let router = TableRouter::new("dest", ["orders", "refunds"]);
+        let mut sink = create_mock_sink(&server, 0).await.with_table_router(router);
+
+        let mock_for = |table: &'static str, id: &'static str| {
+            let server = &server;
+            async move {
+                server
+                    .mock_async(move |when, then| {
+                        when.method(PUT)
+                            .path(format!("/api/demo/{table}/_stream_load"))
+                            .body_includes(id);
+                        then.status(200).json_body_obj(&serde_json::json!({
+                            "Status": "Success",
+                            "Message": "OK"
+                        }));
+                    })
+                    .await
+            }
+        };
+        let orders = mock_for("orders", "\"id\":1").await;
+        let refunds = mock_for("refunds", "\"id\":2").await;
+        let events = mock_for("events", "\"id\":3").await;
+
+        let record = |id: i64, dest: Option<&str>| {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_digit("id", id));
+            if let Some(dest) = dest {
+                record.append(DataField::from_chars("dest", dest));
+            }
+            Arc::new(record)
+        };
+        sink.sink_records(vec![
+            record(1, Some("orders")),
+            record(2, Some("refunds")),
+            record(3, None),
+            record(4, Some("users")),
+        ])
+        .await
+        .expect("all tables load");
+
+        orders.assert_async().await;
+        refunds.assert_async().await;
+        events.assert_async().await;
+
+        // 不同表的相同内容使用不同 label
+        let payload = [Bytes::from_static(b"{}\n")];
+        let routed = sink.target("orders");
+        assert_ne!(
+            sink.generate_label(routed, &payload, 0),
+            sink.generate_label(&sink.target, &payload, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn schema_rejected_records_are_not_loaded() {
+        use crate::utils::db_metrics::DB_SINK_ROWS;
+        use crate::utils::schema::{InvalidAction, RecordValidator};
+
+        let server = MockServer::start_async().await;
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {"name": {"enum": ["alice"]}}
+        });
+        let mut sink = create_mock_sink(&server, 0).await.with_schema(Some(
+            RecordValidator::new("doris", &schema, &InvalidAction::Count).unwrap(),
+        ));
+        sink.target.table = "demo.doris_schema_t".into();
+        let invalid = DB_SINK_ROWS.with_label_values(&["doris", "demo.doris_schema_t", "invalid"]);
+        let ok = DB_SINK_ROWS.with_label_values(&["doris", "demo.doris_schema_t", "ok"]);
+        let (invalid_before, ok_before) = (invalid.get(), ok.get());
+
+        let load_mock = server
+            .mock_async(|when, then| {
+                when.method(PUT)
+                    .path("/api/demo/events/_stream_load")
+                    .body_includes("alice")
+                    .body_excludes("bob");
+                then.status(200).json_body_obj(&serde_json::json!({
+                    "Status": "Success",
+                    "Message": "OK"
+                }));
+            })
+            .await;
+        let mut rejected = DataRecord::default();
+        rejected.append(DataField::from_digit("id", 2));
+        rejected.append(DataField::from_chars("name", "bob"));
+        sink.sink_records(vec![Arc::new(sample_record()), Arc::new(rejected)])
+            .await
+            .expect("valid record is loaded");
+
+        load_mock.assert_calls_async(1).await;
+        assert_eq!(ok.get(), ok_before + 1);
+        assert_eq!(invalid.get(), invalid_before + 1);
+    }
 }
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::utils::compress::RequestCompression;
+use crate::utils::http_client::HttpClientOptions;
+use crate::utils::tls::TlsOptions;
+
 const DEFAULT_TIMEOUT_SECS: u64 = 60;
 const DEFAULT_MAX_RETRIES: i32 = 3;
 const DEFAULT_PROTOCOL: &str = "http";
@@ -24,6 +28,24 @@ pub struct ElasticsearchSinkConfig {
     pub timeout_secs: u64,
     /// 最大重试次数（-1 表示无限重试）
     pub max_retries: i32,
+    /// 作为文档 `_id` 的记录字段名；为空时由 Elasticsearch 自动生成 ID
+    #[serde(default)]
+    pub id_field: Option<String>,
+    /// HTTPS 连接的 TLS 配置（自定义 CA、客户端证书等）
+    #[serde(default)]
+    pub tls: TlsOptions,
+    /// HTTP 客户端超时与连接池配置；`request_timeout_secs` 覆盖 `timeout_secs`
+    #[serde(default)]
+    pub http: HttpClientOptions,
+    /// Bulk 请求体压缩方式；为空时不压缩
+    #[serde(default)]
+    pub compression: Option<RequestCompression>,
+    /// 单次 Bulk 请求的最大文档数；为空时整批一次写入
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+    /// 单次 Bulk 请求体（压缩前）的最大字节数，与 `batch_size` 先到先拆分
+    #[serde(default)]
+    pub flush_bytes: Option<usize>,
 }
 
 impl ElasticsearchSinkConfig {
@@ -64,9 +86,52 @@ impl ElasticsearchSinkConfig {
             password,
             timeout_secs: timeout_secs.unwrap_or(Self::default_timeout_secs()),
             max_retries: max_retries.unwrap_or(Self::default_max_retries()),
+            id_field: None,
+            tls: TlsOptions::default(),
+            http: HttpClientOptions::default(),
+            compression: None,
+            batch_size: None,
+            flush_bytes: None,
         }
     }
 
+    /// 指定 TLS 配置
+    pub fn with_tls(mut self, tls: TlsOptions) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// 指定 HTTP 客户端超时与连接池配置
+    pub fn with_http(mut self, http: HttpClientOptions) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// 指定 Bulk 请求体压缩方式（需服务端开启 `http.compression`）
+    pub fn with_compression(mut self, compression: Option<RequestCompression>) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// 指定单次 Bulk 请求的文档数与字节数上限
+    pub fn with_flush_limits(
+        mut self,
+        batch_size: Option<usize>,
+        flush_bytes: Option<usize>,
+    ) -> Self {
+        self.batch_size = batch_size;
+        self.flush_bytes = flush_bytes;
+        self
+    }
+
+    /// 指定作为文档 `_id` 的记录字段，相同 ID 的文档会被覆盖（幂等写入）
+    pub fn with_id_field(mut self, id_field: Option<String>) -> Self {
+        self.id_field = id_field
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty());
+        self
+    }
+
     /// 获取完整的 endpoint URL
     pub fn endpoint(&self) -> String {
         format!("{}://{}:{}", self.protocol, self.host, self.port)
@@ -119,6 +184,7 @@ mod tests {
         assert_eq!(cfg.index, "test_index");
         assert_eq!(cfg.username, "elastic");
         assert_eq!(cfg.endpoint(), "http://localhost:9200");
+        assert_eq!(cfg.id_field, None);
     }
 
     #[test]
@@ -1,4 +1,10 @@
 use crate::elasticsearch::{ElasticsearchSink, ElasticsearchSinkConfig};
+use crate::spill::{self, SPILL_DIR_PARAM};
+use crate::utils::compress::{COMPRESSION_PARAM, RequestCompression};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::flush_limit::{FLUSH_BYTES_PARAM, parse_flush_bytes};
+use crate::utils::http_client::{HTTP_CLIENT_PARAMS, HttpClientOptions};
+use crate::utils::tls::{TLS_PARAMS, TlsOptions};
 use async_trait::async_trait;
 use serde_json::{Value, json};
 use wp_connector_api::{
@@ -15,6 +21,8 @@ impl SinkFactory for ElasticsearchSinkFactory {
     }
 
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        spill::parse_spill_dir(spec)?;
         ensure_not_empty(spec, "host")?;
         ensure_not_empty(spec, "index")?;
         ensure_not_empty(spec, "username")?;
@@ -42,6 +50,11 @@ impl SinkFactory for ElasticsearchSinkFactory {
         {
             return Err(SinkReason::sink("elasticsearch.timeout_secs must be > 0").into());
         }
+        TlsOptions::from_params("elasticsearch", &spec.params)?;
+        HttpClientOptions::from_params("elasticsearch", &spec.params)?;
+        RequestCompression::from_params("elasticsearch", &spec.params)?;
+        parse_u64_param(spec, &["batch_size"])?;
+        parse_flush_bytes(spec)?;
 
         Ok(())
     }
@@ -55,6 +68,12 @@ impl SinkFactory for ElasticsearchSinkFactory {
         let password = optional_string(spec, "password").unwrap_or_default();
         let timeout_secs: Option<u64> = parse_u64_param(spec, &["timeout_secs", "timeout"])?;
         let max_retries = parse_i32_param(spec, &["max_retries", "retries"])?;
+        let id_field = optional_string(spec, "id_field");
+        let tls = TlsOptions::from_params("elasticsearch", &spec.params)?;
+        let http = HttpClientOptions::from_params("elasticsearch", &spec.params)?;
+        let compression = RequestCompression::from_params("elasticsearch", &spec.params)?;
+        let batch_size = parse_u64_param(spec, &["batch_size"])?.map(|n| n as usize);
+        let flush_bytes = parse_flush_bytes(spec)?;
 
         let cfg = ElasticsearchSinkConfig::new(
             protocol,
@@ -65,7 +84,12 @@ impl SinkFactory for ElasticsearchSinkFactory {
             password,
             timeout_secs,
             max_retries,
-        );
+        )
+        .with_id_field(id_field)
+        .with_tls(tls)
+        .with_http(http)
+        .with_compression(compression)
+        .with_flush_limits(batch_size, flush_bytes);
 
         let sink = ElasticsearchSink::new(cfg).await.map_err(|err| {
             SinkError::from(SinkReason::sink(format!(
@@ -73,7 +97,8 @@ impl SinkFactory for ElasticsearchSinkFactory {
             )))
         })?;
 
-        Ok(SinkHandle::new(Box::new(sink)))
+        let handle = spill::wrap_from_spec(spec, SinkHandle::new(Box::new(sink)))?;
+        decorate_sink(spec, handle)
     }
 }
 
@@ -94,8 +119,16 @@ impl SinkDefProvider for ElasticsearchSinkFactory {
                 "timeout",
                 "max_retries",
                 "retries",
+                "id_field",
+                "batch_size",
+                FLUSH_BYTES_PARAM,
+                COMPRESSION_PARAM,
+                SPILL_DIR_PARAM,
             ]
             .into_iter()
+            .chain(TLS_PARAMS)
+            .chain(HTTP_CLIENT_PARAMS)
+            .chain(DECORATOR_PARAMS)
             .map(str::to_string)
             .collect(),
             default_params: elasticsearch_defaults(),
@@ -257,6 +290,15 @@ mod tests {
         assert!(factory.validate_spec(&spec).is_err());
     }
 
+    #[test]
+    fn validate_rejects_empty_spill_dir() {
+        let mut spec = base_spec();
+        spec.params
+            .insert(SPILL_DIR_PARAM.into(), Value::String(" ".into()));
+        let factory = ElasticsearchSinkFactory;
+        assert!(factory.validate_spec(&spec).is_err());
+    }
+
     #[test]
     fn validate_accepts_minimal_spec() {
         let spec = base_spec();
@@ -55,6 +55,14 @@
 //! - `password`: 认证密码（可选）
 //! - `timeout_secs`: 请求超时时间，默认 60 秒
 //! - `max_retries`: 最大重试次数，默认 3 次，-1 表示无限重试
+//! - `tls_ca_cert` / `tls_client_cert` / `tls_client_key` / `tls_insecure`: HTTPS 连接的 TLS 配置，
+//!   见 [`crate::utils::tls`]
+//! - `request_timeout_secs` / `connect_timeout_secs` / `pool_max_idle_per_host`: HTTP 客户端超时与
+//!   连接池配置，`request_timeout_secs` 覆盖 `timeout_secs`，见 [`crate::utils::http_client`]
+//! - `id_field`: 作为文档 `_id` 的记录字段（可选），相同 ID 覆盖写入以保证幂等；
+//!   未配置或记录缺少该字段时由 Elasticsearch 自动生成 ID
+//! - `batch_size` / `flush_bytes`: 单次 Bulk 请求的最大文档数 / 请求体字节数（可选），
+//!   任一先达到即拆分为下一个请求，用于避开 `http.max_content_length`（默认 100MB）
 //!
 //! # 错误处理
 //!
@@ -9,7 +9,11 @@
 //! - 支持负载均衡和故障转移
 
 use crate::elasticsearch::config::ElasticsearchSinkConfig;
+use crate::utils::compress::RequestCompression;
+use crate::utils::flush_limit::FlushLimits;
 use crate::utils::fmt::{BatchFormat, fmt_bytes};
+use crate::utils::shutdown::DeadlineCtrl;
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
 use crate::utils::time_stat_utils::TimeStatUtils;
 use async_trait::async_trait;
 use reqwest::Client;
@@ -27,13 +31,17 @@ static INSTANCE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 pub struct ElasticsearchSink {
     client: Client,
-    url: String,   // 预先构建的完整 URL
-    index: String, // 索引名称
+    url: String,              // 预先构建的完整 URL
+    index: String,            // 索引名称
+    id_field: Option<String>, // 作为文档 `_id` 的字段名
     username: String,
     password: String,
     max_retries: i32,
-    instance_id: u64,          // 实例唯一 ID
-    time_stats: TimeStatUtils, // 时间统计工具
+    compression: Option<RequestCompression>, // 请求体压缩方式
+    limits: FlushLimits,                     // 单次 Bulk 请求的文档数/字节数上限
+    instance_id: u64,                        // 实例唯一 ID
+    time_stats: TimeStatUtils,               // 时间统计工具
+    counters: SinkCounters,                  // 收发计数
 }
 
 #[derive(Debug, Deserialize)]
@@ -67,9 +75,12 @@ impl ElasticsearchSink {
     /// # Returns
     /// * `anyhow::Result<Self>` - 成功返回初始化后的 sink
     pub async fn new(config: ElasticsearchSinkConfig) -> anyhow::Result<Self> {
-        let client = Client::builder()
+        let builder = Client::builder()
             .timeout(Duration::from_secs(config.timeout_secs))
-            .no_proxy() // 禁用所有代理
+            .no_proxy(); // 禁用所有代理
+        let client = config
+            .http
+            .apply_reqwest(config.tls.apply_reqwest("elasticsearch", builder)?)
             .build()?;
 
         // 预先构建完整的 Bulk API URL
@@ -82,11 +93,15 @@ impl ElasticsearchSink {
             client,
             url,
             index: config.index,
+            id_field: config.id_field,
             username: config.username,
             password: config.password,
             max_retries: config.max_retries,
+            compression: config.compression,
+            limits: FlushLimits::new(config.batch_size, config.flush_bytes),
             instance_id,
             time_stats: TimeStatUtils::new(),
+            counters: SinkCounters::default(),
         })
     }
 
@@ -97,28 +112,68 @@ impl ElasticsearchSink {
     ///
     /// # Returns
     /// * `SinkResult<Vec<u8>>` - NDJSON 字节流
+    #[cfg(test)]
     fn records_to_ndjson(&self, records: &[Arc<DataRecord>]) -> SinkResult<Vec<u8>> {
         let mut ndjson = Vec::new();
-
         for record in records {
-            // 操作行：指定索引操作
-            let action = serde_json::json!({
+            ndjson.extend_from_slice(&self.bulk_entry(record)?);
+        }
+        Ok(ndjson)
+    }
+
+    /// 按 `batch_size` / `flush_bytes` 将批量记录拆分为若干个 Bulk 请求体，附带各请求体的文档数
+    fn records_to_bulk_bodies(
+        &self,
+        records: &[Arc<DataRecord>],
+    ) -> SinkResult<Vec<(usize, Vec<u8>)>> {
+        let entries = records
+            .iter()
+            .map(|record| self.bulk_entry(record))
+            .collect::<SinkResult<Vec<_>>>()?;
+        Ok(self
+            .limits
+            .split(entries, Vec::len)
+            .into_iter()
+            .map(|chunk| (chunk.len(), chunk.concat()))
+            .collect())
+    }
+
+    /// 单条记录的 Bulk 条目：操作行 + 文档行
+    fn bulk_entry(&self, record: &Arc<DataRecord>) -> SinkResult<Vec<u8>> {
+        // 操作行：指定索引操作
+        let action = self.action_line(record);
+        let mut entry = serde_json::to_vec(&action)
+            .map_err(|e| sink_error(format!("json serialization failed for action: {}", e)))?;
+        entry.push(b'\n');
+
+        // 文档行：实际数据
+        let doc_bytes = fmt_bytes(vec![Arc::clone(record)], BatchFormat::Ndjson);
+        entry.extend_from_slice(&doc_bytes);
+        entry.push(b'\n');
+        Ok(entry)
+    }
+
+    /// 构建 Bulk 操作行；配置了 `id_field` 且记录带有该字段时写入 `_id`
+    fn action_line(&self, record: &DataRecord) -> serde_json::Value {
+        let doc_id = self
+            .id_field
+            .as_deref()
+            .and_then(|field| record.get_value(field))
+            .map(|value| value.to_string())
+            .filter(|id| !id.is_empty());
+        match doc_id {
+            Some(id) => serde_json::json!({
+                "index": {
+                    "_index": &self.index,
+                    "_id": id
+                }
+            }),
+            None => serde_json::json!({
                 "index": {
                     "_index": &self.index
                 }
-            });
-            let action_bytes = serde_json::to_vec(&action)
-                .map_err(|e| sink_error(format!("json serialization failed for action: {}", e)))?;
-            ndjson.extend_from_slice(&action_bytes);
-            ndjson.push(b'\n');
-
-            // 文档行：实际数据
-            let doc_bytes = fmt_bytes(vec![Arc::clone(record)], BatchFormat::Ndjson);
-            ndjson.extend_from_slice(&doc_bytes);
-            ndjson.push(b'\n');
+            }),
         }
-
-        Ok(ndjson)
     }
 
     /// 执行 Bulk 请求
@@ -128,7 +183,7 @@ impl ElasticsearchSink {
     ///
     /// # Returns
     /// * `SinkResult<()>` - 成功或错误
-    async fn bulk_request(&self, ndjson: Vec<u8>) -> SinkResult<()> {
+    async fn bulk_request(&self, ndjson: &[u8]) -> SinkResult<()> {
         let mut retries = 0;
         let max_retries = if self.max_retries < 0 {
             i32::MAX
@@ -137,12 +192,15 @@ impl ElasticsearchSink {
         };
 
         loop {
-            let request = self
+            let mut request = self
                 .client
                 .post(&self.url)
                 .basic_auth(&self.username, Some(&self.password))
-                .header("Content-Type", "application/x-ndjson")
-                .body(ndjson.clone());
+                .header("Content-Type", "application/x-ndjson");
+            if let Some(compression) = self.compression {
+                request = request.header("Content-Encoding", compression.content_encoding());
+            }
+            let request = request.body(ndjson.to_vec());
 
             match request.send().await {
                 Ok(response) => {
@@ -197,6 +255,16 @@ impl ElasticsearchSink {
         }
     }
 
+    /// 按配置压缩 Bulk 请求体
+    fn encode_body(&self, ndjson: Vec<u8>) -> SinkResult<Vec<u8>> {
+        match self.compression {
+            Some(compression) => compression
+                .encode(&ndjson)
+                .map_err(|e| sink_error(format!("compress bulk body failed: {}", e))),
+            None => Ok(ndjson),
+        }
+    }
+
     /// 解析 Bulk API 响应
     ///
     /// # Arguments
@@ -253,6 +321,15 @@ impl AsyncCtrl for ElasticsearchSink {
     }
 }
 
+impl SinkMetrics for ElasticsearchSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
+}
+
+// bulk 请求在 `sink_records` 内同步完成，停止时没有待排空的缓冲
+impl DeadlineCtrl for ElasticsearchSink {}
+
 #[async_trait]
 impl AsyncRecordSink for ElasticsearchSink {
     async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
@@ -264,14 +341,24 @@ impl AsyncRecordSink for ElasticsearchSink {
             return Ok(());
         }
 
+        self.counters.receive(data.len());
+
         // 开始统计
         self.time_stats.start_stat(data.len() as u64);
 
-        // 转换为 NDJSON
-        let ndjson = self.records_to_ndjson(&data)?;
-
-        // 发送批量请求
-        self.bulk_request(ndjson).await?;
+        // 转换为 NDJSON，并按阈值拆分后逐个发送批量请求；
+        // 某个请求失败时，该请求及其后未发送的文档都计为失败
+        let mut unsent = data.len();
+        for (docs, ndjson) in self.records_to_bulk_bodies(&data)? {
+            let body = self.encode_body(ndjson)?;
+            let result = self.bulk_request(&body).await;
+            if result.is_err() {
+                self.counters.finish(unsent, 0, &result);
+                return result;
+            }
+            self.counters.finish(docs, body.len(), &result);
+            unsent -= docs;
+        }
 
         // 结束统计
         self.time_stats.end_stat();
@@ -315,3 +402,201 @@ impl AsyncRawDataSink for ElasticsearchSink {
 fn sink_error(msg: impl Into<String>) -> SinkError {
     SinkError::from(SinkReason::Sink(msg.into()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_model_core::model::DataField;
+
+    async fn test_sink(id_field: Option<&str>) -> ElasticsearchSink {
+        let cfg = ElasticsearchSinkConfig::new(
+            None,
+            "localhost".into(),
+            None,
+            "logs".into(),
+            "elastic".into(),
+            "password".into(),
+            None,
+            None,
+        )
+        .with_id_field(id_field.map(str::to_string));
+        ElasticsearchSink::new(cfg).await.expect("build sink")
+    }
+
+    fn first_action(ndjson: &[u8]) -> serde_json::Value {
+        let line = ndjson.split(|b| *b == b'\n').next().unwrap();
+        serde_json::from_slice(line).unwrap()
+    }
+
+    #[tokio::test]
+    async fn action_line_includes_id_from_field() {
+        let sink = test_sink(Some("event_id")).await;
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("event_id", "evt-42"));
+        record.append(DataField::from_chars("message", "hello"));
+
+        let ndjson = sink.records_to_ndjson(&[Arc::new(record)]).unwrap();
+        assert_eq!(
+            first_action(&ndjson),
+            serde_json::json!({"index": {"_index": "logs", "_id": "evt-42"}})
+        );
+    }
+
+    #[tokio::test]
+    async fn bulk_bodies_split_on_batch_size_or_flush_bytes() {
+        let record = |message: String| {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_chars("message", message));
+            Arc::new(record)
+        };
+        let cfg = ElasticsearchSinkConfig::new(
+            None,
+            "localhost".into(),
+            None,
+            "logs".into(),
+            "elastic".into(),
+            "password".into(),
+            None,
+            None,
+        )
+        .with_flush_limits(Some(10), Some(2048));
+        let sink = ElasticsearchSink::new(cfg).await.expect("build sink");
+
+        // 小文档：每 10 条一个请求
+        let small: Vec<_> = (0..25).map(|i| record(i.to_string())).collect();
+        let bodies = sink.records_to_bulk_bodies(&small).unwrap();
+        let docs: Vec<usize> = bodies.iter().map(|(docs, _)| *docs).collect();
+        assert_eq!(docs, vec![10, 10, 5]);
+
+        // 大文档：先达到字节上限
+        let large: Vec<_> = (0..4).map(|_| record("x".repeat(900))).collect();
+        let bodies = sink.records_to_bulk_bodies(&large).unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert!(bodies.iter().all(|(_, b)| b.len() <= 2048));
+        let joined: Vec<u8> = bodies.into_iter().flat_map(|(_, b)| b).collect();
+        assert_eq!(joined, sink.records_to_ndjson(&large).unwrap());
+    }
+
+    #[tokio::test]
+    async fn action_line_omits_id_when_unset_or_missing() {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 7));
+        let records = [Arc::new(record)];
+        let expected = serde_json::json!({"index": {"_index": "logs"}});
+
+        let sink = test_sink(None).await;
+        assert_eq!(
+            first_action(&sink.records_to_ndjson(&records).unwrap()),
+            expected
+        );
+
+        let sink = test_sink(Some("event_id")).await;
+        assert_eq!(
+            first_action(&sink.records_to_ndjson(&records).unwrap()),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn gzip_bulk_body_decompresses_to_ndjson() {
+        use flate2::read::GzDecoder;
+        use httpmock::prelude::*;
+        use std::io::Read;
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("message", "hello"));
+        let records = vec![Arc::new(record)];
+        let expected = test_sink(None).await.records_to_ndjson(&records).unwrap();
+
+        let server = MockServer::start_async().await;
+        let bulk_mock = server
+            .mock_async(move |when, then| {
+                when.method(POST)
+                    .path("/_bulk")
+                    .header("content-encoding", "gzip")
+                    .is_true(move |req| {
+                        let mut body = Vec::new();
+                        GzDecoder::new(&req.body_vec()[..])
+                            .read_to_end(&mut body)
+                            .is_ok()
+                            && body == expected
+                    });
+                then.status(200)
+                    .json_body(serde_json::json!({"errors": false, "items": []}));
+            })
+            .await;
+        let cfg = ElasticsearchSinkConfig::new(
+            None,
+            server.host(),
+            Some(server.port()),
+            "logs".into(),
+            "elastic".into(),
+            "password".into(),
+            None,
+            Some(0),
+        )
+        .with_compression(Some(RequestCompression::Gzip));
+        let mut sink = ElasticsearchSink::new(cfg).await.expect("build sink");
+
+        sink.sink_records(records).await.expect("bulk accepted");
+        bulk_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn sink_metrics_track_flushed_and_failed_records() {
+        use httpmock::prelude::*;
+
+        let record = |message: &str| {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_chars("message", message));
+            Arc::new(record)
+        };
+        let server = MockServer::start_async().await;
+        let ok_mock = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/_bulk").body_includes("keep");
+                then.status(200)
+                    .json_body(serde_json::json!({"errors": false, "items": []}));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/_bulk").body_includes("reject");
+                then.status(400).body("bad request");
+            })
+            .await;
+        let cfg = ElasticsearchSinkConfig::new(
+            None,
+            server.host(),
+            Some(server.port()),
+            "logs".into(),
+            "elastic".into(),
+            "password".into(),
+            None,
+            Some(0),
+        )
+        .with_flush_limits(Some(2), None);
+        let mut sink = ElasticsearchSink::new(cfg).await.expect("build sink");
+        assert_eq!(sink.metrics(), SinkMetricsSnapshot::default());
+
+        let kept: Vec<_> = (0..3).map(|_| record("keep")).collect();
+        let expected_bytes = sink.records_to_ndjson(&kept).unwrap().len();
+        sink.sink_records(kept).await.expect("bulk accepted");
+        assert!(
+            sink.sink_records(vec![record("reject"), record("reject")])
+                .await
+                .is_err()
+        );
+        ok_mock.assert_calls_async(2).await;
+
+        assert_eq!(
+            sink.metrics(),
+            SinkMetricsSnapshot {
+                received: 5,
+                flushed: 3,
+                failed: 2,
+                bytes_sent: expected_bytes as u64,
+            }
+        );
+    }
+}
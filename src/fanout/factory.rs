@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkFactory, SinkHandle,
+    SinkReason, SinkResult, SinkSpec,
+};
+
+use crate::fanout::{FailMode, FanoutSink};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+
+/// Fan-out Sink 工厂；子 Sink 的 `kind` 须是通过 [`FanoutSinkFactory::with_factory`] 注册的工厂
+#[derive(Default, Clone)]
+pub struct FanoutSinkFactory {
+    factories: Vec<Arc<dyn SinkFactory>>,
+}
+
+impl FanoutSinkFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册可作为子 Sink 的工厂；同一 `kind` 以先注册者为准
+    pub fn with_factory(mut self, factory: impl SinkFactory) -> Self {
+        self.factories.push(Arc::new(factory));
+        self
+    }
+
+    fn factory(&self, kind: &str) -> Option<&Arc<dyn SinkFactory>> {
+        self.factories.iter().find(|f| f.kind() == kind)
+    }
+
+    /// 解析 `sinks` 为子 Sink 规格，并检查对应工厂已注册
+    fn child_specs(&self, spec: &SinkSpec) -> SinkResult<Vec<(Arc<dyn SinkFactory>, SinkSpec)>> {
+        let entries = match spec.params.get("sinks") {
+            Some(Value::Array(entries)) if !entries.is_empty() => entries,
+            _ => return Err(SinkReason::sink("fanout.sinks must be a non-empty array").into()),
+        };
+        let mut children = Vec::with_capacity(entries.len());
+        for (idx, entry) in entries.iter().enumerate() {
+            let key = format!("fanout.sinks[{idx}]");
+            let child = nested_sink_spec(spec, &key, entry, idx)?;
+            let factory = self.factory(&child.kind).ok_or_else(|| {
+                SinkReason::sink(format!(
+                    "{key}.kind '{}' is not registered for fanout",
+                    child.kind
+                ))
+            })?;
+            children.push((factory.clone(), child));
+        }
+        Ok(children)
+    }
+}
+
+/// 解析嵌套的子 Sink 规格 `{ kind, name?, params?, filter? }`；`key` 为错误信息中的参数路径，
+/// 未指定 `name` 时取 `{kind}{idx}`，子 Sink 名称前缀为父 Sink 名称
+pub(crate) fn nested_sink_spec(
+    parent: &SinkSpec,
+    key: &str,
+    entry: &Value,
+    idx: usize,
+) -> SinkResult<SinkSpec> {
+    let obj = entry
+        .as_object()
+        .ok_or_else(|| SinkReason::sink(format!("{key} must be an object")))?;
+    let kind = obj
+        .get("kind")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| SinkReason::sink(format!("{key}.kind must not be empty")))?;
+    let name = match obj.get("name") {
+        None => format!("{kind}{idx}"),
+        Some(Value::String(s)) if !s.trim().is_empty() => s.trim().to_string(),
+        Some(_) => {
+            return Err(SinkReason::sink(format!("{key}.name must be a string")).into());
+        }
+    };
+    let params: ParamMap = match obj.get("params") {
+        None => ParamMap::new(),
+        Some(Value::Object(map)) => map.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        Some(_) => {
+            return Err(SinkReason::sink(format!("{key}.params must be an object")).into());
+        }
+    };
+    let filter = match obj.get("filter") {
+        None => None,
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(_) => {
+            return Err(SinkReason::sink(format!("{key}.filter must be a string")).into());
+        }
+    };
+    Ok(SinkSpec {
+        name: format!("{}.{name}", parent.name),
+        kind: kind.to_string(),
+        connector_id: String::new(),
+        group: parent.group.clone(),
+        params,
+        filter,
+    })
+}
+
+fn parse_fail_mode(spec: &SinkSpec) -> SinkResult<FailMode> {
+    match spec.params.get("fail_mode") {
+        None => Ok(FailMode::default()),
+        Some(v) => v.as_str().and_then(FailMode::parse).ok_or_else(|| {
+            SinkReason::sink("fanout.fail_mode must be one of: any, best_effort").into()
+        }),
+    }
+}
+
+#[async_trait]
+impl SinkFactory for FanoutSinkFactory {
+    fn kind(&self) -> &'static str {
+        "fanout"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        parse_fail_mode(spec)?;
+        for (factory, child) in self.child_specs(spec)? {
+            factory.validate_spec(&child)?;
+        }
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let mut sink = FanoutSink::new(parse_fail_mode(spec)?);
+        for (factory, child) in self.child_specs(spec)? {
+            let handle = factory.build(&child, ctx).await?;
+            sink = sink.with_child(child.name, handle.sink);
+        }
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+impl SinkDefProvider for FanoutSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "fanout_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec!["sinks", "fail_mode"]
+                .into_iter()
+                .chain(DECORATOR_PARAMS)
+                .map(str::to_string)
+                .collect(),
+            default_params: fanout_defaults(),
+            origin: Some("wp-connectors:fanout_sink".into()),
+        }
+    }
+}
+
+fn fanout_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("sinks".into(), json!([]));
+    params.insert("fail_mode".into(), json!("any"));
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemorySinkFactory;
+    use wp_model_core::model::{DataField, DataRecord};
+
+    fn spec(sinks: Value) -> SinkSpec {
+        let mut params = fanout_defaults();
+        params.insert("sinks".into(), sinks);
+        SinkSpec {
+            name: "mirror".into(),
+            kind: "fanout".into(),
+            connector_id: String::new(),
+            group: "test".into(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn builds_children_from_nested_specs() {
+        let memory = MemorySinkFactory::new();
+        let factory = FanoutSinkFactory::new().with_factory(memory.clone());
+        let spec = spec(json!([
+            {"kind": "memory", "name": "primary"},
+            {"kind": "memory", "filter": "level == \"error\""},
+        ]));
+        factory.validate_spec(&spec).expect("valid spec");
+
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+        let mut handle = factory.build(&spec, &ctx).await.expect("build");
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("level", "info"));
+        handle.sink.sink_record(&record).await.expect("sink");
+
+        // 第二个子 Sink 按自身 filter 丢弃了该记录
+        assert_eq!(memory.buffer().lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn validate_rejects_bad_children() {
+        let factory = FanoutSinkFactory::new().with_factory(MemorySinkFactory::new());
+        for (sinks, expect) in [
+            (json!([]), "fanout.sinks must be a non-empty array"),
+            (
+                json!([{"kind": "kafka"}]),
+                "fanout.sinks[0].kind 'kafka' is not registered",
+            ),
+            (
+                json!([{"kind": "memory", "params": 1}]),
+                "fanout.sinks[0].params",
+            ),
+        ] {
+            let err = factory.validate_spec(&spec(sinks)).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+
+        let mut bad_mode = spec(json!([{"kind": "memory"}]));
+        bad_mode.params.insert("fail_mode".into(), json!("all"));
+        let err = factory.validate_spec(&bad_mode).expect_err("bad fail_mode");
+        assert!(err.to_string().contains("fanout.fail_mode"));
+    }
+}
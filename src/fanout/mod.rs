@@ -0,0 +1,21 @@
+//! Fan-out sink：将同一数据流镜像写入多个下游 Sink
+//!
+//! 子 Sink 在 `sinks` 参数中以嵌套规格列出，由注册到 [`FanoutSinkFactory`] 的工厂构建：
+//!
+//! ```text
+//! sinks = [
+//!   { kind = "kafka", params = { brokers = "kafka:9092", topic = "wp_events" } },
+//!   { kind = "clickhouse", name = "ck", params = { endpoint = "http://ck:8123", ... } },
+//! ]
+//! fail_mode = "any"   # any | best_effort
+//! ```
+//!
+//! - `any`（默认）：任一子 Sink 失败即返回错误；
+//! - `best_effort`：只要有子 Sink 写入成功即视为成功，失败仅记录日志。
+
+mod factory;
+mod sink;
+
+pub use factory::FanoutSinkFactory;
+pub(crate) use factory::nested_sink_spec;
+pub use sink::{FailMode, FanoutSink};
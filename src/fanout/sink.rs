@@ -0,0 +1,247 @@
+//! Fan-out Sink 实现
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, SinkReason, SinkResult,
+};
+use wp_model_core::model::DataRecord;
+
+/// 把 `$call` 依次作用于每个子 Sink，收集失败信息后按失败模式汇总为 `$op` 的结果
+macro_rules! fan_out {
+    ($self:ident, $op:literal, $child:ident => $call:expr) => {{
+        let mut errors = Vec::new();
+        for $child in &mut $self.children {
+            if let Err(e) = $call.await {
+                errors.push(format!("{}: {e}", $child.name));
+            }
+        }
+        $self.conclude($op, errors)
+    }};
+}
+
+/// 子 Sink 失败时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailMode {
+    /// 任一子 Sink 失败即返回错误
+    ///
+    /// 写入不是原子的：部分子 Sink 失败时，其余子 Sink 已经写入成功。调用方据此重试时
+    /// 会把同一批数据再次写入所有子 Sink，已成功的子 Sink 会收到重复数据。
+    #[default]
+    Any,
+    /// 至少一个子 Sink 成功即返回成功
+    BestEffort,
+}
+
+impl FailMode {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "any" => Some(Self::Any),
+            "best_effort" => Some(Self::BestEffort),
+            _ => None,
+        }
+    }
+}
+
+struct Child {
+    name: String,
+    sink: Box<dyn AsyncSink + 'static>,
+}
+
+/// 将每次写入依次转发给所有子 Sink；某个子 Sink 失败不会跳过后续子 Sink
+pub struct FanoutSink {
+    children: Vec<Child>,
+    fail_mode: FailMode,
+}
+
+impl FanoutSink {
+    pub fn new(fail_mode: FailMode) -> Self {
+        Self {
+            children: Vec::new(),
+            fail_mode,
+        }
+    }
+
+    /// 追加一个子 Sink，`name` 用于错误信息
+    pub fn with_child(
+        mut self,
+        name: impl Into<String>,
+        sink: Box<dyn AsyncSink + 'static>,
+    ) -> Self {
+        self.children.push(Child {
+            name: name.into(),
+            sink,
+        });
+        self
+    }
+
+    /// 按失败模式汇总各子 Sink 的错误
+    fn conclude(&self, op: &str, errors: Vec<String>) -> SinkResult<()> {
+        if errors.is_empty() {
+            return Ok(());
+        }
+        let msg = format!(
+            "fanout {op} failed on {}/{} sinks: {}",
+            errors.len(),
+            self.children.len(),
+            errors.join("; ")
+        );
+        if self.fail_mode == FailMode::BestEffort && errors.len() < self.children.len() {
+            wp_log::warn_data!("{}", msg);
+            return Ok(());
+        }
+        Err(SinkReason::sink(msg).into())
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for FanoutSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        fan_out!(self, "stop", child => child.sink.stop())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        fan_out!(self, "reconnect", child => child.sink.reconnect())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for FanoutSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        fan_out!(self, "sink_record", child => child.sink.sink_record(data))
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        fan_out!(self, "sink_records", child => child.sink.sink_records(data.clone()))
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for FanoutSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        fan_out!(self, "sink_str", child => child.sink.sink_str(data))
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        fan_out!(self, "sink_bytes", child => child.sink.sink_bytes(data))
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        fan_out!(self, "sink_str_batch", child => child.sink.sink_str_batch(data.clone()))
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        fan_out!(self, "sink_bytes_batch", child => child.sink.sink_bytes_batch(data.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryBuffer, MemorySink};
+    use wp_connector_api::SinkError;
+    use wp_model_core::model::DataField;
+
+    /// 所有写入都失败的子 Sink
+    struct FailingSink;
+
+    fn fail() -> SinkResult<()> {
+        Err(SinkError::from(SinkReason::sink("backend down")))
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for FailingSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            fail()
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for FailingSink {
+        async fn sink_record(&mut self, _data: &DataRecord) -> SinkResult<()> {
+            fail()
+        }
+        async fn sink_records(&mut self, _data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            fail()
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for FailingSink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            fail()
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            fail()
+        }
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            fail()
+        }
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            fail()
+        }
+    }
+
+    fn records() -> Vec<Arc<DataRecord>> {
+        (0..2)
+            .map(|i| {
+                let mut record = DataRecord::default();
+                record.append(DataField::from_digit("id", i));
+                Arc::new(record)
+            })
+            .collect()
+    }
+
+    fn fanout(fail_mode: FailMode) -> (FanoutSink, MemoryBuffer, MemoryBuffer) {
+        let (a, b) = (MemorySink::new(), MemorySink::new());
+        let (buf_a, buf_b) = (a.buffer(), b.buffer());
+        let sink = FanoutSink::new(fail_mode)
+            .with_child("a", Box::new(a))
+            .with_child("broken", Box::new(FailingSink))
+            .with_child("b", Box::new(b));
+        (sink, buf_a, buf_b)
+    }
+
+    #[tokio::test]
+    async fn any_mode_fails_but_still_writes_healthy_children() {
+        let (mut sink, buf_a, buf_b) = fanout(FailMode::Any);
+        let err = sink
+            .sink_records(records())
+            .await
+            .expect_err("broken child");
+        assert!(
+            err.to_string()
+                .contains("fanout sink_records failed on 1/3 sinks: broken")
+        );
+        assert_eq!(buf_a.lock().unwrap().len(), 2);
+        assert_eq!(buf_b.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn best_effort_mode_succeeds_while_any_child_succeeds() {
+        let (mut sink, buf_a, buf_b) = fanout(FailMode::BestEffort);
+        sink.sink_records(records()).await.expect("best effort");
+        sink.sink_str("raw").await.expect("best effort raw");
+        assert_eq!(buf_a.lock().unwrap().len(), 3);
+        assert_eq!(buf_b.lock().unwrap().len(), 3);
+
+        let mut all_broken = FanoutSink::new(FailMode::BestEffort)
+            .with_child("x", Box::new(FailingSink))
+            .with_child("y", Box::new(FailingSink));
+        assert!(all_broken.sink_records(records()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn stop_reaches_every_child() {
+        let (mut sink, buf_a, _) = fanout(FailMode::Any);
+        sink.sink_record(&records()[0])
+            .await
+            .expect_err("broken child");
+        sink.stop().await.expect("all children stop");
+        assert_eq!(buf_a.lock().unwrap().len(), 1);
+    }
+}
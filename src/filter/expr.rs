@@ -0,0 +1,225 @@
+//! Sink 过滤表达式解析与求值
+//!
+//! 语法：
+//!
+//! ```text
+//! expr   := and ( "||" and )*
+//! and    := clause ( "&&" clause )*
+//! clause := "has(" field ")" | "!has(" field ")"
+//!         | field "==" literal | field "!=" literal
+//! ```
+//!
+//! `literal` 可以是单/双引号包裹的字符串或不含空白的裸值，比较时与字段值的
+//! 字符串形式逐字匹配。
+
+use wp_model_core::model::DataRecord;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Clause {
+    Has(String),
+    Missing(String),
+    Eq(String, String),
+    Ne(String, String),
+}
+
+impl Clause {
+    fn matches(&self, record: &DataRecord) -> bool {
+        let value_of = |field: &str| record.get_value(field).map(|v| v.to_string());
+        match self {
+            Clause::Has(field) => record.get_value(field).is_some(),
+            Clause::Missing(field) => record.get_value(field).is_none(),
+            Clause::Eq(field, expected) => value_of(field).as_deref() == Some(expected.as_str()),
+            Clause::Ne(field, expected) => value_of(field).as_deref() != Some(expected.as_str()),
+        }
+    }
+}
+
+/// 预解析的过滤表达式（析取范式：若干 `&&` 子句组以 `||` 连接）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterExpr {
+    any_of: Vec<Vec<Clause>>,
+}
+
+impl FilterExpr {
+    /// 解析过滤表达式
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let tokens = tokenize(raw)?;
+        if tokens.is_empty() {
+            return Err("filter expression must not be empty".into());
+        }
+        let mut any_of = Vec::new();
+        for group in tokens.split(|t| *t == Token::Or) {
+            let mut all_of = Vec::new();
+            for clause in group.split(|t| *t == Token::And) {
+                all_of.push(parse_clause(clause, raw)?);
+            }
+            any_of.push(all_of);
+        }
+        Ok(Self { any_of })
+    }
+
+    /// 判断记录是否满足过滤条件
+    pub fn matches(&self, record: &DataRecord) -> bool {
+        self.any_of
+            .iter()
+            .any(|all_of| all_of.iter().all(|clause| clause.matches(record)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Eq,
+    Ne,
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+fn tokenize(raw: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = raw.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(ch) if ch == c => break,
+                        Some(ch) => text.push(ch),
+                        None => return Err(format!("unterminated string in filter '{raw}'")),
+                    }
+                }
+                tokens.push(Token::Quoted(text));
+            }
+            '=' | '!' | '&' | '|' => {
+                chars.next();
+                let next = chars.peek().copied();
+                let token = match (c, next) {
+                    ('=', Some('=')) => Token::Eq,
+                    ('!', Some('=')) => Token::Ne,
+                    ('&', Some('&')) => Token::And,
+                    ('|', Some('|')) => Token::Or,
+                    ('!', _) => {
+                        tokens.push(Token::Not);
+                        continue;
+                    }
+                    _ => return Err(format!("unexpected '{c}' in filter '{raw}'")),
+                };
+                chars.next();
+                tokens.push(token);
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || "()=!&|\"'".contains(ch) {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_clause(tokens: &[Token], raw: &str) -> Result<Clause, String> {
+    let invalid = || format!("invalid filter clause in '{raw}'");
+    match tokens {
+        [
+            Token::Word(f),
+            Token::LParen,
+            Token::Word(field),
+            Token::RParen,
+        ] if f == "has" => Ok(Clause::Has(field.clone())),
+        [
+            Token::Not,
+            Token::Word(f),
+            Token::LParen,
+            Token::Word(field),
+            Token::RParen,
+        ] if f == "has" => Ok(Clause::Missing(field.clone())),
+        [
+            Token::Word(field),
+            op,
+            Token::Word(lit) | Token::Quoted(lit),
+        ] => match op {
+            Token::Eq => Ok(Clause::Eq(field.clone(), lit.clone())),
+            Token::Ne => Ok(Clause::Ne(field.clone(), lit.clone())),
+            _ => Err(invalid()),
+        },
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_model_core::model::DataField;
+
+    fn record() -> DataRecord {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("level", "error"));
+        record.append(DataField::from_chars("service", "api gateway"));
+        record.append(DataField::from_digit("code", 500));
+        record
+    }
+
+    #[test]
+    fn equality_and_presence_predicates() {
+        let rec = record();
+        let cases = [
+            ("level == error", true),
+            ("level == 'warn'", false),
+            ("service == \"api gateway\"", true),
+            ("code == 500", true),
+            ("level != error", false),
+            ("missing != error", true),
+            ("has(level)", true),
+            ("has(missing)", false),
+            ("!has(missing)", true),
+            ("has(level) && code == 404", false),
+            ("level == warn || code == 500", true),
+            ("level == warn || has(missing) && code == 500", false),
+        ];
+        for (expr, expected) in cases {
+            let filter = FilterExpr::parse(expr).unwrap_or_else(|e| panic!("{expr}: {e}"));
+            assert_eq!(filter.matches(&rec), expected, "{expr}");
+        }
+    }
+
+    #[test]
+    fn parse_rejects_malformed_expressions() {
+        for expr in [
+            "",
+            "level",
+            "level = error",
+            "level == ",
+            "has(level",
+            "level == 'open",
+            "&& level == a",
+        ] {
+            assert!(
+                FilterExpr::parse(expr).is_err(),
+                "{expr:?} should be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Sink 记录过滤
+//!
+//! `SinkSpec.filter` 配置过滤表达式后，工厂构建的 Sink 会被 [`FilteredSink`]
+//! 包装，只有满足表达式的记录才会写入下游，例如：
+//!
+//! ```text
+//! level == "error" && has(trace_id)
+//! ```
+//!
+//! 表达式语法见 [`FilterExpr`]。
+
+mod expr;
+mod sink;
+
+pub use expr::FilterExpr;
+pub use sink::FilteredSink;
+
+use wp_connector_api::{SinkHandle, SinkReason, SinkResult, SinkSpec};
+
+/// 解析 `SinkSpec.filter`；未配置或为空白时返回 `None`
+pub fn parse_filter(spec: &SinkSpec) -> SinkResult<Option<FilterExpr>> {
+    let Some(raw) = spec
+        .filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    else {
+        return Ok(None);
+    };
+    FilterExpr::parse(raw)
+        .map(Some)
+        .map_err(|e| SinkReason::sink(format!("{}.filter is invalid: {e}", spec.kind)).into())
+}
+
+/// 按 `SinkSpec.filter` 包装已构建的 Sink；未配置时原样返回
+pub fn wrap_from_spec(spec: &SinkSpec, handle: SinkHandle) -> SinkResult<SinkHandle> {
+    match parse_filter(spec)? {
+        Some(filter) => Ok(SinkHandle::new(Box::new(FilteredSink::new(
+            handle.sink,
+            filter,
+        )))),
+        None => Ok(handle),
+    }
+}
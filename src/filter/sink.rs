@@ -0,0 +1,163 @@
+//! 记录过滤 Sink 实现
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, SinkResult};
+use wp_model_core::model::DataRecord;
+
+use super::FilterExpr;
+
+/// 过滤装饰器：仅将满足 [`FilterExpr`] 的记录交给内部 Sink，其余记录静默丢弃
+///
+/// 原始文本/字节无法求值，直接透传。
+pub struct FilteredSink {
+    inner: Box<dyn AsyncSink + 'static>,
+    filter: FilterExpr,
+}
+
+impl FilteredSink {
+    pub fn new(inner: Box<dyn AsyncSink + 'static>, filter: FilterExpr) -> Self {
+        Self { inner, filter }
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for FilteredSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for FilteredSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        if !self.filter.matches(data) {
+            return Ok(());
+        }
+        self.inner.sink_record(data).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        let kept: Vec<_> = data
+            .into_iter()
+            .filter(|record| self.filter.matches(record))
+            .collect();
+        if kept.is_empty() {
+            return Ok(());
+        }
+        self.inner.sink_records(kept).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for FilteredSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.inner.sink_str(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.inner.sink_bytes(data).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.inner.sink_str_batch(data).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.inner.sink_bytes_batch(data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use wp_model_core::model::DataField;
+
+    #[derive(Default)]
+    struct CollectSink {
+        names: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl CollectSink {
+        fn push(&self, record: &DataRecord) {
+            let name = record
+                .get_value("name")
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            self.names.lock().unwrap().push(name);
+        }
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for CollectSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for CollectSink {
+        async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+            self.push(data);
+            Ok(())
+        }
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            data.iter().for_each(|r| self.push(r));
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for CollectSink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    fn record(name: &str, level: &str) -> DataRecord {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("name", name));
+        record.append(DataField::from_chars("level", level));
+        record
+    }
+
+    #[tokio::test]
+    async fn only_matching_records_reach_inner_sink() {
+        let inner = CollectSink::default();
+        let names = inner.names.clone();
+        let filter = FilterExpr::parse("level == error").unwrap();
+        let mut sink = FilteredSink::new(Box::new(inner), filter);
+
+        sink.sink_record(&record("a", "error")).await.unwrap();
+        sink.sink_record(&record("b", "info")).await.unwrap();
+        sink.sink_records(vec![
+            Arc::new(record("c", "info")),
+            Arc::new(record("d", "error")),
+        ])
+        .await
+        .unwrap();
+        sink.sink_records(vec![Arc::new(record("e", "debug"))])
+            .await
+            .unwrap();
+
+        assert_eq!(*names.lock().unwrap(), vec!["a", "d"]);
+    }
+}
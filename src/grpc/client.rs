@@ -0,0 +1,248 @@
+//! `RecordPush/PushRecords` 一元调用客户端
+//!
+//! 基于 reqwest 的 HTTP/2 连接按 gRPC 协议手工封帧：请求体为 1 字节压缩标志 + 4 字节大端长度 +
+//! protobuf 消息；调用结果取自响应头（trailers-only 响应）或 trailers 中的 `grpc-status`。
+
+use std::time::Duration;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use http_body_util::BodyExt;
+use prost::Message;
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue, TE};
+use wp_connector_api::{SinkError, SinkResult};
+
+use super::config::GrpcSinkConfig;
+use super::proto::{PushRequest, PushResponse};
+use crate::utils::error_kind::ErrorKind;
+
+/// `PushRecords` 的 gRPC 方法路径
+pub const PUSH_RECORDS_PATH: &str = "/wp.push.v1.RecordPush/PushRecords";
+
+const GRPC_CONTENT_TYPE: &str = "application/grpc";
+/// 帧头：压缩标志（1 字节）+ 消息长度（4 字节）
+const FRAME_HEADER_LEN: usize = 5;
+
+pub struct PushClient {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl PushClient {
+    pub fn new(config: &GrpcSinkConfig) -> SinkResult<Self> {
+        let builder = reqwest::Client::builder()
+            .http2_prior_knowledge()
+            .timeout(Duration::from_secs(config.request_timeout_secs));
+        let client = config
+            .tls
+            .apply_reqwest("grpc", builder)?
+            .build()
+            .map_err(|err| {
+                SinkError::from(
+                    ErrorKind::Config.sink("grpc", format!("build grpc client failed: {err}")),
+                )
+            })?;
+        Ok(Self {
+            client,
+            url: format!(
+                "{}{PUSH_RECORDS_PATH}",
+                config.endpoint.trim_end_matches('/')
+            ),
+        })
+    }
+
+    /// 发送一次 `PushRecords` 调用，非 `OK` 状态按 gRPC 状态码映射为错误类别
+    pub async fn push_records(&self, request: &PushRequest) -> SinkResult<PushResponse> {
+        let response = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, GRPC_CONTENT_TYPE)
+            .header(TE, HeaderValue::from_static("trailers"))
+            .body(encode_frame(request))
+            .send()
+            .await
+            .map_err(|err| grpc_error(ErrorKind::Connect, format!("send failed: {err}")))?;
+        if !response.status().is_success() {
+            return Err(grpc_error(
+                ErrorKind::Backend,
+                format!("unexpected http status {}", response.status()),
+            ));
+        }
+        let (parts, body) = http::Response::from(response).into_parts();
+        let collected = body.collect().await.map_err(|err| {
+            grpc_error(ErrorKind::Connect, format!("read response failed: {err}"))
+        })?;
+        let trailers = collected.trailers().cloned().unwrap_or_default();
+        check_status(&parts.headers, &trailers)?;
+        decode_frame(collected.to_bytes())
+    }
+}
+
+fn encode_frame(request: &PushRequest) -> Bytes {
+    let len = request.encoded_len();
+    let mut buf = BytesMut::with_capacity(FRAME_HEADER_LEN + len);
+    buf.put_u8(0);
+    buf.put_u32(len as u32);
+    request
+        .encode(&mut buf)
+        .expect("buffer reserved for the encoded message");
+    buf.freeze()
+}
+
+fn decode_frame(body: Bytes) -> SinkResult<PushResponse> {
+    if body.len() < FRAME_HEADER_LEN {
+        return Err(grpc_error(
+            ErrorKind::Backend,
+            "response is missing the message frame",
+        ));
+    }
+    if body[0] != 0 {
+        return Err(grpc_error(
+            ErrorKind::Backend,
+            "compressed responses are not supported",
+        ));
+    }
+    let len = u32::from_be_bytes([body[1], body[2], body[3], body[4]]) as usize;
+    let message = body
+        .get(FRAME_HEADER_LEN..FRAME_HEADER_LEN + len)
+        .ok_or_else(|| grpc_error(ErrorKind::Backend, "response frame is truncated"))?;
+    PushResponse::decode(message)
+        .map_err(|err| grpc_error(ErrorKind::Backend, format!("decode response failed: {err}")))
+}
+
+/// 检查 `grpc-status`：优先取 trailers，trailers-only 响应则取响应头
+fn check_status(headers: &HeaderMap, trailers: &HeaderMap) -> SinkResult<()> {
+    let source = if trailers.contains_key("grpc-status") {
+        trailers
+    } else {
+        headers
+    };
+    let code = source
+        .get("grpc-status")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u32>().ok())
+        .ok_or_else(|| grpc_error(ErrorKind::Backend, "response is missing grpc-status"))?;
+    if code == 0 {
+        return Ok(());
+    }
+    let message = source
+        .get("grpc-message")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    Err(grpc_error(
+        status_kind(code),
+        format!("PushRecords failed with status {code}: {message}"),
+    ))
+}
+
+/// gRPC 状态码到错误类别的映射
+fn status_kind(code: u32) -> ErrorKind {
+    match code {
+        // DEADLINE_EXCEEDED / UNAVAILABLE
+        4 | 14 => ErrorKind::Connect,
+        // PERMISSION_DENIED / UNAUTHENTICATED
+        7 | 16 => ErrorKind::Auth,
+        // INVALID_ARGUMENT
+        3 => ErrorKind::Serialize,
+        _ => ErrorKind::Backend,
+    }
+}
+
+fn grpc_error(kind: ErrorKind, msg: impl std::fmt::Display) -> SinkError {
+    SinkError::from(kind.sink("grpc", msg))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::grpc::proto::Record;
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+
+    /// 模拟 `RecordPush` 服务：记录收到的请求，按 `status` 返回结果
+    pub(crate) struct MockServer {
+        pub endpoint: String,
+        pub requests: Arc<Mutex<Vec<PushRequest>>>,
+    }
+
+    pub(crate) async fn mock_server(status: u32) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let seen = seen.clone();
+                tokio::spawn(async move {
+                    let mut conn = h2::server::handshake(socket).await.unwrap();
+                    // 连接由 accept 循环驱动，每个调用在独立任务中处理
+                    while let Some(Ok((req, respond))) = conn.accept().await {
+                        tokio::spawn(respond_push(req, respond, status, seen.clone()));
+                    }
+                });
+            }
+        });
+        MockServer { endpoint, requests }
+    }
+
+    async fn respond_push(
+        req: http::Request<h2::RecvStream>,
+        mut respond: h2::server::SendResponse<Bytes>,
+        status: u32,
+        seen: Arc<Mutex<Vec<PushRequest>>>,
+    ) {
+        assert_eq!(req.uri().path(), PUSH_RECORDS_PATH);
+        let mut body = req.into_body();
+        let mut data = BytesMut::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.unwrap();
+            let _ = body.flow_control().release_capacity(chunk.len());
+            data.extend_from_slice(&chunk);
+        }
+        let request = PushRequest::decode(&data[FRAME_HEADER_LEN..]).unwrap();
+        let accepted = request.records.len() as u64;
+        seen.lock().unwrap().push(request);
+
+        let response = http::Response::builder()
+            .header(CONTENT_TYPE, GRPC_CONTENT_TYPE)
+            .body(())
+            .unwrap();
+        let mut stream = respond.send_response(response, false).unwrap();
+        if status == 0 {
+            let frame = encode_response(&PushResponse { accepted });
+            stream.send_data(frame, false).unwrap();
+        }
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", status.into());
+        trailers.insert("grpc-message", HeaderValue::from_static("rejected"));
+        stream.send_trailers(trailers).unwrap();
+    }
+
+    fn encode_response(response: &PushResponse) -> Bytes {
+        let mut buf = BytesMut::new();
+        buf.put_u8(0);
+        buf.put_u32(response.encoded_len() as u32);
+        response.encode(&mut buf).unwrap();
+        buf.freeze()
+    }
+
+    #[tokio::test]
+    async fn push_records_round_trips_through_mock_server() {
+        let server = mock_server(0).await;
+        let client = PushClient::new(&GrpcSinkConfig::new(&server.endpoint)).unwrap();
+        let request = PushRequest {
+            records: vec![Record::default(), Record::default()],
+        };
+        let response = client.push_records(&request).await.unwrap();
+        assert_eq!(response.accepted, 2);
+        assert_eq!(*server.requests.lock().unwrap(), vec![request]);
+
+        let server = mock_server(16).await;
+        let client = PushClient::new(&GrpcSinkConfig::new(&server.endpoint)).unwrap();
+        let err = client
+            .push_records(&PushRequest::default())
+            .await
+            .expect_err("unauthenticated");
+        assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Auth));
+        assert!(err.to_string().contains("status 16: rejected"), "{err}");
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::tls::TlsOptions;
+
+const DEFAULT_BATCH: usize = 500;
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// gRPC Sink 配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrpcSinkConfig {
+    /// 服务地址，`http://`（h2c）或 `https://`（TLS）
+    pub endpoint: String,
+    /// TLS 选项，仅对 `https://` 地址生效
+    #[serde(default)]
+    pub tls: TlsOptions,
+    /// 每个 `PushRecords` 请求携带的记录数上限，缓冲满该数量时发送
+    #[serde(default = "GrpcSinkConfig::default_batch")]
+    pub batch: usize,
+    /// 单次调用超时（秒）
+    #[serde(default = "GrpcSinkConfig::default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+}
+
+impl GrpcSinkConfig {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            tls: TlsOptions::default(),
+            batch: DEFAULT_BATCH,
+            request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+        }
+    }
+
+    pub fn with_tls(mut self, tls: TlsOptions) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    pub fn with_batch(mut self, batch: usize) -> Self {
+        self.batch = batch.max(1);
+        self
+    }
+
+    pub fn with_request_timeout_secs(mut self, secs: u64) -> Self {
+        self.request_timeout_secs = secs.max(1);
+        self
+    }
+
+    pub fn default_batch() -> usize {
+        DEFAULT_BATCH
+    }
+
+    pub fn default_request_timeout_secs() -> u64 {
+        DEFAULT_REQUEST_TIMEOUT_SECS
+    }
+}
@@ -0,0 +1,167 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkFactory, SinkHandle,
+    SinkReason, SinkResult, SinkSpec,
+};
+
+use crate::grpc::{GrpcSink, GrpcSinkConfig};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::tls::{TLS_PARAMS, TlsOptions};
+
+pub struct GrpcSinkFactory;
+
+#[async_trait]
+impl SinkFactory for GrpcSinkFactory {
+    fn kind(&self) -> &'static str {
+        "grpc"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        build_config(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let sink = GrpcSink::new(build_config(spec)?)?;
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+fn build_config(spec: &SinkSpec) -> SinkResult<GrpcSinkConfig> {
+    let endpoint = match spec.params.get("endpoint") {
+        Some(Value::String(s)) if !s.trim().is_empty() => s.trim().to_string(),
+        _ => return Err(SinkReason::sink("grpc.endpoint must not be empty").into()),
+    };
+    let url = reqwest::Url::parse(&endpoint)
+        .map_err(|e| SinkReason::sink(format!("grpc.endpoint '{endpoint}' is invalid: {e}")))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(SinkReason::sink(
+            "grpc.endpoint must be an http:// (h2c) or https:// url with a host",
+        )
+        .into());
+    }
+    if !matches!(url.path(), "" | "/") || url.query().is_some() {
+        return Err(SinkReason::sink(
+            "grpc.endpoint must not contain a path or query; the method path is fixed",
+        )
+        .into());
+    }
+    let tls = TlsOptions::from_params("grpc", &spec.params)?;
+    if tls.is_configured() && url.scheme() != "https" {
+        return Err(SinkReason::sink("grpc.tls_* options require an https:// endpoint").into());
+    }
+    let mut config = GrpcSinkConfig::new(endpoint).with_tls(tls);
+    if let Some(n) = parse_positive(spec, "batch")? {
+        config = config.with_batch(n as usize);
+    }
+    if let Some(n) = parse_positive(spec, "request_timeout_secs")? {
+        config = config.with_request_timeout_secs(n);
+    }
+    Ok(config)
+}
+
+fn parse_positive(spec: &SinkSpec, key: &str) -> SinkResult<Option<u64>> {
+    match spec.params.get(key) {
+        None => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Ok(Some(n)),
+            _ => Err(SinkReason::sink(format!("grpc.{key} must be a positive integer")).into()),
+        },
+    }
+}
+
+impl SinkDefProvider for GrpcSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "grpc_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: ["endpoint", "batch", "request_timeout_secs"]
+                .into_iter()
+                .chain(TLS_PARAMS)
+                .chain(DECORATOR_PARAMS)
+                .map(str::to_string)
+                .collect(),
+            default_params: grpc_defaults(),
+            origin: Some("wp-connectors:grpc_sink".into()),
+        }
+    }
+}
+
+fn grpc_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("endpoint".into(), json!("http://localhost:50051"));
+    params.insert("batch".into(), json!(GrpcSinkConfig::default_batch()));
+    params.insert(
+        "request_timeout_secs".into(),
+        json!(GrpcSinkConfig::default_request_timeout_secs()),
+    );
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(params: ParamMap) -> SinkSpec {
+        SinkSpec {
+            name: "push".into(),
+            kind: "grpc".into(),
+            connector_id: String::new(),
+            group: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn build_config_validates_endpoint_and_batch() {
+        let mut params = grpc_defaults();
+        params.insert("batch".into(), json!(50));
+        let config = build_config(&spec(params.clone())).expect("valid spec");
+        assert_eq!(config.endpoint, "http://localhost:50051");
+        assert_eq!(config.batch, 50);
+
+        for (key, bad, expect) in [
+            ("endpoint", json!(""), "grpc.endpoint must not be empty"),
+            ("endpoint", json!("localhost:50051"), "grpc.endpoint"),
+            (
+                "endpoint",
+                json!("tcp://host:50051"),
+                "http:// (h2c) or https://",
+            ),
+            (
+                "endpoint",
+                json!("http://host:50051/api"),
+                "must not contain a path",
+            ),
+            ("tls_insecure", json!(true), "require an https:// endpoint"),
+            ("batch", json!(0), "grpc.batch"),
+        ] {
+            let mut params = params.clone();
+            params.insert(key.into(), bad);
+            let err = GrpcSinkFactory
+                .validate_spec(&spec(params))
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn build_connects_to_mock_server() {
+        let server = crate::grpc::client::tests::mock_server(0).await;
+        let mut params = grpc_defaults();
+        params.insert("endpoint".into(), json!(server.endpoint));
+        let mut handle = GrpcSinkFactory
+            .build(&spec(params), &SinkBuildCtx::new(std::env::temp_dir()))
+            .await
+            .expect("build grpc sink");
+        let mut record = wp_model_core::model::DataRecord::default();
+        record.append(wp_model_core::model::DataField::from_chars("msg", "hi"));
+        handle.sink.sink_record(&record).await.unwrap();
+        handle.sink.stop().await.unwrap();
+        assert_eq!(server.requests.lock().unwrap().len(), 1);
+    }
+}
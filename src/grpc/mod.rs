@@ -0,0 +1,26 @@
+//! gRPC 记录推送 Sink
+//!
+//! - 服务端实现 `push.proto` 中的 `wp.push.v1.RecordPush/PushRecords` 一元调用；
+//! - 记录在内存中缓冲，每满 `batch` 条发送一个 `PushRequest`，停止时发送剩余缓冲；
+//!   调用失败时记录保留在缓冲中，下次写入或停止时重试；
+//! - `http://` 地址使用 h2c（HTTP/2 明文），`https://` 地址使用 TLS，支持 `tls_*` 参数；
+//! - 客户端为手写的 gRPC 封帧（不依赖 tonic），不支持消息压缩。
+//!
+//! # Example
+//! ```no_run
+//! use wp_connectors::grpc::{GrpcSink, GrpcSinkConfig};
+//!
+//! let config = GrpcSinkConfig::new("http://collector:50051").with_batch(1_000);
+//! let sink = GrpcSink::new(config);
+//! ```
+
+mod client;
+pub mod config;
+mod factory;
+pub mod proto;
+mod sink;
+
+pub use client::{PUSH_RECORDS_PATH, PushClient};
+pub use config::GrpcSinkConfig;
+pub use factory::GrpcSinkFactory;
+pub use sink::GrpcSink;
@@ -0,0 +1,119 @@
+//! `wp.push.v1` 消息类型（对应 `push.proto`，手写 prost 定义，无需构建期代码生成）
+//!
+//! 记录字段按类型映射：chars/symbol -> `str`、digit -> `int`、float -> `float`、
+//! bool -> `bool`、time -> `time_ms`（UTC 毫秒），空值不设置 `value`，其余类型写入字符串形式；
+//! `Ignore` 类型的字段不写入。
+
+use wp_model_core::model::{DataRecord, DataType, Value};
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PushRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub records: Vec<Record>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Record {
+    #[prost(message, repeated, tag = "1")]
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Field {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(oneof = "FieldValue", tags = "2, 3, 4, 5, 6")]
+    pub value: Option<FieldValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum FieldValue {
+    #[prost(string, tag = "2")]
+    Str(String),
+    #[prost(int64, tag = "3")]
+    Int(i64),
+    #[prost(double, tag = "4")]
+    Float(f64),
+    #[prost(bool, tag = "5")]
+    Bool(bool),
+    #[prost(int64, tag = "6")]
+    TimeMs(i64),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PushResponse {
+    /// 服务端接收的记录数
+    #[prost(uint64, tag = "1")]
+    pub accepted: u64,
+}
+
+impl From<&DataRecord> for Record {
+    fn from(record: &DataRecord) -> Self {
+        let fields = record
+            .items
+            .iter()
+            .filter(|field| *field.get_meta() != DataType::Ignore)
+            .map(|field| Field {
+                name: field.get_name().to_string(),
+                value: field_value(field.get_value()),
+            })
+            .collect();
+        Self { fields }
+    }
+}
+
+fn field_value(value: &Value) -> Option<FieldValue> {
+    match value {
+        Value::Null | Value::Ignore(_) => None,
+        Value::Bool(v) => Some(FieldValue::Bool(*v)),
+        Value::Chars(v) => Some(FieldValue::Str(v.to_string())),
+        Value::Symbol(v) => Some(FieldValue::Str(v.to_string())),
+        Value::Digit(v) => Some(FieldValue::Int(*v)),
+        Value::Float(v) => Some(FieldValue::Float(*v)),
+        Value::Time(v) => Some(FieldValue::TimeMs(v.and_utc().timestamp_millis())),
+        other => Some(FieldValue::Str(other.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use prost::Message;
+    use wp_model_core::model::DataField;
+
+    #[test]
+    fn record_converts_to_proto_fields() {
+        let time = NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap();
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("host", "web-1"));
+        record.append(DataField::from_digit("status", 200));
+        record.append(DataField::from_float("latency", 0.25));
+        record.append(DataField::from_bool("ok", true));
+        record.append(DataField::from_time("ts", time));
+        record.append(DataField::from_ignore("skip"));
+
+        let proto = Record::from(&record);
+        let expected = [
+            ("host", Some(FieldValue::Str("web-1".into()))),
+            ("status", Some(FieldValue::Int(200))),
+            ("latency", Some(FieldValue::Float(0.25))),
+            ("ok", Some(FieldValue::Bool(true))),
+            ("ts", Some(FieldValue::TimeMs(1_714_550_400_000))),
+        ]
+        .map(|(name, value)| Field {
+            name: name.into(),
+            value,
+        });
+        assert_eq!(proto.fields, expected);
+
+        let request = PushRequest {
+            records: vec![proto],
+        };
+        let decoded = PushRequest::decode(request.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, request);
+    }
+}
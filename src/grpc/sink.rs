@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use prost::Message;
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
+use wp_model_core::model::DataRecord;
+
+use super::client::PushClient;
+use super::config::GrpcSinkConfig;
+use super::proto::{PushRequest, Record};
+use crate::utils::shutdown::DeadlineCtrl;
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
+
+/// gRPC Sink 实现，缓冲记录并按 `batch` 条一次 `PushRecords` 调用发送
+pub struct GrpcSink {
+    config: GrpcSinkConfig,
+    client: PushClient,
+    pending: Vec<Record>,
+    stopped: bool,
+    counters: SinkCounters,
+}
+
+impl GrpcSink {
+    pub fn new(config: GrpcSinkConfig) -> SinkResult<Self> {
+        let client = PushClient::new(&config)?;
+        Ok(Self {
+            config,
+            client,
+            pending: Vec::new(),
+            stopped: false,
+            counters: SinkCounters::default(),
+        })
+    }
+
+    /// 发送缓冲中的记录；`all` 为 false 时只发送满 `batch` 的部分。
+    /// 调用失败时该批及其后的记录保留在缓冲中，下次写入或停止时重试
+    async fn flush(&mut self, all: bool) -> SinkResult<()> {
+        while self.pending.len() >= self.config.batch || (all && !self.pending.is_empty()) {
+            let n = self.pending.len().min(self.config.batch);
+            let request = PushRequest {
+                records: self.pending[..n].to_vec(),
+            };
+            let bytes = request.encoded_len();
+            let result = self.client.push_records(&request).await;
+            self.counters.finish(n, bytes, &result);
+            result?;
+            self.pending.drain(..n);
+        }
+        Ok(())
+    }
+
+    fn ensure_running(&self) -> SinkResult<()> {
+        if self.stopped {
+            return Err(sink_error("grpc sink is stopped"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for GrpcSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        if self.stopped {
+            return Ok(());
+        }
+        self.flush(true).await?;
+        self.stopped = true;
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+// 默认的 `stop_with_deadline` 即可：`stop` 会发送剩余缓冲
+impl DeadlineCtrl for GrpcSink {}
+
+impl SinkMetrics for GrpcSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for GrpcSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.ensure_running()?;
+        self.counters.receive(1);
+        self.pending.push(Record::from(data));
+        self.flush(false).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.ensure_running()?;
+        self.counters.receive(data.len());
+        self.pending
+            .extend(data.iter().map(|record| Record::from(record.as_ref())));
+        self.flush(false).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for GrpcSink {
+    async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+        Err(sink_error("grpc sink does not accept raw input"))
+    }
+
+    async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+        Err(sink_error("grpc sink does not accept raw bytes"))
+    }
+
+    async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+        Err(sink_error("grpc sink does not accept raw input"))
+    }
+
+    async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+        Err(sink_error("grpc sink does not accept raw bytes"))
+    }
+}
+
+fn sink_error(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::client::tests::mock_server;
+    use wp_model_core::model::DataField;
+
+    fn record(id: i64) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", id));
+        Arc::new(record)
+    }
+
+    #[tokio::test]
+    async fn batches_records_and_flushes_on_stop() {
+        let server = mock_server(0).await;
+        let mut sink = GrpcSink::new(GrpcSinkConfig::new(&server.endpoint).with_batch(2)).unwrap();
+
+        sink.sink_records((1..=5).map(record).collect())
+            .await
+            .unwrap();
+        let sizes: Vec<usize> = server
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.records.len())
+            .collect();
+        assert_eq!(sizes, vec![2, 2]);
+
+        sink.stop().await.unwrap();
+        let requests = server.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].records, vec![Record::from(record(5).as_ref())]);
+        assert!(sink.sink_record(&record(6)).await.is_err());
+        assert_eq!(sink.metrics().flushed, 5);
+    }
+
+    #[tokio::test]
+    async fn failed_push_keeps_records_buffered() {
+        let server = mock_server(14).await;
+        let mut sink = GrpcSink::new(GrpcSinkConfig::new(&server.endpoint).with_batch(2)).unwrap();
+
+        sink.sink_records(vec![record(1)]).await.unwrap();
+        assert!(server.requests.lock().unwrap().is_empty());
+        let err = sink.stop().await.expect_err("unavailable");
+        assert!(err.to_string().contains("[grpc:connect]"), "{err}");
+        assert_eq!(sink.pending.len(), 1);
+    }
+}
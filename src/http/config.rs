@@ -1,7 +1,10 @@
 /// HTTP Sink configuration structure
 ///
 /// This module defines the configuration parameters for the HTTP Sink.
+use crate::utils::envelope::Envelope;
+use crate::utils::http_retry::HttpRetry;
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// Configuration for HTTP Sink
 ///
@@ -36,8 +39,17 @@ pub struct HttpSinkConfig {
     /// Maximum retry attempts (-1 for infinite, 0 for no retry, default: 3)
     pub max_retries: i32,
 
+    /// Delay before the first retry in milliseconds, doubled per retry (default: 1000)
+    pub retry_backoff_ms: u64,
+
+    /// Wait as long as the server's `Retry-After` header asks (default: true)
+    pub respect_retry_after: bool,
+
     /// Compression algorithm (none, gzip)
     pub compression: String,
+
+    /// Optional envelope template applied to every formatted record
+    pub envelope: Option<Envelope>,
 }
 
 impl HttpSinkConfig {
@@ -78,10 +90,35 @@ impl HttpSinkConfig {
             batch_size: batch_size.unwrap_or_else(Self::default_batch_size),
             timeout_secs: timeout_secs.unwrap_or_else(Self::default_timeout_secs),
             max_retries: max_retries.unwrap_or_else(Self::default_max_retries),
+            retry_backoff_ms: Self::default_retry_backoff_ms(),
+            respect_retry_after: true,
             compression: compression.unwrap_or_else(Self::default_compression),
+            envelope: None,
         }
     }
 
+    /// Wrap every formatted record with the given envelope template
+    pub fn with_envelope(mut self, envelope: Option<Envelope>) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    /// Override the retry backoff and `Retry-After` handling
+    pub fn with_retry_backoff(mut self, retry_backoff_ms: u64, respect_retry_after: bool) -> Self {
+        self.retry_backoff_ms = retry_backoff_ms;
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    /// Retry budget applied to every request
+    pub fn retry(&self) -> HttpRetry {
+        HttpRetry::new(
+            self.max_retries.into(),
+            Duration::from_millis(self.retry_backoff_ms),
+            self.respect_retry_after,
+        )
+    }
+
     /// Default HTTP method
     pub fn default_method() -> String {
         "POST".to_string()
@@ -107,6 +144,20 @@ impl HttpSinkConfig {
         3
     }
 
+    /// Default delay before the first retry in milliseconds
+    pub fn default_retry_backoff_ms() -> u64 {
+        1000
+    }
+
+    /// Default retry budget, used when the spec leaves retry params unset
+    pub fn default_retry() -> HttpRetry {
+        HttpRetry::new(
+            Self::default_max_retries().into(),
+            Duration::from_millis(Self::default_retry_backoff_ms()),
+            true,
+        )
+    }
+
     /// Default compression algorithm
     pub fn default_compression() -> String {
         "none".to_string()
@@ -141,6 +192,9 @@ mod tests {
         assert_eq!(cfg.batch_size, 1);
         assert_eq!(cfg.timeout_secs, 60);
         assert_eq!(cfg.max_retries, 3);
+        assert_eq!(cfg.retry_backoff_ms, 1000);
+        assert!(cfg.respect_retry_after);
+        assert_eq!(cfg.retry(), HttpSinkConfig::default_retry());
         assert_eq!(cfg.compression, "none");
     }
 
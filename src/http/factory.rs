@@ -1,4 +1,8 @@
 use crate::http::{HttpSink, HttpSinkConfig};
+use crate::spill::{self, SPILL_DIR_PARAM};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::envelope::{ENVELOPE_PARAMS, Envelope};
+use crate::utils::http_retry::{HTTP_RETRY_PARAMS, HttpRetry};
 use async_trait::async_trait;
 use serde_json::{Value, json};
 use std::collections::HashMap;
@@ -20,6 +24,8 @@ impl SinkFactory for HttpSinkFactory {
     }
 
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        spill::parse_spill_dir(spec)?;
         // Validate endpoint
         let endpoint = required_string(spec, "endpoint")?;
         validate_url_scheme(&endpoint)?;
@@ -46,6 +52,16 @@ impl SinkFactory for HttpSinkFactory {
             validate_compression(&compression)?;
         }
 
+        // Validate retry budget
+        HttpRetry::from_params("http", &spec.params, HttpSinkConfig::default_retry())?;
+
+        // Validate envelope; csv carries a header row and cannot be wrapped per record
+        if Envelope::from_params("http", &spec.params)?.is_some()
+            && optional_string(spec, "fmt").as_deref() == Some("csv")
+        {
+            return Err(SinkReason::sink("http.envelope is not supported with fmt = csv").into());
+        }
+
         Ok(())
     }
 
@@ -58,8 +74,10 @@ impl SinkFactory for HttpSinkFactory {
         let fmt = optional_string(spec, "fmt");
         let batch_size = get_usize(spec, "batch_size");
         let timeout_secs = get_u64(spec, "timeout_secs");
-        let max_retries = get_i32(spec, "max_retries");
+        let retry = HttpRetry::from_params("http", &spec.params, HttpSinkConfig::default_retry())?;
+        let max_retries = i32::try_from(retry.max_retries).unwrap_or(i32::MAX);
         let compression = optional_string(spec, "compression");
+        let envelope = Envelope::from_params("http", &spec.params)?;
 
         let config = HttpSinkConfig::new(
             endpoint,
@@ -70,15 +88,21 @@ impl SinkFactory for HttpSinkFactory {
             fmt,
             batch_size,
             timeout_secs,
-            max_retries,
+            Some(max_retries),
             compression,
-        );
+        )
+        .with_retry_backoff(
+            u64::try_from(retry.backoff.as_millis()).unwrap_or(u64::MAX),
+            retry.respect_retry_after,
+        )
+        .with_envelope(envelope);
 
         let sink = HttpSink::new(config).await.map_err(|err| {
             SinkError::from(SinkReason::sink(format!("init http sink failed: {err}")))
         })?;
 
-        Ok(SinkHandle::new(Box::new(sink)))
+        let handle = spill::wrap_from_spec(spec, SinkHandle::new(Box::new(sink)))?;
+        decorate_sink(spec, handle)
     }
 }
 
@@ -97,10 +121,13 @@ impl SinkDefProvider for HttpSinkFactory {
                 "fmt",
                 "batch_size",
                 "timeout_secs",
-                "max_retries",
                 "compression",
+                SPILL_DIR_PARAM,
             ]
             .into_iter()
+            .chain(HTTP_RETRY_PARAMS)
+            .chain(ENVELOPE_PARAMS)
+            .chain(DECORATOR_PARAMS)
             .map(str::to_string)
             .collect(),
             default_params: http_sink_defaults(),
@@ -184,14 +211,6 @@ fn get_usize(spec: &SinkSpec, key: &str) -> Option<usize> {
         .map(|v| v as usize)
 }
 
-/// Read i32 parameter
-fn get_i32(spec: &SinkSpec, key: &str) -> Option<i32> {
-    spec.params
-        .get(key)
-        .and_then(Value::as_i64)
-        .map(|v| v as i32)
-}
-
 /// Parse headers from spec parameters
 fn parse_headers(spec: &SinkSpec) -> SinkResult<Option<HashMap<String, String>>> {
     match spec.params.get("headers") {
@@ -234,6 +253,11 @@ fn http_sink_defaults() -> ParamMap {
         "max_retries".into(),
         json!(HttpSinkConfig::default_max_retries()),
     );
+    params.insert(
+        "retry_backoff_ms".into(),
+        json!(HttpSinkConfig::default_retry_backoff_ms()),
+    );
+    params.insert("respect_retry_after".into(), json!(true));
     params.insert(
         "compression".into(),
         json!(HttpSinkConfig::default_compression()),
@@ -401,6 +425,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn validate_envelope_rejects_csv_and_missing_data() {
+        let factory = HttpSinkFactory;
+        let mut spec = base_spec();
+        spec.params
+            .insert("envelope".into(), Value::String("{\"ts\":{ts}}".into()));
+        let err = factory.validate_spec(&spec).expect_err("no data slot");
+        assert!(err.to_string().contains("http.envelope"), "{err}");
+
+        spec.params.insert(
+            "envelope".into(),
+            Value::String("{\"ts\":{ts},\"data\":{data}}".into()),
+        );
+        assert!(factory.validate_spec(&spec).is_ok());
+
+        spec.params
+            .insert("fmt".into(), Value::String("csv".into()));
+        let err = factory.validate_spec(&spec).expect_err("csv with envelope");
+        assert!(err.to_string().contains("fmt = csv"), "{err}");
+    }
+
+    #[test]
+    fn validate_checks_retry_params() {
+        let factory = HttpSinkFactory;
+        let mut spec = base_spec();
+        spec.params.insert("max_retries".into(), json!(-1));
+        spec.params.insert("retry_backoff_ms".into(), json!(250));
+        spec.params
+            .insert("respect_retry_after".into(), json!(false));
+        assert!(factory.validate_spec(&spec).is_ok());
+
+        for (key, bad) in [
+            ("max_retries", json!(-2)),
+            ("retry_backoff_ms", json!(0)),
+            ("respect_retry_after", json!("no")),
+        ] {
+            let mut spec = spec.clone();
+            spec.params.insert(key.into(), bad);
+            let err = factory.validate_spec(&spec).expect_err(key);
+            assert!(err.to_string().contains(&format!("http.{key}")), "{err}");
+        }
+    }
+
     #[test]
     fn validate_rejects_unsupported_compression() {
         let mut spec = base_spec();
@@ -35,6 +35,8 @@
 //! - **batch_size**: Number of records per request (default: `1`)
 //! - **timeout_secs**: Request timeout in seconds (default: `60`)
 //! - **max_retries**: Maximum retry attempts (default: `3`, use `-1` for infinite)
+//! - **retry_backoff_ms**: Delay before the first retry, doubled per retry (default: `1000`)
+//! - **respect_retry_after**: Wait as long as a `Retry-After` header asks (default: `true`)
 //! - **compression**: Compression algorithm (default: `none`)
 //!   - Supported: none, gzip
 //!
@@ -191,20 +193,21 @@
 //!
 //! The following errors trigger automatic retry with exponential backoff:
 //! - HTTP 5xx status codes (server errors)
-//! - HTTP 408 (Request Timeout)
 //! - HTTP 429 (Too Many Requests)
 //! - Network errors (connection refused, DNS failure, timeout)
 //!
 //! ## Non-Retryable Errors
 //!
 //! The following errors fail immediately without retry:
-//! - HTTP 4xx status codes (client errors, except 408 and 429)
+//! - HTTP 4xx status codes (client errors, except 429)
 //! - Invalid configuration
 //! - Data formatting errors
 //!
 //! ## Retry Strategy
 //!
-//! - **Exponential Backoff**: Delay = 1000ms × 2^(attempt), capped at 1024 seconds
+//! - **Exponential Backoff**: Delay = `retry_backoff_ms` × 2^(attempt), capped at 60 seconds
+//! - **Retry-After**: A `Retry-After` header (seconds or HTTP date) replaces the backoff,
+//!   also capped at 60 seconds; set `respect_retry_after = false` to ignore it
 //! - **Configurable Limit**: Set `max_retries` to control retry attempts
 //! - **Infinite Retry**: Set `max_retries = -1` for unlimited retries
 //! - **Non-Blocking**: Uses async sleep, doesn't block other operations
@@ -225,12 +228,16 @@
 
 mod config;
 mod factory;
+mod pull_source;
+mod pull_source_factory;
 mod sink;
 mod source;
 mod source_factory;
 
 pub use config::HttpSinkConfig;
 pub use factory::HttpSinkFactory;
+pub use pull_source::{HttpPullMode, HttpPullSource, HttpPullSourceConfig};
+pub use pull_source_factory::HttpPullSourceFactory;
 pub use sink::HttpSink;
 pub use source::{HttpSource, HttpSourceConfig};
 pub use source_factory::HttpSourceFactory;
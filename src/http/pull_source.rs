@@ -0,0 +1,351 @@
+//! HTTP 拉取 Source
+//!
+//! 与被动接收推送的 [`HttpSource`](super::HttpSource) 不同，该 Source 主动访问远端 URL：
+//! - `poll`：按 `interval_ms` 周期请求（支持长轮询），每个非空响应体作为一条事件；
+//! - `sse`：保持 `text/event-stream` 长连接，每个 SSE 事件的 `data` 作为一条事件。
+//!
+//! 两种模式下连接断开或请求失败时返回 `Disconnect`，下一次 `receive` 间隔 `interval_ms` 后重连。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use reqwest::header::{ACCEPT, HeaderMap, HeaderName, HeaderValue};
+use wp_conf_base::ConfParser;
+use wp_connector_api::{DataSource, SourceBatch, SourceEvent, SourceReason, SourceResult, Tags};
+use wp_model_core::event_id::next_wp_event_id;
+use wp_model_core::raw::RawData;
+
+use crate::WP_SRC_VAL;
+
+const DEFAULT_INTERVAL_MS: u64 = 1000;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 拉取模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpPullMode {
+    Poll,
+    Sse,
+}
+
+impl HttpPullMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "poll" => Some(Self::Poll),
+            "sse" => Some(Self::Sse),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Poll => "poll",
+            Self::Sse => "sse",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpPullSourceConfig {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub mode: HttpPullMode,
+    pub interval_ms: u64,
+}
+
+impl HttpPullSourceConfig {
+    pub fn default_interval_ms() -> u64 {
+        DEFAULT_INTERVAL_MS
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+}
+
+pub struct HttpPullSource {
+    key: String,
+    tags: Arc<Tags>,
+    config: HttpPullSourceConfig,
+    client: reqwest::Client,
+    // 首次请求立即发出，之后每次请求（或重连）前等待 interval
+    started: bool,
+    stream: Option<SseStream>,
+}
+
+struct SseStream {
+    response: reqwest::Response,
+    parser: SseParser,
+}
+
+impl HttpPullSource {
+    pub fn new(key: String, tags: Tags, config: HttpPullSourceConfig) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .default_headers(build_headers(&config.headers)?)
+            .build()?;
+        Ok(Self {
+            key,
+            tags: Arc::new(tags),
+            config,
+            client,
+            started: false,
+            stream: None,
+        })
+    }
+
+    async fn wait_turn(&mut self) {
+        if self.started && self.config.interval_ms > 0 {
+            tokio::time::sleep(self.config.interval()).await;
+        }
+        self.started = true;
+    }
+
+    async fn request(&self, accept: Option<&'static str>) -> SourceResult<reqwest::Response> {
+        let mut req = self.client.get(&self.config.url);
+        if let Some(accept) = accept {
+            req = req.header(ACCEPT, accept);
+        }
+        let response = req.send().await.map_err(|err| {
+            SourceReason::Disconnect(format!(
+                "http_pull request {} failed: {err}",
+                self.config.url
+            ))
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SourceReason::SupplierError(format!(
+                "http_pull {} responded with status {status}",
+                self.config.url
+            ))
+            .into());
+        }
+        Ok(response)
+    }
+
+    async fn poll_once(&mut self) -> SourceResult<SourceBatch> {
+        loop {
+            self.wait_turn().await;
+            let body = self.request(None).await?.bytes().await.map_err(|err| {
+                SourceReason::Disconnect(format!("http_pull read body failed: {err}"))
+            })?;
+            // 长轮询超时常以空响应结束，此时不产出事件，继续下一轮
+            if !body.is_empty() {
+                return Ok(self.build_batch(vec![body]));
+            }
+        }
+    }
+
+    async fn next_sse_events(&mut self) -> SourceResult<SourceBatch> {
+        loop {
+            if self.stream.is_none() {
+                self.wait_turn().await;
+                let response = self.request(Some("text/event-stream")).await?;
+                self.stream = Some(SseStream {
+                    response,
+                    parser: SseParser::default(),
+                });
+            }
+            let Some(stream) = self.stream.as_mut() else {
+                continue;
+            };
+            match stream.response.chunk().await {
+                Ok(Some(chunk)) => {
+                    let events = stream.parser.feed(&chunk);
+                    if !events.is_empty() {
+                        return Ok(self.build_batch(events));
+                    }
+                }
+                // 服务端正常结束流：丢弃连接，下一轮按 interval 重连
+                Ok(None) => self.stream = None,
+                Err(err) => {
+                    self.stream = None;
+                    return Err(SourceReason::Disconnect(format!(
+                        "http_pull sse stream {} broken: {err}",
+                        self.config.url
+                    ))
+                    .into());
+                }
+            }
+        }
+    }
+
+    fn build_batch(&self, payloads: Vec<Bytes>) -> SourceBatch {
+        payloads
+            .into_iter()
+            .map(|payload| {
+                SourceEvent::new(
+                    next_wp_event_id(),
+                    self.key.as_str(),
+                    RawData::Bytes(payload),
+                    self.tags.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl DataSource for HttpPullSource {
+    async fn receive(&mut self) -> SourceResult<SourceBatch> {
+        match self.config.mode {
+            HttpPullMode::Poll => self.poll_once().await,
+            HttpPullMode::Sse => self.next_sse_events().await,
+        }
+    }
+
+    fn try_receive(&mut self) -> Option<SourceBatch> {
+        None
+    }
+
+    fn identifier(&self) -> String {
+        self.key.clone()
+    }
+
+    async fn close(&mut self) -> SourceResult<()> {
+        self.stream = None;
+        Ok(())
+    }
+}
+
+fn build_headers(headers: &[(String, String)]) -> anyhow::Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        map.insert(
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(value)?,
+        );
+    }
+    Ok(map)
+}
+
+pub fn build_pull_source_tags(tags: &[String], config: &HttpPullSourceConfig) -> Tags {
+    let mut meta_tags = Tags::from_parse(tags);
+    meta_tags.set(WP_SRC_VAL, config.url.clone());
+    meta_tags
+}
+
+/// 增量解析 `text/event-stream`；只关心 `data` 字段，空行触发一次事件分发
+#[derive(Default)]
+struct SseParser {
+    pending: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8]) -> Vec<Bytes> {
+        self.pending.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(pos) = self.pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(Bytes::from(self.data.join("\n")));
+                    self.data.clear();
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            if field == "data" {
+                self.data
+                    .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn payloads(batch: &SourceBatch) -> Vec<String> {
+        batch
+            .iter()
+            .map(|event| {
+                String::from_utf8(event.payload.clone().into_bytes().to_vec()).expect("utf8")
+            })
+            .collect()
+    }
+
+    /// 极简 HTTP 服务端：对每个连接返回固定的响应头与响应体
+    async fn start_mock_server(content_type: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let resp = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(resp.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        format!("http://{addr}/events")
+    }
+
+    fn config(url: String, mode: HttpPullMode) -> HttpPullSourceConfig {
+        HttpPullSourceConfig {
+            url,
+            headers: vec![("X-Token".into(), "secret".into())],
+            mode,
+            interval_ms: 10,
+        }
+    }
+
+    #[test]
+    fn sse_parser_joins_data_lines_and_skips_comments() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed(b": keep-alive\ndata: a").is_empty());
+        let events = parser.feed(b"\ndata: b\r\n\r\nevent: x\ndata:c\n\n");
+        assert_eq!(
+            events,
+            vec![Bytes::from_static(b"a\nb"), Bytes::from_static(b"c")]
+        );
+    }
+
+    #[tokio::test]
+    async fn sse_mode_emits_each_event() {
+        let url = start_mock_server(
+            "text/event-stream",
+            "data: {\"id\":1}\n\ndata: {\"id\":2}\n\n",
+        )
+        .await;
+        let conf = config(url.clone(), HttpPullMode::Sse);
+        let tags = build_pull_source_tags(&[], &conf);
+        let mut source = HttpPullSource::new("sse".into(), tags, conf).expect("build source");
+
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            let batch = source.receive().await.expect("receive sse events");
+            assert_eq!(batch[0].tags.get(WP_SRC_VAL), Some(url.as_str()));
+            received.extend(payloads(&batch));
+        }
+        assert_eq!(received, vec![r#"{"id":1}"#, r#"{"id":2}"#]);
+        source.close().await.expect("close");
+    }
+
+    #[tokio::test]
+    async fn poll_mode_emits_response_body_per_request() {
+        let url = start_mock_server("application/json", r#"{"ok":true}"#).await;
+        let conf = config(url, HttpPullMode::Poll);
+        let mut source =
+            HttpPullSource::new("poll".into(), Tags::new(), conf).expect("build source");
+
+        for _ in 0..2 {
+            let batch = source.receive().await.expect("poll response");
+            assert_eq!(payloads(&batch), vec![r#"{"ok":true}"#]);
+        }
+    }
+}
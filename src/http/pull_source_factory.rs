@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SourceBuildCtx, SourceDefProvider, SourceFactory,
+    SourceHandle, SourceMeta, SourceReason, SourceResult, SourceSpec, SourceSvcIns,
+};
+
+use crate::http::pull_source::{
+    HttpPullMode, HttpPullSource, HttpPullSourceConfig, build_pull_source_tags,
+};
+
+/// 主动拉取 HTTP/SSE 端点的 Source 工厂（kind = `http_pull`）
+pub struct HttpPullSourceFactory;
+
+#[async_trait]
+impl SourceFactory for HttpPullSourceFactory {
+    fn kind(&self) -> &'static str {
+        "http_pull"
+    }
+
+    fn validate_spec(&self, spec: &SourceSpec) -> SourceResult<()> {
+        build_http_pull_config(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SourceSpec, _ctx: &SourceBuildCtx) -> SourceResult<SourceSvcIns> {
+        let config = build_http_pull_config(spec)?;
+        let meta_tags = build_pull_source_tags(&spec.tags, &config);
+        let source = HttpPullSource::new(spec.name.clone(), meta_tags.clone(), config)
+            .map_err(|err| SourceReason::Other(format!("init http_pull source failed: {err}")))?;
+
+        let mut meta = SourceMeta::new(spec.name.clone(), spec.kind.clone());
+        meta.tags = meta_tags;
+
+        Ok(SourceSvcIns::new().with_sources(vec![SourceHandle::new(Box::new(source), meta)]))
+    }
+}
+
+impl SourceDefProvider for HttpPullSourceFactory {
+    fn source_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "http_pull_src".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Source,
+            allow_override: vec!["url", "headers", "mode", "interval_ms"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            default_params: http_pull_defaults(),
+            origin: Some("wp-connectors:http_pull_source".into()),
+        }
+    }
+}
+
+fn build_http_pull_config(spec: &SourceSpec) -> SourceResult<HttpPullSourceConfig> {
+    let url = spec
+        .params
+        .get("url")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| SourceReason::Other("http_pull.url must not be empty".into()))?;
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(SourceReason::Other(
+            "http_pull.url must start with 'http://' or 'https://'".into(),
+        )
+        .into());
+    }
+
+    let mode = match spec.params.get("mode") {
+        None => HttpPullMode::Poll,
+        Some(v) => v.as_str().and_then(HttpPullMode::parse).ok_or_else(|| {
+            SourceReason::Other("http_pull.mode must be one of: poll, sse".into())
+        })?,
+    };
+
+    let interval_ms = match spec.params.get("interval_ms") {
+        None => HttpPullSourceConfig::default_interval_ms(),
+        Some(v) => v.as_u64().ok_or_else(|| {
+            SourceReason::Other("http_pull.interval_ms must be a non-negative integer".into())
+        })?,
+    };
+
+    Ok(HttpPullSourceConfig {
+        url: url.to_string(),
+        headers: parse_headers(spec)?,
+        mode,
+        interval_ms,
+    })
+}
+
+fn parse_headers(spec: &SourceSpec) -> SourceResult<Vec<(String, String)>> {
+    let Some(value) = spec.params.get("headers") else {
+        return Ok(Vec::new());
+    };
+    let map = value
+        .as_object()
+        .ok_or_else(|| SourceReason::Other("http_pull.headers must be a table".into()))?;
+    map.iter()
+        .map(|(name, value)| {
+            let value = value.as_str().ok_or_else(|| {
+                SourceReason::Other(format!("http_pull.headers.{name} must be a string"))
+            })?;
+            // 提前校验头名称/取值，避免错误延后到构建 reqwest client 时才暴露
+            reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| SourceReason::Other(format!("http_pull.headers.{name} is invalid")))?;
+            reqwest::header::HeaderValue::from_str(value)
+                .map_err(|_| SourceReason::Other(format!("http_pull.headers.{name} is invalid")))?;
+            Ok((name.clone(), value.to_string()))
+        })
+        .collect()
+}
+
+fn http_pull_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("url".into(), json!("http://127.0.0.1:8080/events"));
+    params.insert("mode".into(), json!(HttpPullMode::Poll.as_str()));
+    params.insert(
+        "interval_ms".into(),
+        json!(HttpPullSourceConfig::default_interval_ms()),
+    );
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn build_spec(params: BTreeMap<String, Value>) -> SourceSpec {
+        SourceSpec {
+            name: "http_pull_source".into(),
+            kind: "http_pull".into(),
+            connector_id: "connector".into(),
+            params,
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn validate_requires_http_url() {
+        let err = HttpPullSourceFactory
+            .validate_spec(&build_spec(BTreeMap::new()))
+            .expect_err("missing url should fail");
+        assert!(err.to_string().contains("http_pull.url must not be empty"));
+
+        let spec = build_spec(BTreeMap::from([("url".into(), json!("ftp://host/x"))]));
+        let err = HttpPullSourceFactory
+            .validate_spec(&spec)
+            .expect_err("non-http url should fail");
+        assert!(err.to_string().contains("http_pull.url must start with"));
+    }
+
+    #[test]
+    fn validate_rejects_bad_mode_interval_and_headers() {
+        for (key, value, expect) in [
+            ("mode", json!("stream"), "http_pull.mode"),
+            ("interval_ms", json!(-1), "http_pull.interval_ms"),
+            (
+                "headers",
+                json!("X-A: 1"),
+                "http_pull.headers must be a table",
+            ),
+            ("headers", json!({"X-A": 1}), "http_pull.headers.X-A"),
+            (
+                "headers",
+                json!({"bad header": "v"}),
+                "http_pull.headers.bad header",
+            ),
+        ] {
+            let spec = build_spec(BTreeMap::from([
+                ("url".into(), json!("http://127.0.0.1:8080/events")),
+                (key.into(), value),
+            ]));
+            let err = HttpPullSourceFactory
+                .validate_spec(&spec)
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+
+    #[test]
+    fn validate_accepts_sse_config_and_defaults() {
+        let spec = build_spec(BTreeMap::from([
+            ("url".into(), json!("https://example.com/stream")),
+            ("mode".into(), json!("SSE")),
+            ("interval_ms".into(), json!(0)),
+            ("headers".into(), json!({"Authorization": "Bearer t"})),
+        ]));
+        let conf = build_http_pull_config(&spec).expect("valid spec");
+        assert_eq!(conf.mode, HttpPullMode::Sse);
+        assert_eq!(conf.headers.len(), 1);
+
+        let def = HttpPullSourceFactory.source_def();
+        assert_eq!(def.id, "http_pull_src");
+        let spec = build_spec(def.default_params);
+        HttpPullSourceFactory
+            .validate_spec(&spec)
+            .expect("defaults validate");
+    }
+}
@@ -2,7 +2,11 @@
 ///
 /// This module provides the main HTTP Sink implementation for sending data to HTTP endpoints.
 use super::config::HttpSinkConfig;
+use crate::utils::envelope::Envelope;
 use crate::utils::fmt::{BatchFormat, fmt_bytes, fmt_bytes_kv_http};
+use crate::utils::http_retry::is_retryable_status;
+use crate::utils::shutdown::DeadlineCtrl;
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
 use crate::utils::time_stat_utils::TimeStatUtils;
 use async_trait::async_trait;
 use flate2::Compression;
@@ -32,6 +36,7 @@ pub struct HttpSink {
     config: HttpSinkConfig,
     instance_id: u64,
     time_stats: TimeStatUtils,
+    counters: SinkCounters,
 }
 
 #[allow(dead_code)] // Methods will be used in later tasks
@@ -63,6 +68,7 @@ impl HttpSink {
             config,
             instance_id,
             time_stats,
+            counters: SinkCounters::default(),
         })
     }
 
@@ -109,6 +115,9 @@ impl HttpSink {
     // }
 
     fn format_records_bytes(&self, records: &[Arc<DataRecord>]) -> SinkResult<Vec<u8>> {
+        if let Some(envelope) = &self.config.envelope {
+            return self.format_enveloped_bytes(envelope, records);
+        }
         let records = records.to_vec();
 
         match self.config.fmt.as_str() {
@@ -125,6 +134,47 @@ impl HttpSink {
         }
     }
 
+    /// Format each record individually and wrap it with the envelope template
+    ///
+    /// For `json` the wrapped records form a JSON array; other formats are
+    /// newline-delimited. `csv` is rejected during validation because its
+    /// header row cannot be wrapped per record.
+    fn format_enveloped_bytes(
+        &self,
+        envelope: &Envelope,
+        records: &[Arc<DataRecord>],
+    ) -> SinkResult<Vec<u8>> {
+        let format_one = |record: &Arc<DataRecord>| {
+            let one = vec![record.clone()];
+            match self.config.fmt.as_str() {
+                "json" | "ndjson" => Ok(fmt_bytes(one, BatchFormat::Ndjson)),
+                "kv" => Ok(fmt_bytes_kv_http(one)),
+                "raw" => Ok(fmt_bytes(one, BatchFormat::Raw)),
+                "proto-text" => Ok(fmt_bytes(one, BatchFormat::ProtoText)),
+                _ => Err(sink_error(format!(
+                    "envelope is not supported with format: {}",
+                    self.config.fmt
+                ))),
+            }
+        };
+        let (open, separator, close) = if self.config.fmt == "json" {
+            ("[", ",", "]")
+        } else {
+            ("", "\n", "")
+        };
+        let mut body = String::from(open);
+        for (idx, record) in records.iter().enumerate() {
+            if idx > 0 {
+                body.push_str(separator);
+            }
+            let bytes = format_one(record)?;
+            let line = String::from_utf8_lossy(&bytes);
+            body.push_str(&envelope.wrap(line.trim_end_matches('\n')));
+        }
+        body.push_str(close);
+        Ok(body.into_bytes())
+    }
+
     /// Compress data using the configured compression algorithm
     ///
     /// # Arguments
@@ -223,65 +273,6 @@ impl HttpSink {
         }
     }
 
-    /// Send an HTTP request and handle the response
-    ///
-    /// # Arguments
-    ///
-    /// * `body` - The request body bytes
-    ///
-    /// # Returns
-    ///
-    /// Returns Ok for 2xx status codes, SinkError for 4xx and 5xx status codes
-    async fn send_request(&self, body: Vec<u8>) -> SinkResult<()> {
-        // Build the request
-        let request = self.build_request(body);
-
-        // Send the request (timeout is configured in the client)
-        let response = request
-            .send()
-            .await
-            .map_err(|e| sink_error(format!("request failed: {}", e)))?;
-
-        // Get status code
-        let status = response.status();
-
-        // Handle response based on status code
-        if status.is_success() {
-            // 2xx: Success
-            log::debug!("http request succeeded: status={}", status);
-            Ok(())
-        } else {
-            // Extract response body for error message
-            let body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| String::from("(unable to read response body)"));
-
-            if status.is_client_error() {
-                // 4xx: Client error (non-retryable)
-                log::error!("http client error: status={}, body={}", status, body);
-                Err(sink_error(format!(
-                    "client error: status={}, body={}",
-                    status, body
-                )))
-            } else if status.is_server_error() {
-                // 5xx: Server error (retryable)
-                log::error!("http server error: status={}, body={}", status, body);
-                Err(sink_error(format!(
-                    "server error: status={}, body={}",
-                    status, body
-                )))
-            } else {
-                // Other status codes
-                log::error!("http unexpected status: status={}, body={}", status, body);
-                Err(sink_error(format!(
-                    "unexpected status: status={}, body={}",
-                    status, body
-                )))
-            }
-        }
-    }
-
     /// Determine if a failed request should be retried
     ///
     /// # Arguments
@@ -297,8 +288,8 @@ impl HttpSink {
     ///
     /// - HTTP 5xx status codes: retry (server errors)
     /// - Network errors (timeout, connection refused, DNS failure): retry
-    /// - HTTP 408 (Request Timeout) and 429 (Too Many Requests): retry
-    /// - HTTP 4xx status codes (except 408, 429): do not retry (client errors)
+    /// - HTTP 429 (Too Many Requests): retry
+    /// - HTTP 4xx status codes (except 429): do not retry (client errors)
     /// - HTTP 2xx status codes: do not retry (success)
     fn should_retry(&self, error: &reqwest::Error, status: Option<StatusCode>) -> bool {
         // Network errors: retry
@@ -306,23 +297,11 @@ impl HttpSink {
             return true;
         }
 
-        // HTTP status codes
-        if let Some(status) = status {
-            // 5xx: retry
-            if status.is_server_error() {
-                return true;
-            }
-            // 4xx: do not retry (except 408 Request Timeout, 429 Too Many Requests)
-            if status.is_client_error() {
-                return status == StatusCode::REQUEST_TIMEOUT
-                    || status == StatusCode::TOO_MANY_REQUESTS;
-            }
-        }
-
-        false
+        // HTTP status codes: 5xx and 429 retry, other 4xx do not
+        status.is_some_and(is_retryable_status)
     }
 
-    /// Send an HTTP request with retry logic and exponential backoff
+    /// Send an HTTP request within the configured retry budget
     ///
     /// # Arguments
     ///
@@ -335,57 +314,18 @@ impl HttpSink {
     /// # Retry Logic
     ///
     /// - Retries up to max_retries times (or indefinitely if max_retries = -1)
-    /// - Uses exponential backoff: delay = 1000ms * 2^attempt (capped at 2^10)
-    /// - Logs retry attempts at WARN level
+    /// - Waits `retry_backoff_ms`, doubled per retry and capped at
+    ///   [`MAX_RETRY_WAIT`](crate::utils::http_retry::MAX_RETRY_WAIT)
+    /// - Honors `Retry-After` on 5xx/429 responses unless `respect_retry_after` is false
     /// - Returns last error if all retries exhausted
     async fn send_with_retry(&self, body: Vec<u8>) -> SinkResult<()> {
-        let mut attempt = 0;
-        let max_attempts = if self.config.max_retries < 0 {
-            i32::MAX
-        } else {
-            self.config.max_retries + 1 // +1 because first attempt is not a retry
-        };
-
-        loop {
-            // Clone body for each attempt
-            let result = self.send_request(body.clone()).await;
-
-            match result {
-                Ok(_) => return Ok(()),
-                Err(e) => {
-                    attempt += 1;
-
-                    // Check if we should retry based on error message
-                    let should_retry = e.to_string().contains("server error")
-                        || e.to_string().contains("request failed")
-                        || e.to_string().contains("timeout");
-
-                    // If we've exhausted retries or error is not retryable, return error
-                    if !should_retry || attempt >= max_attempts {
-                        return Err(e);
-                    }
-
-                    // Calculate exponential backoff delay (capped at 2^10 = 1024 seconds)
-                    let backoff_exp = std::cmp::min(attempt - 1, 10) as u32;
-                    let delay_ms = 1000 * 2_u64.pow(backoff_exp);
-
-                    log::warn!(
-                        "http request failed, retry {}/{}: {}. Waiting {}ms before retry",
-                        attempt,
-                        if self.config.max_retries < 0 {
-                            "∞".to_string()
-                        } else {
-                            self.config.max_retries.to_string()
-                        },
-                        e,
-                        delay_ms
-                    );
-
-                    // Non-blocking sleep
-                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                }
-            }
-        }
+        self.config
+            .retry()
+            .send("http", || self.build_request(body.clone()))
+            .await
+            .map(|response| {
+                log::debug!("http request succeeded: status={}", response.status());
+            })
     }
 }
 
@@ -415,6 +355,15 @@ impl AsyncCtrl for HttpSink {
     }
 }
 
+// Each batch is sent synchronously in `sink_records`; nothing is left to drain on stop
+impl DeadlineCtrl for HttpSink {}
+
+impl SinkMetrics for HttpSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
+}
+
 #[async_trait]
 impl AsyncRecordSink for HttpSink {
     async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
@@ -422,6 +371,8 @@ impl AsyncRecordSink for HttpSink {
     }
 
     async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.counters.receive(data.len());
+
         // Start timing for performance tracking
         self.time_stats.start_stat(data.len() as u64);
 
@@ -432,7 +383,9 @@ impl AsyncRecordSink for HttpSink {
         let body = self.compress_data(&formatted)?;
 
         // Send HTTP request with retry logic
+        let bytes = body.len();
         let result = self.send_with_retry(body).await;
+        self.counters.finish(data.len(), bytes, &result);
 
         // End timing
         self.time_stats.end_stat();
@@ -465,6 +418,8 @@ impl AsyncRecordSink for HttpSink {
 #[async_trait]
 impl AsyncRawDataSink for HttpSink {
     async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.counters.receive(1);
+
         // Start timing for performance tracking
         self.time_stats.start_stat(1);
 
@@ -475,7 +430,9 @@ impl AsyncRawDataSink for HttpSink {
         let body = self.compress_data(data_bytes)?;
 
         // Send HTTP request with retry logic
+        let bytes = body.len();
         let result = self.send_with_retry(body).await;
+        self.counters.finish(1, bytes, &result);
 
         // End timing
         self.time_stats.end_stat();
@@ -503,6 +460,8 @@ impl AsyncRawDataSink for HttpSink {
     }
 
     async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.counters.receive(1);
+
         // Start timing for performance tracking
         self.time_stats.start_stat(1);
 
@@ -510,7 +469,9 @@ impl AsyncRawDataSink for HttpSink {
         let body = self.compress_data(data)?;
 
         // Send HTTP request with retry logic
+        let bytes = body.len();
         let result = self.send_with_retry(body).await;
+        self.counters.finish(1, bytes, &result);
 
         // End timing
         self.time_stats.end_stat();
@@ -538,6 +499,8 @@ impl AsyncRawDataSink for HttpSink {
     }
 
     async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.counters.receive(data.len());
+
         // Start timing for performance tracking
         self.time_stats.start_stat(data.len() as u64);
 
@@ -551,7 +514,9 @@ impl AsyncRawDataSink for HttpSink {
         let body = self.compress_data(data_bytes)?;
 
         // Send HTTP request with retry logic
+        let bytes = body.len();
         let result = self.send_with_retry(body).await;
+        self.counters.finish(data.len(), bytes, &result);
 
         // End timing
         self.time_stats.end_stat();
@@ -581,6 +546,8 @@ impl AsyncRawDataSink for HttpSink {
     }
 
     async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.counters.receive(data.len());
+
         // Start timing for performance tracking
         self.time_stats.start_stat(data.len() as u64);
 
@@ -598,7 +565,9 @@ impl AsyncRawDataSink for HttpSink {
         let body = self.compress_data(&joined)?;
 
         // Send HTTP request with retry logic
+        let bytes = body.len();
         let result = self.send_with_retry(body).await;
+        self.counters.finish(data.len(), bytes, &result);
 
         // End timing
         self.time_stats.end_stat();
@@ -662,6 +631,29 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn stop_with_deadline_returns_drained() {
+        let config = HttpSinkConfig::new(
+            "http://example.com".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let mut sink = HttpSink::new(config).await.unwrap();
+        let report = sink
+            .stop_with_deadline(Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert!(!report.deadline_hit);
+        assert_eq!(report.dropped, 0);
+    }
+
     #[tokio::test]
     async fn format_records_json_array() {
         // Test that format_records with "json" format produces a JSON array
@@ -709,6 +701,43 @@ mod tests {
         assert_eq!(array[1]["name"], "bob");
     }
 
+    #[tokio::test]
+    async fn format_records_json_wraps_each_record_in_envelope() {
+        let envelope =
+            Envelope::parse(r#"{"source":"{source}","data":{data}}"#, Some("edge")).unwrap();
+        let config = HttpSinkConfig::new(
+            "http://example.com".to_string(),
+            None,
+            None,
+            None,
+            None,
+            Some("json".to_string()),
+            None,
+            None,
+            None,
+            None,
+        )
+        .with_envelope(Some(envelope));
+        let sink = HttpSink::new(config).await.unwrap();
+
+        let mut record1 = DataRecord::default();
+        record1.append(DataField::from_digit("id", 1));
+        let mut record2 = DataRecord::default();
+        record2.append(DataField::from_digit("id", 2));
+
+        let body = sink
+            .format_records(&[Arc::new(record1), Arc::new(record2)])
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!([
+                {"source": "edge", "data": {"id": 1}},
+                {"source": "edge", "data": {"id": 2}}
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn format_records_ndjson() {
         // Test that format_records with "ndjson" format produces newline-delimited JSON
@@ -1472,11 +1501,12 @@ mod tests {
         assert!(StatusCode::FORBIDDEN.is_client_error());
         assert!(StatusCode::NOT_FOUND.is_client_error());
 
-        // But 408 and 429 are special cases that should retry
-        assert!(StatusCode::REQUEST_TIMEOUT.is_client_error());
-        assert_eq!(StatusCode::REQUEST_TIMEOUT, StatusCode::REQUEST_TIMEOUT);
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(StatusCode::REQUEST_TIMEOUT));
+
+        // But 429 is a special case that should retry
         assert!(StatusCode::TOO_MANY_REQUESTS.is_client_error());
-        assert_eq!(StatusCode::TOO_MANY_REQUESTS, StatusCode::TOO_MANY_REQUESTS);
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
     }
 
     #[test]
@@ -1566,25 +1596,59 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn send_with_retry_backoff_cap() {
-        // Test that backoff delay is capped at 2^10 = 1024 seconds
-        // We verify the calculation logic without waiting for the full delay
-
-        // Verify the cap calculation
-        for attempt in 0..15 {
-            let backoff_exp = std::cmp::min(attempt, 10) as u32;
-            let delay_ms = 1000 * 2_u64.pow(backoff_exp);
-
-            if attempt <= 10 {
-                assert_eq!(delay_ms, 1000 * 2_u64.pow(attempt as u32));
-            } else {
-                // Should be capped at 2^10
-                assert_eq!(delay_ms, 1000 * 1024);
-            }
+    #[test]
+    fn send_with_retry_backoff_cap() {
+        // Backoff doubles from retry_backoff_ms and is capped at MAX_RETRY_WAIT
+        let config = HttpSinkConfig::new(
+            "http://localhost:9999/nonexistent".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(-1),
+            None,
+        );
+        let policy = config.retry().policy();
+        assert_eq!(policy.max_attempts, u32::MAX);
+        for attempt in 1..15 {
+            let expected = Duration::from_millis(1000 * 2_u64.pow(attempt - 1))
+                .min(crate::utils::http_retry::MAX_RETRY_WAIT);
+            assert_eq!(policy.backoff(attempt), expected);
         }
     }
 
+    #[tokio::test]
+    async fn send_with_retry_waits_for_retry_after_then_succeeds() {
+        use crate::utils::http_retry::testing::{UNAVAILABLE_RETRY_1S, scripted_server};
+
+        let (endpoint, calls) = scripted_server(vec![UNAVAILABLE_RETRY_1S]).await;
+        let config = HttpSinkConfig::new(
+            endpoint,
+            None,
+            None,
+            None,
+            None,
+            Some("json".to_string()),
+            None,
+            Some(5),
+            Some(2),
+            None,
+        )
+        .with_retry_backoff(10, true);
+        let sink = HttpSink::new(config).await.unwrap();
+
+        let start = std::time::Instant::now();
+        sink.send_with_retry(b"{}".to_vec())
+            .await
+            .expect("retry after 503 succeeds");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        // retry_backoff_ms is 10ms, so only Retry-After explains the wait
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
     #[tokio::test]
     async fn sink_record_formats_and_compresses() {
         // Test that sink_record properly formats and compresses data
@@ -1769,6 +1833,63 @@ mod tests {
         assert_eq!(sink.time_stats.total_count, 0);
     }
 
+    #[tokio::test]
+    async fn metrics_snapshot_counts_sent_and_failed_items() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start_async().await;
+        let ok_mock = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/ingest").body_excludes("reject");
+                then.status(200);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/ingest").body_includes("reject");
+                then.status(400).body("bad payload");
+            })
+            .await;
+
+        let config = HttpSinkConfig::new(
+            server.url("/ingest"),
+            Some("POST".to_string()),
+            None,
+            None,
+            None,
+            Some("ndjson".to_string()),
+            None,
+            Some(1),
+            Some(0), // No retries
+            None,
+        );
+        let mut sink = HttpSink::new(config).await.unwrap();
+        assert_eq!(sink.metrics(), SinkMetricsSnapshot::default());
+
+        let records: Vec<Arc<DataRecord>> = (0..3)
+            .map(|i| {
+                let mut record = DataRecord::default();
+                record.append(DataField::from_digit("id", i));
+                Arc::new(record)
+            })
+            .collect();
+        let expected_body = sink.format_records_bytes(&records).unwrap().len() + "line".len();
+        sink.sink_records(records).await.unwrap();
+        sink.sink_str("line").await.unwrap();
+        assert!(sink.sink_str_batch(vec!["reject", "me"]).await.is_err());
+        ok_mock.assert_calls_async(2).await;
+
+        assert_eq!(
+            sink.metrics(),
+            SinkMetricsSnapshot {
+                received: 6,
+                flushed: 4,
+                failed: 2,
+                bytes_sent: expected_body as u64,
+            }
+        );
+    }
+
     #[tokio::test]
     async fn sink_str_basic() {
         // Test that sink_str properly sends string data
@@ -0,0 +1,392 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::sync::OnceCell;
+use wp_connector_api::{SinkError, SinkResult};
+
+use super::config::{IcebergSinkConfig, TableIdent};
+use crate::utils::error_kind::ErrorKind;
+use crate::utils::http_client::HttpClientOptions;
+
+/// 表元数据中 Sink 用到的部分
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableMetadata {
+    pub format_version: u8,
+    pub location: String,
+    #[serde(default)]
+    pub current_schema_id: i32,
+    #[serde(default)]
+    pub schemas: Vec<IcebergSchema>,
+    /// 旧版本元数据以 `-1` 表示没有快照
+    #[serde(default)]
+    pub current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+    #[serde(default)]
+    pub last_sequence_number: i64,
+    #[serde(default)]
+    pub default_spec_id: i32,
+    #[serde(default)]
+    pub partition_specs: Vec<PartitionSpec>,
+}
+
+impl TableMetadata {
+    pub fn current_schema(&self) -> Option<&IcebergSchema> {
+        self.schemas
+            .iter()
+            .find(|schema| schema.schema_id == self.current_schema_id)
+    }
+
+    pub fn current_snapshot(&self) -> Option<&Snapshot> {
+        let id = self.current_snapshot_id.filter(|id| *id >= 0)?;
+        self.snapshots.iter().find(|s| s.snapshot_id == id)
+    }
+
+    /// 默认分区规则没有分区字段（或表未声明分区规则）
+    pub fn is_unpartitioned(&self) -> bool {
+        self.partition_specs
+            .iter()
+            .find(|spec| spec.spec_id == self.default_spec_id)
+            .is_none_or(|spec| spec.fields.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergSchema {
+    #[serde(default)]
+    pub schema_id: i32,
+    pub fields: Vec<SchemaField>,
+}
+
+impl IcebergSchema {
+    /// 写入清单元数据的 schema JSON
+    pub fn to_json(&self) -> Value {
+        json!({ "type": "struct", "schema-id": self.schema_id, "fields": self.fields })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaField {
+    pub id: i32,
+    pub name: String,
+    pub required: bool,
+    /// 基本类型为字符串（如 `long`），嵌套类型为对象
+    #[serde(rename = "type")]
+    pub field_type: Value,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Snapshot {
+    pub snapshot_id: i64,
+    #[serde(default)]
+    pub manifest_list: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionSpec {
+    pub spec_id: i32,
+    #[serde(default)]
+    pub fields: Vec<Value>,
+}
+
+/// 待提交的追加快照
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotCommit {
+    pub snapshot_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_snapshot_id: Option<i64>,
+    pub sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub manifest_list: String,
+    pub summary: BTreeMap<String, String>,
+    pub schema_id: i32,
+}
+
+/// 提交结果；`Conflict` 表示表已被其它写入方更新，需基于最新元数据重试
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitStatus {
+    Committed,
+    Conflict,
+}
+
+/// Catalog 接口；默认实现为 [`RestCatalog`]，测试或其它 Catalog 可替换
+#[async_trait]
+pub trait IcebergCatalog: Send + Sync {
+    /// 读取目标表的最新元数据
+    async fn load_table(&self) -> SinkResult<TableMetadata>;
+
+    /// 以“`main` 分支仍指向父快照”为前提，追加快照并将 `main` 指向它
+    async fn commit(&self, snapshot: &SnapshotCommit) -> SinkResult<CommitStatus>;
+}
+
+/// Iceberg REST Catalog 客户端（`/v1/config`、`loadTable`、`updateTable`）
+pub struct RestCatalog {
+    client: reqwest::Client,
+    uri: String,
+    warehouse: String,
+    table: TableIdent,
+    token: Option<String>,
+    /// `/v1/config` 返回的路径前缀，首次请求时获取
+    prefix: OnceCell<Option<String>>,
+}
+
+impl RestCatalog {
+    pub fn new(config: &IcebergSinkConfig, options: &HttpClientOptions) -> SinkResult<Self> {
+        reqwest::Url::parse(&config.catalog_uri).map_err(|e| {
+            catalog_error(
+                ErrorKind::Config,
+                format!("invalid catalog_uri '{}': {}", config.catalog_uri, e),
+            )
+        })?;
+        let client = options
+            .apply_reqwest(reqwest::Client::builder())
+            .build()
+            .map_err(|e| catalog_error(ErrorKind::Config, format!("build client fail: {e}")))?;
+        Ok(Self {
+            client,
+            uri: config.catalog_uri.clone(),
+            warehouse: config.warehouse.clone(),
+            table: config.table.clone(),
+            token: config.token.clone(),
+            prefix: OnceCell::new(),
+        })
+    }
+
+    fn request(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    async fn prefix(&self) -> SinkResult<Option<String>> {
+        self.prefix
+            .get_or_try_init(|| async {
+                let mut url =
+                    reqwest::Url::parse(&format!("{}/v1/config", self.uri)).map_err(|e| {
+                        catalog_error(ErrorKind::Config, format!("invalid catalog_uri: {e}"))
+                    })?;
+                url.query_pairs_mut()
+                    .append_pair("warehouse", &self.warehouse);
+                let request = self.client.get(url);
+                let body: Value = send(self.request(request), "load catalog config")
+                    .await?
+                    .json()
+                    .await
+                    .map_err(|e| {
+                        catalog_error(ErrorKind::Backend, format!("decode catalog config: {e}"))
+                    })?;
+                // overrides 优先于 defaults
+                Ok(["overrides", "defaults"]
+                    .iter()
+                    .find_map(|key| body[key]["prefix"].as_str())
+                    .map(String::from))
+            })
+            .await
+            .cloned()
+    }
+
+    /// `{uri}/v1/{prefix}/namespaces/{ns}/tables/{table}`，多级命名空间以 0x1F 连接
+    async fn table_url(&self) -> SinkResult<reqwest::Url> {
+        let prefix = self.prefix().await?;
+        let mut url = reqwest::Url::parse(&self.uri)
+            .map_err(|e| catalog_error(ErrorKind::Config, format!("invalid catalog_uri: {e}")))?;
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|_| catalog_error(ErrorKind::Config, "catalog_uri cannot be a base"))?;
+            segments.pop_if_empty().push("v1");
+            if let Some(prefix) = &prefix {
+                segments.extend(prefix.split('/').filter(|s| !s.is_empty()));
+            }
+            segments
+                .push("namespaces")
+                .push(&self.table.namespace.join("\u{1f}"))
+                .push("tables")
+                .push(&self.table.name);
+        }
+        Ok(url)
+    }
+}
+
+#[async_trait]
+impl IcebergCatalog for RestCatalog {
+    async fn load_table(&self) -> SinkResult<TableMetadata> {
+        let url = self.table_url().await?;
+        let body: Value = send(self.request(self.client.get(url)), "load table")
+            .await?
+            .json()
+            .await
+            .map_err(|e| catalog_error(ErrorKind::Backend, format!("decode load table: {e}")))?;
+        serde_json::from_value(body["metadata"].clone())
+            .map_err(|e| catalog_error(ErrorKind::Backend, format!("decode table metadata: {e}")))
+    }
+
+    async fn commit(&self, snapshot: &SnapshotCommit) -> SinkResult<CommitStatus> {
+        let url = self.table_url().await?;
+        let body = json!({
+            "identifier": { "namespace": self.table.namespace, "name": self.table.name },
+            "requirements": [{
+                "type": "assert-ref-snapshot-id",
+                "ref": "main",
+                "snapshot-id": snapshot.parent_snapshot_id,
+            }],
+            "updates": [
+                { "action": "add-snapshot", "snapshot": snapshot },
+                {
+                    "action": "set-snapshot-ref",
+                    "ref-name": "main",
+                    "type": "branch",
+                    "snapshot-id": snapshot.snapshot_id,
+                },
+            ],
+        });
+        let request = self.request(self.client.post(url).json(&body));
+        match send(request, "commit table").await {
+            Ok(_) => Ok(CommitStatus::Committed),
+            Err(SendError::Conflict) => Ok(CommitStatus::Conflict),
+            Err(SendError::Failed(err)) => Err(err),
+        }
+    }
+}
+
+/// 发送请求；409 单独区分，供提交时判断冲突
+async fn send(
+    request: reqwest::RequestBuilder,
+    action: &str,
+) -> Result<reqwest::Response, SendError> {
+    let response = request.send().await.map_err(|e| {
+        let kind = if e.is_timeout() || e.is_connect() {
+            ErrorKind::Connect
+        } else {
+            ErrorKind::Backend
+        };
+        SendError::Failed(catalog_error(kind, format!("{action} fail: {e}")))
+    })?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let kind = match status.as_u16() {
+        409 => return Err(SendError::Conflict),
+        401 | 403 => ErrorKind::Auth,
+        404 => ErrorKind::Config,
+        _ => ErrorKind::Backend,
+    };
+    let text = response.text().await.unwrap_or_default();
+    Err(SendError::Failed(catalog_error(
+        kind,
+        format!("{action} fail: HTTP {status}: {text}"),
+    )))
+}
+
+enum SendError {
+    Conflict,
+    Failed(SinkError),
+}
+
+impl From<SendError> for SinkError {
+    fn from(err: SendError) -> Self {
+        match err {
+            SendError::Conflict => {
+                catalog_error(ErrorKind::Backend, "unexpected HTTP 409 Conflict")
+            }
+            SendError::Failed(err) => err,
+        }
+    }
+}
+
+fn catalog_error(kind: ErrorKind, msg: impl std::fmt::Display) -> SinkError {
+    SinkError::from(kind.sink("iceberg", msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    fn table_body() -> Value {
+        json!({
+            "metadata-location": "file:///warehouse/db/logs/metadata/00001.metadata.json",
+            "metadata": {
+                "format-version": 2,
+                "location": "file:///warehouse/db/logs",
+                "current-schema-id": 0,
+                "schemas": [{
+                    "type": "struct",
+                    "schema-id": 0,
+                    "fields": [{ "id": 1, "name": "host", "required": false, "type": "string" }],
+                }],
+                "current-snapshot-id": -1,
+                "last-sequence-number": 0,
+                "default-spec-id": 0,
+                "partition-specs": [{ "spec-id": 0, "fields": [] }],
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn rest_catalog_loads_table_with_prefix_and_detects_conflict() {
+        let server = MockServer::start_async().await;
+        let config_mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/v1/config")
+                    .query_param("warehouse", "lake");
+                then.status(200)
+                    .json_body(json!({ "defaults": {}, "overrides": { "prefix": "lake" } }));
+            })
+            .await;
+        let load = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/v1/lake/namespaces/db%1Fraw/tables/logs")
+                    .header("authorization", "Bearer tok");
+                then.status(200).json_body(table_body());
+            })
+            .await;
+        let commit = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/v1/lake/namespaces/db%1Fraw/tables/logs")
+                    .body_includes(r#""type":"assert-ref-snapshot-id""#);
+                then.status(409)
+                    .json_body(json!({ "error": { "type": "CommitFailedException" } }));
+            })
+            .await;
+
+        let table = TableIdent::parse("db.raw", "logs").unwrap();
+        let config =
+            IcebergSinkConfig::new(server.base_url(), "lake", table).with_token(Some("tok".into()));
+        let catalog = RestCatalog::new(&config, &HttpClientOptions::default()).unwrap();
+        let metadata = catalog.load_table().await.expect("load table");
+        assert_eq!(metadata.format_version, 2);
+        assert!(metadata.current_snapshot().is_none());
+        assert!(metadata.is_unpartitioned());
+        assert_eq!(metadata.current_schema().unwrap().fields[0].name, "host");
+
+        let snapshot = SnapshotCommit {
+            snapshot_id: 7,
+            parent_snapshot_id: None,
+            sequence_number: 1,
+            timestamp_ms: 0,
+            manifest_list: "file:///warehouse/db/logs/metadata/snap-7.avro".into(),
+            summary: BTreeMap::new(),
+            schema_id: 0,
+        };
+        assert_eq!(
+            catalog.commit(&snapshot).await.unwrap(),
+            CommitStatus::Conflict
+        );
+        config_mock.assert_calls_async(1).await;
+        load.assert_async().await;
+        commit.assert_async().await;
+    }
+}
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_FLUSH_RECORDS: usize = 10_000;
+const DEFAULT_FLUSH_BYTES: usize = 64 * 1024 * 1024;
+const DEFAULT_FLUSH_INTERVAL_SECS: u64 = 60;
+const DEFAULT_TARGET_FILE_ROWS: usize = 100_000;
+const DEFAULT_COMMIT_RETRIES: u32 = 3;
+
+/// 表标识：多级命名空间 + 表名
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableIdent {
+    pub namespace: Vec<String>,
+    pub name: String,
+}
+
+impl TableIdent {
+    /// 命名空间以 `.` 分隔多级，如 `analytics.logs`；任一级为空时返回 `None`
+    pub fn parse(namespace: &str, name: &str) -> Option<Self> {
+        let namespace: Vec<String> = namespace.split('.').map(|s| s.trim().to_string()).collect();
+        let name = name.trim();
+        if name.is_empty() || namespace.iter().any(String::is_empty) {
+            return None;
+        }
+        Some(Self {
+            namespace,
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Iceberg Sink 的配置结构
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IcebergSinkConfig {
+    /// REST Catalog 地址，如 `http://iceberg-rest:8181`
+    pub catalog_uri: String,
+    /// 传给 Catalog `/v1/config` 的仓库名
+    pub warehouse: String,
+    pub table: TableIdent,
+    /// Catalog 的 Bearer Token
+    #[serde(default)]
+    pub token: Option<String>,
+    /// 缓冲记录数达到该值时写出并提交
+    pub flush_records: usize,
+    /// 缓冲数据（按 NDJSON 计）达到该字节数时写出并提交
+    pub flush_bytes: usize,
+    /// 缓冲中最早的记录超过该秒数时，下一次写入触发提交
+    pub flush_interval_secs: u64,
+    /// 单个数据文件的最大行数，一次提交可包含多个数据文件
+    pub target_file_rows: usize,
+    /// 提交冲突（表已被其它写入方更新）时的重试次数
+    pub commit_retries: u32,
+}
+
+// 避免在日志中输出 Token
+impl std::fmt::Debug for IcebergSinkConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IcebergSinkConfig")
+            .field("catalog_uri", &self.catalog_uri)
+            .field("warehouse", &self.warehouse)
+            .field("table", &self.table)
+            .field("token", &self.token.as_ref().map(|_| "***"))
+            .field("flush_records", &self.flush_records)
+            .field("flush_bytes", &self.flush_bytes)
+            .field("flush_interval_secs", &self.flush_interval_secs)
+            .field("target_file_rows", &self.target_file_rows)
+            .field("commit_retries", &self.commit_retries)
+            .finish()
+    }
+}
+
+impl IcebergSinkConfig {
+    pub fn new(
+        catalog_uri: impl Into<String>,
+        warehouse: impl Into<String>,
+        table: TableIdent,
+    ) -> Self {
+        Self {
+            catalog_uri: catalog_uri.into().trim_end_matches('/').to_string(),
+            warehouse: warehouse.into(),
+            table,
+            token: None,
+            flush_records: DEFAULT_FLUSH_RECORDS,
+            flush_bytes: DEFAULT_FLUSH_BYTES,
+            flush_interval_secs: DEFAULT_FLUSH_INTERVAL_SECS,
+            target_file_rows: DEFAULT_TARGET_FILE_ROWS,
+            commit_retries: DEFAULT_COMMIT_RETRIES,
+        }
+    }
+
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    pub fn with_flush_records(mut self, records: usize) -> Self {
+        self.flush_records = records.max(1);
+        self
+    }
+
+    pub fn with_flush_bytes(mut self, bytes: usize) -> Self {
+        self.flush_bytes = bytes.max(1);
+        self
+    }
+
+    pub fn with_flush_interval_secs(mut self, secs: u64) -> Self {
+        self.flush_interval_secs = secs.max(1);
+        self
+    }
+
+    pub fn with_target_file_rows(mut self, rows: usize) -> Self {
+        self.target_file_rows = rows.max(1);
+        self
+    }
+
+    pub fn with_commit_retries(mut self, retries: u32) -> Self {
+        self.commit_retries = retries;
+        self
+    }
+
+    pub fn default_flush_records() -> usize {
+        DEFAULT_FLUSH_RECORDS
+    }
+
+    pub fn default_flush_bytes() -> usize {
+        DEFAULT_FLUSH_BYTES
+    }
+
+    pub fn default_flush_interval_secs() -> u64 {
+        DEFAULT_FLUSH_INTERVAL_SECS
+    }
+
+    pub fn default_target_file_rows() -> usize {
+        DEFAULT_TARGET_FILE_ROWS
+    }
+
+    pub fn default_commit_retries() -> u32 {
+        DEFAULT_COMMIT_RETRIES
+    }
+}
@@ -0,0 +1,200 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkFactory, SinkHandle,
+    SinkReason, SinkResult, SinkSpec,
+};
+
+use crate::iceberg::{IcebergSink, IcebergSinkConfig, TableIdent};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::http_client::{HTTP_CLIENT_PARAMS, HttpClientOptions};
+
+pub struct IcebergSinkFactory;
+
+#[async_trait]
+impl SinkFactory for IcebergSinkFactory {
+    fn kind(&self) -> &'static str {
+        "iceberg"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        build_config(spec)?;
+        HttpClientOptions::from_params("iceberg", &spec.params)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let options = HttpClientOptions::from_params("iceberg", &spec.params)?;
+        let sink = IcebergSink::new(build_config(spec)?, &options)?;
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+fn build_config(spec: &SinkSpec) -> SinkResult<IcebergSinkConfig> {
+    let catalog_uri = required_string(spec, "catalog_uri")?;
+    if !catalog_uri.starts_with("http://") && !catalog_uri.starts_with("https://") {
+        return Err(SinkReason::sink("iceberg.catalog_uri must be an http(s) url").into());
+    }
+    let warehouse = required_string(spec, "warehouse")?;
+    let namespace = required_string(spec, "namespace")?;
+    let name = required_string(spec, "table")?;
+    let table = TableIdent::parse(&namespace, &name).ok_or_else(|| {
+        SinkReason::sink("iceberg.namespace must not contain empty levels (e.g. 'db..logs')")
+    })?;
+    let mut config = IcebergSinkConfig::new(catalog_uri, warehouse, table)
+        .with_token(optional_string(spec, "token")?);
+    if let Some(n) = parse_positive(spec, "flush_records")? {
+        config = config.with_flush_records(n as usize);
+    }
+    if let Some(n) = parse_positive(spec, "flush_bytes")? {
+        config = config.with_flush_bytes(n as usize);
+    }
+    if let Some(n) = parse_positive(spec, "flush_interval_secs")? {
+        config = config.with_flush_interval_secs(n);
+    }
+    if let Some(n) = parse_positive(spec, "target_file_rows")? {
+        config = config.with_target_file_rows(n as usize);
+    }
+    if let Some(v) = spec.params.get("commit_retries") {
+        let retries = v
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| {
+                SinkReason::sink("iceberg.commit_retries must be a non-negative integer")
+            })?;
+        config = config.with_commit_retries(retries);
+    }
+    Ok(config)
+}
+
+fn required_string(spec: &SinkSpec, key: &str) -> SinkResult<String> {
+    optional_string(spec, key)?
+        .ok_or_else(|| SinkReason::sink(format!("iceberg.{key} must not be empty")).into())
+}
+
+/// 读取字符串参数，缺省或空字符串视为未配置
+fn optional_string(spec: &SinkSpec, key: &str) -> SinkResult<Option<String>> {
+    match spec.params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.trim()).filter(|s| !s.is_empty()).map(String::from)),
+        Some(_) => Err(SinkReason::sink(format!("iceberg.{key} must be a string")).into()),
+    }
+}
+
+fn parse_positive(spec: &SinkSpec, key: &str) -> SinkResult<Option<u64>> {
+    match spec.params.get(key) {
+        None => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Ok(Some(n)),
+            _ => Err(SinkReason::sink(format!("iceberg.{key} must be a positive integer")).into()),
+        },
+    }
+}
+
+impl SinkDefProvider for IcebergSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "iceberg_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "catalog_uri",
+                "warehouse",
+                "namespace",
+                "table",
+                "token",
+                "flush_records",
+                "flush_bytes",
+                "flush_interval_secs",
+                "target_file_rows",
+                "commit_retries",
+            ]
+            .into_iter()
+            .chain(HTTP_CLIENT_PARAMS)
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect(),
+            default_params: iceberg_defaults(),
+            origin: Some("wp-connectors:iceberg_sink".into()),
+        }
+    }
+}
+
+fn iceberg_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("catalog_uri".into(), json!("http://localhost:8181"));
+    params.insert("warehouse".into(), json!("warehouse"));
+    params.insert("namespace".into(), json!("default"));
+    params.insert("table".into(), json!("wp_events"));
+    params.insert(
+        "flush_records".into(),
+        json!(IcebergSinkConfig::default_flush_records()),
+    );
+    params.insert(
+        "flush_bytes".into(),
+        json!(IcebergSinkConfig::default_flush_bytes()),
+    );
+    params.insert(
+        "flush_interval_secs".into(),
+        json!(IcebergSinkConfig::default_flush_interval_secs()),
+    );
+    params.insert(
+        "target_file_rows".into(),
+        json!(IcebergSinkConfig::default_target_file_rows()),
+    );
+    params.insert(
+        "commit_retries".into(),
+        json!(IcebergSinkConfig::default_commit_retries()),
+    );
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(params: ParamMap) -> SinkSpec {
+        SinkSpec {
+            name: "lake".into(),
+            kind: "iceberg".into(),
+            connector_id: String::new(),
+            group: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn build_config_validates_catalog_table_and_warehouse() {
+        let mut params = iceberg_defaults();
+        params.insert("namespace".into(), json!("analytics.raw"));
+        params.insert("target_file_rows".into(), json!(500));
+        params.insert("token".into(), json!("secret"));
+        let config = build_config(&spec(params.clone())).expect("valid spec");
+        assert_eq!(config.table.namespace, vec!["analytics", "raw"]);
+        assert_eq!(config.table.name, "wp_events");
+        assert_eq!(config.target_file_rows, 500);
+        assert!(!format!("{config:?}").contains("secret"));
+
+        for (key, bad, expect) in [
+            (
+                "catalog_uri",
+                json!("iceberg-rest:8181"),
+                "iceberg.catalog_uri",
+            ),
+            ("warehouse", json!(""), "iceberg.warehouse"),
+            ("namespace", json!("db..raw"), "iceberg.namespace"),
+            ("table", json!(" "), "iceberg.table"),
+            ("flush_records", json!(0), "iceberg.flush_records"),
+            ("commit_retries", json!(-1), "iceberg.commit_retries"),
+        ] {
+            let mut params = params.clone();
+            params.insert(key.into(), bad);
+            let err = IcebergSinkFactory
+                .validate_spec(&spec(params))
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+}
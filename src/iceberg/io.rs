@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use wp_connector_api::{SinkError, SinkResult};
+
+use crate::utils::error_kind::ErrorKind;
+
+/// 表存储读写接口；默认实现为 [`LocalFileIO`]，其它存储可替换
+#[async_trait]
+pub trait FileIO: Send + Sync {
+    /// 以完整内容写入 `location`（表元数据中的绝对位置）
+    async fn write(&self, location: &str, body: Bytes) -> SinkResult<()>;
+
+    async fn read(&self, location: &str) -> SinkResult<Vec<u8>>;
+}
+
+/// 读写本地文件系统上的表（位置为 `file://` URI 或绝对路径），父目录不存在时自动创建
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalFileIO;
+
+impl LocalFileIO {
+    fn path(location: &str) -> SinkResult<PathBuf> {
+        let path = location.strip_prefix("file://").unwrap_or(location);
+        if !path.starts_with('/') {
+            return Err(io_error(
+                ErrorKind::Config,
+                format!("unsupported table location '{location}': only local paths are supported"),
+            ));
+        }
+        Ok(PathBuf::from(path))
+    }
+}
+
+#[async_trait]
+impl FileIO for LocalFileIO {
+    async fn write(&self, location: &str, body: Bytes) -> SinkResult<()> {
+        let path = Self::path(location)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                io_error(
+                    ErrorKind::Backend,
+                    format!("create dir '{}': {e}", parent.display()),
+                )
+            })?;
+        }
+        tokio::fs::write(&path, body)
+            .await
+            .map_err(|e| io_error(ErrorKind::Backend, format!("write '{location}': {e}")))
+    }
+
+    async fn read(&self, location: &str) -> SinkResult<Vec<u8>> {
+        let path = Self::path(location)?;
+        tokio::fs::read(&path)
+            .await
+            .map_err(|e| io_error(ErrorKind::Backend, format!("read '{location}': {e}")))
+    }
+}
+
+fn io_error(kind: ErrorKind, msg: String) -> SinkError {
+    SinkError::from(kind.sink("iceberg", msg))
+}
@@ -0,0 +1,242 @@
+//! 按 Iceberg v2 规范编码 Avro 清单（manifest）与清单列表（manifest list）
+//!
+//! 仅写入规范要求的字段；列统计等可选字段省略，读取方按缺省处理。
+
+use std::sync::LazyLock;
+
+use apache_avro::types::Value as AvroValue;
+use apache_avro::{Reader, Schema, Writer};
+use wp_connector_api::{SinkError, SinkResult};
+
+use crate::utils::error_kind::ErrorKind;
+
+/// 清单条目状态：新增
+const STATUS_ADDED: i32 = 1;
+/// 清单内容类型：数据文件
+const CONTENT_DATA: i32 = 0;
+
+static MANIFEST_ENTRY_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    Schema::parse_str(
+        r#"{
+          "type": "record",
+          "name": "manifest_entry",
+          "fields": [
+            {"name": "status", "type": "int", "field-id": 0},
+            {"name": "snapshot_id", "type": ["null", "long"], "default": null, "field-id": 1},
+            {"name": "sequence_number", "type": ["null", "long"], "default": null, "field-id": 3},
+            {"name": "file_sequence_number", "type": ["null", "long"], "default": null, "field-id": 4},
+            {"name": "data_file", "field-id": 2, "type": {
+              "type": "record",
+              "name": "r2",
+              "fields": [
+                {"name": "content", "type": "int", "field-id": 134},
+                {"name": "file_path", "type": "string", "field-id": 100},
+                {"name": "file_format", "type": "string", "field-id": 101},
+                {"name": "partition", "field-id": 102, "type": {"type": "record", "name": "r102", "fields": []}},
+                {"name": "record_count", "type": "long", "field-id": 103},
+                {"name": "file_size_in_bytes", "type": "long", "field-id": 104}
+              ]
+            }}
+          ]
+        }"#,
+    )
+    .expect("valid manifest_entry schema")
+});
+
+static MANIFEST_FILE_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    Schema::parse_str(
+        r#"{
+          "type": "record",
+          "name": "manifest_file",
+          "fields": [
+            {"name": "manifest_path", "type": "string", "field-id": 500},
+            {"name": "manifest_length", "type": "long", "field-id": 501},
+            {"name": "partition_spec_id", "type": "int", "field-id": 502},
+            {"name": "content", "type": "int", "default": 0, "field-id": 517},
+            {"name": "sequence_number", "type": "long", "default": 0, "field-id": 515},
+            {"name": "min_sequence_number", "type": "long", "default": 0, "field-id": 516},
+            {"name": "added_snapshot_id", "type": "long", "field-id": 503},
+            {"name": "added_files_count", "type": "int", "aliases": ["added_data_files_count"], "field-id": 504},
+            {"name": "existing_files_count", "type": "int", "aliases": ["existing_data_files_count"], "field-id": 505},
+            {"name": "deleted_files_count", "type": "int", "aliases": ["deleted_data_files_count"], "field-id": 506},
+            {"name": "added_rows_count", "type": "long", "field-id": 512},
+            {"name": "existing_rows_count", "type": "long", "field-id": 513},
+            {"name": "deleted_rows_count", "type": "long", "field-id": 514}
+          ]
+        }"#,
+    )
+    .expect("valid manifest_file schema")
+});
+
+/// 一个已写出的 Parquet 数据文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataFile {
+    pub path: String,
+    pub record_count: i64,
+    pub file_size: i64,
+}
+
+/// 清单列表中的一项，指向一个清单文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestFile {
+    pub path: String,
+    pub length: i64,
+    pub spec_id: i32,
+    pub sequence_number: i64,
+    pub snapshot_id: i64,
+    pub added_files: i32,
+    pub added_rows: i64,
+}
+
+impl ManifestFile {
+    fn to_avro(&self) -> AvroValue {
+        AvroValue::Record(vec![
+            ("manifest_path".into(), AvroValue::String(self.path.clone())),
+            ("manifest_length".into(), AvroValue::Long(self.length)),
+            ("partition_spec_id".into(), AvroValue::Int(self.spec_id)),
+            ("content".into(), AvroValue::Int(CONTENT_DATA)),
+            (
+                "sequence_number".into(),
+                AvroValue::Long(self.sequence_number),
+            ),
+            (
+                "min_sequence_number".into(),
+                AvroValue::Long(self.sequence_number),
+            ),
+            (
+                "added_snapshot_id".into(),
+                AvroValue::Long(self.snapshot_id),
+            ),
+            ("added_files_count".into(), AvroValue::Int(self.added_files)),
+            ("existing_files_count".into(), AvroValue::Int(0)),
+            ("deleted_files_count".into(), AvroValue::Int(0)),
+            ("added_rows_count".into(), AvroValue::Long(self.added_rows)),
+            ("existing_rows_count".into(), AvroValue::Long(0)),
+            ("deleted_rows_count".into(), AvroValue::Long(0)),
+        ])
+    }
+}
+
+/// 编码新增数据文件的清单；序列号留空，由清单列表继承
+pub fn write_manifest(
+    schema_json: &str,
+    schema_id: i32,
+    spec_id: i32,
+    snapshot_id: i64,
+    files: &[DataFile],
+) -> SinkResult<Vec<u8>> {
+    let mut writer = Writer::new(&MANIFEST_ENTRY_SCHEMA, Vec::new());
+    for (key, value) in [
+        ("schema", schema_json.to_string()),
+        ("schema-id", schema_id.to_string()),
+        ("partition-spec", "[]".to_string()),
+        ("partition-spec-id", spec_id.to_string()),
+        ("format-version", "2".to_string()),
+        ("content", "data".to_string()),
+    ] {
+        writer
+            .add_user_metadata(key.to_string(), value)
+            .map_err(avro_error)?;
+    }
+    for file in files {
+        let data_file = AvroValue::Record(vec![
+            ("content".into(), AvroValue::Int(CONTENT_DATA)),
+            ("file_path".into(), AvroValue::String(file.path.clone())),
+            ("file_format".into(), AvroValue::String("PARQUET".into())),
+            ("partition".into(), AvroValue::Record(Vec::new())),
+            ("record_count".into(), AvroValue::Long(file.record_count)),
+            ("file_size_in_bytes".into(), AvroValue::Long(file.file_size)),
+        ]);
+        let entry = AvroValue::Record(vec![
+            ("status".into(), AvroValue::Int(STATUS_ADDED)),
+            (
+                "snapshot_id".into(),
+                AvroValue::Union(1, Box::new(AvroValue::Long(snapshot_id))),
+            ),
+            (
+                "sequence_number".into(),
+                AvroValue::Union(0, Box::new(AvroValue::Null)),
+            ),
+            (
+                "file_sequence_number".into(),
+                AvroValue::Union(0, Box::new(AvroValue::Null)),
+            ),
+            ("data_file".into(), data_file),
+        ]);
+        writer.append(entry).map_err(avro_error)?;
+    }
+    writer.into_inner().map_err(avro_error)
+}
+
+/// 读取父快照的清单列表，按本模块的 schema 解析（其它写入方的可选字段被忽略）
+pub fn read_manifest_list(bytes: &[u8]) -> SinkResult<Vec<AvroValue>> {
+    let reader = Reader::with_schema(&MANIFEST_FILE_SCHEMA, bytes).map_err(avro_error)?;
+    reader.map(|value| value.map_err(avro_error)).collect()
+}
+
+/// 编码新快照的清单列表：沿用父快照的全部清单，再追加本次新增的清单
+pub fn write_manifest_list(
+    snapshot_id: i64,
+    parent_snapshot_id: Option<i64>,
+    sequence_number: i64,
+    inherited: Vec<AvroValue>,
+    added: &ManifestFile,
+) -> SinkResult<Vec<u8>> {
+    let mut writer = Writer::new(&MANIFEST_FILE_SCHEMA, Vec::new());
+    let parent = parent_snapshot_id.map_or_else(|| "null".to_string(), |id| id.to_string());
+    for (key, value) in [
+        ("snapshot-id", snapshot_id.to_string()),
+        ("parent-snapshot-id", parent),
+        ("sequence-number", sequence_number.to_string()),
+        ("format-version", "2".to_string()),
+    ] {
+        writer
+            .add_user_metadata(key.to_string(), value)
+            .map_err(avro_error)?;
+    }
+    for value in inherited {
+        writer.append(value).map_err(avro_error)?;
+    }
+    writer.append(added.to_avro()).map_err(avro_error)?;
+    writer.into_inner().map_err(avro_error)
+}
+
+fn avro_error(err: apache_avro::Error) -> SinkError {
+    SinkError::from(ErrorKind::Serialize.sink("iceberg", format!("avro manifest: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_list_keeps_parent_manifests() {
+        let first = ManifestFile {
+            path: "file:///t/metadata/a-m0.avro".into(),
+            length: 100,
+            spec_id: 0,
+            sequence_number: 1,
+            snapshot_id: 11,
+            added_files: 2,
+            added_rows: 20,
+        };
+        let list = write_manifest_list(11, None, 1, Vec::new(), &first).unwrap();
+        let parent = read_manifest_list(&list).unwrap();
+        assert_eq!(parent, vec![first.to_avro()]);
+
+        let second = ManifestFile {
+            path: "file:///t/metadata/b-m0.avro".into(),
+            sequence_number: 2,
+            snapshot_id: 12,
+            ..first.clone()
+        };
+        let list = write_manifest_list(12, Some(11), 2, parent, &second).unwrap();
+        let reader = Reader::new(&list[..]).unwrap();
+        assert_eq!(
+            reader.user_metadata().get("parent-snapshot-id"),
+            Some(&b"11".to_vec())
+        );
+        let entries: Vec<AvroValue> = reader.map(Result::unwrap).collect();
+        assert_eq!(entries, vec![first.to_avro(), second.to_avro()]);
+    }
+}
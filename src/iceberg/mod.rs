@@ -0,0 +1,37 @@
+//! Apache Iceberg 表追加写入 Sink
+//!
+//! - 记录在内存中缓冲，达到 `flush_records` / `flush_bytes`、最早记录超过
+//!   `flush_interval_secs`（由下一次写入触发）或停止时提交一次；
+//! - 每次提交按 `target_file_rows` 将缓冲拆分为若干 Parquet 数据文件（列与 field id
+//!   取自表的当前 schema），写出 Avro 清单与清单列表，再经 Catalog 追加快照；
+//! - 提交以 `main` 分支仍指向父快照为前提，冲突时基于最新元数据重试 `commit_retries` 次；
+//! - 目前仅支持 format v2、未分区、位于本地文件系统（`file://`）的表，列类型限
+//!   `string` / `long` / `double` / `boolean`。Catalog 与存储分别抽象为
+//!   [`IcebergCatalog`] 与 [`FileIO`]，可替换为其它实现。
+//!
+//! # Example
+//! ```no_run
+//! use wp_connectors::iceberg::{IcebergSink, IcebergSinkConfig, TableIdent};
+//! use wp_connectors::utils::http_client::HttpClientOptions;
+//!
+//! let table = TableIdent::parse("analytics", "events").unwrap();
+//! let config = IcebergSinkConfig::new("http://iceberg-rest:8181", "lake", table)
+//!     .with_target_file_rows(50_000);
+//! let sink = IcebergSink::new(config, &HttpClientOptions::default());
+//! ```
+
+mod catalog;
+pub mod config;
+mod factory;
+mod io;
+mod manifest;
+mod sink;
+
+pub use catalog::{
+    CommitStatus, IcebergCatalog, IcebergSchema, PartitionSpec, RestCatalog, SchemaField, Snapshot,
+    SnapshotCommit, TableMetadata,
+};
+pub use config::{IcebergSinkConfig, TableIdent};
+pub use factory::IcebergSinkFactory;
+pub use io::{FileIO, LocalFileIO};
+pub use sink::IcebergSink;
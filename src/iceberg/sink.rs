@@ -0,0 +1,522 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
+use wp_data_fmt::{FormatType, RecordFormatter};
+use wp_model_core::model::{DataRecord, fmt_def::TextFmt};
+
+use super::catalog::{
+    CommitStatus, IcebergCatalog, IcebergSchema, RestCatalog, SnapshotCommit, TableMetadata,
+};
+use super::config::IcebergSinkConfig;
+use super::io::{FileIO, LocalFileIO};
+use super::manifest::{
+    DataFile, ManifestFile, read_manifest_list, write_manifest, write_manifest_list,
+};
+use crate::parquet::{ParquetColumn, ParquetColumnType, encode_parquet_with_ids};
+use crate::utils::error_kind::ErrorKind;
+use crate::utils::http_client::HttpClientOptions;
+use crate::utils::shutdown::DeadlineCtrl;
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
+
+/// Iceberg Sink 实现，缓冲记录并按大小/时间/停止写出数据文件、提交一个追加快照
+pub struct IcebergSink {
+    config: IcebergSinkConfig,
+    catalog: Arc<dyn IcebergCatalog>,
+    io: Arc<dyn FileIO>,
+    records: Vec<Arc<DataRecord>>,
+    /// 缓冲数据按 NDJSON 计的字节数
+    buffered_bytes: usize,
+    /// 缓冲中最早一条记录的写入时间
+    oldest: Option<Instant>,
+    stopped: bool,
+    counters: SinkCounters,
+}
+
+impl IcebergSink {
+    /// 使用 REST Catalog 与本地文件存储创建 Sink
+    pub fn new(config: IcebergSinkConfig, options: &HttpClientOptions) -> SinkResult<Self> {
+        let catalog = RestCatalog::new(&config, options)?;
+        Ok(Self::with_catalog(
+            config,
+            Arc::new(catalog),
+            Arc::new(LocalFileIO),
+        ))
+    }
+
+    /// 使用自定义 Catalog 与存储创建 Sink
+    pub fn with_catalog(
+        config: IcebergSinkConfig,
+        catalog: Arc<dyn IcebergCatalog>,
+        io: Arc<dyn FileIO>,
+    ) -> Self {
+        Self {
+            config,
+            catalog,
+            io,
+            records: Vec::new(),
+            buffered_bytes: 0,
+            oldest: None,
+            stopped: false,
+            counters: SinkCounters::default(),
+        }
+    }
+
+    fn push(&mut self, record: Arc<DataRecord>) {
+        let line = FormatType::from(&TextFmt::Json).fmt_record(&record);
+        self.buffered_bytes += line.len() + 1;
+        self.records.push(record);
+        self.oldest.get_or_insert_with(Instant::now);
+    }
+
+    fn should_flush(&self) -> bool {
+        let interval = Duration::from_secs(self.config.flush_interval_secs);
+        self.records.len() >= self.config.flush_records
+            || self.buffered_bytes >= self.config.flush_bytes
+            || self.oldest.is_some_and(|t| t.elapsed() >= interval)
+    }
+
+    /// 将缓冲提交为一个快照；失败时保留缓冲，下次写入或停止时重试
+    /// （已写出但未提交的文件不会被表引用，由表维护任务清理）
+    async fn flush(&mut self) -> SinkResult<()> {
+        if self.records.is_empty() {
+            return Ok(());
+        }
+        let result = self.commit_buffer().await;
+        let bytes = *result.as_ref().unwrap_or(&0);
+        self.counters.finish(self.records.len(), bytes, &result);
+        if result.is_ok() {
+            self.records.clear();
+            self.buffered_bytes = 0;
+            self.oldest = None;
+        }
+        result.map(|_| ())
+    }
+
+    /// 写出数据文件与清单并提交，返回数据文件的总字节数
+    async fn commit_buffer(&self) -> SinkResult<usize> {
+        let mut table = self.catalog.load_table().await?;
+        let schema = check_table(&table)?.clone();
+        let (columns, field_ids) = table_columns(&schema)?;
+        let location = table.location.trim_end_matches('/').to_string();
+        let commit_id = Uuid::new_v4();
+        let snapshot_id = new_snapshot_id();
+
+        let mut files = Vec::new();
+        let mut bytes = 0;
+        for (idx, chunk) in self
+            .records
+            .chunks(self.config.target_file_rows)
+            .enumerate()
+        {
+            let body = encode_parquet_with_ids(chunk, &columns, &field_ids)?;
+            let path = format!("{location}/data/{commit_id}-{idx:05}.parquet");
+            bytes += body.len();
+            files.push(DataFile {
+                path: path.clone(),
+                record_count: chunk.len() as i64,
+                file_size: body.len() as i64,
+            });
+            self.io.write(&path, Bytes::from(body)).await?;
+        }
+        let manifest = write_manifest(
+            &schema.to_json().to_string(),
+            schema.schema_id,
+            table.default_spec_id,
+            snapshot_id,
+            &files,
+        )?;
+        let manifest_path = format!("{location}/metadata/{commit_id}-m0.avro");
+        let manifest_len = manifest.len() as i64;
+        self.io.write(&manifest_path, Bytes::from(manifest)).await?;
+
+        let rows = self.records.len();
+        for attempt in 0..=self.config.commit_retries {
+            let parent = table.current_snapshot();
+            let inherited = match parent.and_then(|s| s.manifest_list.as_deref()) {
+                Some(list) => read_manifest_list(&self.io.read(list).await?)?,
+                None => Vec::new(),
+            };
+            let sequence_number = table.last_sequence_number + 1;
+            let added = ManifestFile {
+                path: manifest_path.clone(),
+                length: manifest_len,
+                spec_id: table.default_spec_id,
+                sequence_number,
+                snapshot_id,
+                added_files: files.len() as i32,
+                added_rows: rows as i64,
+            };
+            let parent_id = parent.map(|s| s.snapshot_id);
+            let list =
+                write_manifest_list(snapshot_id, parent_id, sequence_number, inherited, &added)?;
+            let list_path =
+                format!("{location}/metadata/snap-{snapshot_id}-{attempt}-{commit_id}.avro");
+            self.io.write(&list_path, Bytes::from(list)).await?;
+            let commit = SnapshotCommit {
+                snapshot_id,
+                parent_snapshot_id: parent_id,
+                sequence_number,
+                timestamp_ms: Utc::now().timestamp_millis(),
+                manifest_list: list_path,
+                summary: BTreeMap::from([
+                    ("operation".to_string(), "append".to_string()),
+                    ("added-data-files".to_string(), files.len().to_string()),
+                    ("added-records".to_string(), rows.to_string()),
+                ]),
+                schema_id: schema.schema_id,
+            };
+            match self.catalog.commit(&commit).await? {
+                CommitStatus::Committed => return Ok(bytes),
+                CommitStatus::Conflict => {
+                    table = self.catalog.load_table().await?;
+                    check_table(&table)?;
+                }
+            }
+        }
+        Err(ErrorKind::Backend
+            .sink(
+                "iceberg",
+                format!(
+                    "commit conflict persisted after {} retries",
+                    self.config.commit_retries
+                ),
+            )
+            .into())
+    }
+
+    fn ensure_running(&self) -> SinkResult<()> {
+        if self.stopped {
+            return Err(sink_error("iceberg sink is stopped"));
+        }
+        Ok(())
+    }
+}
+
+/// 仅支持 v2、未分区的表；返回当前 schema
+fn check_table(table: &TableMetadata) -> SinkResult<&IcebergSchema> {
+    if table.format_version != 2 {
+        return Err(config_error(format!(
+            "unsupported table format version {}: only v2 tables are supported",
+            table.format_version
+        )));
+    }
+    if !table.is_unpartitioned() {
+        return Err(config_error(
+            "partitioned tables are not supported: only unpartitioned tables can be appended",
+        ));
+    }
+    table
+        .current_schema()
+        .ok_or_else(|| config_error("table metadata has no current schema"))
+}
+
+/// 将表的顶层字段映射为 Parquet 列与对应的 field id
+fn table_columns(schema: &IcebergSchema) -> SinkResult<(Vec<ParquetColumn>, Vec<i32>)> {
+    let mut columns = Vec::with_capacity(schema.fields.len());
+    let mut ids = Vec::with_capacity(schema.fields.len());
+    for field in &schema.fields {
+        let kind = match field.field_type.as_str() {
+            Some("string") => ParquetColumnType::String,
+            Some("long") => ParquetColumnType::Int64,
+            Some("double") => ParquetColumnType::Double,
+            Some("boolean") => ParquetColumnType::Bool,
+            _ => {
+                return Err(config_error(format!(
+                    "column '{}' has unsupported type {}: supported types are string, long, double, boolean",
+                    field.name, field.field_type
+                )));
+            }
+        };
+        columns.push(ParquetColumn::new(&field.name, kind));
+        ids.push(field.id);
+    }
+    Ok((columns, ids))
+}
+
+/// 快照 id 须为正数
+fn new_snapshot_id() -> i64 {
+    (Uuid::new_v4().as_u64_pair().0 & i64::MAX as u64) as i64
+}
+
+#[async_trait]
+impl AsyncCtrl for IcebergSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        if self.stopped {
+            return Ok(());
+        }
+        self.flush().await?;
+        self.stopped = true;
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+// 默认的 `stop_with_deadline` 即可：`stop` 会提交剩余缓冲
+impl DeadlineCtrl for IcebergSink {}
+
+impl SinkMetrics for IcebergSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for IcebergSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.sink_records(vec![Arc::new(data.clone())]).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.ensure_running()?;
+        self.counters.receive(data.len());
+        for record in data {
+            self.push(record);
+        }
+        if self.should_flush() {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for IcebergSink {
+    async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+        Err(sink_error("iceberg sink does not accept raw input"))
+    }
+
+    async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+        Err(sink_error("iceberg sink does not accept raw bytes"))
+    }
+
+    async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+        Err(sink_error("iceberg sink does not accept raw input"))
+    }
+
+    async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+        Err(sink_error("iceberg sink does not accept raw bytes"))
+    }
+}
+
+fn sink_error(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+fn config_error(msg: impl std::fmt::Display) -> SinkError {
+    SinkError::from(ErrorKind::Config.sink("iceberg", msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iceberg::catalog::Snapshot;
+    use crate::iceberg::config::TableIdent;
+    use apache_avro::Reader;
+    use apache_avro::types::Value as AvroValue;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use serde_json::json;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use wp_model_core::model::DataField;
+
+    /// 在内存中维护表元数据、按提交推进快照的模拟 Catalog
+    struct MockCatalog {
+        table: Mutex<TableMetadata>,
+        commits: Mutex<Vec<SnapshotCommit>>,
+        /// 剩余需返回冲突的提交次数
+        conflicts: AtomicUsize,
+    }
+
+    impl MockCatalog {
+        fn new(location: &str) -> Self {
+            let table = serde_json::from_value(json!({
+                "format-version": 2,
+                "location": location,
+                "current-schema-id": 1,
+                "schemas": [{
+                    "schema-id": 1,
+                    "fields": [
+                        { "id": 1, "name": "id", "required": true, "type": "long" },
+                        { "id": 2, "name": "host", "required": false, "type": "string" },
+                    ],
+                }],
+                "last-sequence-number": 0,
+                "default-spec-id": 0,
+                "partition-specs": [{ "spec-id": 0, "fields": [] }],
+            }))
+            .unwrap();
+            Self {
+                table: Mutex::new(table),
+                commits: Mutex::new(Vec::new()),
+                conflicts: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl IcebergCatalog for MockCatalog {
+        async fn load_table(&self) -> SinkResult<TableMetadata> {
+            Ok(self.table.lock().unwrap().clone())
+        }
+
+        async fn commit(&self, snapshot: &SnapshotCommit) -> SinkResult<CommitStatus> {
+            let mut table = self.table.lock().unwrap();
+            if self.conflicts.load(Ordering::SeqCst) > 0 {
+                self.conflicts.fetch_sub(1, Ordering::SeqCst);
+                // 模拟其它写入方抢先提交了序列号
+                table.last_sequence_number += 1;
+                return Ok(CommitStatus::Conflict);
+            }
+            assert_eq!(
+                snapshot.parent_snapshot_id,
+                table.current_snapshot().map(|s| s.snapshot_id)
+            );
+            table.snapshots.push(Snapshot {
+                snapshot_id: snapshot.snapshot_id,
+                manifest_list: Some(snapshot.manifest_list.clone()),
+            });
+            table.current_snapshot_id = Some(snapshot.snapshot_id);
+            table.last_sequence_number = snapshot.sequence_number;
+            self.commits.lock().unwrap().push(snapshot.clone());
+            Ok(CommitStatus::Committed)
+        }
+    }
+
+    fn record(id: i64, host: &str) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", id));
+        record.append(DataField::from_chars("host", host));
+        Arc::new(record)
+    }
+
+    fn table_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wp_connectors_iceberg_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn config() -> IcebergSinkConfig {
+        IcebergSinkConfig::new(
+            "http://127.0.0.1:8181",
+            "lake",
+            TableIdent::parse("db", "logs").unwrap(),
+        )
+        .with_flush_records(5)
+        .with_target_file_rows(2)
+    }
+
+    fn avro_entries(path: &str) -> Vec<Vec<(String, AvroValue)>> {
+        let bytes = std::fs::read(path.trim_start_matches("file://")).unwrap();
+        Reader::new(&bytes[..])
+            .unwrap()
+            .map(|value| match value.unwrap() {
+                AvroValue::Record(fields) => fields,
+                other => panic!("unexpected avro value {other:?}"),
+            })
+            .collect()
+    }
+
+    fn field<'a>(fields: &'a [(String, AvroValue)], name: &str) -> &'a AvroValue {
+        &fields.iter().find(|(n, _)| n == name).unwrap().1
+    }
+
+    #[tokio::test]
+    async fn flush_groups_records_into_data_files_and_commits_snapshot() {
+        let dir = table_dir("grouping");
+        let location = format!("file://{}", dir.display());
+        let catalog = Arc::new(MockCatalog::new(&location));
+        let mut sink = IcebergSink::with_catalog(config(), catalog.clone(), Arc::new(LocalFileIO));
+
+        let records = (0..4).map(|i| record(i, "web")).collect();
+        sink.sink_records(records).await.unwrap();
+        assert!(catalog.commits.lock().unwrap().is_empty());
+        sink.sink_records(vec![record(4, "db")]).await.unwrap();
+
+        let commits = catalog.commits.lock().unwrap().clone();
+        assert_eq!(commits.len(), 1);
+        let snapshot = &commits[0];
+        assert_eq!(snapshot.sequence_number, 1);
+        assert_eq!(snapshot.summary["added-data-files"], "3");
+        assert_eq!(snapshot.summary["added-records"], "5");
+
+        let list = avro_entries(&snapshot.manifest_list);
+        assert_eq!(list.len(), 1);
+        let AvroValue::String(manifest) = field(&list[0], "manifest_path") else {
+            panic!("manifest_path");
+        };
+        let counts: Vec<AvroValue> = avro_entries(manifest)
+            .iter()
+            .map(|entry| match field(entry, "data_file") {
+                AvroValue::Record(file) => field(file, "record_count").clone(),
+                other => panic!("unexpected data_file {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            counts,
+            vec![AvroValue::Long(2), AvroValue::Long(2), AvroValue::Long(1)]
+        );
+
+        let data_files: Vec<PathBuf> = std::fs::read_dir(dir.join("data"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(data_files.len(), 3);
+        let reader =
+            SerializedFileReader::new(std::fs::File::open(&data_files[0]).unwrap()).unwrap();
+        let schema = reader.metadata().file_metadata().schema_descr();
+        assert_eq!(schema.column(0).self_type().get_basic_info().id(), 1);
+        assert_eq!(
+            sink.metrics(),
+            SinkMetricsSnapshot {
+                received: 5,
+                flushed: 5,
+                failed: 0,
+                bytes_sent: data_files
+                    .iter()
+                    .map(|p| std::fs::metadata(p).unwrap().len())
+                    .sum(),
+            }
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn commit_retries_on_conflict_and_keeps_parent_manifests() {
+        let dir = table_dir("commit");
+        let location = format!("file://{}", dir.display());
+        let catalog = Arc::new(MockCatalog::new(&location));
+        let mut sink = IcebergSink::with_catalog(config(), catalog.clone(), Arc::new(LocalFileIO));
+
+        sink.sink_records(vec![record(1, "web")]).await.unwrap();
+        sink.flush().await.unwrap();
+        catalog.conflicts.store(1, Ordering::SeqCst);
+        sink.sink_records(vec![record(2, "web")]).await.unwrap();
+        sink.stop().await.unwrap();
+
+        let commits = catalog.commits.lock().unwrap().clone();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[1].parent_snapshot_id, Some(commits[0].snapshot_id));
+        // 冲突后基于最新元数据重新分配序列号
+        assert_eq!(commits[1].sequence_number, 3);
+        assert_eq!(avro_entries(&commits[1].manifest_list).len(), 2);
+        assert!(sink.sink_records(vec![record(3, "web")]).await.is_err());
+
+        catalog.table.lock().unwrap().format_version = 1;
+        let mut sink = IcebergSink::with_catalog(config(), catalog.clone(), Arc::new(LocalFileIO));
+        sink.sink_records(vec![record(4, "web")]).await.unwrap();
+        let err = sink.stop().await.expect_err("v1 table");
+        assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Config));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,238 @@
+//! Kafka sink 的 Avro 序列化（Confluent Schema Registry 线格式）
+//!
+//! 构建 sink 时从 Schema Registry 拉取 `subject` 的最新 schema 并缓存，
+//! 之后每条记录按 schema 字段顺序编码为 Avro datum，并加上 5 字节前缀：
+//!
+//! ```text
+//! 0x00 | schema id（4 字节大端） | Avro binary datum
+//! ```
+
+use std::time::Duration;
+
+use apache_avro::Schema;
+use apache_avro::types::Value as AvroValue;
+use serde::Deserialize;
+use wp_model_core::model::{DataRecord, Value};
+
+use crate::kafka::config::AvroConf;
+
+/// Confluent 线格式的魔数
+pub(crate) const MAGIC_BYTE: u8 = 0;
+
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `GET /subjects/{subject}/versions/latest` 的响应
+#[derive(Debug, Deserialize)]
+struct RegisteredSchema {
+    id: u32,
+    schema: String,
+}
+
+/// 已缓存 schema 的记录编码器
+#[derive(Debug, Clone)]
+pub(crate) struct AvroEncoder {
+    schema: Schema,
+    schema_id: u32,
+}
+
+impl AvroEncoder {
+    /// 由 schema JSON 构建编码器；schema 顶层必须是 record
+    pub(crate) fn new(schema_json: &str, schema_id: u32) -> Result<Self, String> {
+        let schema =
+            Schema::parse_str(schema_json).map_err(|e| format!("invalid avro schema: {e}"))?;
+        if !matches!(schema, Schema::Record(_)) {
+            return Err("avro schema must be a record".into());
+        }
+        Ok(Self { schema, schema_id })
+    }
+
+    /// 从 Schema Registry 拉取 `subject` 的最新版本
+    pub(crate) async fn fetch(conf: &AvroConf) -> anyhow::Result<Self> {
+        let url = format!(
+            "{}/subjects/{}/versions/latest",
+            conf.schema_registry_url.trim_end_matches('/'),
+            conf.subject
+        );
+        let client = reqwest::Client::builder()
+            .timeout(REGISTRY_TIMEOUT)
+            .build()?;
+        let response = client.get(&url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("schema registry {url} returned {status}: {body}");
+        }
+        let registered: RegisteredSchema = response.json().await?;
+        Self::new(&registered.schema, registered.id).map_err(anyhow::Error::msg)
+    }
+
+    /// 编码一条记录：魔数 + schema id + Avro datum
+    pub(crate) fn encode(&self, record: &DataRecord) -> Result<Vec<u8>, String> {
+        let Schema::Record(record_schema) = &self.schema else {
+            unreachable!("checked in AvroEncoder::new");
+        };
+        let mut fields = Vec::with_capacity(record_schema.fields.len());
+        for field in &record_schema.fields {
+            let value = record.get2(&field.name).map(|f| f.get_value());
+            let avro = match (value, &field.default) {
+                (None | Some(Value::Null), Some(default)) if !is_nullable(&field.schema) => {
+                    AvroValue::from(default.clone())
+                        .resolve(&field.schema)
+                        .map_err(|e| format!("field '{}' default: {e}", field.name))?
+                }
+                _ => to_avro(value, &field.schema)
+                    .map_err(|e| format!("field '{}': {e}", field.name))?,
+            };
+            fields.push((field.name.clone(), avro));
+        }
+        let datum = apache_avro::to_avro_datum(&self.schema, AvroValue::Record(fields))
+            .map_err(|e| e.to_string())?;
+        Ok(frame(self.schema_id, &datum))
+    }
+}
+
+/// 加上 Confluent 线格式前缀
+pub(crate) fn frame(schema_id: u32, datum: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(5 + datum.len());
+    out.push(MAGIC_BYTE);
+    out.extend_from_slice(&schema_id.to_be_bytes());
+    out.extend_from_slice(datum);
+    out
+}
+
+fn is_nullable(schema: &Schema) -> bool {
+    match schema {
+        Schema::Null => true,
+        Schema::Union(union) => union.variants().contains(&Schema::Null),
+        _ => false,
+    }
+}
+
+/// 按目标 schema 转换字段值；缺失字段视为 null
+fn to_avro(value: Option<&Value>, schema: &Schema) -> Result<AvroValue, String> {
+    let value = value.filter(|v| !matches!(v, Value::Null));
+    match (schema, value) {
+        (Schema::Null, None) => Ok(AvroValue::Null),
+        (Schema::Union(union), _) => {
+            for (idx, variant) in union.variants().iter().enumerate() {
+                if let Ok(v) = to_avro(value, variant) {
+                    return Ok(AvroValue::Union(idx as u32, Box::new(v)));
+                }
+            }
+            Err("value matches no union branch".into())
+        }
+        (_, None) => Err("missing value".into()),
+        (Schema::Boolean, Some(Value::Bool(b))) => Ok(AvroValue::Boolean(*b)),
+        (Schema::Int, Some(Value::Digit(n))) => i32::try_from(*n)
+            .map(AvroValue::Int)
+            .map_err(|_| format!("{n} out of int range")),
+        (Schema::Long, Some(Value::Digit(n))) => Ok(AvroValue::Long(*n)),
+        (Schema::Float, Some(Value::Float(f))) => Ok(AvroValue::Float(*f as f32)),
+        (Schema::Float, Some(Value::Digit(n))) => Ok(AvroValue::Float(*n as f32)),
+        (Schema::Double, Some(Value::Float(f))) => Ok(AvroValue::Double(*f)),
+        (Schema::Double, Some(Value::Digit(n))) => Ok(AvroValue::Double(*n as f64)),
+        (Schema::TimestampMillis, Some(Value::Time(dt))) => {
+            Ok(AvroValue::TimestampMillis(dt.and_utc().timestamp_millis()))
+        }
+        (Schema::TimestampMillis, Some(Value::Digit(n))) => Ok(AvroValue::TimestampMillis(*n)),
+        (Schema::TimestampMicros, Some(Value::Time(dt))) => {
+            Ok(AvroValue::TimestampMicros(dt.and_utc().timestamp_micros()))
+        }
+        (Schema::TimestampMicros, Some(Value::Digit(n))) => Ok(AvroValue::TimestampMicros(*n)),
+        (Schema::String, Some(v)) => Ok(AvroValue::String(v.to_string())),
+        (Schema::Bytes, Some(v)) => Ok(AvroValue::Bytes(v.to_string().into_bytes())),
+        (Schema::Enum(e), Some(v)) => {
+            let symbol = v.to_string();
+            e.symbols
+                .iter()
+                .position(|s| *s == symbol)
+                .map(|idx| AvroValue::Enum(idx as u32, symbol.clone()))
+                .ok_or_else(|| format!("'{symbol}' is not an enum symbol"))
+        }
+        (schema, Some(v)) => Err(format!("cannot encode {v} as {schema:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apache_avro::from_avro_datum;
+    use httpmock::prelude::*;
+    use wp_model_core::model::DataField;
+
+    const SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "Event",
+        "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "host", "type": "string"},
+            {"name": "score", "type": ["null", "double"], "default": null},
+            {"name": "level", "type": "string", "default": "info"}
+        ]
+    }"#;
+
+    fn record() -> DataRecord {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 42));
+        record.append(DataField::from_chars("host", "edge-01"));
+        record
+    }
+
+    #[test]
+    fn encode_prefixes_magic_byte_and_schema_id() {
+        let encoder = AvroEncoder::new(SCHEMA, 7).expect("schema");
+        let bytes = encoder.encode(&record()).expect("encode");
+        assert_eq!(bytes[0], MAGIC_BYTE);
+        assert_eq!(&bytes[1..5], &7u32.to_be_bytes());
+
+        let decoded = from_avro_datum(&encoder.schema, &mut &bytes[5..], None).expect("decode");
+        assert_eq!(
+            decoded,
+            AvroValue::Record(vec![
+                ("id".into(), AvroValue::Long(42)),
+                ("host".into(), AvroValue::String("edge-01".into())),
+                (
+                    "score".into(),
+                    AvroValue::Union(0, Box::new(AvroValue::Null))
+                ),
+                ("level".into(), AvroValue::String("info".into())),
+            ])
+        );
+    }
+
+    #[test]
+    fn encode_rejects_missing_required_field_and_non_record_schema() {
+        let encoder = AvroEncoder::new(SCHEMA, 1).expect("schema");
+        let mut partial = DataRecord::default();
+        partial.append(DataField::from_digit("id", 1));
+        let err = encoder.encode(&partial).expect_err("host missing");
+        assert!(err.contains("field 'host'"), "{err}");
+
+        assert!(AvroEncoder::new(r#""string""#, 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn fetch_reads_latest_schema_from_registry() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/subjects/events-value/versions/latest");
+                then.status(200).json_body(serde_json::json!({
+                    "subject": "events-value",
+                    "version": 3,
+                    "id": 21,
+                    "schema": SCHEMA,
+                }));
+            })
+            .await;
+        let conf = AvroConf {
+            schema_registry_url: format!("{}/", server.base_url()),
+            subject: "events-value".into(),
+        };
+        let encoder = AvroEncoder::fetch(&conf).await.expect("fetch schema");
+        mock.assert_async().await;
+        let bytes = encoder.encode(&record()).expect("encode");
+        assert_eq!(&bytes[..5], &[MAGIC_BYTE, 0, 0, 0, 21]);
+    }
+}
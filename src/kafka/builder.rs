@@ -0,0 +1,158 @@
+//! 以 Rust 值直接构造 [`KafkaSink`]，无需拼装 `SinkSpec` 参数表
+//!
+//! 工厂解析参数后同样经由 [`KafkaSinkBuilder::build`] 创建 Sink，两条路径的行为一致。
+//! 构造器不做工厂中的参数校验（如 `acks` 取值、`manual` 分区须指定字段），由调用方保证。
+
+use std::time::Duration;
+
+use wp_model_core::model::fmt_def::TextFmt;
+
+use super::config::{KafkaSinkConf, OversizeAction, Partitioner};
+use super::sink::KafkaSink;
+use crate::utils::time_unit::TimeUnit;
+
+pub struct KafkaSinkBuilder {
+    pub(crate) conf: KafkaSinkConf,
+    pub(crate) fmt: TextFmt,
+}
+
+impl KafkaSinkBuilder {
+    /// 未设置的项与 `SinkSpec` 中不配置对应参数时相同
+    pub fn new(brokers: impl Into<String>, topic: impl Into<String>) -> Self {
+        Self {
+            conf: KafkaSinkConf {
+                brokers: brokers.into(),
+                topic: topic.into(),
+                num_partitions: 0,
+                replication: 0,
+                config: None,
+                ..KafkaSinkConf::default()
+            },
+            fmt: TextFmt::Json,
+        }
+    }
+
+    pub(crate) fn from_conf(conf: KafkaSinkConf, fmt: TextFmt) -> Self {
+        Self { conf, fmt }
+    }
+
+    /// 消息体的文本格式（对应 `value_fmt` / `fmt`）
+    pub fn fmt(mut self, fmt: TextFmt) -> Self {
+        self.fmt = fmt;
+        self
+    }
+
+    /// Topic 不存在时自动创建所用的分区数与副本数
+    pub fn topic_layout(mut self, num_partitions: i32, replication: i32) -> Self {
+        self.conf.num_partitions = num_partitions;
+        self.conf.replication = replication;
+        self
+    }
+
+    /// 构建时是否尝试创建 Topic，默认开启
+    pub fn auto_create_topic(mut self, enabled: bool) -> Self {
+        self.conf.auto_create_topic = enabled;
+        self
+    }
+
+    /// 追加一条 librdkafka 配置，形如 `key = value`
+    pub fn config(mut self, entry: impl Into<String>) -> Self {
+        self.conf
+            .config
+            .get_or_insert_with(Vec::new)
+            .push(entry.into());
+        self
+    }
+
+    /// 生产者确认级别：`0` | `1` | `all`
+    pub fn acks(mut self, acks: impl Into<String>) -> Self {
+        self.conf.acks = Some(acks.into());
+        self
+    }
+
+    pub fn delivery_timeout(mut self, timeout: Duration) -> Self {
+        self.conf.delivery_timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// 后台定时 flush 间隔
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.conf.flush_interval_ms = Some(interval.as_millis() as u64);
+        self
+    }
+
+    /// 生产者队列指标采集间隔
+    pub fn stats_interval(mut self, interval: Duration) -> Self {
+        self.conf.stats_interval_ms = Some(interval.as_millis() as u64);
+        self
+    }
+
+    /// 未确认消息数上限
+    pub fn max_inflight_messages(mut self, max: u64) -> Self {
+        self.conf.max_inflight_messages = Some(max);
+        self
+    }
+
+    /// 严格按输入顺序逐条投递，以吞吐换顺序
+    pub fn preserve_order(mut self, enabled: bool) -> Self {
+        self.conf.preserve_order = enabled;
+        self
+    }
+
+    /// 哈希分区策略；按字段指定分区请使用 [`Self::partition_field`]
+    pub fn partitioner(mut self, partitioner: Partitioner) -> Self {
+        self.conf.partitioner = Some(partitioner);
+        self
+    }
+
+    /// 以记录字段的整数值作为目标分区（`partitioner = manual`）
+    pub fn partition_field(mut self, field: impl Into<String>) -> Self {
+        self.conf.partitioner = Some(Partitioner::Manual);
+        self.conf.partition_field = Some(field.into());
+        self
+    }
+
+    /// 消息时间戳取自该字段，整数时间戳按 `unit` 换算
+    pub fn timestamp_field(mut self, field: impl Into<String>, unit: TimeUnit) -> Self {
+        self.conf.timestamp_field = Some(field.into());
+        self.conf.time_unit = unit;
+        self
+    }
+
+    pub fn client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.conf.client_id = Some(client_id.into());
+        self
+    }
+
+    /// 单条消息体的字节数上限，超出的消息丢弃并计数
+    pub fn max_message_bytes(mut self, max: u64) -> Self {
+        self.conf.max_message_bytes = Some(max);
+        self
+    }
+
+    /// 超出 `max_message_bytes` 的消息追加到该死信文件
+    pub fn oversize_dlq(mut self, path: impl Into<String>) -> Self {
+        self.conf.on_oversize = OversizeAction::Dlq;
+        self.conf.dlq_path = Some(path.into());
+        self
+    }
+
+    /// 将每条记录包裹进信封模板，见 [`crate::utils::envelope`]
+    pub fn envelope(mut self, template: impl Into<String>, source: Option<String>) -> Self {
+        self.conf.envelope = Some(template.into());
+        self.conf.envelope_source = source;
+        self
+    }
+
+    /// 消息 key 取自 `field`；`fmt` 为 `None` 时即字段值本身，否则按该格式格式化仅含 key 字段的记录
+    pub fn key_field(mut self, field: impl Into<String>, fmt: Option<TextFmt>) -> Self {
+        self.conf.key_field = Some(field.into());
+        self.conf.key_fmt = fmt.map(|fmt| fmt.to_string());
+        self
+    }
+
+    /// 创建生产者（及不存在的 Topic）并启动配置的后台任务
+    pub async fn build(self) -> anyhow::Result<KafkaSink> {
+        KafkaSink::from_conf(&self.conf, self.fmt).await
+    }
+}
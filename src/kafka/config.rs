@@ -2,8 +2,11 @@ use orion_conf::UvsConfFrom;
 use orion_conf::error::{ConfIOReason, OrionConfResult};
 use orion_error::ToStructError;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use wp_conf_base::structure::Validate;
 
+use crate::utils::time_unit::TimeUnit;
+
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
 pub struct KafkaSourceConf {
     pub key: String,
@@ -11,10 +14,147 @@ pub struct KafkaSourceConf {
     pub topic: Vec<String>,
     pub config: Option<Vec<String>>,
     pub enable: bool,
+    /// 消费延迟采集间隔（秒），0 表示关闭
+    #[serde(default = "default_lag_interval_secs")]
+    pub lag_interval_secs: u64,
+    /// 订阅前是否自动创建缺失的 Topic
+    #[serde(default = "default_auto_create_topics")]
+    pub auto_create_topics: bool,
+    /// 自动创建 Topic 时的分区数
+    #[serde(default = "default_source_topic_setting")]
+    pub num_partitions: i32,
+    /// 自动创建 Topic 时的副本数
+    #[serde(default = "default_source_topic_setting")]
+    pub replication: i32,
+    /// 在途（已产出、未 ack）消息上限，达到后暂停消费；`None` 表示不限制
+    #[serde(default)]
+    pub max_in_flight: Option<usize>,
+    /// 单次拉取的等待上限（毫秒）；到期或无消息时让出该时长后返回 `NotData`，`None` 表示一直等待
+    #[serde(default)]
+    pub poll_timeout_ms: Option<u64>,
+    /// 对应 librdkafka `client.id`，便于在 broker 侧识别连接来源
+    #[serde(default)]
+    pub client_id: Option<String>,
+    /// 对应 librdkafka `group.instance.id`（静态成员），滚动重启时避免触发再均衡；
+    /// `None` 表示动态成员
+    #[serde(default)]
+    pub group_instance_id: Option<String>,
+    /// 消息体的解析方式
+    #[serde(default)]
+    pub value_format: ValueFormat,
+    /// 是否丢弃空载荷（null / tombstone）消息；为 `false` 时以空载荷产出并带上
+    /// `kafka_tombstone` 标签
+    #[serde(default = "default_skip_empty_payloads")]
+    pub skip_empty_payloads: bool,
+    /// 后台预取的批次数；`None` 表示不预取
+    #[serde(default)]
+    pub prefetch: Option<usize>,
+    /// 按 Topic 覆盖的 `key=value` 配置，叠加在 `config` 之上；见 [`Self::split_by_topic_config`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic_config: Option<BTreeMap<String, Vec<String>>>,
+    /// 双向 TLS（mTLS）证书配置
+    #[serde(flatten)]
+    pub tls: KafkaTlsConf,
     //#[serde(default)]
     //pub tags: Vec<String>,
 }
 
+pub(crate) const DEFAULT_LAG_INTERVAL_SECS: u64 = 30;
+
+/// librdkafka 将 `^` 开头的订阅项视为正则表达式
+pub(crate) fn is_topic_pattern(topic: &str) -> bool {
+    topic.starts_with('^')
+}
+
+/// 未显式配置 `client_id` 时由连接器方向与名称派生，例如 `wp-kafka-sink-orders`
+pub(crate) fn default_client_id(scope: &str, name: &str) -> String {
+    format!("wp-kafka-{scope}-{name}")
+}
+
+fn default_lag_interval_secs() -> u64 {
+    DEFAULT_LAG_INTERVAL_SECS
+}
+
+fn default_auto_create_topics() -> bool {
+    true
+}
+
+fn default_skip_empty_payloads() -> bool {
+    true
+}
+
+fn default_source_topic_setting() -> i32 {
+    1
+}
+
+impl KafkaSourceConf {
+    /// 生成消费者配置映射：先写入类型化参数，再应用 `config` 原始条目（后者可覆盖前者）。
+    pub(crate) fn consumer_config(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        if let Some(client_id) = &self.client_id {
+            map.insert("client.id".to_string(), client_id.clone());
+        }
+        if let Some(instance_id) = &self.group_instance_id {
+            map.insert("group.instance.id".to_string(), instance_id.clone());
+        }
+        if let Some(items) = &self.config {
+            for (key, value) in config_entries_to_map(items) {
+                map.insert(key.to_string(), value.to_string());
+            }
+        }
+        self.tls.apply(&mut map);
+        map
+    }
+}
+
+impl KafkaSourceConf {
+    /// 按 `topic_config` 拆分为多个消费者配置。
+    ///
+    /// librdkafka 的 Topic 级配置（如 `auto.offset.reset`）对同一消费者订阅的所有 Topic 生效，
+    /// 因此覆盖项相同的 Topic 归为一组，每组使用一个消费者（同一 `group_id`），其 `config`
+    /// 为全局条目后接该组的覆盖条目。多于一组时，`key` 与 `group.instance.id` 追加组序号以保持唯一。
+    /// 未配置 `topic_config` 时返回自身。
+    pub fn split_by_topic_config(&self) -> Vec<KafkaSourceConf> {
+        let Some(overrides) = &self.topic_config else {
+            return vec![self.clone()];
+        };
+        // 原始覆盖条目、解析后的键值对（用于比较，忽略 `=` 两侧空白等书写差异）、组内 Topic
+        type TopicGroup<'a> = (&'a [String], BTreeMap<&'a str, &'a str>, Vec<String>);
+        let mut groups: Vec<TopicGroup> = Vec::new();
+        for topic in &self.topic {
+            let items = overrides.get(topic).map(Vec::as_slice).unwrap_or(&[]);
+            let pairs: BTreeMap<_, _> = config_entries_to_map(items).into_iter().collect();
+            match groups.iter_mut().find(|(_, key, _)| *key == pairs) {
+                Some((_, _, topics)) => topics.push(topic.clone()),
+                None => groups.push((items, pairs, vec![topic.clone()])),
+            }
+        }
+        let split = groups.len() > 1;
+        groups
+            .into_iter()
+            .enumerate()
+            .map(|(index, (items, _, topics))| {
+                let mut conf = self.clone();
+                conf.topic = topics;
+                conf.topic_config = None;
+                if !items.is_empty() {
+                    let mut config = self.config.clone().unwrap_or_default();
+                    config.extend(items.iter().cloned());
+                    conf.config = Some(config);
+                }
+                if split {
+                    conf.key = format!("{}-{index}", self.key);
+                    conf.group_instance_id = self
+                        .group_instance_id
+                        .as_ref()
+                        .map(|id| format!("{id}-{index}"));
+                }
+                conf
+            })
+            .collect()
+    }
+}
+
 impl Validate for KafkaSourceConf {
     fn validate(&self) -> OrionConfResult<()> {
         if self.brokers.trim().is_empty() {
@@ -35,9 +175,192 @@ impl Validate for KafkaSourceConf {
 pub struct KafkaSinkConf {
     pub brokers: String,
     pub topic: String,
+    /// 构建时是否尝试创建 Topic；创建失败但 Topic 已存在时仅告警
+    #[serde(default = "default_auto_create_topics")]
+    pub auto_create_topic: bool,
     pub num_partitions: i32,
     pub replication: i32,
     pub config: Option<Vec<String>>,
+    /// 生产者确认级别：`0` | `1` | `all`
+    #[serde(default)]
+    pub acks: Option<String>,
+    /// 消息投递超时（毫秒），对应 `delivery.timeout.ms`
+    #[serde(default)]
+    pub delivery_timeout_ms: Option<u64>,
+    /// 后台定时 flush 间隔（毫秒），未设置时不启动 flush 任务
+    #[serde(default)]
+    pub flush_interval_ms: Option<u64>,
+    /// 生产者队列指标采集间隔（毫秒），未设置时不采集
+    #[serde(default)]
+    pub stats_interval_ms: Option<u64>,
+    /// 未确认（在途）消息数上限，达到后发送前等待已有消息投递完成；未设置时不限制
+    #[serde(default)]
+    pub max_inflight_messages: Option<u64>,
+    /// 严格保序：逐条等待投递确认后再发送下一条，且每个连接只允许一个在途请求，
+    /// 避免重试导致乱序；以吞吐换顺序，开启后 `max_inflight_messages` 视为 1
+    #[serde(default)]
+    pub preserve_order: bool,
+    /// 分区策略，对应 librdkafka `partitioner`；`manual` 时由 `partition_field` 指定分区
+    #[serde(default)]
+    pub partitioner: Option<Partitioner>,
+    /// `manual` 模式下取该字段的整数值作为目标分区
+    #[serde(default)]
+    pub partition_field: Option<String>,
+    /// 消息时间戳取自该字段（事件时间）；未配置或记录缺少该字段时使用生产时间
+    #[serde(default)]
+    pub timestamp_field: Option<String>,
+    /// `timestamp_field` 为整数时间戳时的单位
+    #[serde(default)]
+    pub time_unit: TimeUnit,
+    /// 对应 librdkafka `client.id`，便于在 broker 侧识别连接来源
+    #[serde(default)]
+    pub client_id: Option<String>,
+    /// `fmt = avro` 时的 Schema Registry 配置；为 `None` 时按文本格式输出
+    #[serde(default)]
+    pub avro: Option<AvroConf>,
+    /// 单条消息体的字节数上限；超出的消息不交给生产者，按 `on_oversize` 处理。未设置时不检查
+    #[serde(default)]
+    pub max_message_bytes: Option<u64>,
+    /// 超出 `max_message_bytes` 的消息的处理方式
+    #[serde(default)]
+    pub on_oversize: OversizeAction,
+    /// `on_oversize = dlq` 时的死信文件，每行一个 `{"error":..,"bytes":..,"payload":..}` JSON
+    #[serde(default)]
+    pub dlq_path: Option<String>,
+    /// 消息信封模板，见 [`crate::utils::envelope`]；未设置时消息体为格式化后的记录
+    #[serde(default)]
+    pub envelope: Option<String>,
+    /// 信封中 `{source}` 占位符的取值，缺省 `wp`
+    #[serde(default)]
+    pub envelope_source: Option<String>,
+    /// 消息 key 取自该字段；未设置或记录缺少该字段时消息不带 key
+    #[serde(default)]
+    pub key_field: Option<String>,
+    /// key 的文本格式（取值同 `fmt`），按仅含 key 字段的记录格式化；为 `None` 时即字段值本身（`raw`）
+    #[serde(default)]
+    pub key_fmt: Option<String>,
+    /// 双向 TLS（mTLS）证书配置
+    #[serde(flatten)]
+    pub tls: KafkaTlsConf,
+}
+
+/// 双向 TLS 的证书与私钥（PEM 文件路径），对应 librdkafka `ssl.*` 配置项
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Default)]
+pub struct KafkaTlsConf {
+    #[serde(default)]
+    pub ssl_ca_location: Option<String>,
+    #[serde(default)]
+    pub ssl_certificate_location: Option<String>,
+    #[serde(default)]
+    pub ssl_key_location: Option<String>,
+    #[serde(default)]
+    pub ssl_key_password: Option<String>,
+}
+
+impl KafkaTlsConf {
+    fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// 写入 `ssl.*` 配置项；`config` 原始条目中的同名键优先。
+    ///
+    /// 未显式设置 `security.protocol` 时：配置了 `sasl.*` 项则为 `SASL_SSL`，否则为 `SSL`。
+    fn apply(&self, map: &mut HashMap<String, String>) {
+        if self.is_empty() {
+            return;
+        }
+        for (key, value) in [
+            ("ssl.ca.location", &self.ssl_ca_location),
+            ("ssl.certificate.location", &self.ssl_certificate_location),
+            ("ssl.key.location", &self.ssl_key_location),
+            ("ssl.key.password", &self.ssl_key_password),
+        ] {
+            if let Some(value) = value {
+                map.entry(key.to_string()).or_insert_with(|| value.clone());
+            }
+        }
+        let sasl = map.keys().any(|key| key.starts_with("sasl."));
+        map.entry("security.protocol".to_string())
+            .or_insert_with(|| if sasl { "SASL_SSL" } else { "SSL" }.to_string());
+    }
+}
+
+/// Avro 序列化所需的 Schema Registry 配置
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+pub struct AvroConf {
+    pub schema_registry_url: String,
+    /// 缺省为 `<topic>-value`（Confluent TopicNameStrategy）
+    pub subject: String,
+}
+
+/// 超出 `max_message_bytes` 的消息的处理方式
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OversizeAction {
+    /// 丢弃并计数
+    #[default]
+    Skip,
+    /// 丢弃、计数并追加到死信文件（`dlq_path`）
+    Dlq,
+}
+
+/// 生产者分区策略
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Partitioner {
+    /// key 的 CRC32 哈希，空 key 落到同一分区
+    Consistent,
+    /// key 的 CRC32 哈希，空 key 随机分区（librdkafka 默认）
+    ConsistentRandom,
+    /// 与 Java 客户端一致的 murmur2 哈希
+    Murmur2,
+    /// 由记录字段直接指定分区
+    Manual,
+}
+
+impl Partitioner {
+    /// 解析 `partitioner` 参数
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "consistent" => Some(Self::Consistent),
+            "consistent_random" => Some(Self::ConsistentRandom),
+            "murmur2" => Some(Self::Murmur2),
+            "manual" => Some(Self::Manual),
+            _ => None,
+        }
+    }
+
+    /// 对应的 librdkafka `partitioner` 取值；`manual` 不修改 librdkafka 配置
+    pub fn librdkafka_value(self) -> Option<&'static str> {
+        match self {
+            Self::Consistent => Some("consistent"),
+            Self::ConsistentRandom => Some("consistent_random"),
+            Self::Murmur2 => Some("murmur2"),
+            Self::Manual => None,
+        }
+    }
+}
+
+/// Source 消息体的解析方式
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueFormat {
+    /// 原样透传字节，由下游解析
+    #[default]
+    Raw,
+    /// 在 Source 侧解析为 JSON 对象，输出规范化后的记录
+    Json,
+}
+
+impl ValueFormat {
+    /// 解析 `value_format` 参数
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "raw" => Some(Self::Raw),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
 }
 
 impl KafkaSinkConf {
@@ -47,6 +370,36 @@ impl KafkaSinkConf {
             ..Self::default()
         }
     }
+
+    /// 生成生产者配置映射：先写入类型化参数，再应用 `config` 原始条目（后者可覆盖前者）。
+    pub(crate) fn producer_config(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        if let Some(client_id) = &self.client_id {
+            map.insert("client.id".to_string(), client_id.clone());
+        }
+        if let Some(acks) = &self.acks {
+            map.insert("acks".to_string(), acks.clone());
+        }
+        if let Some(timeout) = self.delivery_timeout_ms {
+            map.insert("delivery.timeout.ms".to_string(), timeout.to_string());
+        }
+        if self.preserve_order {
+            map.insert(
+                "max.in.flight.requests.per.connection".to_string(),
+                "1".to_string(),
+            );
+        }
+        if let Some(partitioner) = self.partitioner.and_then(Partitioner::librdkafka_value) {
+            map.insert("partitioner".to_string(), partitioner.to_string());
+        }
+        if let Some(items) = &self.config {
+            for (key, value) in config_entries_to_map(items) {
+                map.insert(key.to_string(), value.to_string());
+            }
+        }
+        self.tls.apply(&mut map);
+        map
+    }
 }
 
 impl Default for KafkaSourceConf {
@@ -63,6 +416,19 @@ impl Default for KafkaSourceConf {
                 "auto.offset.reset = earliest".to_string(),
             ]),
             enable: false,
+            lag_interval_secs: DEFAULT_LAG_INTERVAL_SECS,
+            auto_create_topics: true,
+            num_partitions: 1,
+            replication: 1,
+            max_in_flight: None,
+            poll_timeout_ms: None,
+            client_id: None,
+            group_instance_id: None,
+            value_format: ValueFormat::Raw,
+            skip_empty_payloads: true,
+            prefetch: None,
+            topic_config: None,
+            tls: KafkaTlsConf::default(),
         }
     }
 }
@@ -72,6 +438,7 @@ impl Default for KafkaSinkConf {
         Self {
             brokers: "localhost:9092".to_string(),
             topic: "test".to_string(),
+            auto_create_topic: true,
             num_partitions: 3,
             replication: 1,
             config: Some(vec![
@@ -79,6 +446,117 @@ impl Default for KafkaSinkConf {
                 "queue.buffering.max.kbytes = 2147483647".to_string(),
                 "message.max.bytes = 10485760".to_string(),
             ]),
+            acks: None,
+            delivery_timeout_ms: None,
+            flush_interval_ms: None,
+            stats_interval_ms: None,
+            max_inflight_messages: None,
+            preserve_order: false,
+            partitioner: None,
+            partition_field: None,
+            timestamp_field: None,
+            time_unit: TimeUnit::default(),
+            client_id: None,
+            avro: None,
+            max_message_bytes: None,
+            on_oversize: OversizeAction::default(),
+            dlq_path: None,
+            envelope: None,
+            envelope_source: None,
+            key_field: None,
+            key_fmt: None,
+            tls: KafkaTlsConf::default(),
         }
     }
 }
+
+/// 常见的 librdkafka 配置项，用于在校验阶段提示可能的拼写错误（不在列表中的键仅告警）。
+pub(crate) const KNOWN_CONFIG_KEYS: &[&str] = &[
+    "acks",
+    "allow.auto.create.topics",
+    "api.version.request",
+    "auto.commit.interval.ms",
+    "auto.offset.reset",
+    "batch.num.messages",
+    "batch.size",
+    "bootstrap.servers",
+    "broker.address.family",
+    "check.crcs",
+    "client.id",
+    "client.rack",
+    "compression.codec",
+    "compression.level",
+    "compression.type",
+    "delivery.timeout.ms",
+    "enable.auto.commit",
+    "enable.auto.offset.store",
+    "enable.idempotence",
+    "enable.partition.eof",
+    "fetch.max.bytes",
+    "fetch.min.bytes",
+    "fetch.wait.max.ms",
+    "group.id",
+    "group.instance.id",
+    "heartbeat.interval.ms",
+    "isolation.level",
+    "linger.ms",
+    "max.in.flight.requests.per.connection",
+    "max.partition.fetch.bytes",
+    "max.poll.interval.ms",
+    "message.max.bytes",
+    "message.send.max.retries",
+    "message.timeout.ms",
+    "metadata.max.age.ms",
+    "partition.assignment.strategy",
+    "partitioner",
+    "queue.buffering.max.kbytes",
+    "queue.buffering.max.messages",
+    "queue.buffering.max.ms",
+    "queued.max.messages.kbytes",
+    "queued.min.messages",
+    "receive.message.max.bytes",
+    "reconnect.backoff.max.ms",
+    "reconnect.backoff.ms",
+    "request.required.acks",
+    "request.timeout.ms",
+    "retries",
+    "retry.backoff.ms",
+    "sasl.kerberos.service.name",
+    "sasl.mechanism",
+    "sasl.mechanisms",
+    "sasl.oauthbearer.config",
+    "sasl.password",
+    "sasl.username",
+    "security.protocol",
+    "session.timeout.ms",
+    "socket.keepalive.enable",
+    "socket.timeout.ms",
+    "ssl.ca.location",
+    "ssl.certificate.location",
+    "ssl.endpoint.identification.algorithm",
+    "ssl.key.location",
+    "ssl.key.password",
+    "statistics.interval.ms",
+    "topic.metadata.refresh.interval.ms",
+    "transactional.id",
+];
+
+/// 将 `key=value` 形式的配置项拆分为键值对（仅按第一个 `=` 切分，值中允许出现 `=`）。
+///
+/// 键或值为空时返回 `None`。
+pub(crate) fn split_config_entry(entry: &str) -> Option<(&str, &str)> {
+    let (key, value) = entry.split_once('=')?;
+    let (key, value) = (key.trim(), value.trim());
+    if key.is_empty() || value.is_empty() {
+        return None;
+    }
+    Some((key, value))
+}
+
+/// 将 `config` 列表转换为 librdkafka 配置映射，忽略无法解析的条目。
+pub(crate) fn config_entries_to_map(items: &[String]) -> HashMap<&str, &str> {
+    items
+        .iter()
+        .filter_map(|item| split_config_entry(item))
+        .collect()
+}
@@ -0,0 +1,98 @@
+//! Kafka Source 消息体解析（`value_format`）
+//!
+//! `raw` 原样透传字节；`json` 在 Source 侧把消息体解析为 [`DataRecord`]（字段名 → 类型化值），
+//! 再以规范化 JSON 写入事件。解析失败的消息不会下发，计入
+//! `wparse_kafka_decode_errors_total` 并记录日志。
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use lazy_static::lazy_static;
+use prometheus::{IntCounterVec, register_int_counter_vec};
+use serde_json::Value as JsonValue;
+use wp_model_core::model::{DataField, DataRecord};
+use wp_model_core::raw::RawData;
+
+use crate::kafka::config::ValueFormat;
+use crate::utils::fmt::fmt_str_ndjson;
+
+lazy_static! {
+    pub static ref KAFKA_DECODE_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "wparse_kafka_decode_errors_total",
+        "Kafka messages dropped because the payload did not match value_format.",
+        &["source", "format"]
+    )
+    .expect("register wparse_kafka_decode_errors_total fail");
+}
+
+/// 按 `value_format` 转换消息体
+pub(crate) fn decode_payload(format: ValueFormat, payload: &[u8]) -> Result<RawData, String> {
+    match format {
+        ValueFormat::Raw => Ok(RawData::Bytes(Bytes::copy_from_slice(payload))),
+        ValueFormat::Json => {
+            let record = json_to_record(payload)?;
+            Ok(RawData::from_string(fmt_str_ndjson(vec![Arc::new(record)])))
+        }
+    }
+}
+
+/// 将 JSON 对象解析为记录：布尔、整数、浮点、字符串保留类型，
+/// 嵌套对象/数组以 JSON 文本保存，`null` 字段跳过
+pub(crate) fn json_to_record(payload: &[u8]) -> Result<DataRecord, String> {
+    let value: JsonValue =
+        serde_json::from_slice(payload).map_err(|e| format!("invalid json: {e}"))?;
+    let JsonValue::Object(map) = value else {
+        return Err("json payload must be an object".into());
+    };
+    let mut record = DataRecord::default();
+    for (name, value) in map {
+        let field = match value {
+            JsonValue::Null => continue,
+            JsonValue::Bool(b) => DataField::from_bool(name, b),
+            JsonValue::Number(n) => match n.as_i64() {
+                Some(i) => DataField::from_digit(name, i),
+                None => DataField::from_float(name, n.as_f64().unwrap_or(f64::NAN)),
+            },
+            JsonValue::String(s) => DataField::from_chars(name, s),
+            nested => DataField::from_chars(name, nested.to_string()),
+        };
+        record.append(field);
+    }
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_model_core::model::Value;
+
+    #[test]
+    fn json_payload_becomes_typed_record() {
+        let payload =
+            br#"{"host":"edge-01","status":200,"latency":1.5,"ok":true,"tags":["a"],"trace":null}"#;
+        let record = json_to_record(payload).expect("record");
+        let value = |name: &str| record.get2(name).map(|f| f.get_value().clone());
+        assert_eq!(value("host"), Some(Value::Chars("edge-01".into())));
+        assert_eq!(value("status"), Some(Value::Digit(200)));
+        assert_eq!(value("latency"), Some(Value::Float(1.5)));
+        assert_eq!(value("ok"), Some(Value::Bool(true)));
+        assert_eq!(value("tags"), Some(Value::Chars(r#"["a"]"#.into())));
+        assert!(record.get2("trace").is_none());
+
+        let RawData::String(text) = decode_payload(ValueFormat::Json, payload).expect("decode")
+        else {
+            panic!("json mode emits a string payload");
+        };
+        let normalized: JsonValue = serde_json::from_str(&text).expect("normalized json");
+        assert_eq!(normalized["status"], serde_json::json!(200));
+        assert_eq!(normalized["host"], serde_json::json!("edge-01"));
+    }
+
+    #[test]
+    fn malformed_json_is_rejected_and_raw_passes_through() {
+        assert!(json_to_record(b"{not json").is_err());
+        assert!(json_to_record(b"[1,2]").unwrap_err().contains("object"));
+        let raw = decode_payload(ValueFormat::Raw, b"{not json").expect("raw");
+        assert_eq!(raw.as_bytes(), b"{not json");
+    }
+}
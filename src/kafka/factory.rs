@@ -1,19 +1,36 @@
 use async_trait::async_trait;
 use serde_json::{Value, json};
+use std::collections::BTreeMap;
 
+use rdkafka_wrap::KWError;
 use wp_conf_base::ConfParser;
 use wp_connector_api::{
-    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError, SinkFactory,
-    SinkHandle, SinkReason, SinkResult, SinkSpec, SourceDefProvider, SourceFactory, SourceHandle,
-    SourceMeta, SourceReason, SourceResult, SourceSvcIns, Tags,
+    ConnectorDef, ConnectorScope, DataSource, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError,
+    SinkFactory, SinkHandle, SinkReason, SinkResult, SinkSpec, SourceDefProvider, SourceFactory,
+    SourceHandle, SourceMeta, SourceReason, SourceResult, SourceSvcIns, Tags,
 };
 use wp_model_core::model::fmt_def::TextFmt;
 
 use crate::WP_SRC_VAL;
+use crate::kafka::preflight::{PREFLIGHT_TIMEOUT, check_connectivity};
+use crate::kafka::sink::{DisabledKafkaSink, kafka_error_kind};
 use crate::kafka::{
-    KafkaSink, KafkaSource,
-    config::{KafkaSinkConf, KafkaSourceConf},
+    KafkaSinkBuilder, KafkaSource,
+    config::{
+        AvroConf, DEFAULT_LAG_INTERVAL_SECS, KNOWN_CONFIG_KEYS, KafkaSinkConf, KafkaSourceConf,
+        KafkaTlsConf, OversizeAction, Partitioner, ValueFormat, default_client_id,
+        is_topic_pattern, split_config_entry,
+    },
 };
+use crate::plan::{SinkPlanner, SourcePlanner, redact_secrets};
+use crate::prefetch::{PREFETCH_PARAM, PrefetchSource, parse_prefetch};
+use crate::utils::config_blob::{self, CONFIG_BLOB_PARAM};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::envelope::{ENVELOPE_PARAM, ENVELOPE_PARAMS, ENVELOPE_SOURCE_PARAM, Envelope};
+use crate::utils::error_kind::ErrorKind;
+use crate::utils::fmt::{TEXT_FMT_ALLOWED, parse_text_fmt};
+use crate::utils::ingest_time::{INGEST_TIME_FIELD_PARAM, INGEST_TIME_UNIT_PARAM, IngestTime};
+use crate::utils::time_unit::TimeUnit;
 
 fn build_kafka_conf_from_spec(
     spec: &wp_connector_api::SourceSpec,
@@ -22,14 +39,67 @@ fn build_kafka_conf_from_spec(
     let topics = parse_topics(spec.params.get("topic"))?;
     let group_id = parse_required_string(spec.params.get("group_id"), "kafka.group_id")?;
     let config = parse_config(spec.params.get("config"))?;
+    let topic_config = parse_topic_config(spec.params.get("topic_config"), &topics)?;
+    let lag_interval_secs = parse_lag_interval(spec.params.get("lag_interval_secs"))?;
+    let auto_create_topics = match spec.params.get("auto_create_topics") {
+        None => true,
+        Some(Value::Bool(b)) => *b,
+        Some(_) => {
+            return Err(
+                SourceReason::Other("kafka.auto_create_topics must be a bool".into()).into(),
+            );
+        }
+    };
+    let num_partitions = parse_source_positive_i32(
+        spec.params.get("source_num_partitions"),
+        "kafka.source_num_partitions",
+    )?;
+    let replication = parse_source_positive_i32(
+        spec.params.get("source_replication"),
+        "kafka.source_replication",
+    )?;
+    let max_in_flight = parse_max_in_flight(spec.params.get("max_in_flight"))?;
+    let poll_timeout_ms =
+        parse_source_positive_u64(spec.params.get("poll_timeout_ms"), "kafka.poll_timeout_ms")?;
+    let enable =
+        parse_enable(spec.params.get("enable")).map_err(|msg| SourceReason::Other(msg.into()))?;
+    let client_id = parse_client_id(spec.params.get("client_id"))
+        .map_err(|msg| SourceReason::Other(msg.into()))?
+        .unwrap_or_else(|| default_client_id("source", &spec.name));
+    let group_instance_id = parse_group_instance_id(spec.params.get("group_instance_id"))
+        .map_err(|msg| SourceReason::Other(msg.into()))?;
+    let value_format = parse_value_format(spec.params.get("value_format"))?;
+    let skip_empty_payloads = match spec.params.get("skip_empty_payloads") {
+        None => true,
+        Some(Value::Bool(b)) => *b,
+        Some(_) => {
+            return Err(
+                SourceReason::Other("kafka.skip_empty_payloads must be a bool".into()).into(),
+            );
+        }
+    };
+    let prefetch = parse_prefetch("kafka", spec.params.get(PREFETCH_PARAM))?;
+    let tls = parse_tls(&spec.params).map_err(SourceReason::Other)?;
 
     let conf = KafkaSourceConf {
         key: spec.name.clone(),
         brokers,
         topic: topics,
         config,
-        //TODO: use spec.enable
-        enable: true,
+        enable,
+        lag_interval_secs,
+        auto_create_topics,
+        num_partitions: num_partitions.unwrap_or(1),
+        replication: replication.unwrap_or(1),
+        max_in_flight,
+        poll_timeout_ms,
+        client_id: Some(client_id),
+        group_instance_id,
+        value_format,
+        skip_empty_payloads,
+        prefetch,
+        topic_config,
+        tls,
     };
     Ok((conf, group_id))
 }
@@ -41,18 +111,213 @@ fn build_kafka_sink_conf_from_spec(spec: &SinkSpec) -> SinkResult<(KafkaSinkConf
         parse_positive_i32(spec.params.get("num_partitions"), "kafka.num_partitions")?;
     let replication = parse_positive_i32(spec.params.get("replication"), "kafka.replication")?;
     let config = parse_sink_config(spec.params.get("config"))?;
-    let fmt = parse_sink_fmt(spec.params.get("fmt"))?;
+    // `value_fmt` 优先于 `fmt`，两者均指消息体格式
+    let value_fmt = spec
+        .params
+        .get("value_fmt")
+        .or_else(|| spec.params.get("fmt"));
+    let avro_mode = value_fmt.and_then(Value::as_str).map(str::trim) == Some("avro");
+    // avro 不属于文本格式，文本格式化器不会被使用
+    let fmt = if avro_mode {
+        TextFmt::Json
+    } else {
+        parse_text_fmt("kafka", value_fmt)?
+    };
+    let avro = parse_avro(spec, &topic, avro_mode)?;
+    if Envelope::from_params("kafka", &spec.params)?.is_some() && avro_mode {
+        return Err(SinkReason::sink("kafka.envelope cannot be combined with fmt = avro").into());
+    }
+    let acks = parse_acks(spec.params.get("acks"))?;
+    let delivery_timeout_ms = parse_positive_u64(
+        spec.params.get("delivery_timeout_ms"),
+        "kafka.delivery_timeout_ms",
+    )?;
+    let flush_interval_ms = parse_positive_u64(
+        spec.params.get("flush_interval_ms"),
+        "kafka.flush_interval_ms",
+    )?;
+    let stats_interval_ms = parse_positive_u64(
+        spec.params.get("stats_interval_ms"),
+        "kafka.stats_interval_ms",
+    )?;
+    let max_inflight_messages = parse_positive_u64(
+        spec.params.get("max_inflight_messages"),
+        "kafka.max_inflight_messages",
+    )?;
+    let (partitioner, partition_field) = parse_partitioner(spec)?;
+    let (timestamp_field, time_unit) = parse_timestamp_field(spec)?;
+    let (key_field, key_fmt) = parse_key(spec)?;
+    let (max_message_bytes, on_oversize, dlq_path) = parse_oversize(spec)?;
+    let client_id = parse_client_id(spec.params.get("client_id"))
+        .map_err(SinkReason::sink)?
+        .unwrap_or_else(|| default_client_id("sink", &spec.name));
+    let tls = parse_tls(&spec.params).map_err(SinkReason::sink)?;
+    let auto_create_topic = match spec.params.get("auto_create_topic") {
+        None => true,
+        Some(Value::Bool(b)) => *b,
+        Some(_) => return Err(SinkReason::sink("kafka.auto_create_topic must be a bool").into()),
+    };
+    let preserve_order = match spec.params.get("preserve_order") {
+        None => false,
+        Some(Value::Bool(b)) => *b,
+        Some(_) => return Err(SinkReason::sink("kafka.preserve_order must be a bool").into()),
+    };
 
     let conf = KafkaSinkConf {
         brokers,
         topic,
+        auto_create_topic,
         num_partitions: num_partitions.unwrap_or_default(),
         replication: replication.unwrap_or_default(),
         config,
+        acks,
+        delivery_timeout_ms,
+        flush_interval_ms,
+        stats_interval_ms,
+        max_inflight_messages,
+        preserve_order,
+        partitioner,
+        partition_field,
+        timestamp_field,
+        time_unit,
+        client_id: Some(client_id),
+        avro,
+        max_message_bytes,
+        on_oversize,
+        dlq_path,
+        envelope: spec
+            .params
+            .get(ENVELOPE_PARAM)
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        envelope_source: spec
+            .params
+            .get(ENVELOPE_SOURCE_PARAM)
+            .and_then(Value::as_str)
+            .map(|s| s.trim().to_string()),
+        key_field,
+        key_fmt,
+        tls,
     };
     Ok((conf, fmt))
 }
 
+/// 解析 sink 的全部参数：连接配置、输出格式以及 `enable` / `validate_connectivity` 开关
+fn parse_sink_spec(spec: &SinkSpec) -> SinkResult<(KafkaSinkConf, TextFmt, bool, bool)> {
+    validate_decorators(spec)?;
+    let (conf, fmt) = build_kafka_sink_conf_from_spec(spec)?;
+    let enable = parse_enable(spec.params.get("enable")).map_err(SinkReason::sink)?;
+    let preflight = parse_validate_connectivity(spec.params.get("validate_connectivity"))
+        .map_err(SinkReason::sink)?;
+    Ok((conf, fmt, enable, preflight))
+}
+
+/// 解析 `enable` 开关，缺省为启用
+fn parse_enable(value: Option<&Value>) -> Result<bool, &'static str> {
+    match value {
+        None => Ok(true),
+        Some(Value::Bool(b)) => Ok(*b),
+        Some(_) => Err("kafka.enable must be a bool"),
+    }
+}
+
+/// 解析 `fmt = avro` 的 `schema_registry_url` / `subject`；其它格式下不允许设置这两个参数
+fn parse_avro(spec: &SinkSpec, topic: &str, avro_mode: bool) -> SinkResult<Option<AvroConf>> {
+    let url = spec.params.get("schema_registry_url");
+    let subject = spec.params.get("subject");
+    if !avro_mode {
+        if url.is_some() || subject.is_some() {
+            return Err(SinkReason::sink(
+                "kafka.schema_registry_url / kafka.subject only apply when kafka.fmt = avro",
+            )
+            .into());
+        }
+        return Ok(None);
+    }
+    if cfg!(not(feature = "avro")) {
+        return Err(
+            SinkReason::sink("kafka.fmt = avro requires the `avro` feature to be enabled").into(),
+        );
+    }
+    let schema_registry_url = match url.and_then(Value::as_str).map(str::trim) {
+        Some(u) if u.starts_with("http://") || u.starts_with("https://") => u.to_string(),
+        _ => {
+            return Err(SinkReason::sink(
+                "kafka.schema_registry_url must be an http(s) url when kafka.fmt = avro",
+            )
+            .into());
+        }
+    };
+    let subject = match subject {
+        None => format!("{topic}-value"),
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(s) if !s.is_empty() => s.to_string(),
+            _ => return Err(SinkReason::sink("kafka.subject must be a non-empty string").into()),
+        },
+    };
+    Ok(Some(AvroConf {
+        schema_registry_url,
+        subject,
+    }))
+}
+
+/// 解析 `client_id`；未设置时返回 `None`，由调用方按连接器名称派生
+fn parse_client_id(value: Option<&Value>) -> Result<Option<String>, &'static str> {
+    match value {
+        None => Ok(None),
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(id) if !id.is_empty() => Ok(Some(id.to_string())),
+            _ => Err("kafka.client_id must be a non-empty string"),
+        },
+    }
+}
+
+/// 解析 `group_instance_id`（静态成员标识）；未设置时返回 `None`，保持动态成员
+fn parse_group_instance_id(value: Option<&Value>) -> Result<Option<String>, &'static str> {
+    match value {
+        None => Ok(None),
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(id) if !id.is_empty() => Ok(Some(id.to_string())),
+            _ => Err("kafka.group_instance_id must be a non-empty string"),
+        },
+    }
+}
+
+/// 解析 mTLS 证书参数；客户端证书与私钥必须成对配置
+fn parse_tls(params: &ParamMap) -> Result<KafkaTlsConf, String> {
+    let path = |key: &str| match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(s) if !s.is_empty() => Ok(Some(s.to_string())),
+            _ => Err(format!("kafka.{key} must be a non-empty string")),
+        },
+    };
+    let tls = KafkaTlsConf {
+        ssl_ca_location: path("ssl_ca_location")?,
+        ssl_certificate_location: path("ssl_certificate_location")?,
+        ssl_key_location: path("ssl_key_location")?,
+        ssl_key_password: path("ssl_key_password")?,
+    };
+    if tls.ssl_certificate_location.is_some() != tls.ssl_key_location.is_some() {
+        return Err(
+            "kafka.ssl_certificate_location and kafka.ssl_key_location must be set together".into(),
+        );
+    }
+    if tls.ssl_key_password.is_some() && tls.ssl_key_location.is_none() {
+        return Err("kafka.ssl_key_password requires kafka.ssl_key_location".into());
+    }
+    Ok(tls)
+}
+
+/// 解析 `validate_connectivity` 开关，缺省关闭以保持校验离线、低开销
+fn parse_validate_connectivity(value: Option<&Value>) -> Result<bool, &'static str> {
+    match value {
+        None => Ok(false),
+        Some(Value::Bool(b)) => Ok(*b),
+        Some(_) => Err("kafka.validate_connectivity must be a bool"),
+    }
+}
+
 fn parse_required_string(value: Option<&Value>, field: &str) -> SourceResult<String> {
     if let Some(Value::String(raw)) = value {
         let trimmed = raw.trim();
@@ -64,7 +329,24 @@ fn parse_required_string(value: Option<&Value>, field: &str) -> SourceResult<Str
     Err(SourceReason::Other(format!("{field} must not be empty")).into())
 }
 
+/// 解析订阅的 Topic：逗号分隔字符串或数组；`^` 开头的条目按正则订阅，且只能单独出现
 fn parse_topics(value: Option<&Value>) -> SourceResult<Vec<String>> {
+    let topics = parse_topic_entries(value)?;
+    if topics.iter().any(|topic| is_topic_pattern(topic)) {
+        if topics.len() > 1 {
+            return Err(SourceReason::Other(
+                "kafka.topic pattern ('^...') cannot be combined with other topics".into(),
+            )
+            .into());
+        }
+        regex::Regex::new(&topics[0]).map_err(|err| {
+            SourceReason::Other(format!("kafka.topic pattern is not a valid regex: {err}"))
+        })?;
+    }
+    Ok(topics)
+}
+
+fn parse_topic_entries(value: Option<&Value>) -> SourceResult<Vec<String>> {
     match value {
         Some(Value::String(raw)) => {
             let topics = raw
@@ -117,6 +399,7 @@ fn parse_config(value: Option<&Value>) -> SourceResult<Option<Vec<String>>> {
                 if trimmed.is_empty() {
                     continue;
                 }
+                check_config_entry(trimmed).map_err(SourceReason::Other)?;
                 configs.push(trimmed.to_string());
             }
             if configs.is_empty() {
@@ -130,6 +413,7 @@ fn parse_config(value: Option<&Value>) -> SourceResult<Option<Vec<String>>> {
             if trimmed.is_empty() {
                 Ok(None)
             } else {
+                check_config_entry(trimmed).map_err(SourceReason::Other)?;
                 Ok(Some(vec![trimmed.to_string()]))
             }
         }
@@ -137,6 +421,134 @@ fn parse_config(value: Option<&Value>) -> SourceResult<Option<Vec<String>>> {
     }
 }
 
+/// 解析 `topic_config`：Topic → `key=value` 条目列表（或单条字符串）。
+/// Topic 须在订阅列表中；正则订阅无法确定具体 Topic，不支持按 Topic 覆盖。
+fn parse_topic_config(
+    value: Option<&Value>,
+    topics: &[String],
+) -> SourceResult<Option<BTreeMap<String, Vec<String>>>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let Some(map) = value.as_object() else {
+        return Err(SourceReason::Other("kafka.topic_config must be an object".into()).into());
+    };
+    if map.is_empty() {
+        return Ok(None);
+    }
+    if topics.iter().any(|topic| is_topic_pattern(topic)) {
+        return Err(SourceReason::Other(
+            "kafka.topic_config cannot be used with a topic pattern subscription".into(),
+        )
+        .into());
+    }
+    let mut out = BTreeMap::new();
+    for (topic, items) in map {
+        if !topics.contains(topic) {
+            return Err(SourceReason::Other(format!(
+                "kafka.topic_config.{topic} is not in kafka.topic"
+            ))
+            .into());
+        }
+        let raw: Vec<&str> = match items {
+            Value::String(raw) => vec![raw.as_str()],
+            Value::Array(values) => values
+                .iter()
+                .map(|v| v.as_str())
+                .collect::<Option<_>>()
+                .ok_or_else(|| {
+                    SourceReason::Other(format!(
+                        "kafka.topic_config.{topic} entries must be strings"
+                    ))
+                })?,
+            _ => {
+                return Err(SourceReason::Other(format!(
+                    "kafka.topic_config.{topic} must be a string or array"
+                ))
+                .into());
+            }
+        };
+        let mut entries = Vec::new();
+        for entry in raw.into_iter().map(str::trim).filter(|e| !e.is_empty()) {
+            check_config_entry(entry).map_err(SourceReason::Other)?;
+            entries.push(entry.to_string());
+        }
+        if !entries.is_empty() {
+            out.insert(topic.clone(), entries);
+        }
+    }
+    Ok((!out.is_empty()).then_some(out))
+}
+
+/// 解析可选的正整数参数（`i32` 范围），缺省时为 `None`
+fn parse_source_positive_i32(value: Option<&Value>, field: &str) -> SourceResult<Option<i32>> {
+    match value {
+        None => Ok(None),
+        Some(v) => match v.as_i64().and_then(|n| i32::try_from(n).ok()) {
+            Some(n) if n > 0 => Ok(Some(n)),
+            _ => Err(SourceReason::Other(format!("{field} must be a positive integer")).into()),
+        },
+    }
+}
+
+fn parse_source_positive_u64(value: Option<&Value>, field: &str) -> SourceResult<Option<u64>> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Ok(Some(n)),
+            _ => Err(SourceReason::Other(format!("{field} must be a positive integer")).into()),
+        },
+    }
+}
+
+fn parse_value_format(value: Option<&Value>) -> SourceResult<ValueFormat> {
+    match value {
+        None => Ok(ValueFormat::Raw),
+        Some(Value::String(s)) => ValueFormat::parse(s).ok_or_else(|| {
+            SourceReason::Other("kafka.value_format must be one of: raw, json".into()).into()
+        }),
+        Some(_) => Err(SourceReason::Other("kafka.value_format must be a string".into()).into()),
+    }
+}
+
+fn parse_lag_interval(value: Option<&Value>) -> SourceResult<u64> {
+    match value {
+        None => Ok(DEFAULT_LAG_INTERVAL_SECS),
+        Some(v) => v.as_u64().ok_or_else(|| {
+            SourceReason::Other("kafka.lag_interval_secs must be a non-negative integer".into())
+                .into()
+        }),
+    }
+}
+
+fn parse_max_in_flight(value: Option<&Value>) -> SourceResult<Option<usize>> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => match v.as_u64().and_then(|n| usize::try_from(n).ok()) {
+            Some(n) if n > 0 => Ok(Some(n)),
+            _ => Err(
+                SourceReason::Other("kafka.max_in_flight must be a positive integer".into()).into(),
+            ),
+        },
+    }
+}
+
+/// 校验单条 `key=value` 配置；格式错误直接拒绝，未知键仅告警（可能是拼写错误）。
+fn check_config_entry(entry: &str) -> Result<(), String> {
+    let Some((key, _)) = split_config_entry(entry) else {
+        return Err(format!(
+            "invalid kafka.config entry '{entry}': expected 'key=value'"
+        ));
+    };
+    if !KNOWN_CONFIG_KEYS.contains(&key) {
+        wp_log::warn_data!(
+            "[kafka] config key '{}' is not a known librdkafka property, check for typos",
+            key
+        );
+    }
+    Ok(())
+}
+
 fn parse_sink_required_string(value: Option<&Value>, field: &str) -> SinkResult<String> {
     if let Some(Value::String(raw)) = value {
         let trimmed = raw.trim();
@@ -163,6 +575,159 @@ fn parse_positive_i32(value: Option<&Value>, field: &str) -> SinkResult<Option<i
     }
 }
 
+fn parse_positive_u64(value: Option<&Value>, field: &str) -> SinkResult<Option<u64>> {
+    match value {
+        None => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Ok(Some(n)),
+            _ => Err(SinkReason::sink(format!("{field} must be a positive integer")).into()),
+        },
+    }
+}
+
+fn parse_acks(value: Option<&Value>) -> SinkResult<Option<String>> {
+    let acks = match value {
+        None => return Ok(None),
+        Some(Value::String(raw)) => raw.trim().to_string(),
+        Some(Value::Number(num)) => num.to_string(),
+        Some(_) => return Err(SinkReason::sink("kafka.acks must be a string").into()),
+    };
+    if !matches!(acks.as_str(), "0" | "1" | "all") {
+        return Err(
+            SinkReason::sink(format!("invalid kafka.acks: '{acks}'; allowed: 0,1,all")).into(),
+        );
+    }
+    Ok(Some(acks))
+}
+
+/// 解析 `partitioner` 与 `partition_field`：两者须同时用于 `manual` 模式
+fn parse_partitioner(spec: &SinkSpec) -> SinkResult<(Option<Partitioner>, Option<String>)> {
+    let partitioner = match spec.params.get("partitioner") {
+        None => None,
+        Some(v) => Some(v.as_str().and_then(Partitioner::parse).ok_or_else(|| {
+            SinkReason::sink(
+                "kafka.partitioner must be one of: consistent, consistent_random, murmur2, manual",
+            )
+        })?),
+    };
+    let partition_field = match spec.params.get("partition_field") {
+        None => None,
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(field) if !field.is_empty() => Some(field.to_string()),
+            _ => {
+                return Err(
+                    SinkReason::sink("kafka.partition_field must be a non-empty string").into(),
+                );
+            }
+        },
+    };
+    match (partitioner, &partition_field) {
+        (Some(Partitioner::Manual), None) => Err(SinkReason::sink(
+            "kafka.partitioner = manual requires kafka.partition_field",
+        )
+        .into()),
+        (Some(Partitioner::Manual), Some(_)) | (_, None) => Ok((partitioner, partition_field)),
+        (_, Some(_)) => Err(SinkReason::sink(
+            "kafka.partition_field is only allowed when kafka.partitioner = manual",
+        )
+        .into()),
+    }
+}
+
+/// 解析 `key_field` 与 `key_fmt`；`key_fmt = raw`（缺省）时返回 `None`，即以字段值本身作为 key
+fn parse_key(spec: &SinkSpec) -> SinkResult<(Option<String>, Option<String>)> {
+    let key_field = match spec.params.get("key_field") {
+        None => None,
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(field) if !field.is_empty() => Some(field.to_string()),
+            _ => return Err(SinkReason::sink("kafka.key_field must be a non-empty string").into()),
+        },
+    };
+    let key_fmt = match spec.params.get("key_fmt") {
+        None => None,
+        Some(v) => match v.as_str().map(str::trim) {
+            Some("raw") => None,
+            Some(fmt) if TEXT_FMT_ALLOWED.contains(&fmt) => Some(fmt.to_string()),
+            _ => {
+                return Err(SinkReason::sink(format!(
+                    "kafka.key_fmt must be one of: {}",
+                    TEXT_FMT_ALLOWED.join(", ")
+                ))
+                .into());
+            }
+        },
+    };
+    if key_field.is_none() && spec.params.contains_key("key_fmt") {
+        return Err(
+            SinkReason::sink("kafka.key_fmt only applies when kafka.key_field is set").into(),
+        );
+    }
+    Ok((key_field, key_fmt))
+}
+
+/// 解析 `timestamp_field` 与 `time_unit`；未配置单位时按毫秒解析
+fn parse_timestamp_field(spec: &SinkSpec) -> SinkResult<(Option<String>, TimeUnit)> {
+    let timestamp_field = match spec.params.get("timestamp_field") {
+        None => None,
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(field) if !field.is_empty() => Some(field.to_string()),
+            _ => {
+                return Err(
+                    SinkReason::sink("kafka.timestamp_field must be a non-empty string").into(),
+                );
+            }
+        },
+    };
+    let time_unit = match spec.params.get("time_unit") {
+        None => TimeUnit::default(),
+        Some(v) => v.as_str().and_then(TimeUnit::parse).ok_or_else(|| {
+            SinkReason::sink("kafka.time_unit must be one of: nanos, millis, seconds")
+        })?,
+    };
+    Ok((timestamp_field, time_unit))
+}
+
+/// 解析 `max_message_bytes` / `on_oversize` / `dlq_path`；后两者仅在设置了上限时允许配置
+fn parse_oversize(spec: &SinkSpec) -> SinkResult<(Option<u64>, OversizeAction, Option<String>)> {
+    let max_message_bytes = parse_positive_u64(
+        spec.params.get("max_message_bytes"),
+        "kafka.max_message_bytes",
+    )?;
+    if max_message_bytes.is_none() {
+        if let Some(key) = ["on_oversize", "dlq_path"]
+            .into_iter()
+            .find(|key| spec.params.contains_key(*key))
+        {
+            return Err(SinkReason::sink(format!(
+                "kafka.{key} only applies when kafka.max_message_bytes is set"
+            ))
+            .into());
+        }
+        return Ok((None, OversizeAction::default(), None));
+    }
+    let on_oversize = match spec.params.get("on_oversize").map(Value::as_str) {
+        None | Some(Some("skip")) => OversizeAction::Skip,
+        Some(Some("dlq")) => OversizeAction::Dlq,
+        Some(_) => {
+            return Err(SinkReason::sink("kafka.on_oversize must be one of: skip, dlq").into());
+        }
+    };
+    let dlq_path = match spec.params.get("dlq_path") {
+        None => None,
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(path) if !path.is_empty() => Some(path.to_string()),
+            _ => return Err(SinkReason::sink("kafka.dlq_path must be a non-empty path").into()),
+        },
+    };
+    if on_oversize == OversizeAction::Dlq && dlq_path.is_none() {
+        return Err(SinkReason::sink(
+            "kafka.dlq_path must be set when kafka.on_oversize = \"dlq\"",
+        )
+        .into());
+    }
+    Ok((max_message_bytes, on_oversize, dlq_path))
+}
+
 fn parse_sink_config(value: Option<&Value>) -> SinkResult<Option<Vec<String>>> {
     match value {
         None => Ok(None),
@@ -176,6 +741,7 @@ fn parse_sink_config(value: Option<&Value>) -> SinkResult<Option<Vec<String>>> {
                 if trimmed.is_empty() {
                     continue;
                 }
+                check_config_entry(trimmed).map_err(SinkReason::sink)?;
                 configs.push(trimmed.to_string());
             }
             if configs.is_empty() {
@@ -189,6 +755,7 @@ fn parse_sink_config(value: Option<&Value>) -> SinkResult<Option<Vec<String>>> {
             if trimmed.is_empty() {
                 Ok(None)
             } else {
+                check_config_entry(trimmed).map_err(SinkReason::sink)?;
                 Ok(Some(vec![trimmed.to_string()]))
             }
         }
@@ -196,30 +763,13 @@ fn parse_sink_config(value: Option<&Value>) -> SinkResult<Option<Vec<String>>> {
     }
 }
 
-fn parse_sink_fmt(value: Option<&Value>) -> SinkResult<TextFmt> {
-    match value {
-        None => Ok(TextFmt::Json),
-        Some(Value::String(raw)) => {
-            let trimmed = raw.trim();
-            if trimmed.is_empty() {
-                return Err(SinkReason::sink("kafka.fmt must not be empty").into());
-            }
-            let ok = matches!(
-                trimmed,
-                "json" | "csv" | "show" | "kv" | "raw" | "proto" | "proto-text"
-            );
-            if !ok {
-                return Err(SinkReason::sink(format!(
-                    "invalid fmt: '{}'; allowed: json,csv,show,kv,raw,proto,proto-text",
-                    trimmed
-                ))
-                .into());
-            }
-            Ok(TextFmt::from(trimmed))
-        }
-        Some(_) => Err(SinkReason::sink("kafka.fmt must be a string").into()),
-    }
-}
+/// mTLS 参数名，Source 与 Sink 共用
+const TLS_PARAMS: [&str; 4] = [
+    "ssl_ca_location",
+    "ssl_certificate_location",
+    "ssl_key_location",
+    "ssl_key_password",
+];
 
 pub struct KafkaSourceFactory;
 
@@ -230,7 +780,15 @@ impl wp_connector_api::SourceFactory for KafkaSourceFactory {
     }
 
     fn validate_spec(&self, spec: &wp_connector_api::SourceSpec) -> SourceResult<()> {
-        build_kafka_conf_from_spec(spec)?;
+        let spec = &*config_blob::source_spec("kafka", spec)?;
+        let (conf, _) = build_kafka_conf_from_spec(spec)?;
+        IngestTime::from_params("kafka", &spec.params)?;
+        let preflight = parse_validate_connectivity(spec.params.get("validate_connectivity"))
+            .map_err(|msg| SourceReason::Other(msg.into()))?;
+        if preflight && conf.enable {
+            check_connectivity(&conf.brokers, &conf.consumer_config(), PREFLIGHT_TIMEOUT)
+                .map_err(SourceReason::Other)?;
+        }
         Ok(())
     }
 
@@ -239,19 +797,70 @@ impl wp_connector_api::SourceFactory for KafkaSourceFactory {
         spec: &wp_connector_api::SourceSpec,
         _ctx: &wp_connector_api::SourceBuildCtx,
     ) -> SourceResult<SourceSvcIns> {
+        let spec = &*config_blob::source_spec("kafka", spec)?;
         let (conf, group_id) = build_kafka_conf_from_spec(spec)?;
+        // 已禁用的连接器不建立连接，也不产出任何 Source
+        if !conf.enable {
+            wp_log::info_ctrl!("[kafka] source '{}' is disabled", spec.name);
+            return Ok(SourceSvcIns::new());
+        }
 
+        let ingest_time = IngestTime::from_params("kafka", &spec.params)?;
         let mut meta_tags = Tags::from_parse(&spec.tags);
         let access_source = spec.kind.clone();
         meta_tags.set(WP_SRC_VAL, access_source);
-        let source = KafkaSource::new(spec.name.clone(), meta_tags.clone(), &group_id, &conf)
-            .await
-            .map_err(|err| SourceReason::Other(err.to_string()))?;
+        // 按 Topic 覆盖配置时，覆盖项不同的 Topic 各用一个消费者
+        let mut handles = Vec::new();
+        for conf in conf.split_by_topic_config() {
+            let source = KafkaSource::new(conf.key.clone(), meta_tags.clone(), &group_id, &conf)
+                .await
+                .map_err(|err| SourceReason::Other(err.to_string()))?
+                .with_ingest_time(ingest_time.clone());
+
+            let mut meta = SourceMeta::new(conf.key.clone(), spec.kind.clone());
+            meta.tags = meta_tags.clone();
+            let source: Box<dyn DataSource> = match conf.prefetch {
+                Some(capacity) => Box::new(PrefetchSource::new(Box::new(source), capacity)),
+                None => Box::new(source),
+            };
+            handles.push(SourceHandle::new(source, meta));
+        }
+        Ok(SourceSvcIns::new().with_sources(handles))
+    }
+}
 
-        let mut meta = SourceMeta::new(spec.name.clone(), spec.kind.clone());
-        meta.tags = meta_tags;
-        let handle = SourceHandle::new(Box::new(source), meta);
-        Ok(SourceSvcIns::new().with_sources(vec![handle]))
+// 与 `build` 相同的解析流程，跳过连通性预检与建连
+impl SourcePlanner for KafkaSourceFactory {
+    fn plan(&self, spec: &wp_connector_api::SourceSpec) -> SourceResult<Value> {
+        let spec = &*config_blob::source_spec("kafka", spec)?;
+        let (conf, group_id) = build_kafka_conf_from_spec(spec)?;
+        let preflight = parse_validate_connectivity(spec.params.get("validate_connectivity"))
+            .map_err(|msg| SourceReason::Other(msg.into()))?;
+        let client_config: BTreeMap<_, _> = conf.consumer_config().into_iter().collect();
+        let consumers: Vec<Value> = conf
+            .split_by_topic_config()
+            .into_iter()
+            .map(|consumer| {
+                let client_config: BTreeMap<_, _> =
+                    consumer.consumer_config().into_iter().collect();
+                json!({
+                    "key": consumer.key,
+                    "topic": consumer.topic,
+                    "client_config": client_config,
+                })
+            })
+            .collect();
+        let ingest_time = IngestTime::from_params("kafka", &spec.params)?;
+        let mut plan = json!({
+            "group_id": group_id,
+            "validate_connectivity": preflight,
+            "ingest_time": ingest_time,
+            "client_config": client_config,
+            "consumers": consumers,
+            "conf": conf,
+        });
+        redact_secrets(&mut plan);
+        Ok(plan)
     }
 }
 
@@ -264,16 +873,35 @@ impl SinkFactory for KafkaSinkFactory {
     }
 
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
-        build_kafka_sink_conf_from_spec(spec)?;
+        let spec = &*ErrorKind::Config.tag_sink("kafka", config_blob::sink_spec("kafka", spec))?;
+        let (conf, _, enable, preflight) =
+            ErrorKind::Config.tag_sink("kafka", parse_sink_spec(spec))?;
+        if preflight && enable {
+            check_connectivity(&conf.brokers, &conf.producer_config(), PREFLIGHT_TIMEOUT)
+                .map_err(|msg| ErrorKind::Connect.sink("kafka", msg))?;
+        }
         Ok(())
     }
 
     async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
-        let (conf, fmt) = build_kafka_sink_conf_from_spec(spec)?;
-        let sink = KafkaSink::from_conf(&conf, fmt).await.map_err(|err| {
-            SinkError::from(SinkReason::sink(format!("init kafka sink failed: {err}")))
-        })?;
-        Ok(SinkHandle::new(Box::new(sink)))
+        let spec = &*ErrorKind::Config.tag_sink("kafka", config_blob::sink_spec("kafka", spec))?;
+        let (conf, fmt, enable, _) = ErrorKind::Config.tag_sink("kafka", parse_sink_spec(spec))?;
+        // 已禁用的连接器不建立连接，接收并丢弃数据
+        if !enable {
+            let sink = DisabledKafkaSink::new(&conf.topic);
+            return decorate_sink(spec, SinkHandle::new(Box::new(sink)));
+        }
+        let sink = KafkaSinkBuilder::from_conf(conf, fmt)
+            .build()
+            .await
+            .map_err(|err| {
+                let kind = err
+                    .downcast_ref::<KWError>()
+                    .and_then(kafka_error_kind)
+                    .unwrap_or(ErrorKind::Connect);
+                SinkError::from(kind.sink("kafka", format!("init kafka sink failed: {err}")))
+            })?;
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
     }
 }
 
@@ -283,16 +911,61 @@ impl SourceDefProvider for KafkaSourceFactory {
             id: "kafka_src".into(),
             kind: self.kind().into(),
             scope: ConnectorScope::Source,
-            allow_override: vec!["brokers", "topic", "group_id", "config"]
-                .into_iter()
-                .map(str::to_string)
-                .collect(),
+            allow_override: vec![
+                "brokers",
+                "topic",
+                "group_id",
+                "config",
+                "lag_interval_secs",
+                "auto_create_topics",
+                "source_num_partitions",
+                "source_replication",
+                "max_in_flight",
+                "poll_timeout_ms",
+                "client_id",
+                "group_instance_id",
+                "topic_config",
+                "value_format",
+                "skip_empty_payloads",
+                PREFETCH_PARAM,
+                INGEST_TIME_FIELD_PARAM,
+                INGEST_TIME_UNIT_PARAM,
+                "enable",
+                "validate_connectivity",
+                CONFIG_BLOB_PARAM,
+            ]
+            .into_iter()
+            .chain(TLS_PARAMS)
+            .map(str::to_string)
+            .collect(),
             default_params: kafka_source_defaults(),
             origin: Some("wp-connectors:kafka_source".into()),
         }
     }
 }
 
+impl SinkPlanner for KafkaSinkFactory {
+    fn plan(&self, spec: &SinkSpec) -> SinkResult<Value> {
+        let spec = &*ErrorKind::Config.tag_sink("kafka", config_blob::sink_spec("kafka", spec))?;
+        let (conf, fmt, enable, preflight) =
+            ErrorKind::Config.tag_sink("kafka", parse_sink_spec(spec))?;
+        let fmt = match conf.avro {
+            Some(_) => "avro".to_string(),
+            None => fmt.to_string(),
+        };
+        let client_config: BTreeMap<_, _> = conf.producer_config().into_iter().collect();
+        let mut plan = json!({
+            "fmt": fmt,
+            "enable": enable,
+            "validate_connectivity": preflight,
+            "client_config": client_config,
+            "conf": conf,
+        });
+        redact_secrets(&mut plan);
+        Ok(plan)
+    }
+}
+
 impl SinkDefProvider for KafkaSinkFactory {
     fn sink_def(&self) -> ConnectorDef {
         ConnectorDef {
@@ -303,11 +976,37 @@ impl SinkDefProvider for KafkaSinkFactory {
                 "brokers",
                 "topic",
                 "fmt",
+                "value_fmt",
+                "key_field",
+                "key_fmt",
+                "auto_create_topic",
                 "num_partitions",
                 "replication",
                 "config",
+                "acks",
+                "delivery_timeout_ms",
+                "flush_interval_ms",
+                "stats_interval_ms",
+                "max_inflight_messages",
+                "preserve_order",
+                "max_message_bytes",
+                "on_oversize",
+                "dlq_path",
+                "partitioner",
+                "partition_field",
+                "timestamp_field",
+                "time_unit",
+                "client_id",
+                "schema_registry_url",
+                "subject",
+                "enable",
+                "validate_connectivity",
+                CONFIG_BLOB_PARAM,
             ]
             .into_iter()
+            .chain(TLS_PARAMS)
+            .chain(ENVELOPE_PARAMS)
+            .chain(DECORATOR_PARAMS)
             .map(str::to_string)
             .collect(),
             default_params: kafka_sink_defaults(),
@@ -341,8 +1040,8 @@ fn kafka_sink_defaults() -> ParamMap {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::kafka::config::config_entries_to_map;
     use serde_json::{Value, json};
-    use std::collections::BTreeMap;
 
     fn build_source_spec(params: BTreeMap<String, Value>) -> wp_connector_api::SourceSpec {
         wp_connector_api::SourceSpec {
@@ -393,6 +1092,178 @@ mod tests {
         );
     }
 
+    #[test]
+    fn kafka_conf_from_spec_parses_lag_interval() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        let (conf, _) = build_kafka_conf_from_spec(&build_source_spec(params.clone()))
+            .expect("default lag interval");
+        assert_eq!(conf.lag_interval_secs, DEFAULT_LAG_INTERVAL_SECS);
+
+        params.insert("lag_interval_secs".into(), json!(0));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("lag disabled");
+        assert_eq!(conf.lag_interval_secs, 0);
+
+        params.insert("lag_interval_secs".into(), json!("10s"));
+        let err = build_kafka_conf_from_spec(&build_source_spec(params))
+            .expect_err("non-integer interval");
+        assert!(format!("{err}").contains("kafka.lag_interval_secs"));
+    }
+
+    #[test]
+    fn kafka_conf_from_spec_parses_topic_creation_settings() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("defaults");
+        assert!(conf.auto_create_topics);
+        assert_eq!((conf.num_partitions, conf.replication), (1, 1));
+
+        params.insert("auto_create_topics".into(), json!(false));
+        params.insert("source_num_partitions".into(), json!(12));
+        params.insert("source_replication".into(), json!(3));
+        let (conf, _) = build_kafka_conf_from_spec(&build_source_spec(params.clone()))
+            .expect("custom topic settings");
+        assert!(!conf.auto_create_topics);
+        assert_eq!((conf.num_partitions, conf.replication), (12, 3));
+
+        for (key, bad) in [
+            ("source_num_partitions", json!(0)),
+            ("source_replication", json!("3")),
+            ("auto_create_topics", json!("no")),
+        ] {
+            let mut params = params.clone();
+            params.insert(key.into(), bad);
+            let err = build_kafka_conf_from_spec(&build_source_spec(params))
+                .expect_err("invalid topic setting");
+            assert!(format!("{err}").contains(&format!("kafka.{key}")));
+        }
+    }
+
+    #[test]
+    fn kafka_conf_from_spec_parses_skip_empty_payloads() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("defaults");
+        assert!(conf.skip_empty_payloads);
+
+        params.insert("skip_empty_payloads".into(), json!(false));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("disabled");
+        assert!(!conf.skip_empty_payloads);
+
+        params.insert("skip_empty_payloads".into(), json!("no"));
+        let err = build_kafka_conf_from_spec(&build_source_spec(params)).expect_err("not a bool");
+        assert!(format!("{err}").contains("kafka.skip_empty_payloads must be a bool"));
+    }
+
+    #[test]
+    fn kafka_conf_from_spec_parses_value_format() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("default");
+        assert_eq!(conf.value_format, ValueFormat::Raw);
+
+        params.insert("value_format".into(), json!("JSON"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("json");
+        assert_eq!(conf.value_format, ValueFormat::Json);
+
+        params.insert("value_format".into(), json!("avro"));
+        let err = build_kafka_conf_from_spec(&build_source_spec(params)).expect_err("unknown");
+        assert!(format!("{err}").contains("kafka.value_format"));
+    }
+
+    #[test]
+    fn kafka_conf_from_spec_parses_prefetch() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        params.insert("prefetch".into(), json!(16));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("prefetch");
+        assert_eq!(conf.prefetch, Some(16));
+
+        params.insert("prefetch".into(), json!(-1));
+        let err = build_kafka_conf_from_spec(&build_source_spec(params)).expect_err("negative");
+        assert!(format!("{err}").contains("kafka.prefetch"));
+    }
+
+    #[test]
+    fn kafka_conf_from_spec_parses_max_in_flight() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("unbounded");
+        assert_eq!(conf.max_in_flight, None);
+
+        params.insert("max_in_flight".into(), json!(500));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("bounded");
+        assert_eq!(conf.max_in_flight, Some(500));
+
+        params.insert("max_in_flight".into(), json!(0));
+        let err =
+            build_kafka_conf_from_spec(&build_source_spec(params)).expect_err("zero is rejected");
+        assert!(format!("{err}").contains("kafka.max_in_flight"));
+    }
+
+    #[test]
+    fn kafka_conf_from_spec_parses_poll_timeout() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("default");
+        assert_eq!(conf.poll_timeout_ms, None);
+
+        params.insert("poll_timeout_ms".into(), json!(250));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("timeout");
+        assert_eq!(conf.poll_timeout_ms, Some(250));
+
+        params.insert("poll_timeout_ms".into(), json!(0));
+        let err =
+            build_kafka_conf_from_spec(&build_source_spec(params)).expect_err("zero is rejected");
+        assert!(format!("{err}").contains("kafka.poll_timeout_ms"));
+    }
+
+    #[test]
+    fn kafka_conf_from_spec_accepts_topic_pattern() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("group_id".into(), json!("group-a"));
+        params.insert("topic".into(), json!(r"^events\..*"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("pattern");
+        assert_eq!(conf.topic, vec![r"^events\..*".to_string()]);
+
+        for (topic, expect) in [
+            (json!("^events.(tenant"), "not a valid regex"),
+            (json!([r"^events\..*", "audit"]), "cannot be combined"),
+        ] {
+            params.insert("topic".into(), topic);
+            let err =
+                build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect_err(expect);
+            assert!(format!("{err}").contains(expect), "{err}");
+        }
+    }
+
     #[test]
     fn kafka_conf_from_spec_rejects_missing_topic() {
         let mut params = BTreeMap::new();
@@ -455,6 +1326,733 @@ mod tests {
         );
     }
 
+    #[test]
+    fn kafka_sink_conf_from_spec_applies_acks_and_delivery_timeout() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("sink-topic"));
+        params.insert("acks".into(), json!("all"));
+        params.insert("delivery_timeout_ms".into(), json!(30000));
+        let spec = build_sink_spec(params.clone());
+
+        let (conf, _) = build_kafka_sink_conf_from_spec(&spec).expect("valid sink spec");
+        let producer = conf.producer_config();
+        assert_eq!(producer.get("acks").map(String::as_str), Some("all"));
+        assert_eq!(
+            producer.get("delivery.timeout.ms").map(String::as_str),
+            Some("30000")
+        );
+
+        // 原始 config 条目仍可覆盖类型化参数
+        params.insert("acks".into(), json!(1));
+        params.insert("config".into(), json!(["delivery.timeout.ms=5000"]));
+        let (conf, _) =
+            build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect("valid sink spec");
+        let producer = conf.producer_config();
+        assert_eq!(producer.get("acks").map(String::as_str), Some("1"));
+        assert_eq!(
+            producer.get("delivery.timeout.ms").map(String::as_str),
+            Some("5000")
+        );
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_rejects_invalid_acks_and_timeout() {
+        let base = || {
+            let mut params = BTreeMap::new();
+            params.insert("brokers".into(), json!("localhost:9092"));
+            params.insert("topic".into(), json!("sink-topic"));
+            params
+        };
+        for acks in [json!("2"), json!("leader"), json!(-1)] {
+            let mut params = base();
+            params.insert("acks".into(), acks);
+            let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params))
+                .expect_err("invalid acks");
+            assert!(format!("{err}").contains("kafka.acks"));
+        }
+        for timeout in [json!(0), json!("30s")] {
+            let mut params = base();
+            params.insert("delivery_timeout_ms".into(), timeout);
+            let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params))
+                .expect_err("invalid timeout");
+            assert!(format!("{err}").contains("kafka.delivery_timeout_ms"));
+        }
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_parses_auto_create_topic() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("sink-topic"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert!(conf.auto_create_topic);
+
+        params.insert("auto_create_topic".into(), json!(false));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert!(!conf.auto_create_topic);
+
+        params.insert("auto_create_topic".into(), json!("no"));
+        let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params))
+            .expect_err("invalid auto_create_topic");
+        assert!(format!("{err}").contains("kafka.auto_create_topic must be a bool"));
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_parses_preserve_order() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("sink-topic"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert!(!conf.preserve_order);
+        assert!(
+            !conf
+                .producer_config()
+                .contains_key("max.in.flight.requests.per.connection")
+        );
+
+        params.insert("preserve_order".into(), json!(true));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert!(conf.preserve_order);
+        assert_eq!(
+            conf.producer_config()
+                .get("max.in.flight.requests.per.connection")
+                .map(String::as_str),
+            Some("1")
+        );
+
+        params.insert("preserve_order".into(), json!(1));
+        let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params))
+            .expect_err("invalid preserve_order");
+        assert!(format!("{err}").contains("kafka.preserve_order must be a bool"));
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_parses_envelope() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("sink-topic"));
+        params.insert(ENVELOPE_PARAM.into(), json!(r#"{"ts":{ts},"data":{data}}"#));
+        params.insert(ENVELOPE_SOURCE_PARAM.into(), json!("edge"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert_eq!(
+            conf.envelope.as_deref(),
+            Some(r#"{"ts":{ts},"data":{data}}"#)
+        );
+        assert_eq!(conf.envelope_source.as_deref(), Some("edge"));
+
+        params.insert(ENVELOPE_PARAM.into(), json!(r#"{"ts":{ts}}"#));
+        let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params))
+            .expect_err("envelope without data");
+        assert!(
+            format!("{err}").contains("kafka.envelope template"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_parses_max_inflight_messages() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("sink-topic"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert_eq!(conf.max_inflight_messages, None);
+
+        params.insert("max_inflight_messages".into(), json!(1000));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert_eq!(conf.max_inflight_messages, Some(1000));
+
+        params.insert("max_inflight_messages".into(), json!(0));
+        let err =
+            build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err("zero threshold");
+        assert!(format!("{err}").contains("kafka.max_inflight_messages"));
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_parses_oversize_guard() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("sink-topic"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert_eq!(conf.max_message_bytes, None);
+
+        let mut dlq_only = params.clone();
+        dlq_only.insert("on_oversize".into(), json!("dlq"));
+        let err = build_kafka_sink_conf_from_spec(&build_sink_spec(dlq_only))
+            .expect_err("on_oversize without limit");
+        assert!(format!("{err}").contains("only applies when kafka.max_message_bytes"));
+
+        params.insert("max_message_bytes".into(), json!(1_000_000));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert_eq!(conf.max_message_bytes, Some(1_000_000));
+        assert_eq!(conf.on_oversize, OversizeAction::Skip);
+
+        params.insert("on_oversize".into(), json!("dlq"));
+        let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect_err("dlq without path");
+        assert!(format!("{err}").contains("kafka.dlq_path must be set"));
+
+        params.insert("dlq_path".into(), json!("/tmp/kafka_oversize.jsonl"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert_eq!(conf.on_oversize, OversizeAction::Dlq);
+        assert_eq!(conf.dlq_path.as_deref(), Some("/tmp/kafka_oversize.jsonl"));
+
+        params.insert("on_oversize".into(), json!("truncate"));
+        let err =
+            build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err("unknown action");
+        assert!(format!("{err}").contains("kafka.on_oversize must be one of"));
+    }
+
+    #[test]
+    fn kafka_sink_builder_matches_spec_path() {
+        use crate::kafka::KafkaSinkBuilder;
+        use std::time::Duration;
+
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("b1:9092,b2:9092"));
+        params.insert("topic".into(), json!("events"));
+        params.insert("fmt".into(), json!("kv"));
+        params.insert("num_partitions".into(), json!(6));
+        params.insert("replication".into(), json!(2));
+        params.insert("config".into(), json!(["linger.ms=5"]));
+        params.insert("acks".into(), json!("all"));
+        params.insert("flush_interval_ms".into(), json!(500));
+        params.insert("max_inflight_messages".into(), json!(1000));
+        params.insert("partitioner".into(), json!("manual"));
+        params.insert("partition_field".into(), json!("shard"));
+        params.insert("timestamp_field".into(), json!("ts"));
+        params.insert("time_unit".into(), json!("seconds"));
+        params.insert("client_id".into(), json!("wp-embedded"));
+        params.insert("max_message_bytes".into(), json!(1_000_000));
+        params.insert("on_oversize".into(), json!("dlq"));
+        params.insert("dlq_path".into(), json!("/tmp/kafka_oversize.jsonl"));
+        let (conf, fmt) =
+            build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect("valid sink spec");
+
+        let builder = KafkaSinkBuilder::new("b1:9092,b2:9092", "events")
+            .fmt(TextFmt::Kv)
+            .topic_layout(6, 2)
+            .config("linger.ms=5")
+            .acks("all")
+            .flush_interval(Duration::from_millis(500))
+            .max_inflight_messages(1000)
+            .partition_field("shard")
+            .timestamp_field("ts", TimeUnit::Seconds)
+            .client_id("wp-embedded")
+            .max_message_bytes(1_000_000)
+            .oversize_dlq("/tmp/kafka_oversize.jsonl");
+        assert_eq!(builder.conf, conf);
+        assert_eq!(builder.fmt, fmt);
+
+        // 只给出 brokers 与 topic 时，除工厂补充的 client_id 外与参数缺省一致
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("b1:9092"));
+        params.insert("topic".into(), json!("events"));
+        let (mut conf, fmt) =
+            build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect("valid sink spec");
+        conf.client_id = None;
+        let builder = KafkaSinkBuilder::new("b1:9092", "events");
+        assert_eq!(builder.conf, conf);
+        assert_eq!(builder.fmt, fmt);
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_parses_flush_interval() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("sink-topic"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert_eq!(conf.flush_interval_ms, None);
+
+        params.insert("flush_interval_ms".into(), json!(200));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("valid sink spec");
+        assert_eq!(conf.flush_interval_ms, Some(200));
+
+        params.insert("flush_interval_ms".into(), json!(0));
+        let err =
+            build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err("zero interval");
+        assert!(format!("{err}").contains("kafka.flush_interval_ms"));
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_maps_partitioner() {
+        let base = || {
+            let mut params = BTreeMap::new();
+            params.insert("brokers".into(), json!("localhost:9092"));
+            params.insert("topic".into(), json!("sink-topic"));
+            params
+        };
+        let mut params = base();
+        params.insert("partitioner".into(), json!("murmur2"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect("murmur2");
+        assert_eq!(
+            conf.producer_config()
+                .get("partitioner")
+                .map(String::as_str),
+            Some("murmur2")
+        );
+
+        // manual 模式不改 librdkafka 分区器，由记录字段逐条指定分区
+        let mut params = base();
+        params.insert("partitioner".into(), json!("manual"));
+        params.insert("partition_field".into(), json!("shard"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect("manual");
+        assert_eq!(conf.partitioner, Some(Partitioner::Manual));
+        assert_eq!(conf.partition_field.as_deref(), Some("shard"));
+        assert!(!conf.producer_config().contains_key("partitioner"));
+
+        for (partitioner, field, expect) in [
+            (Some("fnv"), None, "kafka.partitioner must be one of"),
+            (Some("manual"), None, "requires kafka.partition_field"),
+            (Some("murmur2"), Some("shard"), "only allowed when"),
+            (None, Some("shard"), "only allowed when"),
+        ] {
+            let mut params = base();
+            if let Some(p) = partitioner {
+                params.insert("partitioner".into(), json!(p));
+            }
+            if let Some(f) = field {
+                params.insert("partition_field".into(), json!(f));
+            }
+            let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err(expect);
+            assert!(format!("{err}").contains(expect), "{err}");
+        }
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_parses_key_and_value_fmt() {
+        let base = || {
+            let mut params = BTreeMap::new();
+            params.insert("brokers".into(), json!("localhost:9092"));
+            params.insert("topic".into(), json!("sink-topic"));
+            params
+        };
+        let (conf, fmt) = build_kafka_sink_conf_from_spec(&build_sink_spec(base())).unwrap();
+        assert_eq!(
+            (conf.key_field, conf.key_fmt, fmt),
+            (None, None, TextFmt::Json)
+        );
+
+        // value_fmt 优先于 fmt；key_fmt = raw 即字段值本身
+        let mut params = base();
+        params.insert("fmt".into(), json!("csv"));
+        params.insert("value_fmt".into(), json!("kv"));
+        params.insert("key_field".into(), json!("user"));
+        params.insert("key_fmt".into(), json!("raw"));
+        let (conf, fmt) = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).unwrap();
+        assert_eq!(fmt, TextFmt::Kv);
+        assert_eq!(conf.key_field.as_deref(), Some("user"));
+        assert_eq!(conf.key_fmt, None);
+
+        let mut params = base();
+        params.insert("key_field".into(), json!("user"));
+        params.insert("key_fmt".into(), json!("json"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).unwrap();
+        assert_eq!(conf.key_fmt.as_deref(), Some("json"));
+
+        for (key, value, expect) in [
+            (
+                "key_field",
+                json!(""),
+                "kafka.key_field must be a non-empty string",
+            ),
+            (
+                "key_fmt",
+                json!("json"),
+                "only applies when kafka.key_field is set",
+            ),
+            ("value_fmt", json!("bogus"), "invalid fmt: 'bogus'"),
+        ] {
+            let mut params = base();
+            params.insert(key.into(), value);
+            let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err(expect);
+            assert!(format!("{err}").contains(expect), "{err}");
+        }
+        let mut params = base();
+        params.insert("key_field".into(), json!("user"));
+        params.insert("key_fmt".into(), json!("xml"));
+        let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err("key_fmt");
+        assert!(format!("{err}").contains("kafka.key_fmt must be one of"));
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_maps_timestamp_field() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("sink-topic"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("no timestamp field");
+        assert_eq!(conf.timestamp_field, None);
+        assert_eq!(conf.time_unit, TimeUnit::Millis);
+
+        params.insert("timestamp_field".into(), json!("event_time"));
+        params.insert("time_unit".into(), json!("seconds"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()))
+            .expect("timestamp field");
+        assert_eq!(conf.timestamp_field.as_deref(), Some("event_time"));
+        assert_eq!(conf.time_unit, TimeUnit::Seconds);
+
+        for (key, bad, expect) in [
+            (
+                "time_unit",
+                json!("micros"),
+                "kafka.time_unit must be one of",
+            ),
+            ("timestamp_field", json!(" "), "kafka.timestamp_field"),
+        ] {
+            let mut params = params.clone();
+            params.insert(key.into(), bad);
+            let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err(expect);
+            assert!(format!("{err}").contains(expect), "{err}");
+        }
+    }
+
+    #[test]
+    fn kafka_sink_conf_merges_config_blob_with_flat_overrides() {
+        let mut params = BTreeMap::new();
+        params.insert(
+            "config_blob".into(),
+            json!(
+                "brokers = \"blob:9092\"\ntopic = \"blob-topic\"\nacks = \"all\"\n\
+                 partitioner = \"manual\"\npartition_field = \"shard\""
+            ),
+        );
+        params.insert("topic".into(), json!("flat-topic"));
+        let spec = build_sink_spec(params);
+        let spec = config_blob::sink_spec("kafka", &spec).expect("valid blob");
+        let (conf, _) = build_kafka_sink_conf_from_spec(&spec).expect("merged conf");
+        assert_eq!(conf.brokers, "blob:9092");
+        assert_eq!(conf.topic, "flat-topic");
+        assert_eq!(conf.acks.as_deref(), Some("all"));
+        assert_eq!(conf.partitioner, Some(Partitioner::Manual));
+        assert_eq!(conf.partition_field.as_deref(), Some("shard"));
+
+        // blob 中的值同样经过逐项校验
+        let mut params = BTreeMap::new();
+        params.insert(
+            "config_blob".into(),
+            json!({"brokers": "blob:9092", "topic": "t", "acks": "2"}),
+        );
+        let err = KafkaSinkFactory
+            .validate_spec(&build_sink_spec(params))
+            .expect_err("invalid acks in blob");
+        assert!(format!("{err}").contains("kafka.acks"), "{err}");
+    }
+
+    #[test]
+    fn kafka_client_id_defaults_to_connector_name_and_accepts_override() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("source");
+        let client_id = conf.consumer_config()["client.id"].clone();
+        assert!(client_id.contains("kafka_source"), "{client_id}");
+        assert!(client_id.contains("source"), "{client_id}");
+
+        let (conf, _) =
+            build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone())).expect("sink");
+        let client_id = conf.producer_config()["client.id"].clone();
+        assert!(client_id.contains("kafka_sink"), "{client_id}");
+
+        params.insert("client_id".into(), json!("billing-ingest"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("source");
+        assert_eq!(conf.consumer_config()["client.id"], "billing-ingest");
+        let (conf, _) =
+            build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone())).expect("sink");
+        assert_eq!(conf.producer_config()["client.id"], "billing-ingest");
+
+        params.insert("client_id".into(), json!("  "));
+        let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err("blank");
+        assert!(format!("{err}").contains("kafka.client_id"), "{err}");
+    }
+
+    #[test]
+    fn kafka_topic_config_overrides_are_merged_per_consumer() {
+        let mut params = ParamMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!(["orders", "refunds", "audit"]));
+        params.insert("group_id".into(), json!("billing"));
+        params.insert("group_instance_id".into(), json!("ingest-0"));
+        params.insert(
+            "config".into(),
+            json!(["auto.offset.reset=earliest", "fetch.min.bytes=1"]),
+        );
+        params.insert(
+            "topic_config".into(),
+            json!({
+                "refunds": ["auto.offset.reset=latest"],
+                "audit": "auto.offset.reset = latest",
+            }),
+        );
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("topic_config");
+
+        let consumers = conf.split_by_topic_config();
+        assert_eq!(consumers.len(), 2);
+        assert_eq!(consumers[0].topic, vec!["orders".to_string()]);
+        assert_eq!(consumers[0].key, "kafka_source-0");
+        assert_eq!(
+            consumers[0].group_instance_id.as_deref(),
+            Some("ingest-0-0")
+        );
+        let first = consumers[0].consumer_config();
+        assert_eq!(first["auto.offset.reset"], "earliest");
+
+        // `refunds` 与 `audit` 的覆盖项相同，共用一个消费者；覆盖项叠加在全局配置之上
+        assert_eq!(
+            consumers[1].topic,
+            vec!["refunds".to_string(), "audit".to_string()]
+        );
+        assert_eq!(
+            consumers[1].group_instance_id.as_deref(),
+            Some("ingest-0-1")
+        );
+        let second = consumers[1].consumer_config();
+        assert_eq!(second["auto.offset.reset"], "latest");
+        assert_eq!(second["fetch.min.bytes"], "1");
+        assert!(consumers.iter().all(|c| c.topic_config.is_none()));
+
+        for (bad, expect) in [
+            (
+                json!({"payments": ["a=b"]}),
+                "kafka.topic_config.payments is not in kafka.topic",
+            ),
+            (
+                json!({"orders": [1]}),
+                "kafka.topic_config.orders entries must be strings",
+            ),
+            (
+                json!({"orders": ["no-equals"]}),
+                "invalid kafka.config entry",
+            ),
+            (json!(["orders"]), "kafka.topic_config must be an object"),
+        ] {
+            let mut params = params.clone();
+            params.insert("topic_config".into(), bad);
+            let err = build_kafka_conf_from_spec(&build_source_spec(params)).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+
+    #[test]
+    fn kafka_plan_returns_resolved_conf_without_connecting() {
+        let mut params = BTreeMap::new();
+        // 不可达地址且开启预检：plan 不做连通性检查，应立即返回
+        params.insert("brokers".into(), json!("127.0.0.1:1"));
+        params.insert("topic".into(), json!(["orders", "refunds"]));
+        params.insert("group_id".into(), json!("billing"));
+        params.insert("validate_connectivity".into(), json!(true));
+        params.insert("config".into(), json!(["sasl.password = s3cr3t"]));
+        params.insert("ssl_ca_location".into(), json!("/etc/kafka/ca.pem"));
+
+        let plan = KafkaSourceFactory
+            .plan(&build_source_spec(params.clone()))
+            .expect("source plan");
+        assert_eq!(plan["group_id"], "billing");
+        assert_eq!(plan["conf"]["topic"], json!(["orders", "refunds"]));
+        assert_eq!(
+            plan["client_config"]["client.id"],
+            "wp-kafka-source-kafka_source"
+        );
+        assert_eq!(plan["client_config"]["security.protocol"], "SASL_SSL");
+        assert_eq!(plan["client_config"]["sasl.password"], "***");
+        assert_eq!(plan["conf"]["config"], json!(["sasl.password = ***"]));
+
+        params.insert("topic".into(), json!("orders"));
+        params.insert("fmt".into(), json!("kv"));
+        params.insert("acks".into(), json!("all"));
+        let plan = KafkaSinkFactory
+            .plan(&build_sink_spec(params.clone()))
+            .expect("sink plan");
+        assert_eq!(plan["fmt"], "kv");
+        assert_eq!(plan["enable"], true);
+        assert_eq!(plan["conf"]["topic"], "orders");
+        assert_eq!(plan["client_config"]["acks"], "all");
+
+        params.insert("acks".into(), json!("2"));
+        let err = KafkaSinkFactory
+            .plan(&build_sink_spec(params))
+            .expect_err("invalid acks");
+        assert!(format!("{err}").contains("kafka.acks"), "{err}");
+    }
+
+    #[test]
+    fn kafka_group_instance_id_enables_static_membership() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("source");
+        assert_eq!(conf.group_instance_id, None);
+        assert!(!conf.consumer_config().contains_key("group.instance.id"));
+
+        params.insert("group_instance_id".into(), json!(" ingest-0 "));
+        let (conf, _) =
+            build_kafka_conf_from_spec(&build_source_spec(params.clone())).expect("source");
+        assert_eq!(conf.consumer_config()["group.instance.id"], "ingest-0");
+
+        params.insert("group_instance_id".into(), json!(""));
+        let err = build_kafka_conf_from_spec(&build_source_spec(params)).expect_err("blank");
+        assert!(
+            format!("{err}").contains("kafka.group_instance_id"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn kafka_tls_params_derive_ssl_only_config() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9093"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        params.insert("ssl_ca_location".into(), json!("/etc/kafka/ca.pem"));
+        params.insert(
+            "ssl_certificate_location".into(),
+            json!("/etc/kafka/client.pem"),
+        );
+        params.insert("ssl_key_location".into(), json!("/etc/kafka/client.key"));
+        params.insert("ssl_key_password".into(), json!("secret"));
+
+        let (conf, _) = build_kafka_conf_from_spec(&build_source_spec(params.clone())).unwrap();
+        let map = conf.consumer_config();
+        assert_eq!(map["security.protocol"], "SSL");
+        assert_eq!(map["ssl.ca.location"], "/etc/kafka/ca.pem");
+        assert_eq!(map["ssl.certificate.location"], "/etc/kafka/client.pem");
+        assert_eq!(map["ssl.key.location"], "/etc/kafka/client.key");
+        assert_eq!(map["ssl.key.password"], "secret");
+
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).unwrap();
+        let map = conf.producer_config();
+        assert_eq!(map["security.protocol"], "SSL");
+        assert_eq!(map["ssl.key.location"], "/etc/kafka/client.key");
+
+        // 未配置证书时不改动安全协议
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(kafka_sink_defaults()))
+            .expect("plaintext");
+        assert!(!conf.producer_config().contains_key("security.protocol"));
+    }
+
+    #[test]
+    fn kafka_tls_params_combine_with_sasl() {
+        let mut params = kafka_sink_defaults();
+        params.insert(
+            "config".into(),
+            json!([
+                "sasl.mechanisms=SCRAM-SHA-512",
+                "sasl.username=wp",
+                "sasl.password=pw"
+            ]),
+        );
+        params.insert("ssl_ca_location".into(), json!("/etc/kafka/ca.pem"));
+        params.insert(
+            "ssl_certificate_location".into(),
+            json!("/etc/kafka/client.pem"),
+        );
+        params.insert("ssl_key_location".into(), json!("/etc/kafka/client.key"));
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone())).unwrap();
+        let map = conf.producer_config();
+        assert_eq!(map["security.protocol"], "SASL_SSL");
+        assert_eq!(map["sasl.mechanisms"], "SCRAM-SHA-512");
+        assert_eq!(map["ssl.certificate.location"], "/etc/kafka/client.pem");
+        assert!(!map.contains_key("ssl.key.password"));
+
+        // 显式的 security.protocol 优先
+        params.insert(
+            "config".into(),
+            json!(["sasl.mechanisms=PLAIN", "security.protocol=sasl_ssl"]),
+        );
+        let (conf, _) = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).unwrap();
+        assert_eq!(conf.producer_config()["security.protocol"], "sasl_ssl");
+    }
+
+    #[test]
+    fn kafka_tls_params_require_certificate_and_key_together() {
+        for (pairs, expect) in [
+            (
+                vec![("ssl_certificate_location", json!("/etc/kafka/client.pem"))],
+                "must be set together",
+            ),
+            (
+                vec![("ssl_key_location", json!("/etc/kafka/client.key"))],
+                "must be set together",
+            ),
+            (
+                vec![("ssl_key_password", json!("secret"))],
+                "kafka.ssl_key_password requires kafka.ssl_key_location",
+            ),
+            (
+                vec![("ssl_ca_location", json!(""))],
+                "kafka.ssl_ca_location",
+            ),
+        ] {
+            let mut params = kafka_sink_defaults();
+            for (key, value) in &pairs {
+                params.insert(key.to_string(), value.clone());
+            }
+            let err = KafkaSinkFactory
+                .validate_spec(&build_sink_spec(params.clone()))
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+
+            params.insert("group_id".into(), json!("group-a"));
+            let err = KafkaSourceFactory
+                .validate_spec(&build_source_spec(params))
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_parses_avro_registry() {
+        let base = || {
+            let mut params = BTreeMap::new();
+            params.insert("brokers".into(), json!("localhost:9092"));
+            params.insert("topic".into(), json!("events"));
+            params
+        };
+        let mut params = base();
+        params.insert("subject".into(), json!("events-value"));
+        let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err("text fmt");
+        assert!(format!("{err}").contains("only apply when kafka.fmt = avro"));
+
+        let mut params = base();
+        params.insert("fmt".into(), json!("avro"));
+        params.insert("schema_registry_url".into(), json!("http://registry:8081"));
+        let result = build_kafka_sink_conf_from_spec(&build_sink_spec(params.clone()));
+        if cfg!(feature = "avro") {
+            let (conf, _) = result.expect("avro conf");
+            assert_eq!(
+                conf.avro,
+                Some(AvroConf {
+                    schema_registry_url: "http://registry:8081".into(),
+                    subject: "events-value".into(),
+                })
+            );
+            params.remove("schema_registry_url");
+            let err = build_kafka_sink_conf_from_spec(&build_sink_spec(params)).expect_err("url");
+            assert!(format!("{err}").contains("kafka.schema_registry_url"));
+        } else {
+            let err = result.expect_err("feature disabled");
+            assert!(format!("{err}").contains("requires the `avro` feature"));
+        }
+    }
+
     #[test]
     fn kafka_sink_conf_from_spec_rejects_invalid_fmt() {
         let mut params = BTreeMap::new();
@@ -468,6 +2066,53 @@ mod tests {
         assert!(msg.contains("invalid fmt"));
     }
 
+    #[test]
+    fn kafka_conf_from_spec_rejects_config_without_equals() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("topic_a"));
+        params.insert("group_id".into(), json!("group-a"));
+        params.insert("config".into(), json!(["auto.offset.reset latest"]));
+        let spec = build_source_spec(params);
+
+        let err = build_kafka_conf_from_spec(&spec).expect_err("malformed config");
+        let msg = format!("{err}");
+        assert!(msg.contains("auto.offset.reset latest"));
+        assert!(msg.contains("key=value"));
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_rejects_malformed_config() {
+        for bad in ["acks", "=all", "acks="] {
+            let mut params = BTreeMap::new();
+            params.insert("brokers".into(), json!("localhost:9092"));
+            params.insert("topic".into(), json!("sink-topic"));
+            params.insert("config".into(), json!(bad));
+            let spec = build_sink_spec(params);
+
+            let err = build_kafka_sink_conf_from_spec(&spec).expect_err("malformed config");
+            assert!(format!("{err}").contains("expected 'key=value'"), "{bad}");
+        }
+    }
+
+    #[test]
+    fn kafka_sink_conf_from_spec_accepts_value_with_equals() {
+        let mut params = BTreeMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!("sink-topic"));
+        params.insert(
+            "config".into(),
+            json!(["sasl.oauthbearer.config=principal=admin", "linger.ms = 5"]),
+        );
+        let spec = build_sink_spec(params);
+
+        let (conf, _fmt) = build_kafka_sink_conf_from_spec(&spec).expect("valid sink spec");
+        let items = conf.config.expect("config present");
+        let map = config_entries_to_map(&items);
+        assert_eq!(map.get("sasl.oauthbearer.config"), Some(&"principal=admin"));
+        assert_eq!(map.get("linger.ms"), Some(&"5"));
+    }
+
     #[test]
     fn kafka_sink_conf_from_spec_supports_string_config() {
         let mut params = BTreeMap::new();
@@ -479,4 +2124,89 @@ mod tests {
         let (conf, _fmt) = build_kafka_sink_conf_from_spec(&spec).expect("valid sink spec");
         assert_eq!(conf.config, Some(vec!["acks=1".to_string()]));
     }
+
+    #[tokio::test]
+    async fn disabled_spec_builds_noop_handles_without_connecting() {
+        let mut params = kafka_sink_defaults();
+        params.insert("brokers".into(), json!("127.0.0.1:1"));
+        params.insert("enable".into(), json!(false));
+        let spec = build_sink_spec(params);
+        KafkaSinkFactory.validate_spec(&spec).expect("valid spec");
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+        let mut handle = tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            KafkaSinkFactory.build(&spec, &ctx),
+        )
+        .await
+        .expect("no broker round-trip")
+        .expect("build disabled sink");
+        handle
+            .sink
+            .sink_records(vec![std::sync::Arc::new(
+                wp_model_core::model::DataRecord::default(),
+            )])
+            .await
+            .expect("records are dropped");
+        handle.sink.stop().await.expect("stop");
+
+        let mut params = kafka_source_defaults();
+        params.insert("brokers".into(), json!("127.0.0.1:1"));
+        params.insert("enable".into(), json!(false));
+        let ctx = wp_connector_api::SourceBuildCtx::new(std::env::temp_dir());
+        let svc = KafkaSourceFactory
+            .build(&build_source_spec(params), &ctx)
+            .await
+            .expect("build disabled source");
+        assert!(svc.sources.is_empty());
+    }
+
+    #[test]
+    fn enable_must_be_bool() {
+        let mut params = kafka_sink_defaults();
+        params.insert("enable".into(), json!("no"));
+        let err = KafkaSinkFactory
+            .validate_spec(&build_sink_spec(params))
+            .expect_err("string enable");
+        assert!(err.to_string().contains("kafka.enable must be a bool"));
+        assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Config));
+    }
+
+    #[test]
+    fn validate_connectivity_off_skips_broker_round_trip() {
+        let mut params = kafka_sink_defaults();
+        params.insert("brokers".into(), json!("127.0.0.1:1"));
+        let start = std::time::Instant::now();
+        KafkaSinkFactory
+            .validate_spec(&build_sink_spec(params.clone()))
+            .expect("offline validation");
+        params.insert("validate_connectivity".into(), json!(false));
+        KafkaSinkFactory
+            .validate_spec(&build_sink_spec(params))
+            .expect("offline validation");
+        assert!(start.elapsed() < std::time::Duration::from_millis(500));
+    }
+
+    #[test]
+    fn validate_connectivity_on_rejects_unreachable_broker() {
+        let mut params = kafka_sink_defaults();
+        params.insert("brokers".into(), json!("127.0.0.1:1"));
+        params.insert("validate_connectivity".into(), json!(true));
+        let err = KafkaSinkFactory
+            .validate_spec(&build_sink_spec(params))
+            .expect_err("unreachable broker");
+        assert!(
+            err.to_string()
+                .contains("kafka.brokers '127.0.0.1:1' unreachable"),
+            "{err}"
+        );
+        assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Connect));
+
+        let mut params = kafka_source_defaults();
+        params.insert("brokers".into(), json!("127.0.0.1:1"));
+        params.insert("validate_connectivity".into(), json!("yes"));
+        let err = KafkaSourceFactory
+            .validate_spec(&build_source_spec(params))
+            .expect_err("non-bool flag");
+        assert!(err.to_string().contains("kafka.validate_connectivity"));
+    }
 }
@@ -0,0 +1,152 @@
+//! Kafka 消费背压（`max_in_flight`）
+//!
+//! Source 记录已产出但尚未被下游 ack 的消息数：在途数达到上限时暂停当前分配的所有分区，
+//! ack 使在途数回落到上限的一半及以下时恢复消费，避免在阈值附近频繁暂停/恢复。
+//! 未配置 `max_in_flight` 时不跟踪、不暂停，与旧行为一致。
+//!
+//! `AckToken` 只是标记 trait，无法还原出具体的分区与 offset，
+//! 因此每次 ack 按一条消息计数。
+//!
+//! 手动暂停（`KafkaSource::pause`）优先于背压：手动暂停期间 ack 只更新在途数、不恢复分区，
+//! 手动恢复时若背压仍处于暂停状态也不恢复分区。
+
+use rdkafka_wrap::consumer::{Consumer, DefaultConsumerContext, StreamConsumer};
+use rdkafka_wrap::error::KafkaResult;
+
+/// 暂停/恢复当前分配的分区
+pub(crate) trait PartitionPause {
+    fn pause_all(&self) -> KafkaResult<()>;
+    fn resume_all(&self) -> KafkaResult<()>;
+}
+
+impl PartitionPause for StreamConsumer<DefaultConsumerContext> {
+    fn pause_all(&self) -> KafkaResult<()> {
+        self.pause(&self.assignment()?)
+    }
+
+    fn resume_all(&self) -> KafkaResult<()> {
+        self.resume(&self.assignment()?)
+    }
+}
+
+/// 手动暂停期间使用：背压回落时保持分区暂停
+pub(crate) struct KeepPaused;
+
+impl PartitionPause for KeepPaused {
+    fn pause_all(&self) -> KafkaResult<()> {
+        Ok(())
+    }
+
+    fn resume_all(&self) -> KafkaResult<()> {
+        Ok(())
+    }
+}
+
+/// 在途消息计数与暂停状态
+#[derive(Debug)]
+pub(crate) struct InFlightGate {
+    limit: usize,
+    in_flight: usize,
+    paused: bool,
+}
+
+impl InFlightGate {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            in_flight: 0,
+            paused: false,
+        }
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// 记录一条已产出的消息；达到上限时暂停消费
+    pub(crate) fn on_emit(&mut self, ctrl: &dyn PartitionPause) -> KafkaResult<()> {
+        self.in_flight += 1;
+        if !self.paused && self.in_flight >= self.limit {
+            ctrl.pause_all()?;
+            self.paused = true;
+            wp_log::info_data!(
+                "[kafka] {} messages in flight, pause consumption",
+                self.in_flight
+            );
+        }
+        Ok(())
+    }
+
+    /// 记录一次 ack；在途数回落到上限一半及以下时恢复消费
+    pub(crate) fn on_ack(&mut self, ctrl: &dyn PartitionPause) -> KafkaResult<()> {
+        self.in_flight = self.in_flight.saturating_sub(1);
+        if self.paused && self.in_flight <= self.limit / 2 {
+            ctrl.resume_all()?;
+            self.paused = false;
+            wp_log::info_data!(
+                "[kafka] in-flight drained to {}, resume consumption",
+                self.in_flight
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct RecordingPause {
+        calls: RefCell<Vec<&'static str>>,
+    }
+
+    impl PartitionPause for RecordingPause {
+        fn pause_all(&self) -> KafkaResult<()> {
+            self.calls.borrow_mut().push("pause");
+            Ok(())
+        }
+
+        fn resume_all(&self) -> KafkaResult<()> {
+            self.calls.borrow_mut().push("resume");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn crossing_threshold_pauses_and_draining_resumes() {
+        let ctrl = RecordingPause::default();
+        let mut gate = InFlightGate::new(4);
+
+        for _ in 0..3 {
+            gate.on_emit(&ctrl).unwrap();
+        }
+        assert!(ctrl.calls.borrow().is_empty());
+
+        gate.on_emit(&ctrl).unwrap();
+        assert!(gate.is_paused());
+        assert_eq!(*ctrl.calls.borrow(), vec!["pause"]);
+
+        // 回落到一半（2）之前保持暂停
+        gate.on_ack(&ctrl).unwrap();
+        assert!(gate.is_paused());
+        gate.on_ack(&ctrl).unwrap();
+        assert!(!gate.is_paused());
+        assert_eq!(*ctrl.calls.borrow(), vec!["pause", "resume"]);
+    }
+
+    #[test]
+    fn keep_paused_holds_partitions_while_draining() {
+        let ctrl = RecordingPause::default();
+        let mut gate = InFlightGate::new(2);
+        gate.on_emit(&ctrl).unwrap();
+        gate.on_emit(&ctrl).unwrap();
+        assert!(gate.is_paused());
+
+        // 手动暂停期间在途数回落：更新计数但不恢复分区
+        gate.on_ack(&KeepPaused).unwrap();
+        assert!(!gate.is_paused());
+        assert_eq!(*ctrl.calls.borrow(), vec!["pause"]);
+    }
+}
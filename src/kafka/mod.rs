@@ -4,14 +4,32 @@
 //! - source：KafkaSource & 错误映射/建 Topic
 //! - sink：KafkaSink（AsyncRawDataSink/AsyncRecordSink）
 //! - factory：Source/Sink 工厂与注册函数
+//! - decode：Source 消息体解析（`value_format = raw | json`）
+//! - lag：消费延迟采集（`wparse_kafka_consumer_lag`）
+//! - flow：在途消息上限与分区暂停/恢复（`max_in_flight`）
+//! - stats：生产者队列长度与发送错误指标（`stats_interval_ms`）
+//! - preflight：`validate_connectivity` 连通性预检
+//! - avro：`fmt = avro` 的 Confluent 线格式编码（feature = "avro"）
+//! - builder：不经 `SinkSpec` 直接构造 KafkaSink
 
 //mod adapter;
+#[cfg(feature = "avro")]
+mod avro;
+mod builder;
 mod config;
+mod decode;
 mod factory;
+mod flow;
+mod lag;
+mod preflight;
 mod sink;
 mod source;
+mod stats;
 
 // 统一导出：便于上游 `wp_connectors::Source/Sink/Factory` 使用
+pub use builder::KafkaSinkBuilder;
+pub use config::{OversizeAction, Partitioner};
 pub use factory::{KafkaSinkFactory, KafkaSourceFactory};
 pub use sink::KafkaSink;
-pub use source::KafkaSource;
+pub use source::{KAFKA_OFFSET_TAG, KAFKA_PARTITION_TAG, KAFKA_TIMESTAMP_TAG, KafkaSource};
+pub use stats::{ProducerStats, StatsRecorder};
@@ -0,0 +1,39 @@
+//! Kafka 连通性预检
+//!
+//! 配置 `validate_connectivity = true` 时，`validate_spec` 阶段按连接器的客户端配置
+//! （含 SASL / SSL 等认证项）创建临时客户端并拉取一次集群元数据，
+//! broker 不可达或认证失败时直接报错，而不是等到 `build` / 首次收发才暴露。
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rdkafka_wrap::ClientConfig;
+use rdkafka_wrap::consumer::{BaseConsumer, Consumer};
+
+/// 元数据拉取超时，预检需保持短时
+pub(crate) const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 拉取 broker 元数据以确认可达；失败时返回描述性错误
+pub(crate) fn check_connectivity(
+    brokers: &str,
+    config: &HashMap<String, String>,
+    timeout: Duration,
+) -> Result<(), String> {
+    let mut client_config = ClientConfig::new();
+    for (key, value) in config {
+        client_config.set(key, value);
+    }
+    client_config
+        .set("bootstrap.servers", brokers)
+        .set("socket.timeout.ms", timeout.as_millis().to_string());
+    let consumer: BaseConsumer = client_config
+        .create()
+        .map_err(|err| format!("kafka.brokers '{brokers}': create client failed: {err}"))?;
+    let metadata = consumer
+        .fetch_metadata(None, timeout)
+        .map_err(|err| format!("kafka.brokers '{brokers}' unreachable: {err}"))?;
+    if metadata.brokers().is_empty() {
+        return Err(format!("kafka.brokers '{brokers}' returned no brokers"));
+    }
+    Ok(())
+}
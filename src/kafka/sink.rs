@@ -1,53 +1,228 @@
 use async_trait::async_trait;
 use orion_error::ErrorOweBase;
-use rdkafka_wrap::{KWProducer, KWProducerConf, OptionExt};
-use std::collections::HashMap;
+use rdkafka_wrap::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
+use rdkafka_wrap::client::DefaultClientContext;
+use rdkafka_wrap::error::KafkaError;
+use rdkafka_wrap::producer::{BaseRecord, Producer, PurgeConfig};
+use rdkafka_wrap::types::RDKafkaErrorCode;
+use rdkafka_wrap::util::Timeout;
+use rdkafka_wrap::{ClientConfig, KWError, KWProducer, KWProducerConf};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
 use std::sync::Arc;
 use std::time::Duration;
-use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkReason, SinkResult};
+use tokio::{sync::oneshot, task::JoinHandle};
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
 use wp_data_fmt::{FormatType, RecordFormatter};
-use wp_model_core::model::{DataRecord, fmt_def::TextFmt};
+use wp_model_core::model::{DataRecord, Value, fmt_def::TextFmt};
 
-use crate::kafka::config::KafkaSinkConf;
+#[cfg(feature = "avro")]
+use crate::kafka::avro::AvroEncoder;
+use crate::kafka::config::{KafkaSinkConf, OversizeAction};
+use crate::kafka::stats::{record_tx_error, spawn_queue_reporter};
+use crate::utils::envelope::Envelope;
+use crate::utils::error_kind::ErrorKind;
+use crate::utils::retry::{RetryPolicy, reconnect_with_backoff};
+use crate::utils::shutdown::{DeadlineCtrl, DrainReport};
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
+use crate::utils::time_unit::TimeUnit;
 
 type AnyResult<T> = anyhow::Result<T>;
 
+/// 创建 Topic 及查询元数据的超时
+const TOPIC_ADMIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 在途消息达到上限时检查投递进度的间隔
+const INFLIGHT_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// 按 rdkafka 错误码归类；无法判断时返回 `None`，由调用方按场景取默认类别
+pub(crate) fn kafka_error_kind(err: &KWError) -> Option<ErrorKind> {
+    match err {
+        KWError::Kafka(KafkaError::ClientConfig(..) | KafkaError::ClientCreation(_)) => {
+            Some(ErrorKind::Config)
+        }
+        KWError::Kafka(e) => e.rdkafka_error_code().and_then(code_kind),
+        KWError::RdKafka(e) => code_kind(e.code()),
+        KWError::Normal(_) => None,
+    }
+}
+
+fn code_kind(code: RDKafkaErrorCode) -> Option<ErrorKind> {
+    use RDKafkaErrorCode::*;
+    match code {
+        Authentication
+        | SaslAuthenticationFailed
+        | TopicAuthorizationFailed
+        | GroupAuthorizationFailed
+        | ClusterAuthorizationFailed
+        | TransactionalIdAuthorizationFailed => Some(ErrorKind::Auth),
+        BrokerTransportFailure | AllBrokersDown | Resolve | NetworkException | MessageTimedOut => {
+            Some(ErrorKind::Connect)
+        }
+        InvalidMessage | InvalidMessageSize | MessageSizeTooLarge | InvalidRecord => {
+            Some(ErrorKind::Serialize)
+        }
+        _ => None,
+    }
+}
+
+/// 带类别标签的 sink 错误，detail 保留 rdkafka 原始错误
+pub(crate) fn kafka_sink_error(kind: ErrorKind, msg: &str, err: &KWError) -> SinkError {
+    let detail = match err {
+        KWError::Kafka(e) => e.to_string(),
+        KWError::RdKafka(e) => e.to_string(),
+        KWError::Normal(e) => e.to_string(),
+    };
+    SinkError::from(kind.sink("kafka", msg)).with_detail(detail)
+}
+
+/// 读取 `partition_field` 的整数值作为目标分区；字段缺失或不是非负整数时返回 `None`，
+/// 交由 librdkafka 默认分区器处理
+fn record_partition(record: &DataRecord, field: &str) -> Option<i32> {
+    let value = match record.get2(field)?.get_value() {
+        Value::Digit(n) => *n,
+        Value::Chars(s) => s.trim().parse().ok()?,
+        _ => return None,
+    };
+    i32::try_from(value).ok().filter(|p| *p >= 0)
+}
+
+/// 读取 `timestamp_field` 作为消息时间戳（毫秒）：`Value::Time` 按 UTC 换算，整数按
+/// `unit` 换算；字段缺失、类型不符或为负数时返回 `None`，由 librdkafka 使用生产时间
+fn record_timestamp(record: &DataRecord, field: &str, unit: TimeUnit) -> Option<i64> {
+    match record.get2(field)?.get_value() {
+        Value::Time(dt) => Some(dt.and_utc().timestamp_millis()).filter(|ms| *ms >= 0),
+        Value::Digit(epoch) => unit.to_millis(*epoch),
+        Value::Chars(s) => unit.to_millis(s.trim().parse().ok()?),
+        _ => None,
+    }
+}
+
+/// 以追加方式打开死信文件
+fn open_dlq(path: &str) -> AnyResult<BufWriter<File>> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| anyhow::anyhow!("kafka.dlq_path: open '{path}' failed: {e}"))?;
+    Ok(BufWriter::new(file))
+}
+
+fn send_fail(err: KWError) -> SinkError {
+    let kind = kafka_error_kind(&err).unwrap_or(ErrorKind::Backend);
+    kafka_sink_error(kind, "kafka send fail", &err)
+}
+
 pub struct KafkaSink {
     pub(crate) inner: Arc<KWProducer>,
     pub(crate) fmt: TextFmt,
+    /// 定时 flush 间隔及 flush 任务
+    flush_interval: Option<Duration>,
+    stop_tx: Option<oneshot::Sender<()>>,
+    flush_handle: Option<JoinHandle<()>>,
+    /// 队列长度采集间隔及采集任务
+    stats_interval: Option<Duration>,
+    stats_handle: Option<JoinHandle<()>>,
+    partition_field: Option<String>,
+    timestamp_field: Option<String>,
+    time_unit: TimeUnit,
+    /// 未确认消息数上限，见 [`KafkaSink::wait_inflight_below`]
+    max_inflight: Option<usize>,
+    /// 记录消息体的信封；原始输入（`sink_str` / `sink_bytes`）不包裹
+    envelope: Option<Envelope>,
+    /// 消息 key 取自该字段，见 [`KafkaSink::encode_key`]
+    key_field: Option<String>,
+    /// key 的文本格式；为 `None` 时即字段值本身
+    key_fmt: Option<TextFmt>,
+    /// 消息体字节数上限，超出的消息不交给生产者，见 [`KafkaSink::guard_oversize`]
+    max_message_bytes: Option<usize>,
+    /// `on_oversize = dlq` 时的死信文件
+    oversize_dlq: Option<BufWriter<File>>,
+    /// 因超出上限被丢弃的消息数
+    oversize_skipped: u64,
+    /// `fmt = avro` 时缓存的编码器，设置后取代文本格式输出
+    #[cfg(feature = "avro")]
+    avro: Option<Arc<AvroEncoder>>,
+    counters: SinkCounters,
 }
 
 #[async_trait]
 impl AsyncCtrl for KafkaSink {
     async fn stop(&mut self) -> SinkResult<()> {
+        self.stop_flush_task().await;
+        self.abort_stats_task();
         self.inner
-            .flush(rdkafka_wrap::util::Timeout::After(Duration::from_secs(3)))
-            .owe(SinkReason::Sink("kafka stop fail".into()))?;
+            .flush(Timeout::After(Duration::from_secs(3)))
+            .owe(ErrorKind::Backend.sink("kafka", "kafka stop fail"))?;
         Ok(())
     }
     async fn reconnect(&mut self) -> SinkResult<()> {
-        let conf = self.inner.conf.clone();
-        self.inner =
-            Arc::new(KWProducer::new(conf).owe(SinkReason::Sink("kafka  reconnect fail".into()))?);
+        let conf = &self.inner.conf;
+        let producer = reconnect_with_backoff("kafka", RetryPolicy::reconnect(), || async {
+            KWProducer::new(conf.clone()).map_err(|err| {
+                let kind = kafka_error_kind(&err).unwrap_or(ErrorKind::Connect);
+                kafka_sink_error(kind, "kafka reconnect fail", &err)
+            })
+        })
+        .await?;
+        self.inner = Arc::new(producer);
+        // flush 与采集任务持有旧生产者，重连后切换到新实例
+        self.restart_flush_task();
+        self.restart_stats_task();
         Ok(())
     }
 }
 
+#[async_trait]
+impl DeadlineCtrl for KafkaSink {
+    /// 在截止时间内 flush 生产者队列；超时后清空队列并报告未送达的消息数
+    async fn stop_with_deadline(&mut self, deadline: Duration) -> SinkResult<DrainReport> {
+        self.abort_flush_task();
+        self.abort_stats_task();
+        if self.inner.flush(Timeout::After(deadline)).is_ok() {
+            return Ok(DrainReport::drained());
+        }
+        let dropped = self.inner.producer.in_flight_count().max(0) as u64;
+        if dropped > 0 {
+            wp_log::warn_data!(
+                "[kafka] stop deadline hit, dropping {} in-flight messages",
+                dropped
+            );
+            self.inner
+                .producer
+                .purge(PurgeConfig::default().queue().inflight());
+        }
+        Ok(DrainReport::timed_out(dropped))
+    }
+}
+
+// 消息被生产者队列接收即计为 flushed，投递结果由 librdkafka 异步确认
+impl SinkMetrics for KafkaSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
+}
+
 #[async_trait]
 impl AsyncRawDataSink for KafkaSink {
     async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
-        self.inner
-            .publish(data.as_bytes(), Default::default())
-            .await
-            .owe(SinkReason::Sink("kafka send fail".into()))?;
-        Ok(())
+        self.sink_bytes(data.as_bytes()).await
     }
     async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
-        self.inner
+        self.counters.receive(1);
+        if self.guard_oversize(data)? {
+            return Ok(());
+        }
+        self.wait_inflight_below().await;
+        let result = self
+            .inner
             .publish(data, Default::default())
             .await
-            .owe(SinkReason::Sink("kafka send fail".into()))?;
-        Ok(())
+            .map_err(|err| self.send_fail(err));
+        self.counters.finish(1, data.len(), &result);
+        result
     }
 
     async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
@@ -68,14 +243,22 @@ impl AsyncRawDataSink for KafkaSink {
 #[async_trait]
 impl AsyncRecordSink for KafkaSink {
     async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
-        // 非文件类 sink 支持通过参数选择输出格式（默认 json）
-        let fmt = FormatType::from(&self.fmt);
-        let line = format!("{}\n", fmt.fmt_record(data));
-        self.inner
-            .publish(line.as_bytes(), Default::default())
-            .await
-            .owe(SinkReason::Sink("kafka send fail".into()))?;
-        Ok(())
+        self.counters.receive(1);
+        let payload = match self.encode_payload(data) {
+            Ok(payload) => payload,
+            Err(err) => {
+                let result = Err(err);
+                self.counters.finish(1, 0, &result);
+                return result;
+            }
+        };
+        if self.guard_oversize(&payload)? {
+            return Ok(());
+        }
+        self.wait_inflight_below().await;
+        let result = self.send_record(data, &payload).await;
+        self.counters.finish(1, payload.len(), &result);
+        result
     }
     async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
         for item in data {
@@ -85,28 +268,843 @@ impl AsyncRecordSink for KafkaSink {
     }
 }
 
+/// 尽力创建 Topic：已存在时视为成功；其它失败（Broker 禁止创建、缺少 admin 权限等）时
+/// 查询集群元数据，Topic 已存在则仅告警，不存在才返回错误
+async fn ensure_topic(conf: &KafkaSinkConf) -> AnyResult<()> {
+    let mut client_config = ClientConfig::new();
+    for (key, value) in conf.producer_config() {
+        client_config.set(key, value);
+    }
+    let admin: AdminClient<DefaultClientContext> = client_config
+        .set("bootstrap.servers", &conf.brokers)
+        .create()?;
+    let new_topic = NewTopic::new(
+        &conf.topic,
+        conf.num_partitions,
+        TopicReplication::Fixed(conf.replication),
+    );
+    let options = AdminOptions::new().request_timeout(Some(TOPIC_ADMIN_TIMEOUT));
+    let failure = match admin.create_topics([&new_topic], &options).await {
+        Ok(results) => match results.into_iter().next() {
+            Some(Err((_, RDKafkaErrorCode::TopicAlreadyExists))) => {
+                wp_log::info_data!("[kafka] topic '{}' already exists", conf.topic);
+                return Ok(());
+            }
+            Some(Err((_, code))) => code.to_string(),
+            _ => return Ok(()),
+        },
+        Err(err) => err.to_string(),
+    };
+    let exists = admin
+        .inner()
+        .fetch_metadata(None, TOPIC_ADMIN_TIMEOUT)
+        .is_ok_and(|metadata| {
+            metadata
+                .topics()
+                .iter()
+                .any(|t| t.name() == conf.topic && t.error().is_none())
+        });
+    if !exists {
+        anyhow::bail!("create kafka topic '{}' failed: {}", conf.topic, failure);
+    }
+    wp_log::warn_data!(
+        "[kafka] create topic '{}' failed ({}), topic exists, continuing",
+        conf.topic,
+        failure
+    );
+    Ok(())
+}
+
 impl KafkaSink {
     pub async fn from_conf(conf: &KafkaSinkConf, fmt: TextFmt) -> AnyResult<Self> {
+        // 先拉取 schema：Registry 不可用时不创建生产者
+        #[cfg(feature = "avro")]
+        let avro = match &conf.avro {
+            Some(avro) => Some(Arc::new(AvroEncoder::fetch(avro).await?)),
+            None => None,
+        };
         let mut kc = KWProducerConf::new(&conf.brokers).set_topic_conf(
             &conf.topic,
             conf.num_partitions,
             conf.replication,
         );
-        if let Some(items) = &conf.config {
-            let mut m = HashMap::new();
-            for c in items {
-                let v: Vec<&str> = c.split('=').collect();
-                if v.len() >= 2 {
-                    m.insert(v[0].trim(), v[1].trim());
-                }
-            }
-            kc = kc.set_config(m);
+        let producer_config = conf.producer_config();
+        if !producer_config.is_empty() {
+            kc = kc.set_config(producer_config);
         }
         let producer = KWProducer::new(kc)?;
-        producer.create_topic().await?;
-        Ok(Self {
-            inner: Arc::new(producer),
+        if conf.auto_create_topic {
+            ensure_topic(conf).await?;
+        } else {
+            wp_log::info_data!("[kafka] topic auto-creation disabled for '{}'", conf.topic);
+        }
+        let mut sink = Self::new(Arc::new(producer), fmt);
+        sink.partition_field = conf.partition_field.clone();
+        sink.timestamp_field = conf.timestamp_field.clone();
+        sink.time_unit = conf.time_unit;
+        sink.max_inflight = if conf.preserve_order {
+            Some(1)
+        } else {
+            conf.max_inflight_messages
+                .map(|n| usize::try_from(n).unwrap_or(usize::MAX))
+        };
+        if let Some(template) = &conf.envelope {
+            let envelope = Envelope::parse(template, conf.envelope_source.as_deref())
+                .map_err(|e| anyhow::anyhow!("kafka.{e}"))?;
+            sink.envelope = Some(envelope);
+        }
+        sink.key_field = conf.key_field.clone();
+        sink.key_fmt = conf.key_fmt.as_deref().map(TextFmt::from);
+        sink.max_message_bytes = conf
+            .max_message_bytes
+            .map(|n| usize::try_from(n).unwrap_or(usize::MAX));
+        if let (OversizeAction::Dlq, Some(path)) = (conf.on_oversize, &conf.dlq_path) {
+            sink.oversize_dlq = Some(open_dlq(path)?);
+        }
+        #[cfg(feature = "avro")]
+        {
+            sink.avro = avro;
+        }
+        if let Some(ms) = conf.flush_interval_ms {
+            sink.start_flush_task(Duration::from_millis(ms));
+        }
+        sink.stats_interval = conf.stats_interval_ms.map(Duration::from_millis);
+        sink.restart_stats_task();
+        Ok(sink)
+    }
+
+    fn new(inner: Arc<KWProducer>, fmt: TextFmt) -> Self {
+        Self {
+            inner,
             fmt,
-        })
+            flush_interval: None,
+            stop_tx: None,
+            flush_handle: None,
+            stats_interval: None,
+            stats_handle: None,
+            partition_field: None,
+            timestamp_field: None,
+            time_unit: TimeUnit::default(),
+            max_inflight: None,
+            envelope: None,
+            key_field: None,
+            key_fmt: None,
+            max_message_bytes: None,
+            oversize_dlq: None,
+            oversize_skipped: 0,
+            #[cfg(feature = "avro")]
+            avro: None,
+            counters: SinkCounters::default(),
+        }
+    }
+
+    /// 记录需要指定 key、分区或时间戳时构建带显式属性的消息；都没有时返回 `None`，
+    /// 按默认方式发布
+    fn base_record<'a>(
+        &'a self,
+        data: &DataRecord,
+        key: Option<&'a [u8]>,
+        payload: &'a [u8],
+    ) -> Option<BaseRecord<'a, [u8], [u8]>> {
+        let topic = self.inner.conf.topic.as_deref()?;
+        let partition = self
+            .partition_field
+            .as_deref()
+            .and_then(|field| record_partition(data, field));
+        let timestamp = self
+            .timestamp_field
+            .as_deref()
+            .and_then(|field| record_timestamp(data, field, self.time_unit));
+        if key.is_none() && partition.is_none() && timestamp.is_none() {
+            return None;
+        }
+        let mut record = BaseRecord::<[u8], [u8]>::to(topic).payload(payload);
+        if let Some(key) = key {
+            record = record.key(key);
+        }
+        if let Some(partition) = partition {
+            record = record.partition(partition);
+        }
+        if let Some(timestamp) = timestamp {
+            record = record.timestamp(timestamp);
+        }
+        Some(record)
+    }
+
+    /// 配置了 `max_inflight_messages` 时，等待在途（未确认）消息数降到上限以下；
+    /// 投递结果由生产者的后台轮询线程回收，这里只观察队列长度，不会阻塞运行时线程
+    async fn wait_inflight_below(&self) {
+        let Some(limit) = self.max_inflight else {
+            return;
+        };
+        while self.inner.producer.in_flight_count().max(0) as usize >= limit {
+            tokio::time::sleep(INFLIGHT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// 因超出 `max_message_bytes` 被丢弃（含写入死信文件）的消息数
+    pub fn oversize_skipped(&self) -> u64 {
+        self.oversize_skipped
+    }
+
+    /// 消息体超出 `max_message_bytes` 时不交给生产者：计数并告警，`on_oversize = dlq` 时
+    /// 追加到死信文件。返回 `true` 表示消息已被拦截
+    fn guard_oversize(&mut self, payload: &[u8]) -> SinkResult<bool> {
+        let Some(limit) = self.max_message_bytes else {
+            return Ok(false);
+        };
+        if payload.len() <= limit {
+            return Ok(false);
+        }
+        self.oversize_skipped += 1;
+        wp_log::warn_data!(
+            "[kafka] message of {} bytes exceeds max_message_bytes {}, skipped",
+            payload.len(),
+            limit
+        );
+        if let Some(dlq) = self.oversize_dlq.as_mut() {
+            let line = serde_json::json!({
+                "error": format!("message exceeds max_message_bytes {limit}"),
+                "bytes": payload.len(),
+                "payload": String::from_utf8_lossy(payload),
+            });
+            writeln!(dlq, "{line}")
+                .and_then(|_| dlq.flush())
+                .map_err(|e| SinkReason::sink(format!("write dead-letter record failed: {e}")))?;
+        }
+        Ok(true)
+    }
+
+    /// 发送一条已编码的记录
+    async fn send_record(&self, data: &DataRecord, payload: &[u8]) -> SinkResult<()> {
+        let key = self.encode_key(data);
+        match self.base_record(data, key.as_deref(), payload) {
+            Some(record) => {
+                self.inner
+                    .send(record)
+                    .await
+                    .map_err(|(err, _)| self.send_fail(err))?;
+            }
+            None => {
+                self.inner
+                    .publish(payload, Default::default())
+                    .await
+                    .map_err(|err| self.send_fail(err))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 生成消息 key：`key_fmt` 未设置时为 `key_field` 的字段值，否则按该格式格式化仅含
+    /// key 字段的记录；未配置 `key_field` 或记录缺少该字段时返回 `None`
+    fn encode_key(&self, data: &DataRecord) -> Option<Vec<u8>> {
+        let field = self.key_field.as_deref()?;
+        let key = match &self.key_fmt {
+            None => data.get_value(field)?.to_string(),
+            Some(fmt) => {
+                let mut record = DataRecord::default();
+                record.append(data.get_field_owned(field)?);
+                FormatType::from(fmt).fmt_record(&record)
+            }
+        };
+        Some(key.into_bytes())
+    }
+
+    /// 生成消息体：配置了 Avro 时输出 Confluent 线格式，否则按文本格式逐行输出
+    fn encode_payload(&self, data: &DataRecord) -> SinkResult<Vec<u8>> {
+        #[cfg(feature = "avro")]
+        if let Some(encoder) = &self.avro {
+            return encoder.encode(data).map_err(|e| {
+                SinkError::from(
+                    ErrorKind::Serialize.sink("kafka", format!("avro encode fail: {e}")),
+                )
+            });
+        }
+        // 非文件类 sink 支持通过参数选择输出格式（默认 json）
+        let fmt = FormatType::from(&self.fmt);
+        let line = fmt.fmt_record(data);
+        let line = match &self.envelope {
+            Some(envelope) => envelope.wrap(&line),
+            None => line,
+        };
+        Ok(format!("{line}\n").into_bytes())
+    }
+
+    /// 启动后台定时 flush，低流量时也能限制消息在本地队列中的停留时间
+    pub(crate) fn start_flush_task(&mut self, interval: Duration) {
+        if interval.is_zero() || self.flush_handle.is_some() {
+            return;
+        }
+        self.flush_interval = Some(interval);
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let producer = self.inner.clone();
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        // librdkafka 的 flush 为阻塞调用，放到阻塞线程池中执行
+                        let producer = producer.clone();
+                        let flushed = tokio::task::spawn_blocking(move || {
+                            producer.flush(Timeout::After(interval))
+                        })
+                        .await;
+                        match flushed {
+                            Ok(Err(e)) => wp_log::warn_data!("[kafka] periodic flush fail: {}", e),
+                            Err(e) => wp_log::warn_data!("[kafka] periodic flush task fail: {}", e),
+                            Ok(Ok(())) => {}
+                        }
+                    }
+                    _ = &mut stop_rx => break,
+                }
+            }
+        });
+        self.stop_tx = Some(stop_tx);
+        self.flush_handle = Some(handle);
+    }
+
+    /// 按已启动的间隔重启 flush 任务，使其持有当前生产者
+    fn restart_flush_task(&mut self) {
+        self.abort_flush_task();
+        if let Some(interval) = self.flush_interval {
+            self.start_flush_task(interval);
+        }
+    }
+
+    /// 发送失败时计入 `wparse_kafka_producer_tx_errors`
+    fn send_fail(&self, err: KWError) -> SinkError {
+        record_tx_error(self.inner.conf.topic.as_deref().unwrap_or_default());
+        send_fail(err)
+    }
+
+    /// 按 `stats_interval` 启动（或重启）队列长度采集任务
+    fn restart_stats_task(&mut self) {
+        self.abort_stats_task();
+        if let Some(interval) = self.stats_interval.filter(|d| !d.is_zero()) {
+            let topic = self.inner.conf.topic.clone().unwrap_or_default();
+            self.stats_handle = Some(spawn_queue_reporter(self.inner.clone(), topic, interval));
+        }
+    }
+
+    fn abort_stats_task(&mut self) {
+        if let Some(handle) = self.stats_handle.take() {
+            handle.abort();
+        }
+    }
+
+    /// 通知 flush 任务退出并等待其结束
+    async fn stop_flush_task(&mut self) {
+        if let Some(tx) = self.stop_tx.take() {
+            let _ = tx.send(());
+        }
+        if let Some(handle) = self.flush_handle.take()
+            && let Err(e) = handle.await
+        {
+            wp_log::warn_data!("[kafka] flush task join error: {}", e);
+        }
+    }
+
+    fn abort_flush_task(&mut self) {
+        self.stop_tx.take();
+        if let Some(handle) = self.flush_handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+impl Drop for KafkaSink {
+    fn drop(&mut self) {
+        self.abort_flush_task();
+        self.abort_stats_task();
+    }
+}
+
+/// `enable = false` 时使用的空 Sink：不建立 Kafka 连接，接收的数据直接丢弃
+pub(crate) struct DisabledKafkaSink {
+    topic: String,
+    dropped: u64,
+}
+
+impl DisabledKafkaSink {
+    pub(crate) fn new(topic: &str) -> Self {
+        wp_log::info_ctrl!("[kafka] sink for topic '{}' is disabled", topic);
+        Self {
+            topic: topic.to_string(),
+            dropped: 0,
+        }
+    }
+
+    fn drop_items(&mut self, count: usize) -> SinkResult<()> {
+        self.dropped += count as u64;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for DisabledKafkaSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        wp_log::info_ctrl!(
+            "[kafka] disabled sink for topic '{}' dropped {} items",
+            self.topic,
+            self.dropped
+        );
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+// 没有生产者队列，截止时间内无需排空
+impl DeadlineCtrl for DisabledKafkaSink {}
+
+#[async_trait]
+impl AsyncRawDataSink for DisabledKafkaSink {
+    async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+        self.drop_items(1)
+    }
+    async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+        self.drop_items(1)
+    }
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.drop_items(data.len())
+    }
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.drop_items(data.len())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for DisabledKafkaSink {
+    async fn sink_record(&mut self, _data: &DataRecord) -> SinkResult<()> {
+        self.drop_items(1)
+    }
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.drop_items(data.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn unreachable_sink() -> KafkaSink {
+        let conf = KWProducerConf::new("127.0.0.1:1").set_topic_conf("wp_flush", 1, 1);
+        KafkaSink::new(
+            Arc::new(KWProducer::new(conf).expect("create producer")),
+            TextFmt::Json,
+        )
+    }
+
+    #[tokio::test]
+    async fn publish_awaits_prior_deliveries_when_inflight_limit_reached() {
+        let conf = KWProducerConf::new("127.0.0.1:1")
+            .set_topic_conf("wp_inflight", 1, 1)
+            .set_config(std::collections::HashMap::from([(
+                "message.timeout.ms",
+                "300",
+            )]));
+        let mut sink = KafkaSink::new(
+            Arc::new(KWProducer::new(conf).expect("create producer")),
+            TextFmt::Json,
+        );
+        sink.max_inflight = Some(1);
+
+        // 未达上限：入队后立即返回
+        let started = Instant::now();
+        sink.sink_record(&DataRecord::default()).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(200));
+        assert_eq!(sink.inner.producer.in_flight_count(), 1);
+
+        // 达到上限：等待上一条消息投递结束（此处为超时失败）后才继续发送
+        let started = Instant::now();
+        sink.sink_record(&DataRecord::default()).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        assert_eq!(sink.inner.producer.in_flight_count(), 1);
+
+        sink.inner
+            .producer
+            .purge(PurgeConfig::default().queue().inflight());
+    }
+
+    #[test]
+    fn envelope_wraps_formatted_record() {
+        use wp_model_core::model::DataField;
+
+        let mut sink = unreachable_sink();
+        sink.envelope = Some(
+            Envelope::parse(
+                r#"{"source":"{source}","ts":{ts},"data":{data}}"#,
+                Some("edge"),
+            )
+            .unwrap(),
+        );
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("host", "web-1"));
+        let payload = sink.encode_payload(&record).unwrap();
+        assert!(payload.ends_with(b"\n"));
+        let value: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(value["source"], "edge");
+        assert_eq!(value["data"]["host"], "web-1");
+        assert!(value["ts"].as_i64().is_some_and(|ts| ts > 0));
+    }
+
+    #[test]
+    fn key_and_value_are_formatted_independently() {
+        use wp_model_core::model::DataField;
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("user", "alice"));
+        record.append(DataField::from_digit("n", 1));
+
+        // 缺省：key 为字段值本身，消息体按 fmt 输出
+        let mut sink = unreachable_sink();
+        sink.key_field = Some("user".into());
+        let key = sink.encode_key(&record).expect("key");
+        assert_eq!(key, b"alice");
+        assert_eq!(
+            sink.encode_payload(&record).unwrap(),
+            b"{\"user\":\"alice\",\"n\":1}\n"
+        );
+        let message = sink
+            .base_record(&record, Some(key.as_slice()), b"payload")
+            .expect("keyed record");
+        assert_eq!(message.key, Some(&b"alice"[..]));
+
+        // key 按 json、消息体按 kv 输出
+        sink.key_fmt = Some(TextFmt::Json);
+        sink.fmt = TextFmt::Kv;
+        assert_eq!(sink.encode_key(&record).unwrap(), b"{\"user\":\"alice\"}");
+        assert_eq!(
+            sink.encode_payload(&record).unwrap(),
+            b"user: \"alice\", n: 1\n"
+        );
+
+        // 记录缺少 key 字段时不带 key
+        assert!(sink.encode_key(&DataRecord::default()).is_none());
+    }
+
+    #[test]
+    fn manual_partition_reads_record_field() {
+        use wp_model_core::model::DataField;
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("shard", 2));
+        record.append(DataField::from_chars("shard_text", " 5 "));
+        record.append(DataField::from_digit("negative", -1));
+        record.append(DataField::from_chars("host", "web-1"));
+        assert_eq!(record_partition(&record, "shard"), Some(2));
+        assert_eq!(record_partition(&record, "shard_text"), Some(5));
+        assert_eq!(record_partition(&record, "negative"), None);
+        assert_eq!(record_partition(&record, "host"), None);
+        assert_eq!(record_partition(&record, "missing"), None);
+    }
+
+    #[test]
+    fn timestamp_field_sets_message_timestamp() {
+        use wp_model_core::model::DataField;
+
+        let mut sink = unreachable_sink();
+        sink.timestamp_field = Some("event_time".into());
+        sink.time_unit = TimeUnit::Seconds;
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("event_time", 1_700_000_000));
+        let message = sink
+            .base_record(&record, None, b"payload")
+            .expect("explicit record");
+        assert_eq!(message.topic, "wp_flush");
+        assert_eq!(message.timestamp, Some(1_700_000_000_000));
+        assert_eq!(message.partition, None);
+
+        let time = chrono::DateTime::from_timestamp_millis(1_700_000_000_123)
+            .unwrap()
+            .naive_utc();
+        let mut record = DataRecord::default();
+        record.append(DataField::from_time("event_time", time));
+        let message = sink
+            .base_record(&record, None, b"payload")
+            .expect("explicit record");
+        assert_eq!(message.timestamp, Some(1_700_000_000_123));
+
+        // 缺少字段时回退到生产时间
+        assert!(
+            sink.base_record(&DataRecord::default(), None, b"payload")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn send_failures_are_classified_by_error_code() {
+        let produce = |code| KWError::Kafka(KafkaError::MessageProduction(code));
+        for (code, kind) in [
+            (RDKafkaErrorCode::SaslAuthenticationFailed, ErrorKind::Auth),
+            (RDKafkaErrorCode::TopicAuthorizationFailed, ErrorKind::Auth),
+            (RDKafkaErrorCode::AllBrokersDown, ErrorKind::Connect),
+            (RDKafkaErrorCode::MessageTimedOut, ErrorKind::Connect),
+            (RDKafkaErrorCode::MessageSizeTooLarge, ErrorKind::Serialize),
+            (RDKafkaErrorCode::NotEnoughReplicas, ErrorKind::Backend),
+        ] {
+            let err = send_fail(produce(code));
+            assert_eq!(ErrorKind::of_sink(&err), Some(kind), "{code:?}");
+            assert!(err.to_string().contains("kafka send fail"));
+        }
+        let err = send_fail(KWError::Kafka(KafkaError::ClientCreation("bad".into())));
+        assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Config));
+    }
+
+    #[tokio::test]
+    async fn metrics_count_enqueued_messages_and_payload_bytes() {
+        use wp_model_core::model::DataField;
+
+        let mut sink = unreachable_sink();
+        assert_eq!(sink.metrics(), SinkMetricsSnapshot::default());
+
+        let records: Vec<_> = (0..3)
+            .map(|i| {
+                let mut record = DataRecord::default();
+                record.append(DataField::from_digit("id", i));
+                Arc::new(record)
+            })
+            .collect();
+        let payload_bytes: usize = records
+            .iter()
+            .map(|record| sink.encode_payload(record).unwrap().len())
+            .sum();
+        sink.sink_records(records).await.expect("enqueued");
+        sink.sink_str("line").await.expect("enqueued");
+        assert_eq!(
+            sink.metrics(),
+            SinkMetricsSnapshot {
+                received: 4,
+                flushed: 4,
+                failed: 0,
+                bytes_sent: (payload_bytes + "line".len()) as u64,
+            }
+        );
+        // 不等待投递，直接清空队列
+        sink.inner
+            .producer
+            .purge(PurgeConfig::default().queue().inflight());
+    }
+
+    #[tokio::test]
+    async fn oversized_records_are_skipped_or_dead_lettered() {
+        use wp_model_core::model::DataField;
+
+        let large = {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_chars("msg", "x".repeat(512)));
+            record
+        };
+        let small = {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_digit("id", 1));
+            record
+        };
+
+        // skip：超大消息仅计数，不交给生产者
+        let mut sink = unreachable_sink();
+        sink.max_message_bytes = Some(128);
+        sink.sink_record(&large).await.expect("skipped");
+        sink.sink_record(&small).await.expect("enqueued");
+        assert_eq!(sink.oversize_skipped(), 1);
+        assert_eq!(sink.metrics().flushed, 1);
+        sink.inner
+            .producer
+            .purge(PurgeConfig::default().queue().inflight());
+
+        // dlq：超大消息写入死信文件
+        let path = std::env::temp_dir().join("wp_connectors_kafka_oversize.jsonl");
+        let _ = std::fs::remove_file(&path);
+        let mut sink = unreachable_sink();
+        sink.max_message_bytes = Some(128);
+        sink.oversize_dlq = Some(open_dlq(path.to_str().unwrap()).expect("open dlq"));
+        sink.sink_record(&large).await.expect("dead-lettered");
+        sink.sink_bytes(&[b'y'; 200]).await.expect("dead-lettered");
+        sink.sink_record(&small).await.expect("enqueued");
+        assert_eq!(sink.oversize_skipped(), 2);
+        assert_eq!(sink.metrics().flushed, 1);
+
+        let content = std::fs::read_to_string(&path).expect("read dlq");
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("json line"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(
+            lines[0]["payload"]
+                .as_str()
+                .unwrap()
+                .contains(&"x".repeat(512))
+        );
+        assert_eq!(lines[1]["bytes"], 200);
+        sink.inner
+            .producer
+            .purge(PurgeConfig::default().queue().inflight());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn flush_task_start_and_stop_transitions() {
+        let mut sink = unreachable_sink();
+        assert!(sink.flush_handle.is_none());
+
+        sink.start_flush_task(Duration::from_millis(10));
+        assert!(sink.flush_handle.is_some());
+        assert!(sink.stop_tx.is_some());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        sink.stop().await.expect("stop");
+        assert!(sink.flush_handle.is_none());
+        assert!(sink.stop_tx.is_none());
+    }
+
+    #[tokio::test]
+    async fn reconnect_restarts_flush_task_on_new_producer() {
+        let mut sink = unreachable_sink();
+        sink.start_flush_task(Duration::from_millis(10));
+        let old = Arc::downgrade(&sink.inner);
+
+        sink.reconnect().await.expect("reconnect");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        // 旧生产者已无任务持有，flush 任务与 Sink 共享新生产者
+        assert!(old.upgrade().is_none());
+        assert!(sink.flush_handle.is_some());
+        assert_eq!(Arc::strong_count(&sink.inner), 2);
+
+        sink.stop().await.expect("stop");
+    }
+
+    #[tokio::test]
+    async fn flush_task_ignores_zero_interval() {
+        let mut sink = unreachable_sink();
+        sink.start_flush_task(Duration::ZERO);
+        assert!(sink.flush_handle.is_none());
+    }
+
+    #[tokio::test]
+    async fn stop_with_deadline_respects_deadline() {
+        let conf = KWProducerConf::new("127.0.0.1:1").set_topic_conf("wp_deadline", 1, 1);
+        let mut sink = KafkaSink::new(
+            Arc::new(KWProducer::new(conf).expect("create producer")),
+            TextFmt::Json,
+        );
+        let started = Instant::now();
+        let report = sink
+            .stop_with_deadline(Duration::from_millis(200))
+            .await
+            .expect("stop");
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(report.dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn from_conf_tolerates_topic_creation_failure_when_topic_exists() {
+        use rdkafka_wrap::mocking::MockCluster;
+
+        // 模拟集群不支持 CreateTopics，创建请求失败，但 Topic 已存在
+        let cluster = MockCluster::new(1).expect("mock cluster");
+        cluster
+            .create_topic("wp_existing", 1, 1)
+            .expect("create topic");
+        let conf = KafkaSinkConf {
+            brokers: cluster.bootstrap_servers(),
+            topic: "wp_existing".into(),
+            num_partitions: 1,
+            replication: 1,
+            ..KafkaSinkConf::default()
+        };
+        let mut sink = KafkaSink::from_conf(&conf, TextFmt::Json)
+            .await
+            .expect("existing topic is not fatal");
+        sink.stop().await.expect("stop");
+
+        let conf = KafkaSinkConf {
+            topic: "wp_missing".into(),
+            ..conf
+        };
+        assert!(KafkaSink::from_conf(&conf, TextFmt::Json).await.is_err());
+
+        // 关闭自动创建时不访问 admin 接口
+        let conf = KafkaSinkConf {
+            brokers: "127.0.0.1:1".into(),
+            auto_create_topic: false,
+            ..conf
+        };
+        let mut sink = KafkaSink::from_conf(&conf, TextFmt::Json)
+            .await
+            .expect("creation skipped");
+        sink.stop().await.expect("stop");
+    }
+
+    #[tokio::test]
+    async fn preserve_order_delivers_records_in_input_order() {
+        use rdkafka_wrap::consumer::{BaseConsumer, Consumer};
+        use rdkafka_wrap::message::Message;
+        use rdkafka_wrap::mocking::MockCluster;
+        use rdkafka_wrap::{Offset, TopicPartitionList};
+        use wp_model_core::model::DataField;
+
+        let cluster = MockCluster::new(1).expect("mock cluster");
+        cluster
+            .create_topic("wp_ordered", 1, 1)
+            .expect("create topic");
+        let conf = KafkaSinkConf {
+            brokers: cluster.bootstrap_servers(),
+            topic: "wp_ordered".into(),
+            auto_create_topic: false,
+            preserve_order: true,
+            max_inflight_messages: Some(100),
+            ..KafkaSinkConf::default()
+        };
+        let mut sink = KafkaSink::from_conf(&conf, TextFmt::Json)
+            .await
+            .expect("build sink");
+        assert_eq!(sink.max_inflight, Some(1));
+        for id in 0..20 {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_digit("id", id));
+            sink.sink_record(&record).await.unwrap();
+            // 上一条确认之前不会发送下一条
+            assert!(sink.inner.producer.in_flight_count() <= 1);
+        }
+        sink.stop().await.expect("stop");
+
+        let consumer: BaseConsumer = ClientConfig::new()
+            .set("bootstrap.servers", cluster.bootstrap_servers())
+            .set("group.id", "wp_ordered_check")
+            .create()
+            .expect("create consumer");
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition_offset("wp_ordered", 0, Offset::Beginning)
+            .unwrap();
+        consumer.assign(&tpl).unwrap();
+        let mut ids = Vec::new();
+        while ids.len() < 20 {
+            let msg = consumer
+                .poll(Duration::from_secs(5))
+                .expect("message before timeout")
+                .expect("consume");
+            let value: serde_json::Value = serde_json::from_slice(msg.payload().unwrap()).unwrap();
+            ids.push(value["id"].as_i64().unwrap());
+        }
+        assert_eq!(ids, (0..20).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn disabled_sink_drops_everything() {
+        let mut sink = DisabledKafkaSink::new("wp_disabled");
+        sink.sink_records(vec![
+            Arc::new(DataRecord::default()),
+            Arc::new(DataRecord::default()),
+        ])
+        .await
+        .expect("records dropped");
+        sink.sink_str("line").await.expect("raw dropped");
+        sink.stop().await.expect("stop");
+        assert_eq!(sink.dropped, 3);
     }
 }
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use bytes::Bytes;
 use orion_error::ErrorOweBase;
 use rdkafka_wrap::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
 use rdkafka_wrap::client::DefaultClientContext;
@@ -6,22 +7,53 @@ use rdkafka_wrap::config::RDKafkaLogLevel;
 use rdkafka_wrap::error::KafkaError;
 use rdkafka_wrap::types::RDKafkaErrorCode;
 use rdkafka_wrap::{ClientConfig, KWConsumer, KWConsumerConf, Message};
-use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
 use wp_model_core::event_id::next_wp_event_id;
 use wp_model_core::raw::RawData;
 
 use crate::WP_SRC_VAL;
+use crate::kafka::config::{KafkaSourceConf, ValueFormat, is_topic_pattern};
+use crate::kafka::decode::{KAFKA_DECODE_ERRORS, decode_payload};
+use crate::kafka::flow::{InFlightGate, KeepPaused, PartitionPause};
+use crate::kafka::lag::spawn_lag_reporter;
+use crate::utils::ingest_time::IngestTime;
 use wp_connector_api::{
-    DataSource, SourceBatch, SourceError, SourceEvent, SourceReason, SourceResult, Tags,
+    AckToken, DataSource, SourceBatch, SourceCaps, SourceError, SourceEvent, SourceReason,
+    SourceResult, Tags,
 };
 
 type AnyResult<T> = anyhow::Result<T>;
 
+/// 消息所在分区
+pub const KAFKA_PARTITION_TAG: &str = "kafka_partition";
+/// 消息在分区内的 offset
+pub const KAFKA_OFFSET_TAG: &str = "kafka_offset";
+/// broker 记录的消息时间戳（毫秒）；消息不带时间戳时不设置
+pub const KAFKA_TIMESTAMP_TAG: &str = "kafka_timestamp";
+/// `skip_empty_payloads = false` 时空载荷（null / tombstone）消息带上该标签，值为 `true`
+pub const KAFKA_TOMBSTONE_TAG: &str = "kafka_tombstone";
+
+/// 暂停消费期间 `receive` 的等待间隔：背压暂停时返回 `NotData` 以便运行时继续 ack，
+/// 手动暂停时返回空批次
+const PAUSED_RECV_INTERVAL: Duration = Duration::from_millis(50);
+
 pub struct KafkaSource {
     key: String,
     tags: Tags,
     consumer: KWConsumer,
+    lag_task: Option<JoinHandle<()>>,
+    gate: Option<InFlightGate>,
+    poll_timeout: Option<Duration>,
+    value_format: ValueFormat,
+    /// 是否由 [`KafkaSource::pause`] 手动暂停
+    paused: bool,
+    /// 设置后每条消息带上接入时间标签
+    ingest_time: Option<IngestTime>,
+    /// 是否丢弃空载荷消息，见 [`message_payload`]
+    skip_empty_payloads: bool,
 }
 
 impl KafkaSource {
@@ -36,48 +68,213 @@ impl KafkaSource {
         config: &KafkaSourceConf,
     ) -> AnyResult<Self> {
         // Create topics if not exists (best-effort)
-        create_topics(config).await?;
+        ensure_topics(config).await?;
 
         wp_log::info_data!("[kafka] topics: {:?}, group_id: {}", config.topic, group_id);
         let mut conf = KWConsumerConf::new(&config.brokers, group_id)
             .set_log_level(RDKafkaLogLevel::Info)
             .set_topics(config.topic.clone());
-        if let Some(config) = &config.config {
-            let mut map = HashMap::new();
-            for c in config {
-                let v: Vec<&str> = c.split('=').collect();
-                if v.len() >= 2 {
-                    map.insert(v[0].trim(), v[1].trim());
-                }
-            }
-            conf = conf.set_config(map);
+        let consumer_config = config.consumer_config();
+        if !consumer_config.is_empty() {
+            conf = conf.set_config(consumer_config);
         }
         let consumer = KWConsumer::new_subscribe(conf)?;
+        let lag_task = (config.lag_interval_secs > 0).then(|| {
+            spawn_lag_reporter(
+                consumer.consumer.clone(),
+                group_id.to_string(),
+                Duration::from_secs(config.lag_interval_secs),
+            )
+        });
         Ok(Self {
             key,
             consumer,
             tags,
+            lag_task,
+            gate: config.max_in_flight.map(InFlightGate::new),
+            poll_timeout: config.poll_timeout_ms.map(Duration::from_millis),
+            value_format: config.value_format,
+            paused: false,
+            ingest_time: None,
+            skip_empty_payloads: config.skip_empty_payloads,
         })
     }
 
-    pub async fn recv_impl(&mut self) -> SourceResult<SourceBatch> {
+    /// 为每条消息写入接入时间标签，`None` 表示不写入
+    pub fn with_ingest_time(mut self, ingest_time: Option<IngestTime>) -> Self {
+        self.ingest_time = ingest_time;
+        self
+    }
+
+    /// 暂停当前分配的全部分区（如维护窗口），不断开连接、不退出消费组；
+    /// 暂停期间 `receive` 等待片刻后返回空批次
+    pub fn pause(&mut self) -> SourceResult<()> {
         self.consumer
-            .recv()
+            .consumer
+            .pause_all()
+            .map_err(KafkaErrorWrapper)
+            .owe(SourceReason::SupplierError("kafka pause".to_string()))?;
+        self.paused = true;
+        wp_log::info_data!("[kafka] {} consumption paused", self.key);
+        Ok(())
+    }
+
+    /// 恢复 [`KafkaSource::pause`] 暂停的分区；`max_in_flight` 背压仍处于暂停状态时，
+    /// 分区待在途数回落后再恢复
+    pub fn resume(&mut self) -> SourceResult<()> {
+        if !self.paused {
+            return Ok(());
+        }
+        if !self.gate.as_ref().is_some_and(InFlightGate::is_paused) {
+            self.consumer
+                .consumer
+                .resume_all()
+                .map_err(KafkaErrorWrapper)
+                .owe(SourceReason::SupplierError("kafka resume".to_string()))?;
+        }
+        self.paused = false;
+        wp_log::info_data!("[kafka] {} consumption resumed", self.key);
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// 停止消费延迟采集任务
+    fn stop_lag_task(&mut self) {
+        if let Some(task) = self.lag_task.take() {
+            task.abort();
+        }
+    }
+
+    pub async fn recv_impl(&mut self) -> SourceResult<SourceBatch> {
+        if self.paused {
+            tokio::time::sleep(PAUSED_RECV_INTERVAL).await;
+            return Ok(SourceBatch::new());
+        }
+        if self.gate.as_ref().is_some_and(InFlightGate::is_paused) {
+            tokio::time::sleep(PAUSED_RECV_INTERVAL).await;
+            return Err(SourceReason::NotData.into());
+        }
+        let parts = poll_with_timeout(self.consumer.recv(), self.poll_timeout)
             .await
             .map(|msg| {
-                let payload = Bytes::copy_from_slice(msg.payload().unwrap_or(&[]));
-                let mut stags = self.tags.clone();
-                stags.set(WP_SRC_VAL, msg.topic().to_string());
-                vec![SourceEvent::new(
-                    next_wp_event_id(),
-                    self.key.clone(),
-                    RawData::Bytes(payload),
-                    stags.into(),
-                )]
+                message_payload(&self.tags, &msg, self.skip_empty_payloads).map(
+                    |(payload, tags)| {
+                        // 空载荷不经 value_format 解码，原样以空字节产出
+                        let decoded = if payload.is_empty() {
+                            Ok(RawData::Bytes(Bytes::new()))
+                        } else {
+                            decode_payload(self.value_format, payload)
+                        };
+                        (decoded, tags)
+                    },
+                )
             })
             .map_err(KafkaErrorWrapper)
-            .owe(SourceReason::SupplierError("kafka".to_string()))
+            .owe(SourceReason::SupplierError("kafka".to_string()))?;
+        let Some((decoded, mut stags)) = parts else {
+            return Err(SourceReason::NotData.into());
+        };
+        let payload = match decoded {
+            Ok(payload) => payload,
+            Err(err) => {
+                KAFKA_DECODE_ERRORS
+                    .with_label_values(&[&self.key, "json"])
+                    .inc();
+                wp_log::warn_data!(
+                    "[kafka] {} drop message at {}/{}: {}",
+                    self.key,
+                    stags.get(KAFKA_PARTITION_TAG).unwrap_or("-"),
+                    stags.get(KAFKA_OFFSET_TAG).unwrap_or("-"),
+                    err
+                );
+                return Err(SourceReason::NotData.into());
+            }
+        };
+        if let Some(ingest_time) = &self.ingest_time {
+            ingest_time.stamp(&mut stags);
+        }
+        let batch = vec![SourceEvent::new(
+            next_wp_event_id(),
+            self.key.clone(),
+            payload,
+            stags.into(),
+        )];
+        if let Some(gate) = self.gate.as_mut() {
+            gate.on_emit(self.consumer.consumer.as_ref())
+                .map_err(KafkaErrorWrapper)
+                .owe(SourceReason::SupplierError("kafka pause".to_string()))?;
+        }
+        Ok(batch)
+    }
+}
+
+/// 以 `timeout` 为上限等待一次拉取；超时或无消息时先让出 `timeout` 再返回
+/// `NoMessageReceived`（映射为 `NotData`），避免空闲 Topic 上的空转
+async fn poll_with_timeout<T>(
+    poll: impl Future<Output = Result<T, KafkaError>>,
+    timeout: Option<Duration>,
+) -> Result<T, KafkaError> {
+    let Some(timeout) = timeout else {
+        return poll.await;
+    };
+    let start = tokio::time::Instant::now();
+    match tokio::time::timeout(timeout, poll).await {
+        Ok(Err(KafkaError::NoMessageReceived)) => {
+            tokio::time::sleep_until(start + timeout).await;
+            Err(KafkaError::NoMessageReceived)
+        }
+        Ok(result) => result,
+        Err(_) => Err(KafkaError::NoMessageReceived),
+    }
+}
+
+/// 取出消息体并构建事件标签。空载荷（null / tombstone，常见于压缩 Topic）消息在
+/// `skip_empty` 时返回 `None` 丢弃，否则带上 [`KAFKA_TOMBSTONE_TAG`] 以空载荷产出
+fn message_payload<'a, M: Message>(
+    base: &Tags,
+    msg: &'a M,
+    skip_empty: bool,
+) -> Option<(&'a [u8], Tags)> {
+    let payload = msg.payload().unwrap_or_default();
+    if payload.is_empty() && skip_empty {
+        wp_log::debug_data!(
+            "[kafka] skip empty message at {}/{}/{}",
+            msg.topic(),
+            msg.partition(),
+            msg.offset()
+        );
+        return None;
+    }
+    let mut tags = message_tags(base, msg);
+    if payload.is_empty() {
+        tags.set(KAFKA_TOMBSTONE_TAG, "true");
+    }
+    Some((payload, tags))
+}
+
+/// 在基础标签上追加消息元数据：topic、分区、offset 以及（若存在）时间戳
+fn message_tags<M: Message>(base: &Tags, msg: &M) -> Tags {
+    let mut tags = base.clone();
+    tags.set(WP_SRC_VAL, msg.topic().to_string());
+    tags.set(KAFKA_PARTITION_TAG, msg.partition().to_string());
+    tags.set(KAFKA_OFFSET_TAG, msg.offset().to_string());
+    if let Some(ts) = msg.timestamp().to_millis() {
+        tags.set(KAFKA_TIMESTAMP_TAG, ts.to_string());
     }
+    tags
+}
+
+/// 按配置自动创建缺失的 Topic；关闭 `auto_create_topics` 时不访问 admin 接口，返回 `false`
+async fn ensure_topics(config: &KafkaSourceConf) -> AnyResult<bool> {
+    if !config.auto_create_topics {
+        wp_log::info_data!("[kafka] topic auto-creation disabled, subscribe only");
+        return Ok(false);
+    }
+    create_topics(config).await?;
+    Ok(true)
 }
 
 async fn create_topics(config: &KafkaSourceConf) -> AnyResult<()> {
@@ -86,7 +283,15 @@ async fn create_topics(config: &KafkaSourceConf) -> AnyResult<()> {
         .set_log_level(RDKafkaLogLevel::Info)
         .create()?;
     for topic in &config.topic {
-        let new_topic = NewTopic::new(topic, 1, TopicReplication::Fixed(1));
+        if is_topic_pattern(topic) {
+            wp_log::info_data!("[kafka] skip auto-creation for topic pattern '{}'", topic);
+            continue;
+        }
+        let new_topic = NewTopic::new(
+            topic,
+            config.num_partitions,
+            TopicReplication::Fixed(config.replication),
+        );
         let results = admin_client
             .create_topics::<Vec<&NewTopic>>(vec![&new_topic], &AdminOptions::new())
             .await?;
@@ -141,7 +346,169 @@ impl DataSource for KafkaSource {
     fn identifier(&self) -> String {
         self.identifier().to_string()
     }
+    fn caps(&self) -> SourceCaps {
+        SourceCaps {
+            ack: self.gate.is_some(),
+            ..SourceCaps::default()
+        }
+    }
+    async fn close(&mut self) -> SourceResult<()> {
+        self.stop_lag_task();
+        Ok(())
+    }
+    async fn ack(&mut self, _token: Arc<dyn AckToken>) -> SourceResult<()> {
+        let Some(gate) = self.gate.as_mut() else {
+            return Err(SourceReason::SupplierError("ack unsupported".into()).into());
+        };
+        let ctrl: &dyn PartitionPause = if self.paused {
+            &KeepPaused
+        } else {
+            self.consumer.consumer.as_ref()
+        };
+        gate.on_ack(ctrl)
+            .map_err(KafkaErrorWrapper)
+            .owe(SourceReason::SupplierError("kafka resume".to_string()))
+    }
 }
-use bytes::Bytes;
 
-use crate::kafka::config::KafkaSourceConf;
+impl Drop for KafkaSource {
+    fn drop(&mut self) {
+        self.stop_lag_task();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rdkafka_wrap::Timestamp;
+    use rdkafka_wrap::message::OwnedMessage;
+
+    fn synthetic_message(timestamp: Timestamp) -> OwnedMessage {
+        OwnedMessage::new(
+            Some(b"payload".to_vec()),
+            None,
+            "wp_topic".into(),
+            timestamp,
+            3,
+            42,
+            None,
+        )
+    }
+
+    #[test]
+    fn message_tags_carry_partition_offset_and_timestamp() {
+        let mut base = Tags::new();
+        base.set("env", "test");
+        let msg = synthetic_message(Timestamp::CreateTime(1_700_000_000_123));
+        let tags = message_tags(&base, &msg);
+        assert_eq!(tags.get("env"), Some("test"));
+        assert_eq!(tags.get(WP_SRC_VAL), Some("wp_topic"));
+        assert_eq!(tags.get(KAFKA_PARTITION_TAG), Some("3"));
+        assert_eq!(tags.get(KAFKA_OFFSET_TAG), Some("42"));
+        assert_eq!(tags.get(KAFKA_TIMESTAMP_TAG), Some("1700000000123"));
+    }
+
+    #[test]
+    fn message_tags_skip_missing_timestamp() {
+        let msg = synthetic_message(Timestamp::NotAvailable);
+        let tags = message_tags(&Tags::new(), &msg);
+        assert_eq!(tags.get(KAFKA_OFFSET_TAG), Some("42"));
+        assert!(!tags.contains_key(KAFKA_TIMESTAMP_TAG));
+    }
+
+    #[test]
+    fn empty_payload_is_skipped_or_tagged_as_tombstone() {
+        let tombstone = OwnedMessage::new(
+            None,
+            Some(b"user-1".to_vec()),
+            "wp_topic".into(),
+            Timestamp::NotAvailable,
+            0,
+            7,
+            None,
+        );
+        assert!(message_payload(&Tags::new(), &tombstone, true).is_none());
+
+        let (payload, tags) =
+            message_payload(&Tags::new(), &tombstone, false).expect("kept when not skipping");
+        assert!(payload.is_empty());
+        assert_eq!(tags.get(KAFKA_TOMBSTONE_TAG), Some("true"));
+        assert_eq!(tags.get(KAFKA_OFFSET_TAG), Some("7"));
+
+        let msg = synthetic_message(Timestamp::NotAvailable);
+        let (payload, tags) = message_payload(&Tags::new(), &msg, true).expect("non-empty");
+        assert_eq!(payload, b"payload");
+        assert!(!tags.contains_key(KAFKA_TOMBSTONE_TAG));
+    }
+
+    #[tokio::test]
+    async fn poll_timeout_bounds_wait_and_yields_on_empty_poll() {
+        let timeout = Duration::from_millis(50);
+        let start = tokio::time::Instant::now();
+        let pending = std::future::pending::<Result<(), KafkaError>>();
+        let err = poll_with_timeout(pending, Some(timeout))
+            .await
+            .expect_err("idle poll times out");
+        assert_eq!(err, KafkaError::NoMessageReceived);
+        assert!(start.elapsed() >= timeout);
+
+        let start = tokio::time::Instant::now();
+        let empty = async { Err::<(), _>(KafkaError::NoMessageReceived) };
+        poll_with_timeout(empty, Some(timeout))
+            .await
+            .expect_err("empty poll");
+        assert!(start.elapsed() >= timeout);
+
+        let start = tokio::time::Instant::now();
+        let ready = async { Ok::<_, KafkaError>(7) };
+        assert_eq!(poll_with_timeout(ready, Some(timeout)).await, Ok(7));
+        assert!(start.elapsed() < timeout);
+    }
+
+    #[tokio::test]
+    async fn pause_yields_empty_batches_until_resume() {
+        let conf = KWConsumerConf::new("127.0.0.1:1", "wp_pause_group")
+            .set_topics(vec!["wp_pause".to_string()]);
+        let mut source = KafkaSource {
+            key: "kafka_pause".into(),
+            tags: Tags::new(),
+            consumer: KWConsumer::new_subscribe(conf).expect("create consumer"),
+            lag_task: None,
+            gate: None,
+            poll_timeout: Some(Duration::from_millis(200)),
+            value_format: ValueFormat::Raw,
+            paused: false,
+            ingest_time: None,
+            skip_empty_payloads: true,
+        };
+
+        source.pause().expect("pause");
+        assert!(source.is_paused());
+        let start = tokio::time::Instant::now();
+        let batch = source.receive().await.expect("empty batch while paused");
+        assert!(batch.is_empty());
+        assert!(start.elapsed() < Duration::from_millis(200));
+
+        // 恢复后重新拉取：broker 不可达，等满 poll_timeout 后返回无数据
+        source.resume().expect("resume");
+        assert!(!source.is_paused());
+        let start = tokio::time::Instant::now();
+        assert!(source.receive().await.is_err());
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn ensure_topics_skips_admin_when_disabled() {
+        let conf = KafkaSourceConf {
+            // 不可达地址：若访问 admin 接口将报错或超时
+            brokers: "127.0.0.1:1".into(),
+            auto_create_topics: false,
+            ..KafkaSourceConf::default()
+        };
+        let created = tokio::time::timeout(Duration::from_secs(1), ensure_topics(&conf))
+            .await
+            .expect("should return without contacting brokers")
+            .expect("disabled auto-creation never fails");
+        assert!(!created);
+    }
+}
@@ -0,0 +1,163 @@
+//! Kafka 生产者队列与发送错误指标
+//!
+//! 周期性采集生产者状态并写入全局 Prometheus registry：
+//! - `wparse_kafka_producer_queue_len`：librdkafka 本地队列中尚未送达的消息数；
+//! - `wparse_kafka_producer_tx_errors`：发送失败的累计次数。
+//!
+//! librdkafka 的统计 JSON（`statistics.interval.ms`）只能通过生产者 context 的回调获取，
+//! 而 `rdkafka_wrap::KWProducer` 固定使用默认 context，因此 Sink 按 `stats_interval_ms`
+//! 周期读取 `in_flight_count` 作为队列长度，并在发送失败时递增错误计数。
+//! 自行持有生产者 context 的调用方可用 [`ProducerStats::from_json`] 解析回调内容，
+//! 再交给 [`StatsRecorder`] 写入相同的指标。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use prometheus::{IntCounterVec, IntGaugeVec, register_int_counter_vec, register_int_gauge_vec};
+use rdkafka_wrap::KWProducer;
+use rdkafka_wrap::producer::Producer;
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+lazy_static! {
+    pub static ref KAFKA_PRODUCER_QUEUE_LEN: IntGaugeVec = register_int_gauge_vec!(
+        "wparse_kafka_producer_queue_len",
+        "Messages waiting in the Kafka producer queue.",
+        &["topic"]
+    )
+    .expect("register wparse_kafka_producer_queue_len fail");
+    pub static ref KAFKA_PRODUCER_TX_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "wparse_kafka_producer_tx_errors",
+        "Kafka producer transmission errors.",
+        &["topic"]
+    )
+    .expect("register wparse_kafka_producer_tx_errors fail");
+}
+
+/// 一次采集得到的生产者状态
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProducerStats {
+    /// 队列中的消息数
+    pub queue_len: i64,
+    /// 累计发送错误数
+    pub tx_errors: u64,
+}
+
+impl ProducerStats {
+    /// 解析 librdkafka 统计 JSON：`msg_cnt` 为队列长度，各 broker 的 `txerrs` 之和为错误数
+    ///
+    /// 只读取这两个字段，不依赖特定 librdkafka 版本的完整统计结构。
+    pub fn from_json(raw: &[u8]) -> Result<Self, String> {
+        let stats: Value =
+            serde_json::from_slice(raw).map_err(|e| format!("invalid kafka statistics: {e}"))?;
+        let queue_len = stats
+            .get("msg_cnt")
+            .and_then(Value::as_i64)
+            .ok_or("kafka statistics has no msg_cnt")?;
+        let tx_errors = stats
+            .get("brokers")
+            .and_then(Value::as_object)
+            .map(|brokers| {
+                brokers
+                    .values()
+                    .filter_map(|b| b.get("txerrs").and_then(Value::as_u64))
+                    .sum()
+            })
+            .unwrap_or(0);
+        Ok(Self {
+            queue_len,
+            tx_errors,
+        })
+    }
+}
+
+/// 将累计值形式的状态写入指标；计数器按与上次采集的差值递增
+pub struct StatsRecorder {
+    topic: String,
+    last_tx_errors: u64,
+}
+
+impl StatsRecorder {
+    pub fn new(topic: impl Into<String>) -> Self {
+        Self {
+            topic: topic.into(),
+            last_tx_errors: 0,
+        }
+    }
+
+    pub fn record(&mut self, stats: &ProducerStats) {
+        let labels = [self.topic.as_str()];
+        KAFKA_PRODUCER_QUEUE_LEN
+            .with_label_values(&labels)
+            .set(stats.queue_len);
+        // 累计值变小说明客户端已重建，以新值为基准重新计数
+        let delta = stats.tx_errors.checked_sub(self.last_tx_errors);
+        KAFKA_PRODUCER_TX_ERRORS
+            .with_label_values(&labels)
+            .inc_by(delta.unwrap_or(stats.tx_errors));
+        self.last_tx_errors = stats.tx_errors;
+    }
+}
+
+/// 记录一次发送失败
+pub(crate) fn record_tx_error(topic: &str) {
+    KAFKA_PRODUCER_TX_ERRORS.with_label_values(&[topic]).inc();
+}
+
+/// 启动周期性队列长度采集任务；返回的句柄在 Sink 停止或重连时 abort
+pub(crate) fn spawn_queue_reporter(
+    producer: Arc<KWProducer>,
+    topic: String,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let queue_len = producer.producer.in_flight_count().max(0);
+            KAFKA_PRODUCER_QUEUE_LEN
+                .with_label_values(&[topic.as_str()])
+                .set(i64::from(queue_len));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_json_maps_queue_len_and_tx_errors() {
+        let raw = br#"{
+            "name": "rdkafka#producer-1", "type": "producer", "ts": 1,
+            "msg_cnt": 42, "msg_size": 4200, "txmsgs": 5,
+            "brokers": {
+                "b1:9092/1": {"name": "b1:9092/1", "state": "UP", "tx": 5, "txerrs": 3},
+                "b2:9092/2": {"name": "b2:9092/2", "state": "UP", "tx": 5, "txerrs": 4}
+            },
+            "topics": {}
+        }"#;
+        let stats = ProducerStats::from_json(raw).expect("parse stats");
+        assert_eq!(
+            stats,
+            ProducerStats {
+                queue_len: 42,
+                tx_errors: 7
+            }
+        );
+        assert!(ProducerStats::from_json(b"{").is_err());
+        assert!(ProducerStats::from_json(br#"{"brokers": {}}"#).is_err());
+
+        let mut recorder = StatsRecorder::new("stats_test");
+        recorder.record(&stats);
+        recorder.record(&ProducerStats {
+            queue_len: 5,
+            tx_errors: 9,
+        });
+        let labels = ["stats_test"];
+        assert_eq!(KAFKA_PRODUCER_QUEUE_LEN.with_label_values(&labels).get(), 5);
+        assert_eq!(KAFKA_PRODUCER_TX_ERRORS.with_label_values(&labels).get(), 9);
+    }
+}
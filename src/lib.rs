@@ -4,6 +4,42 @@ pub const WP_SRC_VAL: &str = "wp_src_val";
 // 通用工具模块
 pub mod utils;
 
+// 连接器清单与默认参数自检（`verify_all_defaults`）
+pub mod registry;
+
+// 离线构建计划（dry-run），返回解析后的配置而不建立连接
+pub mod plan;
+
+// 通用限速装饰器，所有 Sink 通过 `rate_limit_rps` 参数启用
+pub mod ratelimit;
+
+// Source 预取装饰器，在后台把批次读入有界通道（Kafka 通过 `prefetch` 参数启用）
+pub mod prefetch;
+
+// 通用记录过滤装饰器，所有 Sink 通过 `SinkSpec.filter` 启用
+pub mod filter;
+
+// 通用字段变换装饰器，所有 Sink 通过 `add_fields` / `rename_fields` 参数启用
+pub mod transform;
+
+// 通用采样装饰器，所有 Sink 通过 `sample_rate` 参数启用
+pub mod sample;
+
+// 通用记录去重装饰器，所有 Sink 通过 `dedup_field` 参数启用
+pub mod dedup;
+
+// 通用熔断装饰器，所有 Sink 通过 `circuit_breaker` 参数启用
+pub mod breaker;
+
+// 写入失败记录落盘装饰器，DB 与 HTTP Sink 通过 `spill_dir` 参数启用
+pub mod spill;
+
+// Fan-out Sink：将数据镜像写入多个已注册工厂构建的子 Sink
+pub mod fanout;
+
+// 窗口聚合 Sink：按键分组聚合后写入已注册工厂构建的内部 Sink
+pub mod aggregate;
+
 // Kafka：默认启用（feature = "kafka" 是默认特性）
 #[cfg(feature = "kafka")]
 pub mod kafka;
@@ -16,6 +52,10 @@ pub mod mysql;
 #[cfg(feature = "postgres")]
 pub mod postgres;
 
+// Postgres CDC：可选功能，启用方式 `--features postgres-cdc`
+#[cfg(feature = "postgres-cdc")]
+pub mod postgres_cdc;
+
 // Prometheus：可选功能，启用方式 `--features prometheus`
 #[cfg(feature = "prometheus")]
 pub mod prometheus;
@@ -47,3 +87,51 @@ pub mod clickhouse;
 // HTTP：可选功能，启用方式 `--features http`
 #[cfg(feature = "http")]
 pub mod http;
+
+// NATS：可选功能，启用方式 `--features nats`
+#[cfg(feature = "nats")]
+pub mod nats;
+
+// MQTT：可选功能，启用方式 `--features mqtt`
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+
+// Redis：可选功能，启用方式 `--features redis`
+#[cfg(feature = "redis")]
+pub mod redis;
+
+// Pulsar：可选功能，启用方式 `--features pulsar`
+#[cfg(feature = "pulsar")]
+pub mod pulsar;
+
+// MongoDB：可选功能，启用方式 `--features mongodb`
+#[cfg(feature = "mongodb")]
+pub mod mongodb;
+
+// Parquet：归档用文件 Sink，启用方式 `--features parquet`
+#[cfg(feature = "parquet")]
+pub mod parquet;
+
+// S3：对象存储批量导出 Sink，启用方式 `--features s3`
+#[cfg(feature = "s3")]
+pub mod s3;
+
+// Iceberg：表追加写入 Sink（REST Catalog），启用方式 `--features iceberg`
+#[cfg(feature = "iceberg")]
+pub mod iceberg;
+
+// gRPC：记录推送 Sink（`PushRecords` 一元调用），启用方式 `--features grpc`
+#[cfg(feature = "grpc")]
+pub mod grpc;
+
+// SQLite：边缘/嵌入式部署的本地库 Sink，启用方式 `--features sqlite`
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+// Memory：测试用 Sink，启用方式 `--features memory`（crate 内测试始终可用）
+#[cfg(any(test, feature = "memory"))]
+pub mod memory;
+
+// Null：压测用 Sink，只计数不落地，启用方式 `--features null`（crate 内测试始终可用）
+#[cfg(any(test, feature = "null"))]
+pub mod null;
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkFactory, SinkHandle,
+    SinkResult, SinkSpec,
+};
+
+use crate::memory::{MemoryBuffer, MemorySink};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+
+/// Memory Sink 工厂；同一工厂构建的所有 Sink 共享一个缓冲区
+#[derive(Default, Clone)]
+pub struct MemorySinkFactory {
+    buffer: MemoryBuffer,
+}
+
+impl MemorySinkFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回共享缓冲区句柄，用于断言写入的记录
+    pub fn buffer(&self) -> MemoryBuffer {
+        self.buffer.clone()
+    }
+}
+
+#[async_trait]
+impl SinkFactory for MemorySinkFactory {
+    fn kind(&self) -> &'static str {
+        "memory"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let sink = MemorySink::with_buffer(self.buffer());
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+impl SinkDefProvider for MemorySinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "memory_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: DECORATOR_PARAMS.into_iter().map(str::to_string).collect(),
+            default_params: ParamMap::new(),
+            origin: Some("wp-connectors:memory_sink".into()),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Memory sink implementation for wp-connectors
+//!
+//! 该模块用于管道单元测试：记录保存在内存缓冲区中，供调用方断言写入内容
+
+mod factory;
+mod sink;
+
+pub use factory::MemorySinkFactory;
+pub use sink::{MemoryBuffer, MemorySink};
@@ -0,0 +1,153 @@
+//! Memory Sink 实现 - 在内存中保存写入的记录
+//!
+//! - 记录按写入顺序追加到共享缓冲区 [`MemoryBuffer`]，通过 [`MemorySink::buffer`] 读取；
+//! - 原始数据转换为只含 `payload` 字段的记录；
+//! - `stop` 不清空缓冲区，停止后仍可断言。
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkResult};
+use wp_model_core::model::{DataField, DataRecord};
+
+/// 原始数据写入的字段名
+const RAW_PAYLOAD_FIELD: &str = "payload";
+
+/// 多个 Sink 实例与调用方共享的记录缓冲区
+pub type MemoryBuffer = Arc<Mutex<Vec<DataRecord>>>;
+
+#[derive(Default)]
+pub struct MemorySink {
+    buffer: MemoryBuffer,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用外部提供的缓冲区（例如由工厂共享）
+    pub fn with_buffer(buffer: MemoryBuffer) -> Self {
+        Self { buffer }
+    }
+
+    /// 返回缓冲区句柄
+    pub fn buffer(&self) -> MemoryBuffer {
+        self.buffer.clone()
+    }
+
+    /// 返回当前缓冲区内容的副本
+    pub fn records(&self) -> Vec<DataRecord> {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<DataRecord>> {
+        // 断言失败导致的中毒不影响已写入的数据
+        self.buffer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push_raw(&mut self, payloads: Vec<String>) {
+        let mut buffer = self.lock();
+        for payload in payloads {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_chars(RAW_PAYLOAD_FIELD, payload));
+            buffer.push(record);
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for MemorySink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for MemorySink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.lock().push(data.clone());
+        Ok(())
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.lock()
+            .extend(data.into_iter().map(Arc::unwrap_or_clone));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for MemorySink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.push_raw(vec![data.to_string()]);
+        Ok(())
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.push_raw(vec![String::from_utf8_lossy(data).into_owned()]);
+        Ok(())
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.push_raw(data.into_iter().map(str::to_string).collect());
+        Ok(())
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.push_raw(
+            data.into_iter()
+                .map(|b| String::from_utf8_lossy(b).into_owned())
+                .collect(),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: i64) -> DataRecord {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", id));
+        record
+    }
+
+    fn ids(records: &[DataRecord]) -> Vec<String> {
+        records
+            .iter()
+            .map(|r| r.field("id").unwrap().get_value().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn records_are_kept_in_order_after_stop() {
+        let mut sink = MemorySink::new();
+        sink.sink_record(&record(1)).await.unwrap();
+        sink.sink_records(vec![Arc::new(record(2)), Arc::new(record(3))])
+            .await
+            .unwrap();
+        assert_eq!(ids(&sink.records()), ["1", "2", "3"]);
+
+        let buffer = sink.buffer();
+        sink.stop().await.unwrap();
+        assert_eq!(ids(&buffer.lock().unwrap()), ["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn raw_input_becomes_payload_record() {
+        let mut sink = MemorySink::new();
+        sink.sink_str_batch(vec!["a", "b"]).await.unwrap();
+        let payloads: Vec<String> = sink
+            .records()
+            .iter()
+            .map(|r| r.field(RAW_PAYLOAD_FIELD).unwrap().get_value().to_string())
+            .collect();
+        assert_eq!(payloads, ["a", "b"]);
+    }
+}
@@ -0,0 +1,169 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError, SinkFactory,
+    SinkHandle, SinkReason, SinkResult, SinkSpec,
+};
+
+use crate::mongodb::{MongoSink, MongoSinkConfig};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::flush_limit::{FLUSH_BYTES_PARAM, parse_flush_bytes};
+
+/// MongoDB Sink 工厂，负责验证配置和构建 Sink 实例
+pub struct MongoSinkFactory;
+
+fn build_mongo_sink_conf_from_spec(spec: &SinkSpec) -> SinkResult<MongoSinkConfig> {
+    let uri = required_param(spec, "uri")?;
+    if !uri.starts_with("mongodb://") && !uri.starts_with("mongodb+srv://") {
+        return Err(SinkReason::sink(
+            "mongodb.uri must start with 'mongodb://' or 'mongodb+srv://'",
+        )
+        .into());
+    }
+    let database = required_param(spec, "database")?;
+    let collection = required_param(spec, "collection")?;
+    let batch = match spec.params.get("batch") {
+        None => None,
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Some(n as usize),
+            _ => return Err(SinkReason::sink("mongodb.batch must be a positive integer").into()),
+        },
+    };
+    let mut conf = MongoSinkConfig::new(uri, database, collection, batch);
+    conf.flush_bytes = parse_flush_bytes(spec)?;
+    Ok(conf)
+}
+
+#[async_trait]
+impl SinkFactory for MongoSinkFactory {
+    fn kind(&self) -> &'static str {
+        "mongodb"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        build_mongo_sink_conf_from_spec(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let conf = build_mongo_sink_conf_from_spec(spec)?;
+        let sink = MongoSink::from_conf(&conf).await.map_err(|err| {
+            SinkError::from(SinkReason::sink(format!("init mongodb sink failed: {err}")))
+        })?;
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+impl SinkDefProvider for MongoSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "mongodb_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec!["uri", "database", "collection", "batch", FLUSH_BYTES_PARAM]
+                .into_iter()
+                .chain(DECORATOR_PARAMS)
+                .map(str::to_string)
+                .collect(),
+            default_params: mongo_defaults(),
+            origin: Some("wp-connectors:mongodb_sink".into()),
+        }
+    }
+}
+
+/// 读取必填参数并返回修剪后的字符串
+fn required_param(spec: &SinkSpec, key: &str) -> SinkResult<String> {
+    spec.params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| SinkReason::sink(format!("mongodb.{key} must not be empty")).into())
+}
+
+fn mongo_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("uri".into(), json!(MongoSinkConfig::default_uri()));
+    params.insert("database".into(), json!("wp"));
+    params.insert("collection".into(), json!("wp_events"));
+    params.insert("batch".into(), json!(MongoSinkConfig::default_batch()));
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn base_spec() -> SinkSpec {
+        let mut params = BTreeMap::new();
+        params.insert("uri".into(), json!("mongodb://127.0.0.1:27017"));
+        params.insert("database".into(), json!("wp"));
+        params.insert("collection".into(), json!("events"));
+        SinkSpec {
+            name: "mongodb_sink".into(),
+            kind: "mongodb".into(),
+            connector_id: String::new(),
+            group: "test".into(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_minimal_spec() {
+        let conf = build_mongo_sink_conf_from_spec(&base_spec()).expect("valid spec");
+        assert_eq!(conf.database, "wp");
+        assert_eq!(conf.collection, "events");
+        assert_eq!(conf.batch, MongoSinkConfig::default_batch());
+    }
+
+    #[test]
+    fn validate_rejects_missing_required_params() {
+        for key in ["uri", "database", "collection"] {
+            let mut spec = base_spec();
+            spec.params.insert(key.into(), json!(" "));
+            let err = MongoSinkFactory.validate_spec(&spec).expect_err(key);
+            assert!(format!("{err}").contains(&format!("mongodb.{key}")));
+
+            let mut spec = base_spec();
+            spec.params.remove(key);
+            assert!(MongoSinkFactory.validate_spec(&spec).is_err());
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_uri_scheme_and_batch() {
+        let mut spec = base_spec();
+        spec.params
+            .insert("uri".into(), json!("http://127.0.0.1:27017"));
+        let err = MongoSinkFactory
+            .validate_spec(&spec)
+            .expect_err("http scheme");
+        assert!(format!("{err}").contains("mongodb.uri must start with"));
+
+        let mut spec = base_spec();
+        spec.params.insert("batch".into(), json!(0));
+        let err = MongoSinkFactory
+            .validate_spec(&spec)
+            .expect_err("zero batch");
+        assert!(format!("{err}").contains("mongodb.batch"));
+
+        let mut spec = base_spec();
+        spec.params.insert(FLUSH_BYTES_PARAM.into(), json!(-1));
+        let err = MongoSinkFactory
+            .validate_spec(&spec)
+            .expect_err("negative flush_bytes");
+        assert!(format!("{err}").contains("mongodb.flush_bytes"));
+    }
+
+    #[test]
+    fn sink_def_defaults_validate() {
+        let def = MongoSinkFactory.sink_def();
+        assert_eq!(def.id, "mongodb_sink");
+        let mut spec = base_spec();
+        spec.params = def.default_params;
+        assert!(MongoSinkFactory.validate_spec(&spec).is_ok());
+    }
+}
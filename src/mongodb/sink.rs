@@ -0,0 +1,258 @@
+//! MongoDB Sink 实现
+//!
+//! - 记录字段按类型转换为 BSON：digit -> Int64、float -> Double、time -> DateTime、
+//!   bool -> Boolean、对象/数组递归转换，其余类型写入字符串形式；
+//! - 原始数据写入为 `{ "payload": <str> }` 文档；
+//! - 文档先缓存，累计满 `batch` 条或 `flush_bytes` 字节（BSON 编码后）时 `insert_many`，
+//!   `stop` 时写出剩余文档。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mongodb::bson::{self, Bson, Document};
+use mongodb::{Client, Collection};
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
+use wp_model_core::model::{DataRecord, DataType, Value};
+
+use crate::mongodb::config::MongoSinkConfig;
+use crate::utils::flush_limit::FlushLimits;
+
+type AnyResult<T> = anyhow::Result<T>;
+
+/// 原始数据写入的字段名
+const RAW_PAYLOAD_FIELD: &str = "payload";
+
+pub struct MongoSink {
+    collection: Collection<Document>,
+    limits: FlushLimits,
+    pending: Vec<Document>,
+    pending_bytes: usize,
+}
+
+impl MongoSink {
+    /// 解析连接串并构建 Sink（驱动在首次写入时建立连接）
+    pub async fn from_conf(conf: &MongoSinkConfig) -> AnyResult<Self> {
+        let client = Client::with_uri_str(&conf.uri).await?;
+        let collection = client
+            .database(&conf.database)
+            .collection::<Document>(&conf.collection);
+        Ok(Self {
+            collection,
+            limits: FlushLimits::new(Some(conf.batch), conf.flush_bytes),
+            pending: Vec::with_capacity(conf.batch),
+            pending_bytes: 0,
+        })
+    }
+
+    /// 缓存文档，满一批时写出
+    async fn push(&mut self, docs: Vec<Document>) -> SinkResult<()> {
+        for doc in docs {
+            if self.buffer(doc) {
+                self.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 缓存一条文档，返回是否已达到写出阈值；未配置 `flush_bytes` 时不计算文档大小
+    fn buffer(&mut self, doc: Document) -> bool {
+        if self.limits.bytes.is_some() {
+            self.pending_bytes += bson::to_vec(&doc).map_or(0, |bytes| bytes.len());
+        }
+        self.pending.push(doc);
+        self.limits.reached(self.pending.len(), self.pending_bytes)
+    }
+
+    /// 写出缓存中的全部文档
+    async fn flush(&mut self) -> SinkResult<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let docs = std::mem::take(&mut self.pending);
+        self.pending_bytes = 0;
+        let count = docs.len();
+        self.collection
+            .insert_many(docs)
+            .await
+            .map_err(|e| sink_error(format!("mongodb insert_many ({count} docs) fail: {e}")))?;
+        Ok(())
+    }
+}
+
+/// 将记录转换为 BSON 文档；`Ignore` 类型的字段不写入
+pub(crate) fn record_to_document(record: &DataRecord) -> Document {
+    let mut doc = Document::new();
+    for field in &record.items {
+        if *field.get_meta() == DataType::Ignore {
+            continue;
+        }
+        doc.insert(
+            field.get_name().to_string(),
+            value_to_bson(field.get_value()),
+        );
+    }
+    doc
+}
+
+fn value_to_bson(value: &Value) -> Bson {
+    match value {
+        Value::Null | Value::Ignore(_) => Bson::Null,
+        Value::Bool(v) => Bson::Boolean(*v),
+        Value::Chars(v) => Bson::String(v.to_string()),
+        Value::Symbol(v) => Bson::String(v.to_string()),
+        Value::Float(v) => Bson::Double(*v),
+        Value::Digit(v) => Bson::Int64(*v),
+        Value::Time(v) => {
+            Bson::DateTime(bson::DateTime::from_millis(v.and_utc().timestamp_millis()))
+        }
+        Value::Obj(obj) => {
+            let mut doc = Document::new();
+            for (key, field) in obj.iter() {
+                let field = field.as_field();
+                if *field.get_meta() == DataType::Ignore {
+                    continue;
+                }
+                doc.insert(key.to_string(), value_to_bson(field.get_value()));
+            }
+            Bson::Document(doc)
+        }
+        Value::Array(values) => Bson::Array(
+            values
+                .iter()
+                .map(|field| field.as_field())
+                .filter(|field| *field.get_meta() != DataType::Ignore)
+                .map(|field| value_to_bson(field.get_value()))
+                .collect(),
+        ),
+        other => Bson::String(other.to_string()),
+    }
+}
+
+fn raw_document(payload: &[u8]) -> Document {
+    let mut doc = Document::new();
+    doc.insert(
+        RAW_PAYLOAD_FIELD,
+        String::from_utf8_lossy(payload).into_owned(),
+    );
+    doc
+}
+
+#[async_trait]
+impl AsyncCtrl for MongoSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.flush().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for MongoSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.push(vec![record_to_document(data)]).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        let docs = data
+            .iter()
+            .map(|record| record_to_document(record))
+            .collect();
+        self.push(docs).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for MongoSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.push(vec![raw_document(data.as_bytes())]).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.push(vec![raw_document(data)]).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        let docs = data.iter().map(|s| raw_document(s.as_bytes())).collect();
+        self.push(docs).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        let docs = data.iter().map(|b| raw_document(b)).collect();
+        self.push(docs).await
+    }
+}
+
+/// 统一封装 sink 层错误
+fn sink_error(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use wp_model_core::model::DataField;
+
+    #[test]
+    fn record_converts_to_typed_bson() {
+        let time = NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_milli_opt(8, 30, 0, 250)
+            .unwrap();
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 42));
+        record.append(DataField::from_chars("level", "info"));
+        record.append(DataField::from_time("ts", time));
+        record.append(DataField::from_float("cost", 1.5));
+
+        let doc = record_to_document(&record);
+        assert_eq!(doc.get("id"), Some(&Bson::Int64(42)));
+        assert_eq!(doc.get("level"), Some(&Bson::String("info".into())));
+        assert_eq!(
+            doc.get("ts"),
+            Some(&Bson::DateTime(bson::DateTime::from_millis(
+                time.and_utc().timestamp_millis()
+            )))
+        );
+        assert_eq!(doc.get("cost"), Some(&Bson::Double(1.5)));
+        // 字段顺序与记录一致
+        let keys: Vec<&str> = doc.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["id", "level", "ts", "cost"]);
+    }
+
+    #[tokio::test]
+    async fn buffer_flushes_on_count_or_bytes() {
+        let mut conf = MongoSinkConfig::new(
+            "mongodb://127.0.0.1:27017".into(),
+            "wp".into(),
+            "events".into(),
+            Some(100),
+        );
+        conf.flush_bytes = Some(4096);
+        let mut sink = MongoSink::from_conf(&conf).await.expect("lazy client");
+
+        // 小文档：第 100 条达到条数阈值
+        let due: Vec<bool> = (0..100).map(|_| sink.buffer(raw_document(b"x"))).collect();
+        assert!(due[..99].iter().all(|d| !d));
+        assert!(due[99]);
+        assert!(sink.pending_bytes < 4096);
+
+        // 大文档：两条即达到字节阈值
+        sink.pending.clear();
+        sink.pending_bytes = 0;
+        let large = vec![b'y'; 3000];
+        assert!(!sink.buffer(raw_document(&large)));
+        assert!(sink.buffer(raw_document(&large)));
+        assert_eq!(sink.pending.len(), 2);
+    }
+
+    #[test]
+    fn raw_payload_becomes_single_field_document() {
+        let doc = raw_document(b"raw-line");
+        assert_eq!(doc.get_str(RAW_PAYLOAD_FIELD), Ok("raw-line"));
+    }
+}
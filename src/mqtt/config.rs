@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::tls::TlsOptions;
+
+const DEFAULT_BROKER: &str = "mqtt://127.0.0.1:1883";
+const DEFAULT_QOS: u8 = 1;
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_KEEP_ALIVE_SECS: u64 = 30;
+const PLAIN_PORT: u16 = 1883;
+const TLS_PORT: u16 = 8883;
+
+/// MQTT Sink 的配置结构
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MqttSinkConfig {
+    /// Broker 地址：`mqtt://host[:port]` 或 `mqtts://host[:port]`
+    pub broker: String,
+    /// 发布的 topic，支持 `{field}` 占位符引用记录字段
+    pub topic: String,
+    /// 发布 QoS：0、1 或 2
+    pub qos: u8,
+    /// MQTT client id
+    pub client_id: String,
+    /// 用户名认证（可选，需与 password 同时提供）
+    pub username: Option<String>,
+    /// 密码认证（可选）
+    pub password: Option<String>,
+    /// 连接及等待确认的超时时间（秒）
+    pub connect_timeout_secs: u64,
+    /// 心跳间隔（秒）
+    pub keep_alive_secs: u64,
+    /// `mqtts://` 使用的 TLS 配置
+    #[serde(default)]
+    pub tls: TlsOptions,
+}
+
+/// 解析后的 broker 地址
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokerAddr {
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+}
+
+impl MqttSinkConfig {
+    /// 构建配置，应用默认值
+    pub fn new(broker: &str, topic: String, client_id: String) -> Self {
+        Self {
+            broker: broker.trim().to_string(),
+            topic: topic.trim().to_string(),
+            qos: DEFAULT_QOS,
+            client_id,
+            username: None,
+            password: None,
+            connect_timeout_secs: DEFAULT_CONNECT_TIMEOUT_SECS,
+            keep_alive_secs: DEFAULT_KEEP_ALIVE_SECS,
+            tls: TlsOptions::default(),
+        }
+    }
+
+    /// 解析 `broker`；scheme 支持 `mqtt`/`tcp`（明文）与 `mqtts`/`ssl`（TLS），端口缺省 1883/8883
+    pub fn broker_addr(&self) -> Result<BrokerAddr, String> {
+        let (scheme, rest) = self
+            .broker
+            .split_once("://")
+            .ok_or("must look like mqtt://host:port")?;
+        let tls = match scheme.to_ascii_lowercase().as_str() {
+            "mqtt" | "tcp" => false,
+            "mqtts" | "ssl" => true,
+            other => return Err(format!("unsupported scheme '{other}'")),
+        };
+        let rest = rest.trim_end_matches('/');
+        let (host, port) = match rest.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>()
+                    .map_err(|_| format!("invalid port '{port}'"))?,
+            ),
+            None => (rest, if tls { TLS_PORT } else { PLAIN_PORT }),
+        };
+        if host.is_empty() || host.contains('/') {
+            return Err("host must not be empty".into());
+        }
+        Ok(BrokerAddr {
+            host: host.to_string(),
+            port,
+            tls,
+        })
+    }
+
+    pub fn default_broker() -> &'static str {
+        DEFAULT_BROKER
+    }
+
+    pub fn default_qos() -> u8 {
+        DEFAULT_QOS
+    }
+
+    pub fn default_connect_timeout_secs() -> u64 {
+        DEFAULT_CONNECT_TIMEOUT_SECS
+    }
+
+    pub fn default_keep_alive_secs() -> u64 {
+        DEFAULT_KEEP_ALIVE_SECS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(broker: &str) -> Result<BrokerAddr, String> {
+        MqttSinkConfig::new(broker, "t".into(), "c".into()).broker_addr()
+    }
+
+    #[test]
+    fn broker_addr_applies_scheme_default_ports() {
+        assert_eq!(
+            addr("mqtt://broker.local").unwrap(),
+            BrokerAddr {
+                host: "broker.local".into(),
+                port: 1883,
+                tls: false
+            }
+        );
+        let tls = addr("mqtts://broker.local:9883/").unwrap();
+        assert_eq!((tls.port, tls.tls), (9883, true));
+        assert_eq!(addr("ssl://b").unwrap().port, 8883);
+        assert!(addr("broker:1883").is_err());
+        assert!(addr("http://broker").is_err());
+        assert!(addr("mqtt://broker:abc").is_err());
+        assert!(addr("mqtt://").is_err());
+    }
+}
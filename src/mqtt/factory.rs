@@ -0,0 +1,250 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError, SinkFactory,
+    SinkHandle, SinkReason, SinkResult, SinkSpec,
+};
+use wp_model_core::model::fmt_def::TextFmt;
+
+use crate::mqtt::{MqttSink, MqttSinkConfig};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::fmt::parse_text_fmt;
+use crate::utils::template::FieldTemplate;
+use crate::utils::tls::{TLS_PARAMS, TlsOptions};
+
+/// MQTT Sink 工厂，负责验证配置和构建 Sink 实例
+pub struct MqttSinkFactory;
+
+fn build_mqtt_sink_conf_from_spec(spec: &SinkSpec) -> SinkResult<(MqttSinkConfig, TextFmt)> {
+    let broker = required_param(spec, "broker")?;
+    let topic = required_param(spec, "topic")?;
+    FieldTemplate::parse(&topic)
+        .map_err(|e| SinkReason::sink(format!("mqtt.topic is invalid: {e}")))?;
+    if topic.contains(['+', '#']) {
+        return Err(SinkReason::sink("mqtt.topic must not contain wildcards").into());
+    }
+
+    let client_id =
+        optional_string(spec, "client_id").unwrap_or_else(|| format!("wp-mqtt-{}", spec.name));
+    let mut conf = MqttSinkConfig::new(&broker, topic, client_id);
+    let addr = conf
+        .broker_addr()
+        .map_err(|e| SinkReason::sink(format!("mqtt.broker is invalid: {e}")))?;
+
+    if let Some(v) = spec.params.get("qos") {
+        conf.qos = match v.as_u64() {
+            Some(n @ 0..=2) => n as u8,
+            _ => return Err(SinkReason::sink("mqtt.qos must be 0, 1 or 2").into()),
+        };
+    }
+    conf.username = optional_string(spec, "username");
+    conf.password = optional_string(spec, "password");
+    if conf.username.is_some() != conf.password.is_some() {
+        return Err(
+            SinkReason::sink("mqtt.username and mqtt.password must be set together").into(),
+        );
+    }
+    if let Some(secs) = positive_secs(spec, "connect_timeout_secs")? {
+        conf.connect_timeout_secs = secs;
+    }
+    if let Some(secs) = positive_secs(spec, "keep_alive_secs")? {
+        conf.keep_alive_secs = secs;
+    }
+    conf.tls = TlsOptions::from_params("mqtt", &spec.params)?;
+    if conf.tls.is_configured() && !addr.tls {
+        return Err(SinkReason::sink("mqtt.tls_* options require an mqtts:// broker").into());
+    }
+    let fmt = parse_text_fmt("mqtt", spec.params.get("fmt"))?;
+    Ok((conf, fmt))
+}
+
+#[async_trait]
+impl SinkFactory for MqttSinkFactory {
+    fn kind(&self) -> &'static str {
+        "mqtt"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        build_mqtt_sink_conf_from_spec(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let (conf, fmt) = build_mqtt_sink_conf_from_spec(spec)?;
+        let sink = MqttSink::from_conf(&conf, fmt).await.map_err(|err| {
+            SinkError::from(SinkReason::sink(format!("init mqtt sink failed: {err}")))
+        })?;
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+impl SinkDefProvider for MqttSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "mqtt_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: [
+                "broker",
+                "topic",
+                "qos",
+                "fmt",
+                "client_id",
+                "username",
+                "password",
+                "connect_timeout_secs",
+                "keep_alive_secs",
+            ]
+            .into_iter()
+            .chain(TLS_PARAMS)
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect(),
+            default_params: mqtt_defaults(),
+            origin: Some("wp-connectors:mqtt_sink".into()),
+        }
+    }
+}
+
+/// 读取必填参数并返回修剪后的字符串
+fn required_param(spec: &SinkSpec, key: &str) -> SinkResult<String> {
+    spec.params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| SinkReason::sink(format!("mqtt.{key} must not be empty")).into())
+}
+
+/// 读取可选字符串参数
+fn optional_string(spec: &SinkSpec, key: &str) -> Option<String> {
+    spec.params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// 读取可选的正整数秒数
+fn positive_secs(spec: &SinkSpec, key: &str) -> SinkResult<Option<u64>> {
+    match spec.params.get(key) {
+        None => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(0) | None => {
+                Err(SinkReason::sink(format!("mqtt.{key} must be a positive integer")).into())
+            }
+            Some(n) => Ok(Some(n)),
+        },
+    }
+}
+
+fn mqtt_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("broker".into(), json!(MqttSinkConfig::default_broker()));
+    params.insert("topic".into(), json!("wp/events"));
+    params.insert("qos".into(), json!(MqttSinkConfig::default_qos()));
+    params.insert("fmt".into(), json!("json"));
+    params.insert(
+        "connect_timeout_secs".into(),
+        json!(MqttSinkConfig::default_connect_timeout_secs()),
+    );
+    params.insert(
+        "keep_alive_secs".into(),
+        json!(MqttSinkConfig::default_keep_alive_secs()),
+    );
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn base_spec() -> SinkSpec {
+        let mut params = BTreeMap::new();
+        params.insert("broker".into(), json!("mqtt://127.0.0.1:1883"));
+        params.insert("topic".into(), json!("events/{tenant}"));
+        SinkSpec {
+            name: "mqtt_sink".into(),
+            kind: "mqtt".into(),
+            connector_id: String::new(),
+            group: "test".into(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_minimal_spec() {
+        let (conf, fmt) = build_mqtt_sink_conf_from_spec(&base_spec()).expect("valid spec");
+        assert_eq!(conf.broker, "mqtt://127.0.0.1:1883");
+        assert_eq!(conf.topic, "events/{tenant}");
+        assert_eq!(conf.qos, 1);
+        assert_eq!(conf.client_id, "wp-mqtt-mqtt_sink");
+        assert_eq!(fmt, TextFmt::Json);
+    }
+
+    #[test]
+    fn validate_rejects_empty_broker_and_topic() {
+        for key in ["broker", "topic"] {
+            let mut spec = base_spec();
+            spec.params.insert(key.into(), json!(" "));
+            let err = MqttSinkFactory.validate_spec(&spec).expect_err(key);
+            assert!(format!("{err}").contains(&format!("mqtt.{key}")));
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_broker_topic_qos_and_auth() {
+        let cases = [
+            ("broker", json!("http://127.0.0.1:1883")),
+            ("broker", json!("127.0.0.1:1883")),
+            ("topic", json!("events/#")),
+            ("topic", json!("events/{tenant")),
+            ("qos", json!(3)),
+            ("username", json!("user")),
+            ("keep_alive_secs", json!(0)),
+            ("fmt", json!("bogus")),
+            ("tls_insecure", json!(true)),
+        ];
+        for (key, value) in cases {
+            let mut spec = base_spec();
+            spec.params.insert(key.into(), value.clone());
+            assert!(
+                MqttSinkFactory.validate_spec(&spec).is_err(),
+                "{key}={value}"
+            );
+        }
+
+        let mut spec = base_spec();
+        spec.params
+            .insert("broker".into(), json!("mqtts://broker.local"));
+        spec.params.insert("tls_insecure".into(), json!(true));
+        let (conf, _) = build_mqtt_sink_conf_from_spec(&spec).expect("tls with mqtts");
+        assert!(conf.tls.insecure);
+    }
+
+    #[test]
+    fn sink_def_defaults_validate() {
+        let def = MqttSinkFactory.sink_def();
+        assert_eq!(def.id, "mqtt_sink");
+        let mut spec = base_spec();
+        spec.params = def.default_params;
+        assert!(MqttSinkFactory.validate_spec(&spec).is_ok());
+    }
+
+    #[tokio::test]
+    async fn build_fails_cleanly_when_broker_unreachable() {
+        let mut spec = base_spec();
+        spec.params
+            .insert("broker".into(), json!("mqtt://127.0.0.1:1"));
+        spec.params.insert("connect_timeout_secs".into(), json!(1));
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+        let err = MqttSinkFactory
+            .build(&spec, &ctx)
+            .await
+            .expect_err("unreachable broker");
+        assert!(format!("{err}").contains("init mqtt sink failed"));
+    }
+}
@@ -0,0 +1,25 @@
+//! MQTT sink implementation for wp-connectors
+//!
+//! 将记录按 `fmt` 格式化后以配置的 QoS 发布到 MQTT topic（MQTT 3.1.1）。
+//!
+//! # 配置参数
+//!
+//! - `broker`: Broker 地址（必填），`mqtt://host[:port]` 或 `mqtts://host[:port]`
+//! - `topic`: 发布的 topic（必填），支持 `{field}` 占位符，
+//!   例如 `events/{tenant}`；字段缺失时替换为 `_`
+//! - `qos`: 发布 QoS，0/1/2，默认 1
+//! - `fmt`: 输出格式，默认 json
+//! - `client_id`: MQTT client id，默认 `wp-mqtt-<sink 名称>`
+//! - `username` / `password`: 用户名密码认证（可选，需同时提供）
+//! - `connect_timeout_secs`: 连接及等待确认的超时时间，默认 10 秒
+//! - `keep_alive_secs`: 心跳间隔，默认 30 秒
+//! - `tls_ca_cert` / `tls_client_cert` / `tls_client_key` / `tls_insecure`:
+//!   TLS 配置，仅 `mqtts://` 可用
+
+mod config;
+mod factory;
+mod sink;
+
+pub use config::MqttSinkConfig;
+pub use factory::MqttSinkFactory;
+pub use sink::MqttSink;
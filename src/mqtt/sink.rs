@@ -0,0 +1,400 @@
+//! MQTT Sink 实现
+//!
+//! 记录按 `fmt` 格式化后以配置的 QoS 发布到 topic；topic 可通过 `{field}`
+//! 占位符引用记录字段。rumqttc 的事件循环在后台任务中驱动，批量写入时先逐条
+//! 入队，最后等待全部消息发出（QoS 0）或被 broker 确认（QoS 1/2）。
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rumqttc::{
+    AsyncClient, ConnectReturnCode, Event, EventLoop, MqttOptions, Outgoing, Packet, QoS,
+    TlsConfiguration, Transport,
+};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
+use wp_data_fmt::{FormatType, RecordFormatter};
+use wp_log::warn_data;
+use wp_model_core::model::{DataRecord, fmt_def::TextFmt};
+
+use crate::mqtt::config::MqttSinkConfig;
+use crate::utils::retry::{RetryPolicy, reconnect_with_backoff};
+use crate::utils::template::FieldTemplate;
+
+type AnyResult<T> = anyhow::Result<T>;
+
+/// 客户端请求队列容量
+const REQUEST_CAPACITY: usize = 1024;
+/// 事件循环出错后重新轮询前的等待时间
+const POLL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// 一次 broker 连接：客户端句柄与驱动事件循环的后台任务
+struct Connection {
+    client: AsyncClient,
+    /// 已完成的发布数：QoS 0 为已发出，QoS 1 为收到 PUBACK，QoS 2 为收到 PUBCOMP
+    completed: watch::Receiver<u64>,
+    /// 本连接上已入队的发布数
+    published: u64,
+    last_error: Arc<Mutex<Option<String>>>,
+    task: JoinHandle<()>,
+}
+
+pub struct MqttSink {
+    conf: MqttSinkConfig,
+    topic: FieldTemplate,
+    fmt: TextFmt,
+    qos: QoS,
+    conn: Connection,
+}
+
+impl MqttSink {
+    /// 连接 broker 并构建 Sink
+    pub async fn from_conf(conf: &MqttSinkConfig, fmt: TextFmt) -> AnyResult<Self> {
+        let topic = FieldTemplate::parse(&conf.topic).map_err(|e| anyhow::anyhow!(e))?;
+        let qos = to_qos(conf.qos)?;
+        let conn = connect(conf, qos).await?;
+        Ok(Self {
+            conf: conf.clone(),
+            topic,
+            fmt,
+            qos,
+            conn,
+        })
+    }
+
+    /// 根据记录解析实际发布的 topic
+    fn resolve_topic(&self, record: Option<&DataRecord>) -> String {
+        self.topic.render_with(record, sanitize_topic_level)
+    }
+
+    async fn publish(&mut self, topic: String, payload: Vec<u8>) -> SinkResult<()> {
+        self.conn
+            .client
+            .publish(topic, self.qos, false, payload)
+            .await
+            .map_err(|e| sink_error(format!("mqtt publish fail: {e}")))?;
+        self.conn.published += 1;
+        Ok(())
+    }
+
+    /// 等待已入队的消息全部完成，超时时间为 `connect_timeout_secs`
+    async fn flush(&mut self) -> SinkResult<()> {
+        let target = self.conn.published;
+        let timeout = Duration::from_secs(self.conf.connect_timeout_secs);
+        let wait = self.conn.completed.wait_for(|done| *done >= target);
+        let outcome = tokio::time::timeout(timeout, wait)
+            .await
+            .map(|res| res.is_ok());
+        match outcome {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(sink_error("mqtt event loop stopped")),
+            Err(_) => {
+                let done = *self.conn.completed.borrow();
+                let cause = self
+                    .conn
+                    .last_error
+                    .lock()
+                    .expect("mqtt error lock")
+                    .clone();
+                Err(sink_error(format!(
+                    "mqtt publish not acknowledged within {}s ({done}/{target} done){}",
+                    self.conf.connect_timeout_secs,
+                    cause.map(|e| format!(": {e}")).unwrap_or_default()
+                )))
+            }
+        }
+    }
+
+    async fn publish_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        let fmt = FormatType::from(&self.fmt);
+        let payload = fmt.fmt_record(data);
+        self.publish(self.resolve_topic(Some(data)), payload.into_bytes())
+            .await
+    }
+}
+
+impl Connection {
+    /// 发送 DISCONNECT 并等待后台任务退出，超时则强制终止
+    async fn close(&mut self) {
+        if self.task.is_finished() {
+            return;
+        }
+        let _ = self.client.disconnect().await;
+        if tokio::time::timeout(Duration::from_secs(1), &mut self.task)
+            .await
+            .is_err()
+        {
+            self.task.abort();
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn to_qos(qos: u8) -> AnyResult<QoS> {
+    match qos {
+        0 => Ok(QoS::AtMostOnce),
+        1 => Ok(QoS::AtLeastOnce),
+        2 => Ok(QoS::ExactlyOnce),
+        other => anyhow::bail!("unsupported qos {other}"),
+    }
+}
+
+/// 建立连接：在超时时间内等待 CONNACK，成功后将事件循环移交后台任务
+async fn connect(conf: &MqttSinkConfig, qos: QoS) -> AnyResult<Connection> {
+    let addr = conf.broker_addr().map_err(|e| anyhow::anyhow!(e))?;
+    let mut opts = MqttOptions::new(conf.client_id.clone(), addr.host, addr.port);
+    opts.set_keep_alive(Duration::from_secs(conf.keep_alive_secs));
+    if let (Some(user), Some(pass)) = (&conf.username, &conf.password) {
+        opts.set_credentials(user.clone(), pass.clone());
+    }
+    if addr.tls {
+        let tls = conf.tls.rustls_config("mqtt")?;
+        opts.set_transport(Transport::tls_with_config(TlsConfiguration::Rustls(
+            Arc::new(tls),
+        )));
+    }
+    let (client, mut eventloop) = AsyncClient::new(opts, REQUEST_CAPACITY);
+
+    let timeout = Duration::from_secs(conf.connect_timeout_secs);
+    tokio::time::timeout(timeout, wait_connack(&mut eventloop))
+        .await
+        .map_err(|_| anyhow::anyhow!("connect to {} timed out", conf.broker))??;
+
+    let (done_tx, completed) = watch::channel(0u64);
+    let last_error = Arc::new(Mutex::new(None));
+    let task = tokio::spawn(drive_eventloop(eventloop, qos, done_tx, last_error.clone()));
+    Ok(Connection {
+        client,
+        completed,
+        published: 0,
+        last_error,
+        task,
+    })
+}
+
+async fn wait_connack(eventloop: &mut EventLoop) -> AnyResult<()> {
+    loop {
+        if let Event::Incoming(Packet::ConnAck(ack)) = eventloop.poll().await? {
+            if ack.code == ConnectReturnCode::Success {
+                return Ok(());
+            }
+            anyhow::bail!("broker refused connection: {:?}", ack.code);
+        }
+    }
+}
+
+/// 持续轮询事件循环并统计完成的发布；出错时记录原因，由 rumqttc 在下次轮询时重连
+async fn drive_eventloop(
+    mut eventloop: EventLoop,
+    qos: QoS,
+    done: watch::Sender<u64>,
+    last_error: Arc<Mutex<Option<String>>>,
+) {
+    loop {
+        let completed = match eventloop.poll().await {
+            Ok(Event::Outgoing(Outgoing::Publish(_))) => qos == QoS::AtMostOnce,
+            Ok(Event::Incoming(Packet::PubAck(_))) => qos == QoS::AtLeastOnce,
+            Ok(Event::Incoming(Packet::PubComp(_))) => qos == QoS::ExactlyOnce,
+            Ok(Event::Outgoing(Outgoing::Disconnect)) => return,
+            Ok(_) => false,
+            Err(err) => {
+                warn_data!("[mqtt] event loop error: {}", err);
+                *last_error.lock().expect("mqtt error lock") = Some(err.to_string());
+                tokio::time::sleep(POLL_RETRY_DELAY).await;
+                false
+            }
+        };
+        if completed {
+            done.send_modify(|n| *n += 1);
+        }
+    }
+}
+
+/// 将字段值转换为合法的 topic 层级：`/`、通配符 `+`/`#` 及空白替换为 `_`
+fn sanitize_topic_level(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '/' | '+' | '#' => '_',
+            c if c.is_whitespace() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[async_trait]
+impl AsyncCtrl for MqttSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        let flushed = self.flush().await;
+        self.conn.close().await;
+        flushed
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        let (conf, qos) = (&self.conf, self.qos);
+        let conn = reconnect_with_backoff("mqtt", RetryPolicy::reconnect(), || async {
+            connect(conf, qos)
+                .await
+                .map_err(|e| sink_error(format!("mqtt reconnect fail: {e}")))
+        })
+        .await?;
+        let mut old = std::mem::replace(&mut self.conn, conn);
+        old.close().await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for MqttSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.publish_record(data).await?;
+        self.flush().await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        for record in &data {
+            self.publish_record(record.as_ref()).await?;
+        }
+        self.flush().await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for MqttSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.sink_bytes(data.as_bytes()).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.publish(self.resolve_topic(None), data.to_vec())
+            .await?;
+        self.flush().await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        for item in data {
+            self.publish(self.resolve_topic(None), item.as_bytes().to_vec())
+                .await?;
+        }
+        self.flush().await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        for item in data {
+            self.publish(self.resolve_topic(None), item.to_vec())
+                .await?;
+        }
+        self.flush().await
+    }
+}
+
+/// 统一封装 sink 层错误
+fn sink_error(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::mpsc;
+    use wp_model_core::model::DataField;
+
+    /// 读取一个 MQTT 3.1.1 报文，返回 (首字节, 报文体)
+    async fn read_packet(stream: &mut TcpStream) -> Option<(u8, Vec<u8>)> {
+        let header = stream.read_u8().await.ok()?;
+        let (mut len, mut shift) = (0usize, 0);
+        loop {
+            let byte = stream.read_u8().await.ok()?;
+            len |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        let mut body = vec![0u8; len];
+        stream.read_exact(&mut body).await.ok()?;
+        Some((header, body))
+    }
+
+    /// 极简 MQTT broker：应答 CONNECT/PINGREQ，确认 QoS 1 的 PUBLISH 并转发到 channel
+    async fn start_mock_broker() -> (String, mpsc::UnboundedReceiver<(String, u8, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            while let Some((header, body)) = read_packet(&mut stream).await {
+                match header >> 4 {
+                    1 => stream.write_all(&[0x20, 2, 0, 0]).await.unwrap(),
+                    3 => {
+                        let qos = (header >> 1) & 0x03;
+                        let topic_len = u16::from_be_bytes([body[0], body[1]]) as usize;
+                        let topic = String::from_utf8(body[2..2 + topic_len].to_vec()).unwrap();
+                        let mut offset = 2 + topic_len;
+                        if qos > 0 {
+                            let pkid = [body[offset], body[offset + 1]];
+                            offset += 2;
+                            stream
+                                .write_all(&[0x40, 2, pkid[0], pkid[1]])
+                                .await
+                                .unwrap();
+                        }
+                        let payload = String::from_utf8(body[offset..].to_vec()).unwrap();
+                        let _ = tx.send((topic, qos, payload));
+                    }
+                    12 => stream.write_all(&[0xD0, 0]).await.unwrap(),
+                    14 => break,
+                    _ => {}
+                }
+            }
+        });
+        (format!("mqtt://{addr}"), rx)
+    }
+
+    #[test]
+    fn sanitize_topic_level_replaces_reserved_chars() {
+        assert_eq!(sanitize_topic_level("a/b c+#"), "a_b_c__");
+        assert_eq!(sanitize_topic_level("tenant-1"), "tenant-1");
+    }
+
+    #[tokio::test]
+    async fn sink_records_publish_to_templated_topic_with_qos() {
+        let (url, mut rx) = start_mock_broker().await;
+        let mut conf = MqttSinkConfig::new(&url, "events/{tenant}".into(), "wp-test".into());
+        conf.connect_timeout_secs = 2;
+        let mut sink = MqttSink::from_conf(&conf, TextFmt::Json)
+            .await
+            .expect("connect mock broker");
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("tenant", "acme"));
+        record.append(DataField::from_digit("id", 7));
+        sink.sink_records(vec![Arc::new(record)])
+            .await
+            .expect("publish records");
+        sink.sink_str("raw-line").await.expect("publish raw");
+
+        let (topic, qos, payload) = rx.recv().await.expect("record message");
+        assert_eq!((topic.as_str(), qos), ("events/acme", 1));
+        assert!(payload.contains("\"tenant\":\"acme\""));
+        let (topic, _, payload) = rx.recv().await.expect("raw message");
+        assert_eq!(topic, "events/_");
+        assert_eq!(payload, "raw-line");
+
+        sink.stop().await.expect("stop flushes");
+    }
+}
@@ -0,0 +1,244 @@
+//! 以 Rust 值直接构造 [`MysqlSink`]，无需拼装 `SinkSpec` 参数表
+//!
+//! 工厂解析参数后得到同一个构造器，建连、读取列信息与启动定时写出均在
+//! [`MysqlSinkBuilder::build`] 中完成。
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use wp_connector_api::{SinkError, SinkResult};
+
+use super::config::{MysqlConf, MysqlSslMode};
+use super::sink::{
+    MysqlSink, NullFormat, OversizeAction, TableColumns, connect_error_kind, load_column_lengths,
+    load_table_columns,
+};
+use crate::utils::bytes_encoding::BytesEncoding;
+use crate::utils::column_filter::ColumnFilter;
+use crate::utils::column_map::ColumnMap;
+use crate::utils::error_kind::ErrorKind;
+use crate::utils::schema::RecordValidator;
+use crate::utils::table_route::TableRouter;
+
+pub struct MysqlSinkBuilder {
+    pub(crate) conf: MysqlConf,
+    pub(crate) table: String,
+    pub(crate) columns: Vec<String>,
+    pub(crate) schema: Option<RecordValidator>,
+    pub(crate) column_map: ColumnMap,
+    pub(crate) column_filter: ColumnFilter,
+    pub(crate) oversize: OversizeAction,
+    pub(crate) null_format: NullFormat,
+    pub(crate) bytes_encoding: Option<BytesEncoding>,
+    pub(crate) router: Option<TableRouter>,
+    pub(crate) flush_bytes: Option<usize>,
+    pub(crate) flush_interval: Option<Duration>,
+}
+
+impl MysqlSinkBuilder {
+    /// 未设置的项与 `SinkSpec` 中不配置对应参数时相同（用户名、密码取 `MysqlConf` 默认值）
+    pub fn new(
+        endpoint: impl Into<String>,
+        database: impl Into<String>,
+        table: impl Into<String>,
+    ) -> Self {
+        let table = table.into();
+        let conf = MysqlConf {
+            endpoint: endpoint.into(),
+            database: database.into(),
+            table: Some(table.clone()),
+            ..MysqlConf::default()
+        };
+        Self::from_conf(conf, table)
+    }
+
+    pub(crate) fn from_conf(conf: MysqlConf, table: String) -> Self {
+        Self {
+            conf,
+            table,
+            columns: Vec::new(),
+            schema: None,
+            column_map: ColumnMap::default(),
+            column_filter: ColumnFilter::default(),
+            oversize: OversizeAction::default(),
+            null_format: NullFormat::default(),
+            bytes_encoding: None,
+            router: None,
+            flush_bytes: None,
+            flush_interval: None,
+        }
+    }
+
+    pub fn credentials(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.conf.username = username.into();
+        self.conf.password = password.into();
+        self
+    }
+
+    /// 连接的 TLS 模式与 CA 路径，见 [`MysqlSslMode`]
+    pub fn ssl(mut self, mode: MysqlSslMode, ca: Option<String>) -> Self {
+        self.conf.ssl_mode = Some(mode);
+        self.conf.ssl_ca = ca;
+        self
+    }
+
+    /// 写入的列，按顺序生成 VALUES
+    pub fn columns<I: IntoIterator<Item = S>, S: Into<String>>(mut self, columns: I) -> Self {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 单条 INSERT 语句的最大行数
+    pub fn batch(mut self, batch: usize) -> Self {
+        self.conf.batch = Some(batch);
+        self
+    }
+
+    /// 单条 INSERT 语句 VALUES 部分的最大字节数
+    pub fn flush_bytes(mut self, flush_bytes: usize) -> Self {
+        self.flush_bytes = Some(flush_bytes);
+        self
+    }
+
+    /// 定时写出间隔，见 [`MysqlSink::with_flush_interval`]
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = Some(interval);
+        self
+    }
+
+    pub fn schema(mut self, schema: RecordValidator) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    pub fn column_map(mut self, column_map: ColumnMap) -> Self {
+        self.column_map = column_map;
+        self
+    }
+
+    pub fn column_filter(mut self, column_filter: ColumnFilter) -> Self {
+        self.column_filter = column_filter;
+        self
+    }
+
+    pub fn oversize(mut self, oversize: OversizeAction) -> Self {
+        self.oversize = oversize;
+        self
+    }
+
+    pub fn null_format(mut self, null_format: NullFormat) -> Self {
+        self.null_format = null_format;
+        self
+    }
+
+    /// 字节类字段的文本编码，见 [`BytesEncoding`]
+    pub fn bytes_encoding(mut self, bytes_encoding: BytesEncoding) -> Self {
+        self.bytes_encoding = Some(bytes_encoding);
+        self
+    }
+
+    /// 按记录字段路由到名单中的表
+    pub fn table_router(mut self, router: TableRouter) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    /// 连接数据库、按需读取列长度与路由表的列信息，并启动定时写出任务
+    pub async fn build(self) -> SinkResult<MysqlSink> {
+        let url = self.conf.get_database_url();
+        let mut opt = ConnectOptions::new(url);
+        opt.max_connections(50)
+            .min_connections(1)
+            .connect_timeout(Duration::from_secs(8))
+            .acquire_timeout(Duration::from_secs(8))
+            .idle_timeout(Duration::from_secs(8))
+            .max_lifetime(Duration::from_secs(8))
+            .sqlx_logging(false)
+            .map_sqlx_mysql_opts(|opt| opt.statement_cache_capacity(0))
+            .sqlx_logging_level(log::LevelFilter::Info);
+        let db = Database::connect(opt).await.map_err(|err| {
+            SinkError::from(
+                connect_error_kind(&err).sink("mysql", format!("connect mysql fail: {err}")),
+            )
+        })?;
+        // 仅在需要截断/跳过时读取列长度，默认行为不额外查询
+        let column_lengths = if self.oversize == OversizeAction::Error {
+            Default::default()
+        } else {
+            load_column_lengths(&db, &self.conf.database, &self.table)
+                .await
+                .map_err(|err| {
+                    SinkError::from(
+                        ErrorKind::Backend
+                            .sink("mysql", format!("load mysql column lengths fail: {err}")),
+                    )
+                })?
+        };
+        let routes = match &self.router {
+            Some(router) => {
+                load_routes(&db, &self.conf.database, &self.table, router, self.oversize).await?
+            }
+            None => HashMap::new(),
+        };
+        let mut sink = MysqlSink::new(db, self.table, self.columns)
+            .with_batch(self.conf.batch)
+            .with_flush_bytes(self.flush_bytes)
+            .with_flush_interval(self.flush_interval)
+            .with_schema(self.schema)
+            .with_column_map(self.column_map)
+            .with_column_filter(self.column_filter)
+            .with_oversize(self.oversize, column_lengths)
+            .with_null_format(self.null_format)
+            .with_bytes_encoding(self.bytes_encoding);
+        if let Some(router) = self.router {
+            sink = sink.with_table_routes(router, routes);
+        }
+        sink.start_flush_task();
+        Ok(sink)
+    }
+}
+
+/// 读取路由名单中各表（默认表除外）的列及列长度；表不存在时返回 `config` 错误
+async fn load_routes(
+    db: &DatabaseConnection,
+    database: &str,
+    default_table: &str,
+    router: &TableRouter,
+    oversize: OversizeAction,
+) -> SinkResult<HashMap<String, TableColumns>> {
+    let backend_err = |err: sea_orm::DbErr| {
+        SinkError::from(
+            ErrorKind::Backend.sink("mysql", format!("load mysql routed table fail: {err}")),
+        )
+    };
+    let mut routes = HashMap::new();
+    for table in router.tables().iter().filter(|t| *t != default_table) {
+        let columns = load_table_columns(db, database, table)
+            .await
+            .map_err(backend_err)?;
+        if columns.is_empty() {
+            return Err(ErrorKind::Config
+                .sink(
+                    "mysql",
+                    format!("mysql.tables: table '{database}.{table}' has no columns"),
+                )
+                .into());
+        }
+        let column_lengths = if oversize == OversizeAction::Error {
+            HashMap::new()
+        } else {
+            load_column_lengths(db, database, table)
+                .await
+                .map_err(backend_err)?
+        };
+        routes.insert(
+            table.clone(),
+            TableColumns {
+                columns,
+                column_lengths,
+            },
+        );
+    }
+    Ok(routes)
+}
@@ -1,9 +1,21 @@
-use crate::mysql::config::MysqlConf;
+use crate::mysql::config::{MysqlConf, MysqlSslMode};
+use crate::plan::{SinkPlanner, SourcePlanner, redact_secrets};
+use crate::spill::{self, SPILL_DIR_PARAM};
+use crate::utils::bytes_encoding::{BYTES_ENCODING_PARAM, BytesEncoding, parse_bytes_encoding};
+use crate::utils::column_filter::{COLUMN_FILTER_PARAMS, ColumnFilter};
+use crate::utils::column_map::{COLUMN_MAP_PARAM, ColumnMap};
+use crate::utils::config_blob::{self, CONFIG_BLOB_PARAM};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::error_kind::ErrorKind;
+use crate::utils::flush_limit::{FLUSH_BYTES_PARAM, parse_flush_bytes};
+use crate::utils::ingest_time::{INGEST_TIME_FIELD_PARAM, INGEST_TIME_UNIT_PARAM, IngestTime};
+use crate::utils::schema::{RecordValidator, SCHEMA_PARAMS, SchemaOptions};
+use crate::utils::table_route::{TABLE_ROUTE_PARAMS, TableRouter};
 
-use super::sink::MysqlSink;
-use super::source::MysqlSource;
+use super::builder::MysqlSinkBuilder;
+use super::sink::{NullFormat, NullLiteral, OversizeAction};
+use super::source::{MysqlFetchMode, MysqlSource, MysqlSourceMode, validate_query};
 use async_trait::async_trait;
-use sea_orm::{ConnectOptions, Database};
 use serde_json::json;
 use std::time::Duration;
 use wp_conf_base::ConfParser;
@@ -24,6 +36,7 @@ impl wp_connector_api::SourceFactory for MySQLSourceFactory {
     }
 
     fn validate_spec(&self, spec: &wp_connector_api::SourceSpec) -> SourceResult<()> {
+        let spec = &*config_blob::source_spec("mysql", spec)?;
         let endpoint = spec
             .params
             .get("endpoint")
@@ -41,6 +54,12 @@ impl wp_connector_api::SourceFactory for MySQLSourceFactory {
         if database.trim().is_empty() {
             return Err(SourceReason::Other("mysql.database must not be empty".into()).into());
         }
+        parse_source_mode(spec)?;
+        parse_fetch_mode(spec)?;
+        parse_eof_as_error(spec)?;
+        parse_query(spec)?;
+        parse_ssl(&spec.params).map_err(SourceReason::Other)?;
+        IngestTime::from_params("mysql", &spec.params)?;
 
         Ok(())
     }
@@ -50,35 +69,16 @@ impl wp_connector_api::SourceFactory for MySQLSourceFactory {
         spec: &wp_connector_api::SourceSpec,
         _ctx: &wp_connector_api::SourceBuildCtx,
     ) -> SourceResult<SourceSvcIns> {
-        let mut conf = MysqlConf::default();
-
-        if let Some(s) = spec.params.get("endpoint").and_then(|v| v.as_str()) {
-            conf.endpoint = s.to_string();
-        }
-        if let Some(s) = spec.params.get("database").and_then(|v| v.as_str()) {
-            conf.database = s.to_string();
-        }
-        if let Some(username) = spec.params.get("username").and_then(|v| v.as_str()) {
-            conf.username = username.to_string();
-        }
-        if let Some(password) = spec.params.get("password").and_then(|v| v.as_str()) {
-            conf.password = password.to_string();
-        }
-        // Prefer unsigned for JSON numbers here to avoid negative -> usize casts
-        if let Some(batch) = spec.params.get("batch").and_then(|v| v.as_u64()) {
-            conf.batch = Some(batch as usize);
-        }
-        if let Some(database) = spec.params.get("database").and_then(|v| v.as_str()) {
-            conf.database = database.to_string();
-        }
-        if let Some(table) = spec.params.get("table").and_then(|v| v.as_str()) {
-            conf.table = Some(table.to_string());
-        }
+        let spec = &*config_blob::source_spec("mysql", spec)?;
+        let plan = resolve_source(spec)?;
         let mut meta_tags = Tags::from_parse(&spec.tags);
         meta_tags.set(WP_SRC_VAL, "mysql");
-        let source = MysqlSource::new(spec.name.clone(), meta_tags.clone(), &conf)
+        let source = MysqlSource::new(spec.name.clone(), meta_tags.clone(), &plan.conf, plan.mode)
             .await
-            .map_err(|err| SourceReason::Other(err.to_string()))?;
+            .map_err(|err| SourceReason::Other(err.to_string()))?
+            .with_fetch_mode(plan.fetch_mode)
+            .with_eof_as_error(plan.eof_as_error)
+            .with_ingest_time(plan.ingest_time);
 
         let mut meta = SourceMeta::new(spec.name.clone(), spec.kind.clone());
         meta.tags = meta_tags;
@@ -87,6 +87,286 @@ impl wp_connector_api::SourceFactory for MySQLSourceFactory {
     }
 }
 
+/// Source 在建连之前解析出的配置
+struct SourcePlan {
+    conf: MysqlConf,
+    mode: MysqlSourceMode,
+    fetch_mode: MysqlFetchMode,
+    eof_as_error: bool,
+    ingest_time: Option<IngestTime>,
+}
+
+fn resolve_source(spec: &wp_connector_api::SourceSpec) -> SourceResult<SourcePlan> {
+    let (ssl_mode, ssl_ca) = parse_ssl(&spec.params).map_err(SourceReason::Other)?;
+    // 与 MysqlConf 字段同名的参数（含 config_blob 展开的键）直接反序列化
+    let mut conf = config_blob::typed_conf("mysql", MysqlConf::default(), &spec.params)
+        .map_err(SourceReason::Other)?;
+    conf.query = parse_query(spec)?;
+    conf.ssl_mode = ssl_mode;
+    conf.ssl_ca = ssl_ca;
+    Ok(SourcePlan {
+        conf,
+        mode: parse_source_mode(spec)?,
+        fetch_mode: parse_fetch_mode(spec)?,
+        eof_as_error: parse_eof_as_error(spec)?,
+        ingest_time: IngestTime::from_params("mysql", &spec.params)?,
+    })
+}
+
+// 与 `build` 相同的解析流程，不连接数据库
+impl SourcePlanner for MySQLSourceFactory {
+    fn plan(&self, spec: &wp_connector_api::SourceSpec) -> SourceResult<serde_json::Value> {
+        self.validate_spec(spec)?;
+        let spec = &*config_blob::source_spec("mysql", spec)?;
+        let plan = resolve_source(spec)?;
+        let fetch_mode = match plan.fetch_mode {
+            MysqlFetchMode::OneShot => json!("one_shot"),
+            MysqlFetchMode::Poll(interval) => {
+                json!({ "poll_interval_ms": interval.as_millis() as u64 })
+            }
+        };
+        let mut out = json!({
+            "mode": format!("{:?}", plan.mode).to_ascii_lowercase(),
+            "fetch_mode": fetch_mode,
+            "eof_as_error": plan.eof_as_error,
+            "ingest_time": plan.ingest_time,
+            "conf": plan.conf,
+        });
+        redact_secrets(&mut out);
+        Ok(out)
+    }
+}
+
+/// 解析 Source 输出模式：`json`（默认，整行 JSON）或 `typed`（按列类型构建记录）
+fn parse_source_mode(spec: &wp_connector_api::SourceSpec) -> SourceResult<MysqlSourceMode> {
+    match spec.params.get("mode") {
+        None => Ok(MysqlSourceMode::default()),
+        Some(v) => v.as_str().and_then(MysqlSourceMode::parse).ok_or_else(|| {
+            SourceReason::Other("mysql.mode must be one of: json, typed".into()).into()
+        }),
+    }
+}
+
+/// 解析自定义查询 `query`：须以 `SELECT` 开头、不以 `;` 结尾，且仅支持 json 模式
+fn parse_query(spec: &wp_connector_api::SourceSpec) -> SourceResult<Option<String>> {
+    let Some(raw) = spec.params.get("query") else {
+        return Ok(None);
+    };
+    let raw = raw
+        .as_str()
+        .ok_or_else(|| SourceReason::Other("mysql.query must be a string".into()))?;
+    let query = validate_query(raw).map_err(SourceReason::Other)?;
+    if parse_source_mode(spec)? != MysqlSourceMode::Json {
+        return Err(SourceReason::Other(
+            "mysql.query only supports mode = json; build the row with JSON_OBJECT(...)".into(),
+        )
+        .into());
+    }
+    Ok(Some(query))
+}
+
+/// 解析读完数据后的行为：配置 `poll_interval_ms` 时持续轮询，否则读完即 EOF
+fn parse_fetch_mode(spec: &wp_connector_api::SourceSpec) -> SourceResult<MysqlFetchMode> {
+    match spec.params.get("poll_interval_ms") {
+        None => Ok(MysqlFetchMode::OneShot),
+        Some(v) => match v.as_u64() {
+            Some(ms) if ms > 0 => Ok(MysqlFetchMode::Poll(Duration::from_millis(ms))),
+            _ => Err(SourceReason::Other(
+                "mysql.poll_interval_ms must be a positive integer".into(),
+            )
+            .into()),
+        },
+    }
+}
+
+/// 解析兼容开关 `eof_as_error`：为 true 时一次性模式读完数据直接返回 EOF 错误（默认 false）
+fn parse_eof_as_error(spec: &wp_connector_api::SourceSpec) -> SourceResult<bool> {
+    match spec.params.get("eof_as_error") {
+        None => Ok(false),
+        Some(v) => v.as_bool().ok_or_else(|| {
+            SourceReason::Other("mysql.eof_as_error must be a boolean".into()).into()
+        }),
+    }
+}
+
+/// 解析 TLS 参数 `ssl_mode` 与 `ssl_ca`；`verify_ca` / `verify_identity` 必须提供 CA 路径
+fn parse_ssl(params: &ParamMap) -> Result<(Option<MysqlSslMode>, Option<String>), String> {
+    let mode = match params.get("ssl_mode") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(v) => Some(v.as_str().and_then(MysqlSslMode::parse).ok_or(
+            "mysql.ssl_mode must be one of: disabled, preferred, required, verify_ca, verify_identity",
+        )?),
+    };
+    let ca = match params.get("ssl_ca") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(path) if !path.is_empty() => Some(path.to_string()),
+            _ => return Err("mysql.ssl_ca must be a non-empty string".into()),
+        },
+    };
+    if let Some(mode) = mode
+        && mode.verifies_server()
+        && ca.is_none()
+    {
+        return Err(format!(
+            "mysql.ssl_mode = {} requires mysql.ssl_ca",
+            mode.as_url_value().to_ascii_lowercase()
+        ));
+    }
+    Ok((mode, ca))
+}
+
+/// 解析字符串超出列长度时的处理方式：`error`（默认）、`truncate` 或 `skip`
+fn parse_oversize(spec: &SinkSpec) -> SinkResult<OversizeAction> {
+    match spec.params.get("on_oversize") {
+        None => Ok(OversizeAction::default()),
+        Some(v) => v.as_str().and_then(OversizeAction::parse).ok_or_else(|| {
+            SinkReason::sink("mysql.on_oversize must be one of: error, truncate, skip").into()
+        }),
+    }
+}
+
+/// 解析 `empty_as_null`（缺省 `false`）与 `null_literal`（缺省 `NULL`）
+fn parse_null_format(spec: &SinkSpec) -> SinkResult<NullFormat> {
+    let mut format = NullFormat::default();
+    match spec.params.get("empty_as_null") {
+        None => {}
+        Some(serde_json::Value::Bool(b)) => format.empty_as_null = *b,
+        Some(_) => return Err(SinkReason::sink("mysql.empty_as_null must be a bool").into()),
+    }
+    if let Some(v) = spec.params.get("null_literal") {
+        format.literal = v
+            .as_str()
+            .and_then(NullLiteral::parse)
+            .ok_or_else(|| SinkReason::sink("mysql.null_literal must be one of: NULL, DEFAULT"))?;
+    }
+    Ok(format)
+}
+
+/// 解析 `flush_interval_ms`；未配置时记录不经缓冲直接写出
+fn parse_flush_interval(spec: &SinkSpec) -> SinkResult<Option<Duration>> {
+    match spec.params.get("flush_interval_ms") {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(ms) if ms > 0 => Ok(Some(Duration::from_millis(ms))),
+            _ => Err(SinkReason::sink("mysql.flush_interval_ms must be a positive integer").into()),
+        },
+    }
+}
+
+/// 校验 sink 参数；错误统一由调用方标记为 `config` 类别
+fn validate_sink_params(spec: &SinkSpec) -> SinkResult<()> {
+    validate_decorators(spec)?;
+    spill::parse_spill_dir(spec)?;
+    let endpoint = spec
+        .params
+        .get("endpoint")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    if endpoint.trim().is_empty() {
+        return Err(SinkReason::sink("mysql.endpoint must not be empty").into());
+    }
+    let database = spec
+        .params
+        .get("database")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    if database.trim().is_empty() {
+        return Err(SinkReason::sink("mysql.database must not be empty").into());
+    }
+    if let Some(i) = spec.params.get("batch").and_then(|v| v.as_i64())
+        && i <= 0
+    {
+        return Err(SinkReason::sink("mysql.batch must be > 0").into());
+    }
+    parse_ssl(&spec.params).map_err(SinkReason::sink)?;
+    SchemaOptions::from_params("mysql", &spec.params)?;
+    ColumnMap::from_params("mysql", &spec.params)?;
+    ColumnFilter::from_params("mysql", &spec.params)?;
+    TableRouter::from_params("mysql", &spec.params)?;
+    parse_oversize(spec)?;
+    parse_null_format(spec)?;
+    parse_bytes_encoding(spec)?;
+    parse_flush_bytes(spec)?;
+    parse_flush_interval(spec)?;
+    Ok(())
+}
+
+/// 解析 schema 校验、字段映射、字段过滤、超长处理与 NULL 表示选项
+fn parse_sink_options(
+    spec: &SinkSpec,
+) -> SinkResult<(
+    Option<RecordValidator>,
+    ColumnMap,
+    ColumnFilter,
+    OversizeAction,
+    NullFormat,
+)> {
+    let schema = SchemaOptions::from_params("mysql", &spec.params)?.build("mysql")?;
+    let column_map = ColumnMap::from_params("mysql", &spec.params)?;
+    let column_filter = ColumnFilter::from_params("mysql", &spec.params)?;
+    Ok((
+        schema,
+        column_map,
+        column_filter,
+        parse_oversize(spec)?,
+        parse_null_format(spec)?,
+    ))
+}
+
+/// 解析 Sink 参数，得到与直接使用构造器等价的 [`MysqlSinkBuilder`]（不连接数据库）
+fn resolve_sink(spec: &SinkSpec) -> SinkResult<MysqlSinkBuilder> {
+    let (ssl_mode, ssl_ca) =
+        parse_ssl(&spec.params).map_err(|e| SinkError::from(ErrorKind::Config.sink("mysql", e)))?;
+    // 与 MysqlConf 字段同名的参数（含 config_blob 展开的键）直接反序列化
+    let mut conf = config_blob::typed_conf("mysql", MysqlConf::default(), &spec.params)
+        .map_err(|e| SinkError::from(ErrorKind::Config.sink("mysql", e)))?;
+    conf.ssl_mode = ssl_mode;
+    conf.ssl_ca = ssl_ca;
+    // Use unsigned extraction to match usize semantics
+    if let Some(i) = spec.params.get("batch_size").and_then(|v| v.as_u64()) {
+        conf.batch = Some(i as usize);
+    }
+    // columns 列表在新版配置中不在 conf 中，作为外部参数传入 sink
+    let columns: Vec<String> =
+        if let Some(arr) = spec.params.get("columns").and_then(|v| v.as_array()) {
+            let mut out = Vec::with_capacity(arr.len());
+            for item in arr {
+                if let Some(s) = item.as_str() {
+                    out.push(s.to_string());
+                } else {
+                    return Err(ErrorKind::Config
+                        .sink("mysql", "mysql.columns entries must be string")
+                        .into());
+                }
+            }
+            out
+        } else {
+            Vec::new()
+        };
+    let (schema, column_map, column_filter, oversize, null_format) =
+        ErrorKind::Config.tag_sink("mysql", parse_sink_options(spec))?;
+    let router =
+        ErrorKind::Config.tag_sink("mysql", TableRouter::from_params("mysql", &spec.params))?;
+    let flush_bytes = ErrorKind::Config.tag_sink("mysql", parse_flush_bytes(spec))?;
+    let flush_interval = ErrorKind::Config.tag_sink("mysql", parse_flush_interval(spec))?;
+    let bytes_encoding = ErrorKind::Config.tag_sink("mysql", parse_bytes_encoding(spec))?;
+    let table = conf.table.clone().unwrap_or_else(|| spec.name.clone());
+    Ok(MysqlSinkBuilder {
+        columns,
+        schema,
+        column_map,
+        column_filter,
+        oversize,
+        null_format,
+        bytes_encoding,
+        router,
+        flush_bytes,
+        flush_interval,
+        ..MysqlSinkBuilder::from_conf(conf, table)
+    })
+}
+
 pub struct MySQLSinkFactory;
 
 #[async_trait]
@@ -95,83 +375,37 @@ impl SinkFactory for MySQLSinkFactory {
         "mysql"
     }
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
-        let endpoint = spec
-            .params
-            .get("endpoint")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        if endpoint.trim().is_empty() {
-            return Err(SinkReason::sink("mysql.endpoint must not be empty").into());
-        }
-        let database = spec
-            .params
-            .get("database")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        if database.trim().is_empty() {
-            return Err(SinkReason::sink("mysql.database must not be empty").into());
-        }
-        if let Some(i) = spec.params.get("batch").and_then(|v| v.as_i64())
-            && i <= 0
-        {
-            return Err(SinkReason::sink("mysql.batch must be > 0").into());
-        }
-        Ok(())
+        let spec = &*ErrorKind::Config.tag_sink("mysql", config_blob::sink_spec("mysql", spec))?;
+        ErrorKind::Config.tag_sink("mysql", validate_sink_params(spec))
     }
     async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
-        // Build Mysql conf from flat params
-        let mut conf = MysqlConf::default();
-        if let Some(s) = spec.params.get("endpoint").and_then(|v| v.as_str()) {
-            conf.endpoint = s.to_string();
-        }
-        if let Some(s) = spec.params.get("username").and_then(|v| v.as_str()) {
-            conf.username = s.to_string();
-        }
-        if let Some(s) = spec.params.get("password").and_then(|v| v.as_str()) {
-            conf.password = s.to_string();
-        }
-        if let Some(s) = spec.params.get("database").and_then(|v| v.as_str()) {
-            conf.database = s.to_string();
-        }
-        if let Some(s) = spec.params.get("table").and_then(|v| v.as_str()) {
-            conf.table = Some(s.to_string());
-        }
-        // Use unsigned extraction to match usize semantics
-        if let Some(i) = spec.params.get("batch_size").and_then(|v| v.as_u64()) {
-            conf.batch = Some(i as usize);
-        }
-        // columns 列表在新版配置中不在 conf 中，作为外部参数传入 sink
-        let columns: Vec<String> =
-            if let Some(arr) = spec.params.get("columns").and_then(|v| v.as_array()) {
-                let mut out = Vec::with_capacity(arr.len());
-                for item in arr {
-                    if let Some(s) = item.as_str() {
-                        out.push(s.to_string());
-                    } else {
-                        return Err(SinkReason::sink("mysql.columns entries must be string").into());
-                    }
-                }
-                out
-            } else {
-                Vec::new()
-            };
-        let url = conf.get_database_url();
-        let mut opt = ConnectOptions::new(url.clone());
-        opt.max_connections(50)
-            .min_connections(1)
-            .connect_timeout(Duration::from_secs(8))
-            .acquire_timeout(Duration::from_secs(8))
-            .idle_timeout(Duration::from_secs(8))
-            .max_lifetime(Duration::from_secs(8))
-            .sqlx_logging(false)
-            .map_sqlx_mysql_opts(|opt| opt.statement_cache_capacity(0))
-            .sqlx_logging_level(log::LevelFilter::Info);
-        let db = Database::connect(opt).await.map_err(|err| {
-            SinkError::from(SinkReason::sink(format!("connect mysql fail: {err}")))
-        })?;
-        let table = conf.table.clone().unwrap_or_else(|| spec.name.clone());
-        let sink = MysqlSink::new(db, table, columns);
-        Ok(SinkHandle::new(Box::new(sink)))
+        let spec = &*ErrorKind::Config.tag_sink("mysql", config_blob::sink_spec("mysql", spec))?;
+        let sink = resolve_sink(spec)?.build().await?;
+        let handle = spill::wrap_from_spec(spec, SinkHandle::new(Box::new(sink)))?;
+        decorate_sink(spec, handle)
+    }
+}
+
+impl SinkPlanner for MySQLSinkFactory {
+    fn plan(&self, spec: &SinkSpec) -> SinkResult<serde_json::Value> {
+        self.validate_spec(spec)?;
+        let spec = &*ErrorKind::Config.tag_sink("mysql", config_blob::sink_spec("mysql", spec))?;
+        let plan = resolve_sink(spec)?;
+        let mut out = json!({
+            "table": plan.table,
+            "columns": plan.columns,
+            "on_oversize": format!("{:?}", plan.oversize).to_ascii_lowercase(),
+            "empty_as_null": plan.null_format.empty_as_null,
+            "null_literal": plan.null_format.literal.as_sql(),
+            "bytes_encoding": plan.bytes_encoding.map(BytesEncoding::as_str),
+            "tables": plan.router.as_ref().map(TableRouter::tables),
+            "schema": plan.schema.is_some(),
+            "flush_bytes": plan.flush_bytes,
+            "flush_interval_ms": plan.flush_interval.map(|d| d.as_millis() as u64),
+            "conf": plan.conf,
+        });
+        redact_secrets(&mut out);
+        Ok(out)
     }
 }
 
@@ -181,10 +415,25 @@ impl SourceDefProvider for MySQLSourceFactory {
             id: "mysql_src".into(),
             kind: self.kind().into(),
             scope: ConnectorScope::Source,
-            allow_override: vec!["endpoint", "database", "table", "username", "batch"]
-                .into_iter()
-                .map(str::to_string)
-                .collect(),
+            allow_override: vec![
+                "endpoint",
+                "database",
+                "table",
+                "username",
+                "batch",
+                "mode",
+                "poll_interval_ms",
+                "eof_as_error",
+                "query",
+                "ssl_mode",
+                "ssl_ca",
+                INGEST_TIME_FIELD_PARAM,
+                INGEST_TIME_UNIT_PARAM,
+                CONFIG_BLOB_PARAM,
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
             default_params: mysql_source_defaults(),
             origin: Some("wp-connectors:mysql_source".into()),
         }
@@ -198,9 +447,29 @@ impl SinkDefProvider for MySQLSinkFactory {
             kind: self.kind().into(),
             scope: ConnectorScope::Sink,
             allow_override: vec![
-                "endpoint", "database", "table", "username", "batch", "columns",
+                "endpoint",
+                "database",
+                "table",
+                "username",
+                "batch",
+                "columns",
+                "on_oversize",
+                "empty_as_null",
+                "null_literal",
+                BYTES_ENCODING_PARAM,
+                "ssl_mode",
+                "ssl_ca",
+                FLUSH_BYTES_PARAM,
+                "flush_interval_ms",
+                COLUMN_MAP_PARAM,
+                CONFIG_BLOB_PARAM,
+                SPILL_DIR_PARAM,
             ]
             .into_iter()
+            .chain(COLUMN_FILTER_PARAMS)
+            .chain(TABLE_ROUTE_PARAMS)
+            .chain(SCHEMA_PARAMS)
+            .chain(DECORATOR_PARAMS)
             .map(str::to_string)
             .collect(),
             default_params: mysql_sink_defaults(),
@@ -216,6 +485,7 @@ fn mysql_source_defaults() -> ParamMap {
     params.insert("table".into(), json!("wp_events"));
     params.insert("username".into(), json!("root"));
     params.insert("batch".into(), json!(1024));
+    params.insert("mode".into(), json!("json"));
     params
 }
 
@@ -229,3 +499,259 @@ fn mysql_sink_defaults() -> ParamMap {
     params.insert("batch_size".into(), json!(1024));
     params
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_spec(poll_interval_ms: serde_json::Value) -> wp_connector_api::SourceSpec {
+        let mut params = mysql_source_defaults();
+        params.insert("poll_interval_ms".into(), poll_interval_ms);
+        wp_connector_api::SourceSpec {
+            name: "mysql_src".into(),
+            kind: "mysql".into(),
+            connector_id: String::new(),
+            params,
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn poll_interval_selects_poll_mode() {
+        assert_eq!(
+            parse_fetch_mode(&source_spec(json!(500))).unwrap(),
+            MysqlFetchMode::Poll(Duration::from_millis(500))
+        );
+        let err = MySQLSourceFactory
+            .validate_spec(&source_spec(json!(0)))
+            .expect_err("zero interval");
+        assert!(err.to_string().contains("mysql.poll_interval_ms"));
+    }
+
+    #[test]
+    fn eof_as_error_defaults_to_false() {
+        let mut spec = source_spec(json!(500));
+        assert!(!parse_eof_as_error(&spec).unwrap());
+        spec.params.insert("eof_as_error".into(), json!(true));
+        assert!(parse_eof_as_error(&spec).unwrap());
+        spec.params.insert("eof_as_error".into(), json!("yes"));
+        let err = MySQLSourceFactory
+            .validate_spec(&spec)
+            .expect_err("non-bool flag");
+        assert!(err.to_string().contains("mysql.eof_as_error"));
+    }
+
+    #[test]
+    fn config_blob_merges_into_mysql_conf_with_flat_overrides() {
+        let mut spec = source_spec(json!(500));
+        spec.params = ParamMap::new();
+        spec.params.insert(
+            CONFIG_BLOB_PARAM.into(),
+            json!(
+                r#"{"endpoint": "db:3306", "username": "wp", "database": "from_blob",
+                    "table": "events", "batch": 200}"#
+            ),
+        );
+        spec.params.insert("database".into(), json!("from_flat"));
+        MySQLSourceFactory
+            .validate_spec(&spec)
+            .expect("blob satisfies required params");
+
+        let spec = config_blob::source_spec("mysql", &spec).unwrap();
+        let conf = config_blob::typed_conf("mysql", MysqlConf::default(), &spec.params).unwrap();
+        assert_eq!(conf.endpoint, "db:3306");
+        assert_eq!(conf.username, "wp");
+        assert_eq!(conf.password, MysqlConf::default().password);
+        assert_eq!(conf.database, "from_flat");
+        assert_eq!(conf.table.as_deref(), Some("events"));
+        assert_eq!(conf.batch, Some(200));
+    }
+
+    #[test]
+    fn custom_query_is_validated() {
+        let mut spec = source_spec(json!(500));
+        let query = "SELECT JSON_OBJECT('id', o.id, 'user', u.name) FROM orders o JOIN users u ON o.uid = u.id ORDER BY o.id";
+        spec.params
+            .insert("query".into(), json!(format!("  {query}  ")));
+        assert_eq!(parse_query(&spec).unwrap().as_deref(), Some(query));
+
+        for (bad, expect) in [
+            (json!("DELETE FROM orders"), "must start with SELECT"),
+            (json!("SELECTED"), "must start with SELECT"),
+            (json!("SELECT 1;"), "must not end with ';'"),
+            (json!(1), "must be a string"),
+        ] {
+            let mut spec = spec.clone();
+            spec.params.insert("query".into(), bad);
+            let err = MySQLSourceFactory.validate_spec(&spec).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+
+        spec.params.insert("mode".into(), json!("typed"));
+        let err = MySQLSourceFactory
+            .validate_spec(&spec)
+            .expect_err("typed mode");
+        assert!(err.to_string().contains("only supports mode = json"));
+    }
+
+    #[test]
+    fn ssl_params_thread_into_conf() {
+        let mut spec = source_spec(json!(500));
+        spec.params.insert("ssl_mode".into(), json!("verify_ca"));
+        let err = MySQLSourceFactory
+            .validate_spec(&spec)
+            .expect_err("verify_ca without ca");
+        assert!(err.to_string().contains("requires mysql.ssl_ca"), "{err}");
+
+        spec.params
+            .insert("ssl_ca".into(), json!("/etc/mysql/ca.pem"));
+        MySQLSourceFactory.validate_spec(&spec).unwrap();
+        let conf = resolve_source(&spec).unwrap().conf;
+        assert_eq!(conf.ssl_mode, Some(MysqlSslMode::VerifyCa));
+        assert_eq!(conf.ssl_ca.as_deref(), Some("/etc/mysql/ca.pem"));
+
+        spec.params.insert("ssl_mode".into(), json!("strict"));
+        let err = MySQLSourceFactory
+            .validate_spec(&spec)
+            .expect_err("unknown mode");
+        assert!(
+            err.to_string().contains("mysql.ssl_mode must be one of"),
+            "{err}"
+        );
+    }
+
+    fn sink_spec(params: ParamMap) -> SinkSpec {
+        SinkSpec {
+            name: "mysql_sink".into(),
+            kind: "mysql".into(),
+            connector_id: String::new(),
+            group: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn sink_param_errors_carry_config_kind() {
+        for (key, bad) in [
+            ("endpoint", json!("")),
+            ("batch", json!(0)),
+            ("on_oversize", json!("clip")),
+            ("empty_as_null", json!("yes")),
+            ("null_literal", json!(" ")),
+            ("null_literal", json!("0); DROP TABLE x; --")),
+            ("column_map", json!({"host": 1})),
+            ("exclude_columns", json!("raw_password")),
+            ("flush_interval_ms", json!(0)),
+            ("ssl_mode", json!("verify_ca")),
+        ] {
+            let mut params = mysql_sink_defaults();
+            params.insert(key.into(), bad);
+            let err = MySQLSinkFactory
+                .validate_spec(&sink_spec(params))
+                .expect_err(key);
+            assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Config), "{err}");
+        }
+    }
+
+    #[test]
+    fn null_literal_accepts_keywords_only() {
+        let mut params = mysql_sink_defaults();
+        params.insert("null_literal".into(), json!(" default "));
+        let format = parse_null_format(&sink_spec(params)).expect("keyword");
+        assert_eq!(format.literal, NullLiteral::Default);
+
+        for bad in ["0); DROP TABLE x; --", "''", "now()"] {
+            let mut params = mysql_sink_defaults();
+            params.insert("null_literal".into(), json!(bad));
+            let err = parse_null_format(&sink_spec(params)).expect_err(bad);
+            assert!(
+                err.to_string()
+                    .contains("mysql.null_literal must be one of")
+            );
+        }
+    }
+
+    #[test]
+    fn sink_builder_matches_spec_path() {
+        let mut params = mysql_sink_defaults();
+        params.insert("endpoint".into(), json!("db1:3306"));
+        params.insert("password".into(), json!("secret"));
+        params.insert("flush_bytes".into(), json!(65536));
+        params.insert("flush_interval_ms".into(), json!(200));
+        params.insert("on_oversize".into(), json!("truncate"));
+        params.insert("empty_as_null".into(), json!(true));
+        params.insert("bytes_encoding".into(), json!("hex"));
+        params.insert("column_map".into(), json!({"srcIp": "src_ip"}));
+        params.insert("exclude_columns".into(), json!(["raw_password"]));
+        params.insert("ssl_mode".into(), json!("verify_identity"));
+        params.insert("ssl_ca".into(), json!("/etc/mysql/ca.pem"));
+        let from_spec = resolve_sink(&sink_spec(params)).expect("valid sink spec");
+
+        let builder = MysqlSinkBuilder::new("db1:3306", "wp_data", "wp_events")
+            .credentials("root", "secret")
+            .ssl(
+                MysqlSslMode::VerifyIdentity,
+                Some("/etc/mysql/ca.pem".into()),
+            )
+            .columns(["wp_event_id", "payload"])
+            .batch(1024)
+            .flush_bytes(65536)
+            .flush_interval(Duration::from_millis(200))
+            .oversize(OversizeAction::Truncate)
+            .null_format(NullFormat {
+                empty_as_null: true,
+                ..NullFormat::default()
+            })
+            .bytes_encoding(BytesEncoding::Hex)
+            .column_map([("srcIp", "src_ip")].into_iter().collect())
+            .column_filter(ColumnFilter::default().excluding(["raw_password"]));
+        assert_eq!(builder.conf, from_spec.conf);
+        assert_eq!(builder.table, from_spec.table);
+        assert_eq!(builder.columns, from_spec.columns);
+        assert_eq!(builder.column_map, from_spec.column_map);
+        assert_eq!(builder.column_filter, from_spec.column_filter);
+        assert_eq!(builder.oversize, from_spec.oversize);
+        assert_eq!(builder.null_format, from_spec.null_format);
+        assert_eq!(builder.bytes_encoding, from_spec.bytes_encoding);
+        assert_eq!(builder.router, from_spec.router);
+        assert_eq!(builder.flush_bytes, from_spec.flush_bytes);
+        assert_eq!(builder.flush_interval, from_spec.flush_interval);
+        assert!(builder.schema.is_none() && from_spec.schema.is_none());
+    }
+
+    #[tokio::test]
+    async fn sink_build_against_closed_port_is_connect_error() {
+        let mut params = mysql_sink_defaults();
+        params.insert("endpoint".into(), json!("127.0.0.1:1"));
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+        let err = match MySQLSinkFactory.build(&sink_spec(params), &ctx).await {
+            Ok(_) => panic!("closed port must not connect"),
+            Err(err) => err,
+        };
+        assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Connect), "{err}");
+    }
+
+    #[test]
+    fn plan_returns_resolved_conf_without_connecting() {
+        let mut params = mysql_sink_defaults();
+        params.insert("endpoint".into(), json!("127.0.0.1:1"));
+        params.insert("password".into(), json!("dayu"));
+        params.insert("columns".into(), json!(["id", "host"]));
+        params.remove("table");
+        let plan = MySQLSinkFactory
+            .plan(&sink_spec(params))
+            .expect("sink plan");
+        assert_eq!(plan["table"], "mysql_sink");
+        assert_eq!(plan["columns"], json!(["id", "host"]));
+        assert_eq!(plan["conf"]["endpoint"], "127.0.0.1:1");
+        assert_eq!(plan["conf"]["password"], "***");
+
+        let plan = MySQLSourceFactory
+            .plan(&source_spec(json!(1000)))
+            .expect("source plan");
+        assert_eq!(plan["fetch_mode"], json!({"poll_interval_ms": 1000}));
+        assert_eq!(plan["mode"], "json");
+        assert_eq!(plan["conf"]["password"], "***");
+    }
+}
@@ -4,14 +4,18 @@
 //! - source：MySqlSource & 错误映射/建 Topic
 //! - sink：MySqlSink（AsyncRawDataSink/AsyncRecordSink）
 //! - factory：Source/Sink 工厂与注册函数
+//! - builder：不经 `SinkSpec` 直接构造 MysqlSink
 
 mod adapter;
+mod builder;
 mod config;
 mod factory;
 mod sink;
 mod source;
 
 // 统一导出：便于上游 `wp_connector_mysql::Source/Sink/Factory` 使用
+pub use builder::MysqlSinkBuilder;
+pub use config::MysqlSslMode;
 pub use factory::{MySQLSinkFactory, MySQLSourceFactory};
-pub use sink::MysqlSink;
-pub use source::MysqlSource;
+pub use sink::{MysqlSink, NullFormat, NullLiteral, OversizeAction, TableColumns};
+pub use source::{EOF_TAG, MysqlSource, MysqlSourceMode};
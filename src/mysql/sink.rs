@@ -1,73 +1,644 @@
 use async_trait::async_trait;
-use sea_orm::{ConnectionTrait, DatabaseConnection};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, Statement, TransactionTrait};
+
 use std::collections::HashMap;
 use std::sync::Arc;
-use wp_connector_api::{
-    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
-};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::{Mutex, oneshot};
+use tokio::task::JoinHandle;
+use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkResult};
 use wp_log::error_data;
-use wp_model_core::model::{DataRecord, DataType};
+use wp_model_core::model::{DataRecord, DataType, FieldStorage};
+
+use crate::utils::bytes_encoding::{BytesEncoding, value_text};
+use crate::utils::column_filter::ColumnFilter;
+use crate::utils::column_map::ColumnMap;
+use crate::utils::db_metrics::{record_flush, record_invalid, record_oversize};
+use crate::utils::error_kind::ErrorKind;
+use crate::utils::flush_limit::FlushLimits;
+use crate::utils::retry::{RetryPolicy, reconnect_with_backoff};
+use crate::utils::schema::RecordValidator;
+use crate::utils::shutdown::{DeadlineCtrl, DrainReport};
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
+use crate::utils::table_route::TableRouter;
+
+/// 字符串超出目标列长度时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OversizeAction {
+    /// 原样写入，由数据库报错（默认）
+    #[default]
+    Error,
+    /// 截断到列长度后写入
+    Truncate,
+    /// 跳过整条记录并计数
+    Skip,
+}
+
+impl OversizeAction {
+    /// 解析 `on_oversize` 参数，仅接受 `error` / `truncate` / `skip`
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(Self::Error),
+            "truncate" => Some(Self::Truncate),
+            "skip" => Some(Self::Skip),
+            _ => None,
+        }
+    }
+}
+
+/// VALUES 中表示缺失值的 SQL 关键字；只允许固定关键字，避免拼接任意 SQL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullLiteral {
+    /// 写入 `NULL`（默认）
+    #[default]
+    Null,
+    /// 写入 `DEFAULT`，使用列的默认值
+    Default,
+}
+
+impl NullLiteral {
+    /// 解析 `null_literal` 参数，仅接受 `NULL` / `DEFAULT`（不区分大小写）
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_uppercase().as_str() {
+            "NULL" => Some(Self::Null),
+            "DEFAULT" => Some(Self::Default),
+            _ => None,
+        }
+    }
+
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Self::Null => "NULL",
+            Self::Default => "DEFAULT",
+        }
+    }
+}
+
+/// VALUES 中 NULL 的表示方式
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NullFormat {
+    /// 空字符串是否按 NULL 写入；为 `false` 时写入 `''`
+    pub empty_as_null: bool,
+    /// 表示 NULL 的 SQL 关键字，原样写入、不加引号
+    pub literal: NullLiteral,
+}
+
+/// 以反引号引用 MySQL 标识符，内部的反引号写作两个反引号
+pub(crate) fn quote_ident(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// 引用表名；`db.table` 形式按 `.` 分别引用库名与表名
+fn quote_table(table: &str) -> String {
+    table
+        .split('.')
+        .map(quote_ident)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// 建连/开启事务失败的类别：MySQL 拒绝凭据（`Access denied`）归为 `Auth`，其余为 `Connect`
+pub(crate) fn connect_error_kind(err: &DbErr) -> ErrorKind {
+    if err.to_string().contains("Access denied") {
+        ErrorKind::Auth
+    } else {
+        ErrorKind::Connect
+    }
+}
+
+/// 读取表中字符类型列的最大长度（字符数），来自 `INFORMATION_SCHEMA.COLUMNS`
+pub async fn load_column_lengths(
+    db: &DatabaseConnection,
+    database: &str,
+    table: &str,
+) -> Result<HashMap<String, usize>, DbErr> {
+    let sql = "SELECT COLUMN_NAME, CHARACTER_MAXIMUM_LENGTH \
+               FROM INFORMATION_SCHEMA.COLUMNS \
+               WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? \
+               AND CHARACTER_MAXIMUM_LENGTH IS NOT NULL";
+    let stmt = Statement::from_sql_and_values(
+        db.get_database_backend(),
+        sql,
+        vec![database.into(), table.into()],
+    );
+    let mut lengths = HashMap::new();
+    for row in db.query_all(stmt).await? {
+        let name: String = row.try_get_by_index(0)?;
+        let max: u64 = row.try_get_by_index(1)?;
+        lengths.insert(name, usize::try_from(max).unwrap_or(usize::MAX));
+    }
+    Ok(lengths)
+}
+
+/// 按 `ORDINAL_POSITION` 读取表的全部列名，来自 `INFORMATION_SCHEMA.COLUMNS`
+pub async fn load_table_columns(
+    db: &DatabaseConnection,
+    database: &str,
+    table: &str,
+) -> Result<Vec<String>, DbErr> {
+    let sql = "SELECT COLUMN_NAME FROM INFORMATION_SCHEMA.COLUMNS \
+               WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? \
+               ORDER BY ORDINAL_POSITION";
+    let stmt = Statement::from_sql_and_values(
+        db.get_database_backend(),
+        sql,
+        vec![database.into(), table.into()],
+    );
+    db.query_all(stmt)
+        .await?
+        .into_iter()
+        .map(|row| row.try_get_by_index(0))
+        .collect()
+}
+
+/// 多表路由中一张目标表的列信息
+#[derive(Debug, Clone, Default)]
+pub struct TableColumns {
+    /// 写入的列，顺序即 INSERT 中的列顺序
+    pub columns: Vec<String>,
+    /// 字符类型列的最大长度（字符数），`oversize` 为 `Error` 时为空
+    pub column_lengths: HashMap<String, usize>,
+}
+
+/// 一次写入的目标表及其列信息
+#[derive(Clone, Copy)]
+struct Target<'a> {
+    table: &'a str,
+    columns: &'a [String],
+    lengths: &'a HashMap<String, usize>,
+}
 
 pub struct MysqlSink {
-    pub db: DatabaseConnection,
+    /// 与定时写出任务共享的连接
+    pub db: Arc<DatabaseConnection>,
     pub table: String,
     pub cloumn_name: Vec<String>,
+    /// 单条 INSERT 语句包含的最大行数，`None` 表示整批合并为一条语句
+    pub batch: Option<usize>,
+    /// 单条 INSERT 语句 VALUES 部分的最大字节数，与 `batch` 先到先切分
+    pub flush_bytes: Option<usize>,
+    /// 记录级 Schema 校验，`None` 表示不校验
+    pub schema: Option<RecordValidator>,
+    /// 字段名到列名的映射，匹配 `cloumn_name` 前应用
+    pub column_map: ColumnMap,
+    /// 字段白名单/黑名单，被排除的字段不写入 SQL
+    pub column_filter: ColumnFilter,
+    /// 字符串超出列长度时的处理方式
+    pub oversize: OversizeAction,
+    /// 字符类型列的最大长度（字符数），`oversize` 为 `Error` 时为空
+    pub column_lengths: HashMap<String, usize>,
+    /// 按记录字段选择目标表，`None` 表示全部写入 `table`
+    pub router: Option<TableRouter>,
+    /// 路由名单中各表的列信息（不含默认表）
+    pub routes: HashMap<String, TableColumns>,
+    /// 缺失字段与空字符串的 NULL 表示
+    pub null_format: NullFormat,
+    /// 字节类字段的文本编码，`None` 时按原样输出
+    pub bytes_encoding: Option<BytesEncoding>,
+    /// 定时写出间隔；设置后记录先进入缓冲，累计到 `batch` 行或间隔到达时写出
+    pub flush_interval: Option<Duration>,
+    /// 已向调用方确认、待写出的记录，与定时写出任务共享；最多 `MAX_PENDING_ROWS` 行
+    pending: Arc<Mutex<Vec<Arc<DataRecord>>>>,
+    /// 上一次写出缓冲是否失败；失败后下一次前台写入立即重试，并返回其结果
+    flush_failed: Arc<AtomicBool>,
+    stop_tx: Option<oneshot::Sender<()>>,
+    flush_handle: Option<JoinHandle<()>>,
+    counters: Arc<SinkCounters>,
 }
 
+/// 定时写出模式下缓冲的行数上限；`batch` 更大时按该上限提前写出
+pub(crate) const MAX_PENDING_ROWS: usize = 10_000;
+
 impl MysqlSink {
     pub fn new(db: DatabaseConnection, table: String, cloumn_name: Vec<String>) -> Self {
         Self {
-            db,
+            db: Arc::new(db),
             table,
             cloumn_name,
+            batch: None,
+            flush_bytes: None,
+            schema: None,
+            column_map: ColumnMap::default(),
+            column_filter: ColumnFilter::default(),
+            oversize: OversizeAction::default(),
+            column_lengths: HashMap::new(),
+            router: None,
+            routes: HashMap::new(),
+            null_format: NullFormat::default(),
+            bytes_encoding: None,
+            flush_interval: None,
+            pending: Arc::default(),
+            flush_failed: Arc::default(),
+            stop_tx: None,
+            flush_handle: None,
+            counters: Arc::default(),
         }
     }
 
-    fn base_insert_prefix(&self) -> String {
+    /// 设置单条 INSERT 语句的最大行数
+    pub fn with_batch(mut self, batch: Option<usize>) -> Self {
+        self.batch = batch.filter(|n| *n > 0);
+        self
+    }
+
+    /// 设置单条 INSERT 语句 VALUES 部分的最大字节数
+    pub fn with_flush_bytes(mut self, flush_bytes: Option<usize>) -> Self {
+        self.flush_bytes = flush_bytes.filter(|n| *n > 0);
+        self
+    }
+
+    /// 设置写入前的记录校验器
+    pub fn with_schema(mut self, schema: Option<RecordValidator>) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    /// 设置字段名到列名的映射
+    pub fn with_column_map(mut self, column_map: ColumnMap) -> Self {
+        self.column_map = column_map;
+        self
+    }
+
+    /// 设置字段白名单/黑名单
+    pub fn with_column_filter(mut self, column_filter: ColumnFilter) -> Self {
+        self.column_filter = column_filter;
+        self
+    }
+
+    /// 设置定时写出间隔，`None` 或零表示每批记录立即写出
+    pub fn with_flush_interval(mut self, interval: Option<Duration>) -> Self {
+        self.flush_interval = interval.filter(|d| !d.is_zero());
+        self
+    }
+
+    /// 设置 NULL 的表示方式
+    pub fn with_null_format(mut self, null_format: NullFormat) -> Self {
+        self.null_format = null_format;
+        self
+    }
+
+    /// 设置字节类字段的文本编码
+    pub fn with_bytes_encoding(mut self, bytes_encoding: Option<BytesEncoding>) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
+    /// 设置多表路由及名单中各表的列信息
+    pub fn with_table_routes(
+        mut self,
+        router: TableRouter,
+        routes: HashMap<String, TableColumns>,
+    ) -> Self {
+        self.router = Some(router);
+        self.routes = routes;
+        self
+    }
+
+    /// 目标表的列信息；不在路由名单中的表即默认表
+    fn target<'a>(&'a self, table: &'a str) -> Target<'a> {
+        match self.routes.get(table) {
+            Some(route) if table != self.table => Target {
+                table,
+                columns: &route.columns,
+                lengths: &route.column_lengths,
+            },
+            _ => Target {
+                table: &self.table,
+                columns: &self.cloumn_name,
+                lengths: &self.column_lengths,
+            },
+        }
+    }
+
+    /// 返回记录中允许写入的字段及其目标列名
+    fn writable_fields<'a>(
+        &'a self,
+        record: &'a DataRecord,
+    ) -> impl Iterator<Item = (&'a str, &'a FieldStorage)> {
+        record
+            .items
+            .iter()
+            .filter(|f| *f.get_meta() != DataType::Ignore)
+            .map(|f| (self.column_map.resolve(f.get_name()), f))
+            .filter(|(column, f)| self.column_filter.allows(f.get_name(), column))
+    }
+
+    /// 设置超长字段的处理方式及各列长度上限
+    pub fn with_oversize(
+        mut self,
+        oversize: OversizeAction,
+        column_lengths: HashMap<String, usize>,
+    ) -> Self {
+        self.oversize = oversize;
+        self.column_lengths = column_lengths;
+        self
+    }
+
+    /// 返回记录中第一个超出列长度的列名
+    fn oversized_column<'a>(&'a self, target: Target<'a>, record: &DataRecord) -> Option<&'a str> {
+        self.writable_fields(record).find_map(|(column, f)| {
+            let (column, limit) = target.lengths.get_key_value(column)?;
+            (f.get_value().to_string().chars().count() > *limit).then_some(column.as_str())
+        })
+    }
+
+    /// `skip` 模式下剔除含超长字段的记录，返回 `(保留记录, 跳过数)`
+    fn drop_oversized(
+        &self,
+        target: Target<'_>,
+        records: Vec<Arc<DataRecord>>,
+    ) -> (Vec<Arc<DataRecord>>, usize) {
+        let total = records.len();
+        let kept: Vec<_> = records
+            .into_iter()
+            .filter(|record| match self.oversized_column(target, record) {
+                Some(column) => {
+                    wp_log::warn_data!("record skipped: value exceeds column '{}' length", column);
+                    false
+                }
+                None => true,
+            })
+            .collect();
+        let skipped = total - kept.len();
+        (kept, skipped)
+    }
+
+    /// `truncate` 模式下按列长度截断取值
+    fn fit_column(&self, target: Target<'_>, column: &str, value: String) -> String {
+        match (self.oversize, target.lengths.get(column)) {
+            (OversizeAction::Truncate, Some(limit)) if value.chars().count() > *limit => {
+                value.chars().take(*limit).collect()
+            }
+            _ => value,
+        }
+    }
+
+    /// 将一批记录拆分为若干条多 VALUES 的 INSERT 语句，行数达到 `batch` 或字节数达到
+    /// `flush_bytes` 时开始下一条语句
+    fn build_insert_sqls(&self, target: Target<'_>, records: &[Arc<DataRecord>]) -> Vec<String> {
+        let values = records
+            .iter()
+            .map(|record| self.format_values_tuple(target, record.as_ref()))
+            .collect::<Vec<_>>();
+        FlushLimits::new(self.batch, self.flush_bytes)
+            .split(values, |tuple| tuple.len() + 1)
+            .into_iter()
+            .map(|chunk| {
+                let mut sql = self.base_insert_prefix(target);
+                sql.push_str(&chunk.join(","));
+                sql
+            })
+            .collect()
+    }
+
+    /// 在同一事务中执行整批语句：任一语句失败则回滚，保证批次全有或全无
+    async fn execute_in_transaction(&self, sqls: &[String]) -> SinkResult<()> {
+        let txn = self.db.begin().await.map_err(|e| {
+            SinkError::from(
+                connect_error_kind(&e)
+                    .sink("mysql", format!("mysql begin transaction fail: {}", e)),
+            )
+        })?;
+        for sql in sqls {
+            if let Err(e) = txn.execute_unprepared(sql.as_str()).await {
+                if let Err(rollback_err) = txn.rollback().await {
+                    error_data!("mysql rollback fail: {}", rollback_err);
+                }
+                return Err(SinkError::from(
+                    ErrorKind::Backend
+                        .sink("mysql", format!("mysql exec fail: {}, sql: {}", e, sql)),
+                ));
+            }
+        }
+        txn.commit().await.map_err(|e| {
+            SinkError::from(ErrorKind::Backend.sink("mysql", format!("mysql commit fail: {}", e)))
+        })
+    }
+
+    fn base_insert_prefix(&self, target: Target<'_>) -> String {
         // 使用 INSERT IGNORE：若数据库已写入但客户端因断连未收到响应，重试时避免主键/唯一键冲突
         format!(
             "INSERT IGNORE INTO {} ({}) VALUES ",
-            self.table,
-            self.cloumn_name
+            quote_table(target.table),
+            target
+                .columns
                 .iter()
-                .map(|s| format!("`{}`", s))
+                .map(|s| quote_ident(s))
                 .collect::<Vec<_>>()
                 .join(", ")
         )
     }
 
-    fn format_values_tuple(&self, record: &DataRecord) -> String {
-        let field_map: HashMap<&str, String> = record
-            .items
-            .iter()
-            .filter(|f| *f.get_meta() != DataType::Ignore)
-            .map(|f| (f.get_name(), f.get_value().to_string()))
+    fn format_values_tuple(&self, target: Target<'_>, record: &DataRecord) -> String {
+        let field_map: HashMap<&str, String> = self
+            .writable_fields(record)
+            .map(|(column, f)| (column, value_text(self.bytes_encoding, f.get_value())))
             .collect();
-        let values: Vec<String> = self
-            .cloumn_name
+        let values: Vec<String> = target
+            .columns
             .iter()
             .map(|col_name| match field_map.get(col_name.as_str()) {
-                Some(field) => format!("'{}'", field.replace("'", "''")),
+                Some(field) if field.is_empty() && self.null_format.empty_as_null => {
+                    self.null_format.literal.as_sql().to_string()
+                }
+                Some(field) => {
+                    let field = self.fit_column(target, col_name, field.clone());
+                    format!("'{}'", field.replace("'", "''"))
+                }
                 None => {
                     error_data!("Warning: Missing field for column '{}'", col_name);
-                    "NULL".to_string()
+                    self.null_format.literal.as_sql().to_string()
                 }
             })
             .collect();
         format!("({})", values.join(", "))
     }
+
+    /// 路由、剔除超长记录后在同一事务内写出
+    async fn write_records(&self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        let groups = match &self.router {
+            Some(router) => {
+                let routed = router.route(&self.table, data);
+                record_invalid("mysql", &self.table, routed.rejected);
+                routed.groups
+            }
+            None => vec![(self.table.clone(), data)],
+        };
+        // 逐表剔除超长记录并生成 INSERT；每条 INSERT 携带多个 VALUES
+        let mut sqls = Vec::new();
+        let mut flushed = Vec::with_capacity(groups.len());
+        for (table, records) in groups {
+            let target = self.target(&table);
+            let records = if self.oversize == OversizeAction::Skip {
+                let (kept, skipped) = self.drop_oversized(target, records);
+                record_oversize("mysql", target.table, skipped);
+                kept
+            } else {
+                records
+            };
+            if records.is_empty() {
+                continue;
+            }
+            let table_sqls = self.build_insert_sqls(target, &records);
+            let bytes = table_sqls.iter().map(String::len).sum::<usize>();
+            sqls.extend(table_sqls);
+            flushed.push((table, records.len(), bytes));
+        }
+        if sqls.is_empty() {
+            return Ok(());
+        }
+        // 所有表的语句在同一个事务内提交
+        let result = self.execute_in_transaction(&sqls).await;
+        for (table, rows, bytes) in flushed {
+            record_flush("mysql", &table, rows, &result);
+            self.counters.finish(rows, bytes, &result);
+        }
+        result
+    }
+
+    /// 写出缓冲中的全部记录；持锁写出，保证与前台写出按顺序提交
+    async fn flush_pending(&self) -> SinkResult<()> {
+        let mut pending = self.pending.lock().await;
+        self.write_pending(&mut pending).await
+    }
+
+    /// 写出成功后才清空缓冲；失败时记录留在缓冲中，由下一次写出重试
+    ///
+    /// 缓冲中只有已向调用方返回 `Ok` 的记录，重试它们不会与调用方的重试重复
+    async fn write_pending(&self, pending: &mut Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let result = self.write_records(pending.clone()).await;
+        self.flush_failed.store(result.is_err(), Ordering::Relaxed);
+        if result.is_ok() {
+            pending.clear();
+        }
+        result
+    }
+
+    /// 与本 Sink 共享连接、缓冲和计数的写出端，供定时任务使用
+    fn flush_writer(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            table: self.table.clone(),
+            cloumn_name: self.cloumn_name.clone(),
+            batch: self.batch,
+            flush_bytes: self.flush_bytes,
+            schema: None,
+            column_map: self.column_map.clone(),
+            column_filter: self.column_filter.clone(),
+            oversize: self.oversize,
+            column_lengths: self.column_lengths.clone(),
+            router: self.router.clone(),
+            routes: self.routes.clone(),
+            null_format: self.null_format.clone(),
+            bytes_encoding: self.bytes_encoding,
+            flush_interval: None,
+            pending: self.pending.clone(),
+            flush_failed: self.flush_failed.clone(),
+            stop_tx: None,
+            flush_handle: None,
+            counters: self.counters.clone(),
+        }
+    }
+
+    /// 按 `flush_interval` 定时写出缓冲，避免低流量时记录长时间停留在缓冲中
+    pub(crate) fn start_flush_task(&mut self) {
+        let Some(interval) = self.flush_interval else {
+            return;
+        };
+        if self.flush_handle.is_some() {
+            return;
+        }
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let writer = self.flush_writer();
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        if let Err(e) = writer.flush_pending().await {
+                            error_data!("[mysql] periodic flush fail: {}", e);
+                        }
+                    }
+                    _ = &mut stop_rx => break,
+                }
+            }
+        });
+        self.stop_tx = Some(stop_tx);
+        self.flush_handle = Some(handle);
+    }
+
+    async fn stop_flush_task(&mut self) {
+        if let Some(tx) = self.stop_tx.take() {
+            let _ = tx.send(());
+        }
+        if let Some(handle) = self.flush_handle.take()
+            && let Err(e) = handle.await
+        {
+            error_data!("[mysql] flush task join error: {}", e);
+        }
+    }
+
+    fn abort_flush_task(&mut self) {
+        self.stop_tx.take();
+        if let Some(handle) = self.flush_handle.take() {
+            handle.abort();
+        }
+    }
 }
 
 #[async_trait]
 impl AsyncCtrl for MysqlSink {
     async fn stop(&mut self) -> SinkResult<()> {
-        Ok(())
+        self.stop_flush_task().await;
+        self.flush_pending().await
     }
     async fn reconnect(&mut self) -> SinkResult<()> {
-        self.db.ping().await.map_err(|e| {
-            SinkError::from(SinkReason::Sink(format!("reconnect mysql fail: {}", e)))
-        })?;
-        Ok(())
+        reconnect_with_backoff("mysql", RetryPolicy::reconnect(), || async {
+            self.db.ping().await.map_err(|e| {
+                SinkError::from(
+                    connect_error_kind(&e).sink("mysql", format!("reconnect mysql fail: {}", e)),
+                )
+            })
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl DeadlineCtrl for MysqlSink {
+    /// 在截止时间内写出缓冲；超时后放弃缓冲中的记录。未设置 `flush_interval` 时
+    /// 每批数据在 `sink_records` 内以事务提交，没有待排空的缓冲
+    async fn stop_with_deadline(&mut self, deadline: Duration) -> SinkResult<DrainReport> {
+        self.abort_flush_task();
+        let buffered = self.pending.lock().await.len() as u64;
+        match tokio::time::timeout(deadline, self.flush_pending()).await {
+            Ok(res) => res.map(|_| DrainReport::drained()),
+            Err(_) => {
+                if buffered > 0 {
+                    wp_log::warn_data!(
+                        "[mysql] stop deadline hit, dropping {} buffered rows",
+                        buffered
+                    );
+                }
+                Ok(DrainReport::timed_out(buffered))
+            }
+        }
+    }
+}
+
+impl Drop for MysqlSink {
+    fn drop(&mut self) {
+        self.abort_flush_task();
+    }
+}
+
+impl SinkMetrics for MysqlSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
     }
 }
 
@@ -78,54 +649,78 @@ impl AsyncRecordSink for MysqlSink {
     }
 
     async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
-        let mut raws = Vec::with_capacity(data.len());
-        for record in data {
-            raws.push(self.format_values_tuple(record.as_ref()));
-        }
-        if !raws.is_empty() {
-            // 单条 INSERT + 多个 VALUES
-            let mut sql = self.base_insert_prefix();
-            sql.push_str(&raws.join(","));
-            if let Err(e) = self.db.execute_unprepared(sql.as_str()).await {
-                return Err(SinkError::from(SinkReason::Sink(format!(
-                    "mysql exec cloumns:{:?}, fail: {}, sql: {}",
-                    self.cloumn_name, e, sql
-                ))));
+        self.counters.receive(data.len());
+        // 未通过 Schema 校验的记录不参与 INSERT，只计数（或写入死信文件）
+        let data = match self.schema.as_mut() {
+            Some(validator) => {
+                let (valid, invalid) = validator.split(data)?;
+                record_invalid("mysql", &self.table, invalid);
+                valid
             }
+            None => data,
+        };
+        if self.flush_interval.is_none() {
+            return self.write_records(data).await;
+        }
+        let mut pending = self.pending.lock().await;
+        // 未设置 batch 时没有累计目标，收到即写出；定时写出失败过则立即连同缓冲一起写出
+        let limit = self.batch.unwrap_or(1).min(MAX_PENDING_ROWS);
+        if pending.len() + data.len() < limit && !self.flush_failed.load(Ordering::Relaxed) {
+            pending.extend(data);
+            return Ok(());
+        }
+        let acked = pending.len();
+        pending.extend(data);
+        let result = self.write_pending(&mut pending).await;
+        if result.is_err() {
+            // 本次记录交还调用方重试或落盘，缓冲只保留此前已确认的记录
+            pending.truncate(acked);
         }
-        Ok(())
+        result
     }
 }
 
 #[async_trait]
 impl AsyncRawDataSink for MysqlSink {
     async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
-        Err(SinkError::from(SinkReason::Sink(
-            "mysql sink does not accept raw input".into(),
-        )))
+        Err(SinkError::from(
+            ErrorKind::Config.sink("mysql", "mysql sink does not accept raw input"),
+        ))
     }
     async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
-        Err(SinkError::from(SinkReason::Sink(
-            "mysql sink does not accept raw bytes".into(),
-        )))
+        Err(SinkError::from(
+            ErrorKind::Config.sink("mysql", "mysql sink does not accept raw bytes"),
+        ))
     }
 
     async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
-        Err(SinkError::from(SinkReason::Sink(
-            "mysql sink does not accept raw input".into(),
-        )))
+        Err(SinkError::from(
+            ErrorKind::Config.sink("mysql", "mysql sink does not accept raw input"),
+        ))
     }
     async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
-        Err(SinkError::from(SinkReason::Sink(
-            "mysql sink does not accept raw bytes".into(),
-        )))
+        Err(SinkError::from(
+            ErrorKind::Config.sink("mysql", "mysql sink does not accept raw bytes"),
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MysqlSink;
+    use super::{
+        MAX_PENDING_ROWS, MysqlSink, NullFormat, NullLiteral, OversizeAction, TableColumns,
+        quote_ident,
+    };
+    use crate::utils::column_filter::ColumnFilter;
+    use crate::utils::db_metrics::DB_SINK_ROWS;
+    use crate::utils::error_kind::ErrorKind;
+    use crate::utils::shutdown::{DeadlineCtrl, DrainReport};
+    use crate::utils::table_route::TableRouter;
     use sea_orm::DatabaseConnection;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use wp_connector_api::AsyncRecordSink;
     use wp_model_core::model::{DataField, DataRecord};
 
     fn make_sink(table: &str, columns: Vec<&str>) -> MysqlSink {
@@ -139,8 +734,21 @@ mod tests {
     #[test]
     fn mysql_sink_base_insert_prefix() {
         let sink = make_sink("users", vec!["name", "age"]);
-        let sql = sink.base_insert_prefix();
-        assert_eq!(sql, "INSERT IGNORE INTO users (`name`, `age`) VALUES ");
+        let sql = sink.base_insert_prefix(sink.target(&sink.table));
+        assert_eq!(sql, "INSERT IGNORE INTO `users` (`name`, `age`) VALUES ");
+    }
+
+    #[test]
+    fn mysql_sink_quotes_special_identifiers() {
+        assert_eq!(quote_ident("a`b"), "`a``b`");
+        assert_eq!(quote_ident("src.ip"), "`src.ip`");
+        assert_eq!(quote_ident("order"), "`order`");
+        let sink = make_sink("logs.events", vec!["a`b", "src.ip", "order"]);
+        let sql = sink.base_insert_prefix(sink.target(&sink.table));
+        assert_eq!(
+            sql,
+            "INSERT IGNORE INTO `logs`.`events` (`a``b`, `src.ip`, `order`) VALUES "
+        );
     }
 
     #[test]
@@ -151,7 +759,389 @@ mod tests {
         record.append(DataField::from_digit("age", 42));
         record.append(DataField::from_ignore("unused"));
 
-        let values = sink.format_values_tuple(&record);
+        let values = sink.format_values_tuple(sink.target(&sink.table), &record);
         assert_eq!(values, "('O''Reilly', '42', NULL)");
     }
+
+    #[test]
+    fn mysql_sink_bytes_encoding_renders_hex_fields() {
+        use crate::utils::bytes_encoding::BytesEncoding;
+        use wp_model_core::model::{DataType, HexT, Value};
+
+        let mut record = DataRecord::default();
+        record.append(DataField::new(
+            DataType::Hex,
+            "payload",
+            Value::Hex(HexT(0x7770)),
+        ));
+        let sink = make_sink("blobs", vec!["payload"]);
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &record),
+            "('0x7770')"
+        );
+        let sink = sink.with_bytes_encoding(Some(BytesEncoding::Base64));
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &record),
+            "('d3A=')"
+        );
+    }
+
+    #[test]
+    fn mysql_sink_empty_string_preserved_unless_empty_as_null() {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("name", "alice"));
+        record.append(DataField::from_chars("note", ""));
+
+        // 默认：存在但为空的字段写入 ''，与缺失字段的 NULL 可区分
+        let sink = make_sink("users", vec!["name", "note", "email"]);
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &record),
+            "('alice', '', NULL)"
+        );
+
+        let sink = make_sink("users", vec!["name", "note", "email"]).with_null_format(NullFormat {
+            empty_as_null: true,
+            ..NullFormat::default()
+        });
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &record),
+            "('alice', NULL, NULL)"
+        );
+
+        let sink = make_sink("users", vec!["name", "note", "email"]).with_null_format(NullFormat {
+            empty_as_null: true,
+            literal: NullLiteral::Default,
+        });
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &record),
+            "('alice', DEFAULT, DEFAULT)"
+        );
+    }
+
+    #[test]
+    fn mysql_sink_column_map_routes_field_into_column() {
+        let sink = make_sink("flows", vec!["src_ip", "port"])
+            .with_column_map([("srcIp", "src_ip")].into_iter().collect());
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("srcIp", "10.0.0.1"));
+        record.append(DataField::from_digit("port", 443));
+
+        let values = sink.format_values_tuple(sink.target(&sink.table), &record);
+        assert_eq!(values, "('10.0.0.1', '443')");
+    }
+
+    #[test]
+    fn mysql_sink_excluded_field_never_reaches_insert() {
+        let sink = make_sink("users", vec!["name", "raw_password"])
+            .with_column_filter(ColumnFilter::default().excluding(["raw_password"]));
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("name", "alice"));
+        record.append(DataField::from_chars("raw_password", "s3cret"));
+
+        let sqls = sink.build_insert_sqls(sink.target(&sink.table), &[Arc::new(record.clone())]);
+        assert_eq!(sqls.len(), 1);
+        assert!(!sqls[0].contains("s3cret"), "{}", sqls[0]);
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &record),
+            "('alice', NULL)"
+        );
+
+        let sink = make_sink("users", vec!["name", "raw_password"])
+            .with_column_filter(ColumnFilter::default().including(["name"]));
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &record),
+            "('alice', NULL)"
+        );
+    }
+
+    #[test]
+    fn mysql_sink_oversize_truncates_or_skips_long_values() {
+        let lengths = HashMap::from([("name".to_string(), 3)]);
+        let mut long = DataRecord::default();
+        long.append(DataField::from_chars("name", "abcdef"));
+        let mut short = DataRecord::default();
+        short.append(DataField::from_chars("name", "ab"));
+
+        let sink =
+            make_sink("t", vec!["name"]).with_oversize(OversizeAction::Truncate, lengths.clone());
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &long),
+            "('abc')"
+        );
+
+        let sink =
+            make_sink("t", vec!["name"]).with_oversize(OversizeAction::Skip, lengths.clone());
+        let (kept, skipped) = sink.drop_oversized(
+            sink.target(&sink.table),
+            vec![Arc::new(long.clone()), Arc::new(short)],
+        );
+        assert_eq!(skipped, 1);
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &kept[0]),
+            "('ab')"
+        );
+
+        let sink = make_sink("t", vec!["name"]).with_oversize(OversizeAction::Error, lengths);
+        assert_eq!(
+            sink.format_values_tuple(sink.target(&sink.table), &long),
+            "('abcdef')"
+        );
+    }
+
+    #[test]
+    fn mysql_sink_build_insert_sqls_respects_batch() {
+        let records: Vec<_> = (0..5)
+            .map(|i| {
+                let mut record = DataRecord::default();
+                record.append(DataField::from_digit("id", i));
+                Arc::new(record)
+            })
+            .collect();
+
+        let sink = make_sink("t", vec!["id"]);
+        assert_eq!(
+            sink.build_insert_sqls(sink.target(&sink.table), &records),
+            vec!["INSERT IGNORE INTO `t` (`id`) VALUES ('0'),('1'),('2'),('3'),('4')"]
+        );
+
+        let sink = make_sink("t", vec!["id"]).with_batch(Some(2));
+        assert_eq!(
+            sink.build_insert_sqls(sink.target(&sink.table), &records),
+            vec![
+                "INSERT IGNORE INTO `t` (`id`) VALUES ('0'),('1')",
+                "INSERT IGNORE INTO `t` (`id`) VALUES ('2'),('3')",
+                "INSERT IGNORE INTO `t` (`id`) VALUES ('4')",
+            ]
+        );
+    }
+
+    #[test]
+    fn mysql_sink_build_insert_sqls_splits_on_count_or_bytes() {
+        let record = |note: String| {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_chars("note", note));
+            Arc::new(record)
+        };
+        let sink = make_sink("t", vec!["note"])
+            .with_batch(Some(3))
+            .with_flush_bytes(Some(64));
+
+        // 小记录：先达到条数阈值
+        let small: Vec<_> = (0..7).map(|i| record(i.to_string())).collect();
+        let sqls = sink.build_insert_sqls(sink.target(&sink.table), &small);
+        assert_eq!(sqls.len(), 3);
+        assert!(sqls[0].ends_with("VALUES ('0'),('1'),('2')"));
+        assert!(sqls[2].ends_with("VALUES ('6')"));
+
+        // 大记录：先达到字节阈值，每条语句只放得下一行
+        let large: Vec<_> = (0..3).map(|i| record(format!("{i}").repeat(40))).collect();
+        let sqls = sink.build_insert_sqls(sink.target(&sink.table), &large);
+        assert_eq!(sqls.len(), 3);
+        let prefix = sink.base_insert_prefix(sink.target(&sink.table)).len();
+        assert!(sqls.iter().all(|sql| sql.len() - prefix <= 64));
+    }
+
+    #[test]
+    fn mysql_sink_routes_records_to_separate_inserts_per_table() {
+        let routes = HashMap::from([(
+            "refunds".to_string(),
+            TableColumns {
+                columns: vec!["id".to_string(), "reason".to_string()],
+                ..Default::default()
+            },
+        )]);
+        let sink = make_sink("orders", vec!["id", "amount"])
+            .with_table_routes(TableRouter::new("dest", ["orders", "refunds"]), routes);
+        let record = |id: i64, dest: &str, extra: (&str, &str)| {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_digit("id", id));
+            record.append(DataField::from_chars("dest", dest));
+            record.append(DataField::from_chars(extra.0, extra.1));
+            Arc::new(record)
+        };
+        let routed = sink.router.as_ref().unwrap().route(
+            &sink.table,
+            vec![
+                record(1, "orders", ("amount", "10")),
+                record(2, "refunds", ("reason", "damaged")),
+                record(3, "orders", ("amount", "30")),
+            ],
+        );
+
+        let sqls: Vec<String> = routed
+            .groups
+            .iter()
+            .flat_map(|(table, records)| sink.build_insert_sqls(sink.target(table), records))
+            .collect();
+        assert_eq!(
+            sqls,
+            vec![
+                "INSERT IGNORE INTO `orders` (`id`, `amount`) VALUES ('1', '10'),('3', '30')",
+                "INSERT IGNORE INTO `refunds` (`id`, `reason`) VALUES ('2', 'damaged')",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn mysql_sink_stop_with_deadline_has_nothing_to_drain() {
+        let mut sink = make_sink("t", vec!["id"]);
+        let report = sink
+            .stop_with_deadline(Duration::from_millis(50))
+            .await
+            .expect("stop");
+        assert_eq!(report, DrainReport::drained());
+    }
+
+    #[tokio::test]
+    async fn mysql_sink_flush_interval_writes_partial_batch() {
+        use crate::utils::sink_metrics::SinkMetrics;
+        use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+        use wp_connector_api::AsyncCtrl;
+
+        let db = MockDatabase::new(DatabaseBackend::MySql)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection();
+        let mut sink = MysqlSink::new(db, "mysql_interval_t".into(), vec!["id".into()])
+            .with_batch(Some(100))
+            .with_flush_interval(Some(Duration::from_millis(50)));
+        sink.start_flush_task();
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 1));
+        sink.sink_record(&record).await.expect("buffered");
+        // 未达到 batch，记录仍在缓冲中
+        assert_eq!(sink.metrics().flushed, 0);
+        assert_eq!(sink.pending.lock().await.len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(sink.metrics().flushed, 1);
+        assert!(sink.pending.lock().await.is_empty());
+
+        sink.stop().await.expect("stop");
+        assert!(sink.flush_handle.is_none());
+    }
+
+    #[tokio::test]
+    async fn mysql_sink_failed_flush_hands_back_new_rows() {
+        use wp_connector_api::AsyncCtrl;
+
+        // 未连接的 DatabaseConnection 在开启事务时即失败
+        let mut sink = make_sink("mysql_retain_t", vec!["id"])
+            .with_batch(Some(100))
+            .with_flush_interval(Some(Duration::from_millis(20)));
+        sink.start_flush_task();
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 1));
+        sink.sink_record(&record).await.expect("buffered");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        // 定时写出失败后记录仍在缓冲中
+        assert_eq!(sink.pending.lock().await.len(), 1);
+
+        // 下一次前台写入立即连同缓冲写出；失败时本次记录交还调用方，重试不会在缓冲中重复
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 2));
+        for _ in 0..3 {
+            sink.sink_record(&record).await.expect_err("write fails");
+            assert_eq!(sink.pending.lock().await.len(), 1);
+        }
+
+        sink.stop().await.expect_err("stop flush fails");
+        assert_eq!(sink.pending.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn mysql_sink_pending_rows_are_capped() {
+        use crate::utils::sink_metrics::SinkMetrics;
+
+        let mut sink = make_sink("mysql_cap_t", vec!["id"])
+            .with_batch(Some(MAX_PENDING_ROWS * 2))
+            .with_flush_interval(Some(Duration::from_secs(3600)));
+        let records = |n: usize| {
+            (0..n)
+                .map(|i| {
+                    let mut record = DataRecord::default();
+                    record.append(DataField::from_digit("id", i as i64));
+                    Arc::new(record)
+                })
+                .collect::<Vec<_>>()
+        };
+        sink.sink_records(records(MAX_PENDING_ROWS - 1))
+            .await
+            .expect("buffered");
+        assert_eq!(sink.pending.lock().await.len(), MAX_PENDING_ROWS - 1);
+
+        // 达到上限即写出，失败的新记录计为失败且不进入缓冲
+        sink.sink_records(records(2))
+            .await
+            .expect_err("write fails");
+        assert_eq!(sink.pending.lock().await.len(), MAX_PENDING_ROWS - 1);
+        assert_eq!(sink.metrics().failed, MAX_PENDING_ROWS as u64 + 1);
+    }
+
+    #[tokio::test]
+    async fn mysql_sink_failed_flush_counts_err_rows() {
+        // 未连接的 DatabaseConnection 在开启事务时即失败
+        let mut sink = make_sink("mysql_metrics_t", vec!["id"]);
+        let err = DB_SINK_ROWS.with_label_values(&["mysql", "mysql_metrics_t", "err"]);
+        let before = err.get();
+        let records = (0..3)
+            .map(|i| {
+                let mut record = DataRecord::default();
+                record.append(DataField::from_digit("id", i));
+                Arc::new(record)
+            })
+            .collect();
+
+        let flush_err = sink
+            .sink_records(records)
+            .await
+            .expect_err("unconnected db");
+        assert_eq!(ErrorKind::of_sink(&flush_err), Some(ErrorKind::Connect));
+        assert_eq!(err.get(), before + 3);
+    }
+
+    #[tokio::test]
+    async fn mysql_sink_reconnect_retries_before_failing() {
+        use wp_connector_api::AsyncCtrl;
+        // 未连接的 DatabaseConnection 每次 ping 都失败，用尽退避次数后返回最后一次错误
+        let mut sink = make_sink("mysql_reconnect_t", vec!["id"]);
+        let start = std::time::Instant::now();
+        let err = sink.reconnect().await.expect_err("disconnected db");
+        assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Connect));
+        assert!(err.to_string().contains("reconnect mysql fail"), "{err}");
+        assert!(start.elapsed() >= std::time::Duration::from_millis(1500));
+    }
+
+    #[tokio::test]
+    async fn mysql_sink_schema_drops_invalid_records() {
+        use crate::utils::schema::{InvalidAction, RecordValidator};
+
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {"id": {"type": "integer", "minimum": 0}}
+        });
+        let validator = RecordValidator::new("mysql", &schema, &InvalidAction::Count).unwrap();
+        let mut sink = make_sink("mysql_schema_t", vec!["id"]).with_schema(Some(validator));
+        let invalid = DB_SINK_ROWS.with_label_values(&["mysql", "mysql_schema_t", "invalid"]);
+        let err = DB_SINK_ROWS.with_label_values(&["mysql", "mysql_schema_t", "err"]);
+        let (invalid_before, err_before) = (invalid.get(), err.get());
+        let records = [1, -1]
+            .into_iter()
+            .map(|i| {
+                let mut record = DataRecord::default();
+                record.append(DataField::from_digit("id", i));
+                Arc::new(record)
+            })
+            .collect();
+
+        // 只有通过校验的一条记录进入 INSERT（未连接的数据库使其失败）
+        assert!(sink.sink_records(records).await.is_err());
+        assert_eq!(invalid.get(), invalid_before + 1);
+        assert_eq!(err.get(), err_before + 1);
+    }
 }
@@ -1,27 +1,126 @@
 use crate::mysql::config::MysqlConf as MySqlConf;
+use crate::utils::ingest_time::IngestTime;
 use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime};
+use lazy_static::lazy_static;
 use orion_error::{ToStructError, UvsReason};
-use sea_orm::ConnectionTrait;
-use sea_orm::{ConnectOptions, Database, DatabaseConnection, Statement};
+use prometheus::{IntGaugeVec, register_int_gauge_vec};
+use sea_orm::{ConnectOptions, Database, DatabaseConnection, QueryResult, Statement};
+use sea_orm::{ConnectionTrait, DbErr};
 use std::collections::VecDeque;
+use std::fmt::Write;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
+use tokio::time::Instant;
 use wp_connector_api::{
     DataSource, SourceBatch, SourceError, SourceEvent, SourceReason, SourceResult, Tags,
 };
+use wp_data_fmt::{FormatType, RecordFormatter};
 use wp_log::info_data;
+use wp_model_core::model::{DataField, DataRecord, fmt_def::TextFmt};
 use wp_model_core::raw::RawData;
 
 type AnyResult<T> = anyhow::Result<T>;
 
+/// 一次性模式读完数据后，终止事件携带的标签名（值为 `true`）
+pub const EOF_TAG: &str = "eof";
+
+lazy_static! {
+    /// 各 Source 当前的 checkpoint（已输出的行数游标），与 `.dat` 文件内容一致
+    pub static ref MYSQL_SOURCE_CHECKPOINT: IntGaugeVec = register_int_gauge_vec!(
+        "wparse_mysql_source_checkpoint",
+        "Current checkpoint (row cursor) of each MySQL source.",
+        &["source"]
+    )
+    .expect("register wparse_mysql_source_checkpoint fail");
+}
+
+/// 行数据的输出模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MysqlSourceMode {
+    /// 由 MySQL 端 `JSON_OBJECT` 拼装整行（二进制列 base64），兼容旧行为
+    #[default]
+    Json,
+    /// 按列类型逐列读取并构建 `DataRecord`（digit/float/time/chars），
+    /// 再以 JSON 格式化为事件载荷；NULL 列不输出
+    Typed,
+}
+
+impl MysqlSourceMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "typed" => Some(Self::Typed),
+            _ => None,
+        }
+    }
+}
+
+/// 表中数据读完后的行为
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MysqlFetchMode {
+    /// 查询结果为空时输出一个带 [`EOF_TAG`] 标签的空载荷终止事件，Source 结束（默认）
+    #[default]
+    OneShot,
+    /// 查询结果为空时返回空批次，下一次 `receive` 等待间隔后重新查询，持续读取新增行
+    Poll(Duration),
+}
+
+/// typed 模式下列值的映射类别，由 `INFORMATION_SCHEMA.COLUMNS.DATA_TYPE` 决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Digit,
+    Float,
+    Time,
+    Bytes,
+    Chars,
+}
+
+impl ColumnKind {
+    fn from_data_type(dt: &str) -> Self {
+        match dt.to_ascii_lowercase().as_str() {
+            "tinyint" | "smallint" | "mediumint" | "int" | "integer" | "bigint" | "year" => {
+                Self::Digit
+            }
+            "float" | "double" | "real" | "decimal" | "numeric" => Self::Float,
+            "date" | "datetime" | "timestamp" => Self::Time,
+            "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" => Self::Bytes,
+            _ => Self::Chars,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MysqlColumn {
+    name: String,
+    kind: ColumnKind,
+}
+
+/// typed 模式下单列读取到的原始值
+enum Cell {
+    Text(Option<String>),
+    Bytes(Option<Vec<u8>>),
+}
+
 pub struct MysqlSource {
     key: String,
     db: DatabaseConnection,
     statement: String,
+    mode: MysqlSourceMode,
+    columns: Vec<MysqlColumn>,
+    fetch_mode: MysqlFetchMode,
+    /// 兼容旧行为：读完数据时直接返回 `SourceReason::EOF` 错误，不输出终止事件
+    eof_as_error: bool,
+    /// 一次性模式下数据已读完
+    exhausted: bool,
+    /// 轮询模式下一次查询的最早时间
+    next_poll: Option<Instant>,
     checkpoint: u64,
     checkpoint_path: PathBuf,
     data_cache: VecDeque<String>,
     tags: Tags,
+    /// 设置后每个事件带上接入时间标签
+    ingest_time: Option<IngestTime>,
 }
 
 impl MysqlSource {
@@ -29,16 +128,22 @@ impl MysqlSource {
         &self.key
     }
 
-    pub async fn new(key: String, tags: Tags, config: &MySqlConf) -> AnyResult<Self> {
-        // table 在新版配置中为 Option<String>
+    pub async fn new(
+        key: String,
+        tags: Tags,
+        config: &MySqlConf,
+        mode: MysqlSourceMode,
+    ) -> AnyResult<Self> {
+        // table 在新版配置中为 Option<String>；配置自定义查询时不需要
         let table = config.table.as_deref().unwrap_or("");
-        if table.trim().is_empty() {
+        if config.query.is_none() && table.trim().is_empty() {
             anyhow::bail!("mysql.table must not be empty");
         }
 
         wp_log::info_data!("[mysql] database: {:?}, table: {}", config.database, table);
 
         let checkpoint = MysqlSource::get_checkpoints(&key)?;
+        publish_checkpoint(&key, checkpoint);
 
         let mut opt = ConnectOptions::new(config.get_database_url());
         opt.max_connections(3)
@@ -51,57 +156,87 @@ impl MysqlSource {
             .sqlx_logging_level(log::LevelFilter::Debug);
         let db = Database::connect(opt).await?;
 
-        let cols_sql = "SELECT COLUMN_NAME, DATA_TYPE \
-                    FROM INFORMATION_SCHEMA.COLUMNS \
-                    WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? \
-                    ORDER BY ORDINAL_POSITION";
-        let cols_stmt = Statement::from_sql_and_values(
-            db.get_database_backend(),
-            cols_sql,
-            vec![config.database.clone().into(), table.to_string().into()],
-        );
-
-        let col_rows = db.query_all(cols_stmt).await?;
-        let mut parts = Vec::with_capacity(col_rows.len());
-        for row in col_rows {
-            let name: String = row.try_get_by_index(0)?;
-            let dt: String = row.try_get_by_index(1)?;
-            let expr = match dt.to_ascii_lowercase().as_str() {
-                "binary" | "varbinary" | "blob" | "mediumblob" | "longblob" => {
-                    format!("'{}', TO_BASE64(`{}`)", name, name)
-                }
-                _ => format!("'{}', `{}`", name, name),
-            };
-            parts.push(expr);
-        }
-
         // 采样批大小：复用配置中的 batch 字段；默认 100
         let step_len: usize = config.batch.unwrap_or(100);
-        let statement = format!(
-            "SELECT CAST(JSON_OBJECT({}) AS CHAR CHARACTER SET utf8mb4) FROM `{}` LIMIT {} OFFSET ?;",
-            parts.join(", "),
-            table,
-            step_len
-        );
+        let (statement, columns) = match &config.query {
+            Some(query) => (
+                build_query_statement(
+                    &validate_query(query).map_err(anyhow::Error::msg)?,
+                    step_len,
+                ),
+                Vec::new(),
+            ),
+            None => {
+                let columns = load_columns(&db, &config.database, table).await?;
+                let statement = build_select_statement(table, &columns, mode, step_len);
+                (statement, columns)
+            }
+        };
         let path_str = format!("./.run/.checkpoints/{}.dat", &key);
         let checkpoint_path = Path::new(&path_str).to_path_buf();
         Ok(Self {
             key,
             db,
             statement,
+            mode,
+            columns,
+            fetch_mode: MysqlFetchMode::default(),
+            eof_as_error: false,
+            exhausted: false,
+            next_poll: None,
             checkpoint,
             checkpoint_path,
             data_cache: VecDeque::new(),
             tags,
+            ingest_time: None,
         })
     }
 
+    /// 设置读完数据后的行为
+    pub fn with_fetch_mode(mut self, fetch_mode: MysqlFetchMode) -> Self {
+        self.fetch_mode = fetch_mode;
+        self
+    }
+
+    /// 读完数据时是否沿用旧行为直接返回 `EOF` 错误
+    pub fn with_eof_as_error(mut self, eof_as_error: bool) -> Self {
+        self.eof_as_error = eof_as_error;
+        self
+    }
+
+    /// 为每个事件写入接入时间标签，`None` 表示不写入
+    pub fn with_ingest_time(mut self, ingest_time: Option<IngestTime>) -> Self {
+        self.ingest_time = ingest_time;
+        self
+    }
+
+    /// 一次性模式下数据是否已读完（终止事件已输出）
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// 读完数据后的终止事件：空载荷，带 `eof = true` 标签
+    fn eof_event(&self) -> SourceEvent {
+        let mut tags = self.tags.clone();
+        tags.set(EOF_TAG, "true");
+        SourceEvent::new(
+            self.checkpoint,
+            self.key.clone(),
+            RawData::from_string(String::new()),
+            tags.into(),
+        )
+    }
+
     fn create_event(&self, event_id: u64, json_str: String) -> SourceEvent {
+        let mut tags = self.tags.clone();
+        if let Some(ingest_time) = &self.ingest_time {
+            ingest_time.stamp(&mut tags);
+        }
         SourceEvent::new(
             event_id,
             self.key.clone(),
             RawData::from_string(json_str),
-            self.tags.clone().into(),
+            tags.into(),
         )
     }
 
@@ -122,30 +257,66 @@ impl MysqlSource {
             })?;
 
         if rows.is_empty() {
-            return Err(SourceError::from(SourceReason::EOF));
+            return match self.fetch_mode {
+                MysqlFetchMode::OneShot if self.eof_as_error => {
+                    Err(SourceError::from(SourceReason::EOF))
+                }
+                MysqlFetchMode::OneShot => {
+                    info_data!(
+                        "[mysql] source {} exhausted at {}",
+                        self.key,
+                        self.checkpoint
+                    );
+                    self.exhausted = true;
+                    Ok(())
+                }
+                MysqlFetchMode::Poll(interval) => {
+                    self.next_poll = Some(Instant::now() + interval);
+                    Ok(())
+                }
+            };
         }
 
         // 填充缓存
         for row in rows {
-            let json_str: String = row.try_get_by_index(0).map_err(|e| {
+            let payload = match self.mode {
+                MysqlSourceMode::Json => row.try_get_by_index(0),
+                MysqlSourceMode::Typed => read_typed_row(&row, &self.columns)
+                    .map(|record| FormatType::from(&TextFmt::Json).fmt_record(&record)),
+            }
+            .map_err(|e| {
                 SourceReason::Uvs(UvsReason::data_error())
                     .to_err()
                     .with_detail(e.to_string())
             })?;
-            self.data_cache.push_back(json_str);
+            self.data_cache.push_back(payload);
         }
 
         Ok(())
     }
 
+    /// 拉取下一批事件
+    ///
+    /// 一次性模式读完数据时先输出一次终止事件（见 [`Self::eof_event`]），
+    /// 之后的调用按 `DataSource` 约定返回 `SourceReason::EOF`。
     pub async fn recv_impl(&mut self) -> SourceResult<SourceBatch> {
+        if self.exhausted {
+            return Err(SourceError::from(SourceReason::EOF));
+        }
         if self.data_cache.is_empty() {
+            // 轮询模式上次查询为空时，等待到间隔结束再查询
+            if let Some(at) = self.next_poll.take() {
+                tokio::time::sleep_until(at).await;
+            }
             // 缓存为空时，从数据库获取新数据
             self.fetch_data_from_db().await?;
+            if self.exhausted {
+                return Ok(vec![self.eof_event()]);
+            }
         }
 
         if self.data_cache.is_empty() {
-            // fetch_data_from_db 已经保证空时返回 EOF，这里兜底返回空批次
+            // 轮询模式下暂无新数据，返回空批次
             return Ok(vec![]);
         }
 
@@ -175,9 +346,10 @@ impl MysqlSource {
         Ok(0)
     }
 
-    /// 更新 checkpoint 文件
+    /// 更新 checkpoint 文件与 `wparse_mysql_source_checkpoint` 指标
     pub fn set_checkpoints(&mut self) -> SourceResult<()> {
         self.checkpoint += 1;
+        publish_checkpoint(&self.key, self.checkpoint);
         if let Err(e) = std::fs::write(&self.checkpoint_path, self.checkpoint.to_string()) {
             info_data!("set checkpoint {} failed: {}", self.checkpoint, e);
         }
@@ -185,6 +357,164 @@ impl MysqlSource {
     }
 }
 
+/// 将 checkpoint 写入进度指标，超出 `i64` 时取上限
+fn publish_checkpoint(source: &str, checkpoint: u64) {
+    MYSQL_SOURCE_CHECKPOINT
+        .with_label_values(&[source])
+        .set(i64::try_from(checkpoint).unwrap_or(i64::MAX));
+}
+
+/// 从 `INFORMATION_SCHEMA.COLUMNS` 读取表的列名与类型
+async fn load_columns(
+    db: &DatabaseConnection,
+    database: &str,
+    table: &str,
+) -> Result<Vec<MysqlColumn>, DbErr> {
+    let cols_sql = "SELECT COLUMN_NAME, DATA_TYPE \
+                    FROM INFORMATION_SCHEMA.COLUMNS \
+                    WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? \
+                    ORDER BY ORDINAL_POSITION";
+    let cols_stmt = Statement::from_sql_and_values(
+        db.get_database_backend(),
+        cols_sql,
+        vec![database.into(), table.into()],
+    );
+
+    let col_rows = db.query_all(cols_stmt).await?;
+    let mut columns = Vec::with_capacity(col_rows.len());
+    for row in col_rows {
+        let name: String = row.try_get_by_index(0)?;
+        let dt: String = row.try_get_by_index(1)?;
+        columns.push(MysqlColumn {
+            name,
+            kind: ColumnKind::from_data_type(&dt),
+        });
+    }
+    Ok(columns)
+}
+
+/// 校验自定义查询：须以 `SELECT` 开头且不以 `;` 结尾（分页子句会追加在其后）
+pub(crate) fn validate_query(raw: &str) -> Result<String, String> {
+    let query = raw.trim();
+    let is_select = query
+        .get(..6)
+        .is_some_and(|head| head.eq_ignore_ascii_case("select"))
+        && query[6..].starts_with(char::is_whitespace);
+    if !is_select {
+        return Err("mysql.query must start with SELECT".into());
+    }
+    if query.ends_with(';') {
+        return Err("mysql.query must not end with ';'".into());
+    }
+    Ok(query.to_string())
+}
+
+/// 自定义查询原样作为基础语句，仅追加分页子句；查询须返回一列 JSON 文本（如 `JSON_OBJECT(...)`）
+fn build_query_statement(query: &str, step_len: usize) -> String {
+    format!("{query} LIMIT {step_len} OFFSET ?;")
+}
+
+/// 按模式生成分页查询语句
+///
+/// - json：在服务端用 `JSON_OBJECT` 拼装整行，二进制列转 base64；
+/// - typed：逐列选出，二进制列保留原始字节，其余列统一转为文本后在本地按列类型解析，
+///   避免依赖驱动侧对 DECIMAL/DATETIME/无符号整型等类型的解码支持。
+fn build_select_statement(
+    table: &str,
+    columns: &[MysqlColumn],
+    mode: MysqlSourceMode,
+    step_len: usize,
+) -> String {
+    match mode {
+        MysqlSourceMode::Json => {
+            let parts: Vec<String> = columns
+                .iter()
+                .map(|col| match col.kind {
+                    ColumnKind::Bytes => format!("'{}', TO_BASE64(`{}`)", col.name, col.name),
+                    _ => format!("'{}', `{}`", col.name, col.name),
+                })
+                .collect();
+            format!(
+                "SELECT CAST(JSON_OBJECT({}) AS CHAR CHARACTER SET utf8mb4) FROM `{}` LIMIT {} OFFSET ?;",
+                parts.join(", "),
+                table,
+                step_len
+            )
+        }
+        MysqlSourceMode::Typed => {
+            let parts: Vec<String> = columns
+                .iter()
+                .map(|col| match col.kind {
+                    ColumnKind::Bytes => format!("`{}`", col.name),
+                    _ => format!("CAST(`{}` AS CHAR CHARACTER SET utf8mb4)", col.name),
+                })
+                .collect();
+            format!(
+                "SELECT {} FROM `{}` LIMIT {} OFFSET ?;",
+                parts.join(", "),
+                table,
+                step_len
+            )
+        }
+    }
+}
+
+fn read_typed_row(row: &QueryResult, columns: &[MysqlColumn]) -> Result<DataRecord, DbErr> {
+    let mut cells = Vec::with_capacity(columns.len());
+    for (idx, col) in columns.iter().enumerate() {
+        let cell = match col.kind {
+            ColumnKind::Bytes => Cell::Bytes(row.try_get_by_index(idx)?),
+            _ => Cell::Text(row.try_get_by_index(idx)?),
+        };
+        cells.push(cell);
+    }
+    Ok(build_typed_record(columns, cells))
+}
+
+fn build_typed_record(columns: &[MysqlColumn], cells: Vec<Cell>) -> DataRecord {
+    let mut record = DataRecord::default();
+    for (col, cell) in columns.iter().zip(cells) {
+        if let Some(field) = typed_field(col, cell) {
+            record.append(field);
+        }
+    }
+    record
+}
+
+/// 将单列值转换为对应类型的字段；NULL 返回 None，
+/// 无法按列类型解析的值（如超出 i64 的无符号整数、零值日期）退化为 chars
+fn typed_field(col: &MysqlColumn, cell: Cell) -> Option<DataField> {
+    let name = col.name.as_str();
+    let text = match cell {
+        Cell::Bytes(bytes) => {
+            // 模型中没有字节类型，二进制列以小写十六进制文本输出，保证无损
+            let hex = bytes?.iter().fold(String::new(), |mut out, b| {
+                let _ = write!(out, "{b:02x}");
+                out
+            });
+            return Some(DataField::from_chars(name, hex));
+        }
+        Cell::Text(text) => text?,
+    };
+    let field = match col.kind {
+        ColumnKind::Digit => text.parse().ok().map(|v| DataField::from_digit(name, v)),
+        ColumnKind::Float => text.parse().ok().map(|v| DataField::from_float(name, v)),
+        ColumnKind::Time => parse_mysql_time(&text).map(|v| DataField::from_time(name, v)),
+        ColumnKind::Bytes | ColumnKind::Chars => None,
+    };
+    Some(field.unwrap_or_else(|| DataField::from_chars(name, text)))
+}
+
+fn parse_mysql_time(text: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+}
+
 #[async_trait]
 impl DataSource for MysqlSource {
     async fn receive(&mut self) -> SourceResult<SourceBatch> {
@@ -199,3 +529,223 @@ impl DataSource for MysqlSource {
         self.key.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::time_unit::TimeUnit;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+    use std::collections::BTreeMap;
+    use wp_model_core::model::{DataType, Value};
+
+    /// 基于 MockDatabase 的 Source，每次查询依次返回 `pages` 中的一页
+    fn mock_source(pages: Vec<Vec<&str>>, fetch_mode: MysqlFetchMode) -> MysqlSource {
+        let results = pages.into_iter().map(|page| {
+            page.into_iter()
+                .map(|json| BTreeMap::from([("payload", sea_orm::Value::from(json))]))
+                .collect::<Vec<_>>()
+        });
+        let db = MockDatabase::new(DatabaseBackend::MySql)
+            .append_query_results(results)
+            .into_connection();
+        MysqlSource {
+            key: "mysql_poll_test".into(),
+            db,
+            statement: "SELECT 1 LIMIT 10 OFFSET ?;".into(),
+            mode: MysqlSourceMode::Json,
+            columns: Vec::new(),
+            fetch_mode,
+            eof_as_error: false,
+            exhausted: false,
+            next_poll: None,
+            checkpoint: 0,
+            checkpoint_path: std::env::temp_dir().join("wp_connectors_mysql_poll.dat"),
+            data_cache: VecDeque::new(),
+            tags: Tags::default(),
+            ingest_time: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn poll_mode_returns_empty_batch_and_retries_after_interval() {
+        let interval = Duration::from_millis(50);
+        let mut source = mock_source(
+            vec![vec![], vec![r#"{"id":1}"#]],
+            MysqlFetchMode::Poll(interval),
+        );
+
+        let batch = source
+            .receive()
+            .await
+            .expect("empty result is not an error");
+        assert!(batch.is_empty());
+
+        let started = Instant::now();
+        let batch = source.receive().await.expect("new row");
+        assert!(started.elapsed() >= interval);
+        assert_eq!(batch.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn ingest_time_field_stamps_each_event() {
+        let ingest_time = IngestTime::new("ingest_ts", TimeUnit::Nanos);
+        let mut source = mock_source(
+            vec![vec![r#"{"id":1}"#, r#"{"id":2}"#, r#"{"id":3}"#]],
+            MysqlFetchMode::OneShot,
+        )
+        .with_ingest_time(Some(ingest_time.clone()));
+
+        let before = ingest_time.now();
+        let batch = source.receive().await.expect("rows");
+        let after = ingest_time.now();
+        let stamps: Vec<i64> = batch
+            .iter()
+            .map(|e| {
+                e.tags
+                    .get("ingest_ts")
+                    .expect("ingest tag")
+                    .parse()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(stamps.len(), 3);
+        assert!(stamps.windows(2).all(|w| w[0] <= w[1]), "{stamps:?}");
+        assert!(stamps[0] >= before && stamps[2] <= after, "{stamps:?}");
+    }
+
+    #[tokio::test]
+    async fn advancing_checkpoint_updates_gauge() {
+        let mut source = mock_source(
+            vec![vec![r#"{"id":1}"#, r#"{"id":2}"#]],
+            MysqlFetchMode::OneShot,
+        );
+        source.key = "mysql_checkpoint_gauge_test".into();
+        source.checkpoint = 40;
+        source.checkpoint_path = std::env::temp_dir().join("wp_connectors_mysql_gauge.dat");
+        let gauge = MYSQL_SOURCE_CHECKPOINT.with_label_values(&["mysql_checkpoint_gauge_test"]);
+
+        source.set_checkpoints().expect("checkpoint");
+        assert_eq!(gauge.get(), 41);
+
+        let batch = source.receive().await.expect("rows");
+        assert_eq!(batch.len(), 2);
+        assert_eq!(gauge.get(), 43);
+    }
+
+    #[tokio::test]
+    async fn one_shot_mode_emits_eof_event_exactly_once() {
+        let mut source = mock_source(
+            vec![vec![r#"{"id":1}"#, r#"{"id":2}"#], vec![]],
+            MysqlFetchMode::OneShot,
+        );
+
+        let batch = source.receive().await.expect("rows");
+        assert_eq!(batch.len(), 2);
+        assert!(batch.iter().all(|e| e.tags.get(EOF_TAG).is_none()));
+        assert!(!source.is_exhausted());
+
+        let batch = source.receive().await.expect("eof event is not an error");
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].tags.get(EOF_TAG), Some("true"));
+        assert!(source.is_exhausted());
+
+        let err = source.receive().await.expect_err("drained source");
+        assert!(matches!(err.reason(), SourceReason::EOF));
+        let err = source.receive().await.expect_err("still drained");
+        assert!(matches!(err.reason(), SourceReason::EOF));
+    }
+
+    #[tokio::test]
+    async fn eof_as_error_keeps_legacy_behavior() {
+        let mut source = mock_source(vec![vec![]], MysqlFetchMode::OneShot).with_eof_as_error(true);
+        let err = source.receive().await.expect_err("eof");
+        assert!(matches!(err.reason(), SourceReason::EOF));
+        assert!(!source.is_exhausted());
+    }
+
+    fn column(name: &str, data_type: &str) -> MysqlColumn {
+        MysqlColumn {
+            name: name.into(),
+            kind: ColumnKind::from_data_type(data_type),
+        }
+    }
+
+    fn text(v: &str) -> Cell {
+        Cell::Text(Some(v.into()))
+    }
+
+    #[test]
+    fn typed_record_maps_column_types() {
+        let columns = vec![
+            column("id", "bigint"),
+            column("score", "decimal"),
+            column("created_at", "datetime"),
+            column("day", "date"),
+            column("name", "varchar"),
+            column("raw", "blob"),
+            column("big", "bigint"),
+            column("missing", "int"),
+        ];
+        let cells = vec![
+            text("42"),
+            text("3.50"),
+            text("2024-05-01 12:30:45.123000"),
+            text("2024-05-02"),
+            text("alice"),
+            Cell::Bytes(Some(vec![0x00, 0xff, 0x10])),
+            text("18446744073709551615"),
+            Cell::Text(None),
+        ];
+        let record = build_typed_record(&columns, cells);
+
+        let meta = |name: &str| record.field(name).map(|f| f.get_meta().clone());
+        assert_eq!(meta("id"), Some(DataType::Digit));
+        assert_eq!(meta("score"), Some(DataType::Float));
+        assert_eq!(meta("created_at"), Some(DataType::Time));
+        assert_eq!(meta("day"), Some(DataType::Time));
+        assert_eq!(meta("name"), Some(DataType::Chars));
+        assert_eq!(meta("big"), Some(DataType::Chars));
+        assert!(record.field("missing").is_none());
+
+        assert_eq!(record.field("id").unwrap().get_value(), &Value::Digit(42));
+        assert_eq!(
+            record.field("raw").unwrap().get_value(),
+            &Value::Chars("00ff10".into())
+        );
+    }
+
+    #[test]
+    fn select_statement_depends_on_mode() {
+        let columns = vec![column("id", "int"), column("raw", "varbinary")];
+        let json = build_select_statement("t", &columns, MysqlSourceMode::Json, 10);
+        assert!(json.contains("JSON_OBJECT('id', `id`, 'raw', TO_BASE64(`raw`))"));
+
+        let typed = build_select_statement("t", &columns, MysqlSourceMode::Typed, 10);
+        assert_eq!(
+            typed,
+            "SELECT CAST(`id` AS CHAR CHARACTER SET utf8mb4), `raw` FROM `t` LIMIT 10 OFFSET ?;"
+        );
+    }
+
+    #[test]
+    fn custom_query_is_used_verbatim_as_statement_base() {
+        let query = validate_query(
+            "select JSON_OBJECT('id', o.id, 'user', u.name) FROM orders o JOIN users u ON o.uid = u.id",
+        )
+        .expect("valid query");
+        assert_eq!(
+            build_query_statement(&query, 50),
+            "select JSON_OBJECT('id', o.id, 'user', u.name) FROM orders o JOIN users u ON o.uid = u.id LIMIT 50 OFFSET ?;"
+        );
+    }
+
+    #[test]
+    fn source_mode_parses_known_values() {
+        assert_eq!(
+            MysqlSourceMode::parse("Typed"),
+            Some(MysqlSourceMode::Typed)
+        );
+        assert_eq!(MysqlSourceMode::parse("json"), Some(MysqlSourceMode::Json));
+        assert_eq!(MysqlSourceMode::parse("blob"), None);
+    }
+}
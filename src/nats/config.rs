@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_SERVERS: &str = "nats://127.0.0.1:4222";
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// NATS Sink 的配置结构
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NatsSinkConfig {
+    /// NATS 服务地址列表（例如：`nats://127.0.0.1:4222`）
+    pub servers: Vec<String>,
+    /// 发布的 subject，支持 `{field}` 占位符引用记录字段
+    pub subject: String,
+    /// Token 认证（可选）
+    pub token: Option<String>,
+    /// 用户名认证（可选，需与 password 同时提供）
+    pub username: Option<String>,
+    /// 密码认证（可选）
+    pub password: Option<String>,
+    /// 连接超时时间（秒）
+    pub connect_timeout_secs: u64,
+}
+
+impl NatsSinkConfig {
+    /// 构建配置，应用默认值
+    ///
+    /// # Arguments
+    /// * `servers` - 逗号分隔的 NATS 服务地址
+    /// * `subject` - 发布的 subject（可包含 `{field}` 占位符）
+    /// * `token` - 可选的 token 认证
+    /// * `username` - 可选的用户名
+    /// * `password` - 可选的密码
+    /// * `connect_timeout_secs` - 可选的连接超时时间（默认：10秒）
+    pub fn new(
+        servers: &str,
+        subject: String,
+        token: Option<String>,
+        username: Option<String>,
+        password: Option<String>,
+        connect_timeout_secs: Option<u64>,
+    ) -> Self {
+        Self {
+            servers: split_servers(servers),
+            subject: subject.trim().to_string(),
+            token,
+            username,
+            password,
+            connect_timeout_secs: connect_timeout_secs
+                .unwrap_or(Self::default_connect_timeout_secs()),
+        }
+    }
+
+    pub fn default_servers() -> &'static str {
+        DEFAULT_SERVERS
+    }
+
+    pub fn default_connect_timeout_secs() -> u64 {
+        DEFAULT_CONNECT_TIMEOUT_SECS
+    }
+}
+
+/// 拆分逗号分隔的服务地址并去除空项
+pub(crate) fn split_servers(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_splits_servers_and_applies_defaults() {
+        let cfg = NatsSinkConfig::new(
+            " nats://a:4222, ,nats://b:4222 ",
+            " events.{tenant} ".into(),
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(cfg.servers, vec!["nats://a:4222", "nats://b:4222"]);
+        assert_eq!(cfg.subject, "events.{tenant}");
+        assert_eq!(
+            cfg.connect_timeout_secs,
+            NatsSinkConfig::default_connect_timeout_secs()
+        );
+    }
+}
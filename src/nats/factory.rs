@@ -0,0 +1,222 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError, SinkFactory,
+    SinkHandle, SinkReason, SinkResult, SinkSpec,
+};
+use wp_model_core::model::fmt_def::TextFmt;
+
+use crate::nats::{NatsSink, NatsSinkConfig};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::fmt::parse_text_fmt;
+use crate::utils::template::FieldTemplate;
+
+/// NATS Sink 工厂，负责验证配置和构建 Sink 实例
+pub struct NatsSinkFactory;
+
+fn build_nats_sink_conf_from_spec(spec: &SinkSpec) -> SinkResult<(NatsSinkConfig, TextFmt)> {
+    let servers = required_param(spec, "servers")?;
+    let subject = required_param(spec, "subject")?;
+    FieldTemplate::parse(&subject)
+        .map_err(|e| SinkReason::sink(format!("nats.subject is invalid: {e}")))?;
+    if subject.contains(['*', '>', ' ']) {
+        return Err(SinkReason::sink("nats.subject must not contain wildcards or spaces").into());
+    }
+
+    let token = optional_string(spec, "token");
+    let username = optional_string(spec, "username");
+    let password = optional_string(spec, "password");
+    if username.is_some() != password.is_some() {
+        return Err(
+            SinkReason::sink("nats.username and nats.password must be set together").into(),
+        );
+    }
+    if token.is_some() && username.is_some() {
+        return Err(SinkReason::sink("nats.token and nats.username are mutually exclusive").into());
+    }
+
+    let connect_timeout_secs = match spec.params.get("connect_timeout_secs") {
+        None => None,
+        Some(v) => match v.as_u64() {
+            Some(0) | None => {
+                return Err(SinkReason::sink(
+                    "nats.connect_timeout_secs must be a positive integer",
+                )
+                .into());
+            }
+            Some(n) => Some(n),
+        },
+    };
+    let fmt = parse_text_fmt("nats", spec.params.get("fmt"))?;
+
+    let conf = NatsSinkConfig::new(
+        &servers,
+        subject,
+        token,
+        username,
+        password,
+        connect_timeout_secs,
+    );
+    if conf.servers.is_empty() {
+        return Err(SinkReason::sink("nats.servers must not be empty").into());
+    }
+    Ok((conf, fmt))
+}
+
+#[async_trait]
+impl SinkFactory for NatsSinkFactory {
+    fn kind(&self) -> &'static str {
+        "nats"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        build_nats_sink_conf_from_spec(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let (conf, fmt) = build_nats_sink_conf_from_spec(spec)?;
+        let sink = NatsSink::from_conf(&conf, fmt).await.map_err(|err| {
+            SinkError::from(SinkReason::sink(format!("init nats sink failed: {err}")))
+        })?;
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+impl SinkDefProvider for NatsSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "nats_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "servers",
+                "subject",
+                "fmt",
+                "token",
+                "username",
+                "password",
+                "connect_timeout_secs",
+            ]
+            .into_iter()
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect(),
+            default_params: nats_defaults(),
+            origin: Some("wp-connectors:nats_sink".into()),
+        }
+    }
+}
+
+/// 读取必填参数并返回修剪后的字符串
+fn required_param(spec: &SinkSpec, key: &str) -> SinkResult<String> {
+    spec.params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| SinkReason::sink(format!("nats.{key} must not be empty")).into())
+}
+
+/// 读取可选字符串参数
+fn optional_string(spec: &SinkSpec, key: &str) -> Option<String> {
+    spec.params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn nats_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("servers".into(), json!(NatsSinkConfig::default_servers()));
+    params.insert("subject".into(), json!("wp.events"));
+    params.insert("fmt".into(), json!("json"));
+    params.insert(
+        "connect_timeout_secs".into(),
+        json!(NatsSinkConfig::default_connect_timeout_secs()),
+    );
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn base_spec() -> SinkSpec {
+        let mut params = BTreeMap::new();
+        params.insert("servers".into(), json!("nats://127.0.0.1:4222"));
+        params.insert("subject".into(), json!("events.{tenant}"));
+        SinkSpec {
+            name: "nats_sink".into(),
+            kind: "nats".into(),
+            connector_id: String::new(),
+            group: "test".into(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_minimal_spec() {
+        let (conf, fmt) = build_nats_sink_conf_from_spec(&base_spec()).expect("valid spec");
+        assert_eq!(conf.servers, vec!["nats://127.0.0.1:4222"]);
+        assert_eq!(conf.subject, "events.{tenant}");
+        assert_eq!(fmt, TextFmt::Json);
+    }
+
+    #[test]
+    fn validate_rejects_empty_servers_and_subject() {
+        for key in ["servers", "subject"] {
+            let mut spec = base_spec();
+            spec.params.insert(key.into(), json!(" "));
+            let err = NatsSinkFactory.validate_spec(&spec).expect_err(key);
+            assert!(format!("{err}").contains(&format!("nats.{key}")));
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_subject_and_auth() {
+        let mut spec = base_spec();
+        spec.params.insert("subject".into(), json!("events.>"));
+        assert!(NatsSinkFactory.validate_spec(&spec).is_err());
+
+        let mut spec = base_spec();
+        spec.params
+            .insert("subject".into(), json!("events.{tenant"));
+        assert!(NatsSinkFactory.validate_spec(&spec).is_err());
+
+        let mut spec = base_spec();
+        spec.params.insert("username".into(), json!("user"));
+        assert!(NatsSinkFactory.validate_spec(&spec).is_err());
+
+        let mut spec = base_spec();
+        spec.params.insert("fmt".into(), json!("bogus"));
+        assert!(NatsSinkFactory.validate_spec(&spec).is_err());
+    }
+
+    #[test]
+    fn sink_def_defaults_validate() {
+        let def = NatsSinkFactory.sink_def();
+        assert_eq!(def.id, "nats_sink");
+        let mut spec = base_spec();
+        spec.params = def.default_params;
+        assert!(NatsSinkFactory.validate_spec(&spec).is_ok());
+    }
+
+    #[tokio::test]
+    async fn build_fails_cleanly_when_server_unreachable() {
+        let mut spec = base_spec();
+        spec.params
+            .insert("servers".into(), json!("nats://127.0.0.1:1"));
+        spec.params.insert("connect_timeout_secs".into(), json!(1));
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+        let err = NatsSinkFactory
+            .build(&spec, &ctx)
+            .await
+            .expect_err("unreachable server");
+        assert!(format!("{err}").contains("init nats sink failed"));
+    }
+}
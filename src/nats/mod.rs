@@ -0,0 +1,21 @@
+//! NATS sink implementation for wp-connectors
+//!
+//! 将记录按 `fmt` 格式化后发布到 NATS subject。
+//!
+//! # 配置参数
+//!
+//! - `servers`: NATS 服务地址，逗号分隔（必填）
+//! - `subject`: 发布的 subject（必填），支持 `{field}` 占位符，
+//!   例如 `events.{tenant}`；字段缺失时替换为 `_`
+//! - `fmt`: 输出格式，默认 json
+//! - `token`: Token 认证（可选）
+//! - `username` / `password`: 用户名密码认证（可选，需同时提供）
+//! - `connect_timeout_secs`: 连接超时时间，默认 10 秒
+
+mod config;
+mod factory;
+mod sink;
+
+pub use config::NatsSinkConfig;
+pub use factory::NatsSinkFactory;
+pub use sink::NatsSink;
@@ -0,0 +1,244 @@
+//! NATS Sink 实现
+//!
+//! 记录按 `fmt` 格式化后发布到配置的 subject；subject 可通过 `{field}`
+//! 占位符引用记录字段。批量写入时先逐条发布到客户端缓冲区，最后统一 flush。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_nats::{Client, ConnectOptions};
+use async_trait::async_trait;
+use bytes::Bytes;
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
+use wp_data_fmt::{FormatType, RecordFormatter};
+use wp_model_core::model::{DataRecord, fmt_def::TextFmt};
+
+use crate::nats::config::NatsSinkConfig;
+use crate::utils::retry::{RetryPolicy, reconnect_with_backoff};
+use crate::utils::template::FieldTemplate;
+
+type AnyResult<T> = anyhow::Result<T>;
+
+pub struct NatsSink {
+    client: Client,
+    conf: NatsSinkConfig,
+    subject: FieldTemplate,
+    fmt: TextFmt,
+}
+
+impl NatsSink {
+    /// 连接 NATS 并构建 Sink
+    pub async fn from_conf(conf: &NatsSinkConfig, fmt: TextFmt) -> AnyResult<Self> {
+        let subject = FieldTemplate::parse(&conf.subject).map_err(|e| anyhow::anyhow!(e))?;
+        let client = connect(conf).await?;
+        Ok(Self {
+            client,
+            conf: conf.clone(),
+            subject,
+            fmt,
+        })
+    }
+
+    /// 根据记录解析实际发布的 subject
+    fn resolve_subject(&self, record: Option<&DataRecord>) -> String {
+        self.subject.render_with(record, sanitize_subject_token)
+    }
+
+    async fn publish(&self, subject: String, payload: Bytes) -> SinkResult<()> {
+        self.client
+            .publish(subject, payload)
+            .await
+            .map_err(|e| sink_error(format!("nats publish fail: {e}")))
+    }
+
+    async fn flush(&self) -> SinkResult<()> {
+        self.client
+            .flush()
+            .await
+            .map_err(|e| sink_error(format!("nats flush fail: {e}")))
+    }
+
+    async fn publish_record(&self, data: &DataRecord) -> SinkResult<()> {
+        let fmt = FormatType::from(&self.fmt);
+        let payload = fmt.fmt_record(data);
+        self.publish(self.resolve_subject(Some(data)), Bytes::from(payload))
+            .await
+    }
+}
+
+async fn connect(conf: &NatsSinkConfig) -> AnyResult<Client> {
+    let mut opts = ConnectOptions::new()
+        .connection_timeout(Duration::from_secs(conf.connect_timeout_secs))
+        .name("wp-connectors");
+    if let Some(token) = &conf.token {
+        opts = opts.token(token.clone());
+    }
+    if let (Some(user), Some(pass)) = (&conf.username, &conf.password) {
+        opts = opts.user_and_password(user.clone(), pass.clone());
+    }
+    Ok(opts.connect(conf.servers.as_slice()).await?)
+}
+
+/// 将字段值转换为合法的 subject token：空白、`.`、通配符 `*`/`>` 替换为 `_`
+fn sanitize_subject_token(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '.' | '*' | '>' => '_',
+            c if c.is_whitespace() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[async_trait]
+impl AsyncCtrl for NatsSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.flush().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        let conf = &self.conf;
+        self.client = reconnect_with_backoff("nats", RetryPolicy::reconnect(), || async {
+            connect(conf)
+                .await
+                .map_err(|e| sink_error(format!("nats reconnect fail: {e}")))
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for NatsSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.publish_record(data).await?;
+        self.flush().await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        for record in &data {
+            self.publish_record(record.as_ref()).await?;
+        }
+        self.flush().await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for NatsSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.sink_bytes(data.as_bytes()).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.publish(self.resolve_subject(None), Bytes::copy_from_slice(data))
+            .await?;
+        self.flush().await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        for item in data {
+            self.publish(
+                self.resolve_subject(None),
+                Bytes::copy_from_slice(item.as_bytes()),
+            )
+            .await?;
+        }
+        self.flush().await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        for item in data {
+            self.publish(self.resolve_subject(None), Bytes::copy_from_slice(item))
+                .await?;
+        }
+        self.flush().await
+    }
+}
+
+/// 统一封装 sink 层错误
+fn sink_error(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use wp_model_core::model::DataField;
+
+    /// 极简 NATS 服务端：完成握手、响应 PING，并把收到的 PUB 转发到 channel
+    async fn start_mock_server() -> (String, mpsc::UnboundedReceiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read, mut write) = stream.into_split();
+            let info = format!(
+                "INFO {{\"server_id\":\"mock\",\"version\":\"2.10.0\",\"go\":\"go\",\"host\":\"127.0.0.1\",\"port\":{},\"headers\":true,\"max_payload\":1048576,\"proto\":1}}\r\n",
+                addr.port()
+            );
+            write.write_all(info.as_bytes()).await.unwrap();
+            let mut reader = BufReader::new(read);
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                    break;
+                }
+                let cmd = line.trim_end();
+                if cmd.starts_with("PING") {
+                    write.write_all(b"PONG\r\n").await.unwrap();
+                } else if let Some(rest) = cmd.strip_prefix("PUB ") {
+                    let parts: Vec<&str> = rest.split_whitespace().collect();
+                    let size: usize = parts.last().unwrap().parse().unwrap();
+                    let mut payload = vec![0u8; size + 2];
+                    reader.read_exact(&mut payload).await.unwrap();
+                    payload.truncate(size);
+                    let _ = tx.send((parts[0].to_string(), String::from_utf8(payload).unwrap()));
+                }
+            }
+        });
+        (format!("nats://{addr}"), rx)
+    }
+
+    #[test]
+    fn sanitize_subject_token_replaces_reserved_chars() {
+        assert_eq!(sanitize_subject_token("a.b c*>"), "a_b_c__");
+        assert_eq!(sanitize_subject_token("tenant-1"), "tenant-1");
+    }
+
+    #[tokio::test]
+    async fn sink_records_publish_to_templated_subject() {
+        let (url, mut rx) = start_mock_server().await;
+        let conf = NatsSinkConfig::new(&url, "events.{tenant}".into(), None, None, None, Some(2));
+        let mut sink = NatsSink::from_conf(&conf, TextFmt::Json)
+            .await
+            .expect("connect mock nats");
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("tenant", "acme"));
+        record.append(DataField::from_digit("id", 7));
+        sink.sink_records(vec![Arc::new(record)])
+            .await
+            .expect("publish records");
+        sink.sink_str("raw-line").await.expect("publish raw");
+
+        let (subject, payload) = rx.recv().await.expect("record message");
+        assert_eq!(subject, "events.acme");
+        assert!(payload.contains("\"tenant\":\"acme\""));
+        let (subject, payload) = rx.recv().await.expect("raw message");
+        assert_eq!(subject, "events._");
+        assert_eq!(payload, "raw-line");
+
+        sink.stop().await.expect("stop flushes");
+    }
+}
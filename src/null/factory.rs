@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use std::sync::atomic::Ordering;
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkFactory, SinkHandle,
+    SinkResult, SinkSpec,
+};
+
+use crate::null::{NullCounter, NullSink};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+
+/// Null Sink 工厂；同一工厂构建的所有 Sink 共享一个计数器
+#[derive(Default, Clone)]
+pub struct NullSinkFactory {
+    counter: NullCounter,
+}
+
+impl NullSinkFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回共享计数器句柄
+    pub fn counter(&self) -> NullCounter {
+        self.counter.clone()
+    }
+
+    /// 返回所有 Sink 累计接收的条数
+    pub fn received(&self) -> u64 {
+        self.counter.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl SinkFactory for NullSinkFactory {
+    fn kind(&self) -> &'static str {
+        "null"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let sink = NullSink::with_counter(self.counter());
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+impl SinkDefProvider for NullSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "null_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: DECORATOR_PARAMS.into_iter().map(str::to_string).collect(),
+            default_params: ParamMap::new(),
+            origin: Some("wp-connectors:null_sink".into()),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Null sink implementation for wp-connectors
+//!
+//! 该模块用于压测：丢弃全部写入，仅累计接收条数，以隔离上游 source/parse 吞吐
+
+mod factory;
+mod sink;
+
+pub use factory::NullSinkFactory;
+pub use sink::{NullCounter, NullSink};
@@ -0,0 +1,126 @@
+//! Null Sink 实现 - 丢弃数据，仅计数
+//!
+//! - 每条记录、每段原始文本/字节计为 1，累计到共享计数器 [`NullCounter`]；
+//! - 通过 [`NullSink::received`] 读取当前计数；
+//! - `stop` 不重置计数，停止后仍可读取。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkResult};
+use wp_model_core::model::DataRecord;
+
+use crate::utils::shutdown::DeadlineCtrl;
+
+/// 多个 Sink 实例与调用方共享的接收计数器
+pub type NullCounter = Arc<AtomicU64>;
+
+#[derive(Default)]
+pub struct NullSink {
+    counter: NullCounter,
+}
+
+impl NullSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用外部提供的计数器（例如由工厂共享）
+    pub fn with_counter(counter: NullCounter) -> Self {
+        Self { counter }
+    }
+
+    /// 返回计数器句柄
+    pub fn counter(&self) -> NullCounter {
+        self.counter.clone()
+    }
+
+    /// 返回累计接收条数
+    pub fn received(&self) -> u64 {
+        self.counter.load(Ordering::Relaxed)
+    }
+
+    fn add(&self, n: usize) {
+        self.counter.fetch_add(n as u64, Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for NullSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+// 不缓冲任何数据，停止时没有待排空的内容
+impl DeadlineCtrl for NullSink {}
+
+#[async_trait]
+impl AsyncRecordSink for NullSink {
+    async fn sink_record(&mut self, _data: &DataRecord) -> SinkResult<()> {
+        self.add(1);
+        Ok(())
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.add(data.len());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for NullSink {
+    async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+        self.add(1);
+        Ok(())
+    }
+
+    async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+        self.add(1);
+        Ok(())
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.add(data.len());
+        Ok(())
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.add(data.len());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn counts_records_and_raw_payloads() {
+        let mut sink = NullSink::new();
+        let records = (0..5).map(|_| Arc::new(DataRecord::default())).collect();
+        sink.sink_records(records).await.expect("records");
+        sink.sink_record(&DataRecord::default())
+            .await
+            .expect("record");
+        assert_eq!(sink.received(), 6);
+
+        sink.sink_str("line").await.expect("str");
+        sink.sink_bytes(b"bytes").await.expect("bytes");
+        sink.sink_str_batch(vec!["a", "b"])
+            .await
+            .expect("str batch");
+        sink.sink_bytes_batch(vec![b"c", b"d", b"e"])
+            .await
+            .expect("bytes batch");
+        assert_eq!(sink.received(), 13);
+
+        sink.stop().await.expect("stop");
+        assert_eq!(sink.received(), 13);
+    }
+}
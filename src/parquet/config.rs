@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_FILE_PREFIX: &str = "wp";
+const DEFAULT_ROW_GROUP_ROWS: usize = 10_000;
+const DEFAULT_MAX_FILE_BYTES: u64 = 128 * 1024 * 1024;
+
+/// Parquet 列的值类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParquetColumnType {
+    String,
+    Int64,
+    Double,
+    Bool,
+}
+
+impl ParquetColumnType {
+    /// 解析列类型名，仅接受 `string` / `int64` / `double` / `bool`
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "string" => Some(Self::String),
+            "int64" => Some(Self::Int64),
+            "double" => Some(Self::Double),
+            "bool" => Some(Self::Bool),
+            _ => None,
+        }
+    }
+}
+
+/// 一列的定义：字段名与值类型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParquetColumn {
+    pub name: String,
+    pub kind: ParquetColumnType,
+}
+
+impl ParquetColumn {
+    pub fn new(name: impl Into<String>, kind: ParquetColumnType) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+        }
+    }
+
+    /// 解析 `name` 或 `name:type` 形式的列定义，未写类型时为 `string`
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (name, kind) = match raw.split_once(':') {
+            Some((name, kind)) => (name.trim(), ParquetColumnType::parse(kind)?),
+            None => (raw.trim(), ParquetColumnType::String),
+        };
+        if name.is_empty() {
+            return None;
+        }
+        Some(Self::new(name, kind))
+    }
+}
+
+/// Parquet Sink 的配置结构
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParquetSinkConfig {
+    /// 输出目录，不存在时自动创建
+    pub directory: String,
+    /// 文件名前缀，文件名形如 `{prefix}-{YYYYmmdd-HHMMSS}-{seq}.parquet`
+    pub file_prefix: String,
+    /// 每个行组的行数，缓冲达到该值时写出
+    pub row_group_rows: usize,
+    /// 单个文件的字节数上限，达到后滚动到新文件
+    pub max_file_bytes: u64,
+    /// 显式列定义；为空时由首条记录推断
+    #[serde(default)]
+    pub columns: Option<Vec<ParquetColumn>>,
+}
+
+impl ParquetSinkConfig {
+    pub fn new(directory: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_prefix: DEFAULT_FILE_PREFIX.to_string(),
+            row_group_rows: DEFAULT_ROW_GROUP_ROWS,
+            max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+            columns: None,
+        }
+    }
+
+    pub fn with_file_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.file_prefix = prefix.into();
+        self
+    }
+
+    pub fn with_row_group_rows(mut self, rows: usize) -> Self {
+        self.row_group_rows = rows.max(1);
+        self
+    }
+
+    pub fn with_max_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_bytes = bytes.max(1);
+        self
+    }
+
+    pub fn with_columns(mut self, columns: Option<Vec<ParquetColumn>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    pub fn default_row_group_rows() -> usize {
+        DEFAULT_ROW_GROUP_ROWS
+    }
+
+    pub fn default_max_file_bytes() -> u64 {
+        DEFAULT_MAX_FILE_BYTES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_parse_accepts_optional_type() {
+        assert_eq!(
+            ParquetColumn::parse("src_ip"),
+            Some(ParquetColumn::new("src_ip", ParquetColumnType::String))
+        );
+        assert_eq!(
+            ParquetColumn::parse("bytes : int64"),
+            Some(ParquetColumn::new("bytes", ParquetColumnType::Int64))
+        );
+        assert_eq!(ParquetColumn::parse("bytes:decimal"), None);
+        assert_eq!(ParquetColumn::parse(":bool"), None);
+    }
+}
@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkFactory, SinkHandle,
+    SinkReason, SinkResult, SinkSpec,
+};
+
+use crate::parquet::{ParquetColumn, ParquetSink, ParquetSinkConfig};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+
+pub struct ParquetSinkFactory;
+
+#[async_trait]
+impl SinkFactory for ParquetSinkFactory {
+    fn kind(&self) -> &'static str {
+        "parquet"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        build_config(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let sink = ParquetSink::new(build_config(spec)?);
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+fn build_config(spec: &SinkSpec) -> SinkResult<ParquetSinkConfig> {
+    let directory = spec
+        .params
+        .get("directory")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .unwrap_or("");
+    if directory.is_empty() {
+        return Err(SinkReason::sink("parquet.directory must not be empty").into());
+    }
+    let mut config = ParquetSinkConfig::new(directory);
+    if let Some(v) = spec.params.get("file_prefix") {
+        match v.as_str().map(str::trim) {
+            Some(prefix) if !prefix.is_empty() => config = config.with_file_prefix(prefix),
+            _ => {
+                return Err(
+                    SinkReason::sink("parquet.file_prefix must be a non-empty string").into(),
+                );
+            }
+        }
+    }
+    if let Some(rows) = parse_positive(spec, "row_group_rows")? {
+        config = config.with_row_group_rows(rows as usize);
+    }
+    if let Some(bytes) = parse_positive(spec, "max_file_bytes")? {
+        config = config.with_max_file_bytes(bytes);
+    }
+    Ok(config.with_columns(parse_columns(spec.params.get("columns"))?))
+}
+
+fn parse_positive(spec: &SinkSpec, key: &str) -> SinkResult<Option<u64>> {
+    match spec.params.get(key) {
+        None => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Ok(Some(n)),
+            _ => Err(SinkReason::sink(format!("parquet.{key} must be a positive integer")).into()),
+        },
+    }
+}
+
+/// 解析显式列定义：`["name", "name:int64", ...]`，未配置或为空时由首条记录推断
+fn parse_columns(value: Option<&Value>) -> SinkResult<Option<Vec<ParquetColumn>>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let Some(items) = value.as_array() else {
+        return Err(SinkReason::sink("parquet.columns must be an array").into());
+    };
+    let mut columns = Vec::with_capacity(items.len());
+    for item in items {
+        let column = item.as_str().and_then(ParquetColumn::parse).ok_or_else(|| {
+            SinkReason::sink(format!(
+                "invalid parquet.columns entry {item}: expected 'name' or 'name:string|int64|double|bool'"
+            ))
+        })?;
+        columns.push(column);
+    }
+    Ok((!columns.is_empty()).then_some(columns))
+}
+
+impl SinkDefProvider for ParquetSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "parquet_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "directory",
+                "file_prefix",
+                "row_group_rows",
+                "max_file_bytes",
+                "columns",
+            ]
+            .into_iter()
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect(),
+            default_params: parquet_defaults(),
+            origin: Some("wp-connectors:parquet_sink".into()),
+        }
+    }
+}
+
+fn parquet_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("directory".into(), json!("./data/parquet"));
+    params.insert("file_prefix".into(), json!("wp"));
+    params.insert(
+        "row_group_rows".into(),
+        json!(ParquetSinkConfig::default_row_group_rows()),
+    );
+    params.insert(
+        "max_file_bytes".into(),
+        json!(ParquetSinkConfig::default_max_file_bytes()),
+    );
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parquet::ParquetColumnType;
+
+    fn spec(params: ParamMap) -> SinkSpec {
+        SinkSpec {
+            name: "archive".into(),
+            kind: "parquet".into(),
+            connector_id: String::new(),
+            group: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn build_config_parses_columns_and_rejects_bad_params() {
+        let mut params = parquet_defaults();
+        params.insert("columns".into(), json!(["host", "bytes:int64"]));
+        let config = build_config(&spec(params.clone())).expect("valid spec");
+        assert_eq!(
+            config.columns,
+            Some(vec![
+                ParquetColumn::new("host", ParquetColumnType::String),
+                ParquetColumn::new("bytes", ParquetColumnType::Int64),
+            ])
+        );
+
+        for (key, bad, expect) in [
+            ("directory", json!(""), "parquet.directory"),
+            ("row_group_rows", json!(0), "parquet.row_group_rows"),
+            ("columns", json!(["bytes:decimal"]), "parquet.columns"),
+        ] {
+            let mut params = params.clone();
+            params.insert(key.into(), bad);
+            let err = ParquetSinkFactory
+                .validate_spec(&spec(params))
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+}
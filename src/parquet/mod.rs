@@ -0,0 +1,27 @@
+//! Parquet sink implementation for wp-connectors
+//!
+//! 该模块用于冷数据归档：记录在内存中按行组缓冲，写入按日期命名的 `.parquet` 文件。
+//!
+//! - 列定义可显式配置（`columns = ["src_ip", "bytes:int64"]`），否则由首批首条记录推断；
+//! - 缓冲达到 `row_group_rows` 时写出一个行组，文件达到 `max_file_bytes` 或跨天时滚动；
+//! - 写入中的文件以 `.parquet.inprogress` 结尾，关闭后重命名，避免下游读到不完整文件。
+//!
+//! # Example
+//! ```no_run
+//! use wp_connectors::parquet::{ParquetSink, ParquetSinkConfig};
+//!
+//! let config = ParquetSinkConfig::new("/data/archive").with_row_group_rows(50_000);
+//! let sink = ParquetSink::new(config);
+//! ```
+
+pub mod config;
+mod factory;
+mod sink;
+
+pub use config::{ParquetColumn, ParquetColumnType, ParquetSinkConfig};
+pub use factory::ParquetSinkFactory;
+pub use sink::ParquetSink;
+#[cfg(feature = "s3")]
+pub(crate) use sink::encode_parquet;
+#[cfg(feature = "iceberg")]
+pub(crate) use sink::encode_parquet_with_ids;
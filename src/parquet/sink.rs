@@ -0,0 +1,496 @@
+use async_trait::async_trait;
+use chrono::{Local, NaiveDate};
+use parquet::basic::{Compression, LogicalType, Repetition, Type as PhysicalType};
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::{SerializedColumnWriter, SerializedFileWriter};
+use parquet::schema::types::Type;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
+use wp_model_core::model::{DataRecord, DataType, Value};
+
+use super::config::{ParquetColumn, ParquetColumnType, ParquetSinkConfig};
+use crate::utils::shutdown::DeadlineCtrl;
+
+// 进程内文件序号，避免同一秒内打开的文件重名
+static FILE_SEQ: AtomicU64 = AtomicU64::new(0);
+
+const IN_PROGRESS_SUFFIX: &str = ".inprogress";
+
+/// 正在写入的文件
+struct OpenFile {
+    writer: SerializedFileWriter<File>,
+    path: PathBuf, // 关闭后的最终路径
+    date: NaiveDate,
+}
+
+impl OpenFile {
+    fn in_progress_path(&self) -> PathBuf {
+        in_progress(&self.path)
+    }
+}
+
+/// Parquet Sink 实现，按行组缓冲记录并写入按日期命名的 Parquet 文件
+pub struct ParquetSink {
+    config: ParquetSinkConfig,
+    columns: Option<Vec<ParquetColumn>>, // 显式配置或由首条记录推断
+    buffer: Vec<Arc<DataRecord>>,
+    file: Option<OpenFile>,
+    stopped: bool,
+}
+
+impl ParquetSink {
+    pub fn new(config: ParquetSinkConfig) -> Self {
+        let columns = config.columns.clone().filter(|cols| !cols.is_empty());
+        Self {
+            config,
+            columns,
+            buffer: Vec::new(),
+            file: None,
+            stopped: false,
+        }
+    }
+
+    /// 当前使用的列定义；尚未写入任何记录且未显式配置时为 `None`
+    pub fn columns(&self) -> Option<&[ParquetColumn]> {
+        self.columns.as_deref()
+    }
+
+    /// 将缓冲区写出为一个行组，必要时滚动文件
+    fn flush_row_group(&mut self) -> SinkResult<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let columns = match &self.columns {
+            Some(columns) => columns.clone(),
+            None => {
+                let inferred = infer_columns(&self.buffer[0]);
+                if inferred.is_empty() {
+                    return Err(sink_error("cannot infer parquet columns from empty record"));
+                }
+                self.columns = Some(inferred.clone());
+                inferred
+            }
+        };
+
+        let today = Local::now().date_naive();
+        if self.file.as_ref().is_some_and(|file| file.date != today) {
+            self.close_file()?;
+        }
+        if self.file.is_none() {
+            self.file = Some(self.open_file(&columns, today)?);
+        }
+        let file = self.file.as_mut().expect("parquet file opened");
+        write_row_group(&mut file.writer, &columns, &self.buffer)?;
+        self.buffer.clear();
+
+        if file.writer.bytes_written() as u64 >= self.config.max_file_bytes {
+            self.close_file()?;
+        }
+        Ok(())
+    }
+
+    fn open_file(&self, columns: &[ParquetColumn], date: NaiveDate) -> SinkResult<OpenFile> {
+        let dir = Path::new(&self.config.directory);
+        std::fs::create_dir_all(dir).map_err(|e| {
+            sink_error(format!(
+                "create directory '{}' failed: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+        let seq = FILE_SEQ.fetch_add(1, Ordering::SeqCst);
+        let name = format!(
+            "{}-{}-{:04}.parquet",
+            self.config.file_prefix,
+            Local::now().format("%Y%m%d-%H%M%S"),
+            seq
+        );
+        let path = dir.join(name);
+        let file = File::create(in_progress(&path))
+            .map_err(|e| sink_error(format!("create '{}' failed: {}", path.display(), e)))?;
+        let writer = new_writer(file, columns)?;
+        Ok(OpenFile { writer, path, date })
+    }
+
+    /// 写入文件尾并将 `.inprogress` 文件重命名为最终文件名
+    fn close_file(&mut self) -> SinkResult<()> {
+        let Some(file) = self.file.take() else {
+            return Ok(());
+        };
+        let tmp = file.in_progress_path();
+        file.writer
+            .close()
+            .map_err(|e| sink_error(format!("close parquet file failed: {}", e)))?;
+        std::fs::rename(&tmp, &file.path)
+            .map_err(|e| sink_error(format!("rename '{}' failed: {}", file.path.display(), e)))
+    }
+
+    fn ensure_running(&self) -> SinkResult<()> {
+        if self.stopped {
+            return Err(sink_error("parquet sink is stopped"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for ParquetSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        if self.stopped {
+            return Ok(());
+        }
+        self.stopped = true;
+        self.flush_row_group()?;
+        self.close_file()
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+// 默认的 `stop_with_deadline` 即可：`stop` 会写出剩余缓冲并关闭文件
+impl DeadlineCtrl for ParquetSink {}
+
+#[async_trait]
+impl AsyncRecordSink for ParquetSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.sink_records(vec![Arc::new(data.clone())]).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.ensure_running()?;
+        for record in data {
+            self.buffer.push(record);
+            if self.buffer.len() >= self.config.row_group_rows {
+                self.flush_row_group()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for ParquetSink {
+    async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+        Err(sink_error("parquet sink does not accept raw input"))
+    }
+
+    async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+        Err(sink_error("parquet sink does not accept raw bytes"))
+    }
+
+    async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+        Err(sink_error("parquet sink does not accept raw input"))
+    }
+
+    async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+        Err(sink_error("parquet sink does not accept raw bytes"))
+    }
+}
+
+fn sink_error(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+/// 将一批记录编码为完整的内存 Parquet 文件（单个行组），供对象存储等非文件 Sink 使用；
+/// 未给出列定义时由首条记录推断
+#[cfg_attr(not(feature = "s3"), allow(dead_code))]
+pub(crate) fn encode_parquet(
+    records: &[Arc<DataRecord>],
+    columns: Option<&[ParquetColumn]>,
+) -> SinkResult<Vec<u8>> {
+    let inferred;
+    let columns = match columns.filter(|cols| !cols.is_empty()) {
+        Some(columns) => columns,
+        None => {
+            inferred = records
+                .first()
+                .map(|r| infer_columns(r))
+                .unwrap_or_default();
+            if inferred.is_empty() {
+                return Err(sink_error("cannot infer parquet columns from empty record"));
+            }
+            &inferred
+        }
+    };
+    let mut writer = new_writer(Vec::new(), columns)?;
+    write_row_group(&mut writer, columns, records)?;
+    writer
+        .into_inner()
+        .map_err(|e| sink_error(format!("close parquet file failed: {}", e)))
+}
+
+/// 按给定列与 field id 编码一个完整的内存 Parquet 文件（单个行组），供 Iceberg 数据文件使用
+#[cfg(feature = "iceberg")]
+pub(crate) fn encode_parquet_with_ids(
+    records: &[Arc<DataRecord>],
+    columns: &[ParquetColumn],
+    field_ids: &[i32],
+) -> SinkResult<Vec<u8>> {
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let schema = build_schema(columns, Some(field_ids))?;
+    let mut writer = SerializedFileWriter::new(Vec::new(), schema, Arc::new(props))
+        .map_err(|e| sink_error(format!("create parquet writer failed: {}", e)))?;
+    write_row_group(&mut writer, columns, records)?;
+    writer
+        .into_inner()
+        .map_err(|e| sink_error(format!("close parquet file failed: {}", e)))
+}
+
+fn new_writer<W: Write + Send>(
+    out: W,
+    columns: &[ParquetColumn],
+) -> SinkResult<SerializedFileWriter<W>> {
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    SerializedFileWriter::new(out, build_schema(columns, None)?, Arc::new(props))
+        .map_err(|e| sink_error(format!("create parquet writer failed: {}", e)))
+}
+
+/// 按列定义写出一个行组
+fn write_row_group<W: Write + Send>(
+    writer: &mut SerializedFileWriter<W>,
+    columns: &[ParquetColumn],
+    records: &[Arc<DataRecord>],
+) -> SinkResult<()> {
+    let mut row_group = writer
+        .next_row_group()
+        .map_err(|e| sink_error(format!("start row group failed: {}", e)))?;
+    for column in columns {
+        let mut writer = row_group
+            .next_column()
+            .map_err(|e| sink_error(format!("open column failed: {}", e)))?
+            .ok_or_else(|| sink_error("parquet schema has fewer columns than expected"))?;
+        write_column(&mut writer, column, records)
+            .map_err(|e| sink_error(format!("write column '{}' failed: {}", column.name, e)))?;
+        writer
+            .close()
+            .map_err(|e| sink_error(format!("close column failed: {}", e)))?;
+    }
+    row_group
+        .close()
+        .map_err(|e| sink_error(format!("close row group failed: {}", e)))?;
+    Ok(())
+}
+
+fn in_progress(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(IN_PROGRESS_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// 由记录推断列定义：整数、浮点、布尔保持类型，其余按字符串写入
+fn infer_columns(record: &DataRecord) -> Vec<ParquetColumn> {
+    record
+        .items
+        .iter()
+        .filter(|field| *field.get_meta() != DataType::Ignore)
+        .map(|field| {
+            let kind = match field.get_value() {
+                Value::Digit(_) => ParquetColumnType::Int64,
+                Value::Float(_) => ParquetColumnType::Double,
+                Value::Bool(_) => ParquetColumnType::Bool,
+                _ => ParquetColumnType::String,
+            };
+            ParquetColumn::new(field.get_name(), kind)
+        })
+        .collect()
+}
+
+/// `field_ids` 与 `columns` 一一对应，写入列的 field id（Iceberg 按 id 匹配列）
+fn build_schema(columns: &[ParquetColumn], field_ids: Option<&[i32]>) -> SinkResult<Arc<Type>> {
+    let mut fields = Vec::with_capacity(columns.len());
+    for (idx, column) in columns.iter().enumerate() {
+        let builder = match column.kind {
+            ParquetColumnType::String => {
+                Type::primitive_type_builder(&column.name, PhysicalType::BYTE_ARRAY)
+                    .with_logical_type(Some(LogicalType::String))
+            }
+            ParquetColumnType::Int64 => {
+                Type::primitive_type_builder(&column.name, PhysicalType::INT64)
+            }
+            ParquetColumnType::Double => {
+                Type::primitive_type_builder(&column.name, PhysicalType::DOUBLE)
+            }
+            ParquetColumnType::Bool => {
+                Type::primitive_type_builder(&column.name, PhysicalType::BOOLEAN)
+            }
+        };
+        let field = builder
+            .with_repetition(Repetition::OPTIONAL)
+            .with_id(field_ids.and_then(|ids| ids.get(idx).copied()))
+            .build()
+            .map_err(|e| sink_error(format!("invalid column '{}': {}", column.name, e)))?;
+        fields.push(Arc::new(field));
+    }
+    let schema = Type::group_type_builder("wp_record")
+        .with_fields(fields)
+        .build()
+        .map_err(|e| sink_error(format!("build parquet schema failed: {}", e)))?;
+    Ok(Arc::new(schema))
+}
+
+/// 按列写出一个行组的数据；字段缺失或类型不符时写入 null
+fn write_column(
+    writer: &mut SerializedColumnWriter<'_>,
+    column: &ParquetColumn,
+    records: &[Arc<DataRecord>],
+) -> parquet::errors::Result<()> {
+    let values = records.iter().map(|record| {
+        record
+            .field(&column.name)
+            .filter(|field| *field.get_meta() != DataType::Ignore)
+            .map(|field| field.get_value())
+    });
+    match column.kind {
+        ParquetColumnType::String => {
+            let (data, defs) = collect(values, |value| match value {
+                Value::Null | Value::Ignore(_) => None,
+                Value::Chars(v) => Some(ByteArray::from(v.as_str())),
+                other => Some(ByteArray::from(other.to_string().as_str())),
+            });
+            writer
+                .typed::<ByteArrayType>()
+                .write_batch(&data, Some(&defs), None)?;
+        }
+        ParquetColumnType::Int64 => {
+            let (data, defs) = collect(values, |value| match value {
+                Value::Digit(v) => Some(*v),
+                _ => None,
+            });
+            writer
+                .typed::<Int64Type>()
+                .write_batch(&data, Some(&defs), None)?;
+        }
+        ParquetColumnType::Double => {
+            let (data, defs) = collect(values, |value| match value {
+                Value::Float(v) => Some(*v),
+                Value::Digit(v) => Some(*v as f64),
+                _ => None,
+            });
+            writer
+                .typed::<DoubleType>()
+                .write_batch(&data, Some(&defs), None)?;
+        }
+        ParquetColumnType::Bool => {
+            let (data, defs) = collect(values, |value| match value {
+                Value::Bool(v) => Some(*v),
+                _ => None,
+            });
+            writer
+                .typed::<BoolType>()
+                .write_batch(&data, Some(&defs), None)?;
+        }
+    }
+    Ok(())
+}
+
+/// 收集非空值及定义级别（1 表示有值，0 表示 null）
+fn collect<'a, T>(
+    values: impl Iterator<Item = Option<&'a Value>>,
+    convert: impl Fn(&Value) -> Option<T>,
+) -> (Vec<T>, Vec<i16>) {
+    let mut data = Vec::new();
+    let mut defs = Vec::new();
+    for value in values {
+        match value.and_then(&convert) {
+            Some(v) => {
+                data.push(v);
+                defs.push(1);
+            }
+            None => defs.push(0),
+        }
+    }
+    (data, defs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+    use wp_model_core::model::DataField;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wp_parquet_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn parquet_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<_> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn record(id: i64, host: &str) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", id));
+        record.append(DataField::from_chars("host", host));
+        Arc::new(record)
+    }
+
+    #[tokio::test]
+    async fn records_round_trip_through_parquet_file() {
+        let dir = temp_dir("round_trip");
+        let config = ParquetSinkConfig::new(dir.display().to_string()).with_row_group_rows(2);
+        let mut sink = ParquetSink::new(config);
+        let records = (0..5).map(|i| record(i, &format!("web-{i}"))).collect();
+
+        sink.sink_records(records).await.expect("buffer records");
+        sink.stop().await.expect("close file");
+
+        let files = parquet_files(&dir);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].extension().unwrap(), "parquet");
+        let reader = SerializedFileReader::new(File::open(&files[0]).unwrap()).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 5);
+        assert_eq!(reader.num_row_groups(), 3);
+        let rows: Vec<_> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect();
+        assert_eq!(rows[3].get_long(0).unwrap(), 3);
+        assert_eq!(rows[3].get_string(1).unwrap(), "web-3");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn explicit_columns_write_nulls_for_missing_fields() {
+        let dir = temp_dir("explicit");
+        let columns = vec![
+            ParquetColumn::new("host", ParquetColumnType::String),
+            ParquetColumn::new("bytes", ParquetColumnType::Int64),
+        ];
+        let config = ParquetSinkConfig::new(dir.display().to_string()).with_columns(Some(columns));
+        let mut sink = ParquetSink::new(config);
+
+        sink.sink_records(vec![record(1, "db-1")])
+            .await
+            .expect("buffer records");
+        sink.stop().await.expect("close file");
+
+        let files = parquet_files(&dir);
+        let reader = SerializedFileReader::new(File::open(&files[0]).unwrap()).unwrap();
+        let row = reader.get_row_iter(None).unwrap().next().unwrap().unwrap();
+        assert_eq!(row.get_string(0).unwrap(), "db-1");
+        assert!(row.get_long(1).is_err());
+        assert!(sink.sink_records(vec![record(2, "db-2")]).await.is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,89 @@
+//! 离线构建计划（dry-run）
+//!
+//! `validate_spec` 只做参数校验，解析出完整配置要等到 `build`，而 `build` 会建立网络连接。
+//! 实现 [`SinkPlanner`] / [`SourcePlanner`] 的工厂通过 `plan` 执行 `build` 在建连之前的全部步骤
+//! （`config_blob` 展开、默认值派生、选项解析），返回解析后的配置供检查，便于在 CI 中离线
+//! 校验整套规格。返回值中的口令类字段已脱敏。
+
+use serde_json::Value;
+use wp_connector_api::{SinkResult, SinkSpec, SourceResult, SourceSpec};
+
+const REDACTED: &str = "***";
+
+/// Sink 工厂的离线构建计划
+pub trait SinkPlanner {
+    /// 解析规格直至建连之前，返回解析后的配置；不访问网络
+    fn plan(&self, spec: &SinkSpec) -> SinkResult<Value>;
+}
+
+/// Source 工厂的离线构建计划
+pub trait SourcePlanner {
+    /// 解析规格直至建连之前，返回解析后的配置；不访问网络
+    fn plan(&self, spec: &SourceSpec) -> SourceResult<Value>;
+}
+
+/// 名称中含 `password` / `secret` / `token` 的键视为口令
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    ["password", "secret", "token"]
+        .iter()
+        .any(|word| key.contains(word))
+}
+
+/// 递归脱敏：口令类键的字符串值替换为 `***`，`key = value` 形式的原始配置条目同样处理
+pub fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                if is_secret_key(key) && item.is_string() {
+                    *item = Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(item);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        Value::String(entry) => {
+            if let Some((key, _)) = entry.split_once('=')
+                && is_secret_key(key.trim())
+            {
+                *entry = format!("{} = {REDACTED}", key.trim());
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn redact_secrets_masks_keys_and_raw_entries() {
+        let mut value = json!({
+            "password": "dayu",
+            "username": "root",
+            "conf": {
+                "ssl_key_password": "pw",
+                "ssl_key_password_unset": null,
+                "config": ["sasl.password = s3cr3t", "acks = all"],
+            },
+            "client_config": {"sasl.password": "s3cr3t", "client.id": "wp"},
+        });
+        redact_secrets(&mut value);
+        assert_eq!(
+            value,
+            json!({
+                "password": "***",
+                "username": "root",
+                "conf": {
+                    "ssl_key_password": "***",
+                    "ssl_key_password_unset": null,
+                    "config": ["sasl.password = ***", "acks = all"],
+                },
+                "client_config": {"sasl.password": "***", "client.id": "wp"},
+            })
+        );
+    }
+}
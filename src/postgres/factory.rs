@@ -14,6 +14,9 @@ use crate::postgres::{
     PostgresSink, PostgresSource, config::PostgresConf,
     source::validate_source_cursor_type_and_start_from,
 };
+use crate::spill::{self, SPILL_DIR_PARAM};
+use crate::utils::bytes_encoding::{BYTES_ENCODING_PARAM, parse_bytes_encoding};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
 
 pub struct PostgresSourceFactory;
 
@@ -52,12 +55,16 @@ impl SinkFactory for PostgresSinkFactory {
     }
 
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        spill::parse_spill_dir(spec)?;
+        parse_bytes_encoding(spec)?;
         build_postgres_sink_conf(spec)?;
         Ok(())
     }
 
     async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
         let (conf, columns) = build_postgres_sink_conf(spec)?;
+        let bytes_encoding = parse_bytes_encoding(spec)?;
         let url = conf.get_database_url();
         let mut opt = ConnectOptions::new(url.clone());
 
@@ -74,8 +81,9 @@ impl SinkFactory for PostgresSinkFactory {
             SinkError::from(SinkReason::sink(format!("connect postgres fail: {err}")))
         })?;
         let table = conf.table.clone().unwrap_or_else(|| spec.name.clone());
-        let sink = PostgresSink::new(db, table, columns);
-        Ok(SinkHandle::new(Box::new(sink)))
+        let sink = PostgresSink::new(db, table, columns).with_bytes_encoding(bytes_encoding);
+        let handle = spill::wrap_from_spec(spec, SinkHandle::new(Box::new(sink)))?;
+        decorate_sink(spec, handle)
     }
 }
 
@@ -116,9 +124,17 @@ impl SinkDefProvider for PostgresSinkFactory {
             kind: self.kind().into(),
             scope: ConnectorScope::Sink,
             allow_override: vec![
-                "endpoint", "database", "table", "username", "batch", "columns",
+                "endpoint",
+                "database",
+                "table",
+                "username",
+                "batch",
+                "columns",
+                BYTES_ENCODING_PARAM,
+                SPILL_DIR_PARAM,
             ]
             .into_iter()
+            .chain(DECORATOR_PARAMS)
             .map(str::to_string)
             .collect(),
             default_params: postgres_sink_defaults(),
@@ -8,10 +8,27 @@ use wp_connector_api::{
 use wp_log::error_data;
 use wp_model_core::model::{DataRecord, DataType};
 
+use super::source::quote_ident;
+use crate::utils::bytes_encoding::{BytesEncoding, value_text};
+use crate::utils::retry::{RetryPolicy, reconnect_with_backoff};
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
+
+/// 引用表名；`schema.table` 形式按 `.` 分别引用模式名与表名
+fn quote_table(table: &str) -> String {
+    table
+        .split('.')
+        .map(quote_ident)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 pub struct PostgresSink {
     pub db: DatabaseConnection,
     pub table: String,
     pub cloumn_name: Vec<String>,
+    /// 字节类字段的文本编码，`None` 时按原样输出
+    pub bytes_encoding: Option<BytesEncoding>,
+    counters: SinkCounters,
 }
 
 impl PostgresSink {
@@ -20,17 +37,25 @@ impl PostgresSink {
             db,
             table,
             cloumn_name,
+            bytes_encoding: None,
+            counters: SinkCounters::default(),
         }
     }
 
+    /// 设置字节类字段的文本编码
+    pub fn with_bytes_encoding(mut self, bytes_encoding: Option<BytesEncoding>) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
     fn base_insert_prefix(&self) -> String {
         // 使用 INSERT IGNORE：若数据库已写入但客户端因断连未收到响应，重试时避免主键/唯一键冲突
         format!(
             "INSERT INTO {} ({}) VALUES ",
-            self.table,
+            quote_table(&self.table),
             self.cloumn_name
                 .iter()
-                .map(|s| format!("\"{}\"", s))
+                .map(|s| quote_ident(s))
                 .collect::<Vec<_>>()
                 .join(", ")
         )
@@ -41,7 +66,7 @@ impl PostgresSink {
             .items
             .iter()
             .filter(|f| *f.get_meta() != DataType::Ignore)
-            .map(|f| (f.get_name(), f.get_value().to_string()))
+            .map(|f| (f.get_name(), value_text(self.bytes_encoding, f.get_value())))
             .collect();
         let values: Vec<String> = self
             .cloumn_name
@@ -64,10 +89,18 @@ impl AsyncCtrl for PostgresSink {
         Ok(())
     }
     async fn reconnect(&mut self) -> SinkResult<()> {
-        self.db.ping().await.map_err(|e| {
-            SinkError::from(SinkReason::Sink(format!("reconnect postgres fail: {}", e)))
-        })?;
-        Ok(())
+        reconnect_with_backoff("postgres", RetryPolicy::reconnect(), || async {
+            self.db.ping().await.map_err(|e| {
+                SinkError::from(SinkReason::Sink(format!("reconnect postgres fail: {}", e)))
+            })
+        })
+        .await
+    }
+}
+
+impl SinkMetrics for PostgresSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
     }
 }
 
@@ -78,6 +111,7 @@ impl AsyncRecordSink for PostgresSink {
     }
 
     async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.counters.receive(data.len());
         let mut raws = Vec::with_capacity(data.len());
         for record in data {
             raws.push(self.format_values_tuple(record.as_ref()));
@@ -86,12 +120,15 @@ impl AsyncRecordSink for PostgresSink {
             // 单条 INSERT + 多个 VALUES
             let mut sql = self.base_insert_prefix();
             sql.push_str(&raws.join(","));
-            if let Err(e) = self.db.execute_unprepared(sql.as_str()).await {
-                return Err(SinkError::from(SinkReason::Sink(format!(
+            let result = match self.db.execute_unprepared(sql.as_str()).await {
+                Ok(_) => Ok(()),
+                Err(e) => Err(SinkError::from(SinkReason::Sink(format!(
                     "postgres exec cloumns:{:?}, fail: {}, sql: {}",
                     self.cloumn_name, e, sql
-                ))));
-            }
+                )))),
+            };
+            self.counters.finish(raws.len(), sql.len(), &result);
+            result?;
         }
         Ok(())
     }
@@ -124,9 +161,9 @@ impl AsyncRawDataSink for PostgresSink {
 
 #[cfg(test)]
 mod tests {
-    use super::PostgresSink;
+    use super::{BytesEncoding, PostgresSink};
     use sea_orm::DatabaseConnection;
-    use wp_model_core::model::{DataField, DataRecord};
+    use wp_model_core::model::{DataField, DataRecord, DataType};
 
     fn make_sink(table: &str, columns: Vec<&str>) -> PostgresSink {
         PostgresSink::new(
@@ -140,7 +177,16 @@ mod tests {
     fn postgres_sink_base_insert_prefix() {
         let sink = make_sink("users", vec!["name", "age"]);
         let sql = sink.base_insert_prefix();
-        assert_eq!(sql, "INSERT INTO users (\"name\", \"age\") VALUES ");
+        assert_eq!(sql, "INSERT INTO \"users\" (\"name\", \"age\") VALUES ");
+    }
+
+    #[test]
+    fn postgres_sink_quotes_special_identifiers() {
+        let sink = make_sink("public.events", vec!["a\"b", "src.ip", "select"]);
+        assert_eq!(
+            sink.base_insert_prefix(),
+            "INSERT INTO \"public\".\"events\" (\"a\"\"b\", \"src.ip\", \"select\") VALUES "
+        );
     }
 
     #[test]
@@ -154,4 +200,20 @@ mod tests {
         let values = sink.format_values_tuple(&record);
         assert_eq!(values, "('O''Reilly', '42', NULL)");
     }
+
+    #[test]
+    fn postgres_sink_bytes_encoding_renders_hex_fields() {
+        use wp_model_core::model::{HexT, Value};
+
+        let mut record = DataRecord::default();
+        record.append(DataField::new(
+            DataType::Hex,
+            "payload",
+            Value::Hex(HexT(0xff)),
+        ));
+        let sink = make_sink("blobs", vec!["payload"]);
+        assert_eq!(sink.format_values_tuple(&record), "('0xFF')");
+        let sink = sink.with_bytes_encoding(Some(BytesEncoding::Hex));
+        assert_eq!(sink.format_values_tuple(&record), "('ff')");
+    }
 }
@@ -718,7 +718,7 @@ fn required_opt_field<'a>(name: &str, value: &'a Option<String>) -> AnyResult<&'
 }
 
 /// 给 PostgreSQL 标识符加双引号，并转义内部双引号。
-fn quote_ident(ident: &str) -> String {
+pub(super) fn quote_ident(ident: &str) -> String {
     format!("\"{}\"", ident.replace('"', "\"\""))
 }
 
@@ -0,0 +1,177 @@
+use async_trait::async_trait;
+use serde_json::json;
+use wp_conf_base::ConfParser;
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SourceBuildCtx, SourceDefProvider, SourceFactory,
+    SourceHandle, SourceMeta, SourceReason, SourceResult, SourceSpec, SourceSvcIns, Tags,
+};
+
+use crate::WP_SRC_VAL;
+use crate::postgres_cdc::{PgCdcConf, PgCdcSource};
+use crate::utils::config_blob;
+
+const KIND: &str = "postgres_cdc";
+/// PostgreSQL 标识符长度上限（NAMEDATALEN - 1）
+const MAX_SLOT_NAME_LEN: usize = 63;
+
+pub struct PgCdcSourceFactory;
+
+#[async_trait]
+impl SourceFactory for PgCdcSourceFactory {
+    fn kind(&self) -> &'static str {
+        KIND
+    }
+
+    fn validate_spec(&self, spec: &SourceSpec) -> SourceResult<()> {
+        let spec = &*config_blob::source_spec(KIND, spec)?;
+        build_cdc_conf(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SourceSpec, _ctx: &SourceBuildCtx) -> SourceResult<SourceSvcIns> {
+        let spec = &*config_blob::source_spec(KIND, spec)?;
+        let conf = build_cdc_conf(spec)?;
+        let mut meta_tags = Tags::from_parse(&spec.tags);
+        meta_tags.set(WP_SRC_VAL, KIND);
+        let source = PgCdcSource::new(spec.name.clone(), meta_tags.clone(), &conf)
+            .await
+            .map_err(|err| SourceReason::Other(err.to_string()))?;
+
+        let mut meta = SourceMeta::new(spec.name.clone(), spec.kind.clone());
+        meta.tags = meta_tags;
+        let handle = SourceHandle::new(Box::new(source), meta);
+        Ok(SourceSvcIns::new().with_sources(vec![handle]))
+    }
+}
+
+impl SourceDefProvider for PgCdcSourceFactory {
+    fn source_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "postgres_cdc_src".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Source,
+            allow_override: vec![
+                "endpoint",
+                "database",
+                "username",
+                "password",
+                "slot",
+                "publication",
+                "batch",
+                "poll_interval_ms",
+                "error_backoff_ms",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            default_params: postgres_cdc_defaults(),
+            origin: Some("wp-connectors:postgres_cdc_source".into()),
+        }
+    }
+}
+
+fn postgres_cdc_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("endpoint".into(), json!("localhost:5432"));
+    params.insert("database".into(), json!("wp_data"));
+    params.insert("username".into(), json!("root"));
+    params.insert("password".into(), json!("dayu"));
+    params.insert("slot".into(), json!("wp_cdc"));
+    params.insert("publication".into(), json!("wp_cdc"));
+    params.insert("batch".into(), json!(1000));
+    params.insert("poll_interval_ms".into(), json!(1000));
+    params.insert("error_backoff_ms".into(), json!(2000));
+    params
+}
+
+fn build_cdc_conf(spec: &SourceSpec) -> SourceResult<PgCdcConf> {
+    let conf = config_blob::typed_conf(KIND, PgCdcConf::default(), &spec.params)
+        .map_err(SourceReason::Other)?;
+    validate_cdc_conf(&conf)?;
+    Ok(conf)
+}
+
+fn validate_cdc_conf(conf: &PgCdcConf) -> SourceResult<()> {
+    let fail = |msg: String| Err(SourceReason::Other(msg).into());
+    if conf.endpoint.trim().is_empty() {
+        return fail("postgres_cdc.endpoint must not be empty".into());
+    }
+    if conf.database.trim().is_empty() {
+        return fail("postgres_cdc.database must not be empty".into());
+    }
+    // 复制槽名只允许小写字母、数字和下划线（与 pg_create_logical_replication_slot 的约束一致）
+    let slot = conf.slot.as_str();
+    if slot.is_empty()
+        || slot.len() > MAX_SLOT_NAME_LEN
+        || !slot
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+    {
+        return fail(format!(
+            "postgres_cdc.slot '{slot}' must be 1-{MAX_SLOT_NAME_LEN} chars of [a-z0-9_]"
+        ));
+    }
+    if conf.publication.trim().is_empty() {
+        return fail("postgres_cdc.publication must not be empty".into());
+    }
+    if conf.batch == 0 {
+        return fail("postgres_cdc.batch must be > 0".into());
+    }
+    if conf.poll_interval_ms < 100 {
+        return fail("postgres_cdc.poll_interval_ms must be >= 100".into());
+    }
+    if conf.error_backoff_ms < 200 {
+        return fail("postgres_cdc.error_backoff_ms must be >= 200".into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec_with(key: &str, value: serde_json::Value) -> SourceSpec {
+        let mut params = postgres_cdc_defaults();
+        params.insert(key.into(), value);
+        SourceSpec {
+            name: "orders_cdc".into(),
+            kind: KIND.into(),
+            connector_id: "postgres_cdc_src".into(),
+            params,
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn validate_checks_slot_publication_and_intervals() {
+        let factory = PgCdcSourceFactory;
+        factory
+            .validate_spec(&spec_with("slot", json!("orders_slot_1")))
+            .expect("valid slot");
+
+        for (key, value, expect) in [
+            ("slot", json!("Orders-Slot"), "postgres_cdc.slot"),
+            ("slot", json!(""), "postgres_cdc.slot"),
+            ("slot", json!("s".repeat(64)), "postgres_cdc.slot"),
+            ("publication", json!("  "), "postgres_cdc.publication"),
+            ("batch", json!(0), "postgres_cdc.batch"),
+            (
+                "poll_interval_ms",
+                json!(50),
+                "postgres_cdc.poll_interval_ms",
+            ),
+            (
+                "error_backoff_ms",
+                json!(100),
+                "postgres_cdc.error_backoff_ms",
+            ),
+            ("batch", json!("many"), "postgres_cdc: invalid config"),
+        ] {
+            let err = factory
+                .validate_spec(&spec_with(key, value))
+                .expect_err(key)
+                .to_string();
+            assert!(err.contains(expect), "{key}: {err}");
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// PostgreSQL WAL 位置（`pg_lsn`），文本形式为 `XXXXXXXX/XXXXXXXX`（高、低 32 位的十六进制）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Lsn(pub u64);
+
+impl FromStr for Lsn {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid lsn '{raw}': expected 'XXXXXXXX/XXXXXXXX'");
+        let (hi, lo) = raw.trim().split_once('/').ok_or_else(invalid)?;
+        let hi = u32::from_str_radix(hi, 16).map_err(|_| invalid())?;
+        let lo = u32::from_str_radix(lo, 16).map_err(|_| invalid())?;
+        Ok(Self((u64::from(hi) << 32) | u64::from(lo)))
+    }
+}
+
+impl fmt::Display for Lsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 & 0xFFFF_FFFF)
+    }
+}
+
+/// 根据 source key 生成本地 checkpoint 文件路径，与 MySQL Source 相同的目录与命名
+pub(crate) fn checkpoint_path(source_key: &str) -> PathBuf {
+    Path::new("./.run/.checkpoints").join(format!("{source_key}.dat"))
+}
+
+/// 读取已处理到的 LSN；文件不存在或为空时返回 `None`
+pub(crate) fn load_checkpoint(path: &Path) -> anyhow::Result<Option<Lsn>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+    content.parse().map(Some).map_err(|err: String| {
+        anyhow::anyhow!(
+            "postgres_cdc checkpoint file {} is invalid: {err}",
+            path.display()
+        )
+    })
+}
+
+/// 写入已处理到的 LSN，必要时创建目录
+pub(crate) fn store_checkpoint(path: &Path, lsn: Lsn) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, lsn.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lsn_parses_and_formats_pg_text_form() {
+        let lsn: Lsn = "16/B374D848".parse().unwrap();
+        assert_eq!(lsn, Lsn(0x16_B374_D848));
+        assert_eq!(lsn.to_string(), "16/B374D848");
+        assert_eq!(Lsn(0).to_string(), "0/0");
+        assert!("0/1".parse::<Lsn>().unwrap() < "1/0".parse::<Lsn>().unwrap());
+        for bad in ["", "16", "16/zz", "1/100000000"] {
+            assert!(bad.parse::<Lsn>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn checkpoint_round_trips_through_file() {
+        let dir = std::env::temp_dir().join(format!("wp_pg_cdc_ckpt_{}", std::process::id()));
+        let path = dir.join("orders_cdc.dat");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(load_checkpoint(&path).unwrap(), None);
+        store_checkpoint(&path, Lsn(0x1_0000_00A0)).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1/A0");
+        assert_eq!(load_checkpoint(&path).unwrap(), Some(Lsn(0x1_0000_00A0)));
+
+        std::fs::write(&path, "not-an-lsn").unwrap();
+        let err = load_checkpoint(&path).expect_err("invalid checkpoint");
+        assert!(err.to_string().contains("checkpoint file"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
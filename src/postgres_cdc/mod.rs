@@ -0,0 +1,23 @@
+//! PostgreSQL 逻辑复制（CDC）Source
+//!
+//! 通过 `pgoutput` 逻辑解码插件读取复制槽中的变更，将 INSERT/UPDATE/DELETE 解码为带操作类型的记录，
+//! 并以 LSN 作为 checkpoint。
+//!
+//! 模块划分：
+//! - config：连接、复制槽与 Publication 配置
+//! - lsn：LSN 类型与 checkpoint 文件
+//! - pgoutput：`pgoutput`（协议版本 1）消息解码
+//! - source：PgCdcSource，轮询复制槽并推进确认位置
+//! - factory：Source 工厂
+
+mod config;
+mod factory;
+mod lsn;
+mod pgoutput;
+mod source;
+
+pub use config::PgCdcConf;
+pub use factory::PgCdcSourceFactory;
+pub use lsn::Lsn;
+pub use pgoutput::{ChangeOp, PgOutputDecoder};
+pub use source::{PG_CDC_LSN_TAG, PG_CDC_OP_TAG, PgCdcSource};
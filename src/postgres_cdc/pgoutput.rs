@@ -0,0 +1,369 @@
+//! `pgoutput` 逻辑解码消息（协议版本 1）
+//!
+//! 只解析生成变更记录所需的消息：Relation（表结构）与 Insert / Update / Delete。
+//! Begin、Commit、Truncate、Type、Origin 等其余消息被忽略。列值按文本格式传输，
+//! 按列类型 OID 转换为 digit / float / bool 字段，其余类型保留为 chars。
+
+use std::collections::HashMap;
+
+use wp_model_core::model::{DataField, DataRecord};
+
+use super::lsn::Lsn;
+
+/// 记录中的操作类型字段
+pub const OP_FIELD: &str = "pg_op";
+/// 记录中的 schema 字段
+pub const SCHEMA_FIELD: &str = "pg_schema";
+/// 记录中的表名字段
+pub const TABLE_FIELD: &str = "pg_table";
+/// 记录中的 LSN 字段
+pub const LSN_FIELD: &str = "pg_lsn";
+
+// 常用类型 OID（见 `pg_type`）
+const BOOL_OID: u32 = 16;
+const INT8_OID: u32 = 20;
+const INT2_OID: u32 = 21;
+const INT4_OID: u32 = 23;
+const FLOAT4_OID: u32 = 700;
+const FLOAT8_OID: u32 = 701;
+
+/// 变更操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl ChangeOp {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RelationColumn {
+    name: String,
+    type_oid: u32,
+}
+
+#[derive(Debug, Clone)]
+struct Relation {
+    namespace: String,
+    name: String,
+    columns: Vec<RelationColumn>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TupleValue {
+    Null,
+    /// 未变化的 TOAST 值，消息中不携带内容
+    Unchanged,
+    Text(String),
+}
+
+/// 有状态的解码器：缓存 Relation 消息给出的表结构，用于解释后续的行数据
+#[derive(Debug, Default)]
+pub struct PgOutputDecoder {
+    relations: HashMap<u32, Relation>,
+}
+
+impl PgOutputDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 解码一条消息；INSERT/UPDATE/DELETE 返回变更记录，其余消息返回 `None`
+    ///
+    /// 记录包含 `pg_op` / `pg_schema` / `pg_table` / `pg_lsn` 字段，以及新行（DELETE 时为旧行
+    /// 的键列或整行）的各列；NULL 与未变化的 TOAST 列不输出。
+    pub fn decode(
+        &mut self,
+        lsn: Lsn,
+        data: &[u8],
+    ) -> Result<Option<(ChangeOp, DataRecord)>, String> {
+        let mut reader = Reader::new(data);
+        let tag = reader.u8()?;
+        let (op, oid, tuple) = match tag {
+            b'R' => {
+                let oid = reader.u32()?;
+                let namespace = reader.cstr()?;
+                let name = reader.cstr()?;
+                let _replica_identity = reader.u8()?;
+                let count = reader.u16()?;
+                let mut columns = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    let _flags = reader.u8()?;
+                    let name = reader.cstr()?;
+                    let type_oid = reader.u32()?;
+                    let _type_modifier = reader.u32()?;
+                    columns.push(RelationColumn { name, type_oid });
+                }
+                self.relations.insert(
+                    oid,
+                    Relation {
+                        namespace,
+                        name,
+                        columns,
+                    },
+                );
+                return Ok(None);
+            }
+            b'I' => {
+                let oid = reader.u32()?;
+                reader.expect(b'N')?;
+                (ChangeOp::Insert, oid, reader.tuple()?)
+            }
+            b'U' => {
+                let oid = reader.u32()?;
+                // 可选的旧行（`K` 键列 / `O` 整行），之后是新行
+                if matches!(reader.peek()?, b'K' | b'O') {
+                    reader.u8()?;
+                    reader.tuple()?;
+                }
+                reader.expect(b'N')?;
+                (ChangeOp::Update, oid, reader.tuple()?)
+            }
+            b'D' => {
+                let oid = reader.u32()?;
+                match reader.u8()? {
+                    b'K' | b'O' => {}
+                    other => {
+                        return Err(format!("unexpected delete tuple tag '{}'", other as char));
+                    }
+                }
+                (ChangeOp::Delete, oid, reader.tuple()?)
+            }
+            _ => return Ok(None),
+        };
+        let relation = self
+            .relations
+            .get(&oid)
+            .ok_or_else(|| format!("change for unknown relation oid {oid}"))?;
+        if tuple.len() != relation.columns.len() {
+            return Err(format!(
+                "tuple has {} columns, relation {}.{} has {}",
+                tuple.len(),
+                relation.namespace,
+                relation.name,
+                relation.columns.len()
+            ));
+        }
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars(OP_FIELD, op.as_str()));
+        record.append(DataField::from_chars(
+            SCHEMA_FIELD,
+            relation.namespace.as_str(),
+        ));
+        record.append(DataField::from_chars(TABLE_FIELD, relation.name.as_str()));
+        record.append(DataField::from_chars(LSN_FIELD, lsn.to_string()));
+        for (column, value) in relation.columns.iter().zip(tuple) {
+            if let TupleValue::Text(text) = value {
+                record.append(typed_field(column, text));
+            }
+        }
+        Ok(Some((op, record)))
+    }
+}
+
+/// 按列类型转换文本值；无法解析时退化为 chars
+fn typed_field(column: &RelationColumn, text: String) -> DataField {
+    let name = column.name.as_str();
+    let field = match column.type_oid {
+        INT2_OID | INT4_OID | INT8_OID => text.parse().ok().map(|v| DataField::from_digit(name, v)),
+        FLOAT4_OID | FLOAT8_OID => text.parse().ok().map(|v| DataField::from_float(name, v)),
+        BOOL_OID => match text.as_str() {
+            "t" => Some(DataField::from_bool(name, true)),
+            "f" => Some(DataField::from_bool(name, false)),
+            _ => None,
+        },
+        _ => None,
+    };
+    field.unwrap_or_else(|| DataField::from_chars(name, text))
+}
+
+/// 按网络字节序读取消息字段
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos + n;
+        let bytes = self
+            .data
+            .get(self.pos..end)
+            .ok_or_else(|| format!("message truncated at byte {}", self.pos))?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn peek(&self) -> Result<u8, String> {
+        self.data
+            .get(self.pos)
+            .copied()
+            .ok_or_else(|| format!("message truncated at byte {}", self.pos))
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn expect(&mut self, tag: u8) -> Result<(), String> {
+        match self.u8()? {
+            t if t == tag => Ok(()),
+            t => Err(format!("expected '{}', got '{}'", tag as char, t as char)),
+        }
+    }
+
+    /// 以 `\0` 结尾的字符串
+    fn cstr(&mut self) -> Result<String, String> {
+        let rest = &self.data[self.pos..];
+        let len = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or("unterminated string")?;
+        let text = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.pos += len + 1;
+        Ok(text)
+    }
+
+    fn tuple(&mut self) -> Result<Vec<TupleValue>, String> {
+        let count = self.u16()?;
+        let mut values = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let value = match self.u8()? {
+                b'n' => TupleValue::Null,
+                b'u' => TupleValue::Unchanged,
+                b't' => {
+                    let len = self.u32()? as usize;
+                    TupleValue::Text(String::from_utf8_lossy(self.take(len)?).into_owned())
+                }
+                other => return Err(format!("unsupported tuple value kind '{}'", other as char)),
+            };
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造 Relation 消息：oid 16384，`public.orders(id int8, note text, paid bool)`
+    fn relation_message() -> Vec<u8> {
+        let mut msg = vec![b'R'];
+        msg.extend(16384u32.to_be_bytes());
+        msg.extend(b"public\0orders\0");
+        msg.push(b'd');
+        msg.extend(3u16.to_be_bytes());
+        for (name, oid) in [("id", INT8_OID), ("note", 25), ("paid", BOOL_OID)] {
+            msg.push(1);
+            msg.extend(name.as_bytes());
+            msg.push(0);
+            msg.extend(oid.to_be_bytes());
+            msg.extend((-1i32).to_be_bytes());
+        }
+        msg
+    }
+
+    fn tuple(values: &[Option<&str>]) -> Vec<u8> {
+        let mut out = (values.len() as u16).to_be_bytes().to_vec();
+        for value in values {
+            match value {
+                Some(text) => {
+                    out.push(b't');
+                    out.extend((text.len() as u32).to_be_bytes());
+                    out.extend(text.as_bytes());
+                }
+                None => out.push(b'n'),
+            }
+        }
+        out
+    }
+
+    fn field(record: &DataRecord, name: &str) -> Option<String> {
+        record.get_value(name).map(|v| v.to_string())
+    }
+
+    #[test]
+    fn decodes_synthetic_insert_update_and_delete() {
+        let mut decoder = PgOutputDecoder::new();
+        let lsn = Lsn(0x1_0000_0010);
+        assert!(decoder.decode(lsn, &relation_message()).unwrap().is_none());
+        // Begin 等非变更消息被忽略
+        assert!(decoder.decode(lsn, b"B\0\0").unwrap().is_none());
+
+        let mut insert = vec![b'I'];
+        insert.extend(16384u32.to_be_bytes());
+        insert.push(b'N');
+        insert.extend(tuple(&[Some("42"), Some("first order"), Some("f")]));
+        let (op, record) = decoder.decode(lsn, &insert).unwrap().expect("insert");
+        assert_eq!(op, ChangeOp::Insert);
+        assert_eq!(field(&record, OP_FIELD).as_deref(), Some("insert"));
+        assert_eq!(field(&record, SCHEMA_FIELD).as_deref(), Some("public"));
+        assert_eq!(field(&record, TABLE_FIELD).as_deref(), Some("orders"));
+        assert_eq!(field(&record, LSN_FIELD).as_deref(), Some("1/10"));
+        assert_eq!(field(&record, "id").as_deref(), Some("42"));
+        assert_eq!(field(&record, "note").as_deref(), Some("first order"));
+        assert_eq!(field(&record, "paid").as_deref(), Some("false"));
+
+        let mut update = vec![b'U'];
+        update.extend(16384u32.to_be_bytes());
+        update.push(b'K');
+        update.extend(tuple(&[Some("42"), None, None]));
+        update.push(b'N');
+        update.extend(tuple(&[Some("42"), None, Some("t")]));
+        let (op, record) = decoder.decode(lsn, &update).unwrap().expect("update");
+        assert_eq!(op, ChangeOp::Update);
+        assert_eq!(field(&record, "paid").as_deref(), Some("true"));
+        assert_eq!(field(&record, "note"), None);
+
+        let mut delete = vec![b'D'];
+        delete.extend(16384u32.to_be_bytes());
+        delete.push(b'K');
+        delete.extend(tuple(&[Some("42"), None, None]));
+        let (op, record) = decoder.decode(lsn, &delete).unwrap().expect("delete");
+        assert_eq!(op, ChangeOp::Delete);
+        assert_eq!(field(&record, "id").as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn rejects_unknown_relation_and_truncated_message() {
+        let mut decoder = PgOutputDecoder::new();
+        let mut insert = vec![b'I'];
+        insert.extend(7u32.to_be_bytes());
+        insert.push(b'N');
+        insert.extend(tuple(&[Some("1")]));
+        let err = decoder
+            .decode(Lsn(1), &insert)
+            .expect_err("unknown relation");
+        assert!(err.contains("unknown relation oid 7"), "{err}");
+
+        let truncated = &relation_message()[..10];
+        let err = decoder.decode(Lsn(1), truncated).expect_err("truncated");
+        assert!(
+            err.contains("unterminated") || err.contains("truncated"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::Values;
+use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, Statement, Value};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::time::sleep;
+use wp_connector_api::{DataSource, SourceBatch, SourceEvent, SourceReason, SourceResult, Tags};
+use wp_data_fmt::{FormatType, RecordFormatter};
+use wp_log::{info_data, warn_data};
+use wp_model_core::event_id::next_wp_event_id;
+use wp_model_core::model::fmt_def::TextFmt;
+use wp_model_core::raw::RawData;
+
+use super::config::PgCdcConf;
+use super::lsn::{self, Lsn};
+use super::pgoutput::PgOutputDecoder;
+
+type AnyResult<T> = anyhow::Result<T>;
+
+/// 事件标签：变更操作类型（insert / update / delete）
+pub const PG_CDC_OP_TAG: &str = "pg_cdc_op";
+/// 事件标签：变更所在的 LSN
+pub const PG_CDC_LSN_TAG: &str = "pg_cdc_lsn";
+
+// 通过 SQL 接口读取 pgoutput 二进制变更；upto_nchanges 按事务边界截断
+const PEEK_CHANGES_SQL: &str = "SELECT lsn::text AS lsn, data FROM pg_logical_slot_peek_binary_changes($1, NULL, $2, 'proto_version', '1', 'publication_names', $3)";
+const ADVANCE_SLOT_SQL: &str =
+    "SELECT end_lsn::text AS end_lsn FROM pg_replication_slot_advance($1, $2::text::pg_lsn)";
+
+// ===== Source 主流程 =====
+
+pub struct PgCdcSource {
+    key: String,
+    db: DatabaseConnection,
+    slot: String,
+    publication: String,
+    batch: usize,
+    poll_interval: Duration,
+    error_backoff: Duration,
+    decoder: PgOutputDecoder,
+    checkpoint_path: PathBuf,
+    checkpoint: Option<Lsn>,
+    tags: Tags,
+}
+
+impl PgCdcSource {
+    /// 返回当前 source 的内部标识。
+    pub fn identifier(&self) -> &str {
+        &self.key
+    }
+
+    /// 根据配置创建 CDC Source：建立连接，确认复制槽与 Publication 存在，并加载 LSN checkpoint。
+    pub async fn new(key: String, tags: Tags, config: &PgCdcConf) -> AnyResult<Self> {
+        let mut opt = ConnectOptions::new(config.get_database_url());
+        opt.max_connections(2)
+            .min_connections(1)
+            .connect_timeout(Duration::from_secs(8))
+            .acquire_timeout(Duration::from_secs(8))
+            .idle_timeout(Duration::from_secs(8))
+            .max_lifetime(Duration::from_secs(8))
+            .sqlx_logging(false)
+            .map_sqlx_postgres_opts(|opt| opt.statement_cache_capacity(0))
+            .sqlx_logging_level(log::LevelFilter::Info);
+        let db = Database::connect(opt).await?;
+
+        verify_slot(&db, &config.slot).await?;
+        verify_publication(&db, &config.publication).await?;
+
+        let checkpoint_path = lsn::checkpoint_path(&key);
+        let checkpoint = lsn::load_checkpoint(&checkpoint_path)?;
+        info_data!(
+            "[postgres-cdc] source {} reading slot {} (publication {}), checkpoint {:?}",
+            key,
+            config.slot,
+            config.publication,
+            checkpoint.map(|lsn| lsn.to_string())
+        );
+
+        Ok(Self {
+            key,
+            db,
+            slot: config.slot.clone(),
+            publication: config.publication.clone(),
+            batch: config.batch,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            error_backoff: Duration::from_millis(config.error_backoff_ms),
+            decoder: PgOutputDecoder::new(),
+            checkpoint_path,
+            checkpoint,
+            tags,
+        })
+    }
+
+    /// 阻塞式轮询下一批变更；无变更时按 poll_interval_ms 等待，读取失败时按 error_backoff_ms 退避。
+    async fn recv_impl(&mut self) -> SourceResult<SourceBatch> {
+        loop {
+            let changes = match self.peek_changes().await {
+                Ok(changes) => changes,
+                Err(err) => {
+                    warn_data!(
+                        "[postgres-cdc] peek changes failed, backing off {:?}: {}",
+                        self.error_backoff,
+                        err
+                    );
+                    sleep(self.error_backoff).await;
+                    continue;
+                }
+            };
+            let Some(last_lsn) = changes.last().map(|(lsn, _)| *lsn) else {
+                sleep(self.poll_interval).await;
+                continue;
+            };
+
+            let mut batch = Vec::with_capacity(changes.len());
+            for (lsn, data) in changes {
+                // Relation 消息需始终解码以维护表结构缓存；已在 checkpoint 之前的变更不再输出
+                let decoded = self.decoder.decode(lsn, &data).map_err(|err| {
+                    SourceReason::Other(format!(
+                        "postgres_cdc decode change at {lsn} failed: {err}"
+                    ))
+                })?;
+                let Some((op, record)) = decoded else {
+                    continue;
+                };
+                if self.checkpoint.is_some_and(|done| lsn <= done) {
+                    continue;
+                }
+                let payload = FormatType::from(&TextFmt::Json).fmt_record(&record);
+                let mut tags = self.tags.clone();
+                tags.set(PG_CDC_OP_TAG, op.as_str());
+                tags.set(PG_CDC_LSN_TAG, lsn.to_string());
+                batch.push(SourceEvent::new(
+                    next_wp_event_id(),
+                    self.key.clone(),
+                    RawData::from_string(payload),
+                    tags.into(),
+                ));
+            }
+
+            // 先落盘 checkpoint 再推进复制槽：推进失败只会导致重复读取，由 checkpoint 过滤
+            self.persist_checkpoint(last_lsn)?;
+            if let Err(err) = self.advance_slot(last_lsn).await {
+                warn_data!(
+                    "[postgres-cdc] advance slot {} to {} failed: {}",
+                    self.slot,
+                    last_lsn,
+                    err
+                );
+            }
+            if batch.is_empty() {
+                continue;
+            }
+            return Ok(batch);
+        }
+    }
+
+    /// 读取复制槽中待确认的 pgoutput 消息，返回 (LSN, 原始消息)。
+    async fn peek_changes(&self) -> SourceResult<Vec<(Lsn, Vec<u8>)>> {
+        let statement = Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            PEEK_CHANGES_SQL,
+            Values(vec![
+                Value::String(Some(Box::new(self.slot.clone()))),
+                Value::Int(Some(self.batch.min(i32::MAX as usize) as i32)),
+                Value::String(Some(Box::new(self.publication.clone()))),
+            ]),
+        );
+        let rows = self.db.query_all(statement).await.map_err(|err| {
+            SourceReason::SupplierError(format!("postgres_cdc peek changes failed: {err}"))
+        })?;
+
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            let raw_lsn: String = row.try_get_by("lsn").map_err(|err| {
+                SourceReason::Other(format!("postgres_cdc read lsn failed: {err}"))
+            })?;
+            let lsn = raw_lsn.parse::<Lsn>().map_err(SourceReason::Other)?;
+            let data: Vec<u8> = row.try_get_by("data").map_err(|err: sea_orm::DbErr| {
+                SourceReason::Other(format!("postgres_cdc read data failed: {err}"))
+            })?;
+            out.push((lsn, data));
+        }
+        Ok(out)
+    }
+
+    /// 将复制槽的确认位置推进到 `lsn`，释放之前的 WAL。
+    async fn advance_slot(&self, lsn: Lsn) -> Result<(), sea_orm::DbErr> {
+        let statement = Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            ADVANCE_SLOT_SQL,
+            Values(vec![
+                Value::String(Some(Box::new(self.slot.clone()))),
+                Value::String(Some(Box::new(lsn.to_string()))),
+            ]),
+        );
+        self.db.query_one(statement).await.map(|_| ())
+    }
+
+    /// 将本批最后一个 LSN 写入 checkpoint，并同步更新内存状态。
+    fn persist_checkpoint(&mut self, last_lsn: Lsn) -> SourceResult<()> {
+        if self.checkpoint.is_some_and(|done| last_lsn <= done) {
+            return Ok(());
+        }
+        lsn::store_checkpoint(&self.checkpoint_path, last_lsn).map_err(|err| {
+            SourceReason::Other(format!("postgres_cdc write checkpoint failed: {err}"))
+        })?;
+        self.checkpoint = Some(last_lsn);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DataSource for PgCdcSource {
+    /// DataSource 入口：阻塞等待并返回下一批 SourceEvent。
+    async fn receive(&mut self) -> SourceResult<SourceBatch> {
+        self.recv_impl().await
+    }
+
+    /// 当前 CDC Source 只支持阻塞 receive，非阻塞读取固定返回 None。
+    fn try_receive(&mut self) -> Option<SourceBatch> {
+        None
+    }
+
+    /// 返回 DataSource 对外暴露的标识。
+    fn identifier(&self) -> String {
+        self.key.clone()
+    }
+}
+
+// ===== 启动校验 =====
+
+/// 确认复制槽存在且为 `pgoutput` 逻辑槽。
+async fn verify_slot(db: &DatabaseConnection, slot: &str) -> AnyResult<()> {
+    let statement = Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "SELECT plugin::text AS plugin, slot_type::text AS slot_type FROM pg_replication_slots WHERE slot_name = $1",
+        Values(vec![Value::String(Some(Box::new(slot.to_string())))]),
+    );
+    let row = db.query_one(statement).await?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "postgres_cdc replication slot '{slot}' not found; create it with SELECT pg_create_logical_replication_slot('{slot}', 'pgoutput')"
+        )
+    })?;
+    let slot_type: String = row.try_get_by("slot_type")?;
+    let plugin: Option<String> = row.try_get_by("plugin")?;
+    if slot_type != "logical" || plugin.as_deref() != Some("pgoutput") {
+        anyhow::bail!(
+            "postgres_cdc replication slot '{slot}' must be a logical slot using pgoutput, got {slot_type} / {}",
+            plugin.as_deref().unwrap_or("none")
+        );
+    }
+    Ok(())
+}
+
+/// 确认 Publication 存在。
+async fn verify_publication(db: &DatabaseConnection, publication: &str) -> AnyResult<()> {
+    let statement = Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "SELECT 1 AS found FROM pg_publication WHERE pubname = $1",
+        Values(vec![Value::String(Some(Box::new(publication.to_string())))]),
+    );
+    if db.query_one(statement).await?.is_none() {
+        anyhow::bail!("postgres_cdc publication '{publication}' not found");
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Source 预取装饰器
+//!
+//! 各 Source 的 `receive` 都是同步拉取：下游处理期间不会提前读取下一批。
+//! [`PrefetchSource`] 在后台任务中持续调用内部 Source 的 `receive`，把结果写入
+//! 容量为 N 批的有界通道，`receive` 只从通道取数据；通道写满时后台任务阻塞在发送上，
+//! 直到下游取走数据，从而平滑后端的延迟抖动而不会无限堆积。
+
+mod source;
+
+pub use source::PrefetchSource;
+
+use serde_json::Value;
+use wp_connector_api::{SourceReason, SourceResult};
+
+/// 预取参数名
+pub const PREFETCH_PARAM: &str = "prefetch";
+
+/// 读取 `prefetch` 参数（预取的批次数）；未配置或为 0 时返回 `None`
+pub fn parse_prefetch(kind: &str, value: Option<&Value>) -> SourceResult<Option<usize>> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(0) => Ok(None),
+            Some(n) => Ok(Some(n as usize)),
+            None => Err(SourceReason::Other(format!(
+                "{kind}.{PREFETCH_PARAM} must be a non-negative integer"
+            ))
+            .into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_prefetch_treats_absent_and_zero_as_disabled() {
+        assert_eq!(parse_prefetch("kafka", None).unwrap(), None);
+        assert_eq!(parse_prefetch("kafka", Some(&json!(0))).unwrap(), None);
+        assert_eq!(parse_prefetch("kafka", Some(&json!(8))).unwrap(), Some(8));
+        for bad in [json!(-1), json!("8"), json!(1.5)] {
+            let err = parse_prefetch("kafka", Some(&bad)).expect_err("invalid prefetch");
+            assert!(format!("{err}").contains("kafka.prefetch"), "{err}");
+        }
+    }
+}
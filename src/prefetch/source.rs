@@ -0,0 +1,207 @@
+//! 预取 Source 实现
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::{Mutex, mpsc};
+use tokio::task::JoinHandle;
+use wp_connector_api::{
+    AckToken, CtrlRx, DataSource, SourceBatch, SourceCaps, SourceReason, SourceResult,
+};
+
+type SharedSource = Arc<Mutex<Box<dyn DataSource + 'static>>>;
+
+/// 预取装饰器，在后台把内部 Source 的批次读入有界通道
+///
+/// 后台任务在首次 `start`/`receive` 时启动。`NotData` 不占用通道容量；
+/// `EOF` 转发后任务结束，之后的 `receive` 一律返回 `EOF`。
+/// `ack`/`close` 与后台拉取共用同一把锁，会等待正在进行的一次 `receive` 返回。
+pub struct PrefetchSource {
+    inner: SharedSource,
+    id: String,
+    caps: SourceCaps,
+    capacity: usize,
+    rx: Option<mpsc::Receiver<SourceResult<SourceBatch>>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl PrefetchSource {
+    /// 以最多预取 `capacity` 批包装 `inner`（`capacity` 须大于 0）
+    pub fn new(inner: Box<dyn DataSource + 'static>, capacity: usize) -> Self {
+        Self {
+            id: inner.identifier(),
+            caps: inner.caps(),
+            inner: Arc::new(Mutex::new(inner)),
+            capacity: capacity.max(1),
+            rx: None,
+            worker: None,
+        }
+    }
+
+    fn ensure_worker(&mut self) {
+        if self.worker.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel(self.capacity);
+        self.rx = Some(rx);
+        self.worker = Some(tokio::spawn(prefetch_loop(self.inner.clone(), tx)));
+    }
+
+    fn stop_worker(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            worker.abort();
+        }
+        self.rx = None;
+    }
+}
+
+/// 持续拉取并写入通道；通道已满时阻塞在 `send` 上，接收端关闭后退出
+async fn prefetch_loop(inner: SharedSource, tx: mpsc::Sender<SourceResult<SourceBatch>>) {
+    loop {
+        let result = inner.lock().await.receive().await;
+        let eof = match &result {
+            Err(err) if matches!(err.reason(), SourceReason::NotData) => {
+                tokio::task::yield_now().await;
+                continue;
+            }
+            Err(err) => matches!(err.reason(), SourceReason::EOF),
+            Ok(_) => false,
+        };
+        if tx.send(result).await.is_err() || eof {
+            return;
+        }
+    }
+}
+
+impl Drop for PrefetchSource {
+    fn drop(&mut self) {
+        self.stop_worker();
+    }
+}
+
+#[async_trait]
+impl DataSource for PrefetchSource {
+    async fn receive(&mut self) -> SourceResult<SourceBatch> {
+        self.ensure_worker();
+        let rx = self.rx.as_mut().expect("worker started");
+        match rx.recv().await {
+            Some(result) => result,
+            None => Err(SourceReason::EOF.into()),
+        }
+    }
+
+    fn try_receive(&mut self) -> Option<SourceBatch> {
+        None
+    }
+
+    fn identifier(&self) -> String {
+        self.id.clone()
+    }
+
+    fn caps(&self) -> SourceCaps {
+        self.caps
+    }
+
+    async fn start(&mut self, ctrl_rx: CtrlRx) -> SourceResult<()> {
+        self.inner.lock().await.start(ctrl_rx).await?;
+        self.ensure_worker();
+        Ok(())
+    }
+
+    async fn close(&mut self) -> SourceResult<()> {
+        self.stop_worker();
+        self.inner.lock().await.close().await
+    }
+
+    async fn ack(&mut self, token: Arc<dyn AckToken>) -> SourceResult<()> {
+        self.inner.lock().await.ack(token).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use wp_connector_api::{SourceEvent, Tags};
+    use wp_model_core::raw::RawData;
+
+    /// 每次 `receive` 立即返回一批，内容为递增序号
+    struct SeqSource {
+        produced: Arc<AtomicUsize>,
+        limit: usize,
+    }
+
+    #[async_trait]
+    impl DataSource for SeqSource {
+        async fn receive(&mut self) -> SourceResult<SourceBatch> {
+            let n = self.produced.fetch_add(1, Ordering::SeqCst) + 1;
+            if n > self.limit {
+                return Err(SourceReason::EOF.into());
+            }
+            Ok(vec![SourceEvent::new(
+                n as u64,
+                "seq",
+                RawData::from_string(n.to_string()),
+                Arc::new(Tags::new()),
+            )])
+        }
+        fn try_receive(&mut self) -> Option<SourceBatch> {
+            None
+        }
+        fn identifier(&self) -> String {
+            "seq".into()
+        }
+    }
+
+    fn payload(batch: &SourceBatch) -> String {
+        batch[0].payload.to_string()
+    }
+
+    async fn settle() {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+    }
+
+    #[tokio::test]
+    async fn prefetch_fills_to_capacity_then_blocks_until_drained() {
+        let produced = Arc::new(AtomicUsize::new(0));
+        let inner = SeqSource {
+            produced: produced.clone(),
+            limit: 100,
+        };
+        let mut source = PrefetchSource::new(Box::new(inner), 3);
+        assert_eq!(source.identifier(), "seq");
+
+        assert_eq!(payload(&source.receive().await.unwrap()), "1");
+        settle().await;
+        // 通道中 3 批 + 后台任务手中等待发送的 1 批
+        assert_eq!(produced.load(Ordering::SeqCst), 5);
+        settle().await;
+        assert_eq!(
+            produced.load(Ordering::SeqCst),
+            5,
+            "worker must block when full"
+        );
+
+        assert_eq!(payload(&source.receive().await.unwrap()), "2");
+        settle().await;
+        assert_eq!(produced.load(Ordering::SeqCst), 6);
+
+        source.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn prefetch_forwards_eof_and_stops() {
+        let inner = SeqSource {
+            produced: Arc::new(AtomicUsize::new(0)),
+            limit: 2,
+        };
+        let mut source = PrefetchSource::new(Box::new(inner), 4);
+        assert_eq!(payload(&source.receive().await.unwrap()), "1");
+        assert_eq!(payload(&source.receive().await.unwrap()), "2");
+        for _ in 0..2 {
+            let err = source.receive().await.expect_err("eof");
+            assert!(matches!(err.reason(), SourceReason::EOF));
+        }
+    }
+}
@@ -8,4 +8,12 @@ use serde::Serialize;
 pub struct Prometheus {
     #[educe(Default = "0.0.0.0:9090")]
     pub endpoint: String,
+    /// 标签值为空时以 `unknown` 代替，而不是跳过计数
+    #[educe(Default = true)]
+    #[serde(default = "default_fill_empty_labels")]
+    pub fill_empty_labels: bool,
+}
+
+fn default_fill_empty_labels() -> bool {
+    true
 }
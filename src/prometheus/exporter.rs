@@ -4,6 +4,7 @@ use actix_web::{App, HttpRequest, HttpResponse, HttpServer, get};
 use async_trait::async_trait;
 use prometheus::Encoder;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use sysinfo::System;
 use wp_connector_api::{SinkReason, SinkResult};
 use wp_model_core::model::DataRecord;
@@ -11,7 +12,8 @@ use wp_model_core::model::Value;
 
 use super::metrics::IntoOptField; // 使 .opt() 可见
 use super::metrics::{
-    cpu_usage_stat, memory_usage_stat, parse_all_stat, receive_data_stat, sink_stat,
+    cleanup_stale_series, cpu_usage_stat, memory_usage_stat, parse_all_stat, receive_data_stat,
+    sink_stat,
 };
 use orion_exp::ValueGet0; // 使 .get_value() 可见
 
@@ -30,6 +32,10 @@ async fn metrics(_req: HttpRequest) -> HttpResponse {
 
 pub(crate) struct PrometheusExporter {
     pub(super) system: System,
+    pub(super) fill_empty_labels: bool,
+    /// 标签序列过期时间；写入时若距上次清理已超过该时间则清理一次，未设置时不清理
+    pub(super) series_ttl: Option<Duration>,
+    pub(super) last_cleanup: Instant,
 }
 
 #[async_trait]
@@ -38,19 +44,20 @@ impl wp_connector_api::AsyncRecordSink for PrometheusExporter {
         if let Some(Value::Chars(field)) = data.get2("stage").opt().get_value() {
             match field.as_str() {
                 "Pick" => {
-                    receive_data_stat(data);
+                    receive_data_stat(data, self.fill_empty_labels);
                 }
                 "Parse" => {
-                    parse_all_stat(data);
+                    parse_all_stat(data, self.fill_empty_labels);
                 }
                 "Sink" => {
-                    sink_stat(data);
+                    sink_stat(data, self.fill_empty_labels);
                 }
                 _ => {}
             }
         }
         cpu_usage_stat(data, &mut self.system);
         memory_usage_stat(data, &mut self.system);
+        self.cleanup_if_due();
         Ok(())
     }
 
@@ -104,6 +111,21 @@ impl wp_connector_api::AsyncRawDataSink for PrometheusExporter {
 }
 
 impl PrometheusExporter {
+    /// Prometheus 为拉取模式、没有定时任务，清理在写入时按 TTL 间隔顺带执行
+    fn cleanup_if_due(&mut self) {
+        let Some(ttl) = self.series_ttl else {
+            return;
+        };
+        if self.last_cleanup.elapsed() < ttl {
+            return;
+        }
+        self.last_cleanup = Instant::now();
+        let removed = cleanup_stale_series(ttl);
+        if removed > 0 {
+            wp_log::info_data!("Prometheus removed {} stale label series", removed);
+        }
+    }
+
     pub(super) async fn metrics_service(endpoint: String) -> AnyResult<()> {
         HttpServer::new(|| App::new().service(metrics))
             .bind(endpoint.as_str())?
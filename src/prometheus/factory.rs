@@ -9,6 +9,8 @@ use wp_connector_api::{
 
 use super::config::Prometheus;
 use super::exporter::PrometheusExporter;
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::series_ttl::{SERIES_TTL_PARAM, parse_series_ttl};
 use sysinfo::System;
 
 pub struct PrometheusFactory;
@@ -19,6 +21,7 @@ impl SinkFactory for PrometheusFactory {
         "prometheus"
     }
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
         let endpoint = spec
             .params
             .get("endpoint")
@@ -27,13 +30,27 @@ impl SinkFactory for PrometheusFactory {
         if endpoint.trim().is_empty() {
             return Err(SinkReason::sink("prometheus.endpoint must not be empty").into());
         }
+        if let Some(v) = spec.params.get("fill_empty_labels")
+            && !v.is_boolean()
+        {
+            return Err(SinkReason::sink("prometheus.fill_empty_labels must be a bool").into());
+        }
+        parse_series_ttl("prometheus", &spec.params)?;
         Ok(())
     }
     async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let series_ttl = parse_series_ttl("prometheus", &spec.params)?;
         let mut conf = Prometheus::default();
         if let Some(s) = spec.params.get("endpoint").and_then(|v| v.as_str()) {
             conf.endpoint = s.to_string();
         }
+        if let Some(b) = spec
+            .params
+            .get("fill_empty_labels")
+            .and_then(|v| v.as_bool())
+        {
+            conf.fill_empty_labels = b;
+        }
         let endpoint = conf.endpoint.clone();
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().unwrap();
@@ -43,8 +60,11 @@ impl SinkFactory for PrometheusFactory {
         });
         let sink = PrometheusExporter {
             system: System::new(),
+            fill_empty_labels: conf.fill_empty_labels,
+            series_ttl,
+            last_cleanup: std::time::Instant::now(),
         };
-        Ok(SinkHandle::new(Box::new(sink)))
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
     }
 }
 
@@ -54,7 +74,11 @@ impl SinkDefProvider for PrometheusFactory {
             id: "prometheus_sink".into(),
             kind: self.kind().into(),
             scope: ConnectorScope::Sink,
-            allow_override: vec!["endpoint"].into_iter().map(str::to_string).collect(),
+            allow_override: vec!["endpoint", "fill_empty_labels", SERIES_TTL_PARAM]
+                .into_iter()
+                .chain(DECORATOR_PARAMS)
+                .map(str::to_string)
+                .collect(),
             default_params: prometheus_defaults(),
             origin: Some("wp-connectors:prometheus_sink".into()),
         }
@@ -64,5 +88,6 @@ impl SinkDefProvider for PrometheusFactory {
 fn prometheus_defaults() -> ParamMap {
     let mut params = ParamMap::new();
     params.insert("endpoint".into(), json!("0.0.0.0:9898"));
+    params.insert("fill_empty_labels".into(), json!(true));
     params
 }
@@ -29,9 +29,12 @@ impl<'a> IntoOptField<'a> for Option<&'a FieldStorage> {
     }
 }
 use prometheus::{IntCounterVec, register_int_counter_vec};
+use std::time::{Duration, Instant};
 use wp_model_core::model::DataRecord;
 use wp_model_core::model::Value;
 
+use crate::utils::series_ttl::SeriesTtl;
+
 // ------------- metrics helpers -------------
 
 pub(crate) fn cpu_usage_stat(data: &DataRecord, system: &mut System) {
@@ -122,26 +125,74 @@ pub(crate) fn send_sink(data: &DataRecord) -> (SinkMetrics, u64) {
     (sink_metrics, count as u64)
 }
 
-pub fn receive_data_stat(data: &DataRecord) {
-    let (values, total) = source_values(data);
+/// 标签值为空时使用的占位值
+pub const UNKNOWN_LABEL: &str = "unknown";
+
+pub fn receive_data_stat(data: &DataRecord, fill_empty_labels: bool) {
+    record_receive(&RECV_FROM_SOURCE, &RECV_SERIES, data, fill_empty_labels);
+}
+pub fn parse_all_stat(data: &DataRecord, fill_empty_labels: bool) {
+    record_parse_all(&PARSE_ALL, &PARSE_SERIES, data, fill_empty_labels);
+}
+pub fn sink_stat(data: &DataRecord, fill_empty_labels: bool) {
+    record_sink(&SEND_TO_SINK, &SINK_SERIES, data, fill_empty_labels);
+}
+
+/// 删除超过 `ttl` 未更新的来源 / 解析 / 输出计数序列，返回删除的数量
+pub(crate) fn cleanup_stale_series(ttl: Duration) -> usize {
+    let now = Instant::now();
+    RECV_SERIES.cleanup(&RECV_FROM_SOURCE, ttl, now)
+        + PARSE_SERIES.cleanup(&PARSE_ALL, ttl, now)
+        + SINK_SERIES.cleanup(&SEND_TO_SINK, ttl, now)
+}
+
+fn record_receive(
+    counter: &IntCounterVec,
+    series: &SeriesTtl,
+    data: &DataRecord,
+    fill_empty_labels: bool,
+) {
+    let (mut values, total) = source_values(data);
+    if fill_empty_labels {
+        values.fill_empty(UNKNOWN_LABEL);
+    }
     if values.is_valid() {
-        RECV_FROM_SOURCE
+        series.touch(&values.values());
+        counter
             .with_label_values(&values.values())
             .inc_by(total as u64);
     }
 }
-pub fn parse_all_stat(data: &DataRecord) {
-    let (values, all) = parse_all(data);
+
+fn record_parse_all(
+    counter: &IntCounterVec,
+    series: &SeriesTtl,
+    data: &DataRecord,
+    fill_empty_labels: bool,
+) {
+    let (mut values, all) = parse_all(data);
+    if fill_empty_labels {
+        values.fill_empty(UNKNOWN_LABEL);
+    }
     if values.is_valid() {
-        PARSE_ALL.with_label_values(&values.values()).inc_by(all);
+        series.touch(&values.values());
+        counter.with_label_values(&values.values()).inc_by(all);
     }
 }
-pub fn sink_stat(data: &DataRecord) {
-    let (values, count) = send_sink(data);
+
+fn record_sink(
+    counter: &IntCounterVec,
+    series: &SeriesTtl,
+    data: &DataRecord,
+    fill_empty_labels: bool,
+) {
+    let (mut values, count) = send_sink(data);
+    if fill_empty_labels {
+        values.fill_empty(UNKNOWN_LABEL);
+    }
     if values.is_valid() {
-        SEND_TO_SINK
-            .with_label_values(&values.values())
-            .inc_by(count);
+        series.touch(&values.values());
+        counter.with_label_values(&values.values()).inc_by(count);
     }
 }
 
@@ -161,6 +212,11 @@ macro_rules! generate_metrics {
             pub fn is_valid(&self) -> bool {
                 self.values().iter().all(|x| !x.is_empty())
             }
+            // 将空字段替换为占位值，避免整条计数被跳过
+            #[allow(dead_code)]
+            pub fn fill_empty(&mut self, placeholder: &str) {
+                $( if self.$field.is_empty() { self.$field = placeholder.to_string(); } )*
+            }
         }
     };
 }
@@ -203,4 +259,71 @@ lazy_static! {
         &MemoryMetrics::labels()
     )
     .expect("register wparse_memory_usage fail");
+    static ref RECV_SERIES: SeriesTtl = SeriesTtl::default();
+    static ref PARSE_SERIES: SeriesTtl = SeriesTtl::default();
+    static ref SINK_SERIES: SeriesTtl = SeriesTtl::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::Opts;
+    use wp_model_core::model::DataField;
+
+    fn local_counter(labels: &[&str]) -> IntCounterVec {
+        IntCounterVec::new(Opts::new("test_counter", "test"), labels).expect("counter")
+    }
+
+    /// 缺少 target / wp_access_ip 的 Pick 记录
+    fn record_without_source_name() -> DataRecord {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("stage", "Pick"));
+        record.append(DataField::from_chars("wp_source_type", "kafka"));
+        record.append(DataField::from_digit("total", 3));
+        record
+    }
+
+    #[test]
+    fn missing_label_counts_under_unknown_when_filled() {
+        let counter = local_counter(&RecvMetrics::labels());
+        record_receive(
+            &counter,
+            &SeriesTtl::default(),
+            &record_without_source_name(),
+            true,
+        );
+        let labels = [PID.as_str(), "kafka", UNKNOWN_LABEL];
+        assert_eq!(counter.with_label_values(&labels).get(), 3);
+    }
+
+    #[test]
+    fn missing_label_is_skipped_when_fill_disabled() {
+        let counter = local_counter(&RecvMetrics::labels());
+        record_receive(
+            &counter,
+            &SeriesTtl::default(),
+            &record_without_source_name(),
+            false,
+        );
+        let labels = [PID.as_str(), "kafka", UNKNOWN_LABEL];
+        assert_eq!(counter.with_label_values(&labels).get(), 0);
+    }
+
+    #[test]
+    fn sink_and_parse_fill_missing_labels() {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("wp_sink_name", "es"));
+        record.append(DataField::from_digit("success", 2));
+        let counter = local_counter(&SinkMetrics::labels());
+        record_sink(&counter, &SeriesTtl::default(), &record, true);
+        let labels = [PID.as_str(), UNKNOWN_LABEL, "es"];
+        assert_eq!(counter.with_label_values(&labels).get(), 2);
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("total", 4));
+        let counter = local_counter(&ParseAllMetrics::labels());
+        record_parse_all(&counter, &SeriesTtl::default(), &record, true);
+        let labels = [PID.as_str(), UNKNOWN_LABEL, UNKNOWN_LABEL];
+        assert_eq!(counter.with_label_values(&labels).get(), 4);
+    }
 }
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_SERVICE_URL: &str = "pulsar://127.0.0.1:6650";
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// Pulsar Sink 的配置结构
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PulsarSinkConfig {
+    /// Pulsar 服务地址（例如：`pulsar://127.0.0.1:6650`）
+    pub service_url: String,
+    /// 目标 topic
+    pub topic: String,
+    /// JWT token 认证
+    #[serde(default)]
+    pub token: Option<String>,
+    /// 作为消息 key 的记录字段
+    #[serde(default)]
+    pub key_field: Option<String>,
+    /// 建立连接的超时时间（秒）
+    pub connect_timeout_secs: u64,
+}
+
+impl PulsarSinkConfig {
+    /// 构建配置，应用默认值
+    ///
+    /// # Arguments
+    /// * `service_url` - Pulsar 服务地址
+    /// * `topic` - 目标 topic
+    /// * `token` - 可选的 JWT token
+    /// * `key_field` - 可选的消息 key 字段
+    /// * `connect_timeout_secs` - 可选的连接超时（默认：10 秒）
+    pub fn new(
+        service_url: String,
+        topic: String,
+        token: Option<String>,
+        key_field: Option<String>,
+        connect_timeout_secs: Option<u64>,
+    ) -> Self {
+        Self {
+            service_url: service_url.trim().to_string(),
+            topic: topic.trim().to_string(),
+            token,
+            key_field,
+            connect_timeout_secs: connect_timeout_secs
+                .unwrap_or(Self::default_connect_timeout_secs()),
+        }
+    }
+
+    pub fn default_service_url() -> &'static str {
+        DEFAULT_SERVICE_URL
+    }
+
+    pub fn default_connect_timeout_secs() -> u64 {
+        DEFAULT_CONNECT_TIMEOUT_SECS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_applies_defaults_and_trims() {
+        let cfg = PulsarSinkConfig::new(
+            " pulsar://localhost:6650 ".into(),
+            " wp-events ".into(),
+            None,
+            Some("tenant".into()),
+            None,
+        );
+        assert_eq!(cfg.service_url, "pulsar://localhost:6650");
+        assert_eq!(cfg.topic, "wp-events");
+        assert_eq!(cfg.key_field.as_deref(), Some("tenant"));
+        assert_eq!(
+            cfg.connect_timeout_secs,
+            PulsarSinkConfig::default_connect_timeout_secs()
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError, SinkFactory,
+    SinkHandle, SinkReason, SinkResult, SinkSpec,
+};
+use wp_model_core::model::fmt_def::TextFmt;
+
+use crate::pulsar::{PulsarSink, PulsarSinkConfig};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::fmt::parse_text_fmt;
+
+/// Pulsar Sink 工厂，负责验证配置和构建 Sink 实例
+pub struct PulsarSinkFactory;
+
+fn build_pulsar_sink_conf_from_spec(spec: &SinkSpec) -> SinkResult<(PulsarSinkConfig, TextFmt)> {
+    let service_url = required_param(spec, "service_url")?;
+    if !(service_url.starts_with("pulsar://") || service_url.starts_with("pulsar+ssl://")) {
+        return Err(SinkReason::sink(
+            "pulsar.service_url must start with pulsar:// or pulsar+ssl://",
+        )
+        .into());
+    }
+    let topic = required_param(spec, "topic")?;
+    let token = optional_string(spec, "token");
+    let key_field = optional_string(spec, "key_field");
+    let connect_timeout_secs = match spec.params.get("connect_timeout_secs") {
+        None => None,
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Some(n),
+            _ => {
+                return Err(SinkReason::sink(
+                    "pulsar.connect_timeout_secs must be a positive integer",
+                )
+                .into());
+            }
+        },
+    };
+    let fmt = parse_text_fmt("pulsar", spec.params.get("fmt"))?;
+    let conf = PulsarSinkConfig::new(service_url, topic, token, key_field, connect_timeout_secs);
+    Ok((conf, fmt))
+}
+
+#[async_trait]
+impl SinkFactory for PulsarSinkFactory {
+    fn kind(&self) -> &'static str {
+        "pulsar"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        build_pulsar_sink_conf_from_spec(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let (conf, fmt) = build_pulsar_sink_conf_from_spec(spec)?;
+        let sink = PulsarSink::from_conf(&conf, fmt).await.map_err(|err| {
+            SinkError::from(SinkReason::sink(format!("init pulsar sink failed: {err}")))
+        })?;
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+impl SinkDefProvider for PulsarSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "pulsar_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "service_url",
+                "topic",
+                "fmt",
+                "token",
+                "key_field",
+                "connect_timeout_secs",
+            ]
+            .into_iter()
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect(),
+            default_params: pulsar_defaults(),
+            origin: Some("wp-connectors:pulsar_sink".into()),
+        }
+    }
+}
+
+/// 读取必填参数并返回修剪后的字符串
+fn required_param(spec: &SinkSpec, key: &str) -> SinkResult<String> {
+    spec.params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| SinkReason::sink(format!("pulsar.{key} must not be empty")).into())
+}
+
+/// 读取可选字符串参数
+fn optional_string(spec: &SinkSpec, key: &str) -> Option<String> {
+    spec.params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn pulsar_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert(
+        "service_url".into(),
+        json!(PulsarSinkConfig::default_service_url()),
+    );
+    params.insert(
+        "topic".into(),
+        json!("persistent://public/default/wp-events"),
+    );
+    params.insert("fmt".into(), json!("json"));
+    params.insert(
+        "connect_timeout_secs".into(),
+        json!(PulsarSinkConfig::default_connect_timeout_secs()),
+    );
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn base_spec() -> SinkSpec {
+        let mut params = BTreeMap::new();
+        params.insert("service_url".into(), json!("pulsar://127.0.0.1:6650"));
+        params.insert("topic".into(), json!("wp-events"));
+        SinkSpec {
+            name: "pulsar_sink".into(),
+            kind: "pulsar".into(),
+            connector_id: String::new(),
+            group: "test".into(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_minimal_spec() {
+        let (conf, fmt) = build_pulsar_sink_conf_from_spec(&base_spec()).expect("valid spec");
+        assert_eq!(conf.service_url, "pulsar://127.0.0.1:6650");
+        assert_eq!(conf.topic, "wp-events");
+        assert!(conf.token.is_none());
+        assert!(conf.key_field.is_none());
+        assert_eq!(fmt, TextFmt::Json);
+    }
+
+    #[test]
+    fn validate_reads_optional_params() {
+        let mut spec = base_spec();
+        spec.params.insert("token".into(), json!("jwt"));
+        spec.params.insert("key_field".into(), json!(" tenant "));
+        spec.params.insert("fmt".into(), json!("kv"));
+        let (conf, fmt) = build_pulsar_sink_conf_from_spec(&spec).expect("valid spec");
+        assert_eq!(conf.token.as_deref(), Some("jwt"));
+        assert_eq!(conf.key_field.as_deref(), Some("tenant"));
+        assert_eq!(fmt, TextFmt::Kv);
+    }
+
+    #[test]
+    fn validate_rejects_bad_params() {
+        for key in ["service_url", "topic"] {
+            let mut spec = base_spec();
+            spec.params.insert(key.into(), json!(" "));
+            let err = PulsarSinkFactory.validate_spec(&spec).expect_err(key);
+            assert!(format!("{err}").contains(&format!("pulsar.{key}")));
+        }
+
+        let mut spec = base_spec();
+        spec.params
+            .insert("service_url".into(), json!("http://127.0.0.1:8080"));
+        assert!(PulsarSinkFactory.validate_spec(&spec).is_err());
+
+        let mut spec = base_spec();
+        spec.params.insert("connect_timeout_secs".into(), json!(0));
+        assert!(PulsarSinkFactory.validate_spec(&spec).is_err());
+
+        let mut spec = base_spec();
+        spec.params.insert("fmt".into(), json!("bogus"));
+        assert!(PulsarSinkFactory.validate_spec(&spec).is_err());
+    }
+
+    #[test]
+    fn sink_def_defaults_validate() {
+        let def = PulsarSinkFactory.sink_def();
+        assert_eq!(def.id, "pulsar_sink");
+        let mut spec = base_spec();
+        spec.params = def.default_params;
+        assert!(PulsarSinkFactory.validate_spec(&spec).is_ok());
+    }
+
+    #[tokio::test]
+    async fn build_fails_cleanly_when_server_unreachable() {
+        let mut spec = base_spec();
+        spec.params
+            .insert("service_url".into(), json!("pulsar://127.0.0.1:1"));
+        spec.params.insert("connect_timeout_secs".into(), json!(1));
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+        let err = PulsarSinkFactory
+            .build(&spec, &ctx)
+            .await
+            .expect_err("unreachable server");
+        assert!(format!("{err}").contains("init pulsar sink failed"));
+    }
+}
@@ -0,0 +1,21 @@
+//! Pulsar sink implementation for wp-connectors
+//!
+//! 将记录按 `fmt` 格式化后发布到 Pulsar topic，可通过 `key_field` 指定记录字段作为
+//! 消息 key（partition key），保证同 key 消息路由到同一分区。
+//!
+//! # 配置参数
+//!
+//! - `service_url`: Pulsar 服务地址（必填），例如 `pulsar://127.0.0.1:6650`
+//! - `topic`: 目标 topic（必填），例如 `persistent://public/default/wp-events`
+//! - `fmt`: 输出格式，默认 json
+//! - `token`: 可选的 JWT token 认证
+//! - `key_field`: 可选，作为消息 key 的记录字段
+//! - `connect_timeout_secs`: 建立连接的超时时间，默认 10 秒
+
+mod config;
+mod factory;
+mod sink;
+
+pub use config::PulsarSinkConfig;
+pub use factory::PulsarSinkFactory;
+pub use sink::PulsarSink;
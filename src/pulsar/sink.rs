@@ -0,0 +1,214 @@
+//! Pulsar Sink 实现
+//!
+//! 记录按 `fmt` 格式化后发布到配置的 topic；配置 `key_field` 时以该字段值作为
+//! partition key。批量写入时先逐条提交到生产者，再统一等待 broker 回执。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use pulsar::producer::{Message, SendFuture};
+use pulsar::{Authentication, ConnectionRetryOptions, Producer, Pulsar, TokioExecutor};
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
+use wp_data_fmt::{FormatType, RecordFormatter};
+use wp_model_core::model::{DataRecord, fmt_def::TextFmt};
+
+use crate::pulsar::config::PulsarSinkConfig;
+use crate::utils::retry::{RetryPolicy, reconnect_with_backoff};
+use crate::utils::shutdown::DeadlineCtrl;
+
+type AnyResult<T> = anyhow::Result<T>;
+
+pub struct PulsarSink {
+    producer: Producer<TokioExecutor>,
+    conf: PulsarSinkConfig,
+    fmt: TextFmt,
+}
+
+impl PulsarSink {
+    /// 连接 Pulsar 并构建 Sink
+    pub async fn from_conf(conf: &PulsarSinkConfig, fmt: TextFmt) -> AnyResult<Self> {
+        let producer = connect(conf).await?;
+        Ok(Self {
+            producer,
+            conf: conf.clone(),
+            fmt,
+        })
+    }
+
+    /// 提交单条消息到生产者，返回等待 broker 回执的 future
+    async fn submit(&mut self, message: Message) -> SinkResult<SendFuture> {
+        self.producer
+            .send_non_blocking(message)
+            .await
+            .map_err(|e| sink_error(format!("pulsar send fail: {e}")))
+    }
+
+    /// 等待一组消息的 broker 回执
+    async fn await_receipts(receipts: Vec<SendFuture>) -> SinkResult<()> {
+        for receipt in receipts {
+            receipt
+                .await
+                .map_err(|e| sink_error(format!("pulsar send fail: {e}")))?;
+        }
+        Ok(())
+    }
+
+    fn record_message(&self, record: &DataRecord) -> Message {
+        let payload = FormatType::from(&self.fmt).fmt_record(record);
+        build_message(
+            payload.into_bytes(),
+            record_key(record, self.conf.key_field.as_deref()),
+        )
+    }
+
+    async fn publish_raw(&mut self, payloads: Vec<Vec<u8>>) -> SinkResult<()> {
+        let mut receipts = Vec::with_capacity(payloads.len());
+        for payload in payloads {
+            receipts.push(self.submit(build_message(payload, None)).await?);
+        }
+        Self::await_receipts(receipts).await
+    }
+}
+
+async fn connect(conf: &PulsarSinkConfig) -> AnyResult<Producer<TokioExecutor>> {
+    let timeout = Duration::from_secs(conf.connect_timeout_secs);
+    let mut builder = Pulsar::builder(conf.service_url.as_str(), TokioExecutor)
+        .with_connection_retry_options(ConnectionRetryOptions {
+            connection_timeout: timeout,
+            ..ConnectionRetryOptions::default()
+        });
+    if let Some(token) = &conf.token {
+        builder = builder.with_auth(Authentication {
+            name: "token".into(),
+            data: token.clone().into_bytes(),
+        });
+    }
+    let create = async {
+        let client: Pulsar<TokioExecutor> = builder.build().await?;
+        client
+            .producer()
+            .with_topic(conf.topic.as_str())
+            .build()
+            .await
+    };
+    match tokio::time::timeout(timeout, create).await {
+        Ok(producer) => Ok(producer?),
+        Err(_) => anyhow::bail!(
+            "connect to {} timed out after {:?}",
+            conf.service_url,
+            timeout
+        ),
+    }
+}
+
+/// 读取 `key_field` 对应的字段值作为消息 key；字段缺失或为空时不设置 key
+fn record_key(record: &DataRecord, key_field: Option<&str>) -> Option<String> {
+    key_field
+        .and_then(|name| record.get_value(name))
+        .map(|v| v.to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn build_message(payload: Vec<u8>, key: Option<String>) -> Message {
+    Message {
+        payload,
+        partition_key: key,
+        ..Default::default()
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for PulsarSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.producer
+            .close()
+            .await
+            .map_err(|e| sink_error(format!("pulsar stop fail: {e}")))
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        let conf = &self.conf;
+        self.producer = reconnect_with_backoff("pulsar", RetryPolicy::reconnect(), || async {
+            connect(conf)
+                .await
+                .map_err(|e| sink_error(format!("pulsar reconnect fail: {e}")))
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+// 每批消息在写入接口内等待回执，停止时没有待排空的缓冲
+impl DeadlineCtrl for PulsarSink {}
+
+#[async_trait]
+impl AsyncRecordSink for PulsarSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        let receipt = self.submit(self.record_message(data)).await?;
+        Self::await_receipts(vec![receipt]).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        let mut receipts = Vec::with_capacity(data.len());
+        for record in &data {
+            let message = self.record_message(record.as_ref());
+            receipts.push(self.submit(message).await?);
+        }
+        Self::await_receipts(receipts).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for PulsarSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.publish_raw(vec![data.as_bytes().to_vec()]).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.publish_raw(vec![data.to_vec()]).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.publish_raw(data.into_iter().map(|s| s.as_bytes().to_vec()).collect())
+            .await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.publish_raw(data.into_iter().map(<[u8]>::to_vec).collect())
+            .await
+    }
+}
+
+/// 统一封装 sink 层错误
+fn sink_error(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_model_core::model::DataField;
+
+    #[test]
+    fn record_key_reads_configured_field() {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("tenant", "acme"));
+        record.append(DataField::from_chars("empty", ""));
+
+        assert_eq!(record_key(&record, Some("tenant")).as_deref(), Some("acme"));
+        assert_eq!(record_key(&record, Some("empty")), None);
+        assert_eq!(record_key(&record, Some("missing")), None);
+        assert_eq!(record_key(&record, None), None);
+    }
+
+    #[test]
+    fn build_message_sets_partition_key() {
+        let msg = build_message(b"payload".to_vec(), Some("acme".into()));
+        assert_eq!(msg.payload, b"payload");
+        assert_eq!(msg.partition_key.as_deref(), Some("acme"));
+        assert!(build_message(Vec::new(), None).partition_key.is_none());
+    }
+}
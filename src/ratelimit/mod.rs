@@ -0,0 +1,85 @@
+//! 通用限速装饰器
+//!
+//! 任意 Sink 的 `build` 结果都可以通过 [`wrap_from_spec`] 包装：当参数
+//! `rate_limit_rps` 为正整数时，以令牌桶方式将写入速率限制在每秒 N 条记录以内；
+//! 未配置或为 0 表示不限速。主要用于回灌（backfill）时保护下游系统。
+
+mod sink;
+
+pub use sink::RateLimitedSink;
+
+use serde_json::Value;
+use wp_connector_api::{SinkHandle, SinkReason, SinkResult, SinkSpec};
+
+/// 限速参数名
+pub const RATE_LIMIT_PARAM: &str = "rate_limit_rps";
+
+/// 读取 `rate_limit_rps` 参数；未配置或为 0 时返回 `None`
+pub fn parse_rate_limit_rps(spec: &SinkSpec) -> SinkResult<Option<u64>> {
+    match spec.params.get(RATE_LIMIT_PARAM) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(0) => Ok(None),
+            Some(rps) => Ok(Some(rps)),
+            None => Err(SinkReason::sink(format!(
+                "{}.{RATE_LIMIT_PARAM} must be a non-negative integer",
+                spec.kind
+            ))
+            .into()),
+        },
+    }
+}
+
+/// 按 `rate_limit_rps` 参数包装已构建的 Sink；不限速时原样返回
+pub fn wrap_from_spec(spec: &SinkSpec, handle: SinkHandle) -> SinkResult<SinkHandle> {
+    match parse_rate_limit_rps(spec)? {
+        Some(rps) => Ok(SinkHandle::new(Box::new(RateLimitedSink::new(
+            handle.sink,
+            rps,
+        )))),
+        None => Ok(handle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn spec_with(value: Option<Value>) -> SinkSpec {
+        let mut params = BTreeMap::new();
+        if let Some(v) = value {
+            params.insert(RATE_LIMIT_PARAM.to_string(), v);
+        }
+        SinkSpec {
+            group: "test".into(),
+            name: "limited".into(),
+            kind: "count".into(),
+            connector_id: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn parse_treats_absent_and_zero_as_unlimited() {
+        assert_eq!(parse_rate_limit_rps(&spec_with(None)).unwrap(), None);
+        assert_eq!(
+            parse_rate_limit_rps(&spec_with(Some(json!(0)))).unwrap(),
+            None
+        );
+        assert_eq!(
+            parse_rate_limit_rps(&spec_with(Some(json!(500)))).unwrap(),
+            Some(500)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_values() {
+        for bad in [json!(-1), json!(1.5), json!("100")] {
+            let err = parse_rate_limit_rps(&spec_with(Some(bad))).expect_err("invalid rps");
+            assert!(format!("{err}").contains("count.rate_limit_rps"));
+        }
+    }
+}
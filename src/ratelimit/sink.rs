@@ -0,0 +1,211 @@
+//! 令牌桶限速 Sink 实现
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, SinkResult};
+use wp_model_core::model::DataRecord;
+
+/// 令牌桶：按 `rps` 匀速补充令牌，桶容量为 1 秒的配额
+///
+/// 允许令牌数为负（欠账），大批量写入时一次性扣除并等待补足，
+/// 因此 N 条记录在速率 R 下大约耗时 N/R 秒。
+struct TokenBucket {
+    rps: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(rps: u64) -> Self {
+        Self {
+            rps: rps as f64,
+            tokens: 0.0,
+            last: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rps).min(self.rps);
+        self.last = now;
+    }
+
+    /// 扣除 `n` 个令牌，返回需要等待的时长
+    fn take(&mut self, n: usize) -> Duration {
+        self.refill();
+        self.tokens -= n as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rps)
+        }
+    }
+}
+
+/// 限速装饰器，包装任意 [`AsyncSink`] 并限制每秒写入的记录数
+pub struct RateLimitedSink {
+    inner: Box<dyn AsyncSink + 'static>,
+    bucket: TokenBucket,
+}
+
+impl RateLimitedSink {
+    /// 以每秒 `rps` 条记录的上限包装 `inner`（`rps` 须大于 0）
+    pub fn new(inner: Box<dyn AsyncSink + 'static>, rps: u64) -> Self {
+        Self {
+            inner,
+            bucket: TokenBucket::new(rps.max(1)),
+        }
+    }
+
+    async fn acquire(&mut self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        let wait = self.bucket.take(n);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for RateLimitedSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for RateLimitedSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.acquire(1).await;
+        self.inner.sink_record(data).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.acquire(data.len()).await;
+        self.inner.sink_records(data).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for RateLimitedSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.acquire(1).await;
+        self.inner.sink_str(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.acquire(1).await;
+        self.inner.sink_bytes(data).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.acquire(data.len()).await;
+        self.inner.sink_str_batch(data).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.acquire(data.len()).await;
+        self.inner.sink_bytes_batch(data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingSink {
+        count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for CountingSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for CountingSink {
+        async fn sink_record(&mut self, _data: &DataRecord) -> SinkResult<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            self.count.fetch_add(data.len(), Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for CountingSink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+        async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+            self.count.fetch_add(data.len(), Ordering::SeqCst);
+            Ok(())
+        }
+        async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+            self.count.fetch_add(data.len(), Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn limited(rps: u64) -> (RateLimitedSink, Arc<AtomicUsize>) {
+        let inner = CountingSink::default();
+        let count = inner.count.clone();
+        (RateLimitedSink::new(Box::new(inner), rps), count)
+    }
+
+    fn assert_elapsed_near(elapsed: Duration, expected: Duration) {
+        let tolerance = Duration::from_millis(150);
+        assert!(
+            elapsed + tolerance >= expected && elapsed <= expected + tolerance,
+            "elapsed {elapsed:?} not within {tolerance:?} of {expected:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn single_records_are_paced_to_rps() {
+        let (mut sink, count) = limited(50);
+        let start = std::time::Instant::now();
+        for _ in 0..25 {
+            sink.sink_record(&DataRecord::default()).await.unwrap();
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 25);
+        assert_elapsed_near(start.elapsed(), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn batches_consume_one_token_per_record() {
+        let (mut sink, count) = limited(100);
+        let start = std::time::Instant::now();
+        for _ in 0..3 {
+            let batch = (0..20).map(|_| Arc::new(DataRecord::default())).collect();
+            sink.sink_records(batch).await.unwrap();
+        }
+        sink.sink_str_batch(vec!["a"; 20]).await.unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 80);
+        assert_elapsed_near(start.elapsed(), Duration::from_millis(800));
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_URL: &str = "redis://127.0.0.1:6379";
+const DEFAULT_BATCH: usize = 500;
+
+/// Redis 写入模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RedisSinkMode {
+    /// 使用 `RPUSH` 追加格式化后的载荷到列表
+    List,
+    /// 使用 `XADD` 将记录字段写入 Stream
+    Stream,
+}
+
+impl RedisSinkMode {
+    /// 解析 `mode` 参数，仅接受 `list` / `stream`
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "list" => Some(Self::List),
+            "stream" => Some(Self::Stream),
+            _ => None,
+        }
+    }
+}
+
+/// Redis Sink 的配置结构
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedisSinkConfig {
+    /// Redis 连接地址（例如：`redis://:password@127.0.0.1:6379/0`）
+    pub url: String,
+    /// 列表 key 或 Stream 名称
+    pub key: String,
+    /// 写入模式
+    pub mode: RedisSinkMode,
+    /// 单个 pipeline 中的最大命令数
+    pub batch: usize,
+}
+
+impl RedisSinkConfig {
+    /// 构建配置，应用默认值
+    ///
+    /// # Arguments
+    /// * `url` - Redis 连接地址
+    /// * `key` - 列表 key 或 Stream 名称
+    /// * `mode` - 可选的写入模式（默认：list）
+    /// * `batch` - 可选的 pipeline 批量大小（默认：500）
+    pub fn new(
+        url: String,
+        key: String,
+        mode: Option<RedisSinkMode>,
+        batch: Option<usize>,
+    ) -> Self {
+        Self {
+            url: url.trim().to_string(),
+            key: key.trim().to_string(),
+            mode: mode.unwrap_or(RedisSinkMode::List),
+            batch: batch.unwrap_or(Self::default_batch()).max(1),
+        }
+    }
+
+    pub fn default_url() -> &'static str {
+        DEFAULT_URL
+    }
+
+    pub fn default_batch() -> usize {
+        DEFAULT_BATCH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_defaults_and_mode_parse() {
+        let cfg = RedisSinkConfig::new(" redis://localhost ".into(), " events ".into(), None, None);
+        assert_eq!(cfg.url, "redis://localhost");
+        assert_eq!(cfg.key, "events");
+        assert_eq!(cfg.mode, RedisSinkMode::List);
+        assert_eq!(cfg.batch, RedisSinkConfig::default_batch());
+
+        assert_eq!(RedisSinkMode::parse("Stream"), Some(RedisSinkMode::Stream));
+        assert_eq!(RedisSinkMode::parse("set"), None);
+    }
+}
@@ -0,0 +1,160 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError, SinkFactory,
+    SinkHandle, SinkReason, SinkResult, SinkSpec,
+};
+use wp_model_core::model::fmt_def::TextFmt;
+
+use crate::redis::{RedisSink, RedisSinkConfig, RedisSinkMode};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::fmt::parse_text_fmt;
+
+/// Redis Sink 工厂，负责验证配置和构建 Sink 实例
+pub struct RedisSinkFactory;
+
+fn build_redis_sink_conf_from_spec(spec: &SinkSpec) -> SinkResult<(RedisSinkConfig, TextFmt)> {
+    let url = required_param(spec, "url")?;
+    let key = required_param(spec, "key")?;
+    let mode = match spec.params.get("mode") {
+        None => None,
+        Some(v) => Some(v.as_str().and_then(RedisSinkMode::parse).ok_or_else(|| {
+            SinkError::from(SinkReason::sink("redis.mode must be 'list' or 'stream'"))
+        })?),
+    };
+    let batch = match spec.params.get("batch") {
+        None => None,
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Some(n as usize),
+            _ => return Err(SinkReason::sink("redis.batch must be a positive integer").into()),
+        },
+    };
+    let fmt = parse_text_fmt("redis", spec.params.get("fmt"))?;
+    Ok((RedisSinkConfig::new(url, key, mode, batch), fmt))
+}
+
+#[async_trait]
+impl SinkFactory for RedisSinkFactory {
+    fn kind(&self) -> &'static str {
+        "redis"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        build_redis_sink_conf_from_spec(spec)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let (conf, fmt) = build_redis_sink_conf_from_spec(spec)?;
+        let sink = RedisSink::from_conf(&conf, fmt).await.map_err(|err| {
+            SinkError::from(SinkReason::sink(format!("init redis sink failed: {err}")))
+        })?;
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+impl SinkDefProvider for RedisSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "redis_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec!["url", "key", "mode", "fmt", "batch"]
+                .into_iter()
+                .chain(DECORATOR_PARAMS)
+                .map(str::to_string)
+                .collect(),
+            default_params: redis_defaults(),
+            origin: Some("wp-connectors:redis_sink".into()),
+        }
+    }
+}
+
+/// 读取必填参数并返回修剪后的字符串
+fn required_param(spec: &SinkSpec, key: &str) -> SinkResult<String> {
+    spec.params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| SinkReason::sink(format!("redis.{key} must not be empty")).into())
+}
+
+fn redis_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("url".into(), json!(RedisSinkConfig::default_url()));
+    params.insert("key".into(), json!("wp_events"));
+    params.insert("mode".into(), json!("list"));
+    params.insert("fmt".into(), json!("json"));
+    params.insert("batch".into(), json!(RedisSinkConfig::default_batch()));
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn base_spec() -> SinkSpec {
+        let mut params = BTreeMap::new();
+        params.insert("url".into(), json!("redis://127.0.0.1:6379"));
+        params.insert("key".into(), json!("events"));
+        SinkSpec {
+            name: "redis_sink".into(),
+            kind: "redis".into(),
+            connector_id: String::new(),
+            group: "test".into(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_minimal_spec() {
+        let (conf, fmt) = build_redis_sink_conf_from_spec(&base_spec()).expect("valid spec");
+        assert_eq!(conf.key, "events");
+        assert_eq!(conf.mode, RedisSinkMode::List);
+        assert_eq!(fmt, TextFmt::Json);
+    }
+
+    #[test]
+    fn validate_rejects_empty_url_and_key() {
+        for key in ["url", "key"] {
+            let mut spec = base_spec();
+            spec.params.insert(key.into(), json!(""));
+            let err = RedisSinkFactory.validate_spec(&spec).expect_err(key);
+            assert!(format!("{err}").contains(&format!("redis.{key}")));
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_mode_and_batch() {
+        let mut spec = base_spec();
+        spec.params.insert("mode".into(), json!("set"));
+        assert!(RedisSinkFactory.validate_spec(&spec).is_err());
+
+        let mut spec = base_spec();
+        spec.params.insert("batch".into(), json!(0));
+        assert!(RedisSinkFactory.validate_spec(&spec).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_fmt() {
+        let mut spec = base_spec();
+        spec.params.insert("fmt".into(), json!("bogus"));
+        let err = RedisSinkFactory
+            .validate_spec(&spec)
+            .expect_err("bogus fmt");
+        assert!(format!("{err}").contains("invalid fmt"));
+    }
+
+    #[test]
+    fn sink_def_defaults_validate() {
+        let def = RedisSinkFactory.sink_def();
+        assert_eq!(def.id, "redis_sink");
+        let mut spec = base_spec();
+        spec.params = def.default_params;
+        assert!(RedisSinkFactory.validate_spec(&spec).is_ok());
+    }
+}
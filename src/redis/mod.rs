@@ -0,0 +1,19 @@
+//! Redis sink implementation for wp-connectors
+//!
+//! 将记录推送到 Redis 列表（`RPUSH`）或 Stream（`XADD`），批量写入使用 pipeline。
+//!
+//! # 配置参数
+//!
+//! - `url`: Redis 连接地址（必填），例如 `redis://:password@127.0.0.1:6379/0`
+//! - `key`: 列表 key 或 Stream 名称（必填）
+//! - `mode`: `list`（默认）或 `stream`
+//! - `fmt`: list 模式下的输出格式，默认 json；stream 模式直接写入字段
+//! - `batch`: 单个 pipeline 的最大命令数，默认 500
+
+mod config;
+mod factory;
+mod sink;
+
+pub use config::{RedisSinkConfig, RedisSinkMode};
+pub use factory::RedisSinkFactory;
+pub use sink::RedisSink;
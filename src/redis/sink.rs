@@ -0,0 +1,272 @@
+//! Redis Sink 实现
+//!
+//! - list 模式：记录按 `fmt` 格式化后 `RPUSH` 到列表
+//! - stream 模式：记录字段以 `field -> value` 形式 `XADD` 到 Stream
+//!
+//! 批量写入通过 pipeline 合并，每个 pipeline 最多包含 `batch` 条命令。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use redis::{Client, Pipeline};
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
+use wp_data_fmt::{FormatType, RecordFormatter};
+use wp_model_core::model::{DataRecord, fmt_def::TextFmt};
+
+use crate::redis::config::{RedisSinkConfig, RedisSinkMode};
+use crate::utils::retry::{RetryPolicy, reconnect_with_backoff};
+
+type AnyResult<T> = anyhow::Result<T>;
+
+/// stream 模式下原始数据写入的字段名
+const RAW_PAYLOAD_FIELD: &str = "payload";
+
+pub struct RedisSink {
+    client: Client,
+    conn: MultiplexedConnection,
+    conf: RedisSinkConfig,
+    fmt: TextFmt,
+}
+
+impl RedisSink {
+    /// 连接 Redis 并构建 Sink
+    pub async fn from_conf(conf: &RedisSinkConfig, fmt: TextFmt) -> AnyResult<Self> {
+        let client = Client::open(conf.url.as_str())?;
+        let conn = client.get_multiplexed_async_connection().await?;
+        Ok(Self {
+            client,
+            conn,
+            conf: conf.clone(),
+            fmt,
+        })
+    }
+
+    /// 将单条记录追加到 pipeline
+    fn push_record(&self, pipe: &mut Pipeline, record: &DataRecord) {
+        match self.conf.mode {
+            RedisSinkMode::List => {
+                let payload = FormatType::from(&self.fmt).fmt_record(record);
+                pipe.rpush(&self.conf.key, payload).ignore();
+            }
+            RedisSinkMode::Stream => {
+                let fields: Vec<(String, String)> = record
+                    .items
+                    .iter()
+                    .map(|item| (item.get_name().to_string(), item.get_value().to_string()))
+                    .collect();
+                if fields.is_empty() {
+                    return;
+                }
+                pipe.xadd(&self.conf.key, "*", &fields).ignore();
+            }
+        }
+    }
+
+    /// 将原始载荷追加到 pipeline
+    fn push_raw(&self, pipe: &mut Pipeline, payload: &[u8]) {
+        match self.conf.mode {
+            RedisSinkMode::List => {
+                pipe.rpush(&self.conf.key, payload).ignore();
+            }
+            RedisSinkMode::Stream => {
+                pipe.xadd(&self.conf.key, "*", &[(RAW_PAYLOAD_FIELD, payload)])
+                    .ignore();
+            }
+        }
+    }
+
+    async fn execute(&mut self, pipe: Pipeline) -> SinkResult<()> {
+        pipe.query_async::<()>(&mut self.conn)
+            .await
+            .map_err(|e| sink_error(format!("redis pipeline fail: {e}")))
+    }
+
+    async fn write_records(&mut self, records: &[Arc<DataRecord>]) -> SinkResult<()> {
+        for chunk in records.chunks(self.conf.batch) {
+            let mut pipe = redis::pipe();
+            for record in chunk {
+                self.push_record(&mut pipe, record);
+            }
+            self.execute(pipe).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_raw(&mut self, payloads: &[&[u8]]) -> SinkResult<()> {
+        for chunk in payloads.chunks(self.conf.batch) {
+            let mut pipe = redis::pipe();
+            for payload in chunk {
+                self.push_raw(&mut pipe, payload);
+            }
+            self.execute(pipe).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for RedisSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        let client = &self.client;
+        self.conn = reconnect_with_backoff("redis", RetryPolicy::reconnect(), || async {
+            client
+                .get_multiplexed_async_connection()
+                .await
+                .map_err(|e| sink_error(format!("redis reconnect fail: {e}")))
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for RedisSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        let mut pipe = redis::pipe();
+        self.push_record(&mut pipe, data);
+        self.execute(pipe).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.write_records(&data).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for RedisSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.write_raw(&[data.as_bytes()]).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.write_raw(&[data]).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        let payloads: Vec<&[u8]> = data.iter().map(|s| s.as_bytes()).collect();
+        self.write_raw(&payloads).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.write_raw(&data).await
+    }
+}
+
+/// 统一封装 sink 层错误
+fn sink_error(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use wp_model_core::model::DataField;
+
+    /// 极简 RESP 服务端：对每条命令回复 `+OK`，并把命令参数转发到 channel
+    async fn start_mock_server() -> (String, mpsc::UnboundedReceiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read, mut write) = stream.into_split();
+            let mut reader = BufReader::new(read);
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                    break;
+                }
+                let Some(count) = line.trim_end().strip_prefix('*') else {
+                    continue;
+                };
+                let count: usize = count.parse().unwrap();
+                let mut args = Vec::with_capacity(count);
+                for _ in 0..count {
+                    line.clear();
+                    reader.read_line(&mut line).await.unwrap();
+                    let len: usize = line.trim_end()[1..].parse().unwrap();
+                    let mut buf = vec![0u8; len + 2];
+                    reader.read_exact(&mut buf).await.unwrap();
+                    buf.truncate(len);
+                    args.push(String::from_utf8_lossy(&buf).to_string());
+                }
+                write.write_all(b"+OK\r\n").await.unwrap();
+                let _ = tx.send(args);
+            }
+        });
+        (format!("redis://{addr}"), rx)
+    }
+
+    /// 读取下一条业务命令（跳过连接握手阶段的 CLIENT 等命令）
+    async fn next_command(rx: &mut mpsc::UnboundedReceiver<Vec<String>>) -> Vec<String> {
+        loop {
+            let args = rx.recv().await.expect("command");
+            if matches!(args[0].as_str(), "RPUSH" | "XADD") {
+                return args;
+            }
+        }
+    }
+
+    fn record(id: i64) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", id));
+        record.append(DataField::from_chars("level", "info"));
+        Arc::new(record)
+    }
+
+    #[tokio::test]
+    async fn list_mode_pipelines_rpush() {
+        let (url, mut rx) = start_mock_server().await;
+        let conf = RedisSinkConfig::new(url, "events".into(), None, Some(2));
+        let mut sink = RedisSink::from_conf(&conf, TextFmt::Json)
+            .await
+            .expect("connect mock redis");
+
+        sink.sink_records(vec![record(1), record(2), record(3)])
+            .await
+            .expect("rpush records");
+        sink.sink_str("raw-line").await.expect("rpush raw");
+
+        for id in 1..=3 {
+            let args = next_command(&mut rx).await;
+            assert_eq!(args[..2], ["RPUSH", "events"]);
+            assert!(args[2].contains(&format!("\"id\":{id}")), "{:?}", args);
+        }
+        assert_eq!(next_command(&mut rx).await, ["RPUSH", "events", "raw-line"]);
+    }
+
+    #[tokio::test]
+    async fn stream_mode_xadds_field_map() {
+        let (url, mut rx) = start_mock_server().await;
+        let conf = RedisSinkConfig::new(url, "events".into(), Some(RedisSinkMode::Stream), None);
+        let mut sink = RedisSink::from_conf(&conf, TextFmt::Json)
+            .await
+            .expect("connect mock redis");
+
+        sink.sink_record(&record(7)).await.expect("xadd record");
+        sink.sink_bytes(b"raw").await.expect("xadd raw");
+
+        assert_eq!(
+            next_command(&mut rx).await,
+            ["XADD", "events", "*", "id", "7", "level", "info"]
+        );
+        assert_eq!(
+            next_command(&mut rx).await,
+            ["XADD", "events", "*", RAW_PAYLOAD_FIELD, "raw"]
+        );
+    }
+}
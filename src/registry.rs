@@ -0,0 +1,172 @@
+//! 连接器清单与默认参数自检
+//!
+//! 每个工厂通过 `SinkDefProvider` / `SourceDefProvider` 提供 `default_params`，
+//! 但默认值与 `validate_spec` 各自维护，容易逐渐失配。[`verify_all_defaults`]
+//! 遍历当前启用特性下的全部工厂，用各自的默认参数构建规格并执行校验，返回未通过的项。
+//! 新增连接器时须在此登记。
+
+use std::fmt;
+
+use wp_connector_api::{
+    ConnectorScope, SinkDefProvider, SinkFactory, SinkSpec, SourceDefProvider, SourceFactory,
+    SourceSpec,
+};
+
+/// 默认参数未通过自身校验的连接器
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefaultsMismatch {
+    pub scope: ConnectorScope,
+    /// `ConnectorDef.id`
+    pub id: String,
+    pub kind: String,
+    /// `validate_spec` 返回的错误
+    pub error: String,
+}
+
+impl fmt::Display for DefaultsMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scope = match self.scope {
+            ConnectorScope::Source => "source",
+            ConnectorScope::Sink => "sink",
+        };
+        write!(
+            f,
+            "{scope} '{}' (kind {}) defaults fail validation: {}",
+            self.id, self.kind, self.error
+        )
+    }
+}
+
+/// 以默认参数校验全部已启用的连接器，返回未通过校验的列表（全部通过时为空）
+pub fn verify_all_defaults() -> Vec<DefaultsMismatch> {
+    // fanout / aggregate 为组合连接器：子 Sink 需由使用方填写，不参与自检
+    let mut out = Vec::new();
+
+    #[cfg(feature = "kafka")]
+    {
+        verify_source(&crate::kafka::KafkaSourceFactory, &mut out);
+        verify_sink(&crate::kafka::KafkaSinkFactory, &mut out);
+    }
+    #[cfg(feature = "mysql")]
+    {
+        verify_source(&crate::mysql::MySQLSourceFactory, &mut out);
+        verify_sink(&crate::mysql::MySQLSinkFactory, &mut out);
+    }
+    #[cfg(feature = "postgres")]
+    {
+        verify_source(&crate::postgres::PostgresSourceFactory, &mut out);
+        verify_sink(&crate::postgres::PostgresSinkFactory, &mut out);
+    }
+    #[cfg(feature = "postgres-cdc")]
+    verify_source(&crate::postgres_cdc::PgCdcSourceFactory, &mut out);
+    #[cfg(feature = "prometheus")]
+    verify_sink(&crate::prometheus::PrometheusFactory, &mut out);
+    #[cfg(feature = "doris")]
+    verify_sink(&crate::doris::DorisSinkFactory, &mut out);
+    #[cfg(feature = "count")]
+    {
+        verify_source(&crate::count::CountSourceFactory, &mut out);
+        verify_sink(&crate::count::CountSinkFactory, &mut out);
+    }
+    #[cfg(feature = "victorialogs")]
+    verify_sink(&crate::victorialogs::VictoriaLogSinkFactory, &mut out);
+    #[cfg(feature = "elasticsearch")]
+    verify_sink(&crate::elasticsearch::ElasticsearchSinkFactory, &mut out);
+    #[cfg(feature = "victoriametrics")]
+    verify_sink(&crate::victoriametrics::VictoriaMetricFactory, &mut out);
+    #[cfg(feature = "clickhouse")]
+    verify_sink(&crate::clickhouse::ClickHouseSinkFactory, &mut out);
+    #[cfg(feature = "http")]
+    {
+        verify_source(&crate::http::HttpSourceFactory, &mut out);
+        verify_source(&crate::http::HttpPullSourceFactory, &mut out);
+        verify_sink(&crate::http::HttpSinkFactory, &mut out);
+    }
+    #[cfg(feature = "nats")]
+    verify_sink(&crate::nats::NatsSinkFactory, &mut out);
+    #[cfg(feature = "mqtt")]
+    verify_sink(&crate::mqtt::MqttSinkFactory, &mut out);
+    #[cfg(feature = "redis")]
+    verify_sink(&crate::redis::RedisSinkFactory, &mut out);
+    #[cfg(feature = "pulsar")]
+    verify_sink(&crate::pulsar::PulsarSinkFactory, &mut out);
+    #[cfg(feature = "mongodb")]
+    verify_sink(&crate::mongodb::MongoSinkFactory, &mut out);
+    #[cfg(feature = "parquet")]
+    verify_sink(&crate::parquet::ParquetSinkFactory, &mut out);
+    #[cfg(feature = "s3")]
+    verify_sink(&crate::s3::S3SinkFactory, &mut out);
+    #[cfg(feature = "iceberg")]
+    verify_sink(&crate::iceberg::IcebergSinkFactory, &mut out);
+    #[cfg(feature = "grpc")]
+    verify_sink(&crate::grpc::GrpcSinkFactory, &mut out);
+    #[cfg(feature = "sqlite")]
+    verify_sink(&crate::sqlite::SqliteSinkFactory, &mut out);
+    #[cfg(any(test, feature = "memory"))]
+    verify_sink(&crate::memory::MemorySinkFactory::new(), &mut out);
+    #[cfg(any(test, feature = "null"))]
+    verify_sink(&crate::null::NullSinkFactory::new(), &mut out);
+
+    out
+}
+
+fn verify_sink<F: SinkFactory + SinkDefProvider>(factory: &F, out: &mut Vec<DefaultsMismatch>) {
+    let def = factory.sink_def();
+    let spec = SinkSpec {
+        name: def.id.clone(),
+        kind: def.kind.clone(),
+        connector_id: def.id.clone(),
+        group: String::new(),
+        params: def.default_params.clone(),
+        filter: None,
+    };
+    if let Err(err) = factory.validate_spec(&spec) {
+        out.push(DefaultsMismatch {
+            scope: ConnectorScope::Sink,
+            id: def.id,
+            kind: def.kind,
+            error: err.to_string(),
+        });
+    }
+}
+
+fn verify_source<F: SourceFactory + SourceDefProvider>(
+    factory: &F,
+    out: &mut Vec<DefaultsMismatch>,
+) {
+    let def = factory.source_def();
+    let spec = SourceSpec {
+        name: def.id.clone(),
+        kind: def.kind.clone(),
+        connector_id: def.id.clone(),
+        params: def.default_params.clone(),
+        tags: vec![],
+    };
+    if let Err(err) = factory.validate_spec(&spec) {
+        out.push(DefaultsMismatch {
+            scope: ConnectorScope::Source,
+            id: def.id,
+            kind: def.kind,
+            error: err.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_connector_defaults_pass_validation() {
+        let mismatches = verify_all_defaults();
+        assert!(
+            mismatches.is_empty(),
+            "{}",
+            mismatches
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
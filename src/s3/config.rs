@@ -0,0 +1,252 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_REGION: &str = "us-east-1";
+const DEFAULT_PREFIX: &str = "wp/%Y/%m/%d/";
+const DEFAULT_FLUSH_RECORDS: usize = 10_000;
+const DEFAULT_FLUSH_BYTES: usize = 64 * 1024 * 1024;
+const DEFAULT_FLUSH_INTERVAL_SECS: u64 = 60;
+
+/// 对象内容格式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum S3Format {
+    /// 每行一条 JSON 记录
+    #[default]
+    Ndjson,
+    /// 单个行组的 Parquet 文件，需要启用 `parquet` 特性
+    Parquet,
+}
+
+impl S3Format {
+    /// 解析格式名，仅接受 `ndjson` / `parquet`
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "ndjson" => Some(Self::Ndjson),
+            "parquet" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ndjson => "ndjson",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Ndjson => "application/x-ndjson",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+/// 访问凭据；未配置时发送匿名请求
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct S3Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// STS 临时凭据的会话令牌
+    #[serde(default)]
+    pub session_token: Option<String>,
+}
+
+// 避免在日志中输出密钥
+impl std::fmt::Debug for S3Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"***")
+            .field("session_token", &self.session_token.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+/// 对象键前缀模板，支持 strftime 日期占位符（如 `logs/%Y/%m/%d/%H/`），按 UTC 渲染
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PrefixTemplate(String);
+
+impl PrefixTemplate {
+    /// 解析前缀模板：去掉开头的 `/`，非空时补齐结尾的 `/`；非法占位符报错
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let trimmed = raw.trim().trim_start_matches('/');
+        if StrftimeItems::new(trimmed).any(|item| matches!(item, Item::Error)) {
+            return Err(format!("invalid date token in prefix '{raw}'"));
+        }
+        let mut prefix = trimmed.to_string();
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        Ok(Self(prefix))
+    }
+
+    /// 按给定时间渲染前缀
+    pub fn render(&self, now: DateTime<Utc>) -> String {
+        now.format(&self.0).to_string()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for PrefixTemplate {
+    type Error = String;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        Self::parse(&raw)
+    }
+}
+
+impl From<PrefixTemplate> for String {
+    fn from(template: PrefixTemplate) -> Self {
+        template.0
+    }
+}
+
+/// 生成对象键：`{渲染后的前缀}{YYYYmmddTHHMMSSZ}-{seq}.{ext}`
+pub fn object_key(prefix: &PrefixTemplate, now: DateTime<Utc>, seq: u64, fmt: S3Format) -> String {
+    format!(
+        "{}{}-{:04}.{}",
+        prefix.render(now),
+        now.format("%Y%m%dT%H%M%SZ"),
+        seq,
+        fmt.extension()
+    )
+}
+
+/// S3 Sink 的配置结构
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct S3SinkConfig {
+    /// S3 兼容服务地址，如 `https://s3.us-east-1.amazonaws.com` 或 `http://minio:9000`
+    pub endpoint: String,
+    pub bucket: String,
+    /// 签名使用的区域
+    pub region: String,
+    /// 对象键前缀模板
+    pub prefix: PrefixTemplate,
+    pub fmt: S3Format,
+    /// 缓冲记录数达到该值时上传
+    pub flush_records: usize,
+    /// 缓冲数据（按 NDJSON 计）达到该字节数时上传
+    pub flush_bytes: usize,
+    /// 缓冲中最早的记录超过该秒数时，下一次写入触发上传
+    pub flush_interval_secs: u64,
+    #[serde(default)]
+    pub credentials: Option<S3Credentials>,
+}
+
+impl S3SinkConfig {
+    pub fn new(endpoint: impl Into<String>, bucket: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            bucket: bucket.into(),
+            region: DEFAULT_REGION.to_string(),
+            prefix: PrefixTemplate(DEFAULT_PREFIX.to_string()),
+            fmt: S3Format::default(),
+            flush_records: DEFAULT_FLUSH_RECORDS,
+            flush_bytes: DEFAULT_FLUSH_BYTES,
+            flush_interval_secs: DEFAULT_FLUSH_INTERVAL_SECS,
+            credentials: None,
+        }
+    }
+
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = region.into();
+        self
+    }
+
+    pub fn with_prefix(mut self, prefix: PrefixTemplate) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    pub fn with_fmt(mut self, fmt: S3Format) -> Self {
+        self.fmt = fmt;
+        self
+    }
+
+    pub fn with_flush_records(mut self, records: usize) -> Self {
+        self.flush_records = records.max(1);
+        self
+    }
+
+    pub fn with_flush_bytes(mut self, bytes: usize) -> Self {
+        self.flush_bytes = bytes.max(1);
+        self
+    }
+
+    pub fn with_flush_interval_secs(mut self, secs: u64) -> Self {
+        self.flush_interval_secs = secs.max(1);
+        self
+    }
+
+    pub fn with_credentials(mut self, credentials: Option<S3Credentials>) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    pub fn default_region() -> &'static str {
+        DEFAULT_REGION
+    }
+
+    pub fn default_prefix() -> &'static str {
+        DEFAULT_PREFIX
+    }
+
+    pub fn default_flush_records() -> usize {
+        DEFAULT_FLUSH_RECORDS
+    }
+
+    pub fn default_flush_bytes() -> usize {
+        DEFAULT_FLUSH_BYTES
+    }
+
+    pub fn default_flush_interval_secs() -> u64 {
+        DEFAULT_FLUSH_INTERVAL_SECS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn prefix_template_renders_date_tokens_into_object_key() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 7, 9, 5, 30).unwrap();
+        let prefix = PrefixTemplate::parse("/archive/dt=%Y-%m-%d/hour=%H").unwrap();
+        assert_eq!(prefix.as_str(), "archive/dt=%Y-%m-%d/hour=%H/");
+        assert_eq!(prefix.render(now), "archive/dt=2026-03-07/hour=09/");
+        assert_eq!(
+            object_key(&prefix, now, 12, S3Format::Ndjson),
+            "archive/dt=2026-03-07/hour=09/20260307T090530Z-0012.ndjson"
+        );
+
+        let empty = PrefixTemplate::parse("").unwrap();
+        assert_eq!(
+            object_key(&empty, now, 0, S3Format::Parquet),
+            "20260307T090530Z-0000.parquet"
+        );
+        assert!(PrefixTemplate::parse("logs/%Q/").is_err());
+    }
+
+    #[test]
+    fn format_parse_and_debug_hides_secret() {
+        assert_eq!(S3Format::parse(" NDJSON "), Some(S3Format::Ndjson));
+        assert_eq!(S3Format::parse("parquet"), Some(S3Format::Parquet));
+        assert_eq!(S3Format::parse("csv"), None);
+
+        let creds = S3Credentials {
+            access_key_id: "AKID".into(),
+            secret_access_key: "top-secret".into(),
+            session_token: None,
+        };
+        let debug = format!("{creds:?}");
+        assert!(debug.contains("AKID") && !debug.contains("top-secret"));
+    }
+}
@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkFactory, SinkHandle,
+    SinkReason, SinkResult, SinkSpec,
+};
+
+use crate::s3::{PrefixTemplate, S3Credentials, S3Format, S3Sink, S3SinkConfig};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::http_client::{HTTP_CLIENT_PARAMS, HttpClientOptions};
+
+pub struct S3SinkFactory;
+
+#[async_trait]
+impl SinkFactory for S3SinkFactory {
+    fn kind(&self) -> &'static str {
+        "s3"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        build_config(spec)?;
+        HttpClientOptions::from_params("s3", &spec.params)?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let options = HttpClientOptions::from_params("s3", &spec.params)?;
+        let sink = S3Sink::new(build_config(spec)?, &options)?;
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
+    }
+}
+
+fn build_config(spec: &SinkSpec) -> SinkResult<S3SinkConfig> {
+    let endpoint = required_string(spec, "endpoint")?;
+    if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+        return Err(SinkReason::sink("s3.endpoint must be an http(s) url").into());
+    }
+    let bucket = required_string(spec, "bucket")?;
+    let mut config = S3SinkConfig::new(endpoint, bucket);
+    if spec.params.contains_key("region") {
+        config = config.with_region(required_string(spec, "region")?);
+    }
+    if let Some(v) = spec.params.get("prefix") {
+        let raw = v
+            .as_str()
+            .ok_or_else(|| SinkReason::sink("s3.prefix must be a string"))?;
+        let prefix =
+            PrefixTemplate::parse(raw).map_err(|e| SinkReason::sink(format!("s3.prefix: {e}")))?;
+        config = config.with_prefix(prefix);
+    }
+    if let Some(v) = spec.params.get("fmt") {
+        let fmt = v
+            .as_str()
+            .and_then(S3Format::parse)
+            .ok_or_else(|| SinkReason::sink("s3.fmt must be one of: ndjson, parquet"))?;
+        if fmt == S3Format::Parquet && cfg!(not(feature = "parquet")) {
+            return Err(SinkReason::sink(
+                "s3.fmt = parquet requires the `parquet` feature to be enabled",
+            )
+            .into());
+        }
+        config = config.with_fmt(fmt);
+    }
+    if let Some(n) = parse_positive(spec, "flush_records")? {
+        config = config.with_flush_records(n as usize);
+    }
+    if let Some(n) = parse_positive(spec, "flush_bytes")? {
+        config = config.with_flush_bytes(n as usize);
+    }
+    if let Some(n) = parse_positive(spec, "flush_interval_secs")? {
+        config = config.with_flush_interval_secs(n);
+    }
+    Ok(config.with_credentials(parse_credentials(spec)?))
+}
+
+/// `access_key_id` 与 `secret_access_key` 须同时配置（或同时留空表示匿名访问）
+fn parse_credentials(spec: &SinkSpec) -> SinkResult<Option<S3Credentials>> {
+    let access_key_id = optional_string(spec, "access_key_id")?;
+    let secret_access_key = optional_string(spec, "secret_access_key")?;
+    let session_token = optional_string(spec, "session_token")?;
+    match (access_key_id, secret_access_key) {
+        (Some(access_key_id), Some(secret_access_key)) => Ok(Some(S3Credentials {
+            access_key_id,
+            secret_access_key,
+            session_token,
+        })),
+        (None, None) if session_token.is_none() => Ok(None),
+        _ => Err(SinkReason::sink(
+            "s3.access_key_id and s3.secret_access_key must be set together",
+        )
+        .into()),
+    }
+}
+
+fn required_string(spec: &SinkSpec, key: &str) -> SinkResult<String> {
+    optional_string(spec, key)?
+        .ok_or_else(|| SinkReason::sink(format!("s3.{key} must not be empty")).into())
+}
+
+/// 读取字符串参数，缺省或空字符串视为未配置
+fn optional_string(spec: &SinkSpec, key: &str) -> SinkResult<Option<String>> {
+    match spec.params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.trim()).filter(|s| !s.is_empty()).map(String::from)),
+        Some(_) => Err(SinkReason::sink(format!("s3.{key} must be a string")).into()),
+    }
+}
+
+fn parse_positive(spec: &SinkSpec, key: &str) -> SinkResult<Option<u64>> {
+    match spec.params.get(key) {
+        None => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Ok(Some(n)),
+            _ => Err(SinkReason::sink(format!("s3.{key} must be a positive integer")).into()),
+        },
+    }
+}
+
+impl SinkDefProvider for S3SinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "s3_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "endpoint",
+                "bucket",
+                "region",
+                "prefix",
+                "fmt",
+                "flush_records",
+                "flush_bytes",
+                "flush_interval_secs",
+                "access_key_id",
+                "secret_access_key",
+                "session_token",
+            ]
+            .into_iter()
+            .chain(HTTP_CLIENT_PARAMS)
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect(),
+            default_params: s3_defaults(),
+            origin: Some("wp-connectors:s3_sink".into()),
+        }
+    }
+}
+
+fn s3_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("endpoint".into(), json!("http://localhost:9000"));
+    params.insert("bucket".into(), json!("wp-archive"));
+    params.insert("region".into(), json!(S3SinkConfig::default_region()));
+    params.insert("prefix".into(), json!(S3SinkConfig::default_prefix()));
+    params.insert("fmt".into(), json!("ndjson"));
+    params.insert(
+        "flush_records".into(),
+        json!(S3SinkConfig::default_flush_records()),
+    );
+    params.insert(
+        "flush_bytes".into(),
+        json!(S3SinkConfig::default_flush_bytes()),
+    );
+    params.insert(
+        "flush_interval_secs".into(),
+        json!(S3SinkConfig::default_flush_interval_secs()),
+    );
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(params: ParamMap) -> SinkSpec {
+        SinkSpec {
+            name: "export".into(),
+            kind: "s3".into(),
+            connector_id: String::new(),
+            group: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn build_config_parses_params_and_rejects_bad_values() {
+        let mut params = s3_defaults();
+        params.insert("prefix".into(), json!("events/dt=%Y-%m-%d"));
+        params.insert("flush_records".into(), json!(500));
+        params.insert("access_key_id".into(), json!("AKID"));
+        params.insert("secret_access_key".into(), json!("secret"));
+        let config = build_config(&spec(params.clone())).expect("valid spec");
+        assert_eq!(config.prefix.as_str(), "events/dt=%Y-%m-%d/");
+        assert_eq!(config.flush_records, 500);
+        assert_eq!(config.fmt, S3Format::Ndjson);
+        assert_eq!(
+            config.credentials.map(|c| c.access_key_id),
+            Some("AKID".to_string())
+        );
+
+        for (key, bad, expect) in [
+            ("endpoint", json!("minio:9000"), "s3.endpoint"),
+            ("bucket", json!(""), "s3.bucket"),
+            ("prefix", json!("%Q"), "s3.prefix"),
+            ("fmt", json!("csv"), "s3.fmt"),
+            ("flush_bytes", json!(0), "s3.flush_bytes"),
+            ("secret_access_key", json!(""), "s3.access_key_id"),
+        ] {
+            let mut params = params.clone();
+            params.insert(key.into(), bad);
+            let err = S3SinkFactory
+                .validate_spec(&spec(params))
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+}
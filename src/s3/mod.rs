@@ -0,0 +1,27 @@
+//! S3 兼容对象存储 Sink，用于下游分析的批量导出
+//!
+//! - 记录在内存中缓冲，达到 `flush_records` / `flush_bytes`、最早记录超过
+//!   `flush_interval_secs`（由下一次写入触发）或停止时上传为一个对象；
+//! - 对象键为 `{prefix}{YYYYmmddTHHMMSSZ}-{seq}.{ndjson|parquet}`，`prefix` 支持
+//!   strftime 日期占位符（按 UTC 渲染），如 `logs/dt=%Y-%m-%d/`；
+//! - 通过 path-style `PutObject` 上传，配置了凭据时使用 SigV4 签名；上传逻辑抽象为
+//!   [`ObjectUploader`]，可替换为其它实现。
+//!
+//! # Example
+//! ```no_run
+//! use wp_connectors::s3::{S3Sink, S3SinkConfig};
+//! use wp_connectors::utils::http_client::HttpClientOptions;
+//!
+//! let config = S3SinkConfig::new("http://minio:9000", "archive").with_flush_records(50_000);
+//! let sink = S3Sink::new(config, &HttpClientOptions::default());
+//! ```
+
+pub mod config;
+mod factory;
+mod sink;
+mod uploader;
+
+pub use config::{PrefixTemplate, S3Credentials, S3Format, S3SinkConfig};
+pub use factory::S3SinkFactory;
+pub use sink::S3Sink;
+pub use uploader::{HttpUploader, ObjectUploader};
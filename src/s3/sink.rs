@@ -0,0 +1,313 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
+};
+use wp_data_fmt::{FormatType, RecordFormatter};
+use wp_model_core::model::{DataRecord, fmt_def::TextFmt};
+
+use super::config::{S3Format, S3SinkConfig, object_key};
+use super::uploader::{HttpUploader, ObjectUploader};
+use crate::utils::http_client::HttpClientOptions;
+use crate::utils::shutdown::DeadlineCtrl;
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
+
+// 进程内对象序号，避免同一秒内上传的对象重名
+static OBJECT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// S3 Sink 实现，缓冲记录并按大小/时间/停止批量上传为一个对象
+pub struct S3Sink {
+    config: S3SinkConfig,
+    uploader: Arc<dyn ObjectUploader>,
+    /// `ndjson` 时已编码的对象内容
+    body: Vec<u8>,
+    /// `parquet` 时待编码的记录
+    records: Vec<Arc<DataRecord>>,
+    rows: usize,
+    /// 缓冲数据按 NDJSON 计的字节数
+    buffered_bytes: usize,
+    /// 缓冲中最早一条记录的写入时间
+    oldest: Option<Instant>,
+    stopped: bool,
+    counters: SinkCounters,
+}
+
+impl S3Sink {
+    /// 使用 HTTP 上传器（S3 `PutObject`）创建 Sink
+    pub fn new(config: S3SinkConfig, options: &HttpClientOptions) -> SinkResult<Self> {
+        let uploader = HttpUploader::new(&config, options)?;
+        Ok(Self::with_uploader(config, Arc::new(uploader)))
+    }
+
+    /// 使用自定义上传器创建 Sink
+    pub fn with_uploader(config: S3SinkConfig, uploader: Arc<dyn ObjectUploader>) -> Self {
+        Self {
+            config,
+            uploader,
+            body: Vec::new(),
+            records: Vec::new(),
+            rows: 0,
+            buffered_bytes: 0,
+            oldest: None,
+            stopped: false,
+            counters: SinkCounters::default(),
+        }
+    }
+
+    fn push(&mut self, record: Arc<DataRecord>) {
+        let line = format!("{}\n", FormatType::from(&TextFmt::Json).fmt_record(&record));
+        self.buffered_bytes += line.len();
+        match self.config.fmt {
+            S3Format::Ndjson => self.body.extend_from_slice(line.as_bytes()),
+            S3Format::Parquet => self.records.push(record),
+        }
+        self.rows += 1;
+        self.oldest.get_or_insert_with(Instant::now);
+    }
+
+    fn should_flush(&self) -> bool {
+        let interval = Duration::from_secs(self.config.flush_interval_secs);
+        self.rows >= self.config.flush_records
+            || self.buffered_bytes >= self.config.flush_bytes
+            || self.oldest.is_some_and(|t| t.elapsed() >= interval)
+    }
+
+    /// 将缓冲上传为一个对象；上传失败时保留缓冲，下次写入或停止时重试
+    async fn flush(&mut self) -> SinkResult<()> {
+        if self.rows == 0 {
+            return Ok(());
+        }
+        let body = match self.config.fmt {
+            S3Format::Ndjson => Bytes::from(std::mem::take(&mut self.body)),
+            S3Format::Parquet => Bytes::from(encode_parquet(&self.records)?),
+        };
+        let seq = OBJECT_SEQ.fetch_add(1, Ordering::SeqCst);
+        let key = object_key(&self.config.prefix, Utc::now(), seq, self.config.fmt);
+        let bytes = body.len();
+        let result = self
+            .uploader
+            .put_object(&key, body.clone(), self.config.fmt.content_type())
+            .await;
+        self.counters.finish(self.rows, bytes, &result);
+        match &result {
+            Ok(()) => {
+                self.records.clear();
+                self.rows = 0;
+                self.buffered_bytes = 0;
+                self.oldest = None;
+            }
+            Err(_) if self.config.fmt == S3Format::Ndjson => self.body = Vec::from(body),
+            Err(_) => {}
+        }
+        result
+    }
+
+    fn ensure_running(&self) -> SinkResult<()> {
+        if self.stopped {
+            return Err(sink_error("s3 sink is stopped"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn encode_parquet(records: &[Arc<DataRecord>]) -> SinkResult<Vec<u8>> {
+    crate::parquet::encode_parquet(records, None)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn encode_parquet(_records: &[Arc<DataRecord>]) -> SinkResult<Vec<u8>> {
+    Err(sink_error(
+        "s3.fmt = parquet requires the `parquet` feature to be enabled",
+    ))
+}
+
+#[async_trait]
+impl AsyncCtrl for S3Sink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        if self.stopped {
+            return Ok(());
+        }
+        self.flush().await?;
+        self.stopped = true;
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+// 默认的 `stop_with_deadline` 即可：`stop` 会上传剩余缓冲
+impl DeadlineCtrl for S3Sink {}
+
+impl SinkMetrics for S3Sink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for S3Sink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.sink_records(vec![Arc::new(data.clone())]).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.ensure_running()?;
+        self.counters.receive(data.len());
+        for record in data {
+            self.push(record);
+        }
+        if self.should_flush() {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for S3Sink {
+    async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+        Err(sink_error("s3 sink does not accept raw input"))
+    }
+
+    async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+        Err(sink_error("s3 sink does not accept raw bytes"))
+    }
+
+    async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+        Err(sink_error("s3 sink does not accept raw input"))
+    }
+
+    async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+        Err(sink_error("s3 sink does not accept raw bytes"))
+    }
+}
+
+fn sink_error(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::s3::config::PrefixTemplate;
+    use std::sync::Mutex;
+    use std::sync::atomic::AtomicBool;
+    use wp_model_core::model::DataField;
+
+    /// 记录上传对象的模拟 S3
+    #[derive(Default)]
+    struct MockUploader {
+        objects: Mutex<Vec<(String, Vec<u8>, String)>>,
+        fail: AtomicBool,
+    }
+
+    #[async_trait]
+    impl ObjectUploader for MockUploader {
+        async fn put_object(&self, key: &str, body: Bytes, content_type: &str) -> SinkResult<()> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(sink_error("mock put fail"));
+            }
+            self.objects.lock().unwrap().push((
+                key.to_string(),
+                body.to_vec(),
+                content_type.to_string(),
+            ));
+            Ok(())
+        }
+    }
+
+    fn record(id: i64, host: &str) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", id));
+        record.append(DataField::from_chars("host", host));
+        Arc::new(record)
+    }
+
+    fn config() -> S3SinkConfig {
+        S3SinkConfig::new("http://127.0.0.1:9000", "archive")
+            .with_prefix(PrefixTemplate::parse("logs/%Y/").unwrap())
+            .with_flush_records(3)
+    }
+
+    #[tokio::test]
+    async fn flush_on_size_uploads_one_ndjson_object() {
+        let uploader = Arc::new(MockUploader::default());
+        let mut sink = S3Sink::with_uploader(config(), uploader.clone());
+
+        sink.sink_records(vec![record(1, "web-1"), record(2, "web-2")])
+            .await
+            .unwrap();
+        assert!(uploader.objects.lock().unwrap().is_empty());
+        sink.sink_records(vec![record(3, "web-3")]).await.unwrap();
+
+        let objects = uploader.objects.lock().unwrap().clone();
+        assert_eq!(objects.len(), 1);
+        let (key, body, content_type) = &objects[0];
+        let year = Utc::now().format("%Y").to_string();
+        assert!(key.starts_with(&format!("logs/{year}/")), "{key}");
+        assert!(key.ends_with(".ndjson"), "{key}");
+        assert_eq!(content_type, "application/x-ndjson");
+        let lines: Vec<serde_json::Value> = std::str::from_utf8(body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2]["id"], 3);
+        assert_eq!(lines[2]["host"], "web-3");
+        assert_eq!(
+            sink.metrics(),
+            SinkMetricsSnapshot {
+                received: 3,
+                flushed: 3,
+                failed: 0,
+                bytes_sent: body.len() as u64,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_upload_keeps_buffer_until_stop() {
+        let uploader = Arc::new(MockUploader::default());
+        let mut sink = S3Sink::with_uploader(config(), uploader.clone());
+        uploader.fail.store(true, Ordering::SeqCst);
+
+        let records = (0..3).map(|i| record(i, "db")).collect();
+        assert!(sink.sink_records(records).await.is_err());
+        assert!(sink.stop().await.is_err());
+
+        uploader.fail.store(false, Ordering::SeqCst);
+        sink.stop().await.expect("retry on stop");
+        let objects = uploader.objects.lock().unwrap().clone();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(
+            std::str::from_utf8(&objects[0].1).unwrap().lines().count(),
+            3
+        );
+        assert!(sink.sink_records(vec![record(4, "db")]).await.is_err());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[tokio::test]
+    async fn parquet_format_uploads_parquet_object_on_stop() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let uploader = Arc::new(MockUploader::default());
+        let mut sink =
+            S3Sink::with_uploader(config().with_fmt(S3Format::Parquet), uploader.clone());
+        sink.sink_records(vec![record(1, "web-1")]).await.unwrap();
+        sink.stop().await.unwrap();
+
+        let objects = uploader.objects.lock().unwrap();
+        assert!(objects[0].0.ends_with(".parquet"));
+        let reader = SerializedFileReader::new(Bytes::from(objects[0].1.clone())).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 1);
+    }
+}
@@ -0,0 +1,121 @@
+//! 通用采样装饰器
+//!
+//! 任意 Sink 都可以通过参数 `sample_rate` 只写入一部分数据，用于调试时旁路高流量数据：
+//!
+//! - `sample_rate = 10` 或 `"1/10"`：确定性计数，每 10 条放行第 1 条；
+//! - `sample_rate = 0.05`：按概率随机放行，约 5% 的数据写入内部 Sink；
+//! - `sample_rate = 1` / `1.0`：不采样。
+//!
+//! 其余数据直接丢弃。记录与原始文本/字节都参与采样。
+
+mod sink;
+
+pub use sink::SamplingSink;
+
+use serde_json::Value;
+use wp_connector_api::{SinkHandle, SinkReason, SinkResult, SinkSpec};
+
+/// 采样参数名
+pub const SAMPLE_RATE_PARAM: &str = "sample_rate";
+
+/// 采样方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleRate {
+    /// 每 N 条放行 1 条（N > 1）
+    OneIn(u64),
+    /// 每条以概率 p 放行（0 < p < 1）
+    Probability(f64),
+}
+
+/// 读取 `sample_rate` 参数；未配置或为 1 时返回 `None`
+pub fn parse_sample_rate(spec: &SinkSpec) -> SinkResult<Option<SampleRate>> {
+    let invalid = || {
+        SinkReason::sink(format!(
+            "{}.{SAMPLE_RATE_PARAM} must be an integer N >= 1, \"1/N\", or a probability in (0, 1]",
+            spec.kind
+        ))
+    };
+    let rate = match spec.params.get(SAMPLE_RATE_PARAM) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(raw)) => match raw.trim().strip_prefix("1/") {
+            Some(n) => n.trim().parse::<u64>().ok().map(|n| n as f64),
+            None => raw.trim().parse::<f64>().ok(),
+        },
+        Some(v) => v.as_f64(),
+    };
+    match rate {
+        Some(1.0) => Ok(None),
+        Some(p) if p > 0.0 && p < 1.0 => Ok(Some(SampleRate::Probability(p))),
+        Some(n) if n > 1.0 && n.fract() == 0.0 && n <= u64::MAX as f64 => {
+            Ok(Some(SampleRate::OneIn(n as u64)))
+        }
+        _ => Err(invalid().into()),
+    }
+}
+
+/// 按 `sample_rate` 参数包装已构建的 Sink；不采样时原样返回
+pub fn wrap_from_spec(spec: &SinkSpec, handle: SinkHandle) -> SinkResult<SinkHandle> {
+    match parse_sample_rate(spec)? {
+        Some(rate) => Ok(SinkHandle::new(Box::new(SamplingSink::new(
+            handle.sink,
+            rate,
+        )))),
+        None => Ok(handle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn spec_with(value: Option<Value>) -> SinkSpec {
+        let mut params = BTreeMap::new();
+        if let Some(v) = value {
+            params.insert(SAMPLE_RATE_PARAM.to_string(), v);
+        }
+        SinkSpec {
+            group: "test".into(),
+            name: "sampled".into(),
+            kind: "memory".into(),
+            connector_id: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn parse_accepts_one_in_n_and_probability() {
+        for (value, expect) in [
+            (None, None),
+            (Some(json!(1)), None),
+            (Some(json!("1/1")), None),
+            (Some(json!(10)), Some(SampleRate::OneIn(10))),
+            (Some(json!(" 1/25 ")), Some(SampleRate::OneIn(25))),
+            (Some(json!(0.05)), Some(SampleRate::Probability(0.05))),
+            (Some(json!("0.5")), Some(SampleRate::Probability(0.5))),
+        ] {
+            assert_eq!(
+                parse_sample_rate(&spec_with(value.clone())).unwrap(),
+                expect,
+                "{value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_invalid_values() {
+        for bad in [
+            json!(0),
+            json!(-2),
+            json!(2.5),
+            json!("1/0"),
+            json!("often"),
+            json!(true),
+        ] {
+            let err = parse_sample_rate(&spec_with(Some(bad.clone()))).expect_err("invalid rate");
+            assert!(format!("{err}").contains("memory.sample_rate"), "{bad}");
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! 采样 Sink 实现
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, SinkResult};
+use wp_model_core::model::DataRecord;
+
+use super::SampleRate;
+
+/// 采样装饰器：按 [`SampleRate`] 放行部分数据给内部 Sink，其余丢弃
+///
+/// 计数模式的计数器跨批次累计，因此无论按单条还是按批写入，放行比例都精确为 1/N。
+pub struct SamplingSink {
+    inner: Box<dyn AsyncSink + 'static>,
+    rate: SampleRate,
+    seen: u64,
+    dropped: u64,
+}
+
+impl SamplingSink {
+    pub fn new(inner: Box<dyn AsyncSink + 'static>, rate: SampleRate) -> Self {
+        Self {
+            inner,
+            rate,
+            seen: 0,
+            dropped: 0,
+        }
+    }
+
+    /// 判断下一条数据是否放行
+    fn keep(&mut self) -> bool {
+        let keep = match self.rate {
+            SampleRate::OneIn(n) => self.seen.is_multiple_of(n),
+            SampleRate::Probability(p) => rand::random_bool(p),
+        };
+        self.seen = self.seen.wrapping_add(1);
+        if !keep {
+            self.dropped += 1;
+        }
+        keep
+    }
+
+    fn sample<T>(&mut self, data: Vec<T>) -> Vec<T> {
+        data.into_iter().filter(|_| self.keep()).collect()
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for SamplingSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        wp_log::info_ctrl!(
+            "[sample] {:?} passed {} of {} items",
+            self.rate,
+            self.seen - self.dropped,
+            self.seen
+        );
+        self.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for SamplingSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        if !self.keep() {
+            return Ok(());
+        }
+        self.inner.sink_record(data).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        let data = self.sample(data);
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.inner.sink_records(data).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for SamplingSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        if !self.keep() {
+            return Ok(());
+        }
+        self.inner.sink_str(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        if !self.keep() {
+            return Ok(());
+        }
+        self.inner.sink_bytes(data).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        let data = self.sample(data);
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.inner.sink_str_batch(data).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        let data = self.sample(data);
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.inner.sink_bytes_batch(data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use wp_model_core::model::DataField;
+
+    /// 统计收到的记录数，并保存记录的 `seq` 字段
+    #[derive(Default)]
+    struct CountingSink {
+        count: Arc<AtomicUsize>,
+        seqs: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl CountingSink {
+        fn push(&self, record: &DataRecord) {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            if let Some(seq) = record.get_value("seq") {
+                self.seqs.lock().unwrap().push(seq.to_string());
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for CountingSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for CountingSink {
+        async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+            self.push(data);
+            Ok(())
+        }
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            for record in &data {
+                self.push(record);
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for CountingSink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+        async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+            self.count.fetch_add(data.len(), Ordering::SeqCst);
+            Ok(())
+        }
+        async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+            self.count.fetch_add(data.len(), Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn sampled(rate: SampleRate) -> (SamplingSink, CountingSink) {
+        let inner = CountingSink::default();
+        let probe = CountingSink {
+            count: inner.count.clone(),
+            seqs: inner.seqs.clone(),
+        };
+        (SamplingSink::new(Box::new(inner), rate), probe)
+    }
+
+    fn record(seq: i64) -> DataRecord {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("seq", seq));
+        record
+    }
+
+    #[tokio::test]
+    async fn one_in_n_passes_every_nth_record_across_batches() {
+        let (mut sink, probe) = sampled(SampleRate::OneIn(10));
+        for seq in 0..35 {
+            sink.sink_record(&record(seq)).await.unwrap();
+        }
+        // 计数器延续到批量写入：第 35..100 条中放行 40、50、…、90
+        let batch = (35..100).map(|seq| Arc::new(record(seq))).collect();
+        sink.sink_records(batch).await.unwrap();
+
+        assert_eq!(probe.count.load(Ordering::SeqCst), 10);
+        let expect: Vec<String> = (0..100).step_by(10).map(|n| n.to_string()).collect();
+        assert_eq!(*probe.seqs.lock().unwrap(), expect);
+        assert_eq!(sink.dropped, 90);
+
+        sink.sink_str_batch(vec!["line"; 20]).await.unwrap();
+        assert_eq!(probe.count.load(Ordering::SeqCst), 12);
+    }
+
+    #[tokio::test]
+    async fn probability_passes_roughly_p_of_records() {
+        let (mut sink, probe) = sampled(SampleRate::Probability(0.25));
+        let batch = (0..4000).map(|seq| Arc::new(record(seq))).collect();
+        sink.sink_records(batch).await.unwrap();
+        let passed = probe.count.load(Ordering::SeqCst);
+        assert!((800..1200).contains(&passed), "passed {passed} of 4000");
+    }
+}
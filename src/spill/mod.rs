@@ -0,0 +1,114 @@
+//! 写入失败记录的落盘（dead-letter）装饰器
+//!
+//! DB 与 HTTP 类（HTTP/Elasticsearch/VictoriaLogs）Sink 配置 `spill_dir` 后，构建结果会被
+//! [`SpillSink`] 包装：写入失败时按 [`RetryPolicy`](crate::utils::retry::RetryPolicy) 重连并重试，
+//! 重试用尽后把该批记录以 JSON Lines 追加到 `{spill_dir}/{sink 名}.spill.jsonl`，每行形如：
+//!
+//! ```text
+//! {"error":"mysql insert fail: ...","record":{"id":1,"name":"a"}}
+//! ```
+//!
+//! 落盘成功即视为写入完成，不再向管道返回错误；未配置时行为不变。
+
+mod sink;
+
+pub use sink::SpillSink;
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use wp_connector_api::{SinkHandle, SinkReason, SinkResult, SinkSpec};
+
+/// 落盘目录参数名
+pub const SPILL_DIR_PARAM: &str = "spill_dir";
+
+/// 读取 `spill_dir` 参数；未配置时返回 `None`
+pub fn parse_spill_dir(spec: &SinkSpec) -> SinkResult<Option<PathBuf>> {
+    let dir = match spec.params.get(SPILL_DIR_PARAM) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(s)) if !s.trim().is_empty() => PathBuf::from(s.trim()),
+        Some(_) => {
+            return Err(SinkReason::sink(format!(
+                "{}.{SPILL_DIR_PARAM} must be a non-empty string",
+                spec.kind
+            ))
+            .into());
+        }
+    };
+    if dir.exists() && !dir.is_dir() {
+        return Err(SinkReason::sink(format!(
+            "{}.{SPILL_DIR_PARAM} '{}' is not a directory",
+            spec.kind,
+            dir.display()
+        ))
+        .into());
+    }
+    Ok(Some(dir))
+}
+
+/// 落盘文件路径：每个 Sink 一个文件
+pub fn spill_file(dir: &Path, spec: &SinkSpec) -> PathBuf {
+    dir.join(format!("{}.spill.jsonl", spec.name))
+}
+
+/// 按 `spill_dir` 参数包装已构建的 Sink；未配置时原样返回
+pub fn wrap_from_spec(spec: &SinkSpec, handle: SinkHandle) -> SinkResult<SinkHandle> {
+    let Some(dir) = parse_spill_dir(spec)? else {
+        return Ok(handle);
+    };
+    std::fs::create_dir_all(&dir).map_err(|err| {
+        SinkReason::sink(format!(
+            "{}.{SPILL_DIR_PARAM} create '{}' fail: {err}",
+            spec.kind,
+            dir.display()
+        ))
+    })?;
+    Ok(SinkHandle::new(Box::new(SpillSink::new(
+        handle.sink,
+        spec.kind.clone(),
+        spill_file(&dir, spec),
+    ))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn spec_with(value: Option<Value>) -> SinkSpec {
+        let mut params = BTreeMap::new();
+        if let Some(v) = value {
+            params.insert(SPILL_DIR_PARAM.to_string(), v);
+        }
+        SinkSpec {
+            group: "test".into(),
+            name: "orders".into(),
+            kind: "mysql".into(),
+            connector_id: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn parse_spill_dir_accepts_directory_and_rejects_invalid() {
+        assert_eq!(parse_spill_dir(&spec_with(None)).unwrap(), None);
+        let dir = std::env::temp_dir();
+        let parsed = parse_spill_dir(&spec_with(Some(json!(dir.to_string_lossy()))))
+            .unwrap()
+            .expect("dir");
+        assert_eq!(
+            spill_file(&parsed, &spec_with(None)),
+            dir.join("orders.spill.jsonl")
+        );
+
+        let file = dir.join(format!("wp_spill_not_dir_{}", std::process::id()));
+        std::fs::write(&file, b"x").unwrap();
+        for bad in [json!(""), json!(1), json!(file.to_string_lossy())] {
+            let err = parse_spill_dir(&spec_with(Some(bad))).expect_err("invalid spill_dir");
+            assert!(format!("{err}").contains("mysql.spill_dir"), "{err}");
+        }
+        let _ = std::fs::remove_file(&file);
+    }
+}
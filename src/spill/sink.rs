@@ -0,0 +1,303 @@
+//! 失败记录落盘 Sink 实现
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::io::AsyncWriteExt;
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, SinkError, SinkReason, SinkResult,
+};
+use wp_log::warn_data;
+use wp_model_core::model::DataRecord;
+
+use crate::utils::fmt::fmt_str_ndjson;
+use crate::utils::retry::RetryPolicy;
+
+/// 重试 `$write` 直到成功；用尽次数后把 `$payloads` 落盘
+macro_rules! retry_or_spill {
+    ($self:ident, $write:expr, $payloads:expr) => {{
+        let max_attempts = $self.policy.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match $write.await {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt >= max_attempts => return $self.spill($payloads, &err).await,
+                Err(err) => {
+                    $self.wait_and_reconnect(attempt, max_attempts, &err).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }};
+}
+
+/// 落盘装饰器，包装任意 [`AsyncSink`]，重试用尽的数据写入 JSON Lines 文件
+pub struct SpillSink {
+    inner: Box<dyn AsyncSink + 'static>,
+    /// 日志与错误信息中的 sink 类型
+    label: String,
+    path: PathBuf,
+    policy: RetryPolicy,
+}
+
+impl SpillSink {
+    pub fn new(inner: Box<dyn AsyncSink + 'static>, label: String, path: PathBuf) -> Self {
+        Self {
+            inner,
+            label,
+            path,
+            policy: RetryPolicy::reconnect(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    async fn wait_and_reconnect(&mut self, attempt: u32, max_attempts: u32, err: &SinkError) {
+        let delay = self.policy.backoff(attempt);
+        warn_data!(
+            "[{}] write attempt {}/{} failed: {}; retry in {:?}",
+            self.label,
+            attempt,
+            max_attempts,
+            err,
+            delay
+        );
+        tokio::time::sleep(delay).await;
+        if let Err(err) = self.inner.reconnect().await {
+            warn_data!("[{}] reconnect before retry failed: {}", self.label, err);
+        }
+    }
+
+    /// 每条数据一行：`{"error": ..., <key>: <payload>}`
+    async fn spill(
+        &mut self,
+        payloads: Vec<(&'static str, Value)>,
+        err: &SinkError,
+    ) -> SinkResult<()> {
+        let reason = err.to_string();
+        let mut buf = Vec::new();
+        for (key, payload) in &payloads {
+            let line = json!({ "error": reason, *key: payload });
+            buf.extend_from_slice(line.to_string().as_bytes());
+            buf.push(b'\n');
+        }
+        self.append(&buf).await.map_err(|io| {
+            SinkError::from(SinkReason::sink(format!(
+                "{} spill to '{}' fail: {io}; write error: {reason}",
+                self.label,
+                self.path.display()
+            )))
+        })?;
+        warn_data!(
+            "[{}] spilled {} entries to '{}' after retries: {}",
+            self.label,
+            payloads.len(),
+            self.path.display(),
+            reason
+        );
+        Ok(())
+    }
+
+    async fn append(&self, buf: &[u8]) -> std::io::Result<()> {
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(buf).await?;
+        file.flush().await
+    }
+}
+
+fn record_payloads(records: &[Arc<DataRecord>]) -> Vec<(&'static str, Value)> {
+    records
+        .iter()
+        .map(|record| {
+            let text = fmt_str_ndjson(vec![record.clone()]);
+            let value = serde_json::from_str(&text).unwrap_or(Value::String(text));
+            ("record", value)
+        })
+        .collect()
+}
+
+/// 原始数据按文本落盘；非 UTF-8 字节做有损替换
+fn raw_payloads<'a>(data: impl IntoIterator<Item = &'a [u8]>) -> Vec<(&'static str, Value)> {
+    data.into_iter()
+        .map(|raw| {
+            (
+                "raw",
+                Value::String(String::from_utf8_lossy(raw).into_owned()),
+            )
+        })
+        .collect()
+}
+
+#[async_trait]
+impl AsyncCtrl for SpillSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for SpillSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        retry_or_spill!(
+            self,
+            self.inner.sink_record(data),
+            record_payloads(&[Arc::new(data.clone())])
+        )
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        retry_or_spill!(
+            self,
+            self.inner.sink_records(data.clone()),
+            record_payloads(&data)
+        )
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for SpillSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        retry_or_spill!(
+            self,
+            self.inner.sink_str(data),
+            raw_payloads([data.as_bytes()])
+        )
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        retry_or_spill!(self, self.inner.sink_bytes(data), raw_payloads([data]))
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        retry_or_spill!(
+            self,
+            self.inner.sink_str_batch(data.clone()),
+            raw_payloads(data.iter().map(|s| s.as_bytes()))
+        )
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        retry_or_spill!(
+            self,
+            self.inner.sink_bytes_batch(data.clone()),
+            raw_payloads(data.iter().copied())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use wp_model_core::model::DataField;
+
+    const FAST: RetryPolicy =
+        RetryPolicy::new(3, Duration::from_millis(1), Duration::from_millis(5));
+
+    /// 始终写入失败的 Sink，统计写入与重连次数
+    #[derive(Default)]
+    struct FailingSink {
+        writes: Arc<AtomicUsize>,
+        reconnects: Arc<AtomicUsize>,
+    }
+
+    impl FailingSink {
+        fn fail(&self) -> SinkResult<()> {
+            self.writes.fetch_add(1, Ordering::SeqCst);
+            Err(SinkReason::sink("backend unavailable").into())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for FailingSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            self.reconnects.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for FailingSink {
+        async fn sink_record(&mut self, _data: &DataRecord) -> SinkResult<()> {
+            self.fail()
+        }
+        async fn sink_records(&mut self, _data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            self.fail()
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for FailingSink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            self.fail()
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            self.fail()
+        }
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            self.fail()
+        }
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            self.fail()
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_records_land_in_spill_file_with_error() {
+        let path = std::env::temp_dir().join(format!("wp_spill_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let inner = FailingSink::default();
+        let (writes, reconnects) = (inner.writes.clone(), inner.reconnects.clone());
+        let mut sink =
+            SpillSink::new(Box::new(inner), "mysql".into(), path.clone()).with_policy(FAST);
+
+        let records = (1..=2)
+            .map(|i| {
+                let mut record = DataRecord::default();
+                record.append(DataField::from_digit("id", i));
+                Arc::new(record)
+            })
+            .collect();
+        sink.sink_records(records).await.expect("spilled");
+        sink.sink_str("raw line").await.expect("spilled");
+        assert_eq!(writes.load(Ordering::SeqCst), 6);
+        assert_eq!(reconnects.load(Ordering::SeqCst), 4);
+
+        let lines: Vec<Value> = std::fs::read_to_string(&path)
+            .expect("spill file")
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("json line"))
+            .collect();
+        assert_eq!(lines.len(), 3);
+        for line in &lines {
+            assert!(
+                line["error"]
+                    .as_str()
+                    .unwrap()
+                    .contains("backend unavailable"),
+                "{line}"
+            );
+        }
+        assert_eq!(lines[0]["record"]["id"], json!(1));
+        assert_eq!(lines[1]["record"]["id"], json!(2));
+        assert_eq!(lines[2]["raw"], json!("raw line"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
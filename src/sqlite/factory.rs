@@ -0,0 +1,318 @@
+use async_trait::async_trait;
+use sea_orm::{ConnectOptions, ConnectionTrait, Database};
+use serde_json::{Value, json};
+use std::path::Path;
+use wp_connector_api::{
+    ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError, SinkFactory,
+    SinkHandle, SinkReason, SinkResult, SinkSpec,
+};
+
+use crate::spill::{self, SPILL_DIR_PARAM};
+use crate::sqlite::SqliteSink;
+use crate::sqlite::sink::{DEFAULT_BATCH, quote_ident};
+use crate::utils::bytes_encoding::{BYTES_ENCODING_PARAM, BytesEncoding, parse_bytes_encoding};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::error_kind::ErrorKind;
+
+/// 内存库路径，仅用于测试与临时数据
+const MEMORY_PATH: &str = ":memory:";
+
+/// 解析后的 SQLite Sink 参数
+#[derive(Debug, Clone, PartialEq)]
+struct SqliteSinkConf {
+    path: String,
+    table: String,
+    columns: Vec<String>,
+    batch: usize,
+    bytes_encoding: Option<BytesEncoding>,
+    /// 已替换 `{table}` 占位符的建表语句
+    create_table: Option<String>,
+}
+
+impl SqliteSinkConf {
+    fn database_url(&self) -> String {
+        if self.path == MEMORY_PATH {
+            "sqlite::memory:".to_string()
+        } else {
+            // `mode=rwc`：文件不存在时创建
+            format!("sqlite://{}?mode=rwc", self.path)
+        }
+    }
+}
+
+fn parse_sink_conf(spec: &SinkSpec) -> SinkResult<SqliteSinkConf> {
+    validate_decorators(spec)?;
+    spill::parse_spill_dir(spec)?;
+    let path = parse_path(spec.params.get("path"))?;
+    let table = match spec.params.get("table") {
+        None => spec.name.clone(),
+        Some(Value::String(s)) => s.trim().to_string(),
+        Some(_) => return Err(SinkReason::sink("sqlite.table must be a string").into()),
+    };
+    let quoted_table =
+        quote_ident(&table).map_err(|err| SinkReason::sink(format!("sqlite.table {err}")))?;
+    let columns = parse_columns(spec.params.get("columns"))?;
+    let batch = match spec.params.get("batch") {
+        None => DEFAULT_BATCH,
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => n as usize,
+            _ => return Err(SinkReason::sink("sqlite.batch must be a positive integer").into()),
+        },
+    };
+    let create_table = match spec.params.get("create_table") {
+        None => None,
+        Some(Value::String(s)) if !s.trim().is_empty() => Some(s.replace("{table}", &quoted_table)),
+        Some(_) => {
+            return Err(SinkReason::sink("sqlite.create_table must be a non-empty string").into());
+        }
+    };
+    Ok(SqliteSinkConf {
+        path,
+        table,
+        columns,
+        batch,
+        bytes_encoding: parse_bytes_encoding(spec)?,
+        create_table,
+    })
+}
+
+/// 校验数据库文件路径：非空、不是目录，且所在目录已存在
+fn parse_path(value: Option<&Value>) -> SinkResult<String> {
+    let path = match value {
+        Some(Value::String(s)) if !s.trim().is_empty() => s.trim().to_string(),
+        _ => return Err(SinkReason::sink("sqlite.path must not be empty").into()),
+    };
+    if path == MEMORY_PATH {
+        return Ok(path);
+    }
+    let file = Path::new(&path);
+    if file.is_dir() {
+        return Err(SinkReason::sink(format!("sqlite.path '{path}' is a directory")).into());
+    }
+    if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty())
+        && !parent.is_dir()
+    {
+        return Err(SinkReason::sink(format!(
+            "sqlite.path parent directory '{}' does not exist",
+            parent.display()
+        ))
+        .into());
+    }
+    Ok(path)
+}
+
+fn parse_columns(value: Option<&Value>) -> SinkResult<Vec<String>> {
+    let Some(arr) = value.and_then(Value::as_array) else {
+        return Err(SinkReason::sink("sqlite.columns must be a non-empty array").into());
+    };
+    let mut out = Vec::with_capacity(arr.len());
+    for item in arr {
+        let Some(column) = item.as_str() else {
+            return Err(SinkReason::sink("sqlite.columns entries must be string").into());
+        };
+        quote_ident(column).map_err(|err| SinkReason::sink(format!("sqlite.columns {err}")))?;
+        out.push(column.trim().to_string());
+    }
+    if out.is_empty() {
+        return Err(SinkReason::sink("sqlite.columns must be a non-empty array").into());
+    }
+    Ok(out)
+}
+
+pub struct SqliteSinkFactory;
+
+#[async_trait]
+impl SinkFactory for SqliteSinkFactory {
+    fn kind(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        ErrorKind::Config.tag_sink("sqlite", parse_sink_conf(spec))?;
+        Ok(())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let conf = ErrorKind::Config.tag_sink("sqlite", parse_sink_conf(spec))?;
+        // SQLite 同一时刻只允许一个写者；内存库也依赖单连接保持同一份数据
+        let mut opt = ConnectOptions::new(conf.database_url());
+        opt.max_connections(1)
+            .min_connections(1)
+            .sqlx_logging(false);
+        let db = Database::connect(opt).await.map_err(|err| {
+            SinkError::from(ErrorKind::Connect.sink("sqlite", format!("open sqlite fail: {err}")))
+        })?;
+        if let Some(sql) = &conf.create_table {
+            db.execute_unprepared(sql).await.map_err(|err| {
+                SinkError::from(
+                    ErrorKind::Backend.sink("sqlite", format!("sqlite create_table fail: {err}")),
+                )
+            })?;
+        }
+        let sink = SqliteSink::new(db, conf.table, conf.columns)
+            .with_batch(conf.batch)
+            .with_bytes_encoding(conf.bytes_encoding);
+        let handle = spill::wrap_from_spec(spec, SinkHandle::new(Box::new(sink)))?;
+        decorate_sink(spec, handle)
+    }
+}
+
+impl SinkDefProvider for SqliteSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "sqlite_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "path",
+                "table",
+                "columns",
+                "batch",
+                "create_table",
+                BYTES_ENCODING_PARAM,
+                SPILL_DIR_PARAM,
+            ]
+            .into_iter()
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect(),
+            default_params: sqlite_sink_defaults(),
+            origin: Some("wp-connectors:sqlite_sink".into()),
+        }
+    }
+}
+
+fn sqlite_sink_defaults() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("path".into(), json!("wp_events.db"));
+    params.insert("table".into(), json!("wp_events"));
+    params.insert("columns".into(), json!(["wp_event_id", "payload"]));
+    params.insert("batch".into(), json!(DEFAULT_BATCH));
+    params.insert(
+        "create_table".into(),
+        json!("CREATE TABLE IF NOT EXISTS {table} (wp_event_id INTEGER, payload TEXT)"),
+    );
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseConnection, Statement};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use wp_model_core::model::{DataField, DataRecord};
+
+    fn build_sink_spec(params: BTreeMap<String, Value>) -> SinkSpec {
+        SinkSpec {
+            name: "sqlite_sink".into(),
+            kind: "sqlite".into(),
+            connector_id: "connector".into(),
+            group: "group".into(),
+            params,
+            filter: None,
+        }
+    }
+
+    fn base_params(path: &str) -> BTreeMap<String, Value> {
+        BTreeMap::from([
+            ("path".into(), json!(path)),
+            ("table".into(), json!("events")),
+            ("columns".into(), json!(["id", "name"])),
+        ])
+    }
+
+    #[test]
+    fn sqlite_validate_checks_path_and_columns() {
+        let factory = SqliteSinkFactory;
+        factory
+            .validate_spec(&build_sink_spec(base_params(":memory:")))
+            .expect("memory db");
+
+        let dir = std::env::temp_dir();
+        let cases = [
+            ("path", json!(""), "sqlite.path must not be empty"),
+            ("path", json!(dir.to_string_lossy()), "is a directory"),
+            (
+                "path",
+                json!(dir.join("wp_no_such_dir/events.db").to_string_lossy()),
+                "does not exist",
+            ),
+            (
+                "columns",
+                json!([]),
+                "sqlite.columns must be a non-empty array",
+            ),
+            ("columns", json!([" "]), "identifier must not be empty"),
+            ("batch", json!(0), "sqlite.batch must be a positive integer"),
+            ("create_table", json!(" "), "sqlite.create_table"),
+        ];
+        for (key, value, expect) in cases {
+            let mut params = base_params(":memory:");
+            params.insert(key.into(), value);
+            let err = factory
+                .validate_spec(&build_sink_spec(params))
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+            assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Config));
+        }
+    }
+
+    #[test]
+    fn sqlite_create_table_template_uses_quoted_table() {
+        let mut params = base_params(":memory:");
+        params.insert(
+            "create_table".into(),
+            json!("CREATE TABLE IF NOT EXISTS {table} (id INTEGER)"),
+        );
+        let conf = parse_sink_conf(&build_sink_spec(params)).expect("conf");
+        assert_eq!(
+            conf.create_table.as_deref(),
+            Some("CREATE TABLE IF NOT EXISTS \"events\" (id INTEGER)")
+        );
+    }
+
+    #[tokio::test]
+    async fn sqlite_factory_creates_table_and_writes_file() {
+        let path = std::env::temp_dir().join(format!("wp_sqlite_sink_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut params = base_params(&path.to_string_lossy());
+        params.insert(
+            "create_table".into(),
+            json!("CREATE TABLE IF NOT EXISTS {table} (id INTEGER, name TEXT)"),
+        );
+        params.insert("batch".into(), json!(2));
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+        let mut handle = SqliteSinkFactory
+            .build(&build_sink_spec(params), &ctx)
+            .await
+            .expect("build sqlite sink");
+        let records = (0..3)
+            .map(|i| {
+                let mut record = DataRecord::default();
+                record.append(DataField::from_digit("id", i));
+                record.append(DataField::from_chars("name", "n"));
+                Arc::new(record)
+            })
+            .collect();
+        handle.sink.sink_records(records).await.expect("write");
+        handle.sink.stop().await.expect("stop");
+
+        let db: DatabaseConnection = Database::connect(format!("sqlite://{}", path.display()))
+            .await
+            .expect("reopen");
+        let count: i64 = db
+            .query_one(Statement::from_string(
+                db.get_database_backend(),
+                "SELECT COUNT(*) FROM events",
+            ))
+            .await
+            .expect("count")
+            .expect("row")
+            .try_get_by_index(0)
+            .unwrap();
+        assert_eq!(count, 3);
+        drop(db);
+        let _ = std::fs::remove_file(&path);
+    }
+}
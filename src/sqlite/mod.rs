@@ -0,0 +1,11 @@
+//! wp-connector-sqlite: SQLite Sink + Factory
+//!
+//! 面向边缘/嵌入式部署，写入本地 SQLite 文件：
+//! - sink：SqliteSink（参数化多 VALUES INSERT，整批一个事务）
+//! - factory：Sink 工厂，校验文件路径并按 `create_table` 模板建表
+
+mod factory;
+mod sink;
+
+pub use factory::SqliteSinkFactory;
+pub use sink::SqliteSink;
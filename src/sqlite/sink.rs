@@ -0,0 +1,429 @@
+use async_trait::async_trait;
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement, TransactionTrait};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkResult};
+use wp_log::error_data;
+use wp_model_core::model::{DataRecord, DataType, Value};
+
+use crate::utils::bytes_encoding::BytesEncoding;
+use crate::utils::db_metrics::record_flush;
+use crate::utils::error_kind::ErrorKind;
+use crate::utils::retry::{RetryPolicy, reconnect_with_backoff};
+use crate::utils::shutdown::DeadlineCtrl;
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
+
+/// 单条语句允许的绑定参数上限（SQLite 3.32 起 `SQLITE_MAX_VARIABLE_NUMBER` 的默认值）
+pub(crate) const SQLITE_MAX_VARIABLES: usize = 32766;
+
+/// 单条 INSERT 语句的默认行数
+pub(crate) const DEFAULT_BATCH: usize = 500;
+
+/// 以双引号引用标识符，内部的双引号写作两个双引号；名称为空时返回错误
+pub(crate) fn quote_ident(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("identifier must not be empty".into());
+    }
+    Ok(quote(name))
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub struct SqliteSink {
+    pub db: DatabaseConnection,
+    pub table: String,
+    pub columns: Vec<String>,
+    /// 单条 INSERT 语句包含的最大行数，另受绑定参数上限约束
+    pub batch: usize,
+    /// 字节类字段的文本编码，`None` 时按原样输出
+    pub bytes_encoding: Option<BytesEncoding>,
+    counters: SinkCounters,
+}
+
+impl SqliteSink {
+    pub fn new(db: DatabaseConnection, table: String, columns: Vec<String>) -> Self {
+        Self {
+            db,
+            table,
+            columns,
+            batch: DEFAULT_BATCH,
+            bytes_encoding: None,
+            counters: SinkCounters::default(),
+        }
+    }
+
+    /// 设置字节类字段的文本编码
+    pub fn with_bytes_encoding(mut self, bytes_encoding: Option<BytesEncoding>) -> Self {
+        self.bytes_encoding = bytes_encoding;
+        self
+    }
+
+    /// 设置单条 INSERT 语句的最大行数
+    pub fn with_batch(mut self, batch: usize) -> Self {
+        self.batch = batch.max(1);
+        self
+    }
+
+    /// 实际每条语句的行数：不超过 `batch`，且参数个数不超过 SQLite 上限
+    fn rows_per_statement(&self) -> usize {
+        let by_vars = SQLITE_MAX_VARIABLES / self.columns.len().max(1);
+        self.batch.min(by_vars).max(1)
+    }
+
+    fn base_insert_prefix(&self) -> String {
+        // 表名与列名在工厂中已通过 `quote_ident` 校验非空
+        let columns = self
+            .columns
+            .iter()
+            .map(|c| quote(c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("INSERT INTO {} ({columns}) VALUES ", quote(&self.table))
+    }
+
+    /// 将一批记录拆分为若干条参数化的多 VALUES INSERT 语句
+    fn build_insert_statements(&self, records: &[Arc<DataRecord>]) -> Vec<Statement> {
+        let placeholders = format!("({})", vec!["?"; self.columns.len()].join(", "));
+        records
+            .chunks(self.rows_per_statement())
+            .map(|chunk| {
+                let mut sql = self.base_insert_prefix();
+                sql.push_str(&vec![placeholders.as_str(); chunk.len()].join(","));
+                let values = chunk
+                    .iter()
+                    .flat_map(|record| self.row_values(record))
+                    .collect::<Vec<_>>();
+                Statement::from_sql_and_values(self.db.get_database_backend(), sql, values)
+            })
+            .collect()
+    }
+
+    /// 按列顺序取出记录中的值，缺失列绑定为 NULL
+    fn row_values(&self, record: &DataRecord) -> Vec<sea_orm::Value> {
+        let field_map: HashMap<&str, &Value> = record
+            .items
+            .iter()
+            .filter(|f| *f.get_meta() != DataType::Ignore)
+            .map(|f| (f.get_name(), f.get_value()))
+            .collect();
+        self.columns
+            .iter()
+            .map(|column| match field_map.get(column.as_str()) {
+                Some(value) => match self.bytes_encoding.and_then(|e| e.render(value)) {
+                    Some(text) => text.into(),
+                    None => bind_value(value),
+                },
+                None => {
+                    error_data!("Warning: Missing field for column '{}'", column);
+                    sea_orm::Value::String(None)
+                }
+            })
+            .collect()
+    }
+
+    /// 在同一事务中执行整批语句：任一语句失败则回滚，保证批次全有或全无
+    async fn execute_in_transaction(&self, stmts: Vec<Statement>) -> SinkResult<()> {
+        let txn = self.db.begin().await.map_err(|e| {
+            SinkError::from(
+                ErrorKind::Connect.sink("sqlite", format!("sqlite begin transaction fail: {}", e)),
+            )
+        })?;
+        for stmt in stmts {
+            if let Err(e) = txn.execute(stmt).await {
+                if let Err(rollback_err) = txn.rollback().await {
+                    error_data!("sqlite rollback fail: {}", rollback_err);
+                }
+                return Err(SinkError::from(ErrorKind::Backend.sink(
+                    "sqlite",
+                    format!("sqlite exec columns:{:?}, fail: {}", self.columns, e),
+                )));
+            }
+        }
+        txn.commit().await.map_err(|e| {
+            SinkError::from(ErrorKind::Backend.sink("sqlite", format!("sqlite commit fail: {}", e)))
+        })
+    }
+}
+
+/// 数值与布尔按原类型绑定，其余类型按字符串写入（由列亲和性决定存储类型）
+fn bind_value(value: &Value) -> sea_orm::Value {
+    match value {
+        Value::Null => sea_orm::Value::String(None),
+        Value::Bool(b) => (*b).into(),
+        Value::Digit(i) => (*i).into(),
+        Value::Float(f) => (*f).into(),
+        other => other.to_string().into(),
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for SqliteSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        reconnect_with_backoff("sqlite", RetryPolicy::reconnect(), || async {
+            self.db.ping().await.map_err(|e| {
+                SinkError::from(
+                    ErrorKind::Connect.sink("sqlite", format!("reconnect sqlite fail: {}", e)),
+                )
+            })
+        })
+        .await
+    }
+}
+
+// 每批数据在 `sink_records` 内以事务提交，停止时没有待排空的缓冲
+impl DeadlineCtrl for SqliteSink {}
+
+impl SinkMetrics for SqliteSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for SqliteSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.sink_records(vec![Arc::new(data.clone())]).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.counters.receive(data.len());
+        let stmts = self.build_insert_statements(&data);
+        // 参数化语句只计 SQL 文本
+        let bytes = stmts.iter().map(|stmt| stmt.sql.len()).sum();
+        let result = self.execute_in_transaction(stmts).await;
+        record_flush("sqlite", &self.table, data.len(), &result);
+        self.counters.finish(data.len(), bytes, &result);
+        result
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for SqliteSink {
+    async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+        Err(SinkError::from(
+            ErrorKind::Config.sink("sqlite", "sqlite sink does not accept raw input"),
+        ))
+    }
+    async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+        Err(SinkError::from(
+            ErrorKind::Config.sink("sqlite", "sqlite sink does not accept raw bytes"),
+        ))
+    }
+
+    async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+        Err(SinkError::from(
+            ErrorKind::Config.sink("sqlite", "sqlite sink does not accept raw input"),
+        ))
+    }
+    async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+        Err(SinkError::from(
+            ErrorKind::Config.sink("sqlite", "sqlite sink does not accept raw bytes"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BytesEncoding, SqliteSink, quote_ident};
+    use crate::utils::sink_metrics::{SinkMetrics, SinkMetricsSnapshot};
+    use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, Statement};
+    use std::sync::Arc;
+    use wp_connector_api::AsyncRecordSink;
+    use wp_model_core::model::{DataField, DataRecord};
+
+    async fn memory_db() -> DatabaseConnection {
+        // 内存库按连接隔离，只保留一个连接
+        let mut opt = ConnectOptions::new("sqlite::memory:");
+        opt.max_connections(1)
+            .min_connections(1)
+            .sqlx_logging(false);
+        let db = Database::connect(opt).await.expect("open sqlite memory db");
+        db.execute_unprepared("CREATE TABLE events (id INTEGER, name TEXT, score REAL)")
+            .await
+            .expect("create table");
+        db
+    }
+
+    async fn rows(db: &DatabaseConnection) -> Vec<(i64, Option<String>)> {
+        let stmt = Statement::from_string(
+            db.get_database_backend(),
+            "SELECT id, name FROM events ORDER BY id",
+        );
+        db.query_all(stmt)
+            .await
+            .expect("select")
+            .iter()
+            .map(|row| {
+                (
+                    row.try_get_by_index(0).unwrap(),
+                    row.try_get_by_index(1).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn record(id: i64, name: &str) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", id));
+        record.append(DataField::from_chars("name", name));
+        record.append(DataField::from_ignore("unused"));
+        Arc::new(record)
+    }
+
+    fn columns() -> Vec<String> {
+        vec!["id".into(), "name".into()]
+    }
+
+    #[test]
+    fn sqlite_quote_ident_escapes_double_quotes() {
+        assert_eq!(quote_ident(" events ").unwrap(), "\"events\"");
+        assert_eq!(quote_ident("my table").unwrap(), "\"my table\"");
+        assert_eq!(quote_ident("a\"b").unwrap(), "\"a\"\"b\"");
+        assert_eq!(quote_ident("a]b").unwrap(), "\"a]b\"");
+        assert_eq!(quote_ident("src.ip").unwrap(), "\"src.ip\"");
+        assert_eq!(quote_ident("select").unwrap(), "\"select\"");
+        assert!(quote_ident("").is_err());
+    }
+
+    #[tokio::test]
+    async fn sqlite_sink_inserts_rows_with_bound_values() {
+        let mut sink = SqliteSink::new(memory_db().await, "events".into(), columns());
+        sink.sink_records(vec![
+            record(1, "O'Reilly"),
+            record(2, "x); DROP TABLE events; --"),
+        ])
+        .await
+        .expect("insert");
+
+        let mut missing = DataRecord::default();
+        missing.append(DataField::from_digit("id", 3));
+        sink.sink_record(&missing).await.expect("insert with null");
+
+        assert_eq!(
+            rows(&sink.db).await,
+            vec![
+                (1, Some("O'Reilly".into())),
+                (2, Some("x); DROP TABLE events; --".into())),
+                (3, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn sqlite_sink_bytes_encoding_renders_hex_fields() {
+        use wp_model_core::model::{DataType, HexT, Value};
+
+        let mut sink = SqliteSink::new(memory_db().await, "events".into(), columns())
+            .with_bytes_encoding(Some(BytesEncoding::Base64));
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 1));
+        record.append(DataField::new(
+            DataType::Hex,
+            "name",
+            Value::Hex(HexT(0x7770)),
+        ));
+        sink.sink_record(&record).await.expect("insert");
+
+        assert_eq!(rows(&sink.db).await, vec![(1, Some("d3A=".into()))]);
+    }
+
+    #[tokio::test]
+    async fn sqlite_sink_splits_batches_into_statements() {
+        let mut sink = SqliteSink::new(memory_db().await, "events".into(), columns()).with_batch(2);
+        let records: Vec<_> = (0..5).map(|i| record(i, "n")).collect();
+
+        let stmts = sink.build_insert_statements(&records);
+        assert_eq!(stmts.len(), 3);
+        assert_eq!(
+            stmts[0].sql,
+            "INSERT INTO \"events\" (\"id\", \"name\") VALUES (?, ?),(?, ?)"
+        );
+        assert_eq!(stmts[2].values.as_ref().map(|v| v.0.len()), Some(2));
+
+        sink.sink_records(records).await.expect("insert");
+        let ids: Vec<_> = rows(&sink.db).await.into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn sqlite_sink_metrics_track_flushed_and_failed_rows() {
+        let db = memory_db().await;
+        db.execute_unprepared("CREATE TABLE strict_events (id INTEGER NOT NULL, name TEXT)")
+            .await
+            .expect("create table");
+        let mut sink = SqliteSink::new(db, "strict_events".into(), columns());
+        assert_eq!(sink.metrics(), SinkMetricsSnapshot::default());
+
+        sink.sink_records((0..3).map(|i| record(i, "ok")).collect())
+            .await
+            .expect("insert");
+        sink.sink_record(&record(3, "ok")).await.expect("insert");
+        let mut no_id = DataRecord::default();
+        no_id.append(DataField::from_chars("name", "bad"));
+        sink.sink_records(vec![record(4, "a"), Arc::new(no_id)])
+            .await
+            .expect_err("not null violation");
+
+        let sql_len = |rows: usize| {
+            "INSERT INTO \"strict_events\" (\"id\", \"name\") VALUES ".len()
+                + vec!["(?, ?)"; rows].join(",").len()
+        };
+        assert_eq!(
+            sink.metrics(),
+            SinkMetricsSnapshot {
+                received: 6,
+                flushed: 4,
+                failed: 2,
+                bytes_sent: (sql_len(3) + sql_len(1)) as u64,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn sqlite_sink_rolls_back_failed_batch() {
+        let db = memory_db().await;
+        db.execute_unprepared("CREATE TABLE strict_events (id INTEGER NOT NULL, name TEXT)")
+            .await
+            .expect("create table");
+        let mut sink = SqliteSink::new(db, "strict_events".into(), columns()).with_batch(1);
+        let mut no_id = DataRecord::default();
+        no_id.append(DataField::from_chars("name", "c"));
+
+        // 第三条语句违反 NOT NULL，前两条随事务一起回滚
+        let err = sink
+            .sink_records(vec![record(1, "a"), record(2, "b"), Arc::new(no_id)])
+            .await
+            .expect_err("not null violation");
+        assert!(err.to_string().contains("[sqlite:backend]"), "{err}");
+        let count = sink
+            .db
+            .query_one(Statement::from_string(
+                sink.db.get_database_backend(),
+                "SELECT COUNT(*) FROM strict_events",
+            ))
+            .await
+            .expect("count")
+            .expect("row")
+            .try_get_by_index::<i64>(0)
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn sqlite_sink_caps_rows_by_variable_limit() {
+        let columns = (0..1000).map(|i| format!("c{i}")).collect();
+        let sink =
+            SqliteSink::new(DatabaseConnection::default(), "wide".into(), columns).with_batch(500);
+        assert_eq!(sink.rows_per_statement(), 32);
+    }
+}
@@ -0,0 +1,253 @@
+//! Sink 写入前的字段变换
+//!
+//! 任意 Sink 都可以通过以下通用参数在写入前修改记录，由 [`TransformingSink`] 执行：
+//!
+//! - `add_fields`：追加常量字段，值可以是字符串、整数、浮点数或布尔值；
+//! - `rename_fields`：字段改名，`{ 原字段名 = "新字段名" }`；
+//! - `overwrite`：追加的字段与已有字段同名时是否覆盖，默认 `false`（保留原值）。
+//!
+//! 先改名再追加，因此追加字段不会被改名，`overwrite` 判断的也是改名后的字段名。
+//! 原始文本/字节不做变换，直接透传。
+
+mod sink;
+
+pub use sink::TransformingSink;
+
+use serde_json::Value;
+use wp_connector_api::{SinkHandle, SinkReason, SinkResult, SinkSpec};
+use wp_model_core::model::{DataField, DataRecord};
+
+/// 追加字段参数名
+pub const ADD_FIELDS_PARAM: &str = "add_fields";
+/// 字段改名参数名
+pub const RENAME_FIELDS_PARAM: &str = "rename_fields";
+/// 覆盖开关参数名
+pub const OVERWRITE_PARAM: &str = "overwrite";
+
+/// 解析后的字段变换规则
+#[derive(Debug, Clone, Default)]
+pub struct Transform {
+    add: Vec<DataField>,
+    rename: Vec<(String, String)>,
+    overwrite: bool,
+}
+
+impl Transform {
+    /// 追加常量字段
+    pub fn adding(mut self, field: DataField) -> Self {
+        self.add.push(field);
+        self
+    }
+
+    /// 将字段 `from` 改名为 `to`
+    pub fn renaming(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.rename.push((from.into(), to.into()));
+        self
+    }
+
+    /// 追加字段是否覆盖已有的同名字段
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.rename.is_empty()
+    }
+
+    /// 就地变换一条记录
+    ///
+    /// 改名目标已存在时，原有的目标字段被移除，避免出现重名字段。
+    pub fn apply(&self, record: &mut DataRecord) {
+        for (from, to) in &self.rename {
+            if record.field(from).is_none() {
+                continue;
+            }
+            record.remove_field(to);
+            if let Some(field) = record.field_mut(from) {
+                field.set_name(to.as_str());
+            }
+        }
+        for field in &self.add {
+            let name = field.get_name();
+            if record.field(name).is_some() {
+                if !self.overwrite {
+                    continue;
+                }
+                record.remove_field(name);
+            }
+            record.append(field.clone());
+        }
+    }
+}
+
+/// 读取 `add_fields` / `rename_fields` / `overwrite` 参数；均未配置时返回 `None`
+pub fn parse_transform(spec: &SinkSpec) -> SinkResult<Option<Transform>> {
+    let kind = spec.kind.as_str();
+    let mut transform = Transform::default();
+    if let Some(fields) = table_param(spec, ADD_FIELDS_PARAM)? {
+        for (name, value) in fields {
+            transform.add.push(constant_field(kind, name, value)?);
+        }
+    }
+    if let Some(fields) = table_param(spec, RENAME_FIELDS_PARAM)? {
+        for (from, to) in fields {
+            match to.as_str().map(str::trim) {
+                Some(to) if !to.is_empty() => transform.rename.push((from.clone(), to.to_string())),
+                _ => {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.{RENAME_FIELDS_PARAM}.{from} must be a non-empty field name"
+                    ))
+                    .into());
+                }
+            }
+        }
+    }
+    match spec.params.get(OVERWRITE_PARAM) {
+        None | Some(Value::Null) => {}
+        Some(Value::Bool(flag)) => transform.overwrite = *flag,
+        Some(_) => {
+            return Err(
+                SinkReason::sink(format!("{kind}.{OVERWRITE_PARAM} must be a boolean")).into(),
+            );
+        }
+    }
+    Ok((!transform.is_empty()).then_some(transform))
+}
+
+/// 按变换参数包装已构建的 Sink；未配置时原样返回
+pub fn wrap_from_spec(spec: &SinkSpec, handle: SinkHandle) -> SinkResult<SinkHandle> {
+    match parse_transform(spec)? {
+        Some(transform) => Ok(SinkHandle::new(Box::new(TransformingSink::new(
+            handle.sink,
+            transform,
+        )))),
+        None => Ok(handle),
+    }
+}
+
+fn table_param<'a>(
+    spec: &'a SinkSpec,
+    key: &str,
+) -> SinkResult<Option<&'a serde_json::Map<String, Value>>> {
+    match spec.params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Object(map)) => {
+            if map.keys().any(|name| name.trim().is_empty()) {
+                return Err(SinkReason::sink(format!(
+                    "{}.{key} field names must not be empty",
+                    spec.kind
+                ))
+                .into());
+            }
+            Ok(Some(map))
+        }
+        Some(_) => Err(SinkReason::sink(format!("{}.{key} must be a table", spec.kind)).into()),
+    }
+}
+
+fn constant_field(kind: &str, name: &str, value: &Value) -> SinkResult<DataField> {
+    let field = match value {
+        Value::String(s) => DataField::from_chars(name, s.as_str()),
+        Value::Bool(b) => DataField::from_bool(name, *b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => DataField::from_digit(name, i),
+            None => match n.as_f64() {
+                Some(f) => DataField::from_float(name, f),
+                None => {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.{ADD_FIELDS_PARAM}.{name} is out of range"
+                    ))
+                    .into());
+                }
+            },
+        },
+        _ => {
+            return Err(SinkReason::sink(format!(
+                "{kind}.{ADD_FIELDS_PARAM}.{name} must be a string, number or boolean"
+            ))
+            .into());
+        }
+    };
+    Ok(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn spec_with(entries: &[(&str, Value)]) -> SinkSpec {
+        let params: BTreeMap<String, Value> = entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        SinkSpec {
+            group: "test".into(),
+            name: "transformed".into(),
+            kind: "count".into(),
+            connector_id: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn parse_builds_rules_and_skips_empty_config() {
+        assert!(parse_transform(&spec_with(&[])).unwrap().is_none());
+        assert!(
+            parse_transform(&spec_with(&[("overwrite", json!(true))]))
+                .unwrap()
+                .is_none()
+        );
+
+        let transform = parse_transform(&spec_with(&[
+            (
+                "add_fields",
+                json!({"env": "prod", "dc": 3, "ratio": 0.5, "tagged": true}),
+            ),
+            ("rename_fields", json!({"src": "src_ip"})),
+            ("overwrite", json!(true)),
+        ]))
+        .unwrap()
+        .expect("transform");
+        assert_eq!(transform.add.len(), 4);
+        assert_eq!(
+            transform.rename,
+            vec![("src".to_string(), "src_ip".to_string())]
+        );
+        assert!(transform.overwrite);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_values() {
+        for (key, bad, expect) in [
+            (
+                "add_fields",
+                json!(["env"]),
+                "count.add_fields must be a table",
+            ),
+            ("add_fields", json!({"env": [1]}), "count.add_fields.env"),
+            (
+                "add_fields",
+                json!({" ": "x"}),
+                "count.add_fields field names",
+            ),
+            (
+                "rename_fields",
+                json!({"src": ""}),
+                "count.rename_fields.src",
+            ),
+            (
+                "rename_fields",
+                json!({"src": 1}),
+                "count.rename_fields.src",
+            ),
+            ("overwrite", json!("yes"), "count.overwrite"),
+        ] {
+            let err = parse_transform(&spec_with(&[(key, bad)])).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! 字段变换 Sink 实现
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use wp_connector_api::{AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, SinkResult};
+use wp_model_core::model::DataRecord;
+
+use super::Transform;
+
+/// 变换装饰器：按 [`Transform`] 修改每条记录后交给内部 Sink
+///
+/// 单条写入时复制记录；批量写入时对独占的 `Arc` 就地修改，共享的记录才复制。
+pub struct TransformingSink {
+    inner: Box<dyn AsyncSink + 'static>,
+    transform: Transform,
+}
+
+impl TransformingSink {
+    pub fn new(inner: Box<dyn AsyncSink + 'static>, transform: Transform) -> Self {
+        Self { inner, transform }
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for TransformingSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for TransformingSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        let mut record = data.clone();
+        self.transform.apply(&mut record);
+        self.inner.sink_record(&record).await
+    }
+
+    async fn sink_records(&mut self, mut data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        for record in &mut data {
+            self.transform.apply(Arc::make_mut(record));
+        }
+        self.inner.sink_records(data).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for TransformingSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.inner.sink_str(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.inner.sink_bytes(data).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.inner.sink_str_batch(data).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.inner.sink_bytes_batch(data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use wp_model_core::model::DataField;
+
+    type Collected = Arc<Mutex<Vec<Vec<(String, String)>>>>;
+
+    /// 记录收到的每条记录的 `(字段名, 值)` 列表
+    #[derive(Default)]
+    struct CollectSink {
+        records: Collected,
+    }
+
+    impl CollectSink {
+        fn push(&self, record: &DataRecord) {
+            let fields = record
+                .items
+                .iter()
+                .map(|f| (f.get_name().to_string(), f.get_value().to_string()))
+                .collect();
+            self.records.lock().unwrap().push(fields);
+        }
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for CollectSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for CollectSink {
+        async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+            self.push(data);
+            Ok(())
+        }
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            data.iter().for_each(|r| self.push(r));
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for CollectSink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    fn record() -> DataRecord {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("src", "10.0.0.1"));
+        record.append(DataField::from_chars("env", "dev"));
+        record
+    }
+
+    fn transformed(transform: Transform) -> (TransformingSink, Collected) {
+        let inner = CollectSink::default();
+        let records = inner.records.clone();
+        (TransformingSink::new(Box::new(inner), transform), records)
+    }
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn add_fields_appends_constants() {
+        let transform = Transform::default()
+            .adding(DataField::from_chars("cluster", "east"))
+            .adding(DataField::from_digit("dc", 3));
+        let (mut sink, records) = transformed(transform);
+
+        let original = record();
+        sink.sink_record(&original).await.unwrap();
+        sink.sink_records(vec![Arc::new(record())]).await.unwrap();
+
+        let expected = pairs(&[
+            ("src", "10.0.0.1"),
+            ("env", "dev"),
+            ("cluster", "east"),
+            ("dc", "3"),
+        ]);
+        assert_eq!(*records.lock().unwrap(), vec![expected.clone(), expected]);
+        assert_eq!(original.items.len(), 2, "caller's record is untouched");
+    }
+
+    #[tokio::test]
+    async fn rename_fields_renames_and_replaces_target() {
+        let transform = Transform::default()
+            .renaming("src", "env")
+            .renaming("missing", "other");
+        let (mut sink, records) = transformed(transform);
+
+        sink.sink_record(&record()).await.unwrap();
+
+        assert_eq!(
+            *records.lock().unwrap(),
+            vec![pairs(&[("env", "10.0.0.1")])]
+        );
+    }
+
+    #[tokio::test]
+    async fn existing_fields_are_overwritten_only_when_enabled() {
+        let keep = Transform::default().adding(DataField::from_chars("env", "prod"));
+        let (mut sink, records) = transformed(keep.clone());
+        sink.sink_record(&record()).await.unwrap();
+        assert_eq!(
+            *records.lock().unwrap(),
+            vec![pairs(&[("src", "10.0.0.1"), ("env", "dev")])]
+        );
+
+        let (mut sink, records) = transformed(keep.overwrite(true));
+        let shared = Arc::new(record());
+        sink.sink_records(vec![shared.clone()]).await.unwrap();
+        assert_eq!(
+            *records.lock().unwrap(),
+            vec![pairs(&[("src", "10.0.0.1"), ("env", "prod")])]
+        );
+        assert_eq!(
+            shared.get_value("env").map(|v| v.to_string()),
+            Some("dev".to_string()),
+            "shared records are copied before mutation"
+        );
+    }
+}
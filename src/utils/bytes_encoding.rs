@@ -0,0 +1,201 @@
+//! 字节类字段的文本表示
+//!
+//! DB Sink 通过 `bytes_encoding = "base64" | "hex" | "utf8_lossy"` 指定字节类字段写出时的
+//! 文本形式，保证同一份配置下二进制内容的表示方式一致。当前记录模型中的字节类字段只有
+//! `Value::Hex`，按最短大端字节序取出原始字节后编码。未配置时保持原有输出（`0x..`）。
+
+use base64::{Engine as _, engine::general_purpose};
+use std::sync::Arc;
+use wp_connector_api::{SinkReason, SinkResult, SinkSpec};
+use wp_model_core::model::{DataField, DataRecord, HexT, Value};
+
+/// `bytes_encoding` 参数名
+pub const BYTES_ENCODING_PARAM: &str = "bytes_encoding";
+
+/// 字节数据的文本编码方式
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BytesEncoding {
+    /// 标准 base64（带填充）
+    Base64,
+    /// 小写十六进制，不带 `0x` 前缀
+    Hex,
+    /// 按 UTF-8 解码，非法序列替换为 `U+FFFD`
+    Utf8Lossy,
+}
+
+impl BytesEncoding {
+    /// 解析取值，大小写不敏感
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "base64" => Some(Self::Base64),
+            "hex" => Some(Self::Hex),
+            "utf8_lossy" => Some(Self::Utf8Lossy),
+            _ => None,
+        }
+    }
+
+    /// 参数取值形式
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Base64 => "base64",
+            Self::Hex => "hex",
+            Self::Utf8Lossy => "utf8_lossy",
+        }
+    }
+
+    /// 按当前编码把字节渲染为文本
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Base64 => general_purpose::STANDARD.encode(bytes),
+            Self::Hex => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+            Self::Utf8Lossy => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+
+    /// 渲染字节类字段；其余类型返回 `None`，由调用方按原方式输出
+    pub fn render(self, value: &Value) -> Option<String> {
+        match value {
+            Value::Hex(hex) => Some(self.encode(&hex_bytes(hex))),
+            _ => None,
+        }
+    }
+
+    /// 把记录中的字节类字段替换为编码后的字符串；没有字节类字段时原样返回
+    pub fn encode_record(self, record: &Arc<DataRecord>) -> Arc<DataRecord> {
+        if !record
+            .items
+            .iter()
+            .any(|f| matches!(f.get_value(), Value::Hex(_)))
+        {
+            return record.clone();
+        }
+        let mut encoded = DataRecord::default();
+        for field in &record.items {
+            match self.render(field.get_value()) {
+                Some(text) => encoded.append(DataField::from_chars(field.get_name(), text)),
+                None => encoded.append(field.clone()),
+            }
+        }
+        Arc::new(encoded)
+    }
+}
+
+/// 字段值的文本形式：配置了编码的字节类字段按编码输出，其余使用 `Display`
+pub fn value_text(encoding: Option<BytesEncoding>, value: &Value) -> String {
+    encoding
+        .and_then(|encoding| encoding.render(value))
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// `Value::Hex` 的原始字节：去掉前导零的大端字节序，零值为单个 `0x00`
+fn hex_bytes(hex: &HexT) -> Vec<u8> {
+    let bytes = hex.0.to_be_bytes();
+    let start = bytes
+        .iter()
+        .position(|b| *b != 0)
+        .unwrap_or(bytes.len() - 1);
+    bytes[start..].to_vec()
+}
+
+/// 解析可选的 `<kind>.bytes_encoding` 参数；未配置时为 `None`，输出保持不变
+pub fn parse_bytes_encoding(spec: &SinkSpec) -> SinkResult<Option<BytesEncoding>> {
+    let Some(raw) = spec.params.get(BYTES_ENCODING_PARAM) else {
+        return Ok(None);
+    };
+    raw.as_str()
+        .and_then(BytesEncoding::parse)
+        .map(Some)
+        .ok_or_else(|| {
+            SinkReason::sink(format!(
+                "{}.{BYTES_ENCODING_PARAM} must be one of: base64, hex, utf8_lossy",
+                spec.kind
+            ))
+            .into()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn spec(value: Option<serde_json::Value>) -> SinkSpec {
+        let mut params = BTreeMap::new();
+        if let Some(value) = value {
+            params.insert(BYTES_ENCODING_PARAM.to_string(), value);
+        }
+        SinkSpec {
+            group: "test".into(),
+            name: "encoded".into(),
+            kind: "mysql".into(),
+            connector_id: String::new(),
+            params,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn encodes_bytes_in_each_encoding() {
+        let bytes = b"wp\x00\xff";
+        assert_eq!(BytesEncoding::Base64.encode(bytes), "d3AA/w==");
+        assert_eq!(BytesEncoding::Hex.encode(bytes), "777000ff");
+        assert_eq!(BytesEncoding::Utf8Lossy.encode(bytes), "wp\0\u{fffd}");
+        assert_eq!(BytesEncoding::Utf8Lossy.encode("日志".as_bytes()), "日志");
+        assert_eq!(BytesEncoding::Hex.encode(&[]), "");
+    }
+
+    #[test]
+    fn renders_hex_values_only() {
+        let hex = Value::Hex(HexT(0x7770));
+        assert_eq!(BytesEncoding::Hex.render(&hex), Some("7770".into()));
+        assert_eq!(BytesEncoding::Base64.render(&hex), Some("d3A=".into()));
+        assert_eq!(BytesEncoding::Utf8Lossy.render(&hex), Some("wp".into()));
+        assert_eq!(
+            BytesEncoding::Hex.render(&Value::Hex(HexT(0))),
+            Some("00".into())
+        );
+        assert_eq!(BytesEncoding::Hex.render(&Value::Digit(7)), None);
+    }
+
+    #[test]
+    fn value_text_keeps_display_without_encoding() {
+        let hex = Value::Hex(HexT(0xff));
+        assert_eq!(value_text(None, &hex), "0xFF");
+        assert_eq!(value_text(Some(BytesEncoding::Hex), &hex), "ff");
+        assert_eq!(value_text(Some(BytesEncoding::Hex), &Value::Digit(7)), "7");
+    }
+
+    #[test]
+    fn encode_record_replaces_hex_fields() {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", 1));
+        record.append(DataField::new(
+            wp_model_core::model::DataType::Hex,
+            "payload",
+            Value::Hex(HexT(0xff)),
+        ));
+        let encoded = BytesEncoding::Base64.encode_record(&Arc::new(record));
+        assert_eq!(encoded.get_value("id"), Some(&Value::Digit(1)));
+        assert_eq!(
+            encoded.get_value("payload"),
+            Some(&Value::Chars("/w==".into()))
+        );
+    }
+
+    #[test]
+    fn parses_optional_param() {
+        assert_eq!(parse_bytes_encoding(&spec(None)).unwrap(), None);
+        assert_eq!(
+            parse_bytes_encoding(&spec(Some(json!(" HEX ")))).unwrap(),
+            Some(BytesEncoding::Hex)
+        );
+        assert_eq!(
+            parse_bytes_encoding(&spec(Some(json!("utf8_lossy")))).unwrap(),
+            Some(BytesEncoding::Utf8Lossy)
+        );
+        let err = parse_bytes_encoding(&spec(Some(json!("raw")))).unwrap_err();
+        assert!(err.to_string().contains("mysql.bytes_encoding"));
+        assert!(parse_bytes_encoding(&spec(Some(json!(1)))).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+//! DB Sink 字段白名单/黑名单
+//!
+//! MySQL / Doris Sink 支持 `include_columns = [...]` 与 `exclude_columns = [...]`：
+//! 在生成写入内容前过滤字段，被排除的字段不会出现在 SQL 或 Stream Load 数据中。
+//! 名单同时匹配原字段名与 `column_map` 映射后的列名；两者同时配置时先按白名单保留，
+//! 再按黑名单剔除。
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+
+/// 白名单参数名
+pub const INCLUDE_COLUMNS_PARAM: &str = "include_columns";
+/// 黑名单参数名
+pub const EXCLUDE_COLUMNS_PARAM: &str = "exclude_columns";
+/// 字段过滤参数，供各工厂加入 `allow_override`
+pub const COLUMN_FILTER_PARAMS: [&str; 2] = [INCLUDE_COLUMNS_PARAM, EXCLUDE_COLUMNS_PARAM];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnFilter {
+    /// `None` 表示不限制
+    include: Option<HashSet<String>>,
+    exclude: HashSet<String>,
+}
+
+impl ColumnFilter {
+    /// 从 Sink 参数解析名单；未配置时不过滤任何字段
+    pub fn from_params(kind: &str, params: &ParamMap) -> SinkResult<Self> {
+        Ok(Self {
+            include: parse_names(kind, INCLUDE_COLUMNS_PARAM, params)?,
+            exclude: parse_names(kind, EXCLUDE_COLUMNS_PARAM, params)?.unwrap_or_default(),
+        })
+    }
+
+    /// 以白名单构建（测试及代码内构造使用）
+    pub fn including<I: IntoIterator<Item = S>, S: Into<String>>(mut self, names: I) -> Self {
+        self.include = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// 追加黑名单
+    pub fn excluding<I: IntoIterator<Item = S>, S: Into<String>>(mut self, names: I) -> Self {
+        self.exclude.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// 字段是否允许写入；`field` 为原字段名，`column` 为映射后的列名
+    pub fn allows(&self, field: &str, column: &str) -> bool {
+        let hit = |set: &HashSet<String>| set.contains(field) || set.contains(column);
+        if hit(&self.exclude) {
+            return false;
+        }
+        self.include.as_ref().is_none_or(hit)
+    }
+}
+
+fn parse_names(kind: &str, key: &str, params: &ParamMap) -> SinkResult<Option<HashSet<String>>> {
+    let items = match params.get(key) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::Array(items)) => items,
+        Some(_) => {
+            return Err(SinkReason::sink(format!("{kind}.{key} must be an array of names")).into());
+        }
+    };
+    let mut names = HashSet::with_capacity(items.len());
+    for item in items {
+        match item.as_str().map(str::trim) {
+            Some(name) if !name.is_empty() => {
+                names.insert(name.to_string());
+            }
+            _ => {
+                return Err(SinkReason::sink(format!(
+                    "{kind}.{key} entries must be non-empty strings"
+                ))
+                .into());
+            }
+        }
+    }
+    Ok(Some(names))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_params_builds_include_and_exclude_lists() {
+        let mut params = ParamMap::new();
+        let filter = ColumnFilter::from_params("demo", &params).unwrap();
+        assert!(filter.allows("anything", "anything"));
+
+        params.insert("include_columns".into(), json!(["host", "src_ip"]));
+        params.insert("exclude_columns".into(), json!(["raw_password", "host"]));
+        let filter = ColumnFilter::from_params("demo", &params).unwrap();
+        assert!(filter.allows("srcIp", "src_ip"), "matches mapped column");
+        assert!(!filter.allows("host", "host"), "exclude wins");
+        assert!(!filter.allows("raw_password", "pwd"), "matches field name");
+        assert!(!filter.allows("port", "port"), "not in include list");
+
+        for (bad, expect) in [
+            (
+                json!("raw_password"),
+                "demo.exclude_columns must be an array",
+            ),
+            (json!([1]), "demo.exclude_columns entries"),
+            (json!([" "]), "demo.exclude_columns entries"),
+        ] {
+            params.insert("exclude_columns".into(), bad);
+            let err = ColumnFilter::from_params("demo", &params).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+}
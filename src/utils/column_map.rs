@@ -0,0 +1,92 @@
+//! DB Sink 字段名到目标列名的映射
+//!
+//! MySQL / Doris Sink 支持 `column_map = { srcIp = "src_ip" }`：写入前先按映射改名，
+//! 再与目标列匹配；未出现在映射中的字段保留原名。
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+
+/// 列名映射参数名，供各工厂加入 `allow_override`
+pub const COLUMN_MAP_PARAM: &str = "column_map";
+
+/// 源字段名 -> 目标列名
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMap(HashMap<String, String>);
+
+impl ColumnMap {
+    /// 从 Sink 参数解析映射；未配置时为空映射
+    pub fn from_params(kind: &str, params: &ParamMap) -> SinkResult<Self> {
+        let obj = match params.get(COLUMN_MAP_PARAM) {
+            None | Some(Value::Null) => return Ok(Self::default()),
+            Some(Value::Object(obj)) => obj,
+            Some(_) => {
+                return Err(SinkReason::sink(format!(
+                    "{kind}.column_map must be a table of field = \"column\""
+                ))
+                .into());
+            }
+        };
+        let mut map = HashMap::with_capacity(obj.len());
+        for (field, column) in obj {
+            match column.as_str().map(str::trim) {
+                Some(column) if !column.is_empty() => {
+                    map.insert(field.clone(), column.to_string());
+                }
+                _ => {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.column_map.{field} must be a non-empty string"
+                    ))
+                    .into());
+                }
+            }
+        }
+        Ok(Self(map))
+    }
+
+    /// 返回字段对应的目标列名，未映射时返回原字段名
+    pub fn resolve<'a>(&'a self, field: &'a str) -> &'a str {
+        self.0.get(field).map(String::as_str).unwrap_or(field)
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for ColumnMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_params_parses_table_and_rejects_bad_entries() {
+        let mut params = ParamMap::new();
+        assert_eq!(
+            ColumnMap::from_params("demo", &params).unwrap(),
+            ColumnMap::default()
+        );
+
+        params.insert("column_map".into(), json!({"srcIp": "src_ip"}));
+        let map = ColumnMap::from_params("demo", &params).unwrap();
+        assert_eq!(map.resolve("srcIp"), "src_ip");
+        assert_eq!(map.resolve("dstIp"), "dstIp");
+
+        for (bad, expect) in [
+            (json!(["srcIp"]), "demo.column_map must be a table"),
+            (json!({"srcIp": 1}), "demo.column_map.srcIp"),
+            (json!({"srcIp": " "}), "demo.column_map.srcIp"),
+        ] {
+            params.insert("column_map".into(), bad);
+            let err = ColumnMap::from_params("demo", &params).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! HTTP 请求体压缩
+//!
+//! 基于 HTTP 的批量写入 Sink（ClickHouse、Elasticsearch）通过 `request_compression`
+//! 参数开启请求体压缩，并在请求上携带对应的 `Content-Encoding`：
+//! - `none`：不压缩（默认，避免不支持压缩的服务端解析失败）；
+//! - `gzip` / `deflate`：ClickHouse 原生支持；Elasticsearch 需开启 `http.compression`。
+
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::write::{GzEncoder, ZlibEncoder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+
+/// 请求压缩参数名，供各工厂加入 `allow_override`
+pub const COMPRESSION_PARAM: &str = "request_compression";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestCompression {
+    Gzip,
+    Deflate,
+}
+
+impl RequestCompression {
+    /// 从 Sink 参数解析压缩方式；未配置或为 `none` 时返回 `None`
+    pub fn from_params(kind: &str, params: &ParamMap) -> SinkResult<Option<Self>> {
+        let invalid = || {
+            SinkReason::sink(format!(
+                "{kind}.{COMPRESSION_PARAM} must be one of: none, gzip, deflate"
+            ))
+        };
+        match params.get(COMPRESSION_PARAM) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+                "" | "none" => Ok(None),
+                "gzip" => Ok(Some(Self::Gzip)),
+                "deflate" => Ok(Some(Self::Deflate)),
+                _ => Err(invalid().into()),
+            },
+            Some(_) => Err(invalid().into()),
+        }
+    }
+
+    /// 对应的 `Content-Encoding` 取值
+    pub fn content_encoding(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+        }
+    }
+
+    /// 压缩请求体
+    pub fn encode(self, body: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+            Self::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::{GzDecoder, ZlibDecoder};
+    use serde_json::json;
+    use std::io::Read;
+
+    #[test]
+    fn from_params_defaults_to_none() {
+        assert_eq!(
+            RequestCompression::from_params("demo", &ParamMap::new()).unwrap(),
+            None
+        );
+        let mut params = ParamMap::new();
+        params.insert(COMPRESSION_PARAM.into(), json!("GZIP"));
+        assert_eq!(
+            RequestCompression::from_params("demo", &params).unwrap(),
+            Some(RequestCompression::Gzip)
+        );
+        params.insert(COMPRESSION_PARAM.into(), json!("br"));
+        let err = RequestCompression::from_params("demo", &params).expect_err("br");
+        assert!(err.to_string().contains("demo.request_compression"));
+    }
+
+    #[test]
+    fn encode_round_trips() {
+        let body = b"{\"a\":1}\n{\"a\":2}\n";
+
+        let mut out = String::new();
+        GzDecoder::new(&RequestCompression::Gzip.encode(body).unwrap()[..])
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out.as_bytes(), body);
+
+        let mut out = String::new();
+        ZlibDecoder::new(&RequestCompression::Deflate.encode(body).unwrap()[..])
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out.as_bytes(), body);
+    }
+}
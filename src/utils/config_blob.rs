@@ -0,0 +1,162 @@
+//! 整块配置参数 `config_blob`
+//!
+//! 连接器除逐项的扁平参数外，还可以通过 `config_blob` 一次性给出完整配置：
+//! - 值为对象时直接使用；
+//! - 值为字符串时，以 `{` 开头按 JSON 解析，否则按 TOML 解析。
+//!
+//! 对象中的键与扁平参数同名，展开后作为底层参数；同时配置的扁平参数逐项覆盖 blob 中的同名键。
+//! 注意连接器的默认参数同样以扁平参数的形式出现，会覆盖 blob 中的同名键。
+//! 展开后的参数仍经过各工厂原有的校验，或通过 [`typed_conf`] 直接反序列化为配置结构体。
+
+use std::borrow::Cow;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use wp_connector_api::{
+    ParamMap, SinkReason, SinkResult, SinkSpec, SourceReason, SourceResult, SourceSpec,
+};
+
+/// 整块配置参数名
+pub const CONFIG_BLOB_PARAM: &str = "config_blob";
+
+/// 展开 `config_blob`：返回合并后的参数（扁平参数优先），未配置时返回 `None`
+pub fn expand(kind: &str, params: &ParamMap) -> Result<Option<ParamMap>, String> {
+    let Some(raw) = params.get(CONFIG_BLOB_PARAM) else {
+        return Ok(None);
+    };
+    let mut merged: ParamMap = parse_blob(kind, raw)?.into_iter().collect();
+    merged.remove(CONFIG_BLOB_PARAM);
+    for (key, value) in params {
+        if key != CONFIG_BLOB_PARAM {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    Ok(Some(merged))
+}
+
+/// 展开 Sink 规格中的 `config_blob`；未配置时借用原规格
+pub fn sink_spec<'a>(kind: &str, spec: &'a SinkSpec) -> SinkResult<Cow<'a, SinkSpec>> {
+    match expand(kind, &spec.params).map_err(SinkReason::sink)? {
+        Some(params) => Ok(Cow::Owned(SinkSpec {
+            params,
+            ..spec.clone()
+        })),
+        None => Ok(Cow::Borrowed(spec)),
+    }
+}
+
+/// 展开 Source 规格中的 `config_blob`；未配置时借用原规格
+pub fn source_spec<'a>(kind: &str, spec: &'a SourceSpec) -> SourceResult<Cow<'a, SourceSpec>> {
+    match expand(kind, &spec.params).map_err(SourceReason::Other)? {
+        Some(params) => Ok(Cow::Owned(SourceSpec {
+            params,
+            ..spec.clone()
+        })),
+        None => Ok(Cow::Borrowed(spec)),
+    }
+}
+
+/// 以 `base` 为默认值，用参数中与结构体字段同名的键覆盖后反序列化为配置结构体；
+/// 其余参数（装饰器、Schema 等）不参与反序列化
+pub fn typed_conf<T>(kind: &str, base: T, params: &ParamMap) -> Result<T, String>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let Value::Object(mut fields) =
+        serde_json::to_value(base).map_err(|e| format!("{kind}: serialize config fail: {e}"))?
+    else {
+        return Err(format!("{kind}: config must be a struct"));
+    };
+    for (key, value) in params {
+        if let Some(slot) = fields.get_mut(key) {
+            *slot = value.clone();
+        }
+    }
+    serde_json::from_value(Value::Object(fields))
+        .map_err(|e| format!("{kind}: invalid config: {e}"))
+}
+
+fn parse_blob(kind: &str, raw: &Value) -> Result<Map<String, Value>, String> {
+    let invalid = |e: &dyn std::fmt::Display| format!("{kind}.{CONFIG_BLOB_PARAM} invalid: {e}");
+    let value = match raw {
+        Value::Object(map) => return Ok(map.clone()),
+        Value::String(text) if text.trim_start().starts_with('{') => {
+            serde_json::from_str(text).map_err(|e| invalid(&e))?
+        }
+        Value::String(text) => {
+            let table: toml::Table = toml::from_str(text).map_err(|e| invalid(&e))?;
+            serde_json::to_value(table).map_err(|e| invalid(&e))?
+        }
+        _ => Value::Null,
+    };
+    match value {
+        Value::Object(map) => Ok(map),
+        _ => Err(format!(
+            "{kind}.{CONFIG_BLOB_PARAM} must be an object or a JSON/TOML string"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct DemoConf {
+        endpoint: String,
+        database: String,
+        batch: Option<usize>,
+    }
+
+    #[test]
+    fn blob_plus_flat_override_merges_into_typed_conf() {
+        for blob in [
+            json!("endpoint = \"db:3306\"\ndatabase = \"from_blob\"\nbatch = 100"),
+            json!(r#"{"endpoint": "db:3306", "database": "from_blob", "batch": 100}"#),
+            json!({"endpoint": "db:3306", "database": "from_blob", "batch": 100}),
+        ] {
+            let mut params = ParamMap::new();
+            params.insert(CONFIG_BLOB_PARAM.into(), blob);
+            params.insert("database".into(), json!("from_flat"));
+            params.insert("fmt".into(), json!("json"));
+
+            let merged = expand("demo", &params).unwrap().expect("blob present");
+            assert!(!merged.contains_key(CONFIG_BLOB_PARAM));
+            assert_eq!(merged.get("fmt"), Some(&json!("json")));
+
+            let conf = typed_conf("demo", DemoConf::default(), &merged).unwrap();
+            assert_eq!(
+                conf,
+                DemoConf {
+                    endpoint: "db:3306".into(),
+                    database: "from_flat".into(),
+                    batch: Some(100),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_blob_and_mistyped_fields() {
+        assert_eq!(expand("demo", &ParamMap::new()).unwrap(), None);
+
+        for (blob, expect) in [
+            (json!("endpoint = "), "demo.config_blob invalid"),
+            (json!("{\"endpoint\":"), "demo.config_blob invalid"),
+            (json!(42), "demo.config_blob must be an object"),
+        ] {
+            let mut params = ParamMap::new();
+            params.insert(CONFIG_BLOB_PARAM.into(), blob);
+            let err = expand("demo", &params).expect_err(expect);
+            assert!(err.contains(expect), "{err}");
+        }
+
+        let mut params = ParamMap::new();
+        params.insert("batch".into(), json!("many"));
+        let err = typed_conf("demo", DemoConf::default(), &params).expect_err("mistyped batch");
+        assert!(err.contains("demo: invalid config"), "{err}");
+    }
+}
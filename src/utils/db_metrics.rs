@@ -0,0 +1,68 @@
+//! DB Sink 写入指标
+//!
+//! MySQL / Doris / ClickHouse / SQLite Sink 每写出一批记录后，按结果把行数累加到
+//! `wparse_db_sink_rows{sink,table,result}`（`result` 为 `ok` | `err`）；
+//! 未通过 Schema 校验而被丢弃的记录计入 `result="invalid"`，
+//! 因字段超出列长度而被跳过的记录计入 `result="oversize"`。
+//! 指标注册在全局 Prometheus registry 中，由 Prometheus / VictoriaMetrics 导出器统一输出。
+
+use lazy_static::lazy_static;
+use prometheus::{IntCounterVec, register_int_counter_vec};
+use wp_connector_api::SinkResult;
+
+lazy_static! {
+    pub static ref DB_SINK_ROWS: IntCounterVec = register_int_counter_vec!(
+        "wparse_db_sink_rows",
+        "Rows written by DB sinks per flush result.",
+        &["sink", "table", "result"]
+    )
+    .expect("register wparse_db_sink_rows fail");
+}
+
+/// 按一次写出的结果累加行数
+pub(crate) fn record_flush<T>(sink: &str, table: &str, rows: usize, result: &SinkResult<T>) {
+    let outcome = if result.is_ok() { "ok" } else { "err" };
+    DB_SINK_ROWS
+        .with_label_values(&[sink, table, outcome])
+        .inc_by(rows as u64);
+}
+
+/// 累加未通过 Schema 校验的行数
+#[cfg(any(feature = "mysql", feature = "doris"))]
+pub(crate) fn record_invalid(sink: &str, table: &str, rows: usize) {
+    if rows > 0 {
+        DB_SINK_ROWS
+            .with_label_values(&[sink, table, "invalid"])
+            .inc_by(rows as u64);
+    }
+}
+
+/// 累加因超出列长度被跳过的行数
+#[cfg(feature = "mysql")]
+pub(crate) fn record_oversize(sink: &str, table: &str, rows: usize) {
+    if rows > 0 {
+        DB_SINK_ROWS
+            .with_label_values(&[sink, table, "oversize"])
+            .inc_by(rows as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_connector_api::SinkReason;
+
+    #[test]
+    fn record_flush_counts_rows_by_result() {
+        let ok = DB_SINK_ROWS.with_label_values(&["demo", "db_metrics_t", "ok"]);
+        let err = DB_SINK_ROWS.with_label_values(&["demo", "db_metrics_t", "err"]);
+        let (ok_before, err_before) = (ok.get(), err.get());
+
+        record_flush("demo", "db_metrics_t", 3, &Ok(()));
+        let failed: SinkResult<()> = Err(SinkReason::sink("boom").into());
+        record_flush("demo", "db_metrics_t", 2, &failed);
+
+        assert_eq!(ok.get(), ok_before + 3);
+        assert_eq!(err.get(), err_before + 2);
+    }
+}
@@ -0,0 +1,94 @@
+//! Sink 通用装饰器的统一入口
+//!
+//! 各工厂在 `validate_spec` 中调用 [`validate_decorators`]，在 `build` 完成后调用
+//! [`decorate_sink`]，按 spec 中的通用参数（`filter`、`dedup_field`、`sample_rate`、`add_fields`、
+//! `rate_limit_rps`、`circuit_breaker` 等）包装 Sink。
+
+use wp_connector_api::{SinkHandle, SinkResult, SinkSpec};
+
+use crate::{breaker, dedup, filter, ratelimit, sample, transform};
+
+/// 通用装饰器参数名，供各工厂加入 `allow_override`（`filter` 为 spec 字段，不在此列）
+pub const DECORATOR_PARAMS: [&str; 12] = [
+    ratelimit::RATE_LIMIT_PARAM,
+    sample::SAMPLE_RATE_PARAM,
+    breaker::CIRCUIT_BREAKER_PARAM,
+    breaker::BREAKER_OPTION_PARAMS[0],
+    breaker::BREAKER_OPTION_PARAMS[1],
+    breaker::BREAKER_OPTION_PARAMS[2],
+    dedup::DEDUP_FIELD_PARAM,
+    dedup::DEDUP_OPTION_PARAMS[0],
+    dedup::DEDUP_OPTION_PARAMS[1],
+    transform::ADD_FIELDS_PARAM,
+    transform::RENAME_FIELDS_PARAM,
+    transform::OVERWRITE_PARAM,
+];
+
+/// 校验通用装饰器参数
+pub fn validate_decorators(spec: &SinkSpec) -> SinkResult<()> {
+    filter::parse_filter(spec)?;
+    transform::parse_transform(spec)?;
+    dedup::parse_dedup(spec)?;
+    sample::parse_sample_rate(spec)?;
+    ratelimit::parse_rate_limit_rps(spec)?;
+    breaker::parse_circuit_breaker(spec)?;
+    Ok(())
+}
+
+/// 按 spec 包装已构建的 Sink
+///
+/// 过滤在最外层，被丢弃的记录不占用限速配额，也不做字段变换；
+/// 因此过滤表达式使用变换前的字段名。去重紧随过滤，去重键同样使用变换前的字段名；
+/// 采样在去重之后，按比例抽取的是去重后的记录。
+/// 熔断在最内层，熔断期间被拒绝的数据不占用限速配额。
+pub fn decorate_sink(spec: &SinkSpec, handle: SinkHandle) -> SinkResult<SinkHandle> {
+    let handle = breaker::wrap_from_spec(spec, handle)?;
+    let handle = ratelimit::wrap_from_spec(spec, handle)?;
+    let handle = transform::wrap_from_spec(spec, handle)?;
+    let handle = sample::wrap_from_spec(spec, handle)?;
+    let handle = dedup::wrap_from_spec(spec, handle)?;
+    filter::wrap_from_spec(spec, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn spec(filter: Option<&str>, rps: Option<i64>) -> SinkSpec {
+        let mut params = BTreeMap::new();
+        if let Some(rps) = rps {
+            params.insert("rate_limit_rps".to_string(), json!(rps));
+        }
+        SinkSpec {
+            group: "test".into(),
+            name: "decorated".into(),
+            kind: "count".into(),
+            connector_id: String::new(),
+            params,
+            filter: filter.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn validate_decorators_checks_filter_and_rate_limit() {
+        assert!(validate_decorators(&spec(None, None)).is_ok());
+        assert!(validate_decorators(&spec(Some("level == error"), Some(10))).is_ok());
+        assert!(validate_decorators(&spec(Some("  "), None)).is_ok());
+
+        let err = validate_decorators(&spec(Some("level ="), None)).expect_err("bad filter");
+        assert!(format!("{err}").contains("count.filter"));
+        assert!(validate_decorators(&spec(None, Some(-1))).is_err());
+    }
+
+    #[test]
+    fn decorator_params_are_read_by_validate_decorators() {
+        for name in DECORATOR_PARAMS {
+            let mut spec = spec(None, None);
+            spec.params.insert(name.to_string(), json!([]));
+            let err = validate_decorators(&spec).expect_err(name);
+            assert!(format!("{err}").contains(name), "{name}: {err}");
+        }
+    }
+}
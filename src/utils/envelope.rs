@@ -0,0 +1,212 @@
+//! 载荷信封
+//!
+//! 部分下游要求每条记录包裹在固定结构中，例如
+//! `{"source":"{source}","ts":{ts},"data":{data}}`。模板中只识别三个占位符：
+//! `{data}`（格式化后的记录）、`{ts}`（发送时的 Unix 毫秒时间戳）与 `{source}`
+//! （`envelope_source` 参数，缺省 `wp`），其余字符（包括 JSON 的花括号）原样输出。
+//! 占位符两侧紧挨双引号时（如 `"{data}"`）按 JSON 字符串转义替换值。
+
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+
+/// 信封模板参数名
+pub const ENVELOPE_PARAM: &str = "envelope";
+/// `{source}` 占位符取值的参数名
+pub const ENVELOPE_SOURCE_PARAM: &str = "envelope_source";
+/// 信封相关参数，供各 Sink 的 `allow_override` 复用
+pub const ENVELOPE_PARAMS: [&str; 2] = [ENVELOPE_PARAM, ENVELOPE_SOURCE_PARAM];
+
+const DEFAULT_SOURCE: &str = "wp";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Data,
+    Ts,
+    Source,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    /// 占位符；`quoted` 为 true 时替换值按 JSON 字符串转义
+    Slot {
+        slot: Slot,
+        quoted: bool,
+    },
+}
+
+/// 预解析的信封模板
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    parts: Vec<Part>,
+    source: String,
+}
+
+impl Envelope {
+    /// 解析模板；模板必须包含 `{data}`，`source` 缺省为 `wp`
+    pub fn parse(template: &str, source: Option<&str>) -> Result<Self, String> {
+        const SLOTS: [(&str, Slot); 3] = [
+            ("{data}", Slot::Data),
+            ("{ts}", Slot::Ts),
+            ("{source}", Slot::Source),
+        ];
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+        while !rest.is_empty() {
+            let Some((token, slot)) = SLOTS.iter().find(|(token, _)| rest.starts_with(token))
+            else {
+                let ch = rest.chars().next().unwrap_or_default();
+                literal.push(ch);
+                rest = &rest[ch.len_utf8()..];
+                continue;
+            };
+            rest = &rest[token.len()..];
+            let quoted = literal.ends_with('"') && rest.starts_with('"');
+            if !literal.is_empty() {
+                parts.push(Part::Literal(std::mem::take(&mut literal)));
+            }
+            parts.push(Part::Slot {
+                slot: *slot,
+                quoted,
+            });
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        if !parts.iter().any(|part| {
+            matches!(
+                part,
+                Part::Slot {
+                    slot: Slot::Data,
+                    ..
+                }
+            )
+        }) {
+            return Err(format!(
+                "envelope template '{template}' must contain {{data}}"
+            ));
+        }
+        Ok(Self {
+            parts,
+            source: source.unwrap_or(DEFAULT_SOURCE).to_string(),
+        })
+    }
+
+    /// 解析 `envelope` / `envelope_source` 参数；未配置 `envelope` 时返回 `None`
+    pub fn from_params(kind: &str, params: &ParamMap) -> SinkResult<Option<Self>> {
+        let source = match params.get(ENVELOPE_SOURCE_PARAM) {
+            None => None,
+            Some(v) => match v.as_str().map(str::trim) {
+                Some(s) if !s.is_empty() => Some(s.to_string()),
+                _ => {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.{ENVELOPE_SOURCE_PARAM} must be a non-empty string"
+                    ))
+                    .into());
+                }
+            },
+        };
+        let Some(raw) = params.get(ENVELOPE_PARAM) else {
+            if source.is_some() {
+                return Err(SinkReason::sink(format!(
+                    "{kind}.{ENVELOPE_SOURCE_PARAM} requires {kind}.{ENVELOPE_PARAM}"
+                ))
+                .into());
+            }
+            return Ok(None);
+        };
+        let template = raw
+            .as_str()
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| {
+                SinkReason::sink(format!(
+                    "{kind}.{ENVELOPE_PARAM} must be a non-empty string"
+                ))
+            })?;
+        Self::parse(template, source.as_deref())
+            .map(Some)
+            .map_err(|e| SinkReason::sink(format!("{kind}.{e}")).into())
+    }
+
+    /// 以当前时间包裹一条格式化后的记录
+    pub fn wrap(&self, data: &str) -> String {
+        self.wrap_at(data, chrono::Utc::now().timestamp_millis())
+    }
+
+    /// 以给定的 Unix 毫秒时间戳包裹一条格式化后的记录
+    pub fn wrap_at(&self, data: &str, ts_ms: i64) -> String {
+        let ts = ts_ms.to_string();
+        let mut out = String::with_capacity(data.len() + 64);
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => out.push_str(text),
+                Part::Slot { slot, quoted } => {
+                    let value = match slot {
+                        Slot::Data => data,
+                        Slot::Ts => ts.as_str(),
+                        Slot::Source => self.source.as_str(),
+                    };
+                    if *quoted {
+                        push_json_escaped(&mut out, value);
+                    } else {
+                        out.push_str(value);
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// 追加 JSON 字符串转义后的内容（不含两侧引号）
+fn push_json_escaped(out: &mut String, value: &str) {
+    let quoted = serde_json::Value::String(value.to_string()).to_string();
+    out.push_str(&quoted[1..quoted.len() - 1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn wraps_formatted_record_into_template() {
+        let envelope =
+            Envelope::parse(r#"{"source":"{source}","ts":{ts},"data":{data}}"#, None).unwrap();
+        let wrapped = envelope.wrap_at(r#"{"host":"web-1","bytes":42}"#, 1_700_000_000_123);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&wrapped).unwrap(),
+            json!({"source": "wp", "ts": 1_700_000_000_123_i64,
+                   "data": {"host": "web-1", "bytes": 42}})
+        );
+    }
+
+    #[test]
+    fn quoted_placeholders_are_json_escaped() {
+        let envelope =
+            Envelope::parse(r#"{"msg":"{data}","src":"{source}"}"#, Some("edge \"a\"")).unwrap();
+        let wrapped = envelope.wrap_at("line with \"quotes\"\n", 0);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&wrapped).unwrap(),
+            json!({"msg": "line with \"quotes\"\n", "src": "edge \"a\""})
+        );
+    }
+
+    #[test]
+    fn from_params_validates_template_and_source() {
+        let mut params = ParamMap::new();
+        assert!(Envelope::from_params("kafka", &params).unwrap().is_none());
+
+        params.insert(ENVELOPE_SOURCE_PARAM.into(), json!("edge"));
+        let err = Envelope::from_params("kafka", &params).expect_err("source alone");
+        assert!(err.to_string().contains("requires kafka.envelope"), "{err}");
+
+        params.insert(ENVELOPE_PARAM.into(), json!(r#"{"ts":{ts}}"#));
+        let err = Envelope::from_params("kafka", &params).expect_err("no data slot");
+        assert!(err.to_string().contains("must contain {data}"), "{err}");
+
+        params.insert(ENVELOPE_PARAM.into(), json!("<{source}>{data}"));
+        let envelope = Envelope::from_params("kafka", &params).unwrap().unwrap();
+        assert_eq!(envelope.wrap_at("x", 0), "<edge>x");
+    }
+}
@@ -0,0 +1,154 @@
+//! 连接器错误分类
+//!
+//! `SinkReason::Sink` / `SourceReason::Other` 只携带字符串，上游无法区分
+//! “连接被拒绝”“配置错误”“序列化失败”等情形。这里约定在消息前附加
+//! `[<connector>:<kind>]` 标签，并提供解析函数，便于重试/告警按类别分支：
+//!
+//! ```text
+//! [kafka:auth] kafka send fail: ...
+//! [mysql:config] mysql.endpoint must not be empty
+//! ```
+
+use std::fmt;
+
+use wp_connector_api::{SinkError, SinkReason, SourceError, SourceReason};
+
+/// 连接器错误类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// 参数缺失或非法，重试无意义
+    Config,
+    /// 无法建立或维持到后端的连接，可重连后重试
+    Connect,
+    /// 认证/鉴权失败，需人工介入
+    Auth,
+    /// 数据无法编码为后端接受的格式，重试同一批数据无意义
+    Serialize,
+    /// 后端已接收请求但执行失败（SQL 错误、提交失败、队列异常等）
+    Backend,
+}
+
+impl ErrorKind {
+    pub const ALL: [ErrorKind; 5] = [
+        ErrorKind::Config,
+        ErrorKind::Connect,
+        ErrorKind::Auth,
+        ErrorKind::Serialize,
+        ErrorKind::Backend,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::Config => "config",
+            ErrorKind::Connect => "connect",
+            ErrorKind::Auth => "auth",
+            ErrorKind::Serialize => "serialize",
+            ErrorKind::Backend => "backend",
+        }
+    }
+
+    /// 稳定的数字错误码，供指标标签或外部系统使用
+    pub fn code(self) -> u16 {
+        match self {
+            ErrorKind::Config => 1001,
+            ErrorKind::Connect => 1002,
+            ErrorKind::Auth => 1003,
+            ErrorKind::Serialize => 1004,
+            ErrorKind::Backend => 1005,
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == raw)
+    }
+
+    /// 生成带类别标签的 `SinkReason::Sink`
+    pub fn sink(self, connector: &str, msg: impl fmt::Display) -> SinkReason {
+        SinkReason::Sink(format!("[{connector}:{}] {msg}", self.as_str()))
+    }
+
+    /// 生成带类别标签的 `SourceReason::Other`
+    pub fn source(self, connector: &str, msg: impl fmt::Display) -> SourceReason {
+        SourceReason::Other(format!("[{connector}:{}] {msg}", self.as_str()))
+    }
+
+    /// 从 sink 错误中解析类别；未打标签的错误返回 `None`
+    pub fn of_sink(err: &SinkError) -> Option<Self> {
+        match err.reason() {
+            SinkReason::Sink(msg) => parse_tag(msg).map(|(_, kind)| kind),
+            _ => None,
+        }
+    }
+
+    /// 从 source 错误中解析类别；未打标签的错误返回 `None`
+    pub fn of_source(err: &SourceError) -> Option<Self> {
+        match err.reason() {
+            SourceReason::Other(msg) => parse_tag(msg).map(|(_, kind)| kind),
+            _ => None,
+        }
+    }
+
+    /// 为尚未打标签的 `SinkReason::Sink` 错误补充类别，已有标签的保持不变；
+    /// 用于包裹共享的参数解析（schema、column_map 等）结果
+    pub fn tag_sink<T>(
+        self,
+        connector: &str,
+        result: Result<T, SinkError>,
+    ) -> Result<T, SinkError> {
+        result.map_err(|err| match err.reason() {
+            SinkReason::Sink(msg) if parse_tag(msg).is_none() => {
+                let tagged = SinkError::from(self.sink(connector, msg));
+                match err.detail() {
+                    Some(detail) => tagged.with_detail(detail.clone()),
+                    None => tagged,
+                }
+            }
+            _ => err,
+        })
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 解析消息前缀 `[<connector>:<kind>]`
+fn parse_tag(msg: &str) -> Option<(&str, ErrorKind)> {
+    let (tag, _) = msg.strip_prefix('[')?.split_once(']')?;
+    let (connector, kind) = tag.split_once(':')?;
+    Some((connector, ErrorKind::parse(kind)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tagged_reasons_round_trip_kind() {
+        for kind in ErrorKind::ALL {
+            let err = SinkError::from(kind.sink("kafka", "boom"));
+            assert_eq!(ErrorKind::of_sink(&err), Some(kind));
+            assert!(err.to_string().contains(&format!("[kafka:{kind}] boom")));
+
+            let err = SourceError::from(kind.source("mysql", "boom"));
+            assert_eq!(ErrorKind::of_source(&err), Some(kind));
+        }
+        let plain = SinkError::from(SinkReason::sink("[not a tag] boom"));
+        assert_eq!(ErrorKind::of_sink(&plain), None);
+    }
+
+    #[test]
+    fn tag_sink_keeps_existing_kind() {
+        let untagged: Result<(), SinkError> =
+            Err(SinkReason::sink("mysql.batch must be > 0").into());
+        let err = ErrorKind::Config.tag_sink("mysql", untagged).unwrap_err();
+        assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Config));
+        assert!(err.to_string().contains("mysql.batch must be > 0"));
+
+        let tagged: Result<(), SinkError> = Err(ErrorKind::Auth.sink("mysql", "denied").into());
+        let err = ErrorKind::Config.tag_sink("mysql", tagged).unwrap_err();
+        assert_eq!(ErrorKind::of_sink(&err), Some(ErrorKind::Auth));
+    }
+}
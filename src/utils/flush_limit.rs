@@ -0,0 +1,118 @@
+//! 批量写出阈值
+//!
+//! 批量 Sink 按条数（`batch_size` / `batch`）切分写出批次；部分后端限制的是请求体字节数
+//! （如 Elasticsearch `_bulk` 默认 100MB），记录大小差异较大时仅按条数无法避免超限。
+//! 参数 `flush_bytes` 与条数阈值同时生效，任一阈值先达到即结束当前批次。
+//! 单条记录超过 `flush_bytes` 时独占一个批次，不做拆分。
+
+use serde_json::Value;
+use wp_connector_api::{SinkReason, SinkResult, SinkSpec};
+
+/// 字节阈值参数名
+pub const FLUSH_BYTES_PARAM: &str = "flush_bytes";
+
+/// 批次阈值；均为 `None` 时不切分
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlushLimits {
+    /// 每批最多记录数
+    pub records: Option<usize>,
+    /// 每批最多字节数
+    pub bytes: Option<usize>,
+}
+
+impl FlushLimits {
+    pub fn new(records: Option<usize>, bytes: Option<usize>) -> Self {
+        Self {
+            records: records.filter(|n| *n > 0),
+            bytes: bytes.filter(|n| *n > 0),
+        }
+    }
+
+    /// 已累计 `records` 条、`bytes` 字节时是否应结束当前批次
+    pub fn reached(&self, records: usize, bytes: usize) -> bool {
+        self.records.is_some_and(|limit| records >= limit)
+            || self.bytes.is_some_and(|limit| bytes >= limit)
+    }
+
+    /// 按阈值将元素依次切分为若干批次，`size_of` 给出单个元素的字节数
+    ///
+    /// 加入下一个元素会超过字节阈值时先结束当前批次，因此除独占批次的超大元素外，
+    /// 每批字节数不超过 `flush_bytes`。
+    pub fn split<T>(&self, items: Vec<T>, size_of: impl Fn(&T) -> usize) -> Vec<Vec<T>> {
+        let mut batches = Vec::new();
+        let mut current = Vec::new();
+        let mut current_bytes = 0usize;
+        for item in items {
+            let size = size_of(&item);
+            let overflow = self.bytes.is_some_and(|limit| current_bytes + size > limit);
+            if !current.is_empty() && overflow {
+                batches.push(std::mem::take(&mut current));
+                current_bytes = 0;
+            }
+            current.push(item);
+            current_bytes += size;
+            if self.reached(current.len(), current_bytes) {
+                batches.push(std::mem::take(&mut current));
+                current_bytes = 0;
+            }
+        }
+        if !current.is_empty() {
+            batches.push(current);
+        }
+        batches
+    }
+}
+
+/// 读取 `flush_bytes`；未配置时返回 `None`
+pub fn parse_flush_bytes(spec: &SinkSpec) -> SinkResult<Option<usize>> {
+    match spec.params.get(FLUSH_BYTES_PARAM) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => match v.as_u64().and_then(|n| usize::try_from(n).ok()) {
+            Some(n) if n > 0 => Ok(Some(n)),
+            _ => Err(SinkReason::sink(format!(
+                "{}.{FLUSH_BYTES_PARAM} must be a positive integer",
+                spec.kind
+            ))
+            .into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sizes(batches: &[Vec<usize>]) -> Vec<(usize, usize)> {
+        batches
+            .iter()
+            .map(|b| (b.len(), b.iter().sum::<usize>()))
+            .collect()
+    }
+
+    #[test]
+    fn small_records_hit_count_limit_first() {
+        let limits = FlushLimits::new(Some(100), Some(1 << 20));
+        let batches = limits.split(vec![10usize; 250], |n| *n);
+        assert_eq!(sizes(&batches), vec![(100, 1000), (100, 1000), (50, 500)]);
+        assert!(limits.reached(100, 0));
+        assert!(!limits.reached(99, 1000));
+    }
+
+    #[test]
+    fn large_records_hit_byte_limit_first() {
+        let limits = FlushLimits::new(Some(100), Some(1000));
+        let batches = limits.split(vec![400usize, 400, 400, 2500, 300], |n| *n);
+        // 第三条会超出字节阈值，先写出前两条；超大记录独占一批
+        assert_eq!(
+            sizes(&batches),
+            vec![(2, 800), (1, 400), (1, 2500), (1, 300)]
+        );
+        assert!(limits.reached(1, 1000));
+    }
+
+    #[test]
+    fn no_limits_keep_everything_in_one_batch() {
+        let batches = FlushLimits::new(None, Some(0)).split(vec![1usize; 5], |n| *n);
+        assert_eq!(sizes(&batches), vec![(5, 5)]);
+    }
+}
@@ -1,9 +1,39 @@
 use std::sync::Arc;
 
+use serde_json::Value;
+use wp_connector_api::{SinkReason, SinkResult};
 use wp_data_fmt::{
     Csv, FormatType, Json, KeyValue, ProtoTxt, Raw, RecordFormatter, ValueFormatter,
 };
-use wp_model_core::model::{DataField, DataRecord, DataType};
+use wp_model_core::model::{DataField, DataRecord, DataType, fmt_def::TextFmt};
+
+/// Sink `fmt` 参数允许的取值
+pub const TEXT_FMT_ALLOWED: &[&str] = &["json", "csv", "show", "kv", "raw", "proto", "proto-text"];
+
+/// 解析 Sink 的 `fmt` 参数：缺省为 json，未知格式统一报错而非静默回退
+///
+/// `kind` 用作错误信息前缀，例如 `kafka.fmt must be a string`。
+pub fn parse_text_fmt(kind: &str, value: Option<&Value>) -> SinkResult<TextFmt> {
+    match value {
+        None => Ok(TextFmt::Json),
+        Some(Value::String(raw)) => {
+            let trimmed = raw.trim();
+            if trimmed.is_empty() {
+                return Err(SinkReason::sink(format!("{kind}.fmt must not be empty")).into());
+            }
+            if !TEXT_FMT_ALLOWED.contains(&trimmed) {
+                return Err(SinkReason::sink(format!(
+                    "invalid fmt: '{}'; allowed: {}",
+                    trimmed,
+                    TEXT_FMT_ALLOWED.join(",")
+                ))
+                .into());
+            }
+            Ok(TextFmt::from(trimmed))
+        }
+        Some(_) => Err(SinkReason::sink(format!("{kind}.fmt must be a string")).into()),
+    }
+}
 
 pub enum BatchFormat {
     Json,
@@ -262,8 +292,25 @@ pub fn fmt_bytes_proto_text(records: Vec<Arc<DataRecord>>) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
     use wp_model_core::model::{DataField, DataRecord, types::value::ObjectValue};
 
+    #[test]
+    fn parse_text_fmt_accepts_allowlist_and_rejects_unknown() {
+        assert_eq!(parse_text_fmt("kafka", None).unwrap(), TextFmt::Json);
+        assert_eq!(
+            parse_text_fmt("kafka", Some(&json!(" csv "))).unwrap(),
+            TextFmt::Csv
+        );
+
+        let err = parse_text_fmt("kafka", Some(&json!("bogus"))).unwrap_err();
+        assert!(format!("{err}").contains("invalid fmt: 'bogus'"));
+        let err = parse_text_fmt("nats", Some(&json!(""))).unwrap_err();
+        assert!(format!("{err}").contains("nats.fmt must not be empty"));
+        let err = parse_text_fmt("redis", Some(&json!(1))).unwrap_err();
+        assert!(format!("{err}").contains("redis.fmt must be a string"));
+    }
+
     fn sample_record(id: i64, name: &str) -> Arc<DataRecord> {
         let mut record = DataRecord::default();
         record.append(DataField::from_digit("id", id));
@@ -0,0 +1,146 @@
+//! HTTP 推送的认证配置
+//!
+//! VictoriaLogs / VictoriaMetrics 的托管服务需要认证，支持以下两种方式（只能配置其一）：
+//! - `auth_bearer`：以 `Authorization: Bearer <token>` 发送；
+//! - `auth_basic_user` / `auth_basic_pass`：以 `Authorization: Basic <base64>` 发送，密码可省略。
+
+use serde_json::Value;
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+
+/// 认证参数名，供各工厂加入 `allow_override`
+pub const HTTP_AUTH_PARAMS: [&str; 3] = ["auth_bearer", "auth_basic_user", "auth_basic_pass"];
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub enum HttpAuth {
+    #[default]
+    None,
+    Bearer(String),
+    Basic {
+        user: String,
+        pass: Option<String>,
+    },
+}
+
+// 避免凭据出现在日志中
+impl std::fmt::Debug for HttpAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::Bearer(_) => f.write_str("Bearer(***)"),
+            Self::Basic { user, .. } => write!(f, "Basic({user}:***)"),
+        }
+    }
+}
+
+impl HttpAuth {
+    /// 从 Sink 参数解析认证方式；同时配置 bearer 与 basic 时报错
+    pub fn from_params(kind: &str, params: &ParamMap) -> SinkResult<Self> {
+        let bearer = non_empty(kind, "auth_bearer", params)?;
+        let user = non_empty(kind, "auth_basic_user", params)?;
+        let pass = match params.get("auth_basic_pass") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(_) => {
+                return Err(
+                    SinkReason::sink(format!("{kind}.auth_basic_pass must be a string")).into(),
+                );
+            }
+        };
+        match (bearer, user, pass) {
+            (None, None, None) => Ok(Self::None),
+            (Some(token), None, None) => Ok(Self::Bearer(token)),
+            (None, Some(user), pass) => Ok(Self::Basic { user, pass }),
+            (None, None, Some(_)) => Err(SinkReason::sink(format!(
+                "{kind}.auth_basic_pass requires {kind}.auth_basic_user"
+            ))
+            .into()),
+            (Some(_), _, _) => Err(SinkReason::sink(format!(
+                "{kind}: only one auth scheme is allowed, set either auth_bearer or auth_basic_user/auth_basic_pass"
+            ))
+            .into()),
+        }
+    }
+
+    /// 为请求附加 `Authorization` 头
+    pub fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Self::None => request,
+            Self::Bearer(token) => request.bearer_auth(token),
+            Self::Basic { user, pass } => request.basic_auth(user, pass.as_deref()),
+        }
+    }
+}
+
+fn non_empty(kind: &str, key: &str, params: &ParamMap) -> SinkResult<Option<String>> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => match v.as_str().map(str::trim) {
+            Some(s) if !s.is_empty() => Ok(Some(s.to_string())),
+            _ => Err(SinkReason::sink(format!("{kind}.{key} must be a non-empty string")).into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(pairs: &[(&str, Value)]) -> SinkResult<HttpAuth> {
+        let params: ParamMap = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        HttpAuth::from_params("demo", &params)
+    }
+
+    fn authorization(auth: &HttpAuth) -> Option<String> {
+        let request = auth
+            .apply(reqwest::Client::new().post("http://127.0.0.1/insert"))
+            .build()
+            .unwrap();
+        request
+            .headers()
+            .get(reqwest::header::AUTHORIZATION)
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn authorization_header_matches_scheme() {
+        assert_eq!(authorization(&parse(&[]).unwrap()), None);
+
+        let bearer = parse(&[("auth_bearer", json!("t0ken"))]).unwrap();
+        assert_eq!(authorization(&bearer).as_deref(), Some("Bearer t0ken"));
+
+        let basic = parse(&[
+            ("auth_basic_user", json!("user")),
+            ("auth_basic_pass", json!("pass")),
+        ])
+        .unwrap();
+        // base64("user:pass")
+        assert_eq!(authorization(&basic).as_deref(), Some("Basic dXNlcjpwYXNz"));
+        assert_eq!(format!("{basic:?}"), "Basic(user:***)");
+    }
+
+    #[test]
+    fn rejects_conflicting_or_incomplete_schemes() {
+        for (pairs, expect) in [
+            (
+                vec![
+                    ("auth_bearer", json!("t")),
+                    ("auth_basic_user", json!("user")),
+                ],
+                "only one auth scheme",
+            ),
+            (
+                vec![("auth_basic_pass", json!("pass"))],
+                "demo.auth_basic_pass requires demo.auth_basic_user",
+            ),
+            (vec![("auth_bearer", json!(" "))], "demo.auth_bearer"),
+            (vec![("auth_basic_pass", json!(1))], "demo.auth_basic_pass"),
+        ] {
+            let err = parse(&pairs).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! HTTP 客户端超时与连接池配置
+//!
+//! 基于 HTTP 的 Sink（VictoriaLogs、VictoriaMetrics、Elasticsearch、ClickHouse）统一支持：
+//! - `request_timeout_secs`：单个请求的总超时（秒，可为小数），覆盖各 Sink 的默认超时；
+//! - `connect_timeout_secs`：建立 TCP 连接的超时（秒，可为小数）；
+//! - `pool_max_idle_per_host`：每个主机保留的空闲连接数上限，大批量写入时用于连接复用。
+//!
+//! 三者均须为正数，未配置时沿用客户端默认行为。
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+
+/// HTTP 客户端参数名，供各工厂加入 `allow_override`
+pub const HTTP_CLIENT_PARAMS: [&str; 3] = [
+    "request_timeout_secs",
+    "connect_timeout_secs",
+    "pool_max_idle_per_host",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpClientOptions {
+    /// 请求总超时
+    pub request_timeout: Option<Duration>,
+    /// 连接超时
+    pub connect_timeout: Option<Duration>,
+    /// 每个主机的空闲连接数上限
+    pub pool_max_idle_per_host: Option<usize>,
+}
+
+impl HttpClientOptions {
+    /// 从 Sink 参数解析客户端配置
+    pub fn from_params(kind: &str, params: &ParamMap) -> SinkResult<Self> {
+        let pool_max_idle_per_host = match params.get("pool_max_idle_per_host") {
+            None | Some(Value::Null) => None,
+            Some(v) => match v.as_u64() {
+                Some(n) if n > 0 => Some(n as usize),
+                _ => {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.pool_max_idle_per_host must be a positive integer"
+                    ))
+                    .into());
+                }
+            },
+        };
+        Ok(Self {
+            request_timeout: parse_secs(kind, "request_timeout_secs", params)?,
+            connect_timeout: parse_secs(kind, "connect_timeout_secs", params)?,
+            pool_max_idle_per_host,
+        })
+    }
+
+    /// 配置的请求超时，未配置时返回 `default`
+    pub fn request_timeout_or(&self, default: Duration) -> Duration {
+        self.request_timeout.unwrap_or(default)
+    }
+
+    /// 将配置应用到 reqwest 客户端构建器；请求超时覆盖构建器上已有的默认值
+    #[cfg(any(
+        feature = "victorialogs",
+        feature = "victoriametrics",
+        feature = "elasticsearch",
+        feature = "clickhouse",
+        feature = "s3",
+        feature = "iceberg"
+    ))]
+    pub fn apply_reqwest(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(timeout) = self.request_timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(n) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(n);
+        }
+        builder
+    }
+}
+
+/// 解析正数秒数，兼容数字字符串（如 `"2.5"`）
+fn parse_secs(kind: &str, key: &str, params: &ParamMap) -> SinkResult<Option<Duration>> {
+    let secs = match params.get(key) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(s)) => s.trim().parse::<f64>().ok(),
+        Some(v) => v.as_f64(),
+    };
+    match secs {
+        Some(n) if n > 0.0 && n.is_finite() => Ok(Some(Duration::from_secs_f64(n))),
+        _ => Err(SinkReason::sink(format!("{kind}.{key} must be a positive number")).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_params_parses_and_validates() {
+        let mut params = ParamMap::new();
+        assert_eq!(
+            HttpClientOptions::from_params("demo", &params).unwrap(),
+            HttpClientOptions::default()
+        );
+
+        params.insert("request_timeout_secs".into(), json!(120));
+        params.insert("connect_timeout_secs".into(), json!("2.5"));
+        params.insert("pool_max_idle_per_host".into(), json!(16));
+        let opts = HttpClientOptions::from_params("demo", &params).unwrap();
+        assert_eq!(opts.request_timeout, Some(Duration::from_secs(120)));
+        assert_eq!(opts.connect_timeout, Some(Duration::from_millis(2500)));
+        assert_eq!(opts.pool_max_idle_per_host, Some(16));
+
+        for (key, bad) in [
+            ("request_timeout_secs", json!(0)),
+            ("connect_timeout_secs", json!(-1)),
+            ("connect_timeout_secs", json!("soon")),
+            ("pool_max_idle_per_host", json!(0)),
+            ("pool_max_idle_per_host", json!(1.5)),
+        ] {
+            let mut params = ParamMap::new();
+            params.insert(key.into(), bad);
+            let err = HttpClientOptions::from_params("demo", &params).expect_err(key);
+            assert!(err.to_string().contains(&format!("demo.{key}")), "{err}");
+        }
+    }
+
+    #[cfg(any(
+        feature = "victorialogs",
+        feature = "victoriametrics",
+        feature = "elasticsearch",
+        feature = "clickhouse"
+    ))]
+    #[tokio::test]
+    async fn request_timeout_overrides_builder_default() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 每个连接延迟 300ms 后才响应
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = stream.read(&mut buf).await;
+                    tokio::time::sleep(Duration::from_millis(300)).await;
+                    let _ = stream
+                        .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                        .await;
+                });
+            }
+        });
+
+        let client = |secs: f64| {
+            let mut params = ParamMap::new();
+            params.insert("request_timeout_secs".into(), json!(secs));
+            let opts = HttpClientOptions::from_params("demo", &params).unwrap();
+            let builder = reqwest::Client::builder().timeout(Duration::from_millis(50));
+            opts.apply_reqwest(builder).build().unwrap()
+        };
+
+        let err = client(0.1)
+            .get(&url)
+            .send()
+            .await
+            .expect_err("slower than the limit");
+        assert!(err.is_timeout(), "{err}");
+        let resp = client(2.0)
+            .get(&url)
+            .send()
+            .await
+            .expect("within the limit");
+        assert_eq!(resp.status(), reqwest::StatusCode::NO_CONTENT);
+    }
+}
@@ -0,0 +1,317 @@
+//! HTTP 写入的重试预算
+//!
+//! 基于 HTTP 的 Sink（HTTP、VictoriaLogs）统一支持：
+//! - `max_retries`：失败后的重试次数（不含首次请求），`0` 表示不重试，`-1` 表示不限次数；
+//! - `retry_backoff_ms`：首次重试前的等待（毫秒），之后按 [`RetryPolicy`] 逐次翻倍；
+//! - `respect_retry_after`：响应带 `Retry-After` 时按其指示等待，而非退避时间（默认 true）。
+//!
+//! 5xx、429 以及连接/超时错误会重试，其余 4xx 直接失败。单次等待（包括
+//! `Retry-After`）不超过 [`MAX_RETRY_WAIT`]，避免一个响应头让 Sink 长时间停摆。
+
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde_json::Value;
+use wp_connector_api::{ParamMap, SinkError, SinkReason, SinkResult};
+
+use crate::utils::retry::RetryPolicy;
+
+/// 重试参数名，供各工厂加入 `allow_override`
+pub const HTTP_RETRY_PARAMS: [&str; 3] = ["max_retries", "retry_backoff_ms", "respect_retry_after"];
+
+/// 单次重试等待的上限
+pub const MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpRetry {
+    /// 失败后的重试次数，负数表示不限次数
+    pub max_retries: i64,
+    /// 首次重试前的等待时间
+    pub backoff: Duration,
+    /// 是否按响应的 `Retry-After` 等待
+    pub respect_retry_after: bool,
+}
+
+impl HttpRetry {
+    pub const fn new(max_retries: i64, backoff: Duration, respect_retry_after: bool) -> Self {
+        Self {
+            max_retries,
+            backoff,
+            respect_retry_after,
+        }
+    }
+
+    /// 从 Sink 参数解析重试预算，未配置的参数沿用 `default`
+    pub fn from_params(kind: &str, params: &ParamMap, default: Self) -> SinkResult<Self> {
+        let max_retries = match params.get("max_retries") {
+            None | Some(Value::Null) => default.max_retries,
+            Some(v) => match v.as_i64() {
+                Some(n) if n >= -1 => n,
+                _ => {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.max_retries must be an integer >= -1"
+                    ))
+                    .into());
+                }
+            },
+        };
+        let backoff = match params.get("retry_backoff_ms") {
+            None | Some(Value::Null) => default.backoff,
+            Some(v) => match v.as_u64() {
+                Some(ms) if ms > 0 => Duration::from_millis(ms),
+                _ => {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.retry_backoff_ms must be a positive integer"
+                    ))
+                    .into());
+                }
+            },
+        };
+        let respect_retry_after = match params.get("respect_retry_after") {
+            None | Some(Value::Null) => default.respect_retry_after,
+            Some(Value::Bool(b)) => *b,
+            Some(_) => {
+                return Err(
+                    SinkReason::sink(format!("{kind}.respect_retry_after must be a bool")).into(),
+                );
+            }
+        };
+        Ok(Self::new(max_retries, backoff, respect_retry_after))
+    }
+
+    /// 对应的指数退避策略；总尝试次数含首次请求
+    pub fn policy(&self) -> RetryPolicy {
+        let max_attempts = u32::try_from(self.max_retries)
+            .map(|n| n.saturating_add(1))
+            .unwrap_or(u32::MAX);
+        RetryPolicy::new(max_attempts, self.backoff, MAX_RETRY_WAIT.max(self.backoff))
+    }
+
+    /// 第 `attempt` 次（从 1 开始）失败后的等待时间
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        match retry_after.filter(|_| self.respect_retry_after) {
+            Some(wait) => wait.min(MAX_RETRY_WAIT),
+            None => self.policy().backoff(attempt),
+        }
+    }
+
+    /// 发送 `request` 构建的请求直到返回 2xx；可重试的失败按预算等待后重发，
+    /// 用尽次数或遇到不可重试的响应时返回最后一次的错误
+    pub async fn send<F>(&self, label: &str, mut request: F) -> SinkResult<Response>
+    where
+        F: FnMut() -> RequestBuilder,
+    {
+        let max_attempts = self.policy().max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            let (err, retryable, retry_after) = match request().send().await {
+                Ok(resp) if resp.status().is_success() => return Ok(resp),
+                Ok(resp) => {
+                    let status = resp.status();
+                    let retry_after = parse_retry_after(resp.headers());
+                    let body = resp
+                        .text()
+                        .await
+                        .unwrap_or_else(|_| String::from("(unable to read response body)"));
+                    let err = SinkError::from(SinkReason::sink(format!(
+                        "{label} request failed: status={status}, body={body}"
+                    )));
+                    (err, is_retryable_status(status), retry_after)
+                }
+                Err(e) => {
+                    let retryable = e.is_timeout() || e.is_connect() || e.is_request();
+                    let err =
+                        SinkError::from(SinkReason::sink(format!("{label} request failed: {e}")));
+                    (err, retryable, None)
+                }
+            };
+            if !retryable || attempt >= max_attempts {
+                return Err(err);
+            }
+            let delay = self.delay(attempt, retry_after);
+            wp_log::warn_data!(
+                "[{}] attempt {}/{} failed: {}; retry in {:?}",
+                label,
+                attempt,
+                if self.max_retries < 0 {
+                    "∞".to_string()
+                } else {
+                    max_attempts.to_string()
+                },
+                err,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// 5xx 与 429 可重试，其余 4xx 不重试
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// 解析 `Retry-After`：秒数或 HTTP 日期；日期已过时视为立即重试
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = at.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// 测试用的脚本化 HTTP 服务
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// 503 + `Retry-After: 1`
+    pub(crate) const UNAVAILABLE_RETRY_1S: &str = "HTTP/1.1 503 Service Unavailable\r\n\
+         Retry-After: 1\r\nContent-Length: 4\r\nConnection: close\r\n\r\nbusy";
+
+    /// 按顺序返回 `responses` 中的原始响应，之后一律返回 200；返回 `/insert` 地址与请求计数
+    pub(crate) async fn scripted_server(responses: Vec<&'static str>) -> (String, Arc<AtomicU32>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/insert", listener.local_addr().unwrap());
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let n = counter.fetch_add(1, Ordering::SeqCst) as usize;
+                let response = responses
+                    .get(n)
+                    .copied()
+                    .unwrap_or("HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        (url, calls)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::{UNAVAILABLE_RETRY_1S, scripted_server};
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+    use std::sync::atomic::Ordering;
+
+    const DEFAULT: HttpRetry = HttpRetry::new(3, Duration::from_millis(200), true);
+
+    #[test]
+    fn from_params_parses_and_validates() {
+        let mut params = ParamMap::new();
+        assert_eq!(
+            HttpRetry::from_params("demo", &params, DEFAULT).unwrap(),
+            DEFAULT
+        );
+
+        params.insert("max_retries".into(), json!(-1));
+        params.insert("retry_backoff_ms".into(), json!(50));
+        params.insert("respect_retry_after".into(), json!(false));
+        let retry = HttpRetry::from_params("demo", &params, DEFAULT).unwrap();
+        assert_eq!(retry, HttpRetry::new(-1, Duration::from_millis(50), false));
+        assert_eq!(retry.policy().max_attempts, u32::MAX);
+
+        for (key, bad) in [
+            ("max_retries", json!(-2)),
+            ("max_retries", json!("3")),
+            ("retry_backoff_ms", json!(0)),
+            ("respect_retry_after", json!("yes")),
+        ] {
+            let mut params = ParamMap::new();
+            params.insert(key.into(), bad);
+            let err = HttpRetry::from_params("demo", &params, DEFAULT).expect_err(key);
+            assert!(err.to_string().contains(&format!("demo.{key}")), "{err}");
+        }
+    }
+
+    #[test]
+    fn parse_retry_after_accepts_seconds_and_http_date() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("2"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(2)));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[test]
+    fn delay_caps_retry_after_and_falls_back_to_backoff() {
+        let retry = HttpRetry::new(3, Duration::from_millis(100), true);
+        assert_eq!(
+            retry.delay(1, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            retry.delay(1, Some(Duration::from_secs(3600))),
+            MAX_RETRY_WAIT
+        );
+        assert_eq!(retry.delay(2, None), Duration::from_millis(200));
+        let ignore = HttpRetry::new(3, Duration::from_millis(100), false);
+        assert_eq!(
+            ignore.delay(1, Some(Duration::from_secs(2))),
+            Duration::from_millis(100)
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_after_retry_after_delay_then_succeeds() {
+        let (url, calls) = scripted_server(vec![UNAVAILABLE_RETRY_1S]).await;
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let retry = HttpRetry::new(3, Duration::from_millis(10), true);
+        let start = tokio::time::Instant::now();
+        let resp = retry
+            .send("demo", || client.post(&url).body("{}"))
+            .await
+            .expect("second attempt succeeds");
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn client_error_is_not_retried() {
+        let (url, calls) = scripted_server(vec![
+            "HTTP/1.1 400 Bad Request\r\nContent-Length: 3\r\nConnection: close\r\n\r\nbad",
+        ])
+        .await;
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let err = DEFAULT
+            .send("demo", || client.post(&url))
+            .await
+            .expect_err("4xx fails");
+        assert!(err.to_string().contains("status=400"), "{err}");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn too_many_requests_is_retried_until_budget_is_spent() {
+        const THROTTLED: &str =
+            "HTTP/1.1 429 Too Many Requests\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        let (url, calls) = scripted_server(vec![THROTTLED; 5]).await;
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let retry = HttpRetry::new(2, Duration::from_millis(5), true);
+        let err = retry
+            .send("demo", || client.post(&url))
+            .await
+            .expect_err("budget exhausted");
+        assert!(err.to_string().contains("status=429"), "{err}");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
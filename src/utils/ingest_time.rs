@@ -0,0 +1,136 @@
+//! 源端注入的接入时间
+//!
+//! Source 配置 `ingest_time_field` 后，每个产出的 `SourceEvent` 带上以该字段为名的标签，
+//! 值为事件被读出时的 Unix 时间戳，单位由 `ingest_time_unit = "nanos" | "millis" | "seconds"`
+//! 指定（默认 `millis`），供下游计算端到端延迟。
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wp_connector_api::{ParamMap, SourceReason, SourceResult, Tags};
+
+use super::time_unit::TimeUnit;
+
+pub const INGEST_TIME_FIELD_PARAM: &str = "ingest_time_field";
+pub const INGEST_TIME_UNIT_PARAM: &str = "ingest_time_unit";
+
+/// 接入时间标签的名称与单位
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestTime {
+    pub field: String,
+    #[serde(default)]
+    pub unit: TimeUnit,
+}
+
+impl IngestTime {
+    pub fn new(field: impl Into<String>, unit: TimeUnit) -> Self {
+        Self {
+            field: field.into(),
+            unit,
+        }
+    }
+
+    /// 解析 `ingest_time_field` / `ingest_time_unit`；未配置字段时返回 `None`
+    pub fn from_params(connector: &str, params: &ParamMap) -> SourceResult<Option<Self>> {
+        let field = match params.get(INGEST_TIME_FIELD_PARAM) {
+            None | Some(Value::Null) => None,
+            Some(Value::String(s)) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            Some(_) => {
+                return Err(SourceReason::Other(format!(
+                    "{connector}.{INGEST_TIME_FIELD_PARAM} must be a non-empty string"
+                ))
+                .into());
+            }
+        };
+        let unit = match params.get(INGEST_TIME_UNIT_PARAM) {
+            None => TimeUnit::default(),
+            Some(v) => v.as_str().and_then(TimeUnit::parse).ok_or_else(|| {
+                SourceReason::Other(format!(
+                    "{connector}.{INGEST_TIME_UNIT_PARAM} must be one of: nanos, millis, seconds"
+                ))
+            })?,
+        };
+        if field.is_none() && params.contains_key(INGEST_TIME_UNIT_PARAM) {
+            return Err(SourceReason::Other(format!(
+                "{connector}.{INGEST_TIME_UNIT_PARAM} requires {connector}.{INGEST_TIME_FIELD_PARAM}"
+            ))
+            .into());
+        }
+        Ok(field.map(|field| Self::new(field, unit)))
+    }
+
+    /// 当前时间按配置单位表示的 Unix 时间戳
+    pub fn now(&self) -> i64 {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as i64)
+            .unwrap_or_default();
+        match self.unit {
+            TimeUnit::Nanos => nanos,
+            TimeUnit::Millis => nanos / 1_000_000,
+            TimeUnit::Seconds => nanos / 1_000_000_000,
+        }
+    }
+
+    /// 在标签中写入当前接入时间
+    pub fn stamp(&self, tags: &mut Tags) {
+        tags.set(self.field.as_str(), self.now().to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_params_parses_field_and_unit() {
+        let mut params = ParamMap::new();
+        assert_eq!(IngestTime::from_params("kafka", &params).unwrap(), None);
+
+        params.insert(INGEST_TIME_FIELD_PARAM.into(), json!(" ingest_ts "));
+        assert_eq!(
+            IngestTime::from_params("kafka", &params).unwrap(),
+            Some(IngestTime::new("ingest_ts", TimeUnit::Millis))
+        );
+        params.insert(INGEST_TIME_UNIT_PARAM.into(), json!("nanos"));
+        assert_eq!(
+            IngestTime::from_params("kafka", &params).unwrap(),
+            Some(IngestTime::new("ingest_ts", TimeUnit::Nanos))
+        );
+
+        for (key, bad, expect) in [
+            (
+                INGEST_TIME_FIELD_PARAM,
+                json!(""),
+                "kafka.ingest_time_field",
+            ),
+            (
+                INGEST_TIME_UNIT_PARAM,
+                json!("minutes"),
+                "kafka.ingest_time_unit",
+            ),
+        ] {
+            let mut params = params.clone();
+            params.insert(key.into(), bad);
+            let err = IngestTime::from_params("kafka", &params).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+        params.remove(INGEST_TIME_FIELD_PARAM);
+        let err = IngestTime::from_params("kafka", &params).expect_err("unit without field");
+        assert!(err.to_string().contains("requires kafka.ingest_time_field"));
+    }
+
+    #[test]
+    fn stamp_writes_current_time_in_unit() {
+        let millis = IngestTime::new("ingest_ts", TimeUnit::Millis);
+        let nanos = IngestTime::new("ingest_ts", TimeUnit::Nanos);
+        let before = nanos.now();
+        let mut tags = Tags::new();
+        millis.stamp(&mut tags);
+        let stamped: i64 = tags.get("ingest_ts").unwrap().parse().unwrap();
+        assert!(stamped >= before / 1_000_000 && stamped <= millis.now());
+        assert!(nanos.now() >= before);
+    }
+}
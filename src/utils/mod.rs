@@ -1,3 +1,60 @@
 //! 通用工具模块
+pub mod bytes_encoding;
+#[cfg(any(feature = "mysql", feature = "doris"))]
+pub mod column_filter;
+#[cfg(any(feature = "mysql", feature = "doris"))]
+pub mod column_map;
+#[cfg(any(feature = "clickhouse", feature = "elasticsearch"))]
+pub mod compress;
+pub mod config_blob;
+#[cfg(any(
+    feature = "mysql",
+    feature = "doris",
+    feature = "clickhouse",
+    feature = "sqlite"
+))]
+pub mod db_metrics;
+pub mod decorate;
+#[cfg(any(feature = "kafka", feature = "http", feature = "victorialogs"))]
+pub mod envelope;
+pub mod error_kind;
+#[cfg(any(feature = "mysql", feature = "mongodb", feature = "elasticsearch"))]
+pub mod flush_limit;
 pub mod fmt;
+#[cfg(any(feature = "victorialogs", feature = "victoriametrics"))]
+pub mod http_auth;
+#[cfg(any(
+    feature = "victorialogs",
+    feature = "victoriametrics",
+    feature = "elasticsearch",
+    feature = "clickhouse",
+    feature = "s3",
+    feature = "iceberg"
+))]
+pub mod http_client;
+#[cfg(any(feature = "http", feature = "victorialogs"))]
+pub mod http_retry;
+#[cfg(any(feature = "kafka", feature = "mysql"))]
+pub mod ingest_time;
+pub mod retry;
+#[cfg(any(feature = "mysql", feature = "doris"))]
+pub mod schema;
+#[cfg(any(feature = "prometheus", feature = "victoriametrics"))]
+pub mod series_ttl;
+pub mod shutdown;
+pub mod sink_metrics;
+#[cfg(any(feature = "mysql", feature = "doris"))]
+pub mod table_route;
+pub mod template;
 pub mod time_stat_utils;
+#[cfg(any(feature = "victorialogs", feature = "kafka", feature = "mysql"))]
+pub mod time_unit;
+#[cfg(any(
+    feature = "victorialogs",
+    feature = "victoriametrics",
+    feature = "elasticsearch",
+    feature = "clickhouse",
+    feature = "mqtt",
+    feature = "grpc"
+))]
+pub mod tls;
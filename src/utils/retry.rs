@@ -0,0 +1,132 @@
+//! 重试策略与带退避的重连
+//!
+//! `AsyncCtrl::reconnect` 只尝试一次时，后端短暂抖动就会让自动恢复失败。
+//! [`reconnect_with_backoff`] 按 [`RetryPolicy`] 重复执行重连动作，
+//! 每次失败后按指数退避等待，全部失败时返回最后一次的错误。
+
+use std::future::Future;
+use std::time::Duration;
+
+use wp_connector_api::SinkResult;
+
+/// 有界的指数退避重试策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// 总尝试次数（含首次），至少为 1
+    pub max_attempts: u32,
+    /// 首次失败后的等待时间，之后每次翻倍
+    pub initial_backoff: Duration,
+    /// 单次等待时间上限
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub const fn new(max_attempts: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    /// sink 重连使用的默认策略：最多 5 次，100ms 起步，单次不超过 2s
+    pub const fn reconnect() -> Self {
+        Self::new(5, Duration::from_millis(100), Duration::from_secs(2))
+    }
+
+    /// 第 `attempt` 次（从 1 开始）失败后的等待时间
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::reconnect()
+    }
+}
+
+/// 按策略重复执行 `op` 直到成功；用尽次数后返回最后一次的错误
+pub async fn reconnect_with_backoff<T, F, Fut>(
+    label: &str,
+    policy: RetryPolicy,
+    mut op: F,
+) -> SinkResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = SinkResult<T>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt >= max_attempts => return Err(err),
+            Err(err) => {
+                let delay = policy.backoff(attempt);
+                wp_log::warn_data!(
+                    "[{}] reconnect attempt {}/{} failed: {}; retry in {:?}",
+                    label,
+                    attempt,
+                    max_attempts,
+                    err,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use wp_connector_api::{SinkError, SinkReason};
+
+    const FAST: RetryPolicy =
+        RetryPolicy::new(3, Duration::from_millis(5), Duration::from_millis(20));
+
+    #[test]
+    fn backoff_doubles_until_cap() {
+        let policy = RetryPolicy::new(10, Duration::from_millis(100), Duration::from_millis(500));
+        let delays: Vec<_> = (1..=5).map(|n| policy.backoff(n).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+        assert_eq!(policy.backoff(64), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn succeeds_on_second_attempt_after_transient_failure() {
+        let calls = AtomicU32::new(0);
+        let value = reconnect_with_backoff("test", FAST, || async {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(SinkError::from(SinkReason::sink("transient")))
+            } else {
+                Ok(42)
+            }
+        })
+        .await
+        .expect("second attempt succeeds");
+        assert_eq!(value, 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts_with_last_error() {
+        let calls = AtomicU32::new(0);
+        let err = reconnect_with_backoff("test", FAST, || async {
+            let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Err::<(), _>(SinkError::from(SinkReason::sink(format!("attempt {n}"))))
+        })
+        .await
+        .expect_err("all attempts fail");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(err.to_string().contains("attempt 3"), "{err}");
+    }
+}
@@ -0,0 +1,266 @@
+//! DB Sink 记录级 JSON Schema 校验
+//!
+//! MySQL / Doris Sink 支持：
+//! - `schema`：JSON Schema 文件路径；配置后每条记录投影为字段对象（字段名 -> 类型化取值）后校验；
+//! - `on_invalid`：校验失败的处理方式，`count`（默认，丢弃并计入
+//!   `wparse_db_sink_rows{result="invalid"}`）或 `dlq`（同时追加到死信文件）；
+//! - `dlq_path`：`on_invalid = "dlq"` 时的死信文件，每行一个 `{"error":..,"record":..}` JSON。
+//!
+//! 参数形状在 `validate_spec` 阶段校验，Schema 文件在 `build` 阶段读取并编译。
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+
+use serde_json::{Map, Value, json};
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+use wp_model_core::model::{DataRecord, DataType, Value as FieldValue};
+
+/// Schema 校验相关参数名，供各工厂加入 `allow_override`
+pub const SCHEMA_PARAMS: [&str; 3] = ["schema", "on_invalid", "dlq_path"];
+
+/// 校验失败记录的处理方式
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InvalidAction {
+    /// 丢弃并计数
+    #[default]
+    Count,
+    /// 丢弃、计数并追加到死信文件
+    Dlq(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaOptions {
+    /// JSON Schema 文件路径；未配置时不校验
+    pub schema: Option<String>,
+    pub on_invalid: InvalidAction,
+}
+
+impl SchemaOptions {
+    /// 从 Sink 参数解析校验配置（只校验参数形状，不读取文件）
+    pub fn from_params(kind: &str, params: &ParamMap) -> SinkResult<Self> {
+        let path = |key: &str| -> SinkResult<Option<String>> {
+            match params.get(key) {
+                None | Some(Value::Null) => Ok(None),
+                Some(Value::String(s)) if !s.trim().is_empty() => Ok(Some(s.trim().to_string())),
+                Some(_) => {
+                    Err(SinkReason::sink(format!("{kind}.{key} must be a non-empty path")).into())
+                }
+            }
+        };
+        let schema = path("schema")?;
+        let dlq_path = path("dlq_path")?;
+        let on_invalid = match params.get("on_invalid").map(|v| v.as_str()) {
+            None | Some(Some("count")) => InvalidAction::Count,
+            Some(Some("dlq")) => InvalidAction::Dlq(dlq_path.ok_or_else(|| {
+                SinkReason::sink(format!(
+                    "{kind}.dlq_path must be set when {kind}.on_invalid = \"dlq\""
+                ))
+            })?),
+            Some(_) => {
+                return Err(SinkReason::sink(format!(
+                    "{kind}.on_invalid must be one of: count, dlq"
+                ))
+                .into());
+            }
+        };
+        Ok(Self { schema, on_invalid })
+    }
+
+    /// 读取并编译 Schema；未配置 `schema` 时返回 `None`
+    pub fn build(&self, kind: &str) -> SinkResult<Option<RecordValidator>> {
+        let Some(path) = &self.schema else {
+            return Ok(None);
+        };
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| SinkReason::sink(format!("{kind}.schema: read '{path}' failed: {e}")))?;
+        let schema: Value = serde_json::from_str(&raw).map_err(|e| {
+            SinkReason::sink(format!("{kind}.schema: '{path}' is not valid JSON: {e}"))
+        })?;
+        RecordValidator::new(kind, &schema, &self.on_invalid).map(Some)
+    }
+}
+
+/// 已编译的记录校验器
+pub struct RecordValidator {
+    validator: jsonschema::Validator,
+    dlq: Option<BufWriter<File>>,
+}
+
+impl RecordValidator {
+    pub fn new(kind: &str, schema: &Value, on_invalid: &InvalidAction) -> SinkResult<Self> {
+        let validator = jsonschema::validator_for(schema).map_err(|e| {
+            SinkReason::sink(format!("{kind}.schema is not a valid JSON Schema: {e}"))
+        })?;
+        let dlq = match on_invalid {
+            InvalidAction::Count => None,
+            InvalidAction::Dlq(path) => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| {
+                        SinkReason::sink(format!("{kind}.dlq_path: open '{path}' failed: {e}"))
+                    })?;
+                Some(BufWriter::new(file))
+            }
+        };
+        Ok(Self { validator, dlq })
+    }
+
+    /// 拆分出通过校验的记录，返回 `(有效记录, 无效记录数)`；无效记录按配置写入死信文件
+    pub fn split(
+        &mut self,
+        records: Vec<Arc<DataRecord>>,
+    ) -> SinkResult<(Vec<Arc<DataRecord>>, usize)> {
+        let mut valid = Vec::with_capacity(records.len());
+        let mut invalid = 0;
+        for record in records {
+            let projected = record_to_json(&record);
+            match self.validator.validate(&projected) {
+                Ok(()) => valid.push(record),
+                Err(err) => {
+                    invalid += 1;
+                    wp_log::warn_data!("record rejected by schema: {}", err);
+                    if let Some(dlq) = self.dlq.as_mut() {
+                        let line = json!({ "error": err.to_string(), "record": projected });
+                        writeln!(dlq, "{line}").map_err(|e| {
+                            SinkReason::sink(format!("write dead-letter record failed: {e}"))
+                        })?;
+                    }
+                }
+            }
+        }
+        if invalid > 0
+            && let Some(dlq) = self.dlq.as_mut()
+        {
+            dlq.flush()
+                .map_err(|e| SinkReason::sink(format!("flush dead-letter file failed: {e}")))?;
+        }
+        Ok((valid, invalid))
+    }
+}
+
+/// 将记录投影为 JSON 对象：数值、布尔保持类型，时间及其它类型取字符串形式
+pub(crate) fn record_to_json(record: &DataRecord) -> Value {
+    let mut map = Map::new();
+    for field in &record.items {
+        if *field.get_meta() == DataType::Ignore {
+            continue;
+        }
+        map.insert(
+            field.get_name().to_string(),
+            value_to_json(field.get_value()),
+        );
+    }
+    Value::Object(map)
+}
+
+fn value_to_json(value: &FieldValue) -> Value {
+    match value {
+        FieldValue::Null | FieldValue::Ignore(_) => Value::Null,
+        FieldValue::Bool(v) => Value::Bool(*v),
+        FieldValue::Digit(v) => json!(v),
+        FieldValue::Float(v) => json!(v),
+        FieldValue::Chars(v) => Value::String(v.to_string()),
+        FieldValue::Obj(obj) => Value::Object(
+            obj.iter()
+                .map(|(key, field)| (key, field.as_field()))
+                .filter(|(_, field)| *field.get_meta() != DataType::Ignore)
+                .map(|(key, field)| (key.to_string(), value_to_json(field.get_value())))
+                .collect(),
+        ),
+        FieldValue::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|field| field.as_field())
+                .filter(|field| *field.get_meta() != DataType::Ignore)
+                .map(|field| value_to_json(field.get_value()))
+                .collect(),
+        ),
+        other => Value::String(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_model_core::model::DataField;
+
+    fn params(pairs: &[(&str, Value)]) -> ParamMap {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    fn record(level: &str, code: i64) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("level", level));
+        record.append(DataField::from_digit("code", code));
+        Arc::new(record)
+    }
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["level", "code"],
+            "properties": {
+                "level": {"enum": ["info", "error"]},
+                "code": {"type": "integer", "minimum": 0}
+            }
+        })
+    }
+
+    #[test]
+    fn from_params_checks_shape() {
+        let opts = SchemaOptions::from_params("demo", &ParamMap::new()).expect("empty");
+        assert_eq!(opts, SchemaOptions::default());
+
+        for (pairs, expect) in [
+            (vec![("on_invalid", json!("skip"))], "demo.on_invalid"),
+            (
+                vec![("on_invalid", json!("dlq"))],
+                "demo.dlq_path must be set",
+            ),
+            (vec![("schema", json!(""))], "demo.schema"),
+        ] {
+            let err = SchemaOptions::from_params("demo", &params(&pairs)).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+
+    #[test]
+    fn split_keeps_valid_and_writes_rejected_to_dlq() {
+        let dlq = std::env::temp_dir().join("wp_connectors_schema_dlq.jsonl");
+        let _ = std::fs::remove_file(&dlq);
+        let action = InvalidAction::Dlq(dlq.display().to_string());
+        let mut validator = RecordValidator::new("demo", &schema(), &action).expect("compile");
+
+        let (valid, invalid) = validator
+            .split(vec![record("info", 200), record("debug", -1)])
+            .expect("split");
+        assert_eq!(valid.len(), 1);
+        assert_eq!(
+            valid[0].field("level").unwrap().get_value().to_string(),
+            "info"
+        );
+        assert_eq!(invalid, 1);
+
+        let content = std::fs::read_to_string(&dlq).expect("dlq written");
+        let line: Value = serde_json::from_str(content.trim()).expect("json line");
+        assert_eq!(line["record"], json!({"level": "debug", "code": -1}));
+        assert!(line["error"].is_string());
+    }
+
+    #[test]
+    fn build_reports_invalid_schema() {
+        let err = RecordValidator::new("demo", &json!({"type": 12}), &InvalidAction::Count)
+            .err()
+            .expect("invalid schema");
+        assert!(
+            err.to_string()
+                .contains("demo.schema is not a valid JSON Schema")
+        );
+    }
+}
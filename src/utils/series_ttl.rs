@@ -0,0 +1,118 @@
+//! 指标标签序列的过期清理
+//!
+//! 指标向量按标签值累积序列，来源或 Sink 下线后对应序列仍以最后的值持续导出。
+//! [`SeriesTtl`] 记录每组标签值最近一次更新的时间，清理时对超过 TTL 未更新的序列调用
+//! `remove_label_values`，避免标签基数无限增长。
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use prometheus::core::{MetricVec, MetricVecBuilder};
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+
+/// 序列过期时间参数名（秒），未配置时不清理
+pub const SERIES_TTL_PARAM: &str = "series_ttl_secs";
+
+/// 一个指标向量中各标签序列的最近更新时间
+#[derive(Debug, Default)]
+pub struct SeriesTtl {
+    seen: Mutex<HashMap<Vec<String>, Instant>>,
+}
+
+impl SeriesTtl {
+    /// 记录序列被更新；应在更新指标之前调用，避免清理删掉刚写入的值
+    pub fn touch(&self, values: &[&str]) {
+        self.touch_at(values, Instant::now());
+    }
+
+    fn touch_at(&self, values: &[&str], now: Instant) {
+        let key = values.iter().map(|v| v.to_string()).collect();
+        self.seen
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, now);
+    }
+
+    /// 从 `vec` 中删除超过 `ttl` 未更新的序列，返回删除的数量
+    pub fn cleanup<T: MetricVecBuilder>(
+        &self,
+        vec: &MetricVec<T>,
+        ttl: Duration,
+        now: Instant,
+    ) -> usize {
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        let before = seen.len();
+        seen.retain(|values, at| {
+            if now.saturating_duration_since(*at) <= ttl {
+                return true;
+            }
+            let labels: Vec<&str> = values.iter().map(String::as_str).collect();
+            // 序列可能已被其它途径删除，忽略“不存在”的错误
+            let _ = vec.remove_label_values(&labels);
+            false
+        });
+        before - seen.len()
+    }
+}
+
+/// 解析 `series_ttl_secs`：正整数秒
+pub fn parse_series_ttl(kind: &str, params: &ParamMap) -> SinkResult<Option<Duration>> {
+    match params.get(SERIES_TTL_PARAM) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(secs) if secs > 0 => Ok(Some(Duration::from_secs(secs))),
+            _ => Err(SinkReason::sink(format!(
+                "{kind}.{SERIES_TTL_PARAM} must be a positive integer"
+            ))
+            .into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::core::Collector;
+    use prometheus::{IntCounterVec, Opts};
+
+    fn series_count(vec: &IntCounterVec) -> usize {
+        vec.collect()[0].get_metric().len()
+    }
+
+    #[test]
+    fn series_untouched_past_ttl_is_removed_on_cleanup() {
+        let vec = IntCounterVec::new(Opts::new("ttl_counter", "test"), &["sink_name"]).unwrap();
+        let tracker = SeriesTtl::default();
+        let ttl = Duration::from_secs(60);
+        let start = Instant::now();
+
+        for name in ["stale", "live"] {
+            tracker.touch_at(&[name], start);
+            vec.with_label_values(&[name]).inc();
+        }
+        tracker.touch_at(&["live"], start + Duration::from_secs(30));
+        assert_eq!(tracker.cleanup(&vec, ttl, start + ttl), 0);
+        assert_eq!(series_count(&vec), 2);
+
+        let removed = tracker.cleanup(&vec, ttl, start + Duration::from_secs(61));
+        assert_eq!(removed, 1);
+        assert_eq!(series_count(&vec), 1);
+        assert!(vec.remove_label_values(&["stale"]).is_err());
+        assert_eq!(vec.with_label_values(&["live"]).get(), 1);
+    }
+
+    #[test]
+    fn parse_series_ttl_requires_positive_seconds() {
+        let mut params = ParamMap::new();
+        assert_eq!(parse_series_ttl("vm", &params).unwrap(), None);
+        params.insert(SERIES_TTL_PARAM.into(), serde_json::json!(300));
+        assert_eq!(
+            parse_series_ttl("vm", &params).unwrap(),
+            Some(Duration::from_secs(300))
+        );
+        params.insert(SERIES_TTL_PARAM.into(), serde_json::json!(0));
+        let err = parse_series_ttl("vm", &params).expect_err("zero ttl");
+        assert!(err.to_string().contains("vm.series_ttl_secs"), "{err}");
+    }
+}
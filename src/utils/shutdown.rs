@@ -0,0 +1,102 @@
+//! 带截止时间的优雅停止
+//!
+//! `AsyncCtrl::stop` 的排空耗时因 Sink 而异。[`DeadlineCtrl::stop_with_deadline`]
+//! 为停止过程设定上限，超时后放弃剩余数据并通过 [`DrainReport`] 报告丢弃数量，
+//! 使上游流水线的关闭时间可预期。
+//!
+//! 该 trait 只能在具体的 Sink 类型上调用，适用于直接构建 Sink 的调用方。
+//! `SinkFactory::build` 返回的 `SinkHandle` 只持有 `dyn AsyncSink`，各通用装饰器也不转发
+//! 该 trait，经工厂构建的 Sink 只能使用 `AsyncCtrl::stop`。
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use wp_connector_api::{AsyncCtrl, SinkResult};
+
+/// 停止排空的结果
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DrainReport {
+    /// 是否因截止时间到达而中断排空
+    pub deadline_hit: bool,
+    /// 截止时间到达时仍未送达、被放弃的记录数
+    pub dropped: u64,
+}
+
+impl DrainReport {
+    /// 在截止时间内完成排空
+    pub fn drained() -> Self {
+        Self::default()
+    }
+
+    /// 截止时间到达，放弃 `dropped` 条记录
+    pub fn timed_out(dropped: u64) -> Self {
+        Self {
+            deadline_hit: true,
+            dropped,
+        }
+    }
+}
+
+/// 在 `AsyncCtrl` 之上提供有界的停止流程
+///
+/// 默认实现在 `deadline` 内等待 `stop` 完成；无内部缓冲的 Sink 直接使用默认实现即可，
+/// 持有待发送数据的 Sink 应覆盖该方法以统计丢弃数量。
+#[async_trait]
+pub trait DeadlineCtrl: AsyncCtrl + Send {
+    async fn stop_with_deadline(&mut self, deadline: Duration) -> SinkResult<DrainReport> {
+        match tokio::time::timeout(deadline, self.stop()).await {
+            Ok(res) => res.map(|_| DrainReport::drained()),
+            Err(_) => Ok(DrainReport::timed_out(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    struct SlowStop {
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for SlowStop {
+        async fn stop(&mut self) -> SinkResult<()> {
+            tokio::time::sleep(self.delay).await;
+            Ok(())
+        }
+
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    impl DeadlineCtrl for SlowStop {}
+
+    #[tokio::test]
+    async fn default_stop_completes_within_deadline() {
+        let mut sink = SlowStop {
+            delay: Duration::from_millis(10),
+        };
+        let report = sink
+            .stop_with_deadline(Duration::from_secs(1))
+            .await
+            .expect("stop");
+        assert_eq!(report, DrainReport::drained());
+    }
+
+    #[tokio::test]
+    async fn default_stop_is_cut_off_at_deadline() {
+        let mut sink = SlowStop {
+            delay: Duration::from_secs(30),
+        };
+        let started = Instant::now();
+        let report = sink
+            .stop_with_deadline(Duration::from_millis(50))
+            .await
+            .expect("stop");
+        assert!(report.deadline_hit);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
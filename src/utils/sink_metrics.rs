@@ -0,0 +1,97 @@
+//! Sink 内部计数快照
+//!
+//! 管理端点需要在没有 Prometheus 的环境中读取各 Sink 的收发计数。实现 [`SinkMetrics`]
+//! 的 Sink 通过 [`SinkCounters`] 累计计数，[`SinkMetrics::metrics`] 返回当前快照；
+//! 未跟踪计数的 Sink 使用默认实现，返回全零。
+//!
+//! 快照只能在具体的 Sink 类型上读取，适用于直接构建 Sink（如 `MysqlSink::new`、
+//! `HttpSink::new`）的调用方。`SinkFactory::build` 返回的 `SinkHandle` 只持有
+//! `dyn AsyncSink`，各通用装饰器（过滤、去重、限速、熔断、落盘等）也不转发该 trait，
+//! 经工厂构建的 Sink 无法读取计数。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use wp_connector_api::SinkResult;
+
+/// Sink 计数快照
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SinkMetricsSnapshot {
+    /// 收到的记录（或原始数据条目）数
+    pub received: u64,
+    /// 成功写入后端的条数
+    pub flushed: u64,
+    /// 写入失败的条数
+    pub failed: u64,
+    /// 成功发送给后端的载荷字节数（SQL 文本、请求体或消息体）
+    pub bytes_sent: u64,
+}
+
+/// 提供计数快照；默认返回全零
+pub trait SinkMetrics {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        SinkMetricsSnapshot::default()
+    }
+}
+
+/// Sink 内部计数器，可在 `&self` 方法中累计
+#[derive(Debug, Default)]
+pub struct SinkCounters {
+    received: AtomicU64,
+    flushed: AtomicU64,
+    failed: AtomicU64,
+    bytes_sent: AtomicU64,
+}
+
+impl SinkCounters {
+    /// 计入收到的 `n` 条数据
+    pub fn receive(&self, n: usize) {
+        self.received.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    /// 按写入结果计入 `rows` 条数据；成功时同时计入发送的 `bytes`
+    pub fn finish<T>(&self, rows: usize, bytes: usize, result: &SinkResult<T>) {
+        if result.is_ok() {
+            self.flushed.fetch_add(rows as u64, Ordering::Relaxed);
+            self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        } else {
+            self.failed.fetch_add(rows as u64, Ordering::Relaxed);
+        }
+    }
+
+    pub fn snapshot(&self) -> SinkMetricsSnapshot {
+        SinkMetricsSnapshot {
+            received: self.received.load(Ordering::Relaxed),
+            flushed: self.flushed.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_connector_api::{SinkError, SinkReason};
+
+    #[test]
+    fn counters_split_flushed_and_failed() {
+        let counters = SinkCounters::default();
+        counters.receive(5);
+        counters.finish(3, 120, &Ok(()));
+        let failed: SinkResult<()> = Err(SinkError::from(SinkReason::sink("down")));
+        counters.finish(2, 80, &failed);
+        assert_eq!(
+            counters.snapshot(),
+            SinkMetricsSnapshot {
+                received: 5,
+                flushed: 3,
+                failed: 2,
+                bytes_sent: 120,
+            }
+        );
+
+        struct Untracked;
+        impl SinkMetrics for Untracked {}
+        assert_eq!(Untracked.metrics(), SinkMetricsSnapshot::default());
+    }
+}
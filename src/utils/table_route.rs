@@ -0,0 +1,223 @@
+//! DB Sink 多表路由
+//!
+//! MySQL / Doris Sink 支持 `table_field = "<字段名>"` 与 `tables = [...]`：
+//! 按记录中该字段的取值选择目标表，取值须为 `tables` 名单中的表或默认的 `table`。
+//! 缺少该字段的记录写入默认表；取值不在名单内的记录被丢弃并计入 `invalid`。
+//! 一批记录先按目标表分组（按各表首次出现的顺序），再逐表生成写入内容。
+
+use std::sync::Arc;
+
+use serde_json::Value;
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+use wp_model_core::model::DataRecord;
+
+/// 路由字段参数名
+pub const TABLE_FIELD_PARAM: &str = "table_field";
+/// 允许路由到的表名单参数名
+pub const TABLES_PARAM: &str = "tables";
+/// 多表路由参数，供各工厂加入 `allow_override`
+pub const TABLE_ROUTE_PARAMS: [&str; 2] = [TABLE_FIELD_PARAM, TABLES_PARAM];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRouter {
+    field: String,
+    tables: Vec<String>,
+}
+
+/// 分组后的一批记录
+#[derive(Debug, Default)]
+pub struct RoutedBatch {
+    /// `(目标表, 记录)`，按各表首次出现的顺序排列
+    pub groups: Vec<(String, Vec<Arc<DataRecord>>)>,
+    /// 目标表不在名单内而被丢弃的记录数
+    pub rejected: usize,
+}
+
+impl TableRouter {
+    pub fn new<I: IntoIterator<Item = S>, S: Into<String>>(
+        field: impl Into<String>,
+        tables: I,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            tables: tables.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// 从 Sink 参数解析路由配置；未配置 `table_field` 时返回 `None`
+    pub fn from_params(kind: &str, params: &ParamMap) -> SinkResult<Option<Self>> {
+        let field = match params.get(TABLE_FIELD_PARAM) {
+            None | Some(Value::Null) => {
+                if params.contains_key(TABLES_PARAM) {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.{TABLES_PARAM} requires {kind}.{TABLE_FIELD_PARAM}"
+                    ))
+                    .into());
+                }
+                return Ok(None);
+            }
+            Some(v) => match v.as_str().map(str::trim) {
+                Some(s) if !s.is_empty() => s.to_string(),
+                _ => {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.{TABLE_FIELD_PARAM} must be a non-empty field name"
+                    ))
+                    .into());
+                }
+            },
+        };
+        let Some(items) = params
+            .get(TABLES_PARAM)
+            .and_then(Value::as_array)
+            .filter(|items| !items.is_empty())
+        else {
+            return Err(SinkReason::sink(format!(
+                "{kind}.{TABLES_PARAM} must be a non-empty array of table names"
+            ))
+            .into());
+        };
+        let mut tables = Vec::with_capacity(items.len());
+        for item in items {
+            // 表名会拼入 SQL 与 Stream Load URL，只允许字母、数字与下划线
+            match item.as_str().map(str::trim) {
+                Some(name) if is_table_name(name) => {
+                    if !tables.iter().any(|t| t == name) {
+                        tables.push(name.to_string());
+                    }
+                }
+                _ => {
+                    return Err(SinkReason::sink(format!(
+                        "{kind}.{TABLES_PARAM} entries must be table names of [A-Za-z0-9_]"
+                    ))
+                    .into());
+                }
+            }
+        }
+        Ok(Some(Self { field, tables }))
+    }
+
+    /// 路由字段名
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// 名单中的表
+    pub fn tables(&self) -> &[String] {
+        &self.tables
+    }
+
+    /// 按目标表分组；`default` 为未携带路由字段时写入的表
+    pub fn route(&self, default: &str, records: Vec<Arc<DataRecord>>) -> RoutedBatch {
+        let mut batch = RoutedBatch::default();
+        for record in records {
+            let table = match record.get_value(&self.field) {
+                None => default.to_string(),
+                Some(value) => {
+                    let table = value.to_string();
+                    if table != default && !self.tables.contains(&table) {
+                        wp_log::warn_data!(
+                            "record skipped: table '{}' from field '{}' is not allowed",
+                            table,
+                            self.field
+                        );
+                        batch.rejected += 1;
+                        continue;
+                    }
+                    table
+                }
+            };
+            match batch.groups.iter_mut().find(|(name, _)| *name == table) {
+                Some((_, group)) => group.push(record),
+                None => batch.groups.push((table, vec![record])),
+            }
+        }
+        batch
+    }
+}
+
+fn is_table_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wp_model_core::model::DataField;
+
+    fn record(id: i64, table: Option<&str>) -> Arc<DataRecord> {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_digit("id", id));
+        if let Some(table) = table {
+            record.append(DataField::from_chars("dest", table));
+        }
+        Arc::new(record)
+    }
+
+    #[test]
+    fn route_groups_by_table_in_first_seen_order() {
+        let router = TableRouter::new("dest", ["orders", "refunds"]);
+        let batch = router.route(
+            "events",
+            vec![
+                record(1, Some("refunds")),
+                record(2, None),
+                record(3, Some("orders")),
+                record(4, Some("refunds")),
+                record(5, Some("users")),
+                record(6, Some("events")),
+            ],
+        );
+        let ids: Vec<(&str, Vec<String>)> = batch
+            .groups
+            .iter()
+            .map(|(table, records)| {
+                let ids = records
+                    .iter()
+                    .map(|r| r.get_value("id").unwrap().to_string())
+                    .collect();
+                (table.as_str(), ids)
+            })
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("refunds", vec!["1".to_string(), "4".to_string()]),
+                ("events", vec!["2".to_string(), "6".to_string()]),
+                ("orders", vec!["3".to_string()]),
+            ]
+        );
+        assert_eq!(batch.rejected, 1);
+    }
+
+    #[test]
+    fn from_params_validates_field_and_tables() {
+        let mut params = ParamMap::new();
+        assert_eq!(TableRouter::from_params("demo", &params).unwrap(), None);
+
+        params.insert("table_field".into(), json!("dest"));
+        params.insert("tables".into(), json!(["orders", " refunds ", "orders"]));
+        assert_eq!(
+            TableRouter::from_params("demo", &params).unwrap(),
+            Some(TableRouter::new("dest", ["orders", "refunds"]))
+        );
+
+        for (key, bad, expect) in [
+            ("tables", json!([]), "demo.tables must be a non-empty array"),
+            ("tables", json!(["orders; DROP"]), "demo.tables entries"),
+            ("table_field", json!(" "), "demo.table_field"),
+        ] {
+            let mut params = params.clone();
+            params.insert(key.into(), bad);
+            let err = TableRouter::from_params("demo", &params).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+
+        params.remove("table_field");
+        let err = TableRouter::from_params("demo", &params).expect_err("tables alone");
+        assert!(
+            err.to_string()
+                .contains("demo.tables requires demo.table_field")
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! 字段模板工具
+//!
+//! 支持在目标名称（subject/topic 等）中使用 `{field}` 占位符，
+//! 发送时以记录中对应字段的值替换，例如 `events.{tenant}`。
+
+use wp_model_core::model::DataRecord;
+
+/// 占位符对应字段缺失时使用的替代值
+pub const MISSING_FIELD_TOKEN: &str = "_";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+/// 预解析的字段模板，避免每条记录重复解析
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldTemplate {
+    segments: Vec<Segment>,
+}
+
+impl FieldTemplate {
+    /// 解析模板字符串；`{` 与 `}` 必须成对出现且占位符字段名非空
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest[start..].starts_with('}') {
+                return Err(format!("unmatched '}}' in template '{template}'"));
+            }
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('}') else {
+                return Err(format!("unclosed '{{' in template '{template}'"));
+            };
+            let name = after[..end].trim();
+            if name.is_empty() || name.contains('{') {
+                return Err(format!("invalid placeholder in template '{template}'"));
+            }
+            segments.push(Segment::Field(name.to_string()));
+            rest = &after[end + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+        Ok(Self { segments })
+    }
+
+    /// 模板是否包含字段占位符
+    pub fn has_fields(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|seg| matches!(seg, Segment::Field(_)))
+    }
+
+    /// 模板中引用的字段名
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|seg| match seg {
+            Segment::Field(name) => Some(name.as_str()),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    /// 渲染模板；字段值经 `sanitize` 处理，缺失字段使用 [`MISSING_FIELD_TOKEN`]
+    pub fn render_with<F>(&self, record: Option<&DataRecord>, sanitize: F) -> String
+    where
+        F: Fn(&str) -> String,
+    {
+        let mut out = String::new();
+        for seg in &self.segments {
+            match seg {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Field(name) => {
+                    let value = record
+                        .and_then(|r| r.get_value(name))
+                        .map(|v| v.to_string())
+                        .filter(|v| !v.is_empty());
+                    match value {
+                        Some(v) => out.push_str(&sanitize(&v)),
+                        None => out.push_str(MISSING_FIELD_TOKEN),
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// 按原样渲染模板（字段值不做处理）
+    pub fn render(&self, record: Option<&DataRecord>) -> String {
+        self.render_with(record, str::to_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_model_core::model::DataField;
+
+    #[test]
+    fn parse_literal_only_template() {
+        let tpl = FieldTemplate::parse("events.all").unwrap();
+        assert!(!tpl.has_fields());
+        assert_eq!(tpl.render(None), "events.all");
+    }
+
+    #[test]
+    fn render_replaces_fields_and_missing_values() {
+        let tpl = FieldTemplate::parse("events.{tenant}.{kind}").unwrap();
+        assert_eq!(tpl.fields().collect::<Vec<_>>(), vec!["tenant", "kind"]);
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("tenant", "acme"));
+        assert_eq!(tpl.render(Some(&record)), "events.acme._");
+        assert_eq!(tpl.render(None), "events._._");
+    }
+
+    #[test]
+    fn render_with_applies_sanitizer() {
+        let tpl = FieldTemplate::parse("logs/{host}").unwrap();
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("host", "a b"));
+        let out = tpl.render_with(Some(&record), |v| v.replace(' ', "_"));
+        assert_eq!(out, "logs/a_b");
+    }
+
+    #[test]
+    fn parse_rejects_unbalanced_braces() {
+        assert!(FieldTemplate::parse("events.{tenant").is_err());
+        assert!(FieldTemplate::parse("events.tenant}").is_err());
+        assert!(FieldTemplate::parse("events.{}").is_err());
+    }
+}
@@ -0,0 +1,74 @@
+//! 整数时间戳单位
+//!
+//! VictoriaLogs 的 `create_time_field` 与 Kafka 的 `timestamp_field` 读取整数时间戳时，
+//! 通过 `time_unit = "nanos" | "millis" | "seconds"` 指定单位（默认 `millis`）。
+
+use serde::{Deserialize, Serialize};
+
+/// 整数时间戳的单位
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeUnit {
+    Nanos,
+    #[default]
+    Millis,
+    Seconds,
+}
+
+impl TimeUnit {
+    /// 解析 `time_unit` 参数，仅接受 `nanos` / `millis` / `seconds`
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "nanos" => Some(Self::Nanos),
+            "millis" => Some(Self::Millis),
+            "seconds" => Some(Self::Seconds),
+            _ => None,
+        }
+    }
+
+    /// 换算为纳秒；负数或溢出时返回 `None`
+    pub fn to_nanos(self, value: i64) -> Option<i64> {
+        if value < 0 {
+            return None;
+        }
+        match self {
+            Self::Nanos => Some(value),
+            Self::Millis => value.checked_mul(1_000_000),
+            Self::Seconds => value.checked_mul(1_000_000_000),
+        }
+    }
+
+    /// 换算为毫秒（纳秒向下取整）；负数或溢出时返回 `None`
+    pub fn to_millis(self, value: i64) -> Option<i64> {
+        if value < 0 {
+            return None;
+        }
+        match self {
+            Self::Nanos => Some(value / 1_000_000),
+            Self::Millis => Some(value),
+            Self::Seconds => value.checked_mul(1_000),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_between_units() {
+        assert_eq!(TimeUnit::parse(" Seconds "), Some(TimeUnit::Seconds));
+        assert_eq!(TimeUnit::parse("micros"), None);
+        assert_eq!(
+            TimeUnit::Seconds.to_millis(1_700_000_000),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(
+            TimeUnit::Nanos.to_millis(1_700_000_000_123_456_789),
+            Some(1_700_000_000_123)
+        );
+        assert_eq!(TimeUnit::Millis.to_nanos(1), Some(1_000_000));
+        assert_eq!(TimeUnit::Millis.to_millis(-1), None);
+        assert_eq!(TimeUnit::Seconds.to_millis(i64::MAX), None);
+    }
+}
@@ -0,0 +1,337 @@
+//! HTTP 客户端 TLS 配置
+//!
+//! 基于 HTTP 的 Sink（VictoriaLogs、VictoriaMetrics、Elasticsearch、ClickHouse、gRPC）及 MQTT Sink 统一支持：
+//! - `tls_ca_cert`：额外信任的 CA 证书（PEM 文件，可含多张），用于私有 CA 签发的服务端证书；
+//! - `tls_client_cert` / `tls_client_key`：双向 TLS 的客户端证书与私钥（PEM 文件），需成对配置；
+//! - `tls_insecure`：跳过服务端证书校验，仅用于测试环境。
+//!
+//! 参数形状在 `validate_spec` 阶段校验，证书文件在 `build` 阶段读取，
+//! 读取或解析失败以 `<kind>.tls_xxx ...` 形式的 Sink 错误返回。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wp_connector_api::{ParamMap, SinkReason, SinkResult};
+
+/// TLS 相关参数名，供各工厂加入 `allow_override`
+pub const TLS_PARAMS: [&str; 4] = [
+    "tls_ca_cert",
+    "tls_client_cert",
+    "tls_client_key",
+    "tls_insecure",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsOptions {
+    /// CA 证书 PEM 文件路径
+    pub ca_cert: Option<String>,
+    /// 客户端证书 PEM 文件路径
+    pub client_cert: Option<String>,
+    /// 客户端私钥 PEM 文件路径
+    pub client_key: Option<String>,
+    /// 是否跳过服务端证书校验
+    pub insecure: bool,
+}
+
+/// 已读取的证书内容
+struct TlsMaterial {
+    ca_pem: Option<Vec<u8>>,
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl TlsOptions {
+    /// 从 Sink 参数解析 TLS 配置（只校验参数形状，不读取文件）
+    pub fn from_params(kind: &str, params: &ParamMap) -> SinkResult<Self> {
+        let path = |key: &str| -> SinkResult<Option<String>> {
+            match params.get(key) {
+                None | Some(Value::Null) => Ok(None),
+                Some(Value::String(s)) if !s.trim().is_empty() => Ok(Some(s.trim().to_string())),
+                Some(_) => {
+                    Err(SinkReason::sink(format!("{kind}.{key} must be a non-empty path")).into())
+                }
+            }
+        };
+        let insecure = match params.get("tls_insecure") {
+            None | Some(Value::Null) => false,
+            Some(Value::Bool(b)) => *b,
+            Some(_) => {
+                return Err(SinkReason::sink(format!("{kind}.tls_insecure must be a bool")).into());
+            }
+        };
+        let opts = Self {
+            ca_cert: path("tls_ca_cert")?,
+            client_cert: path("tls_client_cert")?,
+            client_key: path("tls_client_key")?,
+            insecure,
+        };
+        if opts.client_cert.is_some() != opts.client_key.is_some() {
+            return Err(SinkReason::sink(format!(
+                "{kind}.tls_client_cert and {kind}.tls_client_key must be set together"
+            ))
+            .into());
+        }
+        Ok(opts)
+    }
+
+    /// 是否配置了任何 TLS 选项
+    pub fn is_configured(&self) -> bool {
+        self.ca_cert.is_some() || self.client_cert.is_some() || self.insecure
+    }
+
+    fn load(&self, kind: &str) -> SinkResult<TlsMaterial> {
+        let ca_pem = self
+            .ca_cert
+            .as_deref()
+            .map(|p| read_pem(kind, "tls_ca_cert", p))
+            .transpose()?;
+        let identity = match (&self.client_cert, &self.client_key) {
+            (Some(cert), Some(key)) => Some((
+                read_pem(kind, "tls_client_cert", cert)?,
+                read_pem(kind, "tls_client_key", key)?,
+            )),
+            _ => None,
+        };
+        Ok(TlsMaterial { ca_pem, identity })
+    }
+
+    /// 将 TLS 配置应用到 reqwest 客户端构建器
+    #[cfg(any(
+        feature = "victorialogs",
+        feature = "victoriametrics",
+        feature = "elasticsearch",
+        feature = "clickhouse",
+        feature = "grpc"
+    ))]
+    pub fn apply_reqwest(
+        &self,
+        kind: &str,
+        mut builder: reqwest::ClientBuilder,
+    ) -> SinkResult<reqwest::ClientBuilder> {
+        let material = self.load(kind)?;
+        if let Some(pem) = material.ca_pem {
+            let certs = reqwest::Certificate::from_pem_bundle(&pem)
+                .ok()
+                .filter(|certs| !certs.is_empty())
+                .ok_or_else(|| invalid_pem(kind, "tls_ca_cert"))?;
+            builder = builder.tls_certs_merge(certs);
+        }
+        if let Some((cert, key)) = material.identity {
+            let mut pem = cert;
+            pem.push(b'\n');
+            pem.extend_from_slice(&key);
+            let identity = reqwest::Identity::from_pem(&pem)
+                .map_err(|_| invalid_pem(kind, "tls_client_cert"))?;
+            builder = builder.identity(identity);
+        }
+        Ok(builder.tls_danger_accept_invalid_certs(self.insecure))
+    }
+
+    /// 构建 rustls 客户端配置，供不基于 reqwest 的客户端（ClickHouse、MQTT）使用；
+    /// 信任 webpki 内置根证书及 `tls_ca_cert` 中的证书
+    #[cfg(any(feature = "clickhouse", feature = "mqtt"))]
+    pub fn rustls_config(&self, kind: &str) -> SinkResult<rustls::ClientConfig> {
+        use rustls::pki_types::pem::PemObject;
+        use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+        use std::sync::Arc;
+
+        let material = self.load(kind)?;
+        let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+        let builder = rustls::ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|e| SinkReason::sink(format!("{kind}: init tls failed: {e}")))?;
+
+        let builder = if self.insecure {
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(insecure::AcceptAnyCert(provider)))
+        } else {
+            let mut roots = rustls::RootCertStore::empty();
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+            if let Some(pem) = &material.ca_pem {
+                let certs = CertificateDer::pem_slice_iter(pem)
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()
+                    .filter(|certs| !certs.is_empty())
+                    .ok_or_else(|| invalid_pem(kind, "tls_ca_cert"))?;
+                for cert in certs {
+                    roots
+                        .add(cert)
+                        .map_err(|_| invalid_pem(kind, "tls_ca_cert"))?;
+                }
+            }
+            builder.with_root_certificates(roots)
+        };
+
+        match material.identity {
+            Some((cert, key)) => {
+                let certs = CertificateDer::pem_slice_iter(&cert)
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()
+                    .filter(|certs| !certs.is_empty())
+                    .ok_or_else(|| invalid_pem(kind, "tls_client_cert"))?;
+                let key = PrivateKeyDer::from_pem_slice(&key)
+                    .map_err(|_| invalid_pem(kind, "tls_client_key"))?;
+                builder
+                    .with_client_auth_cert(certs, key)
+                    .map_err(|e| SinkReason::sink(format!("{kind}.tls_client_key: {e}")).into())
+            }
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+}
+
+fn read_pem(kind: &str, key: &str, path: &str) -> SinkResult<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| SinkReason::sink(format!("{kind}.{key}: read '{path}' failed: {e}")).into())
+}
+
+fn invalid_pem(kind: &str, key: &str) -> wp_connector_api::SinkError {
+    SinkReason::sink(format!("{kind}.{key} is not a valid PEM file")).into()
+}
+
+#[cfg(any(feature = "clickhouse", feature = "mqtt"))]
+mod insecure {
+    use std::sync::Arc;
+
+    use rustls::DigitallySignedStruct;
+    use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use rustls::crypto::{CryptoProvider, verify_tls12_signature, verify_tls13_signature};
+    use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+
+    /// `tls_insecure = true` 时使用：接受任意服务端证书，但仍校验握手签名
+    #[derive(Debug)]
+    pub(super) struct AcceptAnyCert(pub(super) Arc<CryptoProvider>);
+
+    impl ServerCertVerifier for AcceptAnyCert {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            _ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls12_signature(
+                message,
+                cert,
+                dss,
+                &self.0.signature_verification_algorithms,
+            )
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls13_signature(
+                message,
+                cert,
+                dss,
+                &self.0.signature_verification_algorithms,
+            )
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+            self.0.signature_verification_algorithms.supported_schemes()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn params(pairs: &[(&str, Value)]) -> ParamMap {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn from_params_defaults_to_disabled() {
+        let opts = TlsOptions::from_params("demo", &ParamMap::new()).expect("empty params");
+        assert!(!opts.is_configured());
+    }
+
+    #[test]
+    fn from_params_rejects_bad_shapes() {
+        for (pairs, expect) in [
+            (vec![("tls_insecure", json!("yes"))], "demo.tls_insecure"),
+            (vec![("tls_ca_cert", json!(" "))], "demo.tls_ca_cert"),
+            (
+                vec![("tls_client_cert", json!("/tmp/c.pem"))],
+                "must be set together",
+            ),
+        ] {
+            let err = TlsOptions::from_params("demo", &params(&pairs)).expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+
+    #[cfg(any(
+        feature = "victorialogs",
+        feature = "victoriametrics",
+        feature = "elasticsearch",
+        feature = "clickhouse",
+        feature = "grpc"
+    ))]
+    #[test]
+    fn apply_reqwest_reports_missing_and_invalid_ca() {
+        let opts = TlsOptions {
+            ca_cert: Some("/nonexistent/ca.pem".into()),
+            ..Default::default()
+        };
+        let err = opts
+            .apply_reqwest("demo", reqwest::Client::builder())
+            .expect_err("missing ca file");
+        assert!(
+            err.to_string()
+                .contains("demo.tls_ca_cert: read '/nonexistent/ca.pem'")
+        );
+
+        let path = std::env::temp_dir().join("wp_connectors_tls_invalid_ca.pem");
+        std::fs::write(&path, "not a certificate").unwrap();
+        let opts = TlsOptions {
+            ca_cert: Some(path.display().to_string()),
+            ..Default::default()
+        };
+        let err = opts
+            .apply_reqwest("demo", reqwest::Client::builder())
+            .expect_err("invalid pem");
+        assert!(
+            err.to_string()
+                .contains("demo.tls_ca_cert is not a valid PEM file")
+        );
+    }
+
+    #[cfg(any(
+        feature = "victorialogs",
+        feature = "victoriametrics",
+        feature = "elasticsearch",
+        feature = "clickhouse",
+        feature = "grpc"
+    ))]
+    #[test]
+    fn apply_reqwest_accepts_insecure_only() {
+        let opts = TlsOptions {
+            insecure: true,
+            ..Default::default()
+        };
+        let builder = opts
+            .apply_reqwest("demo", reqwest::Client::builder())
+            .expect("insecure");
+        assert!(builder.build().is_ok());
+    }
+}
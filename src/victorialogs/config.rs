@@ -1,6 +1,8 @@
 use educe::Educe;
 use serde::Deserialize;
 use serde::Serialize;
+
+pub use crate::utils::time_unit::TimeUnit;
 #[derive(Educe, Deserialize, Serialize, PartialEq, Clone)]
 #[educe(Debug, Default)]
 pub struct VictoriaLog {
@@ -9,8 +11,22 @@ pub struct VictoriaLog {
     #[educe(Default = "/insert/jsonline")]
     pub insert_path: String,
     pub create_time_field: Option<String>,
+    /// `create_time_field` 为整数时间戳时的单位
+    #[serde(default)]
+    pub time_unit: TimeUnit,
     /// 请求超时（秒），数据量大时可适当调高。
     #[educe(Default = 60.0)]
     pub request_timeout_secs: f64,
     pub tags: Vec<String>,
+    /// `_msg` 取自该字段；未配置或记录缺少该字段时使用按 `fmt` 格式化的整条记录
+    #[serde(default)]
+    pub message_field: Option<String>,
+    /// 是否将记录的全部字段平铺到顶层（默认开启）
+    #[educe(Default = true)]
+    #[serde(default = "default_flatten_fields")]
+    pub flatten_fields: bool,
+}
+
+fn default_flatten_fields() -> bool {
+    true
 }
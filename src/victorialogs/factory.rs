@@ -6,10 +6,17 @@ use wp_connector_api::{
     ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError, SinkFactory,
     SinkHandle, SinkReason, SinkResult, SinkSpec,
 };
-use wp_model_core::model::fmt_def::TextFmt;
 
-use super::config::VictoriaLog;
-use super::sink::VictoriaLogSink;
+use super::config::{TimeUnit, VictoriaLog};
+use super::sink::{DEFAULT_RETRY, VictoriaLogSink};
+use crate::spill::{self, SPILL_DIR_PARAM};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::envelope::{ENVELOPE_PARAMS, Envelope};
+use crate::utils::fmt::parse_text_fmt;
+use crate::utils::http_auth::{HTTP_AUTH_PARAMS, HttpAuth};
+use crate::utils::http_client::{HTTP_CLIENT_PARAMS, HttpClientOptions};
+use crate::utils::http_retry::{HTTP_RETRY_PARAMS, HttpRetry};
+use crate::utils::tls::{TLS_PARAMS, TlsOptions};
 
 pub struct VictoriaLogSinkFactory;
 
@@ -19,6 +26,8 @@ impl SinkFactory for VictoriaLogSinkFactory {
         "victorialogs"
     }
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
+        spill::parse_spill_dir(spec)?;
         let endpoint = spec
             .params
             .get("endpoint")
@@ -27,6 +36,15 @@ impl SinkFactory for VictoriaLogSinkFactory {
         if endpoint.trim().is_empty() {
             return Err(SinkReason::sink("victorialog.endpoint must not be empty").into());
         }
+        parse_time_unit(spec)?;
+        parse_message_field(spec)?;
+        parse_flatten_fields(spec)?;
+        parse_text_fmt("victorialog", spec.params.get("fmt"))?;
+        TlsOptions::from_params("victorialog", &spec.params)?;
+        HttpClientOptions::from_params("victorialog", &spec.params)?;
+        HttpAuth::from_params("victorialog", &spec.params)?;
+        HttpRetry::from_params("victorialog", &spec.params, DEFAULT_RETRY)?;
+        Envelope::from_params("victorialog", &spec.params)?;
         Ok(())
     }
     async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
@@ -44,17 +62,16 @@ impl SinkFactory for VictoriaLogSinkFactory {
         {
             conf.create_time_field = Some(s.to_string());
         }
-        if let Some(v) = spec.params.get("request_timeout_secs") {
-            if let Some(n) = v.as_f64() {
-                if n > 0.0 {
-                    conf.request_timeout_secs = n;
-                }
-            } else if let Some(s) = v.as_str()
-                && let Ok(n) = s.parse::<f64>()
-                && n > 0.0
-            {
-                conf.request_timeout_secs = n;
-            }
+        if let Some(unit) = parse_time_unit(spec)? {
+            conf.time_unit = unit;
+        }
+        conf.message_field = parse_message_field(spec)?;
+        if let Some(flatten) = parse_flatten_fields(spec)? {
+            conf.flatten_fields = flatten;
+        }
+        let http = HttpClientOptions::from_params("victorialog", &spec.params)?;
+        if let Some(timeout) = http.request_timeout {
+            conf.request_timeout_secs = timeout.as_secs_f64();
         }
         if let Some(v) = spec.params.get("tags")
             && let Some(tags) = v.as_array()
@@ -66,14 +83,12 @@ impl SinkFactory for VictoriaLogSinkFactory {
             );
         }
 
-        let fmt = spec
-            .params
-            .get("fmt")
-            .and_then(|v| v.as_str())
-            .map(TextFmt::from)
-            .unwrap_or(TextFmt::Json);
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs_f64(conf.request_timeout_secs))
+        let fmt = parse_text_fmt("victorialog", spec.params.get("fmt"))?;
+        let tls = TlsOptions::from_params("victorialog", &spec.params)?;
+        let builder =
+            reqwest::Client::builder().timeout(Duration::from_secs_f64(conf.request_timeout_secs));
+        let client = http
+            .apply_reqwest(tls.apply_reqwest("victorialog", builder)?)
             .build()
             .map_err(|err| {
                 SinkError::from(SinkReason::sink(format!(
@@ -86,9 +101,20 @@ impl SinkFactory for VictoriaLogSinkFactory {
             client,
             fmt,
             conf.create_time_field.clone(),
+            conf.time_unit,
             conf.tags.clone(),
-        );
-        Ok(SinkHandle::new(Box::new(sink)))
+        )
+        .with_message_field(conf.message_field.clone())
+        .with_flatten_fields(conf.flatten_fields)
+        .with_envelope(Envelope::from_params("victorialog", &spec.params)?)
+        .with_auth(HttpAuth::from_params("victorialog", &spec.params)?)
+        .with_retry(HttpRetry::from_params(
+            "victorialog",
+            &spec.params,
+            DEFAULT_RETRY,
+        )?);
+        let handle = spill::wrap_from_spec(spec, SinkHandle::new(Box::new(sink)))?;
+        decorate_sink(spec, handle)
     }
 }
 
@@ -98,16 +124,66 @@ impl SinkDefProvider for VictoriaLogSinkFactory {
             id: "victorialogs_sink".into(),
             kind: self.kind().into(),
             scope: ConnectorScope::Sink,
-            allow_override: vec!["endpoint", "insert_path", "create_time_field", "tags"]
-                .into_iter()
-                .map(str::to_string)
-                .collect(),
+            allow_override: vec![
+                "endpoint",
+                "insert_path",
+                "create_time_field",
+                "time_unit",
+                "tags",
+                "message_field",
+                "flatten_fields",
+                SPILL_DIR_PARAM,
+            ]
+            .into_iter()
+            .chain(TLS_PARAMS)
+            .chain(HTTP_CLIENT_PARAMS)
+            .chain(HTTP_AUTH_PARAMS)
+            .chain(HTTP_RETRY_PARAMS)
+            .chain(ENVELOPE_PARAMS)
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect(),
             default_params: victorialog_defaults(),
             origin: Some("wp-connectors:victorialogs_sink".into()),
         }
     }
 }
 
+/// 解析可选的 `time_unit` 参数
+fn parse_time_unit(spec: &SinkSpec) -> SinkResult<Option<TimeUnit>> {
+    let Some(raw) = spec.params.get("time_unit") else {
+        return Ok(None);
+    };
+    raw.as_str()
+        .and_then(TimeUnit::parse)
+        .map(Some)
+        .ok_or_else(|| {
+            SinkReason::sink("victorialog.time_unit must be one of: nanos, millis, seconds").into()
+        })
+}
+
+/// 解析可选的 `message_field` 参数，配置时必须为非空字符串
+fn parse_message_field(spec: &SinkSpec) -> SinkResult<Option<String>> {
+    let Some(raw) = spec.params.get("message_field") else {
+        return Ok(None);
+    };
+    match raw.as_str().map(str::trim) {
+        Some(name) if !name.is_empty() => Ok(Some(name.to_string())),
+        _ => Err(SinkReason::sink("victorialog.message_field must be a non-empty string").into()),
+    }
+}
+
+/// 解析可选的 `flatten_fields` 开关
+fn parse_flatten_fields(spec: &SinkSpec) -> SinkResult<Option<bool>> {
+    match spec.params.get("flatten_fields") {
+        None => Ok(None),
+        Some(v) => v
+            .as_bool()
+            .map(Some)
+            .ok_or_else(|| SinkReason::sink("victorialog.flatten_fields must be a bool").into()),
+    }
+}
+
 fn victorialog_defaults() -> ParamMap {
     let mut params = ParamMap::new();
     params.insert("endpoint".into(), json!("http://127.0.0.1:9428"));
@@ -129,8 +205,20 @@ mod tests {
                 "endpoint".to_string(),
                 "insert_path".to_string(),
                 "create_time_field".to_string(),
+                "time_unit".to_string(),
                 "tags".to_string(),
+                "message_field".to_string(),
+                "flatten_fields".to_string(),
+                SPILL_DIR_PARAM.to_string(),
             ]
+            .into_iter()
+            .chain(TLS_PARAMS.map(str::to_string))
+            .chain(HTTP_CLIENT_PARAMS.map(str::to_string))
+            .chain(HTTP_AUTH_PARAMS.map(str::to_string))
+            .chain(HTTP_RETRY_PARAMS.map(str::to_string))
+            .chain(ENVELOPE_PARAMS.map(str::to_string))
+            .chain(DECORATOR_PARAMS.map(str::to_string))
+            .collect::<Vec<_>>()
         );
         assert_eq!(
             def.default_params.get("endpoint").and_then(|v| v.as_str()),
@@ -144,4 +232,111 @@ mod tests {
         );
         assert!(!def.default_params.contains_key("flush_interval_secs"));
     }
+
+    #[test]
+    fn validate_rejects_unknown_time_unit() {
+        let mut spec = SinkSpec {
+            group: "test".into(),
+            name: "victorialogs_sink".into(),
+            kind: "victorialogs".into(),
+            connector_id: String::new(),
+            params: victorialog_defaults(),
+            filter: None,
+        };
+        spec.params.insert("time_unit".into(), json!("seconds"));
+        assert!(VictoriaLogSinkFactory.validate_spec(&spec).is_ok());
+        spec.params.insert("time_unit".into(), json!("minutes"));
+        assert!(VictoriaLogSinkFactory.validate_spec(&spec).is_err());
+    }
+
+    #[test]
+    fn validate_checks_retry_params() {
+        let mut spec = SinkSpec {
+            group: "test".into(),
+            name: "victorialogs_sink".into(),
+            kind: "victorialogs".into(),
+            connector_id: String::new(),
+            params: victorialog_defaults(),
+            filter: None,
+        };
+        spec.params.insert("max_retries".into(), json!(5));
+        spec.params.insert("retry_backoff_ms".into(), json!(50));
+        spec.params
+            .insert("respect_retry_after".into(), json!(false));
+        assert!(VictoriaLogSinkFactory.validate_spec(&spec).is_ok());
+
+        spec.params.insert("retry_backoff_ms".into(), json!(0));
+        let err = VictoriaLogSinkFactory
+            .validate_spec(&spec)
+            .expect_err("zero backoff");
+        assert!(
+            err.to_string().contains("victorialog.retry_backoff_ms"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn validate_checks_message_field_and_flatten_fields() {
+        let mut spec = SinkSpec {
+            group: "test".into(),
+            name: "victorialogs_sink".into(),
+            kind: "victorialogs".into(),
+            connector_id: String::new(),
+            params: victorialog_defaults(),
+            filter: None,
+        };
+        spec.params.insert("message_field".into(), json!("message"));
+        spec.params.insert("flatten_fields".into(), json!(false));
+        assert!(VictoriaLogSinkFactory.validate_spec(&spec).is_ok());
+
+        for (key, bad) in [
+            ("message_field", json!("")),
+            ("flatten_fields", json!("no")),
+        ] {
+            let mut spec = spec.clone();
+            spec.params.insert(key.into(), bad);
+            let err = VictoriaLogSinkFactory.validate_spec(&spec).expect_err(key);
+            assert!(format!("{err}").contains(&format!("victorialog.{key}")));
+        }
+    }
+
+    #[test]
+    fn validate_rejects_unknown_fmt() {
+        let mut spec = SinkSpec {
+            group: "test".into(),
+            name: "victorialogs_sink".into(),
+            kind: "victorialogs".into(),
+            connector_id: String::new(),
+            params: victorialog_defaults(),
+            filter: None,
+        };
+        spec.params.insert("fmt".into(), json!("kv"));
+        assert!(VictoriaLogSinkFactory.validate_spec(&spec).is_ok());
+        spec.params.insert("fmt".into(), json!("bogus"));
+        let err = VictoriaLogSinkFactory
+            .validate_spec(&spec)
+            .expect_err("bogus fmt");
+        assert!(format!("{err}").contains("invalid fmt"));
+    }
+
+    #[tokio::test]
+    async fn build_reports_missing_ca_cert() {
+        let mut spec = SinkSpec {
+            group: "test".into(),
+            name: "victorialogs_sink".into(),
+            kind: "victorialogs".into(),
+            connector_id: String::new(),
+            params: victorialog_defaults(),
+            filter: None,
+        };
+        spec.params
+            .insert("tls_ca_cert".into(), json!("/nonexistent/ca.pem"));
+        assert!(VictoriaLogSinkFactory.validate_spec(&spec).is_ok());
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+        let err = VictoriaLogSinkFactory
+            .build(&spec, &ctx)
+            .await
+            .expect_err("missing ca file");
+        assert!(format!("{err}").contains("victorialog.tls_ca_cert: read '/nonexistent/ca.pem'"));
+    }
 }
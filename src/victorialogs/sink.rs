@@ -3,7 +3,6 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use tokio::time::sleep;
 use wp_connector_api::{
     AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkError, SinkReason, SinkResult,
 };
@@ -11,17 +10,37 @@ use wp_data_fmt::{FormatType, RecordFormatter};
 use wp_log::error_data;
 use wp_model_core::model::{DataRecord, Value, fmt_def::TextFmt};
 
+use super::config::TimeUnit;
+use crate::utils::envelope::Envelope;
+use crate::utils::http_auth::HttpAuth;
+use crate::utils::http_retry::HttpRetry;
+use crate::utils::shutdown::DeadlineCtrl;
+use crate::utils::sink_metrics::{SinkCounters, SinkMetrics, SinkMetricsSnapshot};
+
 pub(crate) struct VictoriaLogSink {
     endpoint: String,
     insert_path: String,
     client: reqwest::Client,
     fmt: TextFmt,
     create_time_field: Option<String>,
+    time_unit: TimeUnit,
     tags: HashMap<String, String>,
+    message_field: Option<String>,
+    flatten_fields: bool,
+    envelope: Option<Envelope>,
+    auth: HttpAuth,
+    retry: HttpRetry,
+    counters: SinkCounters,
 }
 
+/// 未配置重试参数时的预算：最多重试 2 次，200ms 起步
+pub(crate) const DEFAULT_RETRY: HttpRetry = HttpRetry::new(2, Duration::from_millis(200), true);
+
 impl VictoriaLogSink {
-    /// 解析时间字段为字符串，逻辑与原实现一致：优先使用 create_time_field，回退当前时间。
+    /// 解析时间字段为字符串：优先使用 create_time_field，回退当前时间。
+    ///
+    /// 字段为 `Value::Time` 时直接取其纳秒时间戳；为 `Value::Digit` 时按 `time_unit`
+    /// 换算为纳秒，负数或溢出同样回退当前时间。
     fn resolve_timestamp_str(&self, data: &DataRecord) -> String {
         let now = chrono::Utc::now()
             .timestamp_nanos_opt()
@@ -35,30 +54,51 @@ impl VictoriaLogSink {
             return now;
         };
 
-        if let Value::Time(dt) = orin_timestamp.get_value() {
-            return dt
+        match orin_timestamp.get_value() {
+            Value::Time(dt) => dt
                 .and_utc()
                 .timestamp_nanos_opt()
                 .unwrap_or_else(|| dt.and_utc().timestamp_millis())
-                .to_string();
+                .to_string(),
+            Value::Digit(epoch) => self
+                .time_unit
+                .to_nanos(*epoch)
+                .map(|nanos| nanos.to_string())
+                .unwrap_or(now),
+            _ => now,
         }
+    }
 
-        now
+    /// 解析 `_msg`：优先取 `message_field` 字段值，缺失时回退为格式化的整条记录；
+    /// 配置了 `envelope` 时再按模板包裹。
+    fn resolve_message(&self, data: &DataRecord) -> String {
+        let message = match self
+            .message_field
+            .as_deref()
+            .and_then(|name| data.get2(name))
+        {
+            Some(field) => field.get_value().to_string(),
+            None => FormatType::from(&self.fmt).fmt_record(data),
+        };
+        match &self.envelope {
+            Some(envelope) => envelope.wrap(&message),
+            None => message,
+        }
     }
 
     /// 构建单条 JSON line 载荷，供单条或批量发送复用。
     fn build_jsonline(&self, data: &DataRecord) -> SinkResult<String> {
-        let mut value_map = data
-            .items
-            .clone()
-            .into_iter()
-            .map(|item| (item.get_name().to_string(), item.get_value().to_string()))
-            .collect::<HashMap<String, String>>();
+        let mut value_map = if self.flatten_fields {
+            data.items
+                .iter()
+                .map(|item| (item.get_name().to_string(), item.get_value().to_string()))
+                .collect::<HashMap<String, String>>()
+        } else {
+            HashMap::new()
+        };
         let timestamp = self.resolve_timestamp_str(data);
-        let fmt = FormatType::from(&self.fmt);
-        let formatted_msg = fmt.fmt_record(data);
         value_map.extend(self.tags.clone());
-        value_map.insert("_msg".to_string(), formatted_msg);
+        value_map.insert("_msg".to_string(), self.resolve_message(data));
         value_map.insert("_time".to_string(), timestamp);
         serde_json::to_string(&value_map).map_err(|e| {
             SinkError::from(SinkReason::Sink(format!(
@@ -69,52 +109,16 @@ impl VictoriaLogSink {
     }
 
     async fn send_payload(&self, payload: String) -> SinkResult<()> {
-        let client = &self.client;
-        let endpoint = &self.endpoint;
-        let insert_path = &self.insert_path;
-        // 重试次数
-        const MAX_ATTEMPTS: usize = 3;
-        // 是重试之间的退避时间
-        const BACKOFF_MS: [u64; 2] = [200, 500];
-        let url = format!("{}{}", endpoint, insert_path);
-
-        for attempt in 0..MAX_ATTEMPTS {
-            match client.post(&url).body(payload.clone()).send().await {
-                Ok(resp) => {
-                    let status = resp.status();
-                    if status.is_success() {
-                        return Ok(());
-                    }
-                    error_data!("reqwest send error, text: {:?}", resp.text().await);
-                    if !status.is_server_error() {
-                        return Err(SinkError::from(SinkReason::Sink(
-                            "reqwest send error".to_string(),
-                        )));
-                    }
-                }
-                Err(e) => {
-                    error_data!("reqwest send error, text: {:?}", e);
-                    if !(e.is_timeout() || e.is_connect()) {
-                        return Err(SinkError::from(SinkReason::Sink(format!(
-                            "reqwest send fail: {}",
-                            e
-                        ))));
-                    }
-                }
-            }
-
-            if attempt + 1 < MAX_ATTEMPTS {
-                let delay = BACKOFF_MS
-                    .get(attempt)
-                    .copied()
-                    .unwrap_or_else(|| BACKOFF_MS[BACKOFF_MS.len() - 1]);
-                sleep(Duration::from_millis(delay)).await;
-            }
-        }
-
-        Err(SinkError::from(SinkReason::Sink(
-            "reqwest send fail after retries".to_string(),
-        )))
+        let url = format!("{}{}", self.endpoint, self.insert_path);
+        self.retry
+            .send("victorialog", || {
+                self.auth
+                    .apply(self.client.post(&url))
+                    .body(payload.clone())
+            })
+            .await
+            .map(|_| ())
+            .inspect_err(|e| error_data!("victorialog send fail: {}", e))
     }
 
     pub(crate) fn new(
@@ -123,6 +127,7 @@ impl VictoriaLogSink {
         client: reqwest::Client,
         fmt: TextFmt,
         create_time_field: Option<String>,
+        time_unit: TimeUnit,
         tags: Vec<String>,
     ) -> Self {
         let tag_map: HashMap<String, String> = tags
@@ -138,22 +143,65 @@ impl VictoriaLogSink {
             client,
             fmt,
             create_time_field,
+            time_unit,
             tags: tag_map,
+            message_field: None,
+            flatten_fields: true,
+            envelope: None,
+            auth: HttpAuth::None,
+            retry: DEFAULT_RETRY,
+            counters: SinkCounters::default(),
         }
     }
+
+    pub(crate) fn with_message_field(mut self, message_field: Option<String>) -> Self {
+        self.message_field = message_field;
+        self
+    }
+
+    pub(crate) fn with_flatten_fields(mut self, flatten_fields: bool) -> Self {
+        self.flatten_fields = flatten_fields;
+        self
+    }
+
+    pub(crate) fn with_envelope(mut self, envelope: Option<Envelope>) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    pub(crate) fn with_auth(mut self, auth: HttpAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    pub(crate) fn with_retry(mut self, retry: HttpRetry) -> Self {
+        self.retry = retry;
+        self
+    }
+}
+
+impl SinkMetrics for VictoriaLogSink {
+    fn metrics(&self) -> SinkMetricsSnapshot {
+        self.counters.snapshot()
+    }
 }
 
 #[async_trait]
 impl AsyncRecordSink for VictoriaLogSink {
     async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.counters.receive(1);
         let res = self.build_jsonline(data)?;
-        self.send_payload(res).await
+        let bytes = res.len();
+        let result = self.send_payload(res).await;
+        self.counters.finish(1, bytes, &result);
+        result
     }
 
     async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
         if data.is_empty() {
             return Ok(());
         }
+        self.counters.receive(data.len());
         let mut buf = String::new();
         for (idx, record) in data.iter().enumerate() {
             let line = self.build_jsonline(record.as_ref())?;
@@ -162,7 +210,10 @@ impl AsyncRecordSink for VictoriaLogSink {
             }
             buf.push_str(&line);
         }
-        self.send_payload(buf).await
+        let bytes = buf.len();
+        let result = self.send_payload(buf).await;
+        self.counters.finish(data.len(), bytes, &result);
+        result
     }
 }
 
@@ -176,6 +227,9 @@ impl AsyncCtrl for VictoriaLogSink {
     }
 }
 
+// 每批日志在 `sink_records` 内同步推送，停止时没有待排空的缓冲
+impl DeadlineCtrl for VictoriaLogSink {}
+
 #[async_trait]
 impl AsyncRawDataSink for VictoriaLogSink {
     async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
@@ -226,6 +280,7 @@ mod tests {
             client,
             TextFmt::Json,
             None,
+            TimeUnit::default(),
             Vec::new(),
         );
 
@@ -233,6 +288,32 @@ mod tests {
         assert!(result.is_ok(), "sink_record should return Ok");
     }
 
+    #[tokio::test]
+    async fn test_send_payload_waits_for_retry_after() {
+        use crate::utils::http_retry::testing::{UNAVAILABLE_RETRY_1S, scripted_server};
+
+        let (url, calls) = scripted_server(vec![UNAVAILABLE_RETRY_1S]).await;
+        let endpoint = url.trim_end_matches("/insert").to_string();
+        let sink = VictoriaLogSink::new(
+            endpoint,
+            "/insert".into(),
+            reqwest::Client::builder().no_proxy().build().unwrap(),
+            TextFmt::Json,
+            None,
+            TimeUnit::default(),
+            vec![],
+        )
+        .with_retry(HttpRetry::new(2, Duration::from_millis(10), true));
+
+        let start = std::time::Instant::now();
+        sink.send_payload("{}".to_string())
+            .await
+            .expect("503 后重试成功");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        // 退避只有 10ms，等待时长来自 Retry-After
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
     /// 创建用于测试的 VictoriaLogSink 实例（mock server）
     fn create_mock_sink(server: &MockServer) -> VictoriaLogSink {
         let client = reqwest::Client::builder()
@@ -247,15 +328,46 @@ mod tests {
             client,
             TextFmt::Json,
             None,
+            TimeUnit::default(),
             Vec::new(),
         )
     }
 
+    #[tokio::test]
+    async fn sink_records_send_authorization_header() {
+        let server = MockServer::start_async().await;
+        for (auth, header) in [
+            (HttpAuth::Bearer("t0ken".into()), "Bearer t0ken"),
+            (
+                HttpAuth::Basic {
+                    user: "user".into(),
+                    pass: Some("pass".into()),
+                },
+                "Basic dXNlcjpwYXNz",
+            ),
+        ] {
+            let mock = server
+                .mock_async(|when, then| {
+                    when.method(POST)
+                        .path("/insert")
+                        .header("authorization", header);
+                    then.status(200);
+                })
+                .await;
+            let mut sink = create_mock_sink(&server).with_auth(auth);
+            sink.sink_records(vec![Arc::new(DataRecord::default())])
+                .await
+                .expect("authorized push");
+            mock.assert_async().await;
+            mock.delete_async().await;
+        }
+    }
+
     #[test]
     fn test_build_jsonline_contains_msg_and_time() {
         let mut record = DataRecord::default();
         record.append(DataField::from_chars("level", "info"));
-        let sink = create_test_sink(None);
+        let sink = create_test_sink(None, TimeUnit::default());
         let line = sink.build_jsonline(&record).expect("构建 jsonline 失败");
         let parsed: JsonValue = serde_json::from_str(&line).expect("解析 json 失败");
 
@@ -268,6 +380,58 @@ mod tests {
         assert!(time_val.parse::<i64>().is_ok(), "_time 应能解析为 i64");
     }
 
+    #[test]
+    fn test_build_jsonline_message_field_and_flatten() {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("level", "info"));
+        record.append(DataField::from_chars("message", "disk full"));
+
+        // (message_field, flatten_fields, 期望的 _msg, 是否包含平铺字段)
+        let cases = [
+            (None, true, None, true),
+            (None, false, None, false),
+            (Some("message"), true, Some("disk full"), true),
+            (Some("message"), false, Some("disk full"), false),
+            (Some("missing"), false, None, false),
+        ];
+        for (message_field, flatten, expect_msg, expect_flat) in cases {
+            let sink = create_test_sink(None, TimeUnit::default())
+                .with_message_field(message_field.map(str::to_string))
+                .with_flatten_fields(flatten);
+            let line = sink.build_jsonline(&record).expect("构建 jsonline 失败");
+            let parsed: JsonValue = serde_json::from_str(&line).expect("解析 json 失败");
+            let msg = parsed["_msg"].as_str().expect("_msg 应为字符串");
+            match expect_msg {
+                Some(expected) => assert_eq!(msg, expected, "{message_field:?}/{flatten}"),
+                // 回退为按 fmt 格式化的整条记录
+                None => assert!(msg.contains("disk full") && msg.contains("info"), "{msg}"),
+            }
+            assert_eq!(
+                parsed.get("level").is_some(),
+                expect_flat,
+                "{message_field:?}/{flatten}"
+            );
+            assert!(parsed.get("_time").is_some());
+        }
+    }
+
+    #[test]
+    fn test_build_jsonline_wraps_msg_in_envelope() {
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("message", "disk full"));
+        let envelope = Envelope::parse(r#"{"source":"{source}","data":"{data}"}"#, None).unwrap();
+        let sink = create_test_sink(None, TimeUnit::default())
+            .with_message_field(Some("message".into()))
+            .with_envelope(Some(envelope));
+        let line = sink.build_jsonline(&record).expect("构建 jsonline 失败");
+        let parsed: JsonValue = serde_json::from_str(&line).expect("解析 json 失败");
+        let msg = parsed["_msg"].as_str().expect("_msg 应为字符串");
+        assert_eq!(
+            serde_json::from_str::<JsonValue>(msg).expect("信封应为合法 json"),
+            serde_json::json!({"source": "wp", "data": "disk full"})
+        );
+    }
+
     #[tokio::test]
     async fn test_send_payload_success() {
         let server = MockServer::start_async().await;
@@ -313,6 +477,59 @@ mod tests {
         assert_eq!(mock_400.calls(), 1, "4xx 不应重试");
     }
 
+    #[tokio::test]
+    async fn sink_metrics_track_flushed_and_failed_records() {
+        let server = MockServer::start_async().await;
+        let ok_mock = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/insert").body_includes("keep");
+                then.status(200);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/insert").body_includes("reject");
+                then.status(400);
+            })
+            .await;
+
+        let mut sink = create_mock_sink(&server).with_message_field(Some("msg".into()));
+        assert_eq!(sink.metrics(), SinkMetricsSnapshot::default());
+
+        let record_with = |msg: &str| {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_chars("msg", msg));
+            record
+        };
+        let batch: Vec<Arc<DataRecord>> = (0..3).map(|_| Arc::new(record_with("keep"))).collect();
+        let expected_bytes = batch
+            .iter()
+            .map(|record| sink.build_jsonline(record).unwrap().len())
+            .sum::<usize>()
+            + batch.len()
+            - 1;
+        sink.sink_records(batch).await.unwrap();
+        assert!(
+            sink.sink_records(vec![
+                Arc::new(record_with("reject")),
+                Arc::new(record_with("reject")),
+            ])
+            .await
+            .is_err()
+        );
+        ok_mock.assert_calls_async(1).await;
+
+        assert_eq!(
+            sink.metrics(),
+            SinkMetricsSnapshot {
+                received: 5,
+                flushed: 3,
+                failed: 2,
+                bytes_sent: expected_bytes as u64,
+            }
+        );
+    }
+
     /// 创建用于测试的 VictoriaLogSink 实例
     ///
     /// # 参数
@@ -320,7 +537,7 @@ mod tests {
     ///
     /// # 返回
     /// 配置好的 VictoriaLogSink 实例，用于测试
-    fn create_test_sink(create_time_field: Option<&str>) -> VictoriaLogSink {
+    fn create_test_sink(create_time_field: Option<&str>, time_unit: TimeUnit) -> VictoriaLogSink {
         let client = reqwest::Client::builder()
             .no_proxy()
             .timeout(Duration::from_secs(1))
@@ -333,6 +550,7 @@ mod tests {
             client,
             TextFmt::Json,
             create_time_field.map(|s| s.to_string()),
+            time_unit,
             Vec::new(),
         )
     }
@@ -341,28 +559,76 @@ mod tests {
     async fn test_resolve_timestamp_str() {
         // 测试用例定义
         //
-        // 格式: (create_time_field, field_value, description)
+        // 格式: (create_time_field, field, time_unit, expected, description)
         //
         // * create_time_field - sink 配置的时间戳字段（None 表示使用默认）
-        // * field_value       - DataRecord 中实际存储的字段值（None 表示不添加字段）
+        // * field             - DataRecord 中实际存储的字段（None 表示不添加字段）
+        // * time_unit         - 整数时间戳的单位
+        // * expected          - 期望的纳秒时间戳（None 表示回退当前时间）
         // * description       - 测试用例描述
         let cases = vec![
-            (None, None, "no custom field"),
-            (Some("timestamp_field"), None, "field not found"),
+            (None, None, TimeUnit::default(), None, "no custom field"),
+            (
+                Some("timestamp_field"),
+                None,
+                TimeUnit::default(),
+                None,
+                "field not found",
+            ),
             (
                 Some("timestamp_field"),
-                Some("1234567890"),
+                Some(DataField::from_chars("timestamp_field", "1234567890")),
+                TimeUnit::default(),
+                None,
                 "with valid field",
             ),
+            (
+                Some("timestamp_field"),
+                Some(DataField::from_digit(
+                    "timestamp_field",
+                    1_700_000_000_123_456_789,
+                )),
+                TimeUnit::Nanos,
+                Some("1700000000123456789"),
+                "epoch nanos",
+            ),
+            (
+                Some("timestamp_field"),
+                Some(DataField::from_digit("timestamp_field", 1_700_000_000_123)),
+                TimeUnit::Millis,
+                Some("1700000000123000000"),
+                "epoch millis",
+            ),
+            (
+                Some("timestamp_field"),
+                Some(DataField::from_digit("timestamp_field", 1_700_000_000)),
+                TimeUnit::Seconds,
+                Some("1700000000000000000"),
+                "epoch seconds",
+            ),
+            (
+                Some("timestamp_field"),
+                Some(DataField::from_digit("timestamp_field", -5)),
+                TimeUnit::Millis,
+                None,
+                "negative epoch falls back to now",
+            ),
+            (
+                Some("timestamp_field"),
+                Some(DataField::from_digit("timestamp_field", i64::MAX)),
+                TimeUnit::Seconds,
+                None,
+                "overflowing epoch falls back to now",
+            ),
         ];
 
-        for (create_time_field, field_value, desc) in cases {
+        for (create_time_field, field, time_unit, expected, desc) in cases {
             let mut record = DataRecord::default();
-            if let Some(value) = field_value {
-                record.append(DataField::from_chars("timestamp_field", value));
+            if let Some(field) = field {
+                record.append(field);
             }
 
-            let sink = create_test_sink(create_time_field);
+            let sink = create_test_sink(create_time_field, time_unit);
             let timestamp = sink.resolve_timestamp_str(&record);
 
             assert!(
@@ -370,11 +636,16 @@ mod tests {
                 "[{}] timestamp should not be empty",
                 desc
             );
-            assert!(
-                timestamp.parse::<i64>().is_ok(),
-                "[{}] timestamp should be valid i64",
-                desc
-            );
+            let parsed = timestamp.parse::<i64>();
+            assert!(parsed.is_ok(), "[{}] timestamp should be valid i64", desc);
+            match expected {
+                Some(expected) => assert_eq!(timestamp, expected, "[{}]", desc),
+                None => assert!(
+                    parsed.unwrap() > 1_700_000_000_000_000_000,
+                    "[{}] timestamp should fall back to now",
+                    desc
+                ),
+            }
         }
     }
 }
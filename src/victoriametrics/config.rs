@@ -1,6 +1,36 @@
 use educe::Educe;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 推送到 VictoriaMetrics 的导入格式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    /// Prometheus 文本格式，推送到 `/api/v1/import/prometheus`
+    #[default]
+    Prometheus,
+    /// JSON line 格式，推送到 `/api/v1/import`
+    Jsonl,
+}
+
+impl ImportFormat {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "prometheus" => Some(Self::Prometheus),
+            "jsonl" => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Prometheus => "prometheus",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
 #[derive(Educe, Deserialize, Serialize, PartialEq, Clone)]
 #[educe(Debug, Default)]
 pub struct VictoriaMetric {
@@ -8,4 +38,45 @@ pub struct VictoriaMetric {
     pub insert_url: String,
     #[educe(Default = 1.0)]
     pub flush_interval_secs: f64,
+    #[serde(default)]
+    pub import_format: ImportFormat,
+    /// 覆盖各指标默认的 `instance` 标签（默认为进程 PID）
+    #[serde(default)]
+    pub instance: Option<String>,
+    /// 附加到各指标的 `host` 标签
+    #[serde(default)]
+    pub host: Option<String>,
+    /// 附加到各指标的其它静态标签
+    #[serde(default)]
+    pub extra_labels: BTreeMap<String, String>,
+    /// 只处理这些 stage（`Pick` / `Parse` / `Sink`）的记录；未设置时全部处理
+    #[serde(default)]
+    pub stages: Option<BTreeSet<String>>,
+    /// 只保留这些记录标签的取值，其余折叠为 `*`；未设置时全部保留
+    #[serde(default)]
+    pub label_allowlist: Option<BTreeSet<String>>,
+}
+
+impl VictoriaMetric {
+    /// 推送前注入到每个指标的静态标签；`instance` / `host` 优先于 `extra_labels` 中的同名项
+    pub fn static_labels(&self) -> BTreeMap<String, String> {
+        let mut labels = self.extra_labels.clone();
+        if let Some(instance) = &self.instance {
+            labels.insert("instance".into(), instance.clone());
+        }
+        if let Some(host) = &self.host {
+            labels.insert("host".into(), host.clone());
+        }
+        labels
+    }
+}
+
+/// Prometheus 标签名规则：`[a-zA-Z_][a-zA-Z0-9_]*`，且 `__` 前缀保留给内部使用
+pub(crate) fn is_valid_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("__")
 }
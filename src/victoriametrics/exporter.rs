@@ -1,7 +1,9 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use orion_conf::StructError;
+use prometheus::proto::{LabelPair, MetricFamily};
 use prometheus::{Encoder, TextEncoder};
 use std::sync::Arc;
 use sysinfo::System;
@@ -10,11 +12,29 @@ use wp_connector_api::{SinkReason, SinkResult};
 use wp_log::{error_data, info_data};
 use wp_model_core::model::{DataRecord, Value};
 
-use super::metrics::{parse_all_stat, receive_data_stat, sink_stat, system_usage_stat};
-pub(crate) struct VictoriaMetricExporter {
+use super::config::ImportFormat;
+use super::jsonl;
+use super::metrics::{
+    cleanup_stale_series, parse_all_stat, receive_data_stat, sink_stat, system_usage_stat,
+};
+use crate::utils::http_auth::HttpAuth;
+use crate::utils::shutdown::{DeadlineCtrl, DrainReport};
+
+/// VictoriaMetrics 指标导出器，按 `flush_interval` 周期推送进程指标
+pub struct VictoriaMetricExporter {
     insert_url: String,
+    import_format: ImportFormat,
+    /// 推送前注入到每个指标的静态标签
+    static_labels: Arc<BTreeMap<String, String>>,
     client: reqwest::Client,
+    auth: HttpAuth,
     flush_interval: Duration,
+    /// 标签序列过期时间，每次定时推送前清理超过该时间未更新的序列；未设置时不清理
+    series_ttl: Option<Duration>,
+    /// 只处理这些 stage 的记录；未设置时全部处理
+    stages: Option<BTreeSet<String>>,
+    /// 只保留这些记录标签的取值，其余折叠为 `*`；未设置时全部保留
+    label_allowlist: Option<BTreeSet<String>>,
     stop_tx: Option<oneshot::Sender<()>>,
     flush_handle: Option<JoinHandle<()>>,
     system: System,
@@ -25,8 +45,14 @@ impl Clone for VictoriaMetricExporter {
         Self {
             system: System::new(),
             insert_url: self.insert_url.clone(),
+            import_format: self.import_format,
+            static_labels: self.static_labels.clone(),
             client: self.client.clone(),
+            auth: self.auth.clone(),
             flush_interval: self.flush_interval,
+            series_ttl: self.series_ttl,
+            stages: self.stages.clone(),
+            label_allowlist: self.label_allowlist.clone(),
             stop_tx: None,
             flush_handle: None,
         }
@@ -41,16 +67,60 @@ impl VictoriaMetricExporter {
     ) -> Self {
         Self {
             insert_url,
+            import_format: ImportFormat::default(),
+            static_labels: Arc::default(),
             flush_interval,
+            series_ttl: None,
+            stages: None,
+            label_allowlist: None,
             stop_tx: None,
             flush_handle: None,
             client,
+            auth: HttpAuth::None,
             system: System::new(),
         }
     }
 
+    pub(crate) fn with_import_format(mut self, import_format: ImportFormat) -> Self {
+        self.import_format = import_format;
+        self
+    }
+
+    pub(crate) fn with_static_labels(mut self, labels: BTreeMap<String, String>) -> Self {
+        self.static_labels = Arc::new(labels);
+        self
+    }
+
+    pub(crate) fn with_series_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.series_ttl = ttl;
+        self
+    }
+
+    pub(crate) fn with_stages(mut self, stages: Option<BTreeSet<String>>) -> Self {
+        self.stages = stages;
+        self
+    }
+
+    pub(crate) fn with_label_allowlist(mut self, labels: Option<BTreeSet<String>>) -> Self {
+        self.label_allowlist = labels;
+        self
+    }
+
+    pub(crate) fn with_auth(mut self, auth: HttpAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
     pub(crate) async fn save_metric_to_victoriametric(&self, ts_ms: Option<i64>) -> SinkResult<()> {
-        Self::push_metrics(&self.client, &self.insert_url, ts_ms).await
+        Self::push_metrics(
+            &self.client,
+            &self.auth,
+            &self.insert_url,
+            self.import_format,
+            &self.static_labels,
+            ts_ms,
+        )
+        .await
     }
 
     pub(crate) fn start_flush_task(&mut self) {
@@ -79,6 +149,12 @@ impl VictoriaMetricExporter {
                         // CPU/内存统计在此统一刷新，避免在每条 DataRecord 中触发
                         // sysinfo 系统调用（flush 间隔即采样间隔）。
                         system_usage_stat(&mut runner.system);
+                        if let Some(ttl) = runner.series_ttl {
+                            let removed = cleanup_stale_series(ttl);
+                            if removed > 0 {
+                                info_data!("VictoriaMetric removed {} stale label series", removed);
+                            }
+                        }
                         if let Err(err) = runner.save_metric_to_victoriametric(Some(curr_sec * 1000)).await {
                             error_data!("VictoriaMetric periodic push failed: {}", err);
                         }
@@ -91,6 +167,23 @@ impl VictoriaMetricExporter {
         self.flush_handle = Some(handle);
     }
 
+    /// 调整推送间隔；定时任务已在运行时停止旧任务并按新间隔重新启动。
+    /// 间隔为 0 时拒绝修改，保留当前任务。
+    pub fn set_flush_interval(&mut self, interval: Duration) -> SinkResult<()> {
+        if interval.is_zero() {
+            return Err(
+                SinkReason::sink("victoriametrics.flush_interval_secs must be positive").into(),
+            );
+        }
+        self.flush_interval = interval;
+        if self.flush_handle.is_some() {
+            self.stop_now();
+            self.start_flush_task();
+            info_data!("VictoriaMetric flush interval changed to {:?}", interval);
+        }
+        Ok(())
+    }
+
     async fn stop_flush_task(&mut self) {
         if let Err(err) = self.save_metric_to_victoriametric(None).await {
             error_data!("VictoriaMetric periodic push failed: {}", err);
@@ -115,22 +208,18 @@ impl VictoriaMetricExporter {
 
     async fn push_metrics(
         client: &reqwest::Client,
+        auth: &HttpAuth,
         insert_url: &str,
+        import_format: ImportFormat,
+        static_labels: &BTreeMap<String, String>,
         ts_ms: Option<i64>,
     ) -> SinkResult<()> {
-        let encoder = TextEncoder::new();
-        let metric_families = prometheus::gather();
+        let mut metric_families = prometheus::gather();
         if metric_families.is_empty() {
             info_data!("No metrics to export");
             return Ok(());
         }
-        let mut buffer = Vec::new();
-        if let Err(e) = encoder.encode(&metric_families, &mut buffer) {
-            return Err(
-                StructError::from(SinkReason::Sink("prometheus encode error".to_string()))
-                    .with_detail(e.to_string()),
-            );
-        }
+        inject_static_labels(&mut metric_families, static_labels);
         // 优先使用调用方提供的时间戳（来自 DataRecord.end_time），否则退回到当前时间。
         let ts = ts_ms.unwrap_or_else(|| {
             SystemTime::now()
@@ -138,12 +227,32 @@ impl VictoriaMetricExporter {
                 .map(|d| d.as_millis() as i64)
                 .unwrap_or(0)
         });
-        // let buffer = append_timestamp_to_each_sample(&buffer, ts);
-        let url = format!("{}?time_stamp={}", insert_url, ts);
-        let response = client.post(&url).body(buffer).send().await.map_err(|e| {
-            StructError::from(SinkReason::Sink("reqwest send error".to_string()))
-                .with_detail(e.to_string())
-        })?;
+        let (url, buffer) = match import_format {
+            ImportFormat::Prometheus => {
+                let mut buffer = Vec::new();
+                if let Err(e) = TextEncoder::new().encode(&metric_families, &mut buffer) {
+                    return Err(StructError::from(SinkReason::Sink(
+                        "prometheus encode error".to_string(),
+                    ))
+                    .with_detail(e.to_string()));
+                }
+                // let buffer = append_timestamp_to_each_sample(&buffer, ts);
+                (format!("{}?time_stamp={}", insert_url, ts), buffer)
+            }
+            ImportFormat::Jsonl => (
+                jsonl::import_url(insert_url),
+                jsonl::encode(&metric_families, ts),
+            ),
+        };
+        let response = auth
+            .apply(client.post(&url))
+            .body(buffer)
+            .send()
+            .await
+            .map_err(|e| {
+                StructError::from(SinkReason::Sink("reqwest send error".to_string()))
+                    .with_detail(e.to_string())
+            })?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -158,25 +267,59 @@ impl VictoriaMetricExporter {
     }
 }
 
+/// 为每个指标写入静态标签：同名标签覆盖原值，其余追加，并按标签名保持有序
+fn inject_static_labels(families: &mut [MetricFamily], labels: &BTreeMap<String, String>) {
+    if labels.is_empty() {
+        return;
+    }
+    for family in families.iter_mut() {
+        for metric in family.mut_metric().iter_mut() {
+            let pairs = &mut metric.label;
+            for (name, value) in labels {
+                match pairs.iter_mut().find(|pair| pair.name() == name) {
+                    Some(pair) => pair.set_value(value.clone()),
+                    None => {
+                        let mut pair = LabelPair::default();
+                        pair.set_name(name.clone());
+                        pair.set_value(value.clone());
+                        pairs.push(pair);
+                    }
+                }
+            }
+            pairs.sort_by(|a, b| a.name().cmp(b.name()));
+        }
+    }
+}
+
 #[async_trait]
 impl wp_connector_api::AsyncRecordSink for VictoriaMetricExporter {
     /// 只负责按 stage 更新 Prometheus counter，不再触发推送。
     /// 推送完全交由 start_flush_task 启动的定时任务处理，
     /// 解耦"数据收集"与"数据上报"，消除事件驱动推送与定时推送的时序冲突。
+    /// 未在 `stages` 白名单中的 stage 直接跳过。
     async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
-        if let Some(Value::Chars(field)) = data.get2("stage").map(|x| x.get_value()) {
-            match field.as_str() {
-                "Pick" => {
-                    receive_data_stat(data);
-                }
-                "Parse" => {
-                    parse_all_stat(data);
-                }
-                "Sink" => {
-                    sink_stat(data);
-                }
-                _ => {}
+        let Some(Value::Chars(field)) = data.get2("stage").map(|x| x.get_value()) else {
+            return Ok(());
+        };
+        if self
+            .stages
+            .as_ref()
+            .is_some_and(|stages| !stages.contains(field.as_str()))
+        {
+            return Ok(());
+        }
+        let labels = self.label_allowlist.as_ref();
+        match field.as_str() {
+            "Pick" => {
+                receive_data_stat(data, labels);
             }
+            "Parse" => {
+                parse_all_stat(data, labels);
+            }
+            "Sink" => {
+                sink_stat(data, labels);
+            }
+            _ => {}
         }
         Ok(())
     }
@@ -200,6 +343,30 @@ impl wp_connector_api::AsyncCtrl for VictoriaMetricExporter {
     }
 }
 
+#[async_trait]
+impl DeadlineCtrl for VictoriaMetricExporter {
+    /// 停止前的最后一次推送受截止时间约束；超时则放弃该次推送并直接终止定时任务。
+    /// 指标为累计值，未推送的增量会在下次启动后的推送中体现，因此不计入丢弃数。
+    async fn stop_with_deadline(&mut self, deadline: Duration) -> SinkResult<DrainReport> {
+        let pushed = tokio::time::timeout(deadline, self.save_metric_to_victoriametric(None)).await;
+        self.stop_now();
+        match pushed {
+            Ok(Ok(())) => Ok(DrainReport::drained()),
+            Ok(Err(err)) => {
+                error_data!("VictoriaMetric periodic push failed: {}", err);
+                Ok(DrainReport::drained())
+            }
+            Err(_) => {
+                error_data!(
+                    "VictoriaMetric final push exceeded stop deadline {:?}",
+                    deadline
+                );
+                Ok(DrainReport::timed_out(0))
+            }
+        }
+    }
+}
+
 #[async_trait]
 impl wp_connector_api::AsyncRawDataSink for VictoriaMetricExporter {
     async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
@@ -311,6 +478,100 @@ mod tests {
         assert_eq!(sink_counter.get(), sink_before + 1);
     }
 
+    /// 不在 `stages` 白名单中的 stage 不更新任何指标
+    #[tokio::test]
+    async fn sink_record_skips_excluded_stage() {
+        let mut exporter = test_exporter().with_stages(Some(BTreeSet::from(["Sink".to_string()])));
+
+        let mut pick_record = DataRecord::default();
+        pick_record.append(DataField::from_chars("stage", "Pick"));
+        pick_record.append(DataField::from_digit("total", 3));
+        pick_record.append(DataField::from_chars("wp_source_type", "kafka"));
+        pick_record.append(DataField::from_chars("wp_access_ip", "10.0.0.9"));
+        let (pick_values, _) = source_values(&pick_record);
+        let pick_counter = RECV_FROM_SOURCE.with_label_values(&pick_values.values());
+        let before = pick_counter.get();
+        exporter.sink_record(&pick_record).await.unwrap();
+        assert_eq!(pick_counter.get(), before);
+    }
+
+    /// 不在 `label_allowlist` 中的记录标签取值折叠为 `*`
+    #[tokio::test]
+    async fn sink_record_collapses_labels_outside_allowlist() {
+        let mut exporter =
+            test_exporter().with_label_allowlist(Some(BTreeSet::from(["sink_name".to_string()])));
+
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("stage", "Sink"));
+        record.append(DataField::from_digit("success", 4));
+        record.append(DataField::from_chars("wp_sink_group", "group-collapsed"));
+        record.append(DataField::from_chars("wp_sink_name", "sink-allowlisted"));
+        let (mut values, _) = send_sink(&record);
+        values.sink_group = "*".into();
+        let counter = SEND_TO_SINK.with_label_values(&values.values());
+        let before = counter.get();
+        exporter.sink_record(&record).await.unwrap();
+        assert_eq!(counter.get(), before + 4);
+    }
+
+    /// 服务端不响应时，停止流程在截止时间附近返回并终止定时任务
+    #[tokio::test]
+    async fn stop_with_deadline_bounds_final_push() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                held.push(stream);
+            }
+        });
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .build()
+            .expect("client");
+        let mut exporter = VictoriaMetricExporter::new(
+            format!("http://{addr}/insert"),
+            client,
+            Duration::from_secs(60),
+        );
+        exporter.start_flush_task();
+
+        let started = std::time::Instant::now();
+        let report = exporter
+            .stop_with_deadline(Duration::from_millis(100))
+            .await
+            .expect("stop");
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(report.dropped, 0);
+        assert!(exporter.flush_handle.is_none());
+        assert!(exporter.stop_tx.is_none());
+    }
+
+    /// 修改间隔会停止旧定时任务并以新间隔启动新任务；间隔为 0 时保持不变
+    #[tokio::test]
+    async fn set_flush_interval_restarts_flush_task() {
+        let mut exporter = test_exporter();
+        exporter.set_flush_interval(Duration::from_secs(5)).unwrap();
+        assert!(exporter.flush_handle.is_none(), "not started yet");
+
+        exporter.start_flush_task();
+        let old = exporter.flush_handle.as_ref().unwrap().abort_handle();
+        exporter
+            .set_flush_interval(Duration::from_secs(30))
+            .unwrap();
+        let new = exporter.flush_handle.as_ref().unwrap().abort_handle();
+        assert_ne!(old.id(), new.id());
+        assert_eq!(exporter.flush_interval, Duration::from_secs(30));
+        tokio::task::yield_now().await;
+        assert!(old.is_finished(), "old flush task must be stopped");
+        assert!(!new.is_finished());
+
+        assert!(exporter.set_flush_interval(Duration::ZERO).is_err());
+        assert_eq!(exporter.flush_interval, Duration::from_secs(30));
+        assert_eq!(exporter.flush_handle.as_ref().unwrap().id(), new.id());
+        exporter.stop_now();
+    }
+
     // #[tokio::test]
     // async fn flush_task_start_and_stop_transitions() {
     //     let mut exporter = test_exporter();
@@ -322,4 +583,92 @@ mod tests {
     //     assert!(exporter.flush_handle.is_none());
     //     assert!(exporter.stop_tx.is_none());
     // }
+
+    #[tokio::test]
+    async fn push_metrics_sends_authorization_header() {
+        use httpmock::prelude::*;
+
+        // 确保默认 registry 中至少有一个指标
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("wp_rule_name", "auth-target"));
+        let (values, _) = parse_all(&record);
+        PARSE_ALL.with_label_values(&values.values()).inc();
+
+        let server = MockServer::start_async().await;
+        for (auth, header) in [
+            (HttpAuth::Bearer("t0ken".into()), "Bearer t0ken"),
+            (
+                HttpAuth::Basic {
+                    user: "user".into(),
+                    pass: Some("pass".into()),
+                },
+                "Basic dXNlcjpwYXNz",
+            ),
+        ] {
+            let mock = server
+                .mock_async(|when, then| {
+                    when.method(POST)
+                        .path("/api/v1/import/prometheus")
+                        .header("authorization", header);
+                    then.status(204);
+                })
+                .await;
+            let client = reqwest::Client::builder().no_proxy().build().unwrap();
+            let exporter = VictoriaMetricExporter::new(
+                server.url("/api/v1/import/prometheus"),
+                client,
+                Duration::from_secs(1),
+            )
+            .with_auth(auth);
+            exporter
+                .save_metric_to_victoriametric(Some(1_700_000_000_000))
+                .await
+                .expect("authorized push");
+            mock.assert_async().await;
+            mock.delete_async().await;
+        }
+    }
+
+    #[test]
+    fn gathered_families_carry_static_labels() {
+        let registry = prometheus::Registry::new();
+        let counter = prometheus::IntCounterVec::new(
+            prometheus::Opts::new("wp_static_label_test", "test counter"),
+            &["instance", "sink_name"],
+        )
+        .expect("counter");
+        registry
+            .register(Box::new(counter.clone()))
+            .expect("register");
+        counter.with_label_values(&["12345", "s1"]).inc();
+
+        let mut families = registry.gather();
+        let labels = BTreeMap::from([
+            ("instance".to_string(), "edge-01".to_string()),
+            ("host".to_string(), "node-a".to_string()),
+            ("env".to_string(), "prod".to_string()),
+        ]);
+        inject_static_labels(&mut families, &labels);
+
+        let metric = &families[0].get_metric()[0];
+        let pairs: Vec<_> = metric
+            .get_label()
+            .iter()
+            .map(|p| (p.name(), p.value()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("env", "prod"),
+                ("host", "node-a"),
+                ("instance", "edge-01"),
+                ("sink_name", "s1"),
+            ]
+        );
+
+        let mut text = Vec::new();
+        TextEncoder::new().encode(&families, &mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        assert!(text.contains(r#"host="node-a""#), "{text}");
+    }
 }
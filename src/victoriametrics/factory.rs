@@ -1,14 +1,21 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::Duration;
 
 use async_trait::async_trait;
-use serde_json::json;
+use serde_json::{Value, json};
 use wp_connector_api::{
     ConnectorDef, ConnectorScope, ParamMap, SinkBuildCtx, SinkDefProvider, SinkError, SinkFactory,
     SinkHandle, SinkReason, SinkResult, SinkSpec,
 };
 
-use super::config::VictoriaMetric;
+use super::config::{ImportFormat, VictoriaMetric, is_valid_label_name};
 use super::exporter::VictoriaMetricExporter;
+use super::metrics::{RECORD_LABELS, STAGES};
+use crate::utils::decorate::{DECORATOR_PARAMS, decorate_sink, validate_decorators};
+use crate::utils::http_auth::{HTTP_AUTH_PARAMS, HttpAuth};
+use crate::utils::http_client::{HTTP_CLIENT_PARAMS, HttpClientOptions};
+use crate::utils::series_ttl::{SERIES_TTL_PARAM, parse_series_ttl};
+use crate::utils::tls::{TLS_PARAMS, TlsOptions};
 
 pub struct VictoriaMetricFactory;
 
@@ -18,6 +25,7 @@ impl SinkFactory for VictoriaMetricFactory {
         "victoriametrics"
     }
     fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        validate_decorators(spec)?;
         let insert_url = spec
             .params
             .get("insert_url")
@@ -27,6 +35,13 @@ impl SinkFactory for VictoriaMetricFactory {
         if insert_url.trim().is_empty() {
             return Err(SinkReason::sink("victoriametrics.insert_url must not be empty").into());
         }
+        parse_import_format(spec)?;
+        parse_static_labels(spec, &mut VictoriaMetric::default())?;
+        parse_stage_filters(spec, &mut VictoriaMetric::default())?;
+        parse_series_ttl("victoriametrics", &spec.params)?;
+        TlsOptions::from_params("victoriametrics", &spec.params)?;
+        HttpClientOptions::from_params("victoriametrics", &spec.params)?;
+        HttpAuth::from_params("victoriametrics", &spec.params)?;
         Ok(())
     }
     async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
@@ -50,9 +65,15 @@ impl SinkFactory for VictoriaMetricFactory {
         {
             conf.insert_url = s.to_string();
         }
+        conf.import_format = parse_import_format(spec)?;
+        parse_static_labels(spec, &mut conf)?;
+        parse_stage_filters(spec, &mut conf)?;
 
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(5))
+        let tls = TlsOptions::from_params("victoriametrics", &spec.params)?;
+        let builder = reqwest::Client::builder().timeout(Duration::from_secs(5));
+        let http = HttpClientOptions::from_params("victoriametrics", &spec.params)?;
+        let client = http
+            .apply_reqwest(tls.apply_reqwest("victoriametrics", builder)?)
             .build()
             .map_err(|err| {
                 SinkError::from(SinkReason::sink(format!(
@@ -63,10 +84,16 @@ impl SinkFactory for VictoriaMetricFactory {
             conf.insert_url.clone(),
             client,
             Duration::from_secs_f64(conf.flush_interval_secs),
-        );
+        )
+        .with_import_format(conf.import_format)
+        .with_static_labels(conf.static_labels())
+        .with_stages(conf.stages.clone())
+        .with_label_allowlist(conf.label_allowlist.clone())
+        .with_series_ttl(parse_series_ttl("victoriametrics", &spec.params)?)
+        .with_auth(HttpAuth::from_params("victoriametrics", &spec.params)?);
         // 启动定时 flush 任务：计数器收集与推送解耦，
         sink.start_flush_task();
-        Ok(SinkHandle::new(Box::new(sink)))
+        decorate_sink(spec, SinkHandle::new(Box::new(sink)))
     }
 }
 
@@ -76,16 +103,127 @@ impl SinkDefProvider for VictoriaMetricFactory {
             id: "victoriametrics_sink".into(),
             kind: self.kind().into(),
             scope: ConnectorScope::Sink,
-            allow_override: vec!["insert_url", "flush_interval_secs"]
-                .into_iter()
-                .map(str::to_string)
-                .collect(),
+            allow_override: vec![
+                "insert_url",
+                "flush_interval_secs",
+                "import_format",
+                "instance",
+                "host",
+                "extra_labels",
+                "stages",
+                "label_allowlist",
+                SERIES_TTL_PARAM,
+            ]
+            .into_iter()
+            .chain(TLS_PARAMS)
+            .chain(HTTP_CLIENT_PARAMS)
+            .chain(HTTP_AUTH_PARAMS)
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect(),
             default_params: victoriametric_defaults(),
             origin: Some("wp-connectors:victoriametrics_sink".into()),
         }
     }
 }
 
+fn parse_import_format(spec: &SinkSpec) -> SinkResult<ImportFormat> {
+    match spec.params.get("import_format") {
+        None => Ok(ImportFormat::default()),
+        Some(v) => v.as_str().and_then(ImportFormat::parse).ok_or_else(|| {
+            SinkReason::sink("victoriametrics.import_format must be one of: prometheus, jsonl")
+                .into()
+        }),
+    }
+}
+
+/// 解析 `instance` / `host` / `extra_labels` 静态标签
+fn parse_static_labels(spec: &SinkSpec, conf: &mut VictoriaMetric) -> SinkResult<()> {
+    for (key, slot) in [("instance", &mut conf.instance), ("host", &mut conf.host)] {
+        *slot = match spec.params.get(key) {
+            None => None,
+            Some(v) => match v.as_str().map(str::trim) {
+                Some(s) if !s.is_empty() => Some(s.to_string()),
+                _ => {
+                    return Err(SinkReason::sink(format!(
+                        "victoriametrics.{key} must be a non-empty string"
+                    ))
+                    .into());
+                }
+            },
+        };
+    }
+    conf.extra_labels = match spec.params.get("extra_labels") {
+        None => BTreeMap::new(),
+        Some(Value::Object(map)) => {
+            let mut labels = BTreeMap::new();
+            for (name, value) in map {
+                if !is_valid_label_name(name) {
+                    return Err(SinkReason::sink(format!(
+                        "victoriametrics.extra_labels key '{name}' is not a valid label name"
+                    ))
+                    .into());
+                }
+                let Some(value) = value.as_str() else {
+                    return Err(SinkReason::sink(format!(
+                        "victoriametrics.extra_labels.{name} must be a string"
+                    ))
+                    .into());
+                };
+                labels.insert(name.clone(), value.to_string());
+            }
+            labels
+        }
+        Some(_) => {
+            return Err(SinkReason::sink("victoriametrics.extra_labels must be an object").into());
+        }
+    };
+    Ok(())
+}
+
+/// 解析 `stages` / `label_allowlist` 白名单；`stages` 不区分大小写，统一为规范写法
+fn parse_stage_filters(spec: &SinkSpec, conf: &mut VictoriaMetric) -> SinkResult<()> {
+    conf.stages = parse_allowlist(spec, "stages", &STAGES, true)?;
+    conf.label_allowlist = parse_allowlist(spec, "label_allowlist", &RECORD_LABELS, false)?;
+    Ok(())
+}
+
+fn parse_allowlist(
+    spec: &SinkSpec,
+    key: &str,
+    known: &[&str],
+    ignore_case: bool,
+) -> SinkResult<Option<BTreeSet<String>>> {
+    let Some(value) = spec.params.get(key) else {
+        return Ok(None);
+    };
+    let Value::Array(items) = value else {
+        return Err(
+            SinkReason::sink(format!("victoriametrics.{key} must be an array of strings")).into(),
+        );
+    };
+    let mut set = BTreeSet::new();
+    for item in items {
+        let name = item.as_str().map(str::trim).unwrap_or_default();
+        let found = known.iter().find(|k| {
+            if ignore_case {
+                k.eq_ignore_ascii_case(name)
+            } else {
+                **k == name
+            }
+        });
+        let Some(found) = found else {
+            return Err(SinkReason::sink(format!(
+                "victoriametrics.{key} entry {item} must be one of: {}",
+                known.join(", ")
+            ))
+            .into());
+        };
+        set.insert(found.to_string());
+    }
+    Ok(Some(set))
+}
+
 fn victoriametric_defaults() -> ParamMap {
     let mut params = ParamMap::new();
     params.insert(
@@ -95,6 +233,10 @@ fn victoriametric_defaults() -> ParamMap {
     // flush_interval_secs 决定推送到 VictoriaMetrics 的时间分辨率，
     // 1s 可获得秒级数据点，适合 rate([20s+]) 的稳定计算。
     params.insert("flush_interval_secs".into(), json!(1));
+    params.insert(
+        "import_format".into(),
+        json!(ImportFormat::Prometheus.as_str()),
+    );
     params
 }
 
@@ -124,7 +266,24 @@ mod tests {
         assert_eq!(def.id, "victoriametrics_sink");
         assert_eq!(
             def.allow_override,
-            vec!["insert_url".to_string(), "flush_interval_secs".to_string()]
+            [
+                "insert_url",
+                "flush_interval_secs",
+                "import_format",
+                "instance",
+                "host",
+                "extra_labels",
+                "stages",
+                "label_allowlist",
+                SERIES_TTL_PARAM,
+            ]
+            .into_iter()
+            .chain(TLS_PARAMS)
+            .chain(HTTP_CLIENT_PARAMS)
+            .chain(HTTP_AUTH_PARAMS)
+            .chain(DECORATOR_PARAMS)
+            .map(str::to_string)
+            .collect::<Vec<_>>()
         );
         assert_eq!(
             def.default_params
@@ -154,4 +313,113 @@ mod tests {
         let spec = sink_spec(&[("endpoint", json!("http://localhost:8480"))]);
         assert!(VictoriaMetricFactory.validate_spec(&spec).is_ok());
     }
+
+    #[test]
+    fn validate_checks_import_format() {
+        let url = ("insert_url", json!("http://127.0.0.1:8428/api/v1/import"));
+        let spec = sink_spec(&[url.clone(), ("import_format", json!("JSONL"))]);
+        assert_eq!(parse_import_format(&spec).unwrap(), ImportFormat::Jsonl);
+
+        let spec = sink_spec(&[url, ("import_format", json!("influx"))]);
+        let err = VictoriaMetricFactory
+            .validate_spec(&spec)
+            .expect_err("unknown format");
+        assert!(err.to_string().contains("victoriametrics.import_format"));
+    }
+
+    #[test]
+    fn static_labels_parse_and_validate() {
+        let url = ("insert_url", json!("http://127.0.0.1:8428/api/v1/import"));
+        let spec = sink_spec(&[
+            url.clone(),
+            ("instance", json!("edge-01")),
+            ("host", json!("node-a")),
+            (
+                "extra_labels",
+                json!({"env": "prod", "instance": "ignored"}),
+            ),
+        ]);
+        let mut conf = VictoriaMetric::default();
+        parse_static_labels(&spec, &mut conf).expect("valid labels");
+        let labels = conf.static_labels();
+        assert_eq!(labels.get("instance").map(String::as_str), Some("edge-01"));
+        assert_eq!(labels.get("host").map(String::as_str), Some("node-a"));
+        assert_eq!(labels.get("env").map(String::as_str), Some("prod"));
+
+        for (key, value, expect) in [
+            ("instance", json!(""), "victoriametrics.instance"),
+            ("host", json!(1), "victoriametrics.host"),
+            ("extra_labels", json!(["env"]), "must be an object"),
+            (
+                "extra_labels",
+                json!({"1env": "x"}),
+                "not a valid label name",
+            ),
+            (
+                "extra_labels",
+                json!({"__name__": "x"}),
+                "not a valid label name",
+            ),
+            (
+                "extra_labels",
+                json!({"env": 1}),
+                "extra_labels.env must be a string",
+            ),
+        ] {
+            let spec = sink_spec(&[url.clone(), (key, value)]);
+            let err = VictoriaMetricFactory
+                .validate_spec(&spec)
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
+
+    #[test]
+    fn stage_filters_parse_and_validate() {
+        let url = ("insert_url", json!("http://127.0.0.1:8428/api/v1/import"));
+        let spec = sink_spec(&[
+            url.clone(),
+            ("stages", json!(["sink", "Parse"])),
+            ("label_allowlist", json!(["sink_name"])),
+        ]);
+        let mut conf = VictoriaMetric::default();
+        parse_stage_filters(&spec, &mut conf).expect("valid filters");
+        assert_eq!(
+            conf.stages,
+            Some(BTreeSet::from(["Parse".to_string(), "Sink".to_string()]))
+        );
+        assert_eq!(
+            conf.label_allowlist,
+            Some(BTreeSet::from(["sink_name".to_string()]))
+        );
+
+        let mut conf = VictoriaMetric::default();
+        parse_stage_filters(&sink_spec(std::slice::from_ref(&url)), &mut conf).unwrap();
+        assert!(conf.stages.is_none() && conf.label_allowlist.is_none());
+
+        for (key, value, expect) in [
+            (
+                "stages",
+                json!("Sink"),
+                "victoriametrics.stages must be an array",
+            ),
+            ("stages", json!(["Emit"]), "victoriametrics.stages entry"),
+            (
+                "label_allowlist",
+                json!(["pid"]),
+                "victoriametrics.label_allowlist entry",
+            ),
+            (
+                "label_allowlist",
+                json!([1]),
+                "victoriametrics.label_allowlist entry",
+            ),
+        ] {
+            let spec = sink_spec(&[url.clone(), (key, value)]);
+            let err = VictoriaMetricFactory
+                .validate_spec(&spec)
+                .expect_err(expect);
+            assert!(err.to_string().contains(expect), "{err}");
+        }
+    }
 }
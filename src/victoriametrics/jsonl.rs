@@ -0,0 +1,169 @@
+//! VictoriaMetrics JSON line 导入格式
+//!
+//! 每个时间序列输出一行：`{"metric":{"__name__":"x","k":"v"},"values":[1],"timestamps":[ts]}`。
+//! 直方图与摘要按 Prometheus 文本格式的约定展开为 `_bucket`（`le`）/ `quantile`、
+//! `_sum`、`_count` 序列；非有限值（NaN/Inf）无法用 JSON 表示，直接跳过。
+
+use prometheus::proto::{Metric, MetricFamily, MetricType};
+use serde_json::{Map, Value, json};
+
+const PROMETHEUS_IMPORT_SUFFIX: &str = "/import/prometheus";
+const JSONL_IMPORT_SUFFIX: &str = "/import";
+
+/// 将默认的 `/import/prometheus` 路径改写为 JSON line 导入路径；其它地址原样使用
+pub(crate) fn import_url(insert_url: &str) -> String {
+    let url = insert_url.trim_end_matches('/');
+    match url.strip_suffix(PROMETHEUS_IMPORT_SUFFIX) {
+        Some(base) => format!("{base}{JSONL_IMPORT_SUFFIX}"),
+        None => url.to_string(),
+    }
+}
+
+/// 将指标族编码为 JSON line；指标自带时间戳时优先使用，否则使用 `ts_ms`
+pub(crate) fn encode(families: &[MetricFamily], ts_ms: i64) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    for family in families {
+        let name = family.name();
+        for metric in &family.metric {
+            let ts = match metric.timestamp_ms() {
+                0 => ts_ms,
+                ts => ts,
+            };
+            for (suffix, extra, value) in samples(family.type_(), metric) {
+                if !value.is_finite() {
+                    continue;
+                }
+                let line = json!({
+                    "metric": labels(&format!("{name}{suffix}"), metric, extra),
+                    "values": [value],
+                    "timestamps": [ts],
+                });
+                buffer.extend_from_slice(line.to_string().as_bytes());
+                buffer.push(b'\n');
+            }
+        }
+    }
+    buffer
+}
+
+type Sample = (&'static str, Option<(&'static str, String)>, f64);
+
+fn samples(metric_type: MetricType, metric: &Metric) -> Vec<Sample> {
+    match metric_type {
+        MetricType::COUNTER => vec![("", None, metric.counter.value())],
+        MetricType::GAUGE => vec![("", None, metric.gauge.value())],
+        MetricType::UNTYPED => vec![("", None, metric.untyped.value())],
+        MetricType::HISTOGRAM => {
+            let h = &metric.histogram;
+            let mut out: Vec<Sample> = h
+                .bucket
+                .iter()
+                .map(|b| {
+                    let le = format_bound(b.upper_bound());
+                    ("_bucket", Some(("le", le)), b.cumulative_count() as f64)
+                })
+                .collect();
+            // 与文本格式一致：缺少 +Inf 桶时补齐
+            if h.bucket
+                .last()
+                .is_none_or(|b| b.upper_bound() != f64::INFINITY)
+            {
+                out.push((
+                    "_bucket",
+                    Some(("le", format_bound(f64::INFINITY))),
+                    h.sample_count() as f64,
+                ));
+            }
+            out.push(("_sum", None, h.sample_sum()));
+            out.push(("_count", None, h.sample_count() as f64));
+            out
+        }
+        MetricType::SUMMARY => {
+            let s = &metric.summary;
+            let mut out: Vec<Sample> = s
+                .quantile
+                .iter()
+                .map(|q| ("", Some(("quantile", q.quantile().to_string())), q.value()))
+                .collect();
+            out.push(("_sum", None, s.sample_sum()));
+            out.push(("_count", None, s.sample_count() as f64));
+            out
+        }
+    }
+}
+
+fn labels(name: &str, metric: &Metric, extra: Option<(&str, String)>) -> Map<String, Value> {
+    let mut map = Map::new();
+    map.insert("__name__".into(), Value::String(name.to_string()));
+    for pair in &metric.label {
+        map.insert(
+            pair.name().to_string(),
+            Value::String(pair.value().to_string()),
+        );
+    }
+    if let Some((key, value)) = extra {
+        map.insert(key.to_string(), Value::String(value));
+    }
+    map
+}
+
+fn format_bound(bound: f64) -> String {
+    if bound == f64::INFINITY {
+        "+Inf".to_string()
+    } else {
+        bound.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::{IntCounterVec, Opts, Registry};
+
+    #[test]
+    fn counter_family_encodes_to_json_lines() {
+        let registry = Registry::new();
+        let counter = IntCounterVec::new(
+            Opts::new("wp_recv_total", "received events"),
+            &["source", "target"],
+        )
+        .unwrap();
+        registry.register(Box::new(counter.clone())).unwrap();
+        counter.with_label_values(&["kafka", "a"]).inc_by(3);
+        counter.with_label_values(&["http", "b"]).inc_by(5);
+
+        let body = encode(&registry.gather(), 1_700_000_000_000);
+        let lines: Vec<Value> = String::from_utf8(body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                json!({
+                    "metric": {"__name__": "wp_recv_total", "source": "http", "target": "b"},
+                    "values": [5.0],
+                    "timestamps": [1_700_000_000_000_i64],
+                }),
+                json!({
+                    "metric": {"__name__": "wp_recv_total", "source": "kafka", "target": "a"},
+                    "values": [3.0],
+                    "timestamps": [1_700_000_000_000_i64],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn import_url_rewrites_default_prometheus_path() {
+        assert_eq!(
+            import_url("http://vm:8428/api/v1/import/prometheus/"),
+            "http://vm:8428/api/v1/import"
+        );
+        assert_eq!(
+            import_url("http://vm:8480/insert/0/prometheus/api/v1/import"),
+            "http://vm:8480/insert/0/prometheus/api/v1/import"
+        );
+    }
+}
@@ -27,11 +27,46 @@ impl<'a> IntoOptField<'a> for Option<&'a FieldStorage> {
     }
 }
 use prometheus::{IntCounterVec, register_int_counter_vec};
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
 use wp_model_core::model::DataRecord;
 use wp_model_core::model::Value;
 
+use crate::utils::series_ttl::SeriesTtl;
+
 // ------------- metrics helpers -------------
 
+/// 导出器按 `stage` 字段处理的阶段
+pub(crate) const STAGES: [&str; 3] = ["Pick", "Parse", "Sink"];
+
+/// 取自记录字段的标签，可由 `label_allowlist` 限制
+pub(crate) const RECORD_LABELS: [&str; 6] = [
+    "source_type",
+    "source_name",
+    "package_name",
+    "rule_name",
+    "sink_group",
+    "sink_name",
+];
+
+/// 未在白名单中的记录标签取值统一折叠为该值，合并为一条序列
+pub(crate) const COLLAPSED_LABEL: &str = "*";
+
+/// 将不在 `allow` 中的记录标签取值折叠为 [`COLLAPSED_LABEL`]；`None` 表示全部保留
+fn restrict_labels<const N: usize>(
+    allow: Option<&BTreeSet<String>>,
+    fields: [(&str, &mut String); N],
+) {
+    let Some(allow) = allow else {
+        return;
+    };
+    for (name, value) in fields {
+        if !allow.contains(name) {
+            *value = COLLAPSED_LABEL.to_string();
+        }
+    }
+}
+
 /// 一次 sysinfo 刷新同时更新 CPU + 内存两个 gauge，避免重复的系统调用开销。
 /// 在定时 flush 任务中调用，采样间隔即 flush_interval_secs。
 pub(crate) fn system_usage_stat(system: &mut System) {
@@ -111,30 +146,62 @@ pub(crate) fn send_sink(data: &DataRecord) -> (SinkMetrics, u64) {
     (sink_metrics, count as u64)
 }
 
-pub fn receive_data_stat(data: &DataRecord) {
-    let (values, total) = source_values(data);
+pub fn receive_data_stat(data: &DataRecord, labels: Option<&BTreeSet<String>>) {
+    let (mut values, total) = source_values(data);
     if values.is_valid() {
+        restrict_labels(
+            labels,
+            [
+                ("source_type", &mut values.source_type),
+                ("source_name", &mut values.source_name),
+            ],
+        );
+        RECV_SERIES.touch(&values.values());
         RECV_FROM_SOURCE
             .with_label_values(&values.values())
             .inc_by(total as u64);
     }
 }
 
-pub fn parse_all_stat(data: &DataRecord) {
-    let (values, all) = parse_all(data);
+pub fn parse_all_stat(data: &DataRecord, labels: Option<&BTreeSet<String>>) {
+    let (mut values, all) = parse_all(data);
     if values.is_valid() {
+        restrict_labels(
+            labels,
+            [
+                ("package_name", &mut values.package_name),
+                ("rule_name", &mut values.rule_name),
+            ],
+        );
+        PARSE_SERIES.touch(&values.values());
         PARSE_ALL.with_label_values(&values.values()).inc_by(all);
     }
 }
-pub fn sink_stat(data: &DataRecord) {
-    let (values, count) = send_sink(data);
+pub fn sink_stat(data: &DataRecord, labels: Option<&BTreeSet<String>>) {
+    let (mut values, count) = send_sink(data);
     if values.is_valid() {
+        restrict_labels(
+            labels,
+            [
+                ("sink_group", &mut values.sink_group),
+                ("sink_name", &mut values.sink_name),
+            ],
+        );
+        SINK_SERIES.touch(&values.values());
         SEND_TO_SINK
             .with_label_values(&values.values())
             .inc_by(count);
     }
 }
 
+/// 删除超过 `ttl` 未更新的来源 / 解析 / 输出计数序列，返回删除的数量
+pub(crate) fn cleanup_stale_series(ttl: Duration) -> usize {
+    let now = Instant::now();
+    RECV_SERIES.cleanup(&RECV_FROM_SOURCE, ttl, now)
+        + PARSE_SERIES.cleanup(&PARSE_ALL, ttl, now)
+        + SINK_SERIES.cleanup(&SEND_TO_SINK, ttl, now)
+}
+
 macro_rules! generate_metrics {
     ($name:ident; $($field:ident), *) => {
         #[derive(Default, Debug)] pub struct $name { $(pub $field: String,)* }
@@ -196,4 +263,7 @@ lazy_static! {
         &MemoryMetrics::labels()
     )
     .expect("register wparse_memory_usage fail");
+    static ref RECV_SERIES: SeriesTtl = SeriesTtl::default();
+    static ref PARSE_SERIES: SeriesTtl = SeriesTtl::default();
+    static ref SINK_SERIES: SeriesTtl = SeriesTtl::default();
 }
@@ -1,7 +1,9 @@
 pub mod config;
 mod exporter;
 mod factory;
+mod jsonl;
 mod metrics;
 
-pub use config::VictoriaMetric;
+pub use config::{ImportFormat, VictoriaMetric};
+pub use exporter::VictoriaMetricExporter;
 pub use factory::VictoriaMetricFactory;
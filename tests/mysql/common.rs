@@ -170,3 +170,47 @@ pub async fn query_table_count() -> Result<i64> {
     pool.close().await;
     Ok(count)
 }
+
+/// 建立 sea-orm 连接，供直接构造 `MysqlSink` 的测试使用
+pub async fn connect_mysql_db() -> Result<sea_orm::DatabaseConnection> {
+    Ok(sea_orm::Database::connect(mysql_url(Some(TEST_MYSQL_DB))).await?)
+}
+
+/// 创建带触发器的事务测试表：插入 `name = 'boom'` 的行时触发器报错
+pub async fn init_mysql_txn_table(table: &str) -> Result<()> {
+    execute_mysql(
+        Some(TEST_MYSQL_DB),
+        &format!("DROP TABLE IF EXISTS `{table}`"),
+    )
+    .await?;
+    execute_mysql(
+        Some(TEST_MYSQL_DB),
+        &format!(
+            "CREATE TABLE `{table}` (\
+                `id` BIGINT NOT NULL, \
+                `name` VARCHAR(64) NOT NULL, \
+                PRIMARY KEY (`id`)\
+            ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4"
+        ),
+    )
+    .await?;
+    execute_mysql(
+        Some(TEST_MYSQL_DB),
+        &format!(
+            "CREATE TRIGGER `{table}_reject_boom` BEFORE INSERT ON `{table}` FOR EACH ROW \
+             BEGIN IF NEW.name = 'boom' THEN \
+             SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'boom rejected'; END IF; END"
+        ),
+    )
+    .await
+}
+
+pub async fn query_count_of(table: &str) -> Result<i64> {
+    let pool = create_mysql_pool(Some(TEST_MYSQL_DB)).await?;
+    let count = sqlx::query(&format!("SELECT COUNT(*) FROM `{table}`"))
+        .fetch_one(&pool)
+        .await?
+        .try_get::<i64, _>(0)?;
+    pool.close().await;
+    Ok(count)
+}
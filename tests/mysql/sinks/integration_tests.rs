@@ -1,14 +1,18 @@
 #![cfg(all(feature = "mysql", feature = "external_integration"))]
 
 use anyhow::Result;
-use wp_connectors::mysql::MySQLSinkFactory;
+use std::sync::Arc;
+use wp_connector_api::AsyncRecordSink;
+use wp_connectors::mysql::{MySQLSinkFactory, MysqlSink};
+use wp_model_core::model::{DataField, DataRecord};
 
 use crate::common::{
     component_tools::DockerComposeTool,
     sink::{integration_runtime::SinkIntegrationRuntime, sink_info::SinkInfo},
 };
 use crate::mysql_common::{
-    create_mysql_test_config, init_mysql_database, query_table_count, wait_for_mysql_ready,
+    connect_mysql_db, create_mysql_test_config, init_mysql_database, init_mysql_txn_table,
+    query_count_of, query_table_count, wait_for_mysql_ready,
 };
 
 #[tokio::test]
@@ -25,3 +29,40 @@ async fn test_mysql_sink_full_integration() -> Result<()> {
     let runtime = SinkIntegrationRuntime::new(docker_tool, vec![sink_info]);
     runtime.run(true).await
 }
+
+#[tokio::test]
+#[ignore = "集成测试默认忽略，请按需手动执行"]
+async fn test_mysql_sink_batch_is_atomic() -> Result<()> {
+    let docker_tool = DockerComposeTool::new("tests/mysql/component/docker-compose.yml")?;
+    docker_tool.up().await?;
+    wait_for_mysql_ready().await?;
+    init_mysql_database().await?;
+
+    let table = "wp_txn_check";
+    init_mysql_txn_table(table).await?;
+
+    // batch = 1：每条记录一条 INSERT，第二条被触发器拒绝
+    let mut sink = MysqlSink::new(
+        connect_mysql_db().await?,
+        table.to_string(),
+        vec!["id".to_string(), "name".to_string()],
+    )
+    .with_batch(Some(1));
+    let records = [(1, "ok"), (2, "boom"), (3, "ok")]
+        .into_iter()
+        .map(|(id, name)| {
+            let mut record = DataRecord::default();
+            record.append(DataField::from_digit("id", id));
+            record.append(DataField::from_chars("name", name));
+            Arc::new(record)
+        })
+        .collect();
+
+    let result = sink.sink_records(records).await;
+    let count = query_count_of(table).await;
+    docker_tool.down().await?;
+
+    assert!(result.is_err(), "second statement should fail");
+    assert_eq!(count?, 0, "no rows from the failed batch may persist");
+    Ok(())
+}
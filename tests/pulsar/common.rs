@@ -0,0 +1,94 @@
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use wp_connector_api::ParamMap;
+
+pub const TEST_PULSAR_SERVICE_URL: &str = "pulsar://127.0.0.1:6650";
+pub const TEST_PULSAR_ADMIN_URL: &str = "http://127.0.0.1:8080";
+pub const TEST_PULSAR_TOPIC: &str = "persistent://public/default/wp-integration";
+
+const PULSAR_READY_ATTEMPTS: usize = 30;
+const PULSAR_READY_INTERVAL_SECS: u64 = 2;
+
+fn admin_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .expect("build reqwest client")
+}
+
+fn topic_admin_path() -> String {
+    let path = TEST_PULSAR_TOPIC.replacen("://", "/", 1);
+    format!("{}/admin/v2/{}", TEST_PULSAR_ADMIN_URL, path)
+}
+
+pub fn create_pulsar_test_config() -> ParamMap {
+    let mut params = ParamMap::new();
+    params.insert("service_url".into(), json!(TEST_PULSAR_SERVICE_URL));
+    params.insert("topic".into(), json!(TEST_PULSAR_TOPIC));
+    params.insert("fmt".into(), json!("json"));
+    params.insert("key_field".into(), json!("wp_event_id"));
+    params.insert("connect_timeout_secs".into(), json!(30));
+    params
+}
+
+pub async fn wait_for_pulsar_ready() -> Result<()> {
+    let mut last_error = None;
+    for attempt in 1..=PULSAR_READY_ATTEMPTS {
+        match admin_client()
+            .get(format!("{}/admin/v2/brokers/health", TEST_PULSAR_ADMIN_URL))
+            .send()
+            .await
+        {
+            Ok(resp) if resp.status().is_success() => {
+                println!("✓ Pulsar 服务已就绪（第 {} 次探测）", attempt);
+                return Ok(());
+            }
+            Ok(resp) => last_error = Some(format!("status={}", resp.status())),
+            Err(err) => last_error = Some(err.to_string()),
+        }
+        tokio::time::sleep(tokio::time::Duration::from_secs(PULSAR_READY_INTERVAL_SECS)).await;
+    }
+    anyhow::bail!(
+        "等待 Pulsar 就绪超时: {}",
+        last_error.unwrap_or_else(|| "未知错误".to_string())
+    )
+}
+
+pub async fn init_pulsar_topic() -> Result<()> {
+    let response = admin_client()
+        .put(topic_admin_path())
+        .send()
+        .await
+        .context("创建 Pulsar topic 失败")?;
+    // 409 表示 topic 已存在
+    if !response.status().is_success() && response.status().as_u16() != 409 {
+        anyhow::bail!("创建 Pulsar topic 失败: status={}", response.status());
+    }
+    Ok(())
+}
+
+/// 通过 topic 统计中的 `msgInCounter` 获取已写入消息数
+pub async fn query_pulsar_count(_params: ParamMap) -> Result<i64> {
+    let response = admin_client()
+        .get(format!("{}/stats", topic_admin_path()))
+        .send()
+        .await
+        .context("请求 Pulsar topic stats 失败")?;
+    if response.status().as_u16() == 404 {
+        return Ok(0);
+    }
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+        anyhow::bail!(
+            "查询 Pulsar topic stats 失败: status={}, body={}",
+            status,
+            body
+        );
+    }
+    let stats: serde_json::Value = serde_json::from_str(&body)
+        .with_context(|| format!("解析 Pulsar topic stats 失败: {}", body))?;
+    Ok(stats["msgInCounter"].as_i64().unwrap_or(0))
+}
@@ -0,0 +1,27 @@
+#![cfg(all(feature = "pulsar", feature = "external_integration"))]
+
+use anyhow::Result;
+use wp_connectors::pulsar::PulsarSinkFactory;
+
+use crate::common::{
+    component_tools::DockerComposeTool,
+    sink::{integration_runtime::SinkIntegrationRuntime, sink_info::SinkInfo},
+};
+use crate::pulsar_common::{
+    create_pulsar_test_config, init_pulsar_topic, query_pulsar_count, wait_for_pulsar_ready,
+};
+
+#[tokio::test]
+#[ignore = "集成测试默认忽略，请按需手动执行"]
+async fn test_pulsar_sink_full_integration() -> Result<()> {
+    let docker_tool = DockerComposeTool::new("tests/pulsar/component/docker-compose.yml")?;
+
+    let sink_info = SinkInfo::new(PulsarSinkFactory, create_pulsar_test_config())
+        .with_test_name("basic")
+        .with_async_count_fn(|params| async move { query_pulsar_count(params).await })
+        .with_async_init(|| async { init_pulsar_topic().await })
+        .with_async_wait_ready(|_params| async { wait_for_pulsar_ready().await });
+
+    let runtime = SinkIntegrationRuntime::new(docker_tool, vec![sink_info]);
+    runtime.run(true).await
+}
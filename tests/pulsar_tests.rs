@@ -0,0 +1,10 @@
+#![cfg(all(feature = "pulsar", feature = "external_integration"))]
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[path = "pulsar/common.rs"]
+mod pulsar_common;
+
+#[path = "pulsar/sinks/integration_tests.rs"]
+mod integration_tests;